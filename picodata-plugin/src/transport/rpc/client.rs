@@ -57,6 +57,7 @@ impl<'a> RequestBuilder<'a> {
                     to_master,
                 }
             }
+            RequestTarget::RaftLeader => FfiSafeRpcTargetSpecifier::RaftLeader,
         };
         Self {
             target,
@@ -68,6 +69,15 @@ impl<'a> RequestBuilder<'a> {
         }
     }
 
+    /// A shorthand for `RequestBuilder::new(RequestTarget::RaftLeader)`.
+    ///
+    /// The request will be sent to the instance which is the raft leader
+    /// at the moment of sending.
+    #[inline(always)]
+    pub fn raft_leader() -> Self {
+        Self::new(RequestTarget::RaftLeader)
+    }
+
     /// Use service info from `context`.
     /// The request will be sent to an endpoint registered by the specified service.
     #[inline]
@@ -227,6 +237,12 @@ pub enum RequestTarget<'a> {
     /// If the boolean parameter is `true`, then send the request to the replicaset master,
     /// otherwise any replica.
     ReplicasetName(&'a str, bool),
+
+    /// The instance which is the current raft leader.
+    ///
+    /// The leader is determined at the moment the request is sent. If the
+    /// leader is unknown (e.g. during the elections) the request fails.
+    RaftLeader,
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -294,4 +310,30 @@ pub enum FfiSafeRpcTargetSpecifier {
         bucket_id: u64,
         to_master: bool,
     },
+    RaftLeader,
+}
+
+#[cfg(all(feature = "internal_test", not(test)))]
+mod tests {
+    use super::*;
+
+    #[tarantool::test]
+    fn raft_leader_target_to_ffi() {
+        let builder = RequestBuilder::raft_leader()
+            .plugin_service("plugin", "service")
+            .plugin_version("0.1.0")
+            .path("/ping")
+            .input(Request::from_bytes(b"\xa4ping"));
+        let arguments = builder.to_ffi().unwrap();
+        assert!(matches!(
+            arguments.target,
+            FfiSafeRpcTargetSpecifier::RaftLeader
+        ));
+
+        let builder = RequestBuilder::new(RequestTarget::RaftLeader);
+        assert!(matches!(
+            builder.target,
+            FfiSafeRpcTargetSpecifier::RaftLeader
+        ));
+    }
 }
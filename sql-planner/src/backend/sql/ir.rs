@@ -5,8 +5,8 @@ use crate::executor::protocol::VTablesMeta;
 use crate::ir::node::expression::Expression;
 use crate::ir::node::relational::Relational;
 use crate::ir::node::{
-    BoundType, Constant, Delete, FrameType, Join, Node, NodeId, Parameter, Projection, Reference,
-    ScalarFunction, ScanRelation, SubQueryReference, Update,
+    BoundType, Constant, Delete, Except, FrameType, Intersect, Join, Node, NodeId, Parameter,
+    Projection, Reference, ScalarFunction, ScanRelation, SubQueryReference, Update,
 };
 use crate::ir::relation::Column;
 use crate::ir::tree::Snapshot;
@@ -380,10 +380,15 @@ impl ExecutionPlan {
                     OrderByType::Asc => sql.push_str("ASC"),
                     OrderByType::Desc => sql.push_str("DESC"),
                 },
+                SyntaxData::Collate(collation) => {
+                    sql.push_str("COLLATE ");
+                    push_identifier(&mut sql, &format_smolstr!("{collation}"));
+                }
                 SyntaxData::Inline(content) => sql.push_str(content),
                 SyntaxData::From => sql.push_str("FROM"),
                 SyntaxData::Leading => sql.push_str("LEADING"),
                 SyntaxData::Limit(limit) => sql.push_str(&format_smolstr!("LIMIT {limit}")),
+                SyntaxData::Offset(offset) => sql.push_str(&format_smolstr!("OFFSET {offset}")),
                 SyntaxData::Both => sql.push_str("BOTH"),
                 SyntaxData::Trailing => sql.push_str("TRAILING"),
                 SyntaxData::Operator(s) => sql.push_str(s.as_str()),
@@ -450,9 +455,13 @@ impl ExecutionPlan {
                             ));
                         }
                         Node::Relational(rel) => match rel {
-                            Relational::Except { .. } => sql.push_str("EXCEPT"),
+                            Relational::Except(Except { all, .. }) => {
+                                sql.push_str(if *all { "EXCEPT ALL" } else { "EXCEPT" })
+                            }
                             Relational::GroupBy { .. } => sql.push_str("GROUP BY"),
-                            Relational::Intersect { .. } => sql.push_str("INTERSECT"),
+                            Relational::Intersect(Intersect { all, .. }) => {
+                                sql.push_str(if *all { "INTERSECT ALL" } else { "INTERSECT" })
+                            }
                             Relational::Having { .. } => sql.push_str("HAVING"),
                             Relational::OrderBy { .. } => sql.push_str("ORDER BY"),
                             Relational::Delete(Delete { relation, .. }) => {
@@ -605,6 +614,14 @@ impl ExecutionPlan {
                                     ..
                                 }) => {
                                     if *is_system {
+                                        // Tarantool SQL has no boolean aggregates, but `min`/`max`
+                                        // over booleans give the same result: `false < true` and
+                                        // NULLs are skipped by both functions.
+                                        let name = match name.as_str() {
+                                            "bool_and" => "min",
+                                            "bool_or" => "max",
+                                            name => name,
+                                        };
                                         sql.push_str(name);
                                     } else {
                                         push_identifier(&mut sql, name);
@@ -92,3 +92,21 @@ fn select_without_scan() {
     );
     check_sql_with_snapshot(query, vec![], expected, Snapshot::Oldest);
 }
+
+#[test]
+fn projection_bool_aggregates() {
+    let query = r#"SELECT bool_and("product_units"), bool_or("product_units")
+        FROM "hash_testing""#;
+
+    // Boolean aggregates are executed as `min`/`max`.
+    let expected = PatternWithParams::new(
+        format!(
+            "{} {} {}",
+            r#"SELECT min (CAST ("hash_testing"."product_units" as bool)) as "col_1","#,
+            r#"max (CAST ("hash_testing"."product_units" as bool)) as "col_2""#,
+            r#"FROM "hash_testing""#,
+        ),
+        vec![],
+    );
+    check_sql_with_snapshot(query, vec![], expected, Snapshot::Oldest);
+}
@@ -95,3 +95,20 @@ fn selection2_oldest() {
     );
     check_sql_with_snapshot(query, vec![], expected, Snapshot::Oldest);
 }
+
+#[test]
+fn selection_tablesample() {
+    let query = r#"SELECT "product_code" FROM "hash_testing" TABLESAMPLE BERNOULLI(10) REPEATABLE(42)
+        WHERE "product_code" = 'a'"#;
+
+    let expected = PatternWithParams::new(
+        format!(
+            "{} {} {}",
+            r#"SELECT "hash_testing"."product_code" FROM (SELECT * FROM "hash_testing""#,
+            r#"WHERE "_pico_tablesample"(10, 42, "product_code", "identification_number")) as "hash_testing""#,
+            r#"WHERE "hash_testing"."product_code" = CAST($1 AS string)"#,
+        ),
+        vec![Value::from("a")],
+    );
+    check_sql_with_snapshot(query, vec![], expected, Snapshot::Oldest);
+}
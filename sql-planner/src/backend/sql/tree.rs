@@ -10,10 +10,11 @@ use crate::ir::node::{
     ScanRelation, ScanSubQuery, SelectWithoutScan, Selection, SubQueryReference, Trim, UnaryExpr,
     Union, UnionAll, Values, ValuesRow, Window,
 };
-use crate::ir::operator::{OrderByElement, OrderByEntity, OrderByType, Unary};
+use crate::ir::operator::{Collation, OrderByElement, OrderByEntity, OrderByType, SortKey, Unary};
 use crate::ir::transformation::redistribution::{MotionOpcode, MotionPolicy};
 use crate::ir::tree::traversal::{LevelNode, PostOrder};
 use crate::ir::tree::Snapshot;
+use crate::ir::types::{CastType, UnrestrictedType};
 use crate::ir::Plan;
 use serde::{Deserialize, Serialize};
 use smol_str::{format_smolstr, SmolStr, ToSmolStr};
@@ -72,6 +73,8 @@ pub enum SyntaxData {
     OrderByPosition(usize),
     /// "asc" or "desc"
     OrderByType(OrderByType),
+    /// "collate"
+    Collate(Collation),
     /// "as"
     As,
     /// "over"
@@ -103,6 +106,8 @@ pub enum SyntaxData {
     Leading,
     /// "limit"
     Limit(u64),
+    /// "offset"
+    Offset(u64),
     /// "both"
     Both,
     /// "trailing"
@@ -312,6 +317,14 @@ impl SyntaxNode {
         }
     }
 
+    fn new_collate(collation: Collation) -> Self {
+        SyntaxNode {
+            data: SyntaxData::Collate(collation),
+            left: None,
+            right: Vec::new(),
+        }
+    }
+
     fn new_inline(value: &str) -> Self {
         SyntaxNode {
             data: SyntaxData::Inline(value.into()),
@@ -416,6 +429,14 @@ impl SyntaxNode {
         }
     }
 
+    fn new_offset(offset: u64) -> Self {
+        SyntaxNode {
+            data: SyntaxData::Offset(offset),
+            left: None,
+            right: Vec::new(),
+        }
+    }
+
     fn new_both() -> Self {
         SyntaxNode {
             data: SyntaxData::Both,
@@ -1349,18 +1370,22 @@ impl<'p> SyntaxPlan<'p> {
         elem: &OrderByElement,
         parent_id: NodeId,
         need_comma: bool,
-    ) -> [Option<usize>; 3] {
-        let mut nodes = [None, None, None];
+    ) -> [Option<usize>; 4] {
+        let mut nodes = [None, None, None, None];
         match elem.entity {
             OrderByEntity::Expression { expr_id } => {
                 let expr_sn_id = self.pop_from_stack(expr_id, parent_id);
-                nodes[2] = Some(expr_sn_id);
+                nodes[3] = Some(expr_sn_id);
             }
             OrderByEntity::Index { value } => {
                 let sn = SyntaxNode::new_order_index(value);
-                nodes[2] = Some(self.nodes.push_sn_non_plan(sn));
+                nodes[3] = Some(self.nodes.push_sn_non_plan(sn));
             }
         }
+        if let Some(collation) = elem.collation {
+            let sn = SyntaxNode::new_collate(collation);
+            nodes[2] = Some(self.nodes.push_sn_non_plan(sn));
+        }
         if let Some(order_type) = &elem.order_type {
             let sn = SyntaxNode::new_order_type(order_type);
             nodes[1] = Some(self.nodes.push_sn_non_plan(sn));
@@ -1376,7 +1401,7 @@ impl<'p> SyntaxPlan<'p> {
         elems: &mut Vec<OrderByElement>,
         parent_id: NodeId,
     ) -> Vec<usize> {
-        let mut res: Vec<usize> = Vec::with_capacity(elems.len() * 3 - 1);
+        let mut res: Vec<usize> = Vec::with_capacity(elems.len() * 4 - 1);
 
         // The elements on the stack are in the reverse order.
         let first = elems.pop().expect("at least one column in ORDER BY");
@@ -1525,25 +1550,63 @@ impl<'p> SyntaxPlan<'p> {
     }
 
     fn add_scan_relation(&mut self, id: NodeId) {
-        let (_, scan) = self.prologue_rel(id);
+        let (plan, scan) = self.prologue_rel(id);
         let Relational::ScanRelation(ScanRelation {
-            alias, indexed_by, ..
+            alias,
+            relation,
+            indexed_by,
+            sample,
+            ..
         }) = scan
         else {
             panic!("Expected SCAN node");
         };
         let scan_alias = alias.clone();
         let scan_index = indexed_by.clone();
+        let Some(sample) = sample else {
+            let arena = &mut self.nodes;
+            let mut children = Vec::new();
+            if let Some(name) = scan_alias {
+                children.push(arena.push_sn_non_plan(SyntaxNode::new_alias(name)));
+            }
+            if let Some(name) = scan_index {
+                children.push(arena.push_sn_non_plan(SyntaxNode::new_indexed_by(name)));
+            }
+
+            let sn = SyntaxNode::new_pointer(id, None, children);
+            arena.push_sn_plan(sn);
+            return;
+        };
+
+        // Sampled scan is wrapped into a subquery filtering the rows locally:
+        // (SELECT * FROM "t" WHERE "_pico_tablesample"(percent, seed, pk...)) as "t"
+        let table = plan
+            .relations
+            .get(relation)
+            .expect("sampled relation must exist in the plan");
+        let mut args = vec![
+            sample.percent.to_smolstr(),
+            sample
+                .seed
+                .map_or_else(|| SmolStr::new_static("NULL"), |seed| seed.to_smolstr()),
+        ];
+        for pos in &table.primary_key.positions {
+            let column = &table.columns[*pos];
+            args.push(format_smolstr!("\"{}\"", column.name));
+        }
+        let filter = format_smolstr!("WHERE \"_pico_tablesample\"({}))", args.join(", "));
+        let scan_alias = scan_alias.unwrap_or_else(|| relation.clone());
+
         let arena = &mut self.nodes;
+        let left = arena.push_sn_non_plan(SyntaxNode::new_inline("(SELECT * FROM"));
         let mut children = Vec::new();
-        if let Some(name) = scan_alias {
-            children.push(arena.push_sn_non_plan(SyntaxNode::new_alias(name)));
-        }
         if let Some(name) = scan_index {
             children.push(arena.push_sn_non_plan(SyntaxNode::new_indexed_by(name)));
         }
+        children.push(arena.push_sn_non_plan(SyntaxNode::new_inline(&filter)));
+        children.push(arena.push_sn_non_plan(SyntaxNode::new_alias(scan_alias)));
 
-        let sn = SyntaxNode::new_pointer(id, None, children);
+        let sn = SyntaxNode::new_pointer(id, Some(left), children);
         arena.push_sn_plan(sn);
     }
 
@@ -1631,17 +1694,45 @@ impl<'p> SyntaxPlan<'p> {
     }
 
     fn add_limit(&mut self, id: NodeId) {
-        let (_, limit) = self.prologue_rel(id);
-        let Relational::Limit(Limit { limit, child, .. }) = limit else {
+        let (plan, limit) = self.prologue_rel(id);
+        let Relational::Limit(Limit {
+            limit,
+            offset,
+            with_ties,
+            child,
+            ..
+        }) = limit
+        else {
             panic!("expected LIMIT node");
         };
-        let (limit, child) = (*limit, *child);
+        let (limit, offset, with_ties, child) = (*limit, *offset, *with_ties, *child);
+        if with_ties {
+            let (head, tail) = limit_with_ties_sql(plan, id, limit, offset);
+            let child_sn_id = self.pop_from_stack(child, id);
+            let arena = &mut self.nodes;
+            let children: Vec<usize> = vec![
+                arena.push_sn_non_plan(SyntaxNode::new_inline(&head)),
+                child_sn_id,
+                arena.push_sn_non_plan(SyntaxNode::new_inline(&tail)),
+            ];
+            let sn = SyntaxNode::new_pointer(id, None, children);
+            arena.push_sn_plan(sn);
+            return;
+        }
         let child_sn_id = self.pop_from_stack(child, id);
         let arena = &mut self.nodes;
-        let children: Vec<usize> = vec![
+        // Tarantool doesn't support OFFSET without LIMIT,
+        // so the maximum possible limit is used instead.
+        let max_limit = i64::MAX as u64;
+        let mut children: Vec<usize> = vec![
             child_sn_id,
-            arena.push_sn_non_plan(SyntaxNode::new_limit(limit)),
+            arena.push_sn_non_plan(SyntaxNode::new_limit(
+                limit.map_or(max_limit, |limit| limit.min(max_limit)),
+            )),
         ];
+        if offset > 0 {
+            children.push(arena.push_sn_non_plan(SyntaxNode::new_offset(offset.min(max_limit))));
+        }
         let sn = SyntaxNode::new_pointer(id, None, children);
         arena.push_sn_plan(sn);
     }
@@ -1707,15 +1798,23 @@ impl<'p> SyntaxPlan<'p> {
 
     fn add_binary_op(&mut self, id: NodeId) {
         let (_, expr) = self.prologue_expr(id);
-        let (left_plan_id, right_plan_id, op_sn_id) = match expr {
+        let (left_plan_id, right_plan_id, op_sn_id, collate_sn_id) = match expr {
             Expression::Bool(BoolExpr {
-                left, right, op, ..
+                left,
+                right,
+                op,
+                collation,
             }) => {
-                let (op, left, right) = (*op, *left, *right);
+                let (op, left, right, collation) = (*op, *left, *right, *collation);
+                // Collation of the left operand is applied to the whole comparison.
+                let collate_sn_id = collation.map(|collation| {
+                    self.nodes
+                        .push_sn_non_plan(SyntaxNode::new_collate(collation))
+                });
                 let op_sn_id = self
                     .nodes
                     .push_sn_non_plan(SyntaxNode::new_operator(&format!("{op}")));
-                (left, right, op_sn_id)
+                (left, right, op_sn_id, collate_sn_id)
             }
             Expression::Arithmetic(ArithmeticExpr {
                 left, right, op, ..
@@ -1724,7 +1823,7 @@ impl<'p> SyntaxPlan<'p> {
                 let op_sn_id = self
                     .nodes
                     .push_sn_non_plan(SyntaxNode::new_operator(&format!("{op}")));
-                (left, right, op_sn_id)
+                (left, right, op_sn_id, None)
             }
             _ => panic!("Expected binary expression node"),
         };
@@ -1732,7 +1831,9 @@ impl<'p> SyntaxPlan<'p> {
         let right_sn_id = self.pop_expr_from_stack(right_plan_id, id);
         let left_sn_id = self.pop_expr_from_stack(left_plan_id, id);
 
-        let children = vec![left_sn_id, op_sn_id, right_sn_id];
+        let mut children = vec![left_sn_id];
+        children.extend(collate_sn_id);
+        children.extend([op_sn_id, right_sn_id]);
         let sn = SyntaxNode::new_pointer(id, None, children);
         self.nodes.push_sn_plan(sn);
     }
@@ -1858,23 +1959,73 @@ impl<'p> SyntaxPlan<'p> {
     }
 
     fn add_cast(&mut self, id: NodeId) {
-        let (_, expr) = self.prologue_expr(id);
+        let (plan, expr) = self.prologue_expr(id);
         let Expression::Cast(Cast { child, to }) = expr else {
             panic!("Expected CAST node");
         };
         let to_alias = to.to_smolstr();
+        let bounds = to.integer_bounds();
         let child_plan_id = *child;
+        // Storages convert between text and boolean by the same rules as
+        // the router does for the constants (see `Value::cast`):
+        // CAST(".proc_sql_text_to_bool"(expr) as bool)
+        // Temporal types are stored as datetimes or text, the conversion
+        // to them is done by builtin functions (see `Value::cast_to`):
+        // CAST("_pico_to_timestamp"(expr, 'date') as datetime)
+        // Fractional values are rounded before the cast to an integer like
+        // the router does (Tarantool truncates them):
+        // CAST("_pico_round_to_int"(expr) as int)
+        let temporal_arg = to.temporal_name().map(|name| format_smolstr!("'{name}')"));
+        let converter = match (to, plan.calculate_expression_type(child_plan_id)) {
+            (CastType::Boolean, Ok(Some(UnrestrictedType::String))) => {
+                Some("\".proc_sql_text_to_bool\"(")
+            }
+            (CastType::String, Ok(Some(UnrestrictedType::Boolean))) => {
+                Some("\".proc_sql_bool_to_text\"(")
+            }
+            (CastType::Date | CastType::Timestamp, _) => Some("\"_pico_to_timestamp\"("),
+            (CastType::Time, _) => Some("\"_pico_to_time\"("),
+            (
+                CastType::Integer | CastType::SmallInt | CastType::Int4,
+                Ok(Some(UnrestrictedType::Decimal | UnrestrictedType::Double)),
+            ) => Some("\"_pico_round_to_int\"("),
+            _ => None,
+        };
 
         let child_sn_id = self.pop_expr_from_stack(child_plan_id, id);
         let arena = &mut self.nodes;
-        let children = vec![
-            arena.push_sn_non_plan(SyntaxNode::new_lparen()),
-            child_sn_id,
-            arena.push_sn_non_plan(SyntaxNode::new_cast_type(to_alias)),
-            arena.push_sn_non_plan(SyntaxNode::new_rparen()),
-        ];
+        let mut children = vec![arena.push_sn_non_plan(SyntaxNode::new_lparen())];
+        if let Some(converter) = converter {
+            children.push(arena.push_sn_non_plan(SyntaxNode::new_inline(converter)));
+            children.push(child_sn_id);
+            if let Some(arg) = &temporal_arg {
+                children.push(arena.push_sn_non_plan(SyntaxNode::new_comma()));
+                children.push(arena.push_sn_non_plan(SyntaxNode::new_inline(arg)));
+            } else {
+                children.push(arena.push_sn_non_plan(SyntaxNode::new_rparen()));
+            }
+        } else {
+            children.push(child_sn_id);
+        }
+        children.push(arena.push_sn_non_plan(SyntaxNode::new_cast_type(to_alias)));
+        children.push(arena.push_sn_non_plan(SyntaxNode::new_rparen()));
         let cast_sn_id = arena.push_sn_non_plan(SyntaxNode::new_cast());
-        let sn = SyntaxNode::new_pointer(id, Some(cast_sn_id), children);
+        let Some((min, max, name)) = bounds else {
+            let sn = SyntaxNode::new_pointer(id, Some(cast_sn_id), children);
+            arena.push_sn_plan(sn);
+            return;
+        };
+
+        // Storages know only 64-bit integers, so the range of a narrow
+        // integer type is checked by a builtin function:
+        // "_pico_int_range"(CAST(expr as int), min, max, 'type')
+        children.insert(0, cast_sn_id);
+        let range = format_smolstr!("{min}, {max}, '{name}'");
+        children.push(arena.push_sn_non_plan(SyntaxNode::new_comma()));
+        children.push(arena.push_sn_non_plan(SyntaxNode::new_inline(&range)));
+        children.push(arena.push_sn_non_plan(SyntaxNode::new_rparen()));
+        let left = arena.push_sn_non_plan(SyntaxNode::new_inline("\"_pico_int_range\"("));
+        let sn = SyntaxNode::new_pointer(id, Some(left), children);
         arena.push_sn_plan(sn);
     }
 
@@ -2700,6 +2851,86 @@ impl<'p> SyntaxPlan<'p> {
     }
 }
 
+/// Name of the row number column used for `WITH TIES`.
+const FETCH_ROW: &str = "__fetch_row";
+/// Name of the peer group rank column used for `WITH TIES`.
+const FETCH_RANK: &str = "__fetch_rank";
+
+/// Build the SQL wrapping the limit child to return the first rows together
+/// with the rows tied with the last of them (`FETCH FIRST ... WITH TIES`).
+/// The rows are numbered in the `ORDER BY` order and each row gets the
+/// smallest number among its peers, so that a whole group of peers is
+/// returned when its first row fits into the limit.
+///
+/// Returns the SQL that goes before and after the child.
+fn limit_with_ties_sql(
+    plan: &Plan,
+    limit_id: NodeId,
+    limit: Option<u64>,
+    offset: u64,
+) -> (SmolStr, SmolStr) {
+    let Relational::Limit(Limit { child, .. }) = plan
+        .get_relation_node(limit_id)
+        .expect("limit node must exist")
+    else {
+        panic!("expected LIMIT node");
+    };
+    let columns = plan
+        .get_relational_aliases(*child)
+        .expect("limit child must have an output");
+    let keys = plan
+        .get_limit_sort_keys(limit_id)
+        .expect("WITH TIES requires ORDER BY");
+
+    let quoted = |position: usize| -> SmolStr {
+        let column = columns.get(position).expect("sort key must be in output");
+        format_smolstr!("\"{column}\"")
+    };
+    let mut order_by = Vec::with_capacity(keys.len());
+    let mut partition_by = Vec::with_capacity(keys.len());
+    for (key, element) in keys {
+        let (expr, position) = match key {
+            SortKey::Column(position) => (quoted(position), position),
+            SortKey::IsNull(position) => {
+                (format_smolstr!("{} IS NULL", quoted(position)), position)
+            }
+            SortKey::IsNotNull(position) => (
+                format_smolstr!("NOT {} IS NULL", quoted(position)),
+                position,
+            ),
+        };
+        let mut partition_key = quoted(position);
+        let mut order_key = expr;
+        if let (SortKey::Column(_), Some(collation)) = (key, &element.collation) {
+            partition_key = format_smolstr!("{partition_key} COLLATE \"{collation}\"");
+            order_key = partition_key.clone();
+        }
+        match element.order_type {
+            Some(OrderByType::Asc) => order_key = format_smolstr!("{order_key} ASC"),
+            Some(OrderByType::Desc) => order_key = format_smolstr!("{order_key} DESC"),
+            None => {}
+        }
+        order_by.push(order_key);
+        if !partition_by.contains(&partition_key) {
+            partition_by.push(partition_key);
+        }
+    }
+
+    let head = format_smolstr!(
+        "SELECT {} FROM (SELECT *, min(\"{FETCH_ROW}\") OVER (PARTITION BY {}) AS \"{FETCH_RANK}\" \
+        FROM (SELECT *, row_number() OVER (ORDER BY {}) AS \"{FETCH_ROW}\" FROM (",
+        columns.iter().map(|c| format_smolstr!("\"{c}\"")).collect::<Vec<_>>().join(", "),
+        partition_by.join(", "),
+        order_by.join(", "),
+    );
+    let mut tail = format_smolstr!("))) WHERE \"{FETCH_ROW}\" > {offset}");
+    if let Some(limit) = limit {
+        let last = offset.saturating_add(limit);
+        tail = format_smolstr!("{tail} AND \"{FETCH_RANK}\" <= {last}");
+    }
+    (head, format_smolstr!("{tail} ORDER BY \"{FETCH_ROW}\""))
+}
+
 /// Wrapper over `SyntaxNode` `arena` that is used for converting it to SQL.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 pub struct OrderedSyntaxNodes {
@@ -1,4 +1,9 @@
 local dt = require('datetime')
+local decimal = require('decimal')
+local digest = require('digest')
+local ffi = require('ffi')
+local json = require('json')
+local msgpack = require('msgpack')
 
 -- Builtin sbroad funcs implemented in LUA
 local builtins = {}
@@ -50,13 +55,48 @@ builtins._PICO_BUCKET = function(tier)
     return result
 end
 
+-- PostgreSQL template patterns and their strftime equivalents.
+-- Longer patterns go first, so that `HH24` is not read as `HH`.
+local datetime_patterns = {
+    { 'YYYY', '%Y' }, { 'YY', '%y' },
+    { 'Month', '%B' }, { 'Mon', '%b' }, { 'MM', '%m' }, { 'MI', '%M' },
+    { 'DD', '%d' }, { 'HH24', '%H' }, { 'HH12', '%I' }, { 'HH', '%I' },
+    { 'SS', '%S' }, { 'AM', '%p' }, { 'PM', '%p' },
+}
+
+-- A format without `%` is a PostgreSQL template (e.g. `YYYY-MM-DD`),
+-- translate it to strftime. Other formats are passed as is.
+local function datetime_format(fmt)
+    if fmt:find('%', 1, true) then
+        return fmt
+    end
+    local res = {}
+    local i = 1
+    while i <= #fmt do
+        local strftime
+        for _, p in ipairs(datetime_patterns) do
+            if fmt:sub(i, i + #p[1] - 1) == p[1] then
+                strftime = p[2]
+                i = i + #p[1]
+                break
+            end
+        end
+        if strftime == nil then
+            strftime = fmt:sub(i, i)
+            i = i + 1
+        end
+        table.insert(res, strftime)
+    end
+    return table.concat(res)
+end
+
 builtins.TO_DATE = function(s, fmt)
     if s == nil or fmt == nil then
         return nil
     end
     local opts = {}
     if fmt ~= '' then
-        opts = { format = fmt }
+        opts = { format = datetime_format(fmt) }
     end
     -- ignore the second returned value
     local res = dt.parse(s, opts)
@@ -68,13 +108,178 @@ end
 builtins.TO_CHAR = function(date, fmt)
     local res
     if date and fmt then
-        res = date:format(fmt)
+        res = date:format(datetime_format(fmt))
     else
         res = nil
     end
     return res
 end
 
+-- Units of DATE_TRUNC, the planner checks constant units in advance.
+local date_trunc_units = {
+    second = true, minute = true, hour = true, day = true,
+    week = true, month = true, quarter = true, year = true,
+}
+
+-- Truncates the datetime to the unit in its own time zone.
+builtins.DATE_TRUNC = function(unit, date)
+    if unit == nil or date == nil then
+        return nil
+    end
+    unit = unit:lower()
+    if not date_trunc_units[unit] then
+        error(string.format('date_trunc: unit "%s" not recognized', unit))
+    end
+    local t = date:totable()
+    local res = {
+        year = t.year, month = t.month, day = t.day,
+        hour = t.hour, min = t.min, sec = t.sec,
+        tzoffset = t.tzoffset,
+    }
+    if unit == 'second' then
+        return dt.new(res)
+    end
+    res.sec = 0
+    if unit == 'minute' then
+        return dt.new(res)
+    end
+    res.min = 0
+    if unit == 'hour' then
+        return dt.new(res)
+    end
+    res.hour = 0
+    if unit == 'day' then
+        return dt.new(res)
+    end
+    if unit == 'week' then
+        -- Weeks start on Monday like in PostgreSQL. 1970-01-01 is Thursday.
+        local days = math.floor((date.epoch + date.tzoffset * 60) / 86400)
+        return dt.new(res):sub({ day = (days + 3) % 7 })
+    end
+    res.day = 1
+    if unit == 'quarter' then
+        res.month = res.month - (res.month - 1) % 3
+    elseif unit == 'year' then
+        res.month = 1
+    end
+    return dt.new(res)
+end
+
+-- Splits a PostgreSQL numeric template into the integer and the
+-- fractional parts. `9` is a digit, `0` is a digit printed even
+-- when it is a leading zero, `.` (`D`) is the decimal point and
+-- `,` (`G`) is the group separator. The `FM` prefix suppresses
+-- padding spaces and trailing zeros.
+local function numeric_format(fmt)
+    local res = { fill_mode = fmt:sub(1, 2):upper() == 'FM' }
+    local template = res.fill_mode and fmt:sub(3) or fmt
+    template = template:gsub('G', ','):gsub('D', '.')
+    local point = template:find('.', 1, true)
+    if point then
+        res.int = template:sub(1, point - 1)
+        res.frac = template:sub(point + 1)
+    else
+        res.int = template
+    end
+    if not (res.int .. (res.frac or '')):match('^[90,]*$') then
+        error(string.format('invalid numeric format "%s"', fmt))
+    end
+    res.int_digits = select(2, res.int:gsub('[90]', ''))
+    res.frac_digits = select(2, (res.frac or ''):gsub('[90]', ''))
+    return res
+end
+
+-- Formats a number by a PostgreSQL numeric template, see `numeric_format`.
+-- A number that doesn't fit the template is printed as `#` digits.
+builtins.TO_CHAR_NUMBER = function(x, fmt)
+    if x == nil or fmt == nil then
+        return nil
+    end
+    local f = numeric_format(fmt)
+    x = decimal.new(x)
+    local digits = tostring(decimal.rescale(decimal.abs(x), f.frac_digits))
+    local int, frac = digits:match('^(%d+)%.?(%d*)$')
+    if int == '0' then
+        int = ''
+    end
+    local overflow = #int > f.int_digits
+
+    local res = {}
+    local leading = f.int_digits - #int
+    local started = false
+    local n = 0
+    for c in f.int:gmatch('.') do
+        if c == ',' then
+            table.insert(res, started and ',' or ' ')
+        else
+            n = n + 1
+            if overflow then
+                table.insert(res, '#')
+                started = true
+            elseif n > leading then
+                table.insert(res, int:sub(n - leading, n - leading))
+                started = true
+            elseif c == '0' or started then
+                table.insert(res, '0')
+                started = true
+            else
+                table.insert(res, ' ')
+            end
+        end
+    end
+    if f.frac then
+        table.insert(res, '.')
+        n = 0
+        local last_digit = #res
+        for c in f.frac:gmatch('.') do
+            if c == ',' then
+                table.insert(res, ',')
+            else
+                n = n + 1
+                table.insert(res, overflow and '#' or frac:sub(n, n))
+                -- In fill mode trailing zeros at `9` positions are dropped.
+                if not f.fill_mode or c == '0' or res[#res] ~= '0' then
+                    last_digit = #res
+                end
+            end
+        end
+        for i = #res, last_digit + 1, -1 do
+            res[i] = nil
+        end
+    end
+
+    -- The sign takes a position right before the first printed digit.
+    local body = table.concat(res)
+    local spaces = body:match('^ *')
+    local sign = x < 0 and '-' or ' '
+    if f.fill_mode then
+        spaces = ''
+        sign = x < 0 and '-' or ''
+    end
+    return spaces .. sign .. body:sub(#body:match('^ *') + 1)
+end
+
+-- Parses a string by a PostgreSQL numeric template, see `numeric_format`.
+builtins.TO_NUMBER = function(s, fmt)
+    if s == nil or fmt == nil then
+        return nil
+    end
+    local f = numeric_format(fmt)
+    local value = s:gsub('[%s,]', '')
+    local sign, int, point, frac = value:match('^([+-]?)(%d*)(%.?)(%d*)$')
+    if sign == nil or int .. frac == '' or #int > f.int_digits
+        or #frac > f.frac_digits or (point ~= '' and f.frac == nil) then
+        error(string.format('value "%s" does not match format "%s"', s, fmt))
+    end
+    if int == '' then
+        int = '0'
+    end
+    if frac ~= '' then
+        int = int .. '.' .. frac
+    end
+    return decimal.new(sign .. int)
+end
+
 builtins.SUBSTRING_TO_REGEXP = function(string, pattern, expr)
     -- Check for NULL parameters
     if string == nil or pattern == nil or expr == nil then
@@ -88,20 +293,21 @@ builtins.SUBSTRING_TO_REGEXP = function(string, pattern, expr)
     return builtins.SUBSTRING(string, new_pattern)
 end
 
-builtins.JSON_EXTRACT_PATH = function (mp)
-  local args = mp:decode()
+-- Walk the json document (the first argument) by the keys (the rest
+-- of the arguments). Indexing a value that is neither an object nor
+-- an array yields NULL.
+local function json_extract_path(args)
   if args == nil or #args == 0 then
     return nil
   end
-  local map = args[1]
+  local res = args[1]
   local path = args[2]
-  if map == nil or path == nil then
+  if res == nil or path == nil then
     return nil
   end
-  local res = map[path]
   for i, v in ipairs(args) do
-    if i > 2 then
-      if res == nil or type(res) ~= 'table' then
+    if i > 1 then
+      if type(res) ~= 'table' then
         return nil
       end
       res = res[v]
@@ -110,83 +316,1193 @@ builtins.JSON_EXTRACT_PATH = function (mp)
   return res
 end
 
+builtins.JSON_EXTRACT_PATH = function (mp)
+  return json_extract_path(mp:decode())
+end
 
-local function init()
-    if rawget(_G, module) == nil then
-        error('buitins must be initialized after app module was set!')
+builtins.JSON_EXTRACT_PATH_TEXT = function (mp)
+  local res = json_extract_path(mp:decode())
+  if res == nil or type(res) == 'string' then
+    return res
+  end
+  return json.encode(res)
+end
+
+builtins._PICO_JSON_PARSE = function (text)
+  if text == nil then
+    return nil
+  end
+  return json.decode(text)
+end
+
+-- `value IS JSON`: a json document or a text that can be parsed
+-- as one. Malformed text is not json rather than an error.
+builtins._PICO_IS_JSON = function (mp)
+  local value = mp:decode()[1]
+  if value == nil then
+    return nil
+  end
+  if type(value) ~= 'string' then
+    return true
+  end
+  local ok = pcall(json.decode, value)
+  return ok
+end
+
+local function is_json_array(value)
+  local mt = getmetatable(value)
+  if mt ~= nil and mt.__serialize ~= nil then
+    local kind = mt.__serialize
+    return kind == 'seq' or kind == 'sequence' or kind == 'array'
+  end
+  return next(value) == nil or value[1] ~= nil
+end
+
+-- Whether the json document `left` contains `right`: scalars are
+-- equal, every key of an object is contained in the same key of
+-- the other object, every element of an array is contained in some
+-- element of the other array. As in PostgreSQL, a top level array
+-- also contains a scalar element.
+local function json_contains(left, right, is_top)
+  if type(left) ~= 'table' or type(right) ~= 'table' then
+    if is_top and type(left) == 'table' and is_json_array(left) then
+      for _, v in ipairs(left) do
+        if json_contains(v, right, false) then
+          return true
+        end
+      end
+      return false
+    end
+    return left == right
+  end
+  local left_is_array = is_json_array(left)
+  if left_is_array ~= is_json_array(right) then
+    return false
+  end
+  if left_is_array then
+    for _, r in ipairs(right) do
+      local found = false
+      for _, l in ipairs(left) do
+        if json_contains(l, r, false) then
+          found = true
+          break
+        end
+      end
+      if not found then
+        return false
+      end
     end
+    return true
+  end
+  for k, r in pairs(right) do
+    -- Json null is box.NULL, which is equal to nil.
+    if type(left[k]) == 'nil' or not json_contains(left[k], r, false) then
+      return false
+    end
+  end
+  return true
+end
 
-    _G[module].builtins = builtins
+builtins._PICO_JSON_CONTAINS = function (mp)
+  local args = mp:decode()
+  if args[1] == nil or args[2] == nil then
+    return nil
+  end
+  return json_contains(args[1], args[2], true)
 end
 
-local function create_functions()
-    -- We don't want to make builtin functions global,
-    -- so we put them in module. But for function
-    -- to be callable from sql it should have a simple
-    -- name, so for each func we provide a body.
-    local body = string.format("function(...) return %s.builtins._PICO_BUCKET(...) end",
-        module)
-    box.schema.func.create("_pico_bucket", {
-        language = 'LUA',
-        returns = 'ARRAY',
-        body = body,
-        param_list = { 'string' },
-        exports = { 'SQL' },
-        is_deterministic = false,
-        if_not_exists = true
-    })
+-- Split a json path like `$.a."b c"[0]` into keys. Only member
+-- accessors and array subscripts are supported.
+local function parse_json_path(path)
+  if path:sub(1, 1) ~= '$' then
+    error(string.format("syntax error in json path \"%s\"", path))
+  end
+  local keys = {}
+  local pos = 2
+  while pos <= #path do
+    local key, next_pos = path:match('^%.([%w_]+)()', pos)
+    if key == nil then
+      key, next_pos = path:match('^%."([^"]*)"()', pos)
+    end
+    if key == nil then
+      local index
+      index, next_pos = path:match('^%[(%d+)%]()', pos)
+      -- Json arrays are zero-based, lua arrays are one-based.
+      key = index and tonumber(index) + 1
+    end
+    if key == nil then
+      error(string.format("syntax error in json path \"%s\"", path))
+    end
+    table.insert(keys, key)
+    pos = next_pos
+  end
+  return keys
+end
 
-    body = string.format("function(...) return %s.builtins.TO_DATE(...) end",
-        module)
-    box.schema.func.create("to_date", {
-        language = 'LUA',
-        returns = 'datetime',
-        body = body,
-        param_list = { 'string', 'string' },
-        exports = { 'SQL' },
-        is_deterministic = true,
-        if_not_exists = true
-    })
+-- Return the item of the json document matching the path,
+-- NULL if there is none.
+builtins.JSONB_PATH_QUERY = function (mp)
+  local args = mp:decode()
+  local res, path = args[1], args[2]
+  if res == nil or path == nil then
+    return nil
+  end
+  for _, key in ipairs(parse_json_path(path)) do
+    if type(res) ~= 'table' then
+      return nil
+    end
+    res = res[key]
+  end
+  return res
+end
 
-    body = string.format("function(...) return %s.builtins.TO_CHAR(...) end",
-        module)
-    box.schema.func.create("to_char", {
-        language = 'LUA',
-        returns = 'string',
-        body = body,
-        param_list = { 'datetime', 'string' },
-        exports = { 'SQL' },
-        is_deterministic = true,
-        if_not_exists = true
-    })
+-- Local date and time of a text, the time zone offset after it is ignored.
+local function parse_local_datetime(s)
+    local year, month, day, rest = s:match('^(%-?%d+)%-(%d%d)%-(%d%d)(.*)$')
+    if year == nil then
+        return nil
+    end
+    local t = {
+        year = tonumber(year), month = tonumber(month), day = tonumber(day),
+        hour = 0, min = 0, sec = 0, nsec = 0,
+    }
+    local hour, min, time_rest = rest:match('^[Tt ]%s*(%d%d):(%d%d)(.*)$')
+    if hour ~= nil then
+        t.hour, t.min = tonumber(hour), tonumber(min)
+        local sec, frac = time_rest:match('^:(%d%d)%.?(%d*)')
+        if sec ~= nil then
+            t.sec = tonumber(sec)
+            if frac ~= '' then
+                t.nsec = tonumber((frac .. '000000000'):sub(1, 9))
+            end
+        end
+    end
+    return t
+end
 
-    body = string.format("function(...) return %s.builtins.SUBSTRING(...) end",
-        module)
-    box.schema.func.create("substring", {
-        language = 'LUA',
-        returns = 'string',
-        body = body,
-        param_list = { 'string', 'string' },
-        exports = { 'SQL' },
-        is_deterministic = true,
-        if_not_exists = true
-    })
+-- Time of day `hh:mm[:ss[.fraction]]`, the time zone offset is ignored.
+local function parse_time(s)
+    local hour, min, rest = s:match('^(%d%d?):(%d%d)(.*)$')
+    if hour == nil then
+        return nil
+    end
+    local sec, frac = rest:match('^:(%d%d)%.?(%d*)')
+    local nsec = 0
+    if frac ~= nil and frac ~= '' then
+        nsec = tonumber((frac .. '000000000'):sub(1, 9))
+    end
+    return tonumber(hour), tonumber(min), tonumber(sec or 0), nsec
+end
 
-    body = string.format("function(...) return %s.builtins.SUBSTRING_TO_REGEXP(...) end",
-        module)
-    box.schema.func.create("substring_to_regexp", {
-        language = 'LUA',
-        returns = 'string',
-        body = body,
-        param_list = { 'string', 'string', 'string' },
-        exports = { 'SQL' },
-        is_deterministic = true,
-        if_not_exists = true
+-- `hh:mm:ss[.ffffff]` without trailing zeros of the fraction.
+local function format_time(hour, min, sec, nsec)
+    local res = string.format('%02d:%02d:%02d', hour, min, sec)
+    local micro = math.floor(nsec / 1000)
+    if micro == 0 then
+        return res
+    end
+    local frac = string.format('%06d', micro):gsub('0+$', '')
+    return res .. '.' .. frac
+end
+
+-- Datetime fields in UTC.
+local function utc_fields(value)
+    return dt.new({ timestamp = value.epoch, nsec = value.nsec }):totable()
+end
+
+-- `CAST(expr AS date)` and `CAST(expr AS timestamp)`: text keeps its local
+-- date and time, datetimes are taken in UTC. The result is a datetime in UTC.
+builtins._PICO_TO_TIMESTAMP = function(mp)
+    local args = mp:decode()
+    local value, kind = args[1], args[2]
+    if value == nil then
+        return nil
+    end
+    local t
+    if dt.is_datetime(value) then
+        t = utc_fields(value)
+    else
+        t = parse_local_datetime(tostring(value):match('^%s*(.-)%s*$'))
+        if t == nil then
+            error(string.format('invalid input syntax for type %s: "%s"', kind, value))
+        end
+    end
+    if kind == 'date' then
+        t.hour, t.min, t.sec, t.nsec = 0, 0, 0, 0
+    end
+    return dt.new({
+        year = t.year, month = t.month, day = t.day,
+        hour = t.hour, min = t.min, sec = t.sec, nsec = t.nsec,
+        tzoffset = 0,
     })
+end
 
-    body = string.format("function(...) return %s.builtins.JSON_EXTRACT_PATH(...) end",
-        module)
-    box.schema.func.create("json_extract_path", {
+-- `CAST(expr AS time)`: the time of day as text `hh:mm:ss[.ffffff]`,
+-- which orders like the time itself.
+builtins._PICO_TO_TIME = function(mp)
+    local args = mp:decode()
+    local value = args[1]
+    if value == nil then
+        return nil
+    end
+    if dt.is_datetime(value) then
+        local t = utc_fields(value)
+        return format_time(t.hour, t.min, t.sec, t.nsec)
+    end
+    local s = tostring(value):match('^%s*(.-)%s*$')
+    local hour, min, sec, nsec = parse_time(s)
+    if hour == nil then
+        local t = parse_local_datetime(s)
+        if t ~= nil then
+            hour, min, sec, nsec = t.hour, t.min, t.sec, t.nsec
+        end
+    end
+    if hour == nil or hour > 23 or min > 59 or sec > 59 then
+        error(string.format('invalid input syntax for type time: "%s"', value))
+    end
+    return format_time(hour, min, sec, nsec)
+end
+
+-- Rounding of `CAST(expr AS int)` for fractional values: half away from
+-- zero for decimals and half to even for doubles, like on the router.
+builtins._PICO_ROUND_TO_INT = function(x)
+    if x == nil then
+        return nil
+    end
+    if decimal.is_decimal(x) then
+        return decimal.round(x, 0)
+    end
+    local res = math.floor(x)
+    local diff = x - res
+    if diff > 0.5 or (diff == 0.5 and res % 2 ~= 0) then
+        res = res + 1
+    end
+    return res
+end
+
+-- `CAST(expr AS smallint)`: storages know only 64-bit integers,
+-- so the range of a narrow integer type is checked separately.
+builtins._PICO_INT_RANGE = function(value, min, max, name)
+    if value ~= nil and (value < min or value > max) then
+        error(string.format('%s out of range', name))
+    end
+    return value
+end
+
+-- Filter of `TABLESAMPLE BERNOULLI(percent) [REPEATABLE(seed)]`.
+-- Arguments are the percentage, the seed (may be NULL) and the primary
+-- key of the row. With a seed the decision depends only on the seed and
+-- the key, so the same rows are returned while the table doesn't change.
+builtins._PICO_TABLESAMPLE = function(mp)
+    local args = mp:decode()
+    local percent = tonumber(args[1])
+    local seed = args[2]
+    if seed == nil then
+        return math.random() * 100 < percent
+    end
+    local key = { seed }
+    for i = 3, #args do
+        key[i - 1] = args[i]
+    end
+    local hash = digest.murmur(msgpack.encode(key))
+    return hash % 1000000 < percent * 10000
+end
+
+builtins.CEIL = function(x)
+    if x == nil then
+        return nil
+    end
+    if decimal.is_decimal(x) then
+        local res = decimal.round(x, 0)
+        if res < x then
+            res = res + 1
+        end
+        return res
+    end
+    if type(x) == 'cdata' then
+        return x
+    end
+    return math.ceil(x)
+end
+
+builtins.FLOOR = function(x)
+    if x == nil then
+        return nil
+    end
+    if decimal.is_decimal(x) then
+        local res = decimal.round(x, 0)
+        if res > x then
+            res = res - 1
+        end
+        return res
+    end
+    if type(x) == 'cdata' then
+        return x
+    end
+    return math.floor(x)
+end
+
+-- CEIL, FLOOR and ROUND of a double, the planner picks them by the
+-- argument type. Integral Lua numbers are encoded as integers, so the
+-- result is returned as a C double to stay a double in SQL.
+builtins.CEIL_DOUBLE = function(x)
+    if x == nil then
+        return nil
+    end
+    return ffi.cast('double', math.ceil(x))
+end
+
+builtins.FLOOR_DOUBLE = function(x)
+    if x == nil then
+        return nil
+    end
+    return ffi.cast('double', math.floor(x))
+end
+
+builtins.ROUND_DOUBLE = function(x)
+    if x == nil then
+        return nil
+    end
+    if x >= 0 then
+        return ffi.cast('double', math.floor(x + 0.5))
+    end
+    return ffi.cast('double', math.ceil(x - 0.5))
+end
+
+builtins.SIGN = function(x)
+    if x == nil then
+        return nil
+    end
+    if x > 0 then
+        return 1
+    elseif x < 0 then
+        return -1
+    end
+    return 0
+end
+
+-- ROUND(x) rounds half away from zero and keeps the argument type,
+-- ROUND(x, digits) returns a decimal. Negative digits round to
+-- tens, hundreds and so on like in PostgreSQL.
+builtins.ROUND = function (mp)
+    local args = mp:decode()
+    if args == nil or #args == 0 then
+        return nil
+    end
+    local x = args[1]
+    local digits = args[2]
+    if x == nil then
+        return nil
+    end
+    if #args == 1 then
+        if decimal.is_decimal(x) then
+            return decimal.round(x, 0)
+        end
+        if type(x) == 'cdata' then
+            return x
+        end
+        if x >= 0 then
+            return math.floor(x + 0.5)
+        end
+        return math.ceil(x - 0.5)
+    end
+    if digits == nil then
+        return nil
+    end
+    x = decimal.new(x)
+    if digits >= 0 then
+        return decimal.round(x, digits)
+    end
+    local scale = decimal.new(10) ^ -digits
+    return decimal.round(x / scale, 0) * scale
+end
+
+-- Number of bytes in the string (LENGTH and CHAR_LENGTH count characters).
+builtins.OCTET_LENGTH = function(s)
+    if s == nil then
+        return nil
+    end
+    return #s
+end
+
+-- MD5 digest of the string as 32 hexadecimal digits.
+builtins.MD5 = function(s)
+    if s == nil then
+        return nil
+    end
+    return digest.md5_hex(s)
+end
+
+-- SHA-256 digest of the string as 64 hexadecimal digits.
+builtins.SHA256 = function(s)
+    if s == nil then
+        return nil
+    end
+    return digest.sha256_hex(s)
+end
+
+-- SHA-256 digest of the bytea. Lua strings are encoded as msgpack
+-- strings, so the binary result is returned as a raw msgpack bin.
+builtins.SHA256_BYTEA = function(b)
+    if b == nil then
+        return nil
+    end
+    local hash = digest.sha256(b)
+    return msgpack.object_from_raw(string.char(0xc4, #hash) .. hash)
+end
+
+-- Code point of the first character, 0 for an empty string like in PostgreSQL.
+builtins.ASCII = function(s)
+    if s == nil then
+        return nil
+    end
+    if s == '' then
+        return 0
+    end
+    local _, code = utf8.next(s)
+    return code
+end
+
+builtins.CHR = function(code)
+    if code == nil then
+        return nil
+    end
+    return utf8.char(tonumber(code))
+end
+
+-- Pads the string up to `len` characters with `fill` (a space by default)
+-- or truncates it to `len` characters like in PostgreSQL.
+local function pad(mp, is_left)
+    local args = mp:decode()
+    local s, len, fill = args[1], args[2], args[3]
+    if #args == 2 then
+        fill = ' '
+    end
+    if s == nil or len == nil or fill == nil then
+        return nil
+    end
+    len = tonumber(len)
+    if len <= 0 then
+        return ''
+    end
+    local n = utf8.len(s)
+    if n >= len then
+        return utf8.sub(s, 1, len)
+    end
+    if fill == '' then
+        return s
+    end
+    local count = math.ceil((len - n) / utf8.len(fill))
+    local padding = utf8.sub(string.rep(fill, count), 1, len - n)
+    if is_left then
+        return padding .. s
+    end
+    return s .. padding
+end
+
+builtins.LPAD = function(mp)
+    return pad(mp, true)
+end
+
+builtins.RPAD = function(mp)
+    return pad(mp, false)
+end
+
+-- A non-positive count gives an empty string.
+builtins.REPEAT = function(s, n)
+    if s == nil or n == nil then
+        return nil
+    end
+    n = tonumber(n)
+    if n <= 0 then
+        return ''
+    end
+    return string.rep(s, n)
+end
+
+builtins.REVERSE = function(s)
+    if s == nil then
+        return nil
+    end
+    local chars = {}
+    for _, code in utf8.next, s do
+        table.insert(chars, 1, utf8.char(code))
+    end
+    return table.concat(chars)
+end
+
+-- Returns the n-th field of the string split on the delimiter, counting
+-- from the end for a negative n. A field out of range is an empty string,
+-- an empty delimiter gives the whole string as the only field.
+builtins.SPLIT_PART = function(s, delim, n)
+    if s == nil or delim == nil or n == nil then
+        return nil
+    end
+    n = tonumber(n)
+    if n == 0 then
+        error('field position must not be zero')
+    end
+    local fields = {}
+    if delim == '' then
+        if s ~= '' then
+            table.insert(fields, s)
+        end
+    else
+        local start = 1
+        while true do
+            local from, to = s:find(delim, start, true)
+            if from == nil then
+                table.insert(fields, s:sub(start))
+                break
+            end
+            table.insert(fields, s:sub(start, from - 1))
+            start = to + 1
+        end
+    end
+    if n < 0 then
+        n = #fields + n + 1
+    end
+    return fields[n] or ''
+end
+
+-- Splits the string into an array of its parts like in PostgreSQL.
+-- A NULL separator splits the string into characters, an empty one
+-- gives an array with the whole string. Parts equal to `null_string`
+-- are replaced with NULLs.
+builtins.STRING_TO_ARRAY = function(mp)
+    local args = mp:decode()
+    local s, sep, null_string = args[1], args[2], args[3]
+    if s == nil then
+        return nil
+    end
+    local parts = setmetatable({}, { __serialize = 'seq' })
+    if s == '' then
+        return parts
+    end
+    if sep == nil then
+        for _, code in utf8.next, s do
+            table.insert(parts, utf8.char(code))
+        end
+    elseif sep == '' then
+        table.insert(parts, s)
+    else
+        local start = 1
+        while true do
+            local from, to = s:find(sep, start, true)
+            if from == nil then
+                table.insert(parts, s:sub(start))
+                break
+            end
+            table.insert(parts, s:sub(start, from - 1))
+            start = to + 1
+        end
+    end
+    if null_string ~= nil then
+        for i, part in ipairs(parts) do
+            if part == null_string then
+                parts[i] = box.NULL
+            end
+        end
+    end
+    return parts
+end
+
+-- Joins the array elements with the separator. NULL elements are
+-- skipped unless `null_string` is given to replace them.
+builtins.ARRAY_TO_STRING = function(mp)
+    local args = mp:decode()
+    local arr, sep, null_string = args[1], args[2], args[3]
+    if arr == nil or sep == nil then
+        return nil
+    end
+    local parts = {}
+    for i = 1, #arr do
+        local v = arr[i]
+        if v == nil then
+            if null_string ~= nil then
+                table.insert(parts, null_string)
+            end
+        elseif type(v) == 'string' then
+            table.insert(parts, v)
+        else
+            table.insert(parts, json.encode(v))
+        end
+    end
+    return table.concat(parts, sep)
+end
+
+-- Percentile aggregates receive all values of the group
+-- concatenated by GROUP_CONCAT with the default separator.
+-- The values are compared as decimals, so no precision is lost,
+-- and keep their text to be cast back to the key type.
+local function sorted_percentile_values(values, is_desc)
+    local sorted = {}
+    for v in string.gmatch(values, '[^,]+') do
+        table.insert(sorted, { key = decimal.new(v), text = v })
+    end
+    if is_desc then
+        table.sort(sorted, function(a, b) return a.key > b.key end)
+    else
+        table.sort(sorted, function(a, b) return a.key < b.key end)
+    end
+    return sorted
+end
+
+local function check_percentile_fraction(fraction)
+    fraction = decimal.new(fraction)
+    if fraction < 0 or fraction > 1 then
+        error(string.format('percentile value %s is not between 0 and 1', tostring(fraction)))
+    end
+    return fraction
+end
+
+-- Largest integer not greater than the decimal, as a Lua number.
+local function decimal_floor(x)
+    local res = decimal.round(x, 0)
+    if res > x then
+        res = res - 1
+    end
+    return tonumber(tostring(res))
+end
+
+builtins.PERCENTILE_CONT = function(values, fraction, is_desc)
+    if values == nil or fraction == nil then
+        return nil
+    end
+    fraction = check_percentile_fraction(fraction)
+    local sorted = sorted_percentile_values(values, is_desc)
+    local n = #sorted
+    if n == 0 then
+        return nil
+    end
+    local pos = fraction * (n - 1)
+    local lo = decimal_floor(pos)
+    local hi = math.min(lo + 1, n - 1)
+    local lo_key = sorted[lo + 1].key
+    local hi_key = sorted[hi + 1].key
+    return lo_key + (hi_key - lo_key) * (pos - lo)
+end
+
+-- Returns the text of the value, the router casts it back to the key type.
+builtins.PERCENTILE_DISC = function(values, fraction, is_desc)
+    if values == nil or fraction == nil then
+        return nil
+    end
+    fraction = check_percentile_fraction(fraction)
+    local sorted = sorted_percentile_values(values, is_desc)
+    local n = #sorted
+    if n == 0 then
+        return nil
+    end
+    local pos = fraction * n
+    local idx = decimal_floor(pos)
+    if idx < pos then
+        idx = idx + 1
+    end
+    return sorted[math.max(1, idx)].text
+end
+
+
+local function init()
+    if rawget(_G, module) == nil then
+        error('buitins must be initialized after app module was set!')
+    end
+
+    _G[module].builtins = builtins
+end
+
+local function create_functions()
+    -- We don't want to make builtin functions global,
+    -- so we put them in module. But for function
+    -- to be callable from sql it should have a simple
+    -- name, so for each func we provide a body.
+    local body = string.format("function(...) return %s.builtins._PICO_BUCKET(...) end",
+        module)
+    box.schema.func.create("_pico_bucket", {
+        language = 'LUA',
+        returns = 'ARRAY',
+        body = body,
+        param_list = { 'string' },
+        exports = { 'SQL' },
+        is_deterministic = false,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.TO_DATE(...) end",
+        module)
+    box.schema.func.create("to_date", {
+        language = 'LUA',
+        returns = 'datetime',
+        body = body,
+        param_list = { 'string', 'string' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.TO_CHAR(...) end",
+        module)
+    box.schema.func.create("to_char", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        param_list = { 'datetime', 'string' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.DATE_TRUNC(...) end",
+        module)
+    box.schema.func.create("date_trunc", {
+        language = 'LUA',
+        returns = 'datetime',
+        body = body,
+        param_list = { 'string', 'datetime' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    -- TO_CHAR of a number, the planner picks it by the argument type.
+    body = string.format("function(...) return %s.builtins.TO_CHAR_NUMBER(...) end",
+        module)
+    box.schema.func.create("_pico_to_char_number", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        param_list = { 'number', 'string' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.TO_NUMBER(...) end",
+        module)
+    box.schema.func.create("to_number", {
+        language = 'LUA',
+        returns = 'number',
+        body = body,
+        param_list = { 'string', 'string' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.SUBSTRING(...) end",
+        module)
+    box.schema.func.create("substring", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        param_list = { 'string', 'string' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.SUBSTRING_TO_REGEXP(...) end",
+        module)
+    box.schema.func.create("substring_to_regexp", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        param_list = { 'string', 'string', 'string' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.CEIL(...) end",
+        module)
+    box.schema.func.create("ceil", {
+        language = 'LUA',
+        returns = 'number',
+        body = body,
+        param_list = { 'number' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.FLOOR(...) end",
+        module)
+    box.schema.func.create("floor", {
+        language = 'LUA',
+        returns = 'number',
+        body = body,
+        param_list = { 'number' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    -- Rounding of a double, the planner picks it by the argument type.
+    body = string.format("function(...) return %s.builtins.CEIL_DOUBLE(...) end",
+        module)
+    box.schema.func.create("_pico_ceil_double", {
+        language = 'LUA',
+        returns = 'number',
+        body = body,
+        param_list = { 'number' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.FLOOR_DOUBLE(...) end",
+        module)
+    box.schema.func.create("_pico_floor_double", {
+        language = 'LUA',
+        returns = 'number',
+        body = body,
+        param_list = { 'number' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.ROUND_DOUBLE(...) end",
+        module)
+    box.schema.func.create("_pico_round_double", {
+        language = 'LUA',
+        returns = 'number',
+        body = body,
+        param_list = { 'number' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.SIGN(...) end",
+        module)
+    box.schema.func.create("sign", {
+        language = 'LUA',
+        returns = 'integer',
+        body = body,
+        param_list = { 'number' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    -- Takes raw arguments to support both ROUND(x) and ROUND(x, digits).
+    body = string.format("function(...) return %s.builtins.ROUND(...) end",
+        module)
+    box.schema.func.create("round", {
+        language = 'LUA',
+        body = body,
+        exports = {'SQL'},
+        is_deterministic = true,
+        if_not_exists = true,
+        takes_raw_args = true
+    })
+
+    body = string.format("function(...) return %s.builtins.OCTET_LENGTH(...) end",
+        module)
+    box.schema.func.create("octet_length", {
+        language = 'LUA',
+        returns = 'integer',
+        body = body,
+        param_list = { 'string' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.MD5(...) end",
+        module)
+    box.schema.func.create("md5", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        param_list = { 'string' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.SHA256(...) end",
+        module)
+    box.schema.func.create("sha256", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        param_list = { 'string' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    -- SHA256 of a bytea, the planner picks it by the argument type.
+    body = string.format("function(...) return %s.builtins.SHA256_BYTEA(...) end",
+        module)
+    box.schema.func.create("_pico_sha256_bytea", {
+        language = 'LUA',
+        returns = 'varbinary',
+        body = body,
+        param_list = { 'varbinary' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.ASCII(...) end",
+        module)
+    box.schema.func.create("ascii", {
+        language = 'LUA',
+        returns = 'integer',
+        body = body,
+        param_list = { 'string' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.CHR(...) end",
+        module)
+    box.schema.func.create("chr", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        param_list = { 'integer' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    -- Take raw arguments to support both LPAD(s, len) and LPAD(s, len, fill).
+    body = string.format("function(...) return %s.builtins.LPAD(...) end",
+        module)
+    box.schema.func.create("lpad", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        exports = {'SQL'},
+        is_deterministic = true,
+        if_not_exists = true,
+        takes_raw_args = true
+    })
+
+    body = string.format("function(...) return %s.builtins.RPAD(...) end",
+        module)
+    box.schema.func.create("rpad", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        exports = {'SQL'},
+        is_deterministic = true,
+        if_not_exists = true,
+        takes_raw_args = true
+    })
+
+    body = string.format("function(...) return %s.builtins.SPLIT_PART(...) end",
+        module)
+    box.schema.func.create("split_part", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        param_list = { 'string', 'string', 'integer' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    -- Take raw arguments to support the optional `null_string` argument.
+    body = string.format("function(...) return %s.builtins.STRING_TO_ARRAY(...) end",
+        module)
+    box.schema.func.create("string_to_array", {
+        language = 'LUA',
+        returns = 'array',
+        body = body,
+        exports = {'SQL'},
+        is_deterministic = true,
+        if_not_exists = true,
+        takes_raw_args = true
+    })
+
+    body = string.format("function(...) return %s.builtins.ARRAY_TO_STRING(...) end",
+        module)
+    box.schema.func.create("array_to_string", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        exports = {'SQL'},
+        is_deterministic = true,
+        if_not_exists = true,
+        takes_raw_args = true
+    })
+
+    body = string.format("function(...) return %s.builtins.REPEAT(...) end",
+        module)
+    box.schema.func.create("repeat", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        param_list = { 'string', 'integer' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.REVERSE(...) end",
+        module)
+    box.schema.func.create("reverse", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        param_list = { 'string' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.PERCENTILE_CONT(...) end",
+        module)
+    box.schema.func.create("percentile_cont", {
+        language = 'LUA',
+        returns = 'number',
+        body = body,
+        param_list = { 'string', 'number', 'boolean' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins.PERCENTILE_DISC(...) end",
+        module)
+    box.schema.func.create("percentile_disc", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        param_list = { 'string', 'number', 'boolean' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins._PICO_ROUND_TO_INT(...) end",
+        module)
+    box.schema.func.create("_pico_round_to_int", {
+        language = 'LUA',
+        returns = 'number',
+        body = body,
+        param_list = { 'number' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins._PICO_INT_RANGE(...) end",
+        module)
+    box.schema.func.create("_pico_int_range", {
+        language = 'LUA',
+        returns = 'integer',
+        body = body,
+        param_list = { 'integer', 'integer', 'integer', 'string' },
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins._PICO_TO_TIMESTAMP(...) end",
+        module)
+    box.schema.func.create("_pico_to_timestamp", {
+        language = 'LUA',
+        returns = 'datetime',
+        body = body,
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true,
+        takes_raw_args = true
+    })
+
+    body = string.format("function(...) return %s.builtins._PICO_TO_TIME(...) end",
+        module)
+    box.schema.func.create("_pico_to_time", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        exports = { 'SQL' },
+        is_deterministic = true,
+        if_not_exists = true,
+        takes_raw_args = true
+    })
+
+    body = string.format("function(...) return %s.builtins._PICO_TABLESAMPLE(...) end",
+        module)
+    box.schema.func.create("_pico_tablesample", {
+        language = 'LUA',
+        returns = 'boolean',
+        body = body,
+        exports = { 'SQL' },
+        is_deterministic = false,
+        if_not_exists = true,
+        takes_raw_args = true
+    })
+
+    body = string.format("function(...) return %s.builtins.JSON_EXTRACT_PATH(...) end",
+        module)
+    box.schema.func.create("json_extract_path", {
+        language = 'LUA',
+        body = body,
+        exports = {'SQL'},
+        is_deterministic = true,
+        if_not_exists = true,
+        takes_raw_args = true
+    })
+
+    body = string.format("function(...) return %s.builtins.JSON_EXTRACT_PATH_TEXT(...) end",
+        module)
+    box.schema.func.create("json_extract_path_text", {
+        language = 'LUA',
+        returns = 'string',
+        body = body,
+        exports = {'SQL'},
+        is_deterministic = true,
+        if_not_exists = true,
+        takes_raw_args = true
+    })
+
+    body = string.format("function(...) return %s.builtins._PICO_JSON_PARSE(...) end",
+        module)
+    box.schema.func.create("_pico_json_parse", {
+        language = 'LUA',
+        returns = 'any',
+        body = body,
+        param_list = { 'string' },
+        exports = {'SQL'},
+        is_deterministic = true,
+        if_not_exists = true
+    })
+
+    body = string.format("function(...) return %s.builtins._PICO_IS_JSON(...) end",
+        module)
+    box.schema.func.create("_pico_is_json", {
+        language = 'LUA',
+        returns = 'boolean',
+        body = body,
+        exports = {'SQL'},
+        is_deterministic = true,
+        if_not_exists = true,
+        takes_raw_args = true
+    })
+
+    body = string.format("function(...) return %s.builtins._PICO_JSON_CONTAINS(...) end",
+        module)
+    box.schema.func.create("_pico_json_contains", {
+        language = 'LUA',
+        returns = 'boolean',
+        body = body,
+        exports = {'SQL'},
+        is_deterministic = true,
+        if_not_exists = true,
+        takes_raw_args = true
+    })
+
+    body = string.format("function(...) return %s.builtins.JSONB_PATH_QUERY(...) end",
+        module)
+    box.schema.func.create("jsonb_path_query", {
         language = 'LUA',
         body = body,
         exports = {'SQL'},
@@ -35,6 +35,8 @@ pub enum Entity {
     Cache,
     /// corresponding to struct Chain
     Chain,
+    /// string collation
+    Collation,
     /// general variant
     Column,
     /// CTE
@@ -167,6 +169,7 @@ impl fmt::Display for Entity {
             Entity::Bytes => "bytes".to_smolstr(),
             Entity::Cache => "cache".to_smolstr(),
             Entity::Chain => "chain".to_smolstr(),
+            Entity::Collation => "collation".to_smolstr(),
             Entity::Column => "column".to_smolstr(),
             Entity::Cte => "CTE".to_smolstr(),
             Entity::Ddl => "DDL".to_smolstr(),
@@ -294,6 +297,7 @@ pub enum TypeError {
     AmbiguousParameterType(u16, UnrestrictedType, UnrestrictedType),
     CouldNotDetermineParameterType(usize),
     TypesCannotBeMatched(&'static str, UnrestrictedType, UnrestrictedType),
+    ColumnTypesCannotBeMatched(&'static str, SmolStr, UnrestrictedType, UnrestrictedType),
 }
 
 impl fmt::Display for TypeError {
@@ -312,6 +316,9 @@ impl fmt::Display for TypeError {
             TypeError::TypesCannotBeMatched(f, t1, t2) => {
                 format_smolstr!("{f} types {t1} and {t2} cannot be matched")
             }
+            TypeError::ColumnTypesCannotBeMatched(f, column, t1, t2) => {
+                format_smolstr!("{f} types {t1} and {t2} cannot be matched for column \"{column}\"")
+            }
         };
 
         write!(f, "{p}")
@@ -362,6 +369,18 @@ pub enum SbroadError {
     ProtocolError(ProtocolError),
     // Can't use vdbe::SqlError because it doesn't implement PartialEq :(
     VdbeError(SmolStr),
+    /// NOT NULL constraint is violated.
+    /// Params are the table and the column names.
+    NotNullViolation(SmolStr, SmolStr),
+    /// CHECK constraint is violated.
+    /// Params are the table and the constraint names.
+    CheckViolation(SmolStr, SmolStr),
+    /// Text is not a valid input for the type.
+    /// Params are the type name and the text.
+    InvalidTextRepresentation(SmolStr, SmolStr),
+    /// Value does not fit into the type.
+    /// Param is the type name.
+    NumericValueOutOfRange(SmolStr),
     Other(SmolStr),
 }
 
@@ -369,6 +388,35 @@ impl SbroadError {
     pub fn other(msg: impl Into<SmolStr>) -> Self {
         Self::Other(msg.into())
     }
+
+    /// Entity the error is about, if any.
+    pub fn entity(&self) -> Option<&Entity> {
+        match self {
+            SbroadError::FailedTo(_, entity, _) => entity.as_ref(),
+            SbroadError::Invalid(entity, _)
+            | SbroadError::NotFound(entity, _)
+            | SbroadError::NotImplemented(entity, _)
+            | SbroadError::ParsingError(entity, _)
+            | SbroadError::Unsupported(entity, _) => Some(entity),
+            _ => None,
+        }
+    }
+
+    /// Advice on how to fix the error, if any.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            SbroadError::NotFound(Entity::Column | Entity::Table, _) => {
+                Some("unquoted names are case-folded, quote the name to match it exactly")
+            }
+            SbroadError::UseOfBothParamsStyles => {
+                Some("use either $n or ? parameters within a query")
+            }
+            SbroadError::OutdatedStorageSchema => {
+                Some("the schema has changed while the query was running, retry it")
+            }
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for SbroadError {
@@ -411,6 +459,16 @@ impl fmt::Display for SbroadError {
             SbroadError::ProtocolError(err) => {
                 format_smolstr!("{err}")
             }
+            SbroadError::NotNullViolation(table, column) => format_smolstr!(
+                "null value in column \"{column}\" of relation \"{table}\" violates not-null constraint"
+            ),
+            SbroadError::CheckViolation(table, check) => format_smolstr!(
+                "new row for relation \"{table}\" violates check constraint \"{check}\""
+            ),
+            SbroadError::InvalidTextRepresentation(ty, text) => {
+                format_smolstr!("invalid input syntax for type {ty}: \"{text}\"")
+            }
+            SbroadError::NumericValueOutOfRange(ty) => format_smolstr!("{ty} out of range"),
             SbroadError::DispatchError(s) | SbroadError::Other(s) | SbroadError::VdbeError(s) => {
                 s.clone()
             }
@@ -24,12 +24,16 @@
 //! 6. Executes the final IR top subtree and returns the final result to the user.
 use crate::errors::{Action, Entity, SbroadError};
 use crate::executor::bucket::Buckets;
+use crate::executor::engine::helpers::generate_series;
 use crate::executor::engine::{Router, Vshard};
-use crate::executor::ir::ExecutionPlan;
+use crate::executor::ir::{ExecutionPlan, MultisetOp, RecursiveCteMotions};
 use crate::executor::vdbe::ExecutionInsight;
+use crate::executor::vtable::{VTableColumn, VTableTuple, VirtualTable};
+use crate::frontend::sql::ir::SubtreeCloner;
 use crate::ir::node::block::{BlockOwned, MutBlock};
 use crate::ir::node::relational::{MutRelational, Relational};
 use crate::ir::node::{AnonymousBlock, Motion, NodeId};
+use crate::ir::relation::ColumnRole;
 use crate::ir::transformation::redistribution::MotionPolicy;
 use crate::ir::tree::traversal::{PostOrder, REL_CAPACITY};
 use crate::ir::value::Value;
@@ -37,7 +41,7 @@ use crate::ir::{Plan, Slices};
 use crate::BoundStatement;
 use rmp::encode::write_str;
 use smol_str::{format_smolstr, SmolStr};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
 use std::rc::Rc;
 use tarantool::msgpack;
@@ -231,6 +235,10 @@ pub trait Port<'p>: Write {
     fn set_type(&mut self, port_type: PortType);
 
     fn size(&self) -> u32;
+
+    /// Attach a warning about the result for the client. It's dropped
+    /// by the ports which can't deliver it.
+    fn add_warning(&mut self, _message: String) {}
 }
 
 /// Query to execute.
@@ -300,9 +308,47 @@ where
     }
 
     pub fn materialize_subtree<'p>(
+        &mut self,
+        slices: Slices,
+        port: Option<&mut impl Port<'p>>,
+    ) -> Result<(), SbroadError> {
+        // EXPLAIN(RAW) is executed on the storages as is, without iterations
+        // over the recursive CTEs.
+        let recursive_ctes = if self.exec_plan.get_ir_plan().is_raw_explain() {
+            HashMap::new()
+        } else {
+            self.exec_plan.get_recursive_ctes()?
+        };
+        self.materialize_slices(slices, port, &recursive_ctes)
+    }
+
+    /// Expand `GENERATE_SERIES` under the motion into a virtual table.
+    /// Returns `None` if the motion child is not a series.
+    fn materialize_series(&self, motion_id: NodeId) -> Result<Option<VirtualTable>, SbroadError> {
+        let ir = self.exec_plan.get_ir_plan();
+        let Relational::Motion(Motion {
+            child: Some(child_id),
+            alias,
+            ..
+        }) = ir.get_relation_node(motion_id)?
+        else {
+            return Ok(None);
+        };
+        let Some(args) = generate_series::series_args(ir, *child_id)? else {
+            return Ok(None);
+        };
+        let mut virtual_table = generate_series::materialize(ir, *child_id, args)?;
+        if let Some(name) = alias {
+            virtual_table.set_alias(name.as_str());
+        }
+        Ok(Some(virtual_table))
+    }
+
+    fn materialize_slices<'p>(
         &mut self,
         slices: Slices,
         mut port: Option<&mut impl Port<'p>>,
+        recursive_ctes: &HashMap<NodeId, RecursiveCteMotions>,
     ) -> Result<(), SbroadError> {
         let tier = self.exec_plan.get_ir_plan().tier.as_ref();
         // all tables from one tier, so we can use corresponding vshard object
@@ -314,6 +360,36 @@ where
                 if self.exec_plan.get_vtables().contains_key(motion_id) {
                     continue;
                 }
+                // Recursive term is materialized on every iteration of its CTE.
+                if recursive_ctes
+                    .values()
+                    .any(|cte| cte.term_motions.contains(motion_id))
+                {
+                    continue;
+                }
+                if let Some(cte) = recursive_ctes.get(motion_id) {
+                    let virtual_table =
+                        self.materialize_recursive_cte(*motion_id, cte, port.as_deref_mut())?;
+                    self.exec_plan
+                        .set_motion_vtable(motion_id, virtual_table, &vshard)?;
+                    self.get_mut_exec_plan().unlink_motion_subtree(*motion_id)?;
+                    continue;
+                }
+                if !self.exec_plan.get_ir_plan().is_raw_explain() {
+                    if let Some(op) = self.exec_plan.get_multiset_op(*motion_id)? {
+                        let virtual_table = self.materialize_multiset_op(*motion_id, &op)?;
+                        self.exec_plan
+                            .set_motion_vtable(motion_id, virtual_table, &vshard)?;
+                        self.get_mut_exec_plan().unlink_motion_subtree(*motion_id)?;
+                        continue;
+                    }
+                }
+                if let Some(virtual_table) = self.materialize_series(*motion_id)? {
+                    self.exec_plan
+                        .set_motion_vtable(motion_id, virtual_table, &vshard)?;
+                    self.get_mut_exec_plan().unlink_motion_subtree(*motion_id)?;
+                    continue;
+                }
                 let motion = self.exec_plan.get_ir_plan().get_relation_node(*motion_id)?;
                 if let Relational::Motion(Motion { policy, .. }) = motion {
                     match policy {
@@ -332,9 +408,10 @@ where
                                 .get_relation_node(motion_child_id)?;
 
                             if matches!(motion_child, Relational::Values { .. }) {
-                                let virtual_table = self
+                                let mut virtual_table = self
                                     .coordinator
                                     .materialize_values(&mut self.exec_plan, motion_child_id)?;
+                                self.fill_insert_identity(*motion_id, &mut virtual_table)?;
                                 self.exec_plan.set_motion_vtable(
                                     motion_id,
                                     virtual_table,
@@ -362,6 +439,7 @@ where
                 let top_id = self.exec_plan.get_motion_subtree_root(*motion_id)?;
 
                 let buckets = self.bucket_discovery(top_id)?;
+                self.check_buckets_max(&buckets, vshard.bucket_count())?;
                 let mut virtual_table = self.coordinator.materialize_motion(
                     &mut self.exec_plan,
                     motion_id,
@@ -380,6 +458,7 @@ where
                     }
                 }
 
+                self.fill_insert_identity(*motion_id, &mut virtual_table)?;
                 self.exec_plan
                     .set_motion_vtable(motion_id, virtual_table, &vshard)?;
             }
@@ -388,6 +467,205 @@ where
         Ok(())
     }
 
+    /// Evaluate the recursive CTE on the router. The rows of the non-recursive term
+    /// form the initial working table. On every iteration the recursive term is
+    /// executed over the working table and its new rows replace the working table.
+    /// The iterations stop when no new rows are produced.
+    ///
+    /// # Errors
+    /// - the number of iterations exceeds `sql_recursion_max` option
+    /// - the number of rows exceeds `sql_motion_row_max` option
+    /// - failed to materialize the recursive term
+    fn materialize_recursive_cte<'p>(
+        &mut self,
+        motion_id: NodeId,
+        cte: &RecursiveCteMotions,
+        mut port: Option<&mut impl Port<'p>>,
+    ) -> Result<VirtualTable, SbroadError> {
+        let tier = self.exec_plan.get_ir_plan().tier.as_ref();
+        let vshard = self.coordinator.get_vshard_object_by_tier(tier)?;
+        let options = &self.exec_plan.get_ir_plan().effective_options;
+        let (max_iterations, max_rows) = (options.sql_recursion_max, options.sql_motion_row_max);
+
+        let base = self.exec_plan.get_motion_vtable(cte.working_table)?;
+        let new_working_table = || {
+            let mut vtable = VirtualTable::with_columns(base.get_columns().to_vec());
+            if let Some(alias) = base.get_alias() {
+                vtable.set_alias(alias);
+            }
+            vtable
+        };
+        let mut result = VirtualTable::clone(&base);
+        let mut seen: HashSet<VTableTuple> = HashSet::new();
+        if cte.remove_duplicates {
+            result.remove_duplicates();
+            seen.extend(result.get_tuples().iter().cloned());
+        }
+        let mut working_table = new_working_table();
+        *working_table.get_mut_tuples() = result.get_tuples().to_vec();
+
+        let mut iterations = 0;
+        while !working_table.get_tuples().is_empty() {
+            if max_iterations > 0 && iterations >= max_iterations {
+                return Err(SbroadError::Other(format_smolstr!(
+                    "Exceeded maximum number of iterations ({max_iterations}) of the recursive CTE"
+                )));
+            }
+            iterations += 1;
+
+            // Materialization consumes the plan subtree, so every iteration
+            // works with its own copy of the recursive term.
+            let (term_id, cloned) = SubtreeCloner::clone_subtree_with_map(
+                self.exec_plan.get_mut_ir_plan(),
+                cte.recursive_term,
+            )?;
+            let Some(working_table_id) = cloned.get(&cte.working_table) else {
+                return Err(SbroadError::NotFound(
+                    Entity::Node,
+                    format_smolstr!("working table of the recursive CTE under {motion_id:?}"),
+                ));
+            };
+            self.exec_plan
+                .set_motion_vtable(working_table_id, working_table, &vshard)?;
+            let slices = self.exec_plan.get_ir_plan().calculate_slices(term_id)?;
+            self.materialize_slices(slices.into(), port.as_deref_mut(), &HashMap::new())?;
+
+            let vtables = self.exec_plan.get_mut_vtables();
+            let Some(term) = vtables.remove(&term_id) else {
+                return Err(SbroadError::NotFound(
+                    Entity::VirtualTable,
+                    format_smolstr!("for recursive term motion {term_id:?}"),
+                ));
+            };
+            for id in cloned.values() {
+                vtables.remove(id);
+            }
+            let mut term = Rc::unwrap_or_clone(term);
+            let mut tuples = std::mem::take(term.get_mut_tuples());
+            if cte.remove_duplicates {
+                tuples.retain(|tuple| seen.insert(tuple.clone()));
+            }
+            let rows = result.get_tuples().len() + tuples.len();
+            if max_rows > 0 && rows as i64 > max_rows {
+                return Err(SbroadError::UnexpectedNumberOfValues(format_smolstr!(
+                    "Exceeded maximum number of rows ({max_rows}) in virtual table: {rows}"
+                )));
+            }
+            result.get_mut_tuples().extend(tuples.iter().cloned());
+            working_table = new_working_table();
+            *working_table.get_mut_tuples() = tuples;
+        }
+
+        let plan = self.exec_plan.get_ir_plan();
+        if let Relational::Motion(Motion {
+            alias: Some(alias), ..
+        }) = plan.get_relation_node(motion_id)?
+        {
+            result.set_alias(alias.as_str());
+        }
+        Ok(result)
+    }
+
+    /// Combine the virtual tables of `INTERSECT ALL` or `EXCEPT ALL` children
+    /// on the router, as storages don't support these operations.
+    ///
+    /// # Errors
+    /// - virtual tables of the children are not materialized
+    fn materialize_multiset_op(
+        &mut self,
+        motion_id: NodeId,
+        op: &MultisetOp,
+    ) -> Result<VirtualTable, SbroadError> {
+        let vtables = self.exec_plan.get_mut_vtables();
+        let (Some(left), Some(right)) = (vtables.remove(&op.left), vtables.remove(&op.right))
+        else {
+            return Err(SbroadError::NotFound(
+                Entity::VirtualTable,
+                format_smolstr!("for children of multiset operation under {motion_id:?}"),
+            ));
+        };
+        let mut result = Rc::unwrap_or_clone(left);
+        if op.is_intersect {
+            result.intersect_all(&right);
+        } else {
+            result.except_all(&right);
+        }
+        // Bucket index of the left child is meaningless for the combined rows.
+        result.get_mut_bucket_index().clear();
+
+        let plan = self.exec_plan.get_ir_plan();
+        if let Relational::Motion(Motion {
+            alias: Some(alias), ..
+        }) = plan.get_relation_node(motion_id)?
+        {
+            result.set_alias(alias.as_str());
+        }
+        Ok(result)
+    }
+
+    /// Append the values of the identity column omitted in `INSERT`
+    /// to the virtual table of its child motion. The values are allocated
+    /// from the cluster sequence by the coordinator. It must be done before
+    /// resharding, as the identity column may be a part of the sharding key.
+    fn fill_insert_identity(
+        &self,
+        motion_id: NodeId,
+        vtable: &mut VirtualTable,
+    ) -> Result<(), SbroadError> {
+        let plan = self.exec_plan.get_ir_plan();
+        let top_id = plan.get_top()?;
+        if !matches!(plan.get_relation_node(top_id)?, Relational::Insert(_))
+            || plan.dml_child_id(top_id)? != motion_id
+        {
+            return Ok(());
+        }
+        let Some(pos) = plan.insert_omitted_identity(top_id)? else {
+            return Ok(());
+        };
+        let table = plan.dml_node_table(top_id)?;
+        let column = table.columns.get(pos).ok_or_else(|| {
+            SbroadError::NotFound(
+                Entity::Column,
+                format_smolstr!("(identity) at position {pos} for Table {}", table.name),
+            )
+        })?;
+        let values = self
+            .coordinator
+            .next_identity_values(table, vtable.get_tuples().len())?;
+        vtable.add_column(VTableColumn {
+            r#type: column.r#type,
+            role: ColumnRole::User,
+            is_nullable: false,
+        });
+        for (tuple, value) in vtable.get_mut_tuples().iter_mut().zip(values) {
+            tuple.push(Value::from(value));
+        }
+        Ok(())
+    }
+
+    /// Check that a single dispatch doesn't touch more buckets than
+    /// `sql_max_buckets_per_query` option allows.
+    ///
+    /// # Errors
+    /// - the number of buckets exceeds `sql_max_buckets_per_query` option
+    fn check_buckets_max(&self, buckets: &Buckets, bucket_count: u64) -> Result<(), SbroadError> {
+        let options = &self.exec_plan.get_ir_plan().effective_options;
+        let max_buckets = options.sql_max_buckets_per_query as u64;
+        let count = match buckets {
+            Buckets::All => bucket_count,
+            Buckets::Filtered(buckets) => buckets.len() as u64,
+            Buckets::Any => return Ok(()),
+        };
+        if max_buckets > 0 && count > max_buckets {
+            return Err(SbroadError::UnexpectedNumberOfValues(format_smolstr!(
+                "Exceeded maximum number of buckets per query ({max_buckets}): \
+                query touches {count} buckets, filter it by the sharding key \
+                or increase sql_max_buckets_per_query option"
+            )));
+        }
+        Ok(())
+    }
+
     /// Builds explain from current query
     ///
     /// # Errors
@@ -489,6 +767,12 @@ where
             self.exec_plan.set_plan_id(node_id)?;
         }
         let buckets = self.bucket_discovery(top_id)?;
+        let tier = self.exec_plan.get_ir_plan().tier.as_ref();
+        let bucket_count = self
+            .coordinator
+            .get_vshard_object_by_tier(tier)?
+            .bucket_count();
+        self.check_buckets_max(&buckets, bucket_count)?;
         self.coordinator
             .dispatch(&mut self.exec_plan, top_id, &buckets, port)?;
 
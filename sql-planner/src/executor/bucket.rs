@@ -20,6 +20,7 @@ use crate::ir::transformation::redistribution::MotionPolicy;
 use crate::ir::tree::traversal::{LevelNode, PostOrderWithFilter, REL_CAPACITY};
 use crate::ir::tree::Snapshot;
 use crate::ir::value::Value;
+use serde::Serialize;
 use smallvec::SmallVec;
 use std::fmt::Display;
 
@@ -114,6 +115,15 @@ impl Display for Buckets {
     }
 }
 
+/// Estimated dispatch fan-out of a query.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct FanOut {
+    /// Number of buckets the query is sent to.
+    pub buckets: u64,
+    /// Number of storages (replicasets) the query is sent to.
+    pub storages: u64,
+}
+
 impl<T> ExecutingQuery<'_, T>
 where
     T: Router,
@@ -131,12 +141,13 @@ where
         let tier = ir_plan.tier.as_ref();
         let expr = ir_plan.get_expression_node(expr_id)?;
 
-        // Try to collect buckets from expression of type `sharding_key = value`
+        // Try to collect buckets from expression of type `sharding_key = value`.
+        // Collated equality may match strings with different sharding keys.
         if let Expression::Bool(BoolExpr {
             op: op @ (Bool::Eq | Bool::In),
             left,
             right,
-            ..
+            collation: None,
         }) = expr
         {
             let pairs = [(*left, *right), (*right, *left)];
@@ -397,14 +408,20 @@ where
                         self.bucket_map.insert(*output, Buckets::Any);
                     }
                     MotionPolicy::Segment(_) => {
-                        let virtual_table = self.exec_plan.get_motion_vtable(node_id)?;
-                        let buckets = virtual_table
-                            .get_bucket_index()
-                            .keys()
-                            .copied()
-                            .collect::<HashSet<u64, RepeatableState>>();
-                        self.bucket_map
-                            .insert(*output, Buckets::new_filtered(buckets));
+                        if self.exec_plan.contains_vtable_for_motion(node_id) {
+                            let virtual_table = self.exec_plan.get_motion_vtable(node_id)?;
+                            let buckets = virtual_table
+                                .get_bucket_index()
+                                .keys()
+                                .copied()
+                                .collect::<HashSet<u64, RepeatableState>>();
+                            self.bucket_map
+                                .insert(*output, Buckets::new_filtered(buckets));
+                        } else {
+                            // The motion is not materialized yet (e.g. we only estimate
+                            // the fan-out), so the data may be redistributed anywhere.
+                            self.bucket_map.insert(*output, Buckets::All);
+                        }
                     }
                     MotionPolicy::Local => {
                         let child_id = ir_plan.get_first_rel_child(node_id)?;
@@ -700,6 +717,71 @@ where
 
         Ok(top_buckets)
     }
+
+    /// Estimate how many buckets and storages the query would be dispatched to
+    /// without executing it. Motion subtrees are dispatched separately, so their
+    /// buckets are added to the buckets of the top subtree. A sharding predicate
+    /// that can't be pruned (e.g. with an unbound parameter) results in a full
+    /// fan-out.
+    ///
+    /// # Errors
+    /// - Failed to discover buckets.
+    /// - Failed to get a vshard object for the plan tier.
+    pub fn estimate_fanout(&mut self) -> Result<FanOut, SbroadError> {
+        let ir_plan = self.exec_plan.get_ir_plan();
+        if self.is_empty() || !ir_plan.is_dql_or_dml()? {
+            return Ok(FanOut::default());
+        }
+        // The plan left unoptimized after binding is a point get.
+        if ir_plan.optimization_deferred {
+            return Ok(FanOut {
+                buckets: 1,
+                storages: 1,
+            });
+        }
+
+        let top_id = ir_plan.get_top()?;
+        let mut subtrees = vec![top_id];
+        for slice in ir_plan.slices.slices() {
+            for motion_id in slice.positions() {
+                let Relational::Motion(Motion { policy, .. }) =
+                    ir_plan.get_relation_node(*motion_id)?
+                else {
+                    continue;
+                };
+                if matches!(policy, MotionPolicy::Full | MotionPolicy::Segment(_)) {
+                    subtrees.push(ir_plan.get_first_rel_child(*motion_id)?);
+                }
+            }
+        }
+
+        // Bucket discovery caches results in the bucket map, which must not
+        // leak into the real dispatch with materialized motions.
+        let bucket_map = std::mem::take(&mut self.bucket_map);
+        let buckets = subtrees.into_iter().try_fold(Buckets::Any, |acc, id| {
+            acc.disjunct(&self.bucket_discovery(id)?)
+        });
+        self.bucket_map = bucket_map;
+        let buckets = buckets?;
+
+        let tier = self.exec_plan.get_ir_plan().tier.as_ref();
+        let vshard = self.coordinator.get_vshard_object_by_tier(tier)?;
+        let fanout = match &buckets {
+            Buckets::All => FanOut {
+                buckets: vshard.bucket_count(),
+                storages: vshard.storage_count(&buckets)?,
+            },
+            Buckets::Filtered(list) => FanOut {
+                buckets: list.len() as u64,
+                storages: vshard.storage_count(&buckets)?,
+            },
+            Buckets::Any => FanOut {
+                buckets: 0,
+                storages: 1,
+            },
+        };
+        Ok(fanout)
+    }
 }
 
 #[cfg(feature = "mock")]
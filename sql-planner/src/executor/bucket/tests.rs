@@ -2,7 +2,7 @@ use pretty_assertions::assert_eq;
 use std::collections::HashSet;
 
 use crate::collection;
-use crate::executor::bucket::Buckets;
+use crate::executor::bucket::{Buckets, FanOut};
 use crate::executor::engine::mock::RouterRuntimeMock;
 use crate::executor::engine::Vshard;
 use crate::executor::ExecutingQuery;
@@ -617,3 +617,37 @@ fn same_column_in_eq() {
 
     assert_eq!(Buckets::Filtered(collection!()), buckets);
 }
+
+#[test]
+fn fanout_point_query() {
+    let query = r#"select * from test_space where id = 1"#;
+
+    let coordinator = RouterRuntimeMock::new();
+    let mut query = ExecutingQuery::from_text_and_params(&coordinator, query, vec![]).unwrap();
+    let fanout = query.estimate_fanout().unwrap();
+
+    assert_eq!(
+        FanOut {
+            buckets: 1,
+            storages: 1
+        },
+        fanout
+    );
+}
+
+#[test]
+fn fanout_full_scan() {
+    let query = r#"select * from test_space where "sysFrom" = 1"#;
+
+    let coordinator = RouterRuntimeMock::new();
+    let mut query = ExecutingQuery::from_text_and_params(&coordinator, query, vec![]).unwrap();
+    let fanout = query.estimate_fanout().unwrap();
+
+    assert_eq!(
+        FanOut {
+            buckets: coordinator.bucket_count(),
+            storages: 2
+        },
+        fanout
+    );
+}
@@ -20,6 +20,7 @@ use std::sync::OnceLock;
 
 use crate::errors::SbroadError;
 use crate::executor::bucket::Buckets;
+use crate::executor::engine::helpers::IdentifierCase;
 use crate::executor::ir::ExecutionPlan;
 use crate::executor::protocol::SchemaInfo;
 use crate::executor::vtable::VirtualTable;
@@ -27,7 +28,7 @@ use crate::ir::function::Function;
 use crate::ir::relation::Table;
 use crate::ir::types::UnrestrictedType;
 use crate::ir::value::Value;
-use crate::ir::ExplainType;
+use crate::ir::{ExplainType, DEFAULT_MAX_EXPRESSION_DEPTH};
 
 use super::preemption::SchedulerOptions;
 use super::Port;
@@ -87,6 +88,23 @@ pub trait Metadata: Sized {
     /// # Errors
     /// - Metadata does not contain space
     fn sharding_positions_by_space(&self, space: &str) -> Result<Vec<usize>, SbroadError>;
+
+    /// Get the policy of normalizing unquoted identifiers of tables and columns.
+    fn identifier_case(&self) -> IdentifierCase {
+        IdentifierCase::default()
+    }
+
+    /// Get the maximum nesting depth of the expressions in the query.
+    fn max_expression_depth(&self) -> usize {
+        DEFAULT_MAX_EXPRESSION_DEPTH
+    }
+
+    /// Get the estimated number of rows in the table (across the whole cluster).
+    /// Used only for the cost estimation in `EXPLAIN (COSTS)`, `None` means
+    /// that the statistics for the table is unavailable.
+    fn table_row_count(&self, _table_name: &str) -> Option<u64> {
+        None
+    }
 }
 
 pub fn get_builtin_functions() -> &'static [Function] {
@@ -108,11 +126,26 @@ pub fn get_builtin_functions() -> &'static [Function] {
                 DerivedType::new(UnrestrictedType::Datetime),
                 false,
             ),
+            Function::new_stable(
+                "date_trunc".into(),
+                DerivedType::new(UnrestrictedType::Datetime),
+                false,
+            ),
             Function::new_stable(
                 "to_char".into(),
                 DerivedType::new(UnrestrictedType::String),
                 false,
             ),
+            Function::new_stable(
+                "_pico_to_char_number".into(),
+                DerivedType::new(UnrestrictedType::String),
+                false,
+            ),
+            Function::new_stable(
+                "to_number".into(),
+                DerivedType::new(UnrestrictedType::Decimal),
+                false,
+            ),
             Function::new_stable(
                 "substring".into(),
                 DerivedType::new(UnrestrictedType::String),
@@ -134,6 +167,16 @@ pub fn get_builtin_functions() -> &'static [Function] {
                 DerivedType::new(UnrestrictedType::String),
                 true,
             ),
+            Function::new_stable(
+                "length".into(),
+                DerivedType::new(UnrestrictedType::Integer),
+                true,
+            ),
+            Function::new_stable(
+                "char_length".into(),
+                DerivedType::new(UnrestrictedType::Integer),
+                true,
+            ),
             Function::new_stable(
                 "coalesce".into(),
                 DerivedType::new(UnrestrictedType::Any),
@@ -144,11 +187,146 @@ pub fn get_builtin_functions() -> &'static [Function] {
                 DerivedType::new(UnrestrictedType::Any), // any numeric type
                 true,
             ),
+            Function::new_stable(
+                "ceil".into(),
+                DerivedType::new(UnrestrictedType::Any), // any numeric type
+                false,
+            ),
+            Function::new_stable(
+                "floor".into(),
+                DerivedType::new(UnrestrictedType::Any), // any numeric type
+                false,
+            ),
+            Function::new_stable(
+                "round".into(),
+                DerivedType::new(UnrestrictedType::Any), // any numeric type
+                false,
+            ),
+            Function::new_stable(
+                "_pico_ceil_double".into(),
+                DerivedType::new(UnrestrictedType::Double),
+                false,
+            ),
+            Function::new_stable(
+                "_pico_floor_double".into(),
+                DerivedType::new(UnrestrictedType::Double),
+                false,
+            ),
+            Function::new_stable(
+                "_pico_round_double".into(),
+                DerivedType::new(UnrestrictedType::Double),
+                false,
+            ),
+            Function::new_stable(
+                "sign".into(),
+                DerivedType::new(UnrestrictedType::Integer),
+                false,
+            ),
+            Function::new_stable(
+                "octet_length".into(),
+                DerivedType::new(UnrestrictedType::Integer),
+                false,
+            ),
+            Function::new_stable(
+                "md5".into(),
+                DerivedType::new(UnrestrictedType::String),
+                false,
+            ),
+            Function::new_stable(
+                "sha256".into(),
+                DerivedType::new(UnrestrictedType::String),
+                false,
+            ),
+            Function::new_stable(
+                "_pico_sha256_bytea".into(),
+                DerivedType::new(UnrestrictedType::Bytea),
+                false,
+            ),
+            Function::new_stable(
+                "ascii".into(),
+                DerivedType::new(UnrestrictedType::Integer),
+                false,
+            ),
+            Function::new_stable(
+                "chr".into(),
+                DerivedType::new(UnrestrictedType::String),
+                false,
+            ),
+            Function::new_stable(
+                "lpad".into(),
+                DerivedType::new(UnrestrictedType::String),
+                false,
+            ),
+            Function::new_stable(
+                "rpad".into(),
+                DerivedType::new(UnrestrictedType::String),
+                false,
+            ),
+            Function::new_stable(
+                "repeat".into(),
+                DerivedType::new(UnrestrictedType::String),
+                false,
+            ),
+            Function::new_stable(
+                "reverse".into(),
+                DerivedType::new(UnrestrictedType::String),
+                false,
+            ),
+            Function::new_stable(
+                "split_part".into(),
+                DerivedType::new(UnrestrictedType::String),
+                false,
+            ),
+            Function::new_stable(
+                "string_to_array".into(),
+                DerivedType::new(UnrestrictedType::Array),
+                false,
+            ),
+            Function::new_stable(
+                "array_to_string".into(),
+                DerivedType::new(UnrestrictedType::String),
+                false,
+            ),
+            Function::new_stable(
+                "percentile_cont".into(),
+                DerivedType::new(UnrestrictedType::Decimal),
+                false,
+            ),
+            Function::new_stable(
+                "percentile_disc".into(),
+                DerivedType::new(UnrestrictedType::String),
+                false,
+            ),
             Function::new_stable(
                 "json_extract_path".into(),
                 DerivedType::new(UnrestrictedType::Any),
                 false,
             ),
+            Function::new_stable(
+                "json_extract_path_text".into(),
+                DerivedType::new(UnrestrictedType::String),
+                false,
+            ),
+            Function::new_stable(
+                "_pico_json_parse".into(),
+                DerivedType::new(UnrestrictedType::Map),
+                false,
+            ),
+            Function::new_stable(
+                "_pico_is_json".into(),
+                DerivedType::new(UnrestrictedType::Boolean),
+                false,
+            ),
+            Function::new_stable(
+                "_pico_json_contains".into(),
+                DerivedType::new(UnrestrictedType::Boolean),
+                false,
+            ),
+            Function::new_stable(
+                "jsonb_path_query".into(),
+                DerivedType::new(UnrestrictedType::Any),
+                false,
+            ),
             // volatile functions
             Function::new_volatile(
                 "_pico_bucket".into(),
@@ -432,6 +610,13 @@ pub trait Router: QueryCache {
         self.get_vshard_object_by_tier(self.get_current_tier_name()?.as_ref())
     }
 
+    /// `true` if the runtime serves single-row primary key reads with
+    /// a point get (see [`crate::ir::Plan::point_get`]). Optimization
+    /// of such plans is deferred until the parameters are bound.
+    fn serves_point_gets(&self) -> bool {
+        false
+    }
+
     /// Materialize values (on router).
     /// We have two scenarios of vtable materialization:
     /// 1.) In case we're working with VALUES containing **only** constants, we copy them
@@ -447,6 +632,13 @@ pub trait Router: QueryCache {
         values_id: NodeId,
     ) -> Result<VirtualTable, SbroadError>;
 
+    /// Allocate the next `count` values of the table identity column
+    /// from the cluster sequence.
+    ///
+    /// # Errors
+    /// - Failed to reserve a range of the sequence values.
+    fn next_identity_values(&self, table: &Table, count: usize) -> Result<Vec<i64>, SbroadError>;
+
     /// Determines whether audit logging should be performed for the given query plan.
     ///
     /// This function evaluates the query plan against configured audit policies
@@ -510,6 +702,12 @@ pub trait Vshard {
     /// Get a random bucket from the cluster.
     fn get_random_bucket(&self) -> Buckets;
 
+    /// Get the number of storages (replicasets) owning the given buckets.
+    ///
+    /// # Errors
+    /// - Failed to map buckets to replicasets.
+    fn storage_count(&self, buckets: &Buckets) -> Result<u64, SbroadError>;
+
     /// Determine shard for query execution by sharding key value
     ///
     /// # Errors
@@ -16,14 +16,17 @@ use crate::{
     executor::vtable::vtable_indexed_column_name,
     ir::{
         node::{
-            expression::Expression, relational::Relational, Alias, Constant, Limit, Motion, NodeId,
+            expression::Expression, relational::Relational, Constant, Limit, Motion, NodeId,
             Update, Values, ValuesRow,
         },
         types::DerivedType,
     },
 };
 use smol_str::{format_smolstr, SmolStr, ToSmolStr};
-use std::{any::Any, cmp::Ordering, collections::HashMap, rc::Rc, sync::OnceLock};
+use std::{
+    any::Any, cmp::Ordering, collections::HashMap, fmt::Display, rc::Rc, str::FromStr,
+    sync::OnceLock,
+};
 
 use super::{BlockExecData, Metadata, Router, Vshard};
 use crate::executor::Port;
@@ -52,8 +55,56 @@ use tarantool::msgpack::{decode_from_read, Context, Decode, DecodeError};
 use tarantool::msgpack::{Encode, EncodeError};
 use tarantool::tuple::Tuple;
 
+pub mod generate_series;
+pub mod trace;
 pub mod vshard;
 
+/// Policy of normalizing unquoted SQL identifiers.
+/// Quoted identifiers always keep their case.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum IdentifierCase {
+    /// Unquoted identifiers are folded to lowercase (like in PostgreSQL).
+    #[default]
+    Fold = 0,
+    /// Unquoted identifiers keep their case.
+    Preserve = 1,
+}
+
+impl Display for IdentifierCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            IdentifierCase::Fold => "fold",
+            IdentifierCase::Preserve => "preserve",
+        };
+        write!(f, "{value}")
+    }
+}
+
+impl FromStr for IdentifierCase {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "fold" => Ok(IdentifierCase::Fold),
+            "preserve" => Ok(IdentifierCase::Preserve),
+            _ => Err(()),
+        }
+    }
+}
+
+impl TryFrom<u8> for IdentifierCase {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(IdentifierCase::Fold),
+            1 => Ok(IdentifierCase::Preserve),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Transform:
 ///
 /// ```text
@@ -62,10 +113,24 @@ pub mod vshard;
 /// ```
 #[must_use]
 pub fn normalize_name_from_sql(s: &str) -> SmolStr {
+    normalize_name(s, IdentifierCase::Fold)
+}
+
+/// Transform with the given policy for unquoted identifiers:
+///
+/// ```text
+/// * "AbC" -> AbC (same cased, unquoted)
+/// * AbC   -> abc (Fold) or AbC (Preserve)
+/// ```
+#[must_use]
+pub fn normalize_name(s: &str, case: IdentifierCase) -> SmolStr {
     if let (Some('"'), Some('"')) = (s.chars().next(), s.chars().last()) {
         return SmolStr::from(&s[1..s.len() - 1]);
     }
-    SmolStr::new(s.to_lowercase())
+    match case {
+        IdentifierCase::Fold => SmolStr::new(s.to_lowercase()),
+        IdentifierCase::Preserve => SmolStr::new(s),
+    }
 }
 
 /// Transform:
@@ -328,15 +393,19 @@ pub fn init_insert_tuple_builder(
         .map(|(pos, id)| (*id, pos))
         .collect::<AHashMap<_, _>>();
     let relation = plan.dml_node_table(insert_id)?;
+    let identity = plan.insert_omitted_identity(insert_id)?;
     let mut commands = Vec::with_capacity(relation.columns.len());
     for (pos, table_col) in relation.columns.iter().enumerate() {
+        // Generated identity values follow the inserted columns in the tuple.
+        let tuple_pos = if identity == Some(pos) {
+            Some(insert_columns.len())
+        } else {
+            columns_map.get(&pos).copied()
+        };
         if table_col.role == ColumnRole::Sharding {
             let motion_key = plan.insert_motion_key(insert_id)?;
             commands.push(TupleBuilderCommand::CalculateBucketId(motion_key));
-        } else if columns_map.contains_key(&pos) {
-            // It is safe to unwrap here because we have checked that
-            // the column is present in the tuple.
-            let tuple_pos = columns_map[&pos];
+        } else if let Some(tuple_pos) = tuple_pos {
             let vtable_type = &columns
                 .get(tuple_pos)
                 .ok_or_else(|| {
@@ -354,8 +423,7 @@ pub fn init_insert_tuple_builder(
                 ));
             }
         } else {
-            // FIXME: support default values other then NULL (issue #442).
-            commands.push(TupleBuilderCommand::SetValue(Column::default_value()));
+            commands.push(TupleBuilderCommand::SetValue(relation.column_default(pos)));
         }
     }
     Ok(commands)
@@ -515,6 +583,59 @@ pub fn write_insert_args<'t>(
     Ok(())
 }
 
+/// Validate the tuples of the virtual table that are going to be written
+/// by the DML node against the NOT NULL and CHECK constraints of the table.
+///
+/// `INSERT` and sharded `UPDATE` (which moves the row to another bucket,
+/// so the tuple is rewritten as a whole) validate every column of the tuple.
+/// Local `UPDATE` validates only the columns from its `SET` clause.
+///
+/// # Errors
+/// - Invalid DML node or virtual table
+/// - Constraint violation
+pub fn check_dml_constraints(
+    plan: &Plan,
+    dml_id: NodeId,
+    vtable: &VirtualTable,
+) -> Result<(), SbroadError> {
+    let table = plan.dml_node_table(dml_id)?;
+    if table.checks.is_empty() && table.columns.iter().all(|col| col.is_nullable) {
+        return Ok(());
+    }
+    // Pairs of (position in table, position in virtual table).
+    let positions: Vec<(usize, usize)> = match plan.get_relation_node(dml_id)? {
+        Relational::Insert(_) => plan
+            .insert_columns(dml_id)?
+            .iter()
+            .enumerate()
+            .map(|(tuple_pos, table_pos)| (*table_pos, tuple_pos))
+            .collect(),
+        Relational::Update(Update {
+            update_columns_map, ..
+        }) => update_columns_map
+            .iter()
+            .map(|(table_pos, tuple_pos)| (*table_pos, *tuple_pos))
+            .collect(),
+        _ => return Ok(()),
+    };
+    for vt_tuple in vtable.get_tuples() {
+        let mut row = Vec::with_capacity(positions.len());
+        for (table_pos, tuple_pos) in &positions {
+            let value = vt_tuple.get(*tuple_pos).ok_or_else(|| {
+                SbroadError::Invalid(
+                    Entity::Tuple,
+                    Some(format_smolstr!(
+                        "column at position {tuple_pos} not found in virtual table"
+                    )),
+                )
+            })?;
+            row.push((*table_pos, value));
+        }
+        table.validate_row(row)?;
+    }
+    Ok(())
+}
+
 /// Create commands to build the tuple for sharded `Update`,
 ///
 /// # Errors
@@ -680,7 +801,7 @@ fn has_zero_limit_clause(plan: &ExecutionPlan) -> Result<bool, SbroadError> {
     let ir = plan.get_ir_plan();
     let top_id = ir.get_top()?;
     if let Relational::Limit(Limit { limit, .. }) = ir.get_relation_node(top_id)? {
-        return Ok(*limit == 0);
+        return Ok(*limit == Some(0));
     }
     Ok(false)
 }
@@ -927,7 +1048,25 @@ pub fn dispatch_impl<'p>(
         );
     }
 
+    let is_final_stage = top_id == plan.get_ir_plan().get_top()?;
     let mut sub_plan = plan.take_subtree(top_id, buckets)?;
+    // The rows lost with a failed storage are tolerable only in the final
+    // result of a single stage query: a partial motion would silently
+    // corrupt the joins and aggregates over it.
+    if !is_final_stage || !sub_plan.vtables_empty() {
+        sub_plan
+            .get_mut_ir_plan()
+            .effective_options
+            .sql_partial_results = false;
+    }
+    // The result size limit is for the final result, motions are limited
+    // by `sql_motion_row_max`.
+    if !is_final_stage {
+        sub_plan
+            .get_mut_ir_plan()
+            .effective_options
+            .sql_result_row_max = 0;
+    }
 
     let tier = {
         match sub_plan.get_ir_plan().tier.as_ref() {
@@ -1481,24 +1620,12 @@ pub fn try_get_metadata_from_plan(
     }
 
     // Get metadata (column types) from the top node's output tuple.
-    let ir = plan.get_ir_plan();
-    let top_id = ir.get_top()?;
-    let top_output_id = ir.get_relation_node(top_id)?.output();
-    let columns = ir.get_row_list(top_output_id)?;
-    let mut metadata = Vec::with_capacity(columns.len());
-    for col_id in columns {
-        let column = ir.get_expression_node(*col_id)?;
-        let column_type = column.calculate_type(ir)?.to_string();
-        let column_name = if let Expression::Alias(Alias { name, .. }) = column {
-            name.to_string()
-        } else {
-            return Err(SbroadError::Invalid(
-                Entity::Expression,
-                Some(smol_str::format_smolstr!("expected alias, got {column:?}")),
-            ));
-        };
-        metadata.push(MetadataColumn::new(column_name, column_type));
-    }
+    let metadata = plan
+        .get_ir_plan()
+        .output_columns_with_types()?
+        .into_iter()
+        .map(|(name, ty)| MetadataColumn::new(name.to_string(), ty.to_string()))
+        .collect();
     Ok(Some(metadata))
 }
 
@@ -1508,9 +1635,7 @@ fn to_mp_err(msg: SmolStr) -> SbroadError {
 }
 
 fn metadata_write<'p>(port: &mut impl Port<'p>, plan: &Plan) -> Result<(), SbroadError> {
-    let top_id = plan.get_top()?;
-    let top_output_id = plan.get_relation_node(top_id)?.output();
-    let columns = plan.get_row_list(top_output_id)?;
+    let columns = plan.output_columns_with_types()?;
     let mut mp: Vec<u8> = Vec::new();
     let len = u32::try_from(columns.len()).map_err(|e| {
         SbroadError::Invalid(
@@ -1519,16 +1644,11 @@ fn metadata_write<'p>(port: &mut impl Port<'p>, plan: &Plan) -> Result<(), Sbroa
         )
     })?;
     write_array_len(&mut mp, len).map_err(|e| to_mp_err(format_smolstr!("{e}")))?;
-    for col_id in columns {
-        let column = plan.get_expression_node(*col_id)?;
-        let col_type = column.calculate_type(plan)?.to_string();
-        let Expression::Alias(Alias { name, .. }) = column else {
-            return Err(to_mp_err("Expected column to be an alias".into()));
-        };
-
+    for (name, col_type) in columns {
+        let col_type = col_type.to_string();
         write_map_len(&mut mp, 2).map_err(|e| to_mp_err(format_smolstr!("{e}")))?;
         write_str(&mut mp, "name").map_err(|e| to_mp_err(format_smolstr!("{e}")))?;
-        write_str(&mut mp, name).map_err(|e| to_mp_err(format_smolstr!("{e}")))?;
+        write_str(&mut mp, &name).map_err(|e| to_mp_err(format_smolstr!("{e}")))?;
         write_str(&mut mp, "type").map_err(|e| to_mp_err(format_smolstr!("{e}")))?;
         write_str(&mut mp, &col_type).map_err(|e| to_mp_err(format_smolstr!("{e}")))?;
     }
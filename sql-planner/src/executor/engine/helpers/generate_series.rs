@@ -0,0 +1,308 @@
+//! `GENERATE_SERIES(start, stop[, step])` set-returning function.
+//!
+//! In the plan the series is a `VALUES` node with a single row holding
+//! the `generate_series` call. The executor expands the call into a
+//! virtual table on the router once the parameters are bound, so the
+//! arguments must be constants or parameters.
+
+use crate::errors::{Entity, SbroadError};
+use crate::executor::engine::helpers::vtable_columns;
+use crate::executor::vtable::VirtualTable;
+use crate::ir::node::expression::Expression;
+use crate::ir::node::relational::Relational;
+use crate::ir::node::{Cast, Constant, NodeId, Parameter, ScalarFunction, Values, ValuesRow};
+use crate::ir::types::{CastType, UnrestrictedType};
+use crate::ir::value::Value;
+use crate::ir::Plan;
+use smol_str::{format_smolstr, SmolStr};
+use tarantool::datetime::Datetime;
+use time::Duration;
+
+/// Name of the function. It is also used as the name of the scan
+/// and of its only column.
+pub const NAME: &str = "generate_series";
+
+/// Maximum number of elements in a series. The whole series is kept
+/// in a virtual table, so it must not grow unbounded.
+const MAX_ROWS: usize = 100_000;
+
+fn invalid(msg: impl Into<SmolStr>) -> SbroadError {
+    SbroadError::Invalid(
+        Entity::Expression,
+        Some(format_smolstr!("{NAME}: {}", msg.into())),
+    )
+}
+
+/// Type of the series elements deduced from the bounds before their
+/// types are checked: a string or datetime bound makes a datetime series.
+/// The step of a datetime series is an interval string.
+pub fn element_type(plan: &Plan, bounds: &[NodeId]) -> Result<UnrestrictedType, SbroadError> {
+    for bound_id in bounds {
+        let is_datetime = match plan.get_expression_node(*bound_id)? {
+            Expression::Constant(Constant { value }) => {
+                matches!(value, Value::String(_) | Value::Datetime(_))
+            }
+            Expression::Cast(Cast { to, .. }) => *to == CastType::Datetime,
+            Expression::Parameter(Parameter { param_type, .. }) => matches!(
+                param_type.get(),
+                Some(UnrestrictedType::String | UnrestrictedType::Datetime)
+            ),
+            _ => false,
+        };
+        if is_datetime {
+            return Ok(UnrestrictedType::Datetime);
+        }
+    }
+    Ok(UnrestrictedType::Integer)
+}
+
+/// Arguments of the `generate_series` call if the `VALUES` node is a series.
+pub fn series_args(plan: &Plan, values_id: NodeId) -> Result<Option<&[NodeId]>, SbroadError> {
+    let Relational::Values(Values { children, .. }) = plan.get_relation_node(values_id)? else {
+        return Ok(None);
+    };
+    let [row_id] = children.as_slice() else {
+        return Ok(None);
+    };
+    let Relational::ValuesRow(ValuesRow { data, .. }) = plan.get_relation_node(*row_id)? else {
+        return Ok(None);
+    };
+    let [column_id] = plan.get_row_list(*data)? else {
+        return Ok(None);
+    };
+    match plan.get_expression_node(*column_id)? {
+        Expression::ScalarFunction(ScalarFunction { name, children, .. }) if name == NAME => {
+            Ok(Some(children.as_slice()))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Expand the series of the `VALUES` node into a virtual table.
+/// Must be called after the parameters are bound.
+pub fn materialize(
+    plan: &Plan,
+    values_id: NodeId,
+    args: &[NodeId],
+) -> Result<VirtualTable, SbroadError> {
+    let mut args = args
+        .iter()
+        .map(|arg_id| argument_value(plan, *arg_id))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter();
+    let (Some(start), Some(stop)) = (args.next(), args.next()) else {
+        unreachable!("GENERATE_SERIES must have at least two arguments");
+    };
+    let series = expand(start, stop, args.next())?;
+
+    let mut vtable = VirtualTable::with_columns(vtable_columns(plan, values_id)?);
+    vtable.get_mut_tuples().reserve(series.len());
+    for value in series {
+        vtable.add_tuple(vec![value]);
+    }
+    Ok(vtable)
+}
+
+/// Evaluate an argument of the function. Only constants (possibly
+/// wrapped into casts) and bound parameters are supported.
+fn argument_value(plan: &Plan, expr_id: NodeId) -> Result<Value, SbroadError> {
+    match plan.get_expression_node(expr_id)? {
+        Expression::Constant(Constant { value }) => Ok(value.clone()),
+        Expression::Cast(Cast { child, to }) => argument_value(plan, *child)?.cast_to(*to),
+        _ => Err(invalid("arguments must be constants or parameters")),
+    }
+}
+
+/// Expand the series into the list of its elements.
+///
+/// Integer series have an optional step (1 by default). Datetime series
+/// require a step in the interval form, e.g. `'1 day'` or `'2 hours'`.
+/// A string bound is treated as a datetime.
+fn expand(start: Value, stop: Value, step: Option<Value>) -> Result<Vec<Value>, SbroadError> {
+    let is_datetime = |v: &Value| matches!(v, Value::Datetime(_) | Value::String(_));
+    let is_datetime = is_datetime(&start) || is_datetime(&stop);
+    if matches!(step, Some(Value::Null)) {
+        return Ok(Vec::new());
+    }
+
+    let series = match (start, stop) {
+        (Value::Null, _) | (_, Value::Null) => Vec::new(),
+        (Value::Integer(start), Value::Integer(stop)) => {
+            let step = match step {
+                None => 1,
+                Some(Value::Integer(step)) => step,
+                Some(value) => {
+                    return Err(invalid(format_smolstr!(
+                        "step of an integer series must be an integer, got {value}"
+                    )))
+                }
+            };
+            expand_integers(start, stop, step)?
+        }
+        (start, stop) if is_datetime => {
+            let (Value::Datetime(start), Value::Datetime(stop)) = (
+                start.cast(UnrestrictedType::Datetime)?,
+                stop.cast(UnrestrictedType::Datetime)?,
+            ) else {
+                unreachable!("non-null values are cast to datetime");
+            };
+            let step = match step {
+                Some(Value::String(step)) => parse_interval(&step)?,
+                Some(value) => {
+                    return Err(invalid(format_smolstr!(
+                        "step of a datetime series must be an interval, got {value}"
+                    )))
+                }
+                None => return Err(invalid("step is required for a datetime series")),
+            };
+            expand_datetimes(start, stop, step)?
+        }
+        (start, stop) => {
+            return Err(invalid(format_smolstr!(
+                "unsupported bounds {start} and {stop}, expected integers or datetimes"
+            )))
+        }
+    };
+    Ok(series)
+}
+
+fn expand_integers(start: i64, stop: i64, step: i64) -> Result<Vec<Value>, SbroadError> {
+    if step == 0 {
+        return Err(invalid("step size cannot equal zero"));
+    }
+    let (start, stop, step) = (i128::from(start), i128::from(stop), i128::from(step));
+    let len = if (step > 0 && start > stop) || (step < 0 && start < stop) {
+        0
+    } else {
+        (stop - start) / step + 1
+    };
+    check_len(usize::try_from(len).unwrap_or(usize::MAX))?;
+
+    let series = (0..len)
+        .map(|i| {
+            let value = i64::try_from(start + i * step).expect("series element fits into i64");
+            Value::Integer(value)
+        })
+        .collect();
+    Ok(series)
+}
+
+fn expand_datetimes(
+    start: Datetime,
+    stop: Datetime,
+    step: Duration,
+) -> Result<Vec<Value>, SbroadError> {
+    if step.is_zero() {
+        return Err(invalid("step size cannot equal zero"));
+    }
+    let (mut current, stop) = (start.into_inner(), stop.into_inner());
+    let mut series = Vec::new();
+    while (step.is_positive() && current <= stop) || (step.is_negative() && current >= stop) {
+        series.push(Value::Datetime(Datetime::from_inner(current)));
+        check_len(series.len())?;
+        let Some(next) = current.checked_add(step) else {
+            break;
+        };
+        current = next;
+    }
+    Ok(series)
+}
+
+fn check_len(len: usize) -> Result<(), SbroadError> {
+    if len > MAX_ROWS {
+        return Err(invalid(format_smolstr!(
+            "series must not contain more than {MAX_ROWS} elements"
+        )));
+    }
+    Ok(())
+}
+
+/// Parse an interval like `'1 day'`, `'-2 hours'` or `'1 week 3 days'`.
+fn parse_interval(s: &str) -> Result<Duration, SbroadError> {
+    let err = || invalid(format_smolstr!("invalid interval '{s}'"));
+
+    let mut words = s.split_whitespace();
+    let mut total = Duration::ZERO;
+    let mut is_empty = true;
+    while let Some(amount) = words.next() {
+        let amount: i64 = amount.parse().map_err(|_| err())?;
+        let unit = words.next().ok_or_else(err)?;
+        let duration = match unit.to_lowercase().as_str() {
+            "second" | "seconds" | "sec" | "secs" => Duration::seconds(amount),
+            "minute" | "minutes" | "min" | "mins" => Duration::minutes(amount),
+            "hour" | "hours" => Duration::hours(amount),
+            "day" | "days" => Duration::days(amount),
+            "week" | "weeks" => Duration::weeks(amount),
+            _ => return Err(err()),
+        };
+        total = total.checked_add(duration).ok_or_else(err)?;
+        is_empty = false;
+    }
+    if is_empty {
+        return Err(err());
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn interval() {
+        assert_eq!(parse_interval("1 day").unwrap(), Duration::days(1));
+        assert_eq!(parse_interval("-2 Hours").unwrap(), Duration::hours(-2));
+        assert_eq!(
+            parse_interval("1 week 30 minutes").unwrap(),
+            Duration::weeks(1) + Duration::minutes(30)
+        );
+        assert!(parse_interval("").is_err());
+        assert!(parse_interval("1").is_err());
+        assert!(parse_interval("1 month").is_err());
+    }
+
+    #[test]
+    fn integers() {
+        let ints = |v: &[i64]| v.iter().copied().map(Value::Integer).collect::<Vec<_>>();
+
+        assert_eq!(expand_integers(1, 5, 1).unwrap(), ints(&[1, 2, 3, 4, 5]));
+        assert_eq!(expand_integers(1, 6, 2).unwrap(), ints(&[1, 3, 5]));
+        assert_eq!(expand_integers(5, 1, -2).unwrap(), ints(&[5, 3, 1]));
+        assert_eq!(expand_integers(5, 1, 1).unwrap(), ints(&[]));
+        assert_eq!(expand_integers(1, 5, -1).unwrap(), ints(&[]));
+        assert_eq!(
+            expand_integers(i64::MAX - 1, i64::MAX, 1).unwrap(),
+            ints(&[i64::MAX - 1, i64::MAX])
+        );
+        assert!(expand_integers(1, 5, 0).is_err());
+        assert!(expand_integers(0, i64::MAX, 1).is_err());
+    }
+
+    #[test]
+    fn datetimes() {
+        let dt = |inner| Value::Datetime(Datetime::from_inner(inner));
+        let (start, stop) = (
+            dt(datetime!(2024-01-01 0:00 UTC)),
+            dt(datetime!(2024-01-02 0:00 UTC)),
+        );
+
+        let series = expand(
+            start.clone(),
+            stop.clone(),
+            Some(Value::String("12 hours".into())),
+        );
+        assert_eq!(
+            series.unwrap(),
+            vec![
+                dt(datetime!(2024-01-01 0:00 UTC)),
+                dt(datetime!(2024-01-01 12:00 UTC)),
+                dt(datetime!(2024-01-02 0:00 UTC)),
+            ]
+        );
+        assert_eq!(
+            expand(start, stop, None).unwrap_err().to_string(),
+            "invalid expression: generate_series: step is required for a datetime series"
+        );
+    }
+}
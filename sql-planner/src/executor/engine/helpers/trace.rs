@@ -0,0 +1,102 @@
+//! Debug hook reporting every sub-plan the router sends to the storages.
+//!
+//! The hook is not installed by default, so dispatch only pays for a
+//! thread-local lookup per request.
+
+use crate::errors::SbroadError;
+use crate::executor::ir::ExecutionPlan;
+use smol_str::{SmolStr, ToSmolStr};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Description of a sub-plan dispatched to a single replicaset.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DispatchTrace {
+    /// Fingerprint of the query shape the sub-plan belongs to.
+    pub query_id: Option<SmolStr>,
+    /// Replicaset the request is sent to.
+    pub replicaset: String,
+    /// Buckets the sub-plan is executed on.
+    /// Empty when the sub-plan is executed on all buckets of the replicaset.
+    pub buckets: Vec<u64>,
+    /// Type of the sub-plan top node (e.g. `Projection`, `Insert`).
+    pub top: SmolStr,
+    /// Size of the encoded request in bytes.
+    pub payload_size: usize,
+}
+
+pub type DispatchHook = Rc<dyn Fn(&DispatchTrace)>;
+
+thread_local! {
+    static DISPATCH_HOOK: RefCell<Option<DispatchHook>> = const { RefCell::new(None) };
+}
+
+/// Install a hook called for every dispatched sub-plan.
+/// `None` removes the previously installed hook.
+pub fn set_dispatch_hook(hook: Option<DispatchHook>) {
+    DISPATCH_HOOK.with(|h| *h.borrow_mut() = hook);
+}
+
+/// Start a trace of the sub-plan sent to the replicaset.
+/// Returns `None` when no hook is installed.
+///
+/// # Errors
+/// - sub-plan doesn't have a relational top node
+pub fn start_trace(
+    replicaset: &str,
+    buckets: &[u64],
+    sub_plan: &ExecutionPlan,
+) -> Result<Option<DispatchTrace>, SbroadError> {
+    if DISPATCH_HOOK.with(|h| h.borrow().is_none()) {
+        return Ok(None);
+    }
+    let ir = sub_plan.get_ir_plan();
+    let top = ir.get_relation_node(ir.get_top()?)?.name().to_smolstr();
+    Ok(Some(DispatchTrace {
+        query_id: ir.query_fingerprint.clone(),
+        replicaset: replicaset.to_string(),
+        buckets: buckets.to_vec(),
+        top,
+        payload_size: 0,
+    }))
+}
+
+/// Start traces of the sub-plan sent as is to every replicaset.
+/// The sub-plan is described once and the record is copied per replicaset.
+///
+/// # Errors
+/// - sub-plan doesn't have a relational top node
+pub fn start_traces(
+    replicasets: &[String],
+    sub_plan: &ExecutionPlan,
+) -> Result<Vec<DispatchTrace>, SbroadError> {
+    let Some(trace) = start_trace("", &[], sub_plan)? else {
+        return Ok(vec![]);
+    };
+    Ok(replicasets
+        .iter()
+        .map(|rs| DispatchTrace {
+            replicaset: rs.clone(),
+            ..trace.clone()
+        })
+        .collect())
+}
+
+/// Pass the traces of the same request to the hook.
+pub fn finish_traces(traces: Vec<DispatchTrace>, payload_size: usize) {
+    for trace in traces {
+        finish_trace(Some(trace), payload_size);
+    }
+}
+
+/// Pass the trace with the size of the encoded request to the hook.
+pub fn finish_trace(trace: Option<DispatchTrace>, payload_size: usize) {
+    let Some(mut trace) = trace else {
+        return;
+    };
+    let Some(hook) = DISPATCH_HOOK.with(|h| h.borrow().clone()) else {
+        return;
+    };
+    trace.payload_size = payload_size;
+    hook(&trace);
+}
@@ -27,7 +27,7 @@ use crate::ir::relation::{Column, ColumnRole, SpaceEngine, Table};
 use crate::ir::tree::Snapshot;
 use crate::ir::types::{DerivedType, UnrestrictedType};
 use crate::ir::value::Value;
-use crate::ir::Plan;
+use crate::ir::{Plan, DEFAULT_MAX_EXPRESSION_DEPTH};
 use crate::utils::MutexLike;
 use rand::random;
 use serde::{Deserialize, Serialize};
@@ -35,8 +35,11 @@ use std::io::{Result as IoResult, Write};
 use std::rc::Rc;
 use tarantool::space::SpaceId;
 
+use super::helpers::trace::{finish_trace, start_trace};
 use super::helpers::vshard::prepare_rs_to_ir_map;
-use super::helpers::{dispatch_impl, normalize_name_from_sql, table_name};
+use super::helpers::{
+    dispatch_impl, materialize_values, normalize_name_from_sql, table_name, IdentifierCase,
+};
 use super::{get_builtin_functions, BlockExecData, Metadata, QueryCache};
 use crate::executor::result::MetadataColumn;
 use crate::executor::vdbe::{ExecutionInsight, SqlError, SqlStmt};
@@ -129,6 +132,11 @@ pub struct RouterConfigurationMock {
     tables: HashMap<SmolStr, Table>,
     bucket_count: u64,
     sharding_column: SmolStr,
+    identifier_case: IdentifierCase,
+    max_expression_depth: usize,
+    table_row_counts: HashMap<SmolStr, u64>,
+    /// Last allocated identity values of the tables.
+    identity_sequences: HashMap<SmolStr, i64>,
 }
 
 impl Metadata for RouterConfigurationMock {
@@ -147,6 +155,8 @@ impl Metadata for RouterConfigurationMock {
     }
 
     fn function(&self, fn_name: &str) -> Result<&Function, SbroadError> {
+        // Functions are registered lowercase, so unquoted names are
+        // folded regardless of the identifier case policy.
         let name = normalize_name_from_sql(fn_name);
         match self.functions.get(&name) {
             Some(v) => Ok(v),
@@ -171,6 +181,18 @@ impl Metadata for RouterConfigurationMock {
         let table = self.table(space)?;
         Ok(table.get_sk()?.to_vec())
     }
+
+    fn identifier_case(&self) -> IdentifierCase {
+        self.identifier_case
+    }
+
+    fn max_expression_depth(&self) -> usize {
+        self.max_expression_depth
+    }
+
+    fn table_row_count(&self, table_name: &str) -> Option<u64> {
+        self.table_row_counts.get(table_name).copied()
+    }
 }
 
 impl Default for RouterConfigurationMock {
@@ -1530,11 +1552,81 @@ impl RouterConfigurationMock {
             .unwrap(),
         );
 
+        let columns = vec![
+            Column::new(
+                "bucket_id",
+                DerivedType::new(UnrestrictedType::Integer),
+                ColumnRole::Sharding,
+                true,
+            ),
+            Column::new(
+                "id",
+                DerivedType::new(UnrestrictedType::Integer),
+                ColumnRole::User,
+                false,
+            ),
+            Column::new(
+                "a",
+                DerivedType::new(UnrestrictedType::String),
+                ColumnRole::User,
+                true,
+            ),
+        ];
+        let sharding_key: &[&str] = &["id"];
+        let primary_key: &[&str] = &["id"];
+        let mut identity_t = Table::new_sharded(
+            random(),
+            "identity_t",
+            columns,
+            sharding_key,
+            primary_key,
+            SpaceEngine::Memtx,
+        )
+        .unwrap();
+        identity_t.identity = Some(1);
+        tables.insert("identity_t".to_smolstr(), identity_t);
+
+        let columns = vec![
+            Column::new(
+                "bucket_id",
+                DerivedType::new(UnrestrictedType::Integer),
+                ColumnRole::Sharding,
+                true,
+            ),
+            Column::new(
+                "id",
+                DerivedType::new(UnrestrictedType::Integer),
+                ColumnRole::User,
+                false,
+            ),
+            Column::new(
+                "a",
+                DerivedType::new(UnrestrictedType::String),
+                ColumnRole::User,
+                false,
+            ),
+        ];
+        let mut default_t = Table::new_sharded(
+            random(),
+            "default_t",
+            columns,
+            sharding_key,
+            primary_key,
+            SpaceEngine::Memtx,
+        )
+        .unwrap();
+        default_t.defaults = vec![(2, Value::from("x"))];
+        tables.insert("default_t".to_smolstr(), default_t);
+
         RouterConfigurationMock {
             functions,
             tables,
             bucket_count: 10000,
             sharding_column: "bucket_id".into(),
+            identifier_case: IdentifierCase::default(),
+            max_expression_depth: DEFAULT_MAX_EXPRESSION_DEPTH,
+            table_row_counts: HashMap::new(),
+            identity_sequences: HashMap::new(),
         }
     }
 }
@@ -1692,6 +1784,13 @@ impl Vshard for RouterRuntimeMock {
         get_random_bucket(self)
     }
 
+    fn storage_count(&self, buckets: &Buckets) -> Result<u64, SbroadError> {
+        if let Buckets::Any = buckets {
+            return Ok(1);
+        }
+        Ok(self.vshard_mock.group(buckets).len() as u64)
+    }
+
     fn determine_bucket_id(&self, s: &[&Value]) -> Result<u64, SbroadError> {
         Ok(bucket_id_by_tuple(s, self.bucket_count()))
     }
@@ -1727,6 +1826,13 @@ impl Vshard for &RouterRuntimeMock {
         get_random_bucket(self)
     }
 
+    fn storage_count(&self, buckets: &Buckets) -> Result<u64, SbroadError> {
+        if let Buckets::Any = buckets {
+            return Ok(1);
+        }
+        Ok(self.vshard_mock.group(buckets).len() as u64)
+    }
+
     fn determine_bucket_id(&self, s: &[&Value]) -> Result<u64, SbroadError> {
         Ok(bucket_id_by_tuple(s, self.bucket_count()))
     }
@@ -1825,7 +1931,6 @@ fn custom_plan_dispatch(
     rs_bucket_vec.sort_by_key(|(rs, _)| rs.clone());
     let (rs_ir, _) = prepare_rs_to_ir_map(&rs_bucket_vec, plan).unwrap();
     for (rs, ex_plan) in rs_ir {
-        let (pattern, params) = to_sql(&ex_plan);
         let buckets = rs_bucket_vec
             .iter()
             .find_map(|(name, buckets)| {
@@ -1836,6 +1941,10 @@ fn custom_plan_dispatch(
                 }
             })
             .unwrap();
+        let trace = start_trace(&rs, &buckets, &ex_plan).unwrap();
+        let (pattern, params) = to_sql(&ex_plan);
+        let payload = rmp_serde::to_vec(&(&pattern, &params)).unwrap();
+        finish_trace(trace, payload.len());
         info.push((pattern, params, rs, buckets));
     }
     // Sort to get deterministic test results.
@@ -1878,9 +1987,32 @@ impl RouterRuntimeMock {
             .insert(table.name.clone(), table);
     }
 
+    pub fn set_identifier_case(&mut self, case: IdentifierCase) {
+        self.metadata.borrow_mut().identifier_case = case;
+    }
+
+    pub fn set_max_expression_depth(&mut self, depth: usize) {
+        self.metadata.borrow_mut().max_expression_depth = depth;
+    }
+
+    pub fn set_table_row_count(&mut self, table_name: &str, row_count: u64) {
+        self.metadata
+            .borrow_mut()
+            .table_row_counts
+            .insert(table_name.to_smolstr(), row_count);
+    }
+
     pub fn set_vshard_mock(&mut self, rs_count: usize) {
         self.vshard_mock = VshardMock::new(rs_count, self.bucket_count());
     }
+
+    /// Evict cached plans that reference the given table.
+    pub fn invalidate_schema(&self, space_name: &str) -> Result<(), SbroadError> {
+        self.ir_cache
+            .borrow_mut()
+            .retain(|_, plan| plan.relations.get(space_name).is_none())?;
+        Ok(())
+    }
 }
 
 impl Router for RouterRuntimeMock {
@@ -1919,17 +2051,15 @@ impl Router for RouterRuntimeMock {
 
     fn materialize_values(
         &self,
-        _exec_plan: &mut ExecutionPlan,
+        exec_plan: &mut ExecutionPlan,
         values_id: NodeId,
     ) -> Result<VirtualTable, SbroadError> {
-        Ok(self
-            .virtual_tables
-            .borrow()
-            .get(&values_id)
-            .expect(&format!(
-                "Virtual table for values with id {values_id} not found."
-            ))
-            .clone())
+        if let Some(vtable) = self.virtual_tables.borrow().get(&values_id) {
+            return Ok(vtable.clone());
+        }
+        // Constant VALUES are materialized on the router the same way
+        // as in the real runtime.
+        materialize_values(self, exec_plan, values_id)
     }
 
     fn dispatch<'p>(
@@ -1974,6 +2104,18 @@ impl Router for RouterRuntimeMock {
         Ok(self.clone())
     }
 
+    fn next_identity_values(&self, table: &Table, count: usize) -> Result<Vec<i64>, SbroadError> {
+        let mut metadata = self.metadata.borrow_mut();
+        let last = metadata
+            .identity_sequences
+            .entry(table.name.clone())
+            .or_default();
+        let count = i64::try_from(count).map_err(|e| SbroadError::Other(e.to_smolstr()))?;
+        let values = (*last + 1..=*last + count).collect();
+        *last += count;
+        Ok(values)
+    }
+
     fn is_audit_enabled(&self, _plan: &Plan) -> Result<bool, SbroadError> {
         Ok(false)
     }
@@ -14,9 +14,10 @@ use crate::ir::node::expression::{Expression, MutExpression};
 use crate::ir::node::relational::{MutRelational, RelOwned, Relational};
 use crate::ir::node::{
     Alias, ArenaType, ArithmeticExpr, BoolExpr, Bound, BoundType, Case, Cast, Concat, Delete,
-    GroupBy, Having, IndexExpr, Insert, Join, Like, Motion, Node136, NodeId, NodeOwned, OrderBy,
-    Over, Projection, Reference, ReferenceTarget, Row, ScalarFunction, ScanRelation, Selection,
-    SubQueryReference, Trim, UnaryExpr, Update, ValuesRow, Window,
+    Except, GroupBy, Having, IndexExpr, Insert, Intersect, Join, Like, Motion, Node136, Node64,
+    NodeId, NodeOwned, OrderBy, Over, Projection, Reference, ReferenceTarget, Row, ScalarFunction,
+    ScanCte, ScanRelation, Selection, SubQueryReference, Trim, UnaryExpr, Update, ValuesRow,
+    Window,
 };
 use crate::ir::operator::{OrderByElement, OrderByEntity};
 use crate::ir::relation::SpaceEngine;
@@ -65,6 +66,30 @@ pub struct ExecutionPlan {
     pub(crate) plan_id: Option<u64>,
 }
 
+/// Motions of the recursive CTE, that is evaluated on the router.
+#[derive(Debug)]
+pub struct RecursiveCteMotions {
+    /// Motion under the working table: it holds the rows of the non-recursive term.
+    pub working_table: NodeId,
+    /// Motion over the recursive term.
+    pub recursive_term: NodeId,
+    /// Motions of the recursive term subtree, materialized on every iteration.
+    pub term_motions: HashSet<NodeId>,
+    /// Whether the CTE terms are combined with UNION (not UNION ALL).
+    pub remove_duplicates: bool,
+}
+
+/// Motions of the `INTERSECT ALL` or `EXCEPT ALL` node, that is evaluated on the router.
+#[derive(Debug)]
+pub struct MultisetOp {
+    /// Motion over the left child.
+    pub left: NodeId,
+    /// Motion over the right child.
+    pub right: NodeId,
+    /// Whether the children are combined with `INTERSECT ALL` (not `EXCEPT ALL`).
+    pub is_intersect: bool,
+}
+
 /// Translates the original plan's node id to the new sub-plan one.
 struct SubtreeMap {
     inner: AHashMap<NodeId, NodeId>,
@@ -233,6 +258,9 @@ impl ExecutionPlan {
                 MotionOpcode::RemoveDuplicates => {
                     vtable.remove_duplicates();
                 }
+                MotionOpcode::SortBy(position, order_type, nulls, collation) => {
+                    vtable.sort_by_column(*position, order_type, *nulls, *collation)?;
+                }
                 MotionOpcode::ReshardIfNeeded => {
                     // Resharding must be done before applying projection
                     // to the virtual table. Otherwise projection can
@@ -312,6 +340,114 @@ impl ExecutionPlan {
         false
     }
 
+    /// Get the recursive CTEs that are not materialized yet, mapped by
+    /// the motion over their bodies.
+    ///
+    /// # Errors
+    /// - recursive CTE subtree is invalid
+    pub fn get_recursive_ctes(&self) -> Result<HashMap<NodeId, RecursiveCteMotions>, SbroadError> {
+        let plan = self.get_ir_plan();
+        let mut ctes = HashMap::new();
+        for node in plan.nodes.iter64() {
+            let Node64::ScanCte(ScanCte {
+                child: motion_id,
+                is_recursive: true,
+                ..
+            }) = node
+            else {
+                continue;
+            };
+            let Relational::Motion(Motion {
+                child: Some(union_id),
+                ..
+            }) = plan.get_relation_node(*motion_id)?
+            else {
+                continue;
+            };
+            let union = plan.get_relation_node(*union_id)?;
+            let remove_duplicates = matches!(union, Relational::Union(_));
+            let children = union.children();
+            let (Some(working_table_scan), Some(recursive_term)) =
+                (children.get(0), children.get(1))
+            else {
+                return Err(SbroadError::UnexpectedNumberOfValues(
+                    "recursive CTE union must have exactly two children".into(),
+                ));
+            };
+            let working_table = plan.get_first_rel_child(*working_table_scan)?;
+            let base_motions: HashSet<NodeId> = plan
+                .calculate_slices(working_table)?
+                .into_iter()
+                .flatten()
+                .collect();
+            let term_motions = plan
+                .calculate_slices(*recursive_term)?
+                .into_iter()
+                .flatten()
+                .filter(|id| !base_motions.contains(id))
+                .collect();
+            ctes.insert(
+                *motion_id,
+                RecursiveCteMotions {
+                    working_table,
+                    recursive_term: *recursive_term,
+                    term_motions,
+                    remove_duplicates,
+                },
+            );
+        }
+        Ok(ctes)
+    }
+
+    /// Get the `INTERSECT ALL` or `EXCEPT ALL` node under the motion,
+    /// if any. Its children are always motions.
+    ///
+    /// # Errors
+    /// - node is not a motion
+    /// - the children of the node are not motions
+    pub fn get_multiset_op(&self, motion_id: NodeId) -> Result<Option<MultisetOp>, SbroadError> {
+        let plan = self.get_ir_plan();
+        let Relational::Motion(Motion { child, .. }) = plan.get_relation_node(motion_id)? else {
+            return Err(SbroadError::Invalid(
+                Entity::Relational,
+                Some(format_smolstr!("node ({motion_id:?}) is not motion")),
+            ));
+        };
+        let Some(child_id) = child else {
+            return Ok(None);
+        };
+        let (left, right, is_intersect) = match plan.get_relation_node(*child_id)? {
+            Relational::Intersect(Intersect {
+                left,
+                right,
+                all: true,
+                ..
+            }) => (*left, *right, true),
+            Relational::Except(Except {
+                left,
+                right,
+                all: true,
+                ..
+            }) => (*left, *right, false),
+            _ => return Ok(None),
+        };
+        for id in [left, right] {
+            if !plan.get_relation_node(id)?.is_motion() {
+                return Err(SbroadError::Invalid(
+                    Entity::Relational,
+                    Some(format_smolstr!(
+                        "expected motion under multiset operation, got {id:?}"
+                    )),
+                ));
+            }
+        }
+        Ok(Some(MultisetOp {
+            left,
+            right,
+            is_intersect,
+        }))
+    }
+
     /// Extract policy from motion node
     ///
     /// # Errors
@@ -691,6 +827,7 @@ impl ExecutionPlan {
                                 new_elements.push(OrderByElement {
                                     entity: new_entity,
                                     order_type: element.order_type.clone(),
+                                    collation: element.collation,
                                 });
                             }
                             *order_by_elements = new_elements;
@@ -1022,6 +1159,9 @@ impl ExecutionPlan {
         new_plan.stash_constants(Snapshot::Oldest)?;
         new_plan.effective_options = self.get_ir_plan().effective_options.clone();
         new_plan.tier.clone_from(&self.get_ir_plan().tier);
+        new_plan
+            .query_fingerprint
+            .clone_from(&self.get_ir_plan().query_fingerprint);
 
         let vtables = if new_vtables.is_empty() {
             VirtualTableMap::new()
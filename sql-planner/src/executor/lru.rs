@@ -76,6 +76,31 @@ where
         self.lru.get_mut(key)
     }
 
+    /// Evicts all the entries the predicate returns false for.
+    /// Returns the number of evicted entries.
+    ///
+    /// # Errors
+    /// - Eviction function failed.
+    pub fn retain(&mut self, mut f: impl FnMut(&Key, &Value) -> bool) -> Result<usize, SbroadError>
+    where
+        Key: Clone,
+    {
+        let keys: Vec<Key> = self
+            .lru
+            .iter()
+            .filter(|(k, v)| !f(k, v))
+            .map(|(k, _)| k.clone())
+            .collect();
+        for key in &keys {
+            if let Some((k, mut v)) = self.lru.pop_entry(key) {
+                if let Some(ref f) = self.evict_fn {
+                    f(&k, &mut v)?;
+                }
+            }
+        }
+        Ok(keys.len())
+    }
+
     pub fn pop(&mut self) -> Result<Option<Value>, SbroadError> {
         if let Some((k, mut v)) = self.lru.pop_lru() {
             if let Some(ref f) = self.evict_fn {
@@ -48,3 +48,20 @@ fn lru4() {
     cache.put(1, "two".to_string()).unwrap();
     assert_eq!(cache.get(&1).unwrap(), Some(&"two".to_string()));
 }
+
+#[test]
+fn lru_retain() {
+    let evict_fn = Box::new(|_: &usize, value: &mut String| {
+        value.push_str("_evicted");
+        Ok(())
+    });
+    let mut cache: LRUCache<usize, String> = LRUCache::new(4, Some(evict_fn)).unwrap();
+    for (k, v) in [(1, "one"), (2, "two"), (3, "three")] {
+        cache.put(k, v.to_string()).unwrap();
+    }
+    assert_eq!(cache.retain(|_, v| v.len() != 3).unwrap(), 2);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get(&1).unwrap(), None);
+    assert_eq!(cache.get(&2).unwrap(), None);
+    assert_eq!(cache.get(&3).unwrap(), Some(&"three".to_string()));
+}
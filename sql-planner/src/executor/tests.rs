@@ -1,10 +1,17 @@
 use super::*;
 use crate::backend::sql::ir::PatternWithParams;
+use crate::executor::engine::helpers::IdentifierCase;
 use crate::executor::engine::mock::{DispatchInfo, PortMocked, RouterRuntimeMock};
+use crate::executor::engine::{Metadata, QueryCache};
 use crate::executor::vtable::VirtualTable;
+use crate::ir::relation::{Column, ColumnRole, Table};
 use crate::ir::tests::vcolumn_integer_user_non_null;
 use crate::ir::transformation::redistribution::{MotionOpcode, MotionPolicy};
+use crate::ir::types::{DerivedType, UnrestrictedType};
 use crate::ir::value::Value;
+use crate::ir::MAX_PARSER_NESTING_DEPTH;
+use crate::utils::MutexLike;
+use crate::PreparedStatement;
 use pretty_assertions::assert_eq;
 
 // Helper function to format back sql.
@@ -47,6 +54,28 @@ fn shard_query() {
     assert_eq!(buckets, &vec![bucket]);
 }
 
+#[test]
+fn max_buckets_per_query() {
+    let coordinator = RouterRuntimeMock::new();
+
+    let sql = r#"SELECT "FIRST_NAME" FROM "test_space" option(sql_max_buckets_per_query = 10)"#;
+    let mut port = PortMocked::new();
+    let mut query = ExecutingQuery::from_text_and_params(&coordinator, sql, vec![]).unwrap();
+    let err = query.dispatch(&mut port).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unexpected number of values: Exceeded maximum number of buckets per query (10): \
+        query touches 10000 buckets, filter it by the sharding key \
+        or increase sql_max_buckets_per_query option"
+    );
+
+    let sql = r#"SELECT "FIRST_NAME" FROM "test_space" WHERE "id" IN (1, 2)
+        option(sql_max_buckets_per_query = 2)"#;
+    let mut port = PortMocked::new();
+    let mut query = ExecutingQuery::from_text_and_params(&coordinator, sql, vec![]).unwrap();
+    query.dispatch(&mut port).unwrap();
+}
+
 #[test]
 fn shard_union_query() {
     let sql = r#"SELECT *
@@ -742,7 +771,7 @@ pub(crate) fn broadcast_check(sql: &str, pattern: &str, params: Vec<Value>) {
 
 #[test]
 fn groupby_linker_test() {
-    let sql = r#"SELECT t1."id" as "ii" FROM "test_space" as t1 group by t1."id""#;
+    let sql = r#"SELECT t1."sysFrom" as "ii" FROM "test_space" as t1 group by t1."sysFrom""#;
 
     let coordinator = RouterRuntimeMock::new();
 
@@ -811,14 +840,239 @@ fn insert_into_values() {
     assert_eq!(&program.0, &[MotionOpcode::ReshardIfNeeded]);
 }
 
+#[test]
+fn invalidate_schema_replans_query() {
+    // The table is read directly, in a subquery and in a CTE.
+    let queries = [
+        r#"SELECT * FROM "t""#,
+        r#"SELECT * FROM (SELECT * FROM "t")"#,
+        r#"WITH c AS (SELECT * FROM "t") SELECT * FROM c"#,
+    ];
+    let output_len = |coordinator: &RouterRuntimeMock, sql: &str| -> usize {
+        let query = ExecutingQuery::from_text_and_params(coordinator, sql, vec![]).unwrap();
+        let plan = query.get_exec_plan().get_ir_plan();
+        let top_id = plan.get_top().unwrap();
+        let output_id = plan.get_relation_node(top_id).unwrap().output();
+        plan.get_expression_node(output_id)
+            .unwrap()
+            .get_row_list()
+            .unwrap()
+            .len()
+    };
+
+    let mut coordinator = RouterRuntimeMock::new();
+    for sql in queries {
+        assert_eq!(4, output_len(&coordinator, sql), "{sql}");
+    }
+
+    // ALTER TABLE "t" ADD COLUMN "e" INT
+    let mut table = coordinator.metadata().lock().table("t").unwrap();
+    table.columns.push(Column::new(
+        "e",
+        DerivedType::new(UnrestrictedType::Integer),
+        ColumnRole::User,
+        true,
+    ));
+    coordinator.add_table(table);
+
+    // The cached plans still refer to the old schema.
+    for sql in queries {
+        assert_eq!(4, output_len(&coordinator, sql), "{sql}");
+    }
+
+    coordinator.invalidate_schema("t").unwrap();
+    for sql in queries {
+        assert_eq!(5, output_len(&coordinator, sql), "{sql}");
+    }
+}
+
+#[test]
+fn plan_cache_disabled_leaves_cache_untouched() {
+    let sql = r#"SELECT "a" FROM "t""#;
+    let coordinator = RouterRuntimeMock::new();
+
+    // A miss with the cache disabled doesn't populate the cache.
+    PreparedStatement::parse_uncached(&coordinator, sql, &[]).unwrap();
+    assert!(coordinator.cache().lock().is_empty());
+
+    PreparedStatement::parse(&coordinator, sql, &[]).unwrap();
+    assert_eq!(1, coordinator.cache().lock().len());
+
+    // Other entries are neither replaced nor evicted.
+    let other_sql = r#"SELECT "b" FROM "t""#;
+    let cached = PreparedStatement::parse(&coordinator, other_sql, &[]).unwrap();
+    let uncached = PreparedStatement::parse_uncached(&coordinator, other_sql, &[]).unwrap();
+    assert_eq!(2, coordinator.cache().lock().len());
+    let again = PreparedStatement::parse(&coordinator, other_sql, &[]).unwrap();
+    assert!(Rc::ptr_eq(&cached.plan, &again.plan));
+    assert!(!Rc::ptr_eq(&cached.plan, &uncached.plan));
+}
+
+#[test]
+fn identifier_case_resolves_unquoted_columns() {
+    let sql = r#"SELECT A FROM "case_t""#;
+    let projected = |coordinator: &RouterRuntimeMock| -> Vec<SmolStr> {
+        let query = ExecutingQuery::from_text_and_params(coordinator, sql, vec![]).unwrap();
+        let plan = query.get_exec_plan().get_ir_plan();
+        plan.get_relational_aliases(plan.get_top().unwrap())
+            .unwrap()
+    };
+    let new_coordinator = |case: IdentifierCase| -> RouterRuntimeMock {
+        let columns = vec![
+            Column::new(
+                "a",
+                DerivedType::new(UnrestrictedType::Integer),
+                ColumnRole::User,
+                false,
+            ),
+            Column::new(
+                "A",
+                DerivedType::new(UnrestrictedType::String),
+                ColumnRole::User,
+                true,
+            ),
+        ];
+        let table = Table::new_global(1024, "case_t", columns, &["a"]).unwrap();
+        let mut coordinator = RouterRuntimeMock::new();
+        coordinator.add_table(table);
+        coordinator.set_identifier_case(case);
+        coordinator
+    };
+
+    let coordinator = new_coordinator(IdentifierCase::Fold);
+    assert_eq!(vec![SmolStr::from("a")], projected(&coordinator));
+
+    // Unquoted identifiers keep their case.
+    let coordinator = new_coordinator(IdentifierCase::Preserve);
+    assert_eq!(vec![SmolStr::from("A")], projected(&coordinator));
+
+    // Function names are folded regardless of the policy.
+    let sql = r#"SELECT LOWER(A) FROM "case_t""#;
+    ExecutingQuery::from_text_and_params(&coordinator, sql, vec![]).unwrap();
+}
+
+#[test]
+fn deep_or_chain_exceeds_expression_depth() {
+    let filter = (0..200)
+        .map(|i| format!(r#""a" = {i}"#))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+    let sql = format!(r#"SELECT "a" FROM "t" WHERE {filter}"#);
+
+    // The chain is fine under the default limit.
+    let coordinator = RouterRuntimeMock::new();
+    let result = ExecutingQuery::from_text_and_params(&coordinator, &sql, vec![]);
+    assert!(result.is_ok());
+
+    let mut coordinator = RouterRuntimeMock::new();
+    coordinator.set_max_expression_depth(100);
+    let result = ExecutingQuery::from_text_and_params(&coordinator, &sql, vec![]);
+    let Err(error) = result else { unreachable!() };
+    assert_eq!(
+        error.to_string(),
+        "invalid expression: expression nesting depth exceeds the limit of 100"
+    );
+}
+
+#[test]
+fn long_in_list_exceeds_expression_depth() {
+    let list = (0..200)
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(r#"SELECT "a" FROM "t" WHERE "a" IN ({list})"#);
+
+    // The IN-list itself is shallow, but it is replaced with a chain of ORs.
+    let coordinator = RouterRuntimeMock::new();
+    let result = ExecutingQuery::from_text_and_params(&coordinator, &sql, vec![]);
+    assert!(result.is_ok());
+
+    let mut coordinator = RouterRuntimeMock::new();
+    coordinator.set_max_expression_depth(100);
+    let result = ExecutingQuery::from_text_and_params(&coordinator, &sql, vec![]);
+    let Err(error) = result else { unreachable!() };
+    assert_eq!(
+        error.to_string(),
+        "invalid expression: expression nesting depth exceeds the limit of 100"
+    );
+}
+
+#[test]
+fn deep_nesting_is_rejected_before_parsing() {
+    let coordinator = RouterRuntimeMock::new();
+    let expected = format!(
+        "invalid expression: expression nesting depth exceeds the limit of {MAX_PARSER_NESTING_DEPTH}"
+    );
+    let depth = 100_000;
+
+    let sql = format!(
+        r#"SELECT {}1{} FROM "t""#,
+        "(".repeat(depth),
+        ")".repeat(depth)
+    );
+    let result = ExecutingQuery::from_text_and_params(&coordinator, &sql, vec![]);
+    let Err(error) = result else { unreachable!() };
+    assert_eq!(error.to_string(), expected);
+
+    let sql = format!(r#"SELECT {}true FROM "t""#, "NOT ".repeat(depth));
+    let result = ExecutingQuery::from_text_and_params(&coordinator, &sql, vec![]);
+    let Err(error) = result else { unreachable!() };
+    assert_eq!(error.to_string(), expected);
+
+    // Brackets inside string literals are not counted.
+    let sql = format!(r#"SELECT '{}' FROM "t""#, "(".repeat(depth));
+    let result = ExecutingQuery::from_text_and_params(&coordinator, &sql, vec![]);
+    assert!(result.is_ok());
+}
+
 mod between;
 mod bucket_id;
 mod cast;
+mod collate;
 mod concat;
+mod dispatch_trace;
 mod empty_motion;
 mod exec_plan;
 mod frontend;
+mod insert;
 mod like;
 mod not_eq;
 mod not_in;
 mod unnamed_subquery;
+
+#[test]
+fn generate_series_is_expanded_on_router() {
+    let sql = "SELECT * FROM generate_series($1, $2, $3)";
+    let coordinator = RouterRuntimeMock::new();
+    let params = vec![Value::from(1), Value::from(9), Value::from(4)];
+
+    let mut query = ExecutingQuery::from_text_and_params(&coordinator, sql, params).unwrap();
+    let motion_id = query.get_motion_id(0, 0);
+    let slices = query.exec_plan.get_ir_plan().clone_slices();
+    query
+        .materialize_subtree(slices, None::<&mut PortMocked>)
+        .unwrap();
+
+    let vtable = query.exec_plan.get_vtables().get(&motion_id).unwrap();
+    assert_eq!(
+        vtable.get_tuples(),
+        &[
+            vec![Value::from(1)],
+            vec![Value::from(5)],
+            vec![Value::from(9)]
+        ]
+    );
+
+    // Parameters are bound per execution, so the cached plan
+    // expands into another series.
+    let params = vec![Value::from(3), Value::from(1), Value::from(-1)];
+    let mut query = ExecutingQuery::from_text_and_params(&coordinator, sql, params).unwrap();
+    let motion_id = query.get_motion_id(0, 0);
+    let slices = query.exec_plan.get_ir_plan().clone_slices();
+    query
+        .materialize_subtree(slices, None::<&mut PortMocked>)
+        .unwrap();
+
+    let vtable = query.exec_plan.get_vtables().get(&motion_id).unwrap();
+    assert_eq!(vtable.get_tuples().len(), 3);
+}
@@ -183,3 +183,111 @@ fn pgcast8_test() {
         vec![Value::from(1)],
     );
 }
+
+#[test]
+fn cast_text_to_bool_test() {
+    for (text, expected) in [
+        ("t", true),
+        ("YES", true),
+        ("on", true),
+        ("1", true),
+        (" true ", true),
+        ("F", false),
+        ("no", false),
+        ("Off", false),
+        ("0", false),
+        ("false", false),
+    ] {
+        broadcast_check(
+            &format!(r#"SELECT CAST('{text}' as bool) FROM "t1""#),
+            r#"SELECT CAST($1 AS bool) as "col_1" FROM "t1""#,
+            vec![Value::from(expected)],
+        );
+    }
+
+    let coordinator = RouterRuntimeMock::new();
+    let sql = r#"SELECT CAST('maybe' as bool) FROM "t1""#;
+    let result = ExecutingQuery::from_text_and_params(&coordinator, sql, vec![]);
+    let Err(error) = result else { unreachable!() };
+    assert_eq!(
+        error,
+        SbroadError::InvalidTextRepresentation("boolean".into(), "maybe".into())
+    );
+    assert_eq!(
+        error.to_string(),
+        r#"invalid input syntax for type boolean: "maybe""#
+    );
+}
+
+#[test]
+fn cast_bool_to_text_test() {
+    broadcast_check(
+        r#"SELECT CAST(true as text), false::text FROM "t1""#,
+        r#"SELECT CAST($1 AS string) as "col_1", CAST($2 AS string) as "col_2" FROM "t1""#,
+        vec![Value::from("true"), Value::from("false")],
+    );
+}
+
+#[test]
+fn cast_text_column_to_bool_test() {
+    // Storages use the same conversion as the router.
+    broadcast_check(
+        r#"SELECT "a"::bool FROM "t1""#,
+        r#"SELECT CAST (".proc_sql_text_to_bool"("t1"."a") as bool) as "col_1" FROM "t1""#,
+        vec![],
+    );
+}
+
+#[test]
+fn cast_text_column_to_temporal_test() {
+    // Temporal types are stored as datetimes or text, builtins convert to them.
+    broadcast_check(
+        r#"SELECT "a"::date, "a"::time FROM "t1""#,
+        r#"SELECT CAST ("_pico_to_timestamp"("t1"."a", 'date') as datetime) as "col_1", CAST ("_pico_to_time"("t1"."a", 'time') as string) as "col_2" FROM "t1""#,
+        vec![],
+    );
+
+    let coordinator = RouterRuntimeMock::new();
+    let sql = r#"SELECT CAST('12:60' as time) FROM "t1""#;
+    let result = ExecutingQuery::from_text_and_params(&coordinator, sql, vec![]);
+    let Err(error) = result else { unreachable!() };
+    assert_eq!(
+        error.to_string(),
+        r#"invalid input syntax for type time: "12:60""#
+    );
+}
+
+#[test]
+fn cast_to_narrow_integer_test() {
+    broadcast_check(
+        r#"SELECT CAST(32767 as smallint), 2147483647::int4 FROM "t1""#,
+        r#"SELECT CAST($1 AS int) as "col_1", CAST($2 AS int) as "col_2" FROM "t1""#,
+        vec![Value::from(32767), Value::from(2147483647)],
+    );
+
+    // Storages know only 64-bit integers, the range is checked by a builtin.
+    broadcast_check(
+        r#"SELECT "b"::smallint FROM "t1""#,
+        r#"SELECT "_pico_int_range"(CAST ("t1"."b" as int), -32768, 32767, 'smallint') as "col_1" FROM "t1""#,
+        vec![],
+    );
+
+    // Tarantool truncates fractional values, so they are rounded first.
+    broadcast_check(
+        r#"SELECT "b"::double::int, "b"::decimal::smallint FROM "t1""#,
+        r#"SELECT CAST ("_pico_round_to_int"(CAST ("t1"."b" as double)) as int) as "col_1", "_pico_int_range"(CAST ("_pico_round_to_int"(CAST ("t1"."b" as decimal)) as int), -32768, 32767, 'smallint') as "col_2" FROM "t1""#,
+        vec![],
+    );
+
+    let coordinator = RouterRuntimeMock::new();
+    for (sql, ty) in [
+        (r#"SELECT CAST(32768 as smallint) FROM "t1""#, "smallint"),
+        (r#"SELECT 2147483648::bigint::int4 FROM "t1""#, "integer"),
+        (r#"SELECT 1e19::double::int FROM "t1""#, "bigint"),
+    ] {
+        let result = ExecutingQuery::from_text_and_params(&coordinator, sql, vec![]);
+        let Err(error) = result else { unreachable!() };
+        assert_eq!(error, SbroadError::NumericValueOutOfRange(ty.into()));
+        assert_eq!(error.to_string(), format!("{ty} out of range"));
+    }
+}
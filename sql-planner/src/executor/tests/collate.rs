@@ -0,0 +1,32 @@
+use super::*;
+use crate::ir::value::Value;
+
+#[test]
+fn collate1_test() {
+    broadcast_check(
+        r#"SELECT "a" FROM "t1" WHERE "a" COLLATE "unicode_ci" = 'ab'"#,
+        r#"SELECT "t1"."a" FROM "t1" WHERE "t1"."a" COLLATE "unicode_ci" = CAST($1 AS string)"#,
+        vec![Value::from("ab")],
+    );
+}
+
+#[test]
+fn collate2_test() {
+    broadcast_check(
+        r#"SELECT "a" FROM "t1" WHERE "a" < 'ab' COLLATE "C""#,
+        r#"SELECT "t1"."a" FROM "t1" WHERE "t1"."a" COLLATE "binary" < CAST($1 AS string)"#,
+        vec![Value::from("ab")],
+    );
+}
+
+#[test]
+fn collate_bucket_discovery_test() {
+    // Collated equality may match strings with different sharding keys,
+    // so the query can't be routed by them.
+    let sql = r#"SELECT "a" FROM "t1" WHERE "a" COLLATE "unicode_ci" = '1' AND "b" = 2"#;
+    let coordinator = RouterRuntimeMock::new();
+
+    let mut query = ExecutingQuery::from_text_and_params(&coordinator, sql, vec![]).unwrap();
+    let top_id = query.exec_plan.get_ir_plan().get_top().unwrap();
+    assert_eq!(Buckets::All, query.bucket_discovery(top_id).unwrap());
+}
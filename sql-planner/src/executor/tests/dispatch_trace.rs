@@ -0,0 +1,59 @@
+use super::*;
+use crate::executor::engine::helpers::trace::{set_dispatch_hook, DispatchTrace};
+use crate::frontend::sql::fingerprint::query_fingerprint;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn dispatch_trace_two_buckets() {
+    let sql = r#"SELECT "id" FROM "test_space" WHERE "id" = 1 OR "id" = 2"#;
+    let mut coordinator = RouterRuntimeMock::new();
+    // Every replicaset holds a single bucket.
+    coordinator.set_vshard_mock(coordinator.bucket_count() as usize);
+
+    let captured: Rc<RefCell<Vec<DispatchTrace>>> = Rc::default();
+    let sink = captured.clone();
+    set_dispatch_hook(Some(Rc::new(move |trace: &DispatchTrace| {
+        sink.borrow_mut().push(trace.clone());
+    })));
+
+    let mut query = ExecutingQuery::from_text_and_params(&coordinator, sql, vec![]).unwrap();
+    let mut port = PortMocked::new();
+    let res = query.dispatch(&mut port);
+    set_dispatch_hook(None);
+    res.unwrap();
+
+    let info = port.decode();
+    let DispatchInfo::Filtered(filtered) = info.get(0).unwrap() else {
+        panic!("Expected a custom plan dispatch");
+    };
+    let mut expected_buckets = [Value::from(1), Value::from(2)]
+        .iter()
+        .map(|v| coordinator.determine_bucket_id(&[v]).unwrap())
+        .collect::<Vec<_>>();
+    expected_buckets.sort_unstable();
+
+    let query_id = query_fingerprint(sql).unwrap();
+    let mut traces = captured.take();
+    traces.sort_by(|a, b| a.replicaset.cmp(&b.replicaset));
+    assert_eq!(2, traces.len());
+    assert_eq!(filtered.len(), traces.len());
+    for (trace, (_, _, rs, buckets)) in traces.iter().zip(filtered) {
+        assert_eq!(Some(&query_id), trace.query_id.as_ref());
+        assert_eq!(rs, &trace.replicaset);
+        assert_eq!(buckets, &trace.buckets);
+        assert_eq!("Projection", trace.top);
+        assert!(trace.payload_size > 0);
+    }
+    let mut traced_buckets = traces
+        .iter()
+        .flat_map(|trace| trace.buckets.iter().copied())
+        .collect::<Vec<_>>();
+    traced_buckets.sort_unstable();
+    assert_eq!(expected_buckets, traced_buckets);
+
+    // No hook is called once it is removed.
+    let mut query = ExecutingQuery::from_text_and_params(&coordinator, sql, vec![]).unwrap();
+    query.dispatch(&mut PortMocked::new()).unwrap();
+    assert!(captured.borrow().is_empty());
+}
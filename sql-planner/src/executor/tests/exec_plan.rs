@@ -1371,6 +1371,7 @@ fn take_subtree_projection_windows_transfer() {
                 OrderByElement {
                     entity: OrderByEntity::Expression { expr_id: new_ref },
                     order_type: None,
+                    collation: None,
                 }
             })
             .collect();
@@ -0,0 +1,171 @@
+use super::*;
+use crate::executor::engine::helpers::vshard::prepare_rs_to_ir_map;
+use crate::ir::helpers::RepeatableState;
+use crate::ir::value::Value;
+use std::collections::HashSet;
+
+const ROWS: i64 = 500;
+const KEYS: i64 = 50;
+
+/// Materializes VALUES of the insert on the router and splits the plan
+/// into per-replicaset requests the same way DML dispatch does.
+fn insert_requests(
+    coordinator: &RouterRuntimeMock,
+    sql: &str,
+    params: Vec<Value>,
+) -> Vec<(String, ExecutionPlan)> {
+    let mut query = ExecutingQuery::from_text_and_params(coordinator, sql, params).unwrap();
+    let slices = query.exec_plan.get_ir_plan().clone_slices();
+    query
+        .materialize_subtree(slices, None::<&mut PortMocked>)
+        .unwrap();
+
+    let top_id = query.exec_plan.get_ir_plan().get_top().unwrap();
+    let buckets = query.bucket_discovery(top_id).unwrap();
+    let Buckets::Filtered(_) = buckets else {
+        panic!("Expected filtered buckets for insert, got {buckets:?}");
+    };
+    let mut rs_buckets: Vec<(String, Vec<u64>)> =
+        coordinator.vshard_mock.group(&buckets).drain().collect();
+    rs_buckets.sort_by_key(|(rs, _)| rs.clone());
+    let (rs_ir, _) = prepare_rs_to_ir_map(&rs_buckets, query.exec_plan).unwrap();
+    let mut requests: Vec<(String, ExecutionPlan)> = rs_ir.into_iter().collect();
+    requests.sort_by_key(|(rs, _)| rs.clone());
+    requests
+}
+
+fn expected_buckets(coordinator: &RouterRuntimeMock) -> HashSet<u64, RepeatableState> {
+    (0..ROWS)
+        .map(|i| {
+            let key = Value::from(i % KEYS);
+            coordinator.determine_bucket_id(&[&key, &key]).unwrap()
+        })
+        .collect()
+}
+
+fn check_requests(coordinator: &RouterRuntimeMock, requests: &[(String, ExecutionPlan)]) {
+    let expected = expected_buckets(coordinator);
+    // One request per storage rather than one per row.
+    assert_eq!(expected.len(), requests.len());
+
+    let mut rows = 0;
+    let mut seen = HashSet::new();
+    for (_, plan) in requests {
+        let vtables = plan.get_vtables();
+        assert_eq!(1, vtables.len());
+        let vtable = vtables.values().next().unwrap();
+        let bucket_index = vtable.get_bucket_index();
+        assert_eq!(1, bucket_index.len());
+        let bucket_id = *bucket_index.keys().next().unwrap();
+        assert!(expected.contains(&bucket_id));
+        assert!(seen.insert(bucket_id));
+        rows += vtable.get_tuples().len();
+    }
+    assert_eq!(ROWS as usize, rows);
+}
+
+#[test]
+fn insert_batch_by_buckets() {
+    let rows = (0..ROWS)
+        .map(|i| format!("({}, {}, {i}, {i})", i % KEYS, i % KEYS))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(r#"INSERT INTO "t" VALUES {rows}"#);
+
+    let mut coordinator = RouterRuntimeMock::new();
+    // Every replicaset holds a single bucket.
+    coordinator.set_vshard_mock(coordinator.bucket_count() as usize);
+
+    let requests = insert_requests(&coordinator, &sql, vec![]);
+    check_requests(&coordinator, &requests);
+}
+
+#[test]
+fn insert_batch_by_buckets_with_params() {
+    let rows = (0..ROWS)
+        .map(|_| "(?, ?, ?, ?)")
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(r#"INSERT INTO "t" VALUES {rows}"#);
+    let params = (0..ROWS)
+        .flat_map(|i| {
+            [
+                Value::from(i % KEYS),
+                Value::from(i % KEYS),
+                Value::from(i),
+                Value::from(i),
+            ]
+        })
+        .collect();
+
+    let mut coordinator = RouterRuntimeMock::new();
+    coordinator.set_vshard_mock(coordinator.bucket_count() as usize);
+
+    // Sharding key parameters are bound before the rows are grouped.
+    let requests = insert_requests(&coordinator, &sql, params);
+    check_requests(&coordinator, &requests);
+}
+
+#[test]
+fn insert_batch_by_replicasets() {
+    let rows = (0..ROWS)
+        .map(|i| format!("({}, {}, {i}, {i})", i % KEYS, i % KEYS))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(r#"INSERT INTO "t" VALUES {rows}"#);
+
+    let coordinator = RouterRuntimeMock::new();
+    let requests = insert_requests(&coordinator, &sql, vec![]);
+    // Buckets of the same replicaset are sent in a single request.
+    let buckets = Buckets::new_filtered(expected_buckets(&coordinator));
+    assert_eq!(
+        coordinator.vshard_mock.group(&buckets).len(),
+        requests.len()
+    );
+    let rows: usize = requests
+        .iter()
+        .map(|(_, plan)| {
+            plan.get_vtables()
+                .values()
+                .next()
+                .unwrap()
+                .get_tuples()
+                .len()
+        })
+        .sum();
+    assert_eq!(ROWS as usize, rows);
+}
+
+#[test]
+fn insert_identity_values() {
+    let coordinator = RouterRuntimeMock::new();
+    let sql = r#"INSERT INTO "identity_t"("a") VALUES ('a'), ('b'), ('c')"#;
+
+    let mut ids = Vec::new();
+    for _ in 0..2 {
+        let mut query = ExecutingQuery::from_text_and_params(&coordinator, sql, vec![]).unwrap();
+        let slices = query.exec_plan.get_ir_plan().clone_slices();
+        query
+            .materialize_subtree(slices, None::<&mut PortMocked>)
+            .unwrap();
+
+        let plan = query.exec_plan.get_ir_plan();
+        let motion_id = plan.dml_child_id(plan.get_top().unwrap()).unwrap();
+        let vtable = query.exec_plan.get_motion_vtable(motion_id).unwrap();
+        assert_eq!(2, vtable.get_columns().len());
+        for (bucket_id, positions) in vtable.get_bucket_index() {
+            for pos in positions {
+                let id = &vtable.get_tuples()[*pos][1];
+                // Rows are resharded by the generated identity values.
+                assert_eq!(*bucket_id, coordinator.determine_bucket_id(&[id]).unwrap());
+                ids.push(id.clone());
+            }
+        }
+    }
+
+    ids.sort_by_key(|id| match id {
+        Value::Integer(id) => *id,
+        _ => panic!("Expected integer identity value, got {id:?}"),
+    });
+    assert_eq!((1_i64..=6).map(Value::from).collect::<Vec<_>>(), ids);
+}
@@ -3,6 +3,7 @@ use crate::executor::engine::helpers::{TupleBuilderCommand, TupleBuilderPattern}
 use crate::executor::Vshard;
 use crate::ir::helpers::RepeatableState;
 use crate::ir::node::NodeId;
+use crate::ir::operator::{Collation, NullsOrder, OrderByType};
 use crate::ir::relation::{Column, ColumnRole};
 use crate::ir::transformation::redistribution::{ColumnPosition, MotionKey, Target};
 use crate::ir::types::{DerivedType, UnrestrictedType};
@@ -500,6 +501,8 @@ impl VirtualTable {
     /// Values of `pk_columns` are taken from `table_columns`.
     /// Bucket is calculated using `old_shard_key_columns`.
     ///
+    /// Only the first tuple with the given primary key is kept.
+    ///
     /// # Errors
     /// - invalid len of old shard key
     /// - invalid new shard key positions
@@ -524,6 +527,17 @@ impl VirtualTable {
         if self.tuples.is_empty() {
             return Ok(None);
         };
+        // `UPDATE .. FROM` may join a target row with several source rows,
+        // the first match wins.
+        let pk_positions = self.get_primary_key()?.to_vec();
+        let mut updated_keys = HashSet::with_capacity(self.tuples.len());
+        self.tuples.retain(|tuple| {
+            let key: Vec<Value> = pk_positions
+                .iter()
+                .filter_map(|pos| tuple.get(*pos).cloned())
+                .collect();
+            updated_keys.insert(key)
+        });
         let (delete_tuples, mut index) =
             self.create_delete_tuples(runtime, old_shard_columns_len)?;
 
@@ -609,6 +623,127 @@ impl VirtualTable {
         Ok(())
     }
 
+    /// Keeps the tuples of the virtual table that are present in the other one
+    /// with `INTERSECT ALL` semantics: a tuple is kept as many times as
+    /// the minimum of its counts in both tables. The order of the tuples
+    /// is preserved.
+    pub fn intersect_all(&mut self, other: &VirtualTable) {
+        let mut counts = other.tuple_counts();
+        self.tuples.retain(|tuple| match counts.get_mut(tuple) {
+            Some(cnt) if *cnt > 0 => {
+                *cnt -= 1;
+                true
+            }
+            _ => false,
+        });
+    }
+
+    /// Removes the tuples of the other virtual table from the current one
+    /// with `EXCEPT ALL` semantics: a tuple is kept as many times as its
+    /// count in the current table exceeds the count in the other one.
+    /// The order of the tuples is preserved.
+    pub fn except_all(&mut self, other: &VirtualTable) {
+        let mut counts = other.tuple_counts();
+        self.tuples.retain(|tuple| match counts.get_mut(tuple) {
+            Some(cnt) if *cnt > 0 => {
+                *cnt -= 1;
+                false
+            }
+            _ => true,
+        });
+    }
+
+    fn tuple_counts(&self) -> HashMap<&VTableTuple, usize, RepeatableState> {
+        let mut counts = HashMap::with_capacity_and_hasher(self.tuples.len(), RepeatableState);
+        for tuple in &self.tuples {
+            *counts.entry(tuple).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Sorts tuples of the virtual table by the column on the given
+    /// position. The relative order of the tuples with equal values
+    /// in this column is preserved.
+    ///
+    /// The tuples gathered from the storages are compared with the SQL
+    /// semantics (see [`Value::sql_cmp`]), so `NULL`s end up in the same
+    /// place no matter which storage they came from. Strings are compared
+    /// with the tarantool collation, if it is set.
+    ///
+    /// # Errors
+    /// - Failed to build a key definition for the collation.
+    pub fn sort_by_column(
+        &mut self,
+        position: ColumnPosition,
+        order_type: &OrderByType,
+        nulls: NullsOrder,
+        collation: Option<Collation>,
+    ) -> Result<(), SbroadError> {
+        #[cfg(not(feature = "mock"))]
+        if let Some(collation @ (Collation::Unicode | Collation::UnicodeCi)) = collation {
+            return self.sort_by_collated_column(position, order_type, nulls, collation);
+        }
+        self.tuples
+            .sort_by(|l, r| match (l.get(position), r.get(position)) {
+                (Some(l), Some(r)) => l.sql_cmp(r, order_type, nulls, collation),
+                (l, r) => l.cmp(&r),
+            });
+        Ok(())
+    }
+
+    /// Sorts tuples by the column on the given position comparing strings
+    /// with a tarantool key definition, so the order is the same as the
+    /// one of the collated indexes on the storages.
+    #[cfg(not(feature = "mock"))]
+    fn sort_by_collated_column(
+        &mut self,
+        position: ColumnPosition,
+        order_type: &OrderByType,
+        nulls: NullsOrder,
+        collation: Collation,
+    ) -> Result<(), SbroadError> {
+        use std::borrow::Cow;
+        use std::ffi::CString;
+        use tarantool::tuple::{FieldType, KeyDef, KeyDefPart, Tuple};
+
+        let name = CString::new(collation.to_string()).map_err(|e| {
+            SbroadError::Invalid(Entity::KeyDef, Some(format_smolstr!("collation name: {e}")))
+        })?;
+        let part = KeyDefPart {
+            field_no: 0,
+            field_type: FieldType::String,
+            collation: Some(Cow::Borrowed(name.as_c_str())),
+            ..Default::default()
+        };
+        let key_def = KeyDef::new(&[part])
+            .map_err(|e| SbroadError::Invalid(Entity::KeyDef, Some(format_smolstr!("{e}"))))?;
+
+        let mut keyed_tuples = Vec::with_capacity(self.tuples.len());
+        for tuple in std::mem::take(&mut self.tuples) {
+            let key = match tuple.get(position) {
+                Some(value @ Value::String(_)) => {
+                    Some(Tuple::new(std::slice::from_ref(value)).map_err(|e| {
+                        SbroadError::Invalid(Entity::Tuple, Some(format_smolstr!("{e}")))
+                    })?)
+                }
+                _ => None,
+            };
+            keyed_tuples.push((key, tuple));
+        }
+        keyed_tuples.sort_by(|(l_key, l), (r_key, r)| match (l_key, r_key) {
+            (Some(l_key), Some(r_key)) => match order_type {
+                OrderByType::Asc => key_def.compare(l_key, r_key),
+                OrderByType::Desc => key_def.compare(r_key, l_key),
+            },
+            _ => match (l.get(position), r.get(position)) {
+                (Some(l), Some(r)) => l.sql_cmp(r, order_type, nulls, None),
+                (l, r) => l.cmp(&r),
+            },
+        });
+        self.tuples = keyed_tuples.into_iter().map(|(_, tuple)| tuple).collect();
+        Ok(())
+    }
+
     /// Removes duplicates from virtual table, the order
     /// of rows is changed.
     pub fn remove_duplicates(&mut self) {
@@ -720,7 +855,7 @@ pub fn calculate_unified_types(
             | UnrestrictedType::String
             | UnrestrictedType::Integer
             | UnrestrictedType::Datetime => &[],
-            UnrestrictedType::Uuid => &[UnrestrictedType::String],
+            UnrestrictedType::Uuid | UnrestrictedType::Bytea => &[UnrestrictedType::String],
             UnrestrictedType::Double => &[UnrestrictedType::Integer],
             UnrestrictedType::Decimal => &[UnrestrictedType::Integer, UnrestrictedType::Double],
         }
@@ -152,6 +152,43 @@ fn vtable_rearrange_for_update() {
     assert_eq!(expected, vtable);
 }
 
+#[cfg(feature = "mock")]
+#[test]
+fn vtable_rearrange_for_update_duplicate_keys() {
+    let mut vtable = VirtualTable::new();
+
+    // t: a (pk) b (shard key), the row is joined with two source rows
+    let pk_value = Value::from(1);
+    let old_sh_key_value = Value::from(2);
+    vtable.add_column(vcolumn_integer_user_non_null());
+    vtable.add_column(vcolumn_integer_user_non_null());
+    vtable.set_alias("t");
+    vtable.add_tuple(vec![
+        pk_value.clone(),
+        Value::from(3),
+        old_sh_key_value.clone(),
+    ]);
+    vtable.add_tuple(vec![
+        pk_value.clone(),
+        Value::from(4),
+        old_sh_key_value.clone(),
+    ]);
+
+    vtable.set_primary_key(&[0]).unwrap();
+
+    let engine = RouterRuntimeMock::new();
+    let new_shard_cols_positions: Vec<usize> = vec![1];
+    vtable
+        .rearrange_for_update(&engine, 1, &new_shard_cols_positions)
+        .unwrap();
+
+    // The first match is updated once.
+    assert_eq!(
+        vtable.get_tuples(),
+        &[vec![pk_value.clone(), Value::from(3)], vec![pk_value]]
+    );
+}
+
 #[test]
 fn vtable_add_missing_from1() {
     let mut vtable = VirtualTable::new();
@@ -530,3 +567,144 @@ fn vtable_values_types_casting_two_columns_numerical() {
 
     assert_eq!(actual_vtable, expected_vtable)
 }
+
+/// Test sorting of the tuples gathered from two storages:
+/// each storage stream is already sorted, `NULL`s and numbers
+/// of different types must be merged consistently.
+#[test]
+fn vtable_sort_by_column_with_nulls() {
+    let storage1 = vec![
+        vec![Value::Null],
+        vec![Value::Integer(1)],
+        vec![Value::from(decimal!(2.5))],
+        vec![Value::Integer(7)],
+    ];
+    let storage2 = vec![
+        vec![Value::Null],
+        vec![Value::Double(0.5_f64.into())],
+        vec![Value::Integer(3)],
+    ];
+
+    let mut vtable = VirtualTable::new();
+    vtable.add_column(VTableColumn {
+        r#type: DerivedType::new(UnrestrictedType::Any),
+        role: ColumnRole::User,
+        is_nullable: true,
+    });
+    for tuple in storage1.iter().chain(storage2.iter()) {
+        vtable.add_tuple(tuple.clone());
+    }
+
+    vtable
+        .sort_by_column(0, &OrderByType::Asc, NullsOrder::First, None)
+        .unwrap();
+    assert_eq!(
+        vtable.get_tuples(),
+        &[
+            vec![Value::Null],
+            vec![Value::Null],
+            vec![Value::Double(0.5_f64.into())],
+            vec![Value::Integer(1)],
+            vec![Value::from(decimal!(2.5))],
+            vec![Value::Integer(3)],
+            vec![Value::Integer(7)],
+        ]
+    );
+
+    vtable
+        .sort_by_column(0, &OrderByType::Desc, NullsOrder::Last, None)
+        .unwrap();
+    assert_eq!(
+        vtable.get_tuples(),
+        &[
+            vec![Value::Integer(7)],
+            vec![Value::Integer(3)],
+            vec![Value::from(decimal!(2.5))],
+            vec![Value::Integer(1)],
+            vec![Value::Double(0.5_f64.into())],
+            vec![Value::Null],
+            vec![Value::Null],
+        ]
+    );
+
+    vtable
+        .sort_by_column(0, &OrderByType::Asc, NullsOrder::Last, None)
+        .unwrap();
+    assert_eq!(vtable.get_tuples()[0], vec![Value::Double(0.5_f64.into())]);
+    assert_eq!(vtable.get_tuples()[5], vec![Value::Null]);
+    assert_eq!(vtable.get_tuples()[6], vec![Value::Null]);
+}
+
+/// Test merging the streams of two storages: each stream is already
+/// sorted, the combined order must keep the order of every stream and
+/// place `NULL`s as requested no matter which storage answered first.
+#[test]
+fn vtable_sort_by_column_merges_storage_streams() {
+    // Both streams are sorted in descending order with `NULL`s first,
+    // the second column names the storage and the position in its stream.
+    let storage1 = vec![
+        vec![Value::Null, Value::from("s1-0")],
+        vec![Value::Integer(7), Value::from("s1-1")],
+        vec![Value::from(decimal!(2.5)), Value::from("s1-2")],
+        vec![Value::Integer(1), Value::from("s1-3")],
+    ];
+    let storage2 = vec![
+        vec![Value::Null, Value::from("s2-0")],
+        vec![Value::Integer(3), Value::from("s2-1")],
+        vec![Value::Double(1.0_f64.into()), Value::from("s2-2")],
+        vec![Value::Double(0.5_f64.into()), Value::from("s2-3")],
+    ];
+    let merge = |first: &[Vec<Value>], second: &[Vec<Value>]| {
+        let mut vtable = VirtualTable::new();
+        for _ in 0..2 {
+            vtable.add_column(VTableColumn {
+                r#type: DerivedType::new(UnrestrictedType::Any),
+                role: ColumnRole::User,
+                is_nullable: true,
+            });
+        }
+        for tuple in first.iter().chain(second) {
+            vtable.add_tuple(tuple.clone());
+        }
+        vtable
+            .sort_by_column(0, &OrderByType::Desc, NullsOrder::First, None)
+            .unwrap();
+        vtable
+            .get_tuples()
+            .iter()
+            .map(|tuple| tuple[1].clone())
+            .collect::<Vec<_>>()
+    };
+
+    // Equal values (`NULL`s, 1 and 1.0) keep the order the streams arrived in.
+    assert_eq!(
+        merge(&storage1, &storage2),
+        ["s1-0", "s2-0", "s1-1", "s2-1", "s1-2", "s1-3", "s2-2", "s2-3"].map(Value::from)
+    );
+    assert_eq!(
+        merge(&storage2, &storage1),
+        ["s2-0", "s1-0", "s1-1", "s2-1", "s1-2", "s2-2", "s1-3", "s2-3"].map(Value::from)
+    );
+}
+
+#[test]
+fn vtable_multiset_ops() {
+    let new_vtable = |values: &[i64]| {
+        let mut vtable = VirtualTable::new();
+        vtable.add_column(vcolumn_integer_user_non_null());
+        for value in values {
+            vtable.add_tuple(vec![Value::from(*value)]);
+        }
+        vtable
+    };
+    let left = new_vtable(&[1, 1, 1, 2, 2, 3]);
+    let right = new_vtable(&[1, 1, 2, 4]);
+
+    let mut intersect = left.clone();
+    intersect.intersect_all(&right);
+    assert_eq!(intersect.get_tuples(), new_vtable(&[1, 1, 2]).get_tuples());
+
+    let mut except = left;
+    except.except_all(&right);
+    assert_eq!(except.get_tuples(), new_vtable(&[1, 2, 3]).get_tuples());
+}
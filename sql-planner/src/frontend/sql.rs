@@ -9,7 +9,7 @@ use crate::ir::node::deallocate::Deallocate;
 use crate::ir::node::tcl::Tcl;
 use crate::ir::node::{
     Alias, AlterColumn, AlterTable, AlterTableOp, AnonymousBlock, Backup, BlockStatement, Bound,
-    BoundType, Frame, FrameType, GroupBy, Node32, Over, Parameter, Reference,
+    BoundType, Comment, Frame, FrameType, GroupBy, Node32, Over, Parameter, Reference,
     ReferenceAsteriskSource, ReferenceTarget, RenameIndex, Row, ScalarFunction, SubQueryReference,
     TimeParameters, Timestamp, TruncateTable, Values, ValuesRow, Window,
 };
@@ -31,7 +31,9 @@ use tarantool::index::{IndexType, RtreeIndexDistanceType};
 
 use crate::errors::Entity::AST;
 use crate::errors::{Action, Entity, SbroadError};
-use crate::executor::engine::helpers::{normalize_name_from_sql, to_user};
+use crate::executor::engine::helpers::{
+    generate_series, normalize_name, normalize_name_from_sql, to_user, IdentifierCase,
+};
 use crate::executor::engine::Metadata;
 use crate::frontend::sql::ast::{
     AbstractSyntaxTree, ParseNode, ParseNodes, ParseTree, Rule, StackParseNode,
@@ -42,7 +44,10 @@ use crate::frontend::Ast;
 use crate::ir::acl::{AlterOption, AuditPolicyOption};
 use crate::ir::acl::{GrantRevokeType, Privilege};
 use crate::ir::aggregates::AggregateKind;
-use crate::ir::ddl::{AlterSystemType, ColumnDef, SetParamScopeType, SetParamValue};
+use crate::ir::ddl::{
+    AlterSystemType, CheckDef, ColumnDef, DefaultDef, NotificationCommand, SetParamScopeType,
+    SetParamValue, TransactionIsolationLevel,
+};
 use crate::ir::ddl::{Language, ParamDef};
 use crate::ir::expression::{
     ColumnPositionMap, ColumnWithScan, ColumnsRetrievalSpec, ExpressionId, FunctionFeature,
@@ -59,11 +64,13 @@ use crate::ir::node::relational::{MutRelational, Relational};
 use crate::ir::node::{
     AlterSystem, AlterUser, AuditPolicy, BoolExpr, CallProcedure, Constant, CountAsterisk,
     CreateIndex, CreateProc, CreateRole, CreateTable, CreateUser, DropIndex, DropProc, DropRole,
-    DropTable, DropUser, GrantPrivilege, Node, NodeId, RenameRoutine, RevokePrivilege, ScanCte,
-    ScanRelation, SetParam, SetTransaction, Trim,
+    DropTable, DropUser, GrantPrivilege, IndexColumn, Node, NodeId, Notification, Projection,
+    RenameRoutine, RevokePrivilege, SampleMethod, ScanCte, ScanRelation, ScanSubQuery, SetParam,
+    SetTransaction, TableSample, Trim,
 };
 use crate::ir::operator::{
-    Arithmetic, Bool, ConflictStrategy, JoinKind, OrderByElement, OrderByEntity, OrderByType, Unary,
+    Arithmetic, Bool, Collation, ConflictStrategy, JoinKind, NullsOrder, OrderByElement,
+    OrderByEntity, OrderByType, Unary,
 };
 use crate::ir::options::{OptionKind, OptionParamValue, OptionSpec};
 use crate::ir::relation::{Column, ColumnRole, TableKind};
@@ -74,15 +81,18 @@ use crate::ir::tree::traversal::{
 use crate::ir::types::CastType;
 use crate::ir::types::DomainType;
 use crate::ir::value::Value;
-use crate::ir::ExplainType::{Explain, ExplainQueryPlan, ExplainQueryPlanFmt};
-use crate::ir::{node::plugin, Plan};
+use crate::ir::ExplainType::{Explain, ExplainCosts, ExplainQueryPlan, ExplainQueryPlanFmt};
+use crate::ir::{
+    node::plugin, CurrentOf, Plan, RowLockMode, SelectInto, DEFAULT_MAX_EXPRESSION_DEPTH,
+    MAX_PARSER_NESTING_DEPTH,
+};
 use crate::warn;
 use sql_type_system::error::Error as TypeSystemError;
 use tarantool::auth::AuthMethod;
 use tarantool::datetime::Datetime;
 use tarantool::decimal::Decimal;
 use tarantool::space::SpaceEngineType;
-use type_system::{get_parameter_derived_types, TypeAnalyzer};
+use type_system::{coerce_set_operation_columns, get_parameter_derived_types, TypeAnalyzer};
 
 // DDL timeout in seconds (1 day).
 const DEFAULT_TIMEOUT_F64: f64 = 24.0 * 60.0 * 60.0;
@@ -223,9 +233,188 @@ pub fn get_real_function_name(name_from_sql: &str) -> Option<&'static str> {
         .map(|mapping| mapping.tarantool_symbol)
 }
 
+/// Procedures called by the SQL generated for the storages, e.g. the casts
+/// between text and boolean, with their parameter lists. Unlike the ones of
+/// [`FUNCTION_NAME_MAPPINGS`], they can't be called by name in the queries.
+pub const STORAGE_SQL_PROCEDURES: &[(&str, &[&str])] = &[
+    ("proc_sql_text_to_bool", &["string"]),
+    ("proc_sql_bool_to_text", &["boolean"]),
+];
+
+/// Get the parameter list of the procedure exported to tarantool SQL,
+/// `None` if the procedure is not used in SQL.
+pub fn sql_procedure_parameter_list(rust_procedure: &str) -> Option<&'static [&'static str]> {
+    FUNCTION_NAME_MAPPINGS
+        .iter()
+        .find(|mapping| mapping.rust_procedure == rust_procedure)
+        .map(|mapping| mapping.parameter_list)
+        .or_else(|| {
+            STORAGE_SQL_PROCEDURES
+                .iter()
+                .find(|(name, _)| *name == rust_procedure)
+                .map(|(_, parameter_list)| *parameter_list)
+        })
+}
+
 // Helper map to store CTE node ids by their names.
 type CTEs = AHashMap<SmolStr, NodeId>;
 
+/// Terms of the recursive CTE body (`base UNION [ALL] recursive`).
+struct RecursiveCteTerms {
+    /// AST id of the non-recursive term.
+    base: usize,
+    /// AST id of the recursive term.
+    recursive: usize,
+    /// Whether the terms are combined with UNION (not UNION ALL).
+    remove_duplicates: bool,
+}
+
+/// Helper to plan the CTEs defined in `WITH RECURSIVE` clauses, that refer to themselves.
+#[derive(Default)]
+struct RecursiveCtes {
+    /// Map of { AST id of the self-reference -> AST id of the CTE }.
+    references: AHashMap<usize, usize>,
+    /// Map of { AST id of the CTE -> terms of its body }.
+    terms: AHashMap<usize, RecursiveCteTerms>,
+    /// Map of { AST id of the CTE -> plan id of its working table }.
+    working_tables: AHashMap<usize, NodeId>,
+}
+
+impl RecursiveCtes {
+    /// Find recursive CTEs in the AST before it is converted to the plan:
+    /// self-references must be resolved before the CTE itself is built.
+    ///
+    /// # Errors
+    /// - recursive CTE body is not of the form `base UNION [ALL] recursive`
+    /// - self-reference appears in the non-recursive term or more than once
+    fn collect(ast: &AbstractSyntaxTree) -> Result<Self, SbroadError> {
+        let mut recursive_ctes = Self::default();
+        for node in &ast.nodes.arena {
+            if node.rule != Rule::SelectFull {
+                continue;
+            }
+            let mut is_recursive = false;
+            for child_id in &node.children {
+                match ast.nodes.get_node(*child_id)?.rule {
+                    Rule::Recursive => is_recursive = true,
+                    Rule::Cte if is_recursive => recursive_ctes.add_cte(ast, *child_id)?,
+                    _ => {}
+                }
+            }
+        }
+        Ok(recursive_ctes)
+    }
+
+    fn add_cte(&mut self, ast: &AbstractSyntaxTree, cte_id: usize) -> Result<(), SbroadError> {
+        let cte = ast.nodes.get_node(cte_id)?;
+        let name_id = *cte.children.first().expect("CTE must have a name");
+        let name = parse_normalized_identifier(ast, name_id)?;
+        let body_id = *cte.children.last().expect("CTE must have a body");
+        let body = ast.nodes.get_node(body_id)?;
+        if body.rule != Rule::SelectStatement {
+            return Ok(());
+        }
+        let references = Self::find_references(ast, body_id, &name)?;
+        if references.is_empty() {
+            // CTE doesn't refer to itself, so it is planned as a regular one.
+            return Ok(());
+        }
+
+        let invalid = |reason: &str| {
+            SbroadError::Invalid(
+                Entity::Cte,
+                Some(format_smolstr!(
+                    "recursive CTE {} {reason}",
+                    to_user(name.as_str())
+                )),
+            )
+        };
+        let invalid_form =
+            || invalid("must have the form: non-recursive term UNION [ALL] recursive term");
+        let [select_id] = body.children.as_slice() else {
+            return Err(invalid_form());
+        };
+        let select = ast.nodes.get_node(*select_id)?;
+        let [base, op, recursive] = select.children.as_slice() else {
+            return Err(invalid_form());
+        };
+        let remove_duplicates = match ast.nodes.get_node(*op)?.rule {
+            Rule::UnionOp => true,
+            Rule::UnionAllOp => false,
+            _ => return Err(invalid_form()),
+        };
+        if !Self::find_references(ast, *base, &name)?.is_empty() {
+            return Err(invalid(
+                "must not refer to itself in the non-recursive term",
+            ));
+        }
+        if references.len() > 1 {
+            return Err(invalid("must not refer to itself more than once"));
+        }
+
+        for reference_id in references {
+            self.references.insert(reference_id, cte_id);
+        }
+        self.terms.insert(
+            cte_id,
+            RecursiveCteTerms {
+                base: *base,
+                recursive: *recursive,
+                remove_duplicates,
+            },
+        );
+        Ok(())
+    }
+
+    /// Find the scans of the CTE with the given name in the AST subtree.
+    fn find_references(
+        ast: &AbstractSyntaxTree,
+        top_id: usize,
+        name: &SmolStr,
+    ) -> Result<Vec<usize>, SbroadError> {
+        let mut references = Vec::new();
+        let mut stack = vec![top_id];
+        while let Some(id) = stack.pop() {
+            let node = ast.nodes.get_node(id)?;
+            if node.rule == Rule::ScanCteOrTable && parse_normalized_identifier(ast, id)? == *name {
+                references.push(id);
+            }
+            stack.extend(node.children.iter().copied());
+        }
+        Ok(references)
+    }
+
+    /// Get the working table of the recursive CTE, creating it on the first call.
+    /// The working table is a CTE over the non-recursive term.
+    fn get_or_add_working_table(
+        &mut self,
+        ast: &AbstractSyntaxTree,
+        cte_id: usize,
+        name: SmolStr,
+        map: &Translation,
+        plan: &mut Plan,
+    ) -> Result<NodeId, SbroadError> {
+        if let Some(working_table_id) = self.working_tables.get(&cte_id) {
+            return Ok(*working_table_id);
+        }
+        let terms = self
+            .terms
+            .get(&cte_id)
+            .expect("recursive CTE must have terms");
+        let base_id = map.get(terms.base)?;
+        let cte = ast.nodes.get_node(cte_id)?;
+        let mut columns = Vec::with_capacity(cte.children.len());
+        for child_id in &cte.children {
+            if ast.nodes.get_node(*child_id)?.rule == Rule::CteColumn {
+                columns.push(parse_normalized_identifier(ast, *child_id)?);
+            }
+        }
+        let working_table_id = plan.add_cte(base_id, name, columns)?;
+        self.working_tables.insert(cte_id, working_table_id);
+        Ok(working_table_id)
+    }
+}
+
 #[allow(clippy::uninlined_format_args)]
 fn get_timeout(ast: &AbstractSyntaxTree, node_id: usize) -> Result<Decimal, SbroadError> {
     let param_node = ast.nodes.get_node(node_id)?;
@@ -622,6 +811,7 @@ fn parse_create_index(
     let mut table_name = SmolStr::default();
     let mut columns = Vec::new();
     let mut unique = false;
+    let mut nulls_distinct = true;
     let mut index_type = IndexType::Tree;
     let mut bloom_fpr = None;
     let mut page_size = None;
@@ -692,6 +882,8 @@ fn parse_create_index(
         let child_node = ast.nodes.get_node(*child_id)?;
         match child_node.rule {
             Rule::Unique => unique = true,
+            Rule::NullsDistinct => nulls_distinct = true,
+            Rule::NullsNotDistinct => nulls_distinct = false,
             Rule::IfNotExists => if_not_exists = true,
             Rule::Identifier => name = parse_identifier(ast, *child_id)?,
             Rule::Table => table_name = parse_identifier(ast, *child_id)?,
@@ -711,10 +903,33 @@ fn parse_create_index(
                 for part_id in &parts_node.children {
                     let single_part_node = ast.nodes.get_node(*part_id)?;
                     assert!(
-                        single_part_node.rule == Rule::Identifier,
+                        single_part_node.rule == Rule::IndexPart,
                         "Unexpected part node: {single_part_node:?}"
                     );
-                    columns.push(parse_identifier(ast, *part_id)?);
+                    let mut column = IndexColumn {
+                        name: SmolStr::default(),
+                        collation: None,
+                    };
+                    for part_child_id in &single_part_node.children {
+                        let part_child = ast.nodes.get_node(*part_child_id)?;
+                        match part_child.rule {
+                            Rule::Identifier => {
+                                column.name = parse_identifier(ast, *part_child_id)?;
+                            }
+                            Rule::CollatePostfix => {
+                                let name_id = part_child
+                                    .children
+                                    .first()
+                                    .expect("Expected Identifier under CollatePostfix.");
+                                let name = normalize_name_from_sql(parse_string_value_node(
+                                    ast, *name_id,
+                                )?);
+                                column.collation = Some(Collation::from_name(&name)?);
+                            }
+                            _ => panic!("Unexpected index part rule: {part_child:?}"),
+                        }
+                    }
+                    columns.push(column);
                 }
             }
             Rule::IndexOptions => {
@@ -772,11 +987,18 @@ fn parse_create_index(
             _ => panic!("Unexpected index rule: {child_node:?}"),
         }
     }
+    if !nulls_distinct && !unique {
+        return Err(SbroadError::Invalid(
+            Entity::Index,
+            Some("NULLS NOT DISTINCT is allowed only for unique indexes".into()),
+        ));
+    }
     let index = CreateIndex {
         name,
         table_name,
         columns,
         unique,
+        nulls_distinct,
         if_not_exists,
         index_type,
         bloom_fpr,
@@ -855,6 +1077,42 @@ fn parse_rename_index(
     })
 }
 
+fn parse_comment(ast: &AbstractSyntaxTree, node: &ParseNode) -> Result<Comment, SbroadError> {
+    assert_eq!(node.rule, Rule::Comment);
+    let mut table_name = SmolStr::default();
+    let mut column_name = None;
+    let mut comment = None;
+    let mut timeout = get_default_timeout();
+    for child_id in &node.children {
+        let child_node = ast.nodes.get_node(*child_id)?;
+        match child_node.rule {
+            Rule::CommentOnTable | Rule::CommentOnColumn => {
+                for target_id in &child_node.children {
+                    let target_node = ast.nodes.get_node(*target_id)?;
+                    match target_node.rule {
+                        Rule::Table => table_name = parse_identifier(ast, *target_id)?,
+                        Rule::Identifier => column_name = Some(parse_identifier(ast, *target_id)?),
+                        _ => panic!("Unexpected comment target node: {target_node:?}"),
+                    }
+                }
+            }
+            Rule::SingleQuotedString => {
+                let text = retrieve_string_literal(ast, *child_id)?;
+                comment = Some(escape_single_quotes(&text));
+            }
+            Rule::Null => comment = None,
+            Rule::Timeout => timeout = get_timeout(ast, *child_id)?,
+            _ => panic!("Unexpected comment node: {child_node:?}"),
+        }
+    }
+    Ok(Comment {
+        table_name,
+        column_name,
+        comment,
+        timeout,
+    })
+}
+
 fn parse_column_def_type(
     node: &ParseNode,
     ast: &AbstractSyntaxTree,
@@ -883,6 +1141,17 @@ fn parse_column_def_type(
                 Rule::TypeJSON => DomainType::Json,
                 Rule::TypeString | Rule::TypeVarchar | Rule::TypeText => DomainType::String,
                 Rule::TypeUuid => DomainType::Uuid,
+                Rule::TypeBytea => DomainType::Bytea,
+                // Storages have no such types, they are available in CAST only.
+                Rule::TypeDate | Rule::TypeTime | Rule::TypeTimestamp => {
+                    let name = CastType::try_from(&node.rule)?
+                        .temporal_name()
+                        .expect("temporal type expected");
+                    return Err(SbroadError::Unsupported(
+                        Entity::Type,
+                        Some(format_smolstr!("{name} columns, use datetime instead")),
+                    ));
+                }
                 _ => {
                     panic!("Met unexpected rule under Type: {:?}.", node.rule);
                 }
@@ -921,6 +1190,9 @@ fn parse_create_table(
     let mut unlogged = DEFAULT_UNLOGGED;
     let mut wait_applied_globally = DEFAULT_WAIT_APPLIED_GLOBALLY;
     let mut pk_contains_bucket_id = false;
+    let mut identity: Option<SmolStr> = None;
+    let mut checks: Vec<CheckDef> = Vec::new();
+    let mut defaults: Vec<DefaultDef> = Vec::new();
 
     let nullable_primary_key_column_error = Err(SbroadError::Invalid(
         Entity::Column,
@@ -967,8 +1239,54 @@ fn parse_create_table(
                     for def_child_id in column_def_children.iter().skip(2) {
                         let def_child_node = ast.nodes.get_node(*def_child_id)?;
                         match def_child_node.rule {
+                            Rule::ColumnDefIdentity => {
+                                if identity.is_some() {
+                                    return Err(SbroadError::Invalid(
+                                        Entity::Column,
+                                        Some(format_smolstr!(
+                                            "Table can have only one identity column."
+                                        )),
+                                    ));
+                                }
+                                if !matches!(data_type, DomainType::Integer | DomainType::Unsigned)
+                                {
+                                    return Err(SbroadError::Invalid(
+                                        Entity::Column,
+                                        Some(format_smolstr!(
+                                            "Identity column {name} must be of integer or unsigned type."
+                                        )),
+                                    ));
+                                }
+                                // Infer not null on identity column
+                                is_nullable = false;
+                                identity = Some(name.clone());
+                            }
+                            Rule::ColumnDefDefault => {
+                                if identity.as_ref() == Some(&name) {
+                                    return Err(SbroadError::Invalid(
+                                        Entity::Column,
+                                        Some(format_smolstr!(
+                                            "Identity column {name} can't have a default value."
+                                        )),
+                                    ));
+                                }
+                                defaults.push(parse_column_default(
+                                    ast,
+                                    def_child_node,
+                                    &name,
+                                    &data_type,
+                                )?);
+                            }
                             Rule::ColumnDefIsNull => {
                                 is_nullable = parse_column_null_or_not_null(ast, def_child_node)?;
+                                if is_nullable && identity.as_ref() == Some(&name) {
+                                    return Err(SbroadError::Invalid(
+                                        Entity::Column,
+                                        Some(format_smolstr!(
+                                            "Identity column {name} mustn't be nullable."
+                                        )),
+                                    ));
+                                }
                                 if is_nullable {
                                     let name = name.clone();
                                     explicit_null_columns.insert(name);
@@ -987,6 +1305,22 @@ fn parse_create_table(
                                 is_nullable = false;
                                 pk_keys.push(name);
                             }
+                            Rule::ColumnDefCheck => {
+                                let check = parse_column_check(
+                                    ast,
+                                    def_child_node,
+                                    &table_name,
+                                    &name,
+                                    &data_type,
+                                )?;
+                                if checks.iter().any(|c| c.name == check.name) {
+                                    return Err(SbroadError::DuplicatedValue(format_smolstr!(
+                                        "Constraint {} is specified more than once.",
+                                        check.name
+                                    )));
+                                }
+                                checks.push(check);
+                            }
                             _ => panic!("Unexpected rules met under ColumnDef."),
                         }
                     }
@@ -1241,6 +1575,246 @@ fn parse_create_table(
         timeout,
         tier,
         pk_contains_bucket_id,
+        identity,
+        checks,
+        defaults,
+    })
+}
+
+/// Derives the table created by `SELECT ... INTO` from the query output
+/// and the statement filling it from the query text without the `INTO`
+/// clause. The primary key given in the `INTO` clause is also the
+/// sharding key.
+fn parse_select_into(
+    ast: &AbstractSyntaxTree,
+    into_id: usize,
+    query: &str,
+    plan: &Plan,
+) -> Result<SelectInto, SbroadError> {
+    let into = ast.nodes.get_node(into_id)?;
+    let mut name = None;
+    let mut primary_key: Vec<SmolStr> = Vec::new();
+    for child_id in &into.children {
+        let child = ast.nodes.get_node(*child_id)?;
+        match child.rule {
+            Rule::Table => name = Some(parse_identifier(ast, *child_id)?),
+            Rule::PrimaryKey => {
+                // First child is a `PrimaryKeyMark` that we should skip.
+                for pk_col_id in child.children.iter().skip(1) {
+                    primary_key.push(parse_identifier(ast, *pk_col_id)?);
+                }
+            }
+            _ => {}
+        }
+    }
+    let name = name.expect("INTO clause must have a table name");
+
+    let mut format: Vec<ColumnDef> = Vec::new();
+    for (column_name, column_type) in plan.output_columns_with_types()? {
+        if column_name == DEFAULT_BUCKET_ID_COLUMN_NAME {
+            return Err(SbroadError::Invalid(
+                Entity::Column,
+                Some(format_smolstr!(
+                    "{DEFAULT_BUCKET_ID_COLUMN_NAME} is reserved for system use in sharded tables. Choose another name."
+                )),
+            ));
+        }
+        if format.iter().any(|c| c.name == column_name) {
+            return Err(SbroadError::DuplicatedValue(format_smolstr!(
+                "Column {column_name} is specified more than once."
+            )));
+        }
+        let data_type = match column_type.get() {
+            Some(UnrestrictedType::Boolean) => DomainType::Boolean,
+            Some(UnrestrictedType::Datetime) => DomainType::Datetime,
+            Some(UnrestrictedType::Decimal) => DomainType::Decimal,
+            Some(UnrestrictedType::Double) => DomainType::Double,
+            Some(UnrestrictedType::Integer) => DomainType::Integer,
+            Some(UnrestrictedType::String) => DomainType::String,
+            Some(UnrestrictedType::Uuid) => DomainType::Uuid,
+            Some(UnrestrictedType::Bytea) => DomainType::Bytea,
+            Some(ty) => {
+                return Err(SbroadError::Unsupported(
+                    Entity::Column,
+                    Some(format_smolstr!(
+                        "Column {column_name} of type {ty} can't be created by SELECT INTO."
+                    )),
+                ))
+            }
+            None => {
+                return Err(SbroadError::Invalid(
+                    Entity::Column,
+                    Some(format_smolstr!(
+                        "Type of column {column_name} can't be determined, cast it explicitly."
+                    )),
+                ))
+            }
+        };
+        format.push(ColumnDef {
+            name: column_name,
+            data_type,
+            // All columns but the primary key ones are nullable.
+            is_nullable: true,
+        });
+    }
+
+    if primary_key.is_empty() {
+        return Err(SbroadError::Invalid(
+            Entity::Query,
+            Some("SELECT INTO requires PRIMARY KEY of the created table.".into()),
+        ));
+    }
+    for (i, key) in primary_key.iter().enumerate() {
+        if primary_key[..i].contains(key) {
+            return Err(SbroadError::DuplicatedValue(format_smolstr!(
+                "Primary key column {key} is specified more than once."
+            )));
+        }
+        let Some(column) = format.iter_mut().find(|c| &c.name == key) else {
+            return Err(SbroadError::Invalid(
+                Entity::Column,
+                Some(format_smolstr!(
+                    "Primary key column {key} not found in the query output."
+                )),
+            ));
+        };
+        if !column.data_type.is_scalar() {
+            return Err(SbroadError::Invalid(
+                Entity::Column,
+                Some(format_smolstr!(
+                    "Sharding key column {key} is not of scalar type."
+                )),
+            ));
+        }
+        column.is_nullable = false;
+    }
+
+    let quote = |name: &str| format_smolstr!("\"{}\"", name.replace('"', "\"\""));
+    let columns: Vec<_> = format.iter().map(|c| quote(&c.name)).collect();
+    let insert = format_smolstr!(
+        "INSERT INTO {} ({}) {query}",
+        quote(&name),
+        columns.join(", ")
+    );
+    let create_table = CreateTable {
+        name,
+        format,
+        primary_key: primary_key.clone(),
+        sharding_key: Some(primary_key),
+        engine_type: SpaceEngineType::default(),
+        unlogged: DEFAULT_UNLOGGED,
+        if_not_exists: DEFAULT_IF_NOT_EXISTS,
+        wait_applied_globally: DEFAULT_WAIT_APPLIED_GLOBALLY,
+        timeout: get_default_timeout(),
+        tier: None,
+        pk_contains_bucket_id: false,
+        identity: None,
+        checks: Vec::new(),
+        defaults: Vec::new(),
+    };
+    Ok(SelectInto {
+        create_table,
+        insert,
+        params: Vec::new(),
+    })
+}
+
+/// Parses a `ColumnDefCheck`: `[CONSTRAINT <name>] CHECK (<column> <op> <value>)`.
+/// The constraint must refer to the column it is declared on, and the constant
+/// must be castable to the column type. Unnamed constraints are named
+/// `<table>_<column>_check` like in PostgreSQL.
+fn parse_column_check(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+    table_name: &str,
+    column_name: &SmolStr,
+    data_type: &DomainType,
+) -> Result<CheckDef, SbroadError> {
+    let mut name = format_smolstr!("{table_name}_{column_name}_check");
+    let mut column = None;
+    let mut op = None;
+    let mut text = None;
+    for child_id in &node.children {
+        let child = ast.nodes.get_node(*child_id)?;
+        match child.rule {
+            Rule::CheckName => name = parse_identifier(ast, *child_id)?,
+            Rule::Identifier => column = Some(parse_identifier(ast, *child_id)?),
+            Rule::CheckOp => {
+                let op_id = *child.children.first().expect("CheckOp must have a child");
+                op = Some(Bool::from(parse_string_value_node(ast, op_id)?)?);
+            }
+            Rule::CheckValue => text = Some(parse_constraint_expr(ast, child)?),
+            _ => panic!("Unexpected rule met under ColumnDefCheck."),
+        }
+    }
+    let column = column.expect("ColumnDefCheck must contain a column");
+    if &column != column_name {
+        return Err(SbroadError::Invalid(
+            Entity::Column,
+            Some(format_smolstr!(
+                "CHECK constraint {name} of column {column_name} refers to column {column}."
+            )),
+        ));
+    }
+    let text = text.expect("ColumnDefCheck must contain a value");
+    let value = Value::from(text.as_str())
+        .cast(UnrestrictedType::from(data_type))
+        .map_err(|_| {
+            SbroadError::Invalid(
+                Entity::Value,
+                Some(format_smolstr!(
+                    "CHECK constraint {name} compares column {column_name} of type {data_type} with {text}."
+                )),
+            )
+        })?;
+    Ok(CheckDef {
+        name,
+        column,
+        op: op.expect("ColumnDefCheck must contain an operator"),
+        value,
+    })
+}
+
+/// Parses a `ColumnDefDefault`: `DEFAULT <value>`. The constant must be
+/// castable to the column type.
+fn parse_column_default(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+    column_name: &SmolStr,
+    data_type: &DomainType,
+) -> Result<DefaultDef, SbroadError> {
+    let value_id = *node
+        .children
+        .first()
+        .expect("ColumnDefDefault must have a value");
+    let text = parse_constraint_expr(ast, ast.nodes.get_node(value_id)?)?;
+    let value = Value::from(text.as_str())
+        .cast(UnrestrictedType::from(data_type))
+        .map_err(|_| {
+            SbroadError::Invalid(
+                Entity::Value,
+                Some(format_smolstr!(
+                    "Default value {text} doesn't match type {data_type} of column {column_name}."
+                )),
+            )
+        })?;
+    Ok(DefaultDef {
+        column: column_name.clone(),
+        value,
+    })
+}
+
+/// Returns the text of a `CheckValue` constant of a CHECK constraint or a
+/// column default.
+fn parse_constraint_expr(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<SmolStr, SbroadError> {
+    let value_id = *node.children.first().expect("CheckValue must have a child");
+    let value_node = ast.nodes.get_node(value_id)?;
+    Ok(match value_node.rule {
+        Rule::SingleQuotedString => escape_single_quotes(&retrieve_string_literal(ast, value_id)?),
+        _ => parse_string_value_node(ast, value_id)?.into(),
     })
 }
 
@@ -1346,21 +1920,24 @@ fn parse_alter_table(
                             }
                         }
 
-                        Rule::AlterTableColumnDrop | Rule::AlterTableColumnAlter => {
+                        Rule::AlterTableColumnDrop => {
+                            let mut if_exists = DEFAULT_IF_EXISTS;
+                            for id in &node.children {
+                                let node = ast.nodes.get_node(*id)?;
+                                match node.rule {
+                                    Rule::IfExists => if_exists = true,
+                                    Rule::AlterTableColumnDropParam => {
+                                        let name = parse_identifier(ast, node.first_child())?;
+                                        column_ops.push(AlterColumn::Drop { name, if_exists });
+                                    }
+                                    rule => unreachable!("pest should not allow rule: {rule:?}"),
+                                }
+                            }
+                        }
+                        Rule::AlterTableColumnAlter => {
                             return Err(SbroadError::Unsupported(
                                 Entity::Ddl,
-                                Some(format_smolstr!(
-                                    "`ALTER TABLE _ {}` is not yet supported",
-                                    match node.rule {
-                                        Rule::AlterTableColumnDrop => {
-                                            "DROP COLUMN"
-                                        }
-                                        Rule::AlterTableColumnAlter => {
-                                            "ALTER COLUMN"
-                                        }
-                                        _ => unreachable!(),
-                                    }
-                                )),
+                                Some("`ALTER TABLE _ ALTER COLUMN` is not yet supported".into()),
                             ))
                         }
                         rule => unreachable!("pest should not allow rule: {rule:?}"),
@@ -1530,7 +2107,24 @@ fn parse_set_param(ast: &AbstractSyntaxTree, node: &ParseNode) -> Result<SetPara
                             .first()
                             .expect("Param name expected under NamedParam.");
                         let param_name = parse_identifier(ast, *param_name_id)?;
-                        param_value = Some(SetParamValue::NamedParam { name: param_name });
+                        if param_name.eq_ignore_ascii_case("application_name") {
+                            let value_id = conf_param_child
+                                .children
+                                .get(1)
+                                .expect("Param value expected under NamedParam.");
+                            let value = parse_named_param_value(ast, *value_id)?;
+                            param_value = Some(SetParamValue::ApplicationName { value });
+                        } else if param_name.eq_ignore_ascii_case("plan_cache") {
+                            let value_id = conf_param_child
+                                .children
+                                .get(1)
+                                .expect("Param value expected under NamedParam.");
+                            let value = parse_named_param_value(ast, *value_id)?;
+                            let enabled = parse_plan_cache_value(&value)?;
+                            param_value = Some(SetParamValue::PlanCache { enabled });
+                        } else {
+                            param_value = Some(SetParamValue::NamedParam { name: param_name });
+                        }
                     }
                     Rule::TimeZoneParam => param_value = Some(SetParamValue::TimeZone),
                     _ => panic!("Unexpected rule met under ConfParam."),
@@ -1546,6 +2140,124 @@ fn parse_set_param(ast: &AbstractSyntaxTree, node: &ParseNode) -> Result<SetPara
     })
 }
 
+/// Text of a `SET` parameter value. `DEFAULT` resets the value to an empty string.
+fn parse_named_param_value(
+    ast: &AbstractSyntaxTree,
+    node_id: usize,
+) -> Result<SmolStr, SbroadError> {
+    let node = ast.nodes.get_node(node_id)?;
+    let value_id = *node
+        .children
+        .first()
+        .expect("NamedParamValue must have child.");
+    let value_node = ast.nodes.get_node(value_id)?;
+    match value_node.rule {
+        Rule::ParamValueDefault => Ok(SmolStr::default()),
+        Rule::SingleQuotedString => Ok(escape_single_quotes(&retrieve_string_literal(
+            ast, value_id,
+        )?)),
+        Rule::Identifier => parse_identifier(ast, value_id),
+        _ => Ok(parse_string_value_node(ast, value_id)?.into()),
+    }
+}
+
+/// Value of `SET plan_cache`. Like boolean parameters in Postgres, it accepts
+/// `on`/`off` and `true`/`false`; `DEFAULT` enables the cache.
+fn parse_plan_cache_value(value: &str) -> Result<bool, SbroadError> {
+    match value.to_lowercase().as_str() {
+        "" | "on" | "true" => Ok(true),
+        "off" | "false" => Ok(false),
+        _ => Err(SbroadError::Invalid(
+            Entity::Value,
+            Some(format_smolstr!(
+                "parameter \"plan_cache\" requires a boolean value, got: {value}"
+            )),
+        )),
+    }
+}
+
+fn parse_set_transaction(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<SetTransaction, SbroadError> {
+    let mut scope_type = SetParamScopeType::Local;
+    let mut isolation_level = None;
+    let sub_rule_id = node
+        .children
+        .first()
+        .expect("SetTransaction must have child.");
+    let sub_rule = ast.nodes.get_node(*sub_rule_id)?;
+    if let Rule::SetSessionCharacteristics = sub_rule.rule {
+        scope_type = SetParamScopeType::Session;
+    }
+    let mode_id = sub_rule
+        .children
+        .first()
+        .expect("Transaction mode expected under SetTransaction.");
+    let mode = ast.nodes.get_node(*mode_id)?;
+    if let Rule::TransactionMode = mode.rule {
+        let mode_child_id = mode
+            .children
+            .first()
+            .expect("TransactionMode must have child.");
+        let mode_child = ast.nodes.get_node(*mode_child_id)?;
+        if let Rule::IsolationLevel = mode_child.rule {
+            let level_id = mode_child
+                .children
+                .first()
+                .expect("Isolation level expected under IsolationLevel.");
+            let level = match ast.nodes.get_node(*level_id)?.rule {
+                Rule::ReadUncommited => TransactionIsolationLevel::ReadUncommitted,
+                Rule::ReadCommited => TransactionIsolationLevel::ReadCommitted,
+                Rule::RepeatableRead => TransactionIsolationLevel::RepeatableRead,
+                Rule::Serializable => TransactionIsolationLevel::Serializable,
+                _ => panic!("Unexpected rule met under IsolationLevel."),
+            };
+            isolation_level = Some(level);
+        }
+    }
+    Ok(SetTransaction {
+        scope_type,
+        isolation_level,
+        timeout: get_default_timeout(),
+    })
+}
+
+fn parse_notification(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<Notification, SbroadError> {
+    let mut channel = None;
+    let mut payload = SmolStr::default();
+    for child_id in &node.children {
+        let child_node = ast.nodes.get_node(*child_id)?;
+        match child_node.rule {
+            Rule::Identifier => channel = Some(parse_identifier(ast, *child_id)?),
+            Rule::UnlistenAll => channel = None,
+            Rule::SingleQuotedString => {
+                let text = retrieve_string_literal(ast, *child_id)?;
+                payload = escape_single_quotes(&text);
+            }
+            _ => panic!("Unexpected notification node: {child_node:?}"),
+        }
+    }
+    let command = match node.rule {
+        Rule::Listen => NotificationCommand::Listen {
+            channel: channel.expect("LISTEN must have a channel"),
+        },
+        Rule::Unlisten => NotificationCommand::Unlisten { channel },
+        Rule::Notify => NotificationCommand::Notify {
+            channel: channel.expect("NOTIFY must have a channel"),
+            payload,
+        },
+        _ => panic!("Unexpected notification rule: {:?}", node.rule),
+    };
+    Ok(Notification {
+        command,
+        timeout: get_default_timeout(),
+    })
+}
+
 fn parse_deallocate(ast: &AbstractSyntaxTree, node: &ParseNode) -> Result<Deallocate, SbroadError> {
     let param_name = if let Some(identifier_node_id) = node.children.first() {
         Some(parse_identifier(ast, *identifier_node_id)?)
@@ -1570,7 +2282,7 @@ fn parse_select_full<M: Metadata>(
     for child_id in &node.children {
         let child_node = ast.nodes.get_node(*child_id)?;
         match child_node.rule {
-            Rule::Cte => continue,
+            Rule::Recursive | Rule::Cte => continue,
             Rule::SelectStatement => {
                 top_id = Some(parse_select_statement(
                     ast,
@@ -1602,13 +2314,29 @@ fn parse_select_statement<M: Metadata>(
     let node = ast.nodes.get_node(node_id)?;
     assert_eq!(node.rule, Rule::SelectStatement);
     let mut top_id = None;
+    let mut select_id = None;
     let mut limit = None;
+    let mut offset = None;
+    let mut fetch = None;
+    let mut with_ties = false;
     for child_id in &node.children {
         let child_node = ast.nodes.get_node(*child_id)?;
         match child_node.rule {
             Rule::SelectWithOptionalContinuation => {
-                let select_id = map.get(*child_id)?;
-                top_id = Some(select_id);
+                select_id = Some(map.get(*child_id)?);
+                top_id = select_id;
+            }
+            Rule::LockingClause => {
+                let select_id = select_id.expect("LockingClause must follow the select");
+                let strength_id = child_node.children[0];
+                let (clause, mode) = match ast.nodes.get_node(strength_id)?.rule {
+                    Rule::ForUpdate => ("FOR UPDATE", RowLockMode::Update),
+                    Rule::ForShare => ("FOR SHARE", RowLockMode::Share),
+                    _ => unreachable!("Unexpected locking clause child: {child_node:?}"),
+                };
+                check_locking_clause(plan, select_id, clause)?;
+                // The strongest lock requested in the query is taken.
+                plan.row_lock = plan.row_lock.max(Some(mode));
             }
             Rule::Limit => {
                 let child_node = ast.nodes.get_node(child_node.children[0])?;
@@ -1618,6 +2346,23 @@ fn parse_select_statement<M: Metadata>(
                     _ => unreachable!("Unexpected limit child: {child_node:?}"),
                 }
             }
+            Rule::Offset => {
+                let child_node = ast.nodes.get_node(child_node.children[0])?;
+                offset = Some(parse_unsigned(child_node)?);
+            }
+            Rule::Fetch => {
+                // FETCH FIRST ROW ONLY is the same as FETCH FIRST 1 ROW ONLY.
+                fetch = Some(1);
+                for fetch_child_id in &child_node.children {
+                    let fetch_child = ast.nodes.get_node(*fetch_child_id)?;
+                    match fetch_child.rule {
+                        Rule::Unsigned => fetch = Some(parse_unsigned(fetch_child)?),
+                        Rule::FetchOnly => (),
+                        Rule::FetchWithTies => with_ties = true,
+                        _ => unreachable!("Unexpected fetch child: {fetch_child:?}"),
+                    }
+                }
+            }
             Rule::OrderBy => {
                 top_id = Some(ast.parse_order_by(
                     plan,
@@ -1633,19 +2378,79 @@ fn parse_select_statement<M: Metadata>(
         }
     }
     let top_id = top_id.expect("SelectStatement must have at least one child");
-    if let Some(limit) = limit {
-        // It's guaranteed from `parse_unsigned` that limit > 0, so cast is safe.
-        return plan.add_limit(top_id, limit as u64);
+    if limit.is_some() && fetch.is_some() {
+        return Err(SbroadError::Invalid(
+            Entity::Query,
+            Some("LIMIT and FETCH cannot be specified together".into()),
+        ));
+    }
+    let limit = limit.or(fetch);
+    if limit.is_some() || offset.is_some() {
+        // It's guaranteed from `parse_unsigned` that limit and offset are
+        // non-negative, so casts are safe.
+        let limit_id = plan.add_limit(
+            top_id,
+            limit.map(|limit| limit as u64),
+            offset.map_or(0, |offset| offset as u64),
+            with_ties,
+        )?;
+        if with_ties {
+            // Check that the rows tied with the last one can be determined.
+            plan.get_limit_sort_keys(limit_id)?;
+        }
+        return Ok(limit_id);
     }
     Ok(top_id)
 }
 
+/// Check that the locking clause (`FOR UPDATE`, `FOR SHARE`) can be applied
+/// to the select the same way Postgres does it.
+fn check_locking_clause(plan: &Plan, select_id: NodeId, clause: &str) -> Result<(), SbroadError> {
+    let conflict = match plan.get_relation_node(select_id)? {
+        Relational::Union(_)
+        | Relational::UnionAll(_)
+        | Relational::Except(_)
+        | Relational::Intersect(_) => Some("UNION/INTERSECT/EXCEPT"),
+        Relational::Projection(Projection {
+            output,
+            windows,
+            is_distinct,
+            group_by,
+            having,
+            ..
+        }) => {
+            if *is_distinct {
+                Some("DISTINCT clause")
+            } else if group_by.is_some() {
+                Some("GROUP BY clause")
+            } else if having.is_some() {
+                Some("HAVING clause")
+            } else if !windows.is_empty() {
+                Some("window functions")
+            } else if plan.contains_aggregates(*output, true)? {
+                Some("aggregate functions")
+            } else {
+                None
+            }
+        }
+        _ => None,
+    };
+    if let Some(conflict) = conflict {
+        return Err(SbroadError::Invalid(
+            Entity::Query,
+            Some(format_smolstr!("{clause} is not allowed with {conflict}")),
+        ));
+    }
+    Ok(())
+}
+
 fn parse_scan_cte_or_table<M>(
     ast: &AbstractSyntaxTree,
     metadata: &M,
     node_id: usize,
     map: &mut Translation,
     ctes: &mut CTEs,
+    recursive_ctes: &mut RecursiveCtes,
     plan: &mut Plan,
 ) -> Result<(), SbroadError>
 where
@@ -1654,6 +2459,13 @@ where
     let node = ast.nodes.get_node(node_id)?;
     assert_eq!(node.rule, Rule::ScanCteOrTable);
     let scan_name = parse_normalized_identifier(ast, node_id)?;
+    // Self-reference of the recursive CTE is a scan of its working table.
+    if let Some(cte_id) = recursive_ctes.references.get(&node_id).copied() {
+        let working_table_id =
+            recursive_ctes.get_or_add_working_table(ast, cte_id, scan_name, map, plan)?;
+        map.add(node_id, working_table_id);
+        return Ok(());
+    }
     // First we try to find CTE with the given name, cause CTE should have higher precedence
     // over table with the same name
     let cte = ctes.get(&scan_name).copied();
@@ -1688,6 +2500,7 @@ fn parse_cte<M: Metadata>(
     node_id: usize,
     map: &mut Translation,
     ctes: &mut CTEs,
+    recursive_ctes: &RecursiveCtes,
     plan: &mut Plan,
     type_analyzer: &mut TypeAnalyzer,
     pairs_map: &mut ParsingPairsMap,
@@ -1738,7 +2551,22 @@ fn parse_cte<M: Metadata>(
             )),
         ));
     }
-    let cte_id = plan.add_cte(child_id, name.clone(), columns)?;
+    let cte_id = if let Some(working_table_id) = recursive_ctes.working_tables.get(&node_id) {
+        // The columns were already applied to the working table.
+        let terms = recursive_ctes
+            .terms
+            .get(&node_id)
+            .expect("recursive CTE must have terms");
+        let recursive_term_id = map.get(terms.recursive)?;
+        plan.add_recursive_cte(
+            *working_table_id,
+            recursive_term_id,
+            name.clone(),
+            terms.remove_duplicates,
+        )?
+    } else {
+        plan.add_cte(child_id, name.clone(), columns)?
+    };
     ctes.insert(name, cte_id);
     map.add(node_id, cte_id);
     Ok(())
@@ -1811,11 +2639,18 @@ fn parse_insert<M: Metadata>(
             }
         }
 
-        for column in &rel.columns {
+        for (pos, column) in rel.columns.iter().enumerate() {
             if let ColumnRole::Sharding = column.get_role() {
                 continue;
             }
-            if !column.is_nullable && !selected_col_names.contains(&column.name) {
+            // Identity column is filled from the sequence when omitted.
+            if rel.identity == Some(pos) {
+                continue;
+            }
+            if !column.is_nullable
+                && !selected_col_names.contains(&column.name)
+                && rel.column_default(pos) == Value::Null
+            {
                 return Err(SbroadError::Invalid(
                     Entity::Column,
                     Some(format_smolstr!(
@@ -1826,12 +2661,14 @@ fn parse_insert<M: Metadata>(
             }
         }
 
-        let mut column_types = Vec::with_capacity(selected_col_names.len());
+        let mut columns = Vec::with_capacity(selected_col_names.len());
+        let mut defaults = Vec::with_capacity(selected_col_names.len());
         for name in &selected_col_names {
-            let column = rel
+            let (pos, column) = rel
                 .columns
                 .iter()
-                .find(|c| &c.name == name)
+                .enumerate()
+                .find(|(_, c)| &c.name == name)
                 .ok_or_else(|| {
                     SbroadError::Other(format_smolstr!(
                         "column {} of table {} does not exist",
@@ -1839,8 +2676,8 @@ fn parse_insert<M: Metadata>(
                         &relation
                     ))
                 })?;
-            let col_type = column.r#type.get().expect("column type must be known");
-            column_types.push(col_type);
+            columns.push(column.clone());
+            defaults.push(rel.column_default(pos));
         }
 
         let ast_rel_child_id = node
@@ -1850,7 +2687,8 @@ fn parse_insert<M: Metadata>(
         let plan_rel_child_id = parse_insert_source(
             *ast_rel_child_id,
             ast,
-            &column_types,
+            &columns,
+            &defaults,
             map,
             type_analyzer,
             pairs_map,
@@ -1866,18 +2704,21 @@ fn parse_insert<M: Metadata>(
         )
     } else {
         // insert into t ...
-        let mut column_types = Vec::with_capacity(rel.columns.len());
-        for column in &rel.columns {
-            if column.role != ColumnRole::Sharding {
-                let col_type = column.r#type.get().expect("column type must be known");
-                column_types.push(col_type);
-            }
-        }
+        let (columns, defaults): (Vec<Column>, Vec<Value>) = rel
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(pos, column)| {
+                column.role != ColumnRole::Sharding && rel.identity != Some(*pos)
+            })
+            .map(|(pos, column)| (column.clone(), rel.column_default(pos)))
+            .unzip();
 
         let plan_child_id = parse_insert_source(
             *ast_child_id,
             ast,
-            &column_types,
+            &columns,
+            &defaults,
             map,
             type_analyzer,
             pairs_map,
@@ -1893,7 +2734,8 @@ fn parse_insert<M: Metadata>(
 fn parse_insert_source<M: Metadata>(
     node_id: usize,
     ast: &AbstractSyntaxTree,
-    column_types: &[UnrestrictedType],
+    columns: &[Column],
+    defaults: &[Value],
     map: &Translation,
     type_analyzer: &mut TypeAnalyzer,
     pairs_map: &mut ParsingPairsMap,
@@ -1902,6 +2744,11 @@ fn parse_insert_source<M: Metadata>(
 ) -> Result<NodeId, SbroadError> {
     use sql_type_system::expr::Type;
 
+    let column_types: Vec<UnrestrictedType> = columns
+        .iter()
+        .map(|column| column.r#type.get().expect("column type must be known"))
+        .collect();
+
     let node = ast.nodes.get_node(node_id)?;
     match node.rule {
         Rule::SelectFull => map.get(node_id),
@@ -1909,7 +2756,8 @@ fn parse_insert_source<M: Metadata>(
             let values_rows_ids = parse_values_rows(
                 &node.children,
                 type_analyzer,
-                column_types,
+                &column_types,
+                Some((columns, defaults)),
                 pairs_map,
                 worker,
                 plan,
@@ -2154,9 +3002,10 @@ fn parse_anonymous_block(
 /// Get String value under node that is considered to be an identifier
 /// (on which rules on name normalization should be applied).
 fn parse_identifier(ast: &AbstractSyntaxTree, node_id: usize) -> Result<SmolStr, SbroadError> {
-    Ok(normalize_name_from_sql(parse_string_value_node(
-        ast, node_id,
-    )?))
+    Ok(normalize_name(
+        parse_string_value_node(ast, node_id)?,
+        ast.identifier_case,
+    ))
 }
 
 fn parse_optional_identifier(
@@ -2165,7 +3014,7 @@ fn parse_optional_identifier(
 ) -> Result<Option<SmolStr>, SbroadError> {
     let string_value = parse_string_value(ast, node_id)?;
 
-    Ok(string_value.map(|val| normalize_name_from_sql(val)))
+    Ok(string_value.map(|val| normalize_name(val, ast.identifier_case)))
 }
 
 fn parse_indexed_by_expr(
@@ -2182,13 +3031,65 @@ fn parse_indexed_by_expr(
     parse_optional_identifier(ast, *child_id)
 }
 
+/// Parse `TABLESAMPLE BERNOULLI(percent) [REPEATABLE(seed)]`.
+fn parse_table_sample(
+    ast: &AbstractSyntaxTree,
+    node_id: usize,
+) -> Result<TableSample, SbroadError> {
+    let node = ast.nodes.get_node(node_id)?;
+    let method_node = ast.nodes.get_node(node.child_n(0))?;
+    let method = match method_node.rule {
+        Rule::Bernoulli => SampleMethod::Bernoulli,
+        _ => unreachable!("Unexpected sampling method: {method_node:?}"),
+    };
+
+    let percent_str = parse_string_value_node(ast, node.child_n(1))?;
+    let percent = Decimal::from_str(percent_str).map_err(|_| {
+        SbroadError::Invalid(
+            Entity::Query,
+            Some(format_smolstr!(
+                "invalid TABLESAMPLE percentage: {percent_str}"
+            )),
+        )
+    })?;
+    if percent <= Decimal::from(0) || percent > Decimal::from(100) {
+        return Err(SbroadError::Invalid(
+            Entity::Query,
+            Some(format_smolstr!(
+                "TABLESAMPLE percentage {percent} is not in range (0, 100]"
+            )),
+        ));
+    }
+
+    let seed = match node.children.get(2) {
+        Some(seed_id) => {
+            let seed_str = parse_string_value_node(ast, *seed_id)?;
+            let seed = seed_str.parse::<i64>().map_err(|_| {
+                SbroadError::Invalid(
+                    Entity::Query,
+                    Some(format_smolstr!("invalid REPEATABLE seed: {seed_str}")),
+                )
+            })?;
+            Some(seed)
+        }
+        None => None,
+    };
+
+    Ok(TableSample {
+        method,
+        percent,
+        seed,
+    })
+}
+
 fn parse_normalized_identifier(
     ast: &AbstractSyntaxTree,
     node_id: usize,
 ) -> Result<SmolStr, SbroadError> {
-    Ok(normalize_name_from_sql(parse_string_value_node(
-        ast, node_id,
-    )?))
+    Ok(normalize_name(
+        parse_string_value_node(ast, node_id)?,
+        ast.identifier_case,
+    ))
 }
 
 /// Common logic for parsing GRANT/REVOKE queries.
@@ -2414,8 +3315,15 @@ fn parse_unsigned(ast_node: &ParseNode) -> Result<i64, SbroadError> {
     }
 }
 
-/// Common logic for [`crate::ir::options::OptionKind::VdbeOpcodeMax`]
-/// and [`crate::ir::options::OptionKind::MotionRowMax`] parsing.
+/// Common logic for [`crate::ir::options::OptionKind::VdbeOpcodeMax`],
+/// [`crate::ir::options::OptionKind::MotionRowMax`],
+/// [`crate::ir::options::OptionKind::RecursionMax`],
+/// [`crate::ir::options::OptionKind::ReplicaLagMax`],
+/// [`crate::ir::options::OptionKind::MaxBucketsPerQuery`],
+/// [`crate::ir::options::OptionKind::WaitIndex`],
+/// [`crate::ir::options::OptionKind::WaitIndexTimeout`],
+/// [`crate::ir::options::OptionKind::ResultRowMax`] and
+/// [`crate::ir::options::OptionKind::DispatchConcurrencyMax`] parsing.
 fn parse_option<M: Metadata>(
     ast: &AbstractSyntaxTree,
     type_analyzer: &mut TypeAnalyzer,
@@ -2468,6 +3376,57 @@ fn parse_option<M: Metadata>(
     Ok(value)
 }
 
+/// Parse the value of [`crate::ir::options::OptionKind::PartialResults`].
+fn parse_bool_option<M: Metadata>(
+    ast: &AbstractSyntaxTree,
+    type_analyzer: &mut TypeAnalyzer,
+    option_node_id: usize,
+    pairs_map: &mut ParsingPairsMap,
+    worker: &mut ExpressionsWorker<M>,
+    plan: &mut Plan,
+) -> Result<OptionParamValue, SbroadError> {
+    let ast_node = ast.nodes.get_node(option_node_id)?;
+    let value = match ast_node.rule {
+        Rule::Parameter => {
+            let plan_id = parse_scalar_expr(
+                Pairs::single(pairs_map.remove_pair(option_node_id)),
+                type_analyzer,
+                DerivedType::new(UnrestrictedType::Boolean),
+                &[],
+                worker,
+                plan,
+                true,
+            )?;
+
+            let Expression::Parameter(&Parameter { index, .. }) =
+                plan.get_expression_node(plan_id)?
+            else {
+                unreachable!("Expected Parameter expression under Parameter node");
+            };
+
+            OptionParamValue::Parameter {
+                index: index.checked_sub(1).ok_or(SbroadError::Invalid(
+                    AST,
+                    Some("Parameter position 0 is not allowed".into()),
+                ))? as usize,
+            }
+        }
+        Rule::True | Rule::False => OptionParamValue::Value {
+            val: Value::Boolean(ast_node.rule == Rule::True),
+        },
+        _ => {
+            return Err(SbroadError::Invalid(
+                AST,
+                Some(format_smolstr!(
+                    "unexpected child of boolean option. id: {option_node_id}"
+                )),
+            ))
+        }
+    };
+
+    Ok(value)
+}
+
 fn parse_read_preference_option<M: Metadata>(
     ast: &AbstractSyntaxTree,
     type_analyzer: &mut TypeAnalyzer,
@@ -2647,8 +3606,8 @@ lazy_static::lazy_static! {
     static ref PRATT_PARSER: PrattParser<Rule> = {
         use pest::pratt_parser::{Assoc::Left, Op};
         use Rule::{Add, And, Between, ConcatInfixOp, Divide, Eq, Escape, Gt, GtEq,
-            In, IndexPostfix, IsPostfix, CastPostfix, Like, Similar, Lt, LtEq, Modulo, Multiply,
-            NotEq, Or, Subtract, UnaryNot
+            In, IndexPostfix, IsPostfix, CastPostfix, CollatePostfix, JsonField, JsonTextField,
+            JsonContains, Like, LikeAny, Similar, Lt, LtEq, Modulo, Multiply, NotEq, Or, Subtract, UnaryNot
         };
 
         // Precedence is defined lowest to highest.
@@ -2658,7 +3617,7 @@ lazy_static::lazy_static! {
             .op(Op::prefix(UnaryNot))
             // ESCAPE must be followed by LIKE
             .op(Op::infix(Escape, Left))
-            .op(Op::infix(Like, Left))
+            .op(Op::infix(Like, Left) | Op::infix(LikeAny, Left))
             .op(Op::infix(Similar, Left))
             .op(Op::infix(Between, Left))
             .op(
@@ -2666,9 +3625,11 @@ lazy_static::lazy_static! {
                 | Op::infix(Gt, Left) | Op::infix(GtEq, Left) | Op::infix(Lt, Left)
                 | Op::infix(LtEq, Left) | Op::infix(In, Left)
             )
+            .op(Op::infix(JsonField, Left) | Op::infix(JsonTextField, Left) | Op::infix(JsonContains, Left))
             .op(Op::infix(Add, Left) | Op::infix(Subtract, Left))
             .op(Op::infix(Multiply, Left) | Op::infix(Divide, Left) | Op::infix(ConcatInfixOp, Left) | Op::infix(Modulo, Left))
             .op(Op::postfix(IsPostfix))
+            .op(Op::postfix(CollatePostfix))
             .op(Op::postfix(IndexPostfix))
             .op(Op::postfix(CastPostfix))
     };
@@ -2677,14 +3638,17 @@ lazy_static::lazy_static! {
 lazy_static::lazy_static! {
     static ref SELECT_PRATT_PARSER: PrattParser<Rule> = {
         use pest::pratt_parser::{Assoc::Left, Op};
-        use Rule::{UnionOp, UnionAllOp, ExceptOp};
+        use Rule::{UnionOp, UnionAllOp, ExceptOp, ExceptAllOp, IntersectOp, IntersectAllOp};
 
+        // INTERSECT binds tighter than UNION and EXCEPT.
         PrattParser::new()
             .op(
                 Op::infix(UnionOp, Left)
             | Op::infix(UnionAllOp, Left)
             | Op::infix(ExceptOp, Left)
+            | Op::infix(ExceptAllOp, Left)
         )
+            .op(Op::infix(IntersectOp, Left) | Op::infix(IntersectAllOp, Left))
     };
 }
 
@@ -2711,6 +3675,7 @@ fn try_deconstruct_between_expr<'a>(
         op: Bool::Between,
         left: lhs_id,
         right: rhs_id,
+        ..
     }) = expr
     else {
         return None;
@@ -2796,6 +3761,9 @@ where
     curr_window_sqs: Vec<NodeId>,
     /// Are we inside a GroupBy grouping expression.
     inside_grouping_expression: bool,
+    /// Ids of scan nodes met during parsing (tables, CTEs and subqueries under FROM).
+    /// Used to detect subqueries referencing columns of an outer query.
+    scans: Vec<NodeId>,
 }
 
 impl<'worker, M> ExpressionsWorker<'worker, M>
@@ -2824,6 +3792,7 @@ where
             named_windows_sqs: HashMap::new(),
             curr_window_sqs: Vec::new(),
             inside_grouping_expression: false,
+            scans: Vec::new(),
         }
     }
 
@@ -2838,6 +3807,48 @@ where
         Ok(())
     }
 
+    /// Check whether a column that was not found among `referred_relation_ids`
+    /// belongs to some other scan met earlier, i.e. to an outer query.
+    fn check_outer_reference(
+        &mut self,
+        plan: &Plan,
+        referred_relation_ids: &[NodeId],
+        col_name: &str,
+        scan_name: Option<&str>,
+    ) -> Result<(), SbroadError> {
+        let mut visible_ids = HashSet::new();
+        for rel_id in referred_relation_ids {
+            let dfs = PostOrder::with_capacity(|x| plan.nodes.rel_iter(x), REL_CAPACITY);
+            visible_ids.extend(dfs.into_iter(*rel_id).map(|LevelNode(_, id)| id));
+        }
+
+        let outer_scans: Vec<NodeId> = self
+            .scans
+            .iter()
+            .filter(|id| !visible_ids.contains(*id))
+            .copied()
+            .collect();
+        for scan_id in outer_scans {
+            self.build_columns_map(plan, scan_id)?;
+            if self
+                .columns_map_get_positions(scan_id, col_name, scan_name)
+                .is_ok()
+            {
+                let column = match scan_name {
+                    Some(scan_name) => {
+                        format_smolstr!("{}.{}", to_user(scan_name), to_user(col_name))
+                    }
+                    None => to_user(col_name),
+                };
+                return Err(SbroadError::NotImplemented(
+                    Entity::Column,
+                    format_smolstr!("{column} refers to an outer query: correlated subqueries are"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
     fn columns_map_get_positions(
         &self,
         rel_id: NodeId,
@@ -2922,12 +3933,15 @@ enum ParseExpression {
         name: String,
         args: Vec<ParseExpression>,
         feature: Option<FunctionFeature>,
+        order_key: Option<Box<ParseExpression>>,
     },
+    /// For `LIKE ANY` the right child is a `Row` of patterns.
     Like {
         left: Box<ParseExpression>,
         right: Box<ParseExpression>,
         escape: Option<Box<ParseExpression>>,
         is_ilike: bool,
+        is_any: bool,
     },
     Similar {
         left: Box<ParseExpression>,
@@ -2958,6 +3972,11 @@ enum ParseExpression {
         cast_type: CastType,
         child: Box<ParseExpression>,
     },
+    /// Explicit collation of a sort key or a comparison operand.
+    Collate {
+        collation: Collation,
+        child: Box<ParseExpression>,
+    },
     Case {
         search_expr: Option<Box<ParseExpression>>,
         when_blocks: Vec<(Box<ParseExpression>, Box<ParseExpression>)>,
@@ -3001,12 +4020,16 @@ enum SelectOp {
     Union,
     UnionAll,
     Except,
+    ExceptAll,
+    Intersect,
+    IntersectAll,
 }
 
 /// Helper struct denoting any combination of
 /// * SELECT
 /// * UNION (ALL)
-/// * EXCEPT
+/// * EXCEPT (ALL)
+/// * INTERSECT (ALL)
 #[derive(Clone)]
 enum SelectSet {
     PlanId {
@@ -3029,9 +4052,19 @@ impl SelectSet {
                 match op {
                     u @ (SelectOp::Union | SelectOp::UnionAll) => {
                         let remove_duplicates = matches!(u, SelectOp::Union);
+                        coerce_set_operation_columns(plan, left_id, right_id, "UNION")?;
                         plan.add_union(left_id, right_id, remove_duplicates)
                     }
-                    SelectOp::Except => plan.add_except(left_id, right_id),
+                    e @ (SelectOp::Except | SelectOp::ExceptAll) => {
+                        let all = matches!(e, SelectOp::ExceptAll);
+                        coerce_set_operation_columns(plan, left_id, right_id, "EXCEPT")?;
+                        plan.add_except(left_id, right_id, all)
+                    }
+                    i @ (SelectOp::Intersect | SelectOp::IntersectAll) => {
+                        let all = matches!(i, SelectOp::IntersectAll);
+                        coerce_set_operation_columns(plan, left_id, right_id, "INTERSECT")?;
+                        plan.add_intersect(left_id, right_id, all)
+                    }
                 }
             }
         }
@@ -3118,7 +4151,13 @@ impl Plan {
                         _ => continue,
                     };
 
-                    self.replace_const_with_reference(&final_proj_cols, group_by, expr, pos)?;
+                    self.replace_const_with_reference(
+                        final_proj_id,
+                        &final_proj_cols,
+                        group_by,
+                        expr,
+                        pos,
+                    )?;
                 }
                 Expression::ScalarFunction(ScalarFunction { name, .. }) => {
                     return Err(SbroadError::Invalid(
@@ -3174,6 +4213,7 @@ impl Plan {
 
     fn replace_const_with_reference(
         &mut self,
+        final_proj_id: NodeId,
         final_proj_cols: &[NodeId],
         groupby_id: NodeId,
         expr: &mut NodeId,
@@ -3199,15 +4239,17 @@ impl Plan {
             ));
         };
         let alias_node = self.get_expression_node(alias_id)?;
-        if let Expression::Alias(Alias { child, .. }) = alias_node {
-            let expr_node = self.get_expression_node(*child)?;
-            if let Expression::Reference(Reference {
+        let Expression::Alias(Alias { child, .. }) = alias_node else {
+            return Ok(());
+        };
+        let child = *child;
+        let groupby_child_id = self.get_first_rel_child(groupby_id)?;
+        match self.get_expression_node(child)? {
+            Expression::Reference(Reference {
                 position, col_type, ..
-            }) = expr_node
-            {
-                let node_id = self.get_first_rel_child(groupby_id)?;
+            }) => {
                 let ref_id = self.nodes.add_ref(
-                    ReferenceTarget::Single(node_id),
+                    ReferenceTarget::Single(groupby_child_id),
                     *position,
                     *col_type,
                     None,
@@ -3215,7 +4257,10 @@ impl Plan {
                 );
 
                 *expr = ref_id;
-            } else if let Expression::ScalarFunction(ScalarFunction { name, .. }) = expr_node {
+            }
+            Expression::ScalarFunction(ScalarFunction {
+                name, is_window, ..
+            }) if Expression::is_aggregate_name(name) && !is_window => {
                 return Err(SbroadError::Invalid(
                     Entity::Query,
                     Some(format_smolstr!(
@@ -3223,6 +4268,16 @@ impl Plan {
                     )),
                 ));
             }
+            _ => {
+                // The position points at a computed column, so we group by a copy
+                // of its expression. Aggregates planning then matches it with the
+                // projection column the same way as for `group by a + b`.
+                let proj_child_id = self.get_first_rel_child(final_proj_id)?;
+                let new_expr_id = SubtreeCloner::clone_subtree(self, child)?;
+                self.replace_target_in_subtree(new_expr_id, proj_child_id, groupby_child_id)?;
+                self.check_grouping_expr_subtree(new_expr_id)?;
+                *expr = new_expr_id;
+            }
         }
 
         Ok(())
@@ -3230,6 +4285,93 @@ impl Plan {
 }
 
 impl ParseExpression {
+    /// Check that the expression tree is not nested deeper than `max_depth`.
+    /// The recursive routines working with the expressions (plan population,
+    /// comparison, hashing) would overflow the stack on deeper trees, so the
+    /// tree is traversed iteratively here.
+    fn check_depth(&self, max_depth: usize) -> Result<(), SbroadError> {
+        let mut stack: Vec<(&ParseExpression, usize)> = vec![(self, 1)];
+        while let Some((expr, depth)) = stack.pop() {
+            if depth > max_depth {
+                return Err(nesting_depth_error(max_depth));
+            }
+            stack.extend(expr.children().into_iter().map(|child| (child, depth + 1)));
+        }
+        Ok(())
+    }
+
+    fn children(&self) -> Vec<&ParseExpression> {
+        match self {
+            ParseExpression::PlanId { .. } | ParseExpression::SubQueryPlanId { .. } => vec![],
+            ParseExpression::Infix { left, right, .. }
+            | ParseExpression::InterimBetween { left, right, .. } => vec![&**left, &**right],
+            ParseExpression::Like {
+                left,
+                right,
+                escape,
+                ..
+            }
+            | ParseExpression::Similar {
+                left,
+                right,
+                escape,
+            } => {
+                let mut children: Vec<&ParseExpression> = vec![&**left, &**right];
+                children.extend(escape.as_deref());
+                children
+            }
+            ParseExpression::FinalBetween {
+                left,
+                center,
+                right,
+                ..
+            } => vec![&**left, &**center, &**right],
+            ParseExpression::Function {
+                args, order_key, ..
+            } => {
+                let mut children: Vec<&ParseExpression> = args.iter().collect();
+                children.extend(order_key.as_deref());
+                children
+            }
+            ParseExpression::Row { children } => children.iter().collect(),
+            ParseExpression::Prefix { child, .. }
+            | ParseExpression::Exists { child, .. }
+            | ParseExpression::Is { child, .. }
+            | ParseExpression::Cast { child, .. }
+            | ParseExpression::Collate { child, .. } => vec![&**child],
+            ParseExpression::Index { child, which } => vec![&**child, &**which],
+            ParseExpression::Case {
+                search_expr,
+                when_blocks,
+                else_expr,
+            } => {
+                let mut children: Vec<&ParseExpression> =
+                    search_expr.as_deref().into_iter().collect();
+                for (cond, res) in when_blocks {
+                    children.push(cond);
+                    children.push(res);
+                }
+                children.extend(else_expr.as_deref());
+                children
+            }
+            ParseExpression::Trim {
+                pattern, target, ..
+            } => {
+                let mut children: Vec<&ParseExpression> = pattern.as_deref().into_iter().collect();
+                children.push(target);
+                children
+            }
+        }
+    }
+
+    /// Split off the top-level `COLLATE` clause of the expression.
+    fn split_collation(&self) -> (&ParseExpression, Option<Collation>) {
+        match self {
+            ParseExpression::Collate { collation, child } => (&**child, Some(*collation)),
+            expr => (expr, None),
+        }
+    }
+
     #[allow(clippy::too_many_lines)]
     fn populate_plan<M>(
         &self,
@@ -3249,9 +4391,36 @@ impl ParseExpression {
                 let which_plan_id = which.populate_plan(plan, worker)?;
                 plan.add_index(child_plan_id, which_plan_id)?
             }
-            ParseExpression::Cast { cast_type, child } => {
-                let child_plan_id = child.populate_plan(plan, worker)?;
-                plan.add_cast(child_plan_id, *cast_type)?
+            ParseExpression::Cast { cast_type, child } => {
+                let child_plan_id = child.populate_plan(plan, worker)?;
+                match (cast_type, plan.get_expression_node(child_plan_id)?) {
+                    // JSON literal: the text is validated right away and decoded
+                    // into a document on the storages.
+                    (
+                        CastType::Json,
+                        Expression::Constant(Constant {
+                            value: Value::String(text),
+                        }),
+                    ) => {
+                        if serde_json::from_str::<serde_json::Value>(text).is_err() {
+                            return Err(SbroadError::InvalidTextRepresentation(
+                                "json".into(),
+                                text.to_smolstr(),
+                            ));
+                        }
+                        let func = worker.metadata.function("_pico_json_parse")?;
+                        plan.add_stable_function(func, vec![child_plan_id], None)?
+                    }
+                    _ => plan.add_cast(child_plan_id, *cast_type)?,
+                }
+            }
+            ParseExpression::Collate { .. } => {
+                return Err(SbroadError::Unsupported(
+                    Entity::Collation,
+                    Some(
+                        "COLLATE is allowed only for ORDER BY keys and comparison operands".into(),
+                    ),
+                ));
             }
             ParseExpression::Case {
                 search_expr,
@@ -3300,6 +4469,78 @@ impl ParseExpression {
                 right,
                 escape,
                 is_ilike,
+                is_any: true,
+            } => {
+                let ParseExpression::Row { children: patterns } = &**right else {
+                    panic!("Expected a row of patterns for LIKE ANY");
+                };
+
+                // `a LIKE ANY (p1, ..., pn)` is `a LIKE p1 OR ... OR a LIKE pn`,
+                // so NULL patterns follow three-valued logic. No patterns yield FALSE.
+                if patterns.is_empty() {
+                    return Ok(plan.add_const(Value::Boolean(false)));
+                }
+
+                let plan_left_id = left.populate_plan(plan, worker)?;
+                let plan_escape_id = if let Some(escape) = escape {
+                    Some(escape.populate_plan(plan, worker)?)
+                } else {
+                    None
+                };
+
+                // The patterns of an array parameter (`a LIKE ANY ($1)`) are not known
+                // until binding, so the parameter is kept in a row and expanded later.
+                if let [ParseExpression::PlanId { plan_id }] = patterns.as_slice() {
+                    if plan.get_array_param(*plan_id)?.is_some() {
+                        let (mut like_left_id, mut pattern_id) = (plan_left_id, *plan_id);
+                        if *is_ilike {
+                            let lower_func = worker.metadata.function("lower")?;
+                            like_left_id =
+                                plan.add_stable_function(lower_func, vec![like_left_id], None)?;
+                            pattern_id =
+                                plan.add_stable_function(lower_func, vec![pattern_id], None)?;
+                        }
+                        let row_id = plan.nodes.add_row(vec![pattern_id], None);
+                        return plan.add_like(like_left_id, row_id, plan_escape_id);
+                    }
+                }
+
+                let mut disjunction_id = None;
+                for (i, pattern) in patterns.iter().enumerate() {
+                    // Every comparison needs its own copy of the shared operands,
+                    // see `fix_betweens` for the same problem with BETWEEN.
+                    let (mut like_left_id, like_escape_id) = if i == 0 {
+                        (plan_left_id, plan_escape_id)
+                    } else {
+                        (
+                            SubtreeCloner::clone_subtree(plan, plan_left_id)?,
+                            plan_escape_id
+                                .map(|id| SubtreeCloner::clone_subtree(plan, id))
+                                .transpose()?,
+                        )
+                    };
+                    let mut pattern_id = pattern.populate_plan(plan, worker)?;
+                    if *is_ilike {
+                        let lower_func = worker.metadata.function("lower")?;
+                        like_left_id =
+                            plan.add_stable_function(lower_func, vec![like_left_id], None)?;
+                        pattern_id =
+                            plan.add_stable_function(lower_func, vec![pattern_id], None)?;
+                    }
+                    let like_id = plan.add_like(like_left_id, pattern_id, like_escape_id)?;
+                    disjunction_id = Some(match disjunction_id {
+                        Some(prev_id) => plan.add_cond(prev_id, Bool::Or, like_id)?,
+                        None => like_id,
+                    });
+                }
+                disjunction_id.expect("patterns are not empty")
+            }
+            ParseExpression::Like {
+                left,
+                right,
+                escape,
+                is_ilike,
+                is_any: false,
             } => {
                 let mut plan_left_id = left.populate_plan(plan, worker)?;
 
@@ -3369,19 +4610,41 @@ impl ParseExpression {
                 left,
                 right,
             } => {
+                let (left, right, collation) = match op {
+                    ParseExpressionInfixOperator::InfixBool(
+                        Bool::Eq | Bool::Gt | Bool::GtEq | Bool::Lt | Bool::LtEq | Bool::NotEq,
+                    ) => {
+                        let (left, left_collation) = left.split_collation();
+                        let (right, right_collation) = right.split_collation();
+                        let collation = match (left_collation, right_collation) {
+                            (Some(l), Some(r)) if l != r => {
+                                return Err(SbroadError::Invalid(
+                                    Entity::Collation,
+                                    Some(format_smolstr!(
+                                        "mismatch between explicit collations \"{l}\" and \"{r}\""
+                                    )),
+                                ));
+                            }
+                            (l, r) => l.or(r),
+                        };
+                        (left, right, collation)
+                    }
+                    _ => (&**left, &**right, None),
+                };
+
                 let left_plan_id = left.populate_plan(plan, worker)?;
 
                 let right_plan_id = match op {
                     ParseExpressionInfixOperator::InfixBool(op) => match op {
                         Bool::In => {
-                            if let ParseExpression::SubQueryPlanId { plan_id } = &**right {
+                            if let ParseExpression::SubQueryPlanId { plan_id } = right {
                                 plan.add_replaced_subquery(*plan_id, worker)?
                             } else {
                                 right.populate_plan(plan, worker)?
                             }
                         }
                         Bool::Eq | Bool::Gt | Bool::GtEq | Bool::Lt | Bool::LtEq | Bool::NotEq => {
-                            if let ParseExpression::SubQueryPlanId { plan_id } = &**right {
+                            if let ParseExpression::SubQueryPlanId { plan_id } = right {
                                 plan.add_replaced_subquery(*plan_id, worker)?
                             } else {
                                 right.populate_plan(plan, worker)?
@@ -3448,7 +4711,7 @@ impl ParseExpression {
                         plan.add_arithmetic_to_plan(left_plan_id, arith.clone(), right_plan_id)?
                     }
                     ParseExpressionInfixOperator::InfixBool(bool) => {
-                        plan.add_cond(left_plan_id, *bool, right_plan_id)?
+                        plan.add_collated_cond(left_plan_id, *bool, right_plan_id, collation)?
                     }
                     ParseExpressionInfixOperator::Escape => {
                         unreachable!("escape op is not added to AST")
@@ -3468,6 +4731,7 @@ impl ParseExpression {
                 name,
                 args,
                 feature,
+                order_key,
             } => {
                 let is_distinct = matches!(feature, Some(FunctionFeature::Distinct));
                 let mut plan_arg_ids = Vec::new();
@@ -3476,13 +4740,36 @@ impl ParseExpression {
                     plan_arg_ids.push(arg_plan_id);
                 }
                 if let Some(kind) = AggregateKind::from_name(name) {
-                    plan.add_aggregate_function(kind, plan_arg_ids, is_distinct)?
+                    if let (Some(order_key), Some(FunctionFeature::OrderBy(order_type, nulls, _))) =
+                        (order_key, feature)
+                    {
+                        let (order_key, collation) = order_key.split_collation();
+                        let order_key_id = order_key.populate_plan(plan, worker)?;
+                        plan.add_ordered_aggregate_function(
+                            kind,
+                            plan_arg_ids,
+                            order_key_id,
+                            order_type.clone(),
+                            *nulls,
+                            collation,
+                        )?
+                    } else {
+                        plan.add_aggregate_function(kind, plan_arg_ids, is_distinct)?
+                    }
                 } else if is_distinct {
                     return Err(SbroadError::Invalid(
                         Entity::Query,
                         Some("DISTINCT modifier is allowed only for aggregate functions".into()),
                     ));
+                } else if order_key.is_some() {
+                    return Err(SbroadError::Invalid(
+                        Entity::Query,
+                        Some("ORDER BY modifier is allowed only for aggregate functions".into()),
+                    ));
                 } else {
+                    if name.eq_ignore_ascii_case("date_trunc") {
+                        check_date_trunc_unit(plan, &plan_arg_ids)?;
+                    }
                     let func = worker.metadata.function(name)?;
                     match func.volatility {
                         VolatilityType::Stable => {
@@ -3616,6 +4903,14 @@ fn cast_type_from_pair(type_pair: Pair<Rule>) -> Result<CastType, SbroadError> {
     let column_def_type = column_def_type_pairs
         .next()
         .expect("concrete type expected under Type");
+    if column_def_type.as_rule() == Rule::TypeInt {
+        let type_cast = match column_def_type.as_str().to_lowercase().as_str() {
+            "smallint" | "int2" => CastType::SmallInt,
+            "int4" => CastType::Int4,
+            _ => CastType::Integer,
+        };
+        return Ok(type_cast);
+    }
     if column_def_type.as_rule() != Rule::TypeVarchar {
         return CastType::try_from(&column_def_type.as_rule());
     }
@@ -3857,7 +5152,7 @@ fn parse_window_func<M: Metadata>(
                             let expr_pair = order_item_inner
                                 .next()
                                 .expect("Expected expression in ORDER BY");
-                            let expr_id = parse_expr_no_type_check(
+                            let parse_expr = parse_expr_pratt(
                                 Pairs::single(expr_pair.clone()),
                                 param_types,
                                 referred_relation_ids,
@@ -3865,6 +5160,8 @@ fn parse_window_func<M: Metadata>(
                                 plan,
                                 false,
                             )?;
+                            let (parse_expr, collation) = parse_expr.split_collation();
+                            let expr_id = parse_expr.populate_plan(plan, worker)?;
 
                             let (order_type, order_nulls) = {
                                 let mut order_type = Some(OrderByType::Asc);
@@ -3874,8 +5171,8 @@ fn parse_window_func<M: Metadata>(
                                     match rule {
                                         Rule::Asc => {}
                                         Rule::Desc => order_type = Some(OrderByType::Desc),
-                                        Rule::NullsFirst => order_nulls = Some(OrderNulls::First),
-                                        Rule::NullsLast => order_nulls = Some(OrderNulls::Last),
+                                        Rule::NullsFirst => order_nulls = Some(NullsOrder::First),
+                                        Rule::NullsLast => order_nulls = Some(NullsOrder::Last),
                                         rule => unreachable!(
                                             "{}",
                                             format!(
@@ -3892,8 +5189,8 @@ fn parse_window_func<M: Metadata>(
                                 let is_null_expr_id =
                                     plan.add_unary(Unary::IsNull, nulls_expr_id)?;
                                 let top_expr_id = match order_nulls {
-                                    OrderNulls::Last => is_null_expr_id,
-                                    OrderNulls::First => {
+                                    NullsOrder::Last => is_null_expr_id,
+                                    NullsOrder::First => {
                                         plan.add_unary(Unary::Not, is_null_expr_id)?
                                     }
                                 };
@@ -3903,12 +5200,14 @@ fn parse_window_func<M: Metadata>(
                                 order_by_elements.push(OrderByElement {
                                     entity: new_entity_first,
                                     order_type: None,
+                                    collation: None,
                                 });
                             }
 
                             order_by_elements.push(OrderByElement {
                                 entity: OrderByEntity::Expression { expr_id },
                                 order_type,
+                                collation,
                             });
                         }
                         ordering = Some(order_by_elements)
@@ -3999,6 +5298,64 @@ fn parse_window_func<M: Metadata>(
     Ok(plan.nodes.push(over.into()))
 }
 
+/// Parse ordered-set aggregate `percentile_cont(fraction) WITHIN GROUP (ORDER BY key)`.
+fn parse_within_group<M: Metadata>(
+    pair: Pair<Rule>,
+    param_types: &[DerivedType],
+    referred_relation_ids: &[NodeId],
+    worker: &mut ExpressionsWorker<M>,
+    plan: &mut Plan,
+) -> Result<NodeId, SbroadError> {
+    let mut inner = pair.into_inner();
+
+    let name_pair = inner
+        .next()
+        .expect("Function name expected under WithinGroup");
+    let func_name = normalize_name_from_sql(name_pair.as_str());
+    if !matches!(func_name.as_str(), "percentile_cont" | "percentile_disc") {
+        return Err(SbroadError::Invalid(
+            Entity::Query,
+            Some(format_smolstr!(
+                "WITHIN GROUP is not supported for function: {}",
+                to_user(&func_name)
+            )),
+        ));
+    }
+
+    let fraction_pair = inner.next().expect("Fraction expected under WithinGroup");
+    let fraction_id = parse_expr_no_type_check(
+        Pairs::single(fraction_pair),
+        param_types,
+        referred_relation_ids,
+        worker,
+        plan,
+        true,
+    )?;
+
+    let mut order_by_inner = inner
+        .next()
+        .expect("ORDER BY expected under WithinGroup")
+        .into_inner();
+    let key_pair = order_by_inner
+        .next()
+        .expect("ORDER BY inside WITHIN GROUP must contain an expression");
+    let order_type = match order_by_inner.next().map(|p| p.as_rule()) {
+        Some(Rule::Desc) => OrderByType::Desc,
+        _ => OrderByType::Asc,
+    };
+    let key_id = parse_expr_no_type_check(
+        Pairs::single(key_pair),
+        param_types,
+        referred_relation_ids,
+        worker,
+        plan,
+        true,
+    )?;
+
+    let func = worker.metadata.function(&func_name)?;
+    plan.add_percentile_function(func, fraction_id, key_id, order_type)
+}
+
 fn parse_frame_bound<M: Metadata>(
     bound: Pair<Rule>,
     param_types: &[DerivedType],
@@ -4082,6 +5439,7 @@ fn parse_substring<M: Metadata>(
                 name: "substring".to_string(),
                 args: vec![string_expr, from_expr, for_expr],
                 feature: Some(FunctionFeature::Substring(Substring::FromFor)),
+                order_key: None,
             })
         }
         Rule::SubstringRegular => {
@@ -4116,6 +5474,7 @@ fn parse_substring<M: Metadata>(
                 name: "substring".to_string(),
                 args: vec![string_expr, from_expr, for_expr],
                 feature: Some(FunctionFeature::Substring(Substring::Regular)),
+                order_key: None,
             })
         }
         Rule::SubstringFor => {
@@ -4152,6 +5511,7 @@ fn parse_substring<M: Metadata>(
                     ParseExpression::PlanId { plan_id: for_id },
                 ],
                 feature: Some(FunctionFeature::Substring(Substring::For)),
+                order_key: None,
             })
         }
         Rule::SubstringFrom => {
@@ -4178,6 +5538,7 @@ fn parse_substring<M: Metadata>(
                 name: "substring".to_string(),
                 args: vec![string_expr, from_expr],
                 feature: Some(FunctionFeature::Substring(Substring::From)),
+                order_key: None,
             })
         }
         Rule::SubstringSimilar => {
@@ -4230,6 +5591,7 @@ fn parse_substring<M: Metadata>(
                 name: "substring".to_string(),
                 args,
                 feature: Some(FunctionFeature::Substring(Substring::Similar)),
+                order_key: None,
             })
         }
         _ => Err(SbroadError::ParsingError(
@@ -4307,6 +5669,10 @@ where
                     let plan_id = parse_window_func(primary, param_types, referred_relation_ids, worker, plan)?;
                     ParseExpression::PlanId { plan_id }
                 }
+                Rule::WithinGroup => {
+                    let plan_id = parse_within_group(primary, param_types, referred_relation_ids, worker, plan)?;
+                    ParseExpression::PlanId { plan_id }
+                }
                 Rule::IdentifierWithOptionalContinuation => {
                     let mut inner_pairs = primary.into_inner();
                     let first_identifier = inner_pairs.next().expect(
@@ -4314,7 +5680,7 @@ where
                     ).as_str();
 
                     let mut scan_name = None;
-                    let mut col_name = normalize_name_from_sql(first_identifier);
+                    let mut col_name = normalize_name(first_identifier, worker.metadata.identifier_case());
 
 					// simple id, without reference continuation or function invocation continuation
 					let is_simple_id = inner_pairs.is_empty();
@@ -4325,7 +5691,7 @@ where
                             Rule::ReferenceContinuation => {
                                 let col_name_pair = continuation.into_inner()
                                     .next().expect("Reference continuation must contain an Identifier");
-                                let second_identifier = normalize_name_from_sql(col_name_pair.as_str());
+                                let second_identifier = normalize_name(col_name_pair.as_str(), worker.metadata.identifier_case());
                                 scan_name = Some(col_name);
                                 col_name = second_identifier;
                             }
@@ -4334,6 +5700,7 @@ where
                                 let mut function_name = String::from(first_identifier);
                                 let mut args_pairs = continuation.into_inner();
                                 let mut feature = None;
+                                let mut order_key = None;
                                 let mut parse_exprs_args = Vec::new();
                                 let function_args = args_pairs.next();
                                 if let Some(function_args) = function_args {
@@ -4403,6 +5770,40 @@ where
                                             }
 
                                             for arg in arg_pairs_to_parse {
+                                                if let Rule::FunctionOrderBy = arg.as_rule() {
+                                                    if feature.is_some() {
+                                                        return Err(SbroadError::NotImplemented(
+                                                            Entity::Aggregate,
+                                                            "with both DISTINCT and ORDER BY".to_smolstr(),
+                                                        ));
+                                                    }
+                                                    let mut order_by_inner = arg.into_inner();
+                                                    let key_pair = order_by_inner
+                                                        .next()
+                                                        .expect("ORDER BY inside function call must contain an expression");
+                                                    let mut order_type = OrderByType::Asc;
+                                                    let mut nulls = None;
+                                                    for flag in order_by_inner {
+                                                        match flag.as_rule() {
+                                                            Rule::Desc => order_type = OrderByType::Desc,
+                                                            Rule::NullsFirst => nulls = Some(NullsOrder::First),
+                                                            Rule::NullsLast => nulls = Some(NullsOrder::Last),
+                                                            _ => {}
+                                                        }
+                                                    }
+                                                    let nulls = nulls.unwrap_or_else(|| NullsOrder::default_for(&order_type));
+                                                    let key_expr = parse_expr_pratt(
+                                                        key_pair.into_inner(),
+                                                        param_types,
+                                                        referred_relation_ids,
+                                                        worker,
+                                                        plan,
+                                                        safe_for_volatile_function,
+                                                    )?;
+                                                    feature = Some(FunctionFeature::OrderBy(order_type, nulls, None));
+                                                    order_key = Some(Box::new(key_expr));
+                                                    continue;
+                                                }
                                                 let arg_expr = parse_expr_pratt(
                                                     arg.into_inner(),
                                                     param_types,
@@ -4417,10 +5818,21 @@ where
                                         rule => unreachable!("{}", format!("Unexpected rule under FunctionInvocation: {rule:?}"))
                                     }
                                 }
+                                // `NOW()` is a synonym for `CURRENT_TIMESTAMP`, so it is
+                                // evaluated once on the router as well.
+                                if function_name.eq_ignore_ascii_case("now") && parse_exprs_args.is_empty() && feature.is_none() {
+                                    let timestamp = Timestamp::DateTime(TimeParameters {
+                                        precision: 6,
+                                        include_timezone: true,
+                                    });
+                                    let plan_id = plan.nodes.push(timestamp.into());
+                                    return Ok(ParseExpression::PlanId { plan_id });
+                                }
                                 return Ok(ParseExpression::Function {
                                     name: function_name,
                                     args: parse_exprs_args,
                                     feature,
+                                    order_key,
                                 })
                             }
                             rule => unreachable!("Expr::parse expected identifier continuation, found {:?}", rule)
@@ -4476,6 +5888,7 @@ where
                                 col_with_scan,
                             )?
                         } else {
+                            worker.check_outer_reference(plan, referred_relation_ids, &col_name, scan_name.as_deref())?;
                             return Err(SbroadError::NotFound(
                                 Entity::Column,
                                 format_smolstr!("'{col_name}' in the join children",),
@@ -4512,6 +5925,7 @@ where
 									let alias_id = plan.nodes.add_alias(&col_name, ref_id)?;
 									ParseExpression::PlanId { plan_id: alias_id }
 								} else {
+									worker.check_outer_reference(plan, referred_relation_ids, &col_name, scan_name.as_deref())?;
 									return Err(e);
 								}
 							}
@@ -4543,6 +5957,28 @@ where
                     }
                     ParseExpression::Row { children }
                 }
+                Rule::LikePatterns => {
+                    let mut pattern_pairs: Vec<Pair<Rule>> = primary.into_inner().collect();
+                    if pattern_pairs
+                        .first()
+                        .is_some_and(|pair| pair.as_rule() == Rule::ArrayConstructor)
+                    {
+                        pattern_pairs = pattern_pairs.remove(0).into_inner().collect();
+                    }
+                    let mut children = Vec::new();
+                    for expr_pair in pattern_pairs {
+                        let child_parse_expr = parse_expr_pratt(
+                            expr_pair.into_inner(),
+                            param_types,
+                            referred_relation_ids,
+                            worker,
+                            plan,
+                            safe_for_volatile_function,
+                        )?;
+                        children.push(child_parse_expr);
+                    }
+                    ParseExpression::Row { children }
+                }
                 Rule::Decimal
                 | Rule::Double
                 | Rule::Unsigned
@@ -4734,7 +6170,18 @@ where
                         left: Box::new(lhs),
                         right: Box::new(rhs),
                         escape: None,
-                        is_ilike
+                        is_ilike,
+                        is_any: false,
+                    })
+                },
+                Rule::LikeAny => {
+                    let is_ilike = op.as_str().to_lowercase().starts_with("ilike");
+                    return Ok(ParseExpression::Like {
+                        left: Box::new(lhs),
+                        right: Box::new(rhs),
+                        escape: None,
+                        is_ilike,
+                        is_any: true,
                     })
                 },
                 Rule::Similar => {
@@ -4777,6 +6224,27 @@ where
                 Rule::Multiply      => ParseExpressionInfixOperator::InfixArithmetic(Arithmetic::Multiply),
                 Rule::Add        => ParseExpressionInfixOperator::InfixArithmetic(Arithmetic::Add),
                 Rule::ConcatInfixOp => ParseExpressionInfixOperator::Concat,
+                Rule::JsonField | Rule::JsonTextField => {
+                    let name = if op.as_rule() == Rule::JsonField {
+                        "json_extract_path"
+                    } else {
+                        "json_extract_path_text"
+                    };
+                    return Ok(ParseExpression::Function {
+                        name: name.to_string(),
+                        args: vec![lhs, rhs],
+                        feature: None,
+                        order_key: None,
+                    })
+                }
+                Rule::JsonContains => {
+                    return Ok(ParseExpression::Function {
+                        name: "_pico_json_contains".to_string(),
+                        args: vec![lhs, rhs],
+                        feature: None,
+                        order_key: None,
+                    })
+                }
                 rule           => unreachable!("Expr::parse expected infix operation, found {:?}", rule),
             };
 
@@ -4846,6 +6314,13 @@ where
                     let cast_type = cast_type_from_pair(ty_pair)?;
                     Ok(ParseExpression::Cast { child: Box::new(child), cast_type })
                 }
+                Rule::CollatePostfix => {
+                    let name_pair = op.into_inner().next()
+                        .expect("Expected Identifier under CollatePostfix.");
+                    let name = normalize_name_from_sql(name_pair.as_str());
+                    let collation = Collation::from_name(&name)?;
+                    Ok(ParseExpression::Collate { child: Box::new(child), collation })
+                }
                 Rule::IsPostfix => {
                     let mut inner = op.into_inner();
                     let (is_not, value_index) = match inner.len() {
@@ -4861,16 +6336,31 @@ where
                         Rule::True => Some(true),
                         Rule::False => Some(false),
                         Rule::Unknown | Rule::Null => None,
-                        _ => unreachable!("Is value must be TRUE, FALSE, NULL or UNKNOWN")
+                        Rule::IsJson => {
+                            // Malformed text is not JSON, so the check is done
+                            // by a builtin instead of a cast that would fail.
+                            let is_json = ParseExpression::Function {
+                                name: "_pico_is_json".to_string(),
+                                args: vec![child],
+                                feature: None,
+                                order_key: None,
+                            };
+                            if !is_not {
+                                return Ok(is_json);
+                            }
+                            return Ok(ParseExpression::Prefix { op: Unary::Not, child: Box::new(is_json) });
+                        }
+                        _ => unreachable!("Is value must be TRUE, FALSE, NULL, UNKNOWN or JSON")
                     };
                     Ok(ParseExpression::Is { is_not, child: Box::new(child), value })
                 }
                 rule => unreachable!("Expr::parse expected postfix operator, found {:?}", rule),
             }
         })
-        .parse(expression_pairs);
+        .parse(expression_pairs)?;
 
-    res
+    res.check_depth(worker.borrow().metadata.max_expression_depth())?;
+    Ok(res)
 }
 
 // Mapping between pest's Pair and corresponding id
@@ -4890,7 +6380,10 @@ fn parse_select_set_pratt(
                     let mut pairs = primary.into_inner();
                     let mut select_child_pair =
                         pairs.next().expect("select must have at least one child");
-                    assert!(matches!(select_child_pair.as_rule(), Rule::Projection));
+                    assert!(matches!(
+                        select_child_pair.as_rule(),
+                        Rule::Projection | Rule::TableShorthand
+                    ));
                     for pair in pairs {
                         if let Rule::OrderBy = pair.as_rule() {
                             select_child_pair = pair;
@@ -4913,6 +6406,9 @@ fn parse_select_set_pratt(
                 Rule::UnionOp => SelectOp::Union,
                 Rule::UnionAllOp => SelectOp::UnionAll,
                 Rule::ExceptOp => SelectOp::Except,
+                Rule::ExceptAllOp => SelectOp::ExceptAll,
+                Rule::IntersectOp => SelectOp::Intersect,
+                Rule::IntersectAllOp => SelectOp::IntersectAll,
                 rule => unreachable!("Expr::parse expected infix operation, found {:?}", rule),
             };
             Ok(SelectSet::Infix {
@@ -4983,10 +6479,121 @@ where
     Ok(expr_id)
 }
 
+/// Parse ORDER BY key expression and get plan id along with the key collation.
+/// The same as `parse_scalar_expr`, but the top-level `COLLATE` clause is
+/// split off the expression.
+fn parse_sort_key_expr<M>(
+    expression_pairs: Pairs<Rule>,
+    type_analyzer: &mut TypeAnalyzer,
+    referred_relation_ids: &[NodeId],
+    worker: &mut ExpressionsWorker<M>,
+    plan: &mut Plan,
+) -> Result<(NodeId, Option<Collation>), SbroadError>
+where
+    M: Metadata,
+{
+    let param_types = get_parameter_derived_types(type_analyzer);
+    let parse_expr = parse_expr_pratt(
+        expression_pairs,
+        &param_types,
+        referred_relation_ids,
+        worker,
+        plan,
+        false,
+    )?;
+    let (parse_expr, collation) = parse_expr.split_collation();
+    let expr_id = parse_expr.populate_plan(plan, worker)?;
+
+    type_system::analyze_and_coerce_scalar_expr(
+        type_analyzer,
+        expr_id,
+        DerivedType::unknown(),
+        plan,
+        &worker.subquery_replaces,
+    )?;
+
+    Ok((expr_id, collation))
+}
+
+/// Build a plan of `GENERATE_SERIES(start, stop[, step])`: a subquery
+/// over a single-row `VALUES` holding the function call. The executor
+/// expands the call into the series elements after parameters are bound.
+fn parse_generate_series<M>(
+    ast: &AbstractSyntaxTree,
+    node_id: usize,
+    type_analyzer: &mut TypeAnalyzer,
+    pairs_map: &mut ParsingPairsMap,
+    worker: &mut ExpressionsWorker<M>,
+    plan: &mut Plan,
+) -> Result<NodeId, SbroadError>
+where
+    M: Metadata,
+{
+    let node = ast.nodes.get_node(node_id)?;
+    let param_types = get_parameter_derived_types(type_analyzer);
+    let mut args = Vec::with_capacity(node.children.len());
+    for ast_arg_id in &node.children {
+        let arg_pair = pairs_map.remove_pair(*ast_arg_id);
+        args.push(parse_expr_no_type_check(
+            Pairs::single(arg_pair),
+            &param_types,
+            &[],
+            worker,
+            plan,
+            false,
+        )?);
+    }
+
+    // Bounds have the type of the series, the step of a datetime
+    // series is an interval string.
+    let ty = generate_series::element_type(plan, &args[..2])?;
+    for (pos, arg_id) in args.iter().enumerate() {
+        let desired_type = match ty {
+            UnrestrictedType::Datetime if pos == 2 => UnrestrictedType::String,
+            _ => ty,
+        };
+        type_system::analyze_and_coerce_scalar_expr(
+            type_analyzer,
+            *arg_id,
+            DerivedType::new(desired_type),
+            plan,
+            &worker.subquery_replaces,
+        )?;
+    }
+
+    let func_id = plan.nodes.push(
+        ScalarFunction {
+            name: generate_series::NAME.to_smolstr(),
+            children: args,
+            feature: None,
+            volatility_type: VolatilityType::Stable,
+            func_type: DerivedType::new(ty),
+            is_system: false,
+            is_window: false,
+        }
+        .into(),
+    );
+    let row_id = plan.nodes.add_row(vec![func_id], None);
+    let values_row_id = plan.add_values_row(row_id)?;
+    let values_id = plan.add_values(vec![values_row_id])?;
+
+    let child_id = plan.add_sub_query(values_id, Some(generate_series::NAME))?;
+    let proj_id = plan.add_proj(child_id, vec![], &[], false, false)?;
+    let proj_output_id = plan.get_relational_output(proj_id)?;
+    let column_id = plan.get_row_list(proj_output_id)?[0];
+    if let MutExpression::Alias(Alias { name, .. }) = plan.get_mut_expression_node(column_id)? {
+        *name = generate_series::NAME.to_smolstr();
+    }
+    plan.add_sub_query(proj_id, Some(generate_series::NAME))
+}
+
+/// Parse rows of `VALUES`. For `INSERT ... VALUES` the target columns and their
+/// default values must be provided to resolve `DEFAULT` markers in the rows.
 fn parse_values_rows<M>(
     rows: &[usize],
     type_analyzer: &mut TypeAnalyzer,
     desired_types: &[UnrestrictedType],
+    insert_columns: Option<(&[Column], &[Value])>,
     pairs_map: &mut ParsingPairsMap,
     worker: &mut ExpressionsWorker<M>,
     plan: &mut Plan,
@@ -5002,14 +6609,35 @@ where
         // Consider the following queries:
         //  - `VALUES (1), ('text')`: both rows are fine, but their types cannot be matched
         //  - `VALUES ($1), (1)`: to infer parameter type in the 1st row we need the 2nd row
-        let expr_id = parse_expr_no_type_check(
-            Pairs::single(row_pair),
-            &param_types,
-            &[],
-            worker,
-            plan,
-            true,
-        )?;
+        let expr_id = if let Rule::InsertRow = row_pair.as_rule() {
+            let (columns, defaults) = insert_columns.expect("INSERT row requires target columns");
+            let mut list = Vec::new();
+            for (pos, item) in row_pair.into_inner().enumerate() {
+                let item_id = if let Rule::DefaultValue = item.as_rule() {
+                    default_column_value(columns.get(pos), defaults.get(pos), plan)?
+                } else {
+                    parse_expr_no_type_check(
+                        Pairs::single(item),
+                        &param_types,
+                        &[],
+                        worker,
+                        plan,
+                        true,
+                    )?
+                };
+                list.push(item_id);
+            }
+            plan.nodes.add_row(list, None)
+        } else {
+            parse_expr_no_type_check(
+                Pairs::single(row_pair),
+                &param_types,
+                &[],
+                worker,
+                plan,
+                true,
+            )?
+        };
         let values_row_id = plan.add_values_row(expr_id)?;
         plan.fix_subquery_rows(worker, values_row_id)?;
         values_rows_ids.push(values_row_id);
@@ -5026,6 +6654,32 @@ where
     Ok(values_rows_ids)
 }
 
+/// Get the value `DEFAULT` stands for in `INSERT ... VALUES` row.
+///
+/// It is the declared default of the column or NULL, which is not allowed
+/// for non-nullable columns.
+fn default_column_value(
+    column: Option<&Column>,
+    default: Option<&Value>,
+    plan: &mut Plan,
+) -> Result<NodeId, SbroadError> {
+    let default = default.cloned().unwrap_or(Value::Null);
+    // In case there are more values than columns, the error is
+    // reported by the type analysis of the whole row.
+    if let Some(column) = column {
+        if !column.is_nullable && default == Value::Null {
+            return Err(SbroadError::Invalid(
+                Entity::Column,
+                Some(format_smolstr!(
+                    "NonNull column {} has no default value",
+                    to_user(&column.name)
+                )),
+            ));
+        }
+    }
+    Ok(plan.add_const(default))
+}
+
 fn parse_select(
     select_pairs: Pairs<Rule>,
     pos_to_ast_id: &SelectChildPairTranslation,
@@ -5345,10 +6999,58 @@ impl<'pairs_map> ParsingPairsMap<'pairs_map> {
 /// * ast_id (value) -- id of our AST node.
 type PairToAstIdTranslation<'i> = HashMap<Pair<'i, Rule>, usize>;
 
-#[derive(Clone, Debug)]
-enum OrderNulls {
-    First,
-    Last,
+pub(crate) fn nesting_depth_error(max_depth: usize) -> SbroadError {
+    SbroadError::Invalid(
+        Entity::Expression,
+        Some(format_smolstr!(
+            "expression nesting depth exceeds the limit of {max_depth}"
+        )),
+    )
+}
+
+/// Check that brackets in the query text are not nested deeper than
+/// `max_depth`. Runs before the query is handed to the parser, which
+/// would overflow the stack on deeper nesting.
+fn check_bracket_depth(query: &str, max_depth: usize) -> Result<(), SbroadError> {
+    let mut quote = None;
+    let mut depth: usize = 0;
+    for c in query.chars() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '\'' | '"' => quote = Some(c),
+            '(' | '[' => {
+                depth += 1;
+                if depth > max_depth {
+                    return Err(nesting_depth_error(max_depth));
+                }
+            }
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Check that an `Expr` pair does not contain a chain of `NOT` prefixes
+/// longer than `max_depth`. The Pratt parser recurses into every prefix.
+fn check_prefix_chain(expr: &Pair<'_, Rule>, max_depth: usize) -> Result<(), SbroadError> {
+    let mut chain: usize = 0;
+    for child in expr.clone().into_inner() {
+        if child.as_rule() != Rule::UnaryNot {
+            chain = 0;
+            continue;
+        }
+        chain += 1;
+        if chain > max_depth {
+            return Err(nesting_depth_error(max_depth));
+        }
+    }
+    Ok(())
 }
 
 impl AbstractSyntaxTree {
@@ -5357,6 +7059,11 @@ impl AbstractSyntaxTree {
         AbstractSyntaxTree {
             nodes: ParseNodes::new(),
             top: None,
+            identifier_case: IdentifierCase::default(),
+            max_expression_depth: DEFAULT_MAX_EXPRESSION_DEPTH,
+            select_into: None,
+            current_of: None,
+            fingerprint: None,
         }
     }
 
@@ -5378,6 +7085,8 @@ impl AbstractSyntaxTree {
         pairs_to_ast_id: &mut PairToAstIdTranslation<'query>,
         tnt_parameters_ordered: &mut Vec<Pair<'query, Rule>>,
     ) -> Result<(), SbroadError> {
+        let parser_depth = self.max_expression_depth.min(MAX_PARSER_NESTING_DEPTH);
+        check_bracket_depth(query, parser_depth)?;
         let mut command_pair = match ParseTree::parse(Rule::Command, query) {
             Ok(p) => p,
             Err(e) => {
@@ -5387,6 +7096,7 @@ impl AbstractSyntaxTree {
                 ))
             }
         };
+        self.fingerprint = Some(fingerprint::fingerprint_pairs(query, command_pair.clone()));
         let top_pair = command_pair
             .next()
             .expect("Query expected as a first parsing tree child.");
@@ -5401,6 +7111,7 @@ impl AbstractSyntaxTree {
         let mut has_tnt_params = false;
         let mut has_pg_params = false;
         let mut tnt_parameters = Vec::new();
+        let mut into_spans = Vec::new();
         let mut stack: Vec<StackParseNode> = vec![top];
         while !stack.is_empty() {
             let stack_node: StackParseNode = match stack.pop() {
@@ -5408,6 +7119,23 @@ impl AbstractSyntaxTree {
                 None => break,
             };
 
+            if stack_node.pair.as_rule() == Rule::CurrentOf {
+                self.fill_current_of(stack_node.pair);
+                continue;
+            }
+
+            if stack_node.pair.as_rule() == Rule::Expr {
+                check_prefix_chain(&stack_node.pair, parser_depth)?;
+            }
+
+            if stack_node.pair.as_rule() == Rule::TableShorthand {
+                let scan_id = self.fill_table_shorthand(&stack_node, pos_to_ast_id)?;
+                for parse_child in stack_node.pair.into_inner() {
+                    stack.push(StackParseNode::new(parse_child, Some(scan_id)));
+                }
+                continue;
+            }
+
             // Save node to AST.
             let arena_node_id = self.nodes.push_node(ParseNode::new(
                 stack_node.pair.as_rule(),
@@ -5438,12 +7166,15 @@ impl AbstractSyntaxTree {
             match stack_node.pair.as_rule() {
                 Rule::Expr
                 | Rule::Row
+                | Rule::InsertRow
                 | Rule::Literal
                 | Rule::SelectWithOptionalContinuation
                 | Rule::Parameter => {
                     // * `Expr`s are parsed using Pratt parser with a separate `parse_expr`
                     //   function call on the stage of `resolve_metadata`.
                     // * `Row`s are added to support parsing Row expressions under `Values` nodes.
+                    // * `InsertRow`s are the same rows under `InsertValues` which may contain
+                    //   `DEFAULT` markers.
                     // * `Literal`s are added to support procedure calls and
                     //   ALTER SYSTEM which should not contain all possible `Expr`s.
                     // * `SelectWithOptionalContinuation` is also parsed using Pratt parser
@@ -5462,6 +7193,10 @@ impl AbstractSyntaxTree {
                 Rule::SubQuery => {
                     pairs_to_ast_id.insert(stack_node.pair.clone(), arena_node_id);
                 }
+                Rule::SelectInto => {
+                    let span = stack_node.pair.as_span();
+                    into_spans.push((arena_node_id, span.start()..span.end()));
+                }
                 _ => {}
             }
 
@@ -5483,10 +7218,64 @@ impl AbstractSyntaxTree {
 
         self.transform_update()?;
         self.transform_delete()?;
+        if let Some(into_id) = self.transform_select_into()? {
+            // The table is filled by the query without the `INTO` clause.
+            let (_, span) = into_spans
+                .into_iter()
+                .find(|(id, _)| *id == into_id)
+                .expect("INTO clause span must be saved");
+            let query = format_smolstr!("{}{}", &query[..span.start], &query[span.end..]);
+            self.select_into = Some((into_id, query));
+        }
         self.transform_select()?;
         Ok(())
     }
 
+    /// Fill the nodes of `SELECT * FROM t` under the `Select` node
+    /// for the `TABLE t` shorthand. Returns the id of the `Scan` node
+    /// the table belongs to.
+    fn fill_table_shorthand(
+        &mut self,
+        stack_node: &StackParseNode,
+        pos_to_ast_id: &mut SelectChildPairTranslation,
+    ) -> Result<usize, SbroadError> {
+        let select_id = stack_node.arena_parent_id;
+        if let Some(select_id) = select_id {
+            self.nodes.update_value(select_id, None)?;
+        }
+
+        // Children are prepended, so add the scan first to keep
+        // the projection in front of it.
+        let scan_id = self.nodes.push_node(ParseNode::new(Rule::Scan, None));
+        self.nodes.add_child(select_id, scan_id)?;
+
+        let projection_id = self.nodes.push_node(ParseNode::new(Rule::Projection, None));
+        self.nodes.add_child(select_id, projection_id)?;
+        let asterisk_id = self
+            .nodes
+            .push_node(ParseNode::new(Rule::Asterisk, Some("*".into())));
+        self.nodes.add_child(Some(projection_id), asterisk_id)?;
+        pos_to_ast_id.insert(stack_node.pair.line_col(), projection_id);
+
+        Ok(scan_id)
+    }
+
+    /// Remember the cursor of `WHERE CURRENT OF`. The clause is not added
+    /// to the tree: the statement is built without a filter, which is
+    /// resolved from the row the cursor is positioned on before execution.
+    fn fill_current_of(&mut self, pair: Pair<'_, Rule>) {
+        let span = pair.as_span();
+        let cursor = pair
+            .into_inner()
+            .next()
+            .expect("cursor name expected in CURRENT OF");
+        let cursor_id = self.nodes.push_node(ParseNode::new(
+            Rule::Identifier,
+            Some(SmolStr::from(cursor.as_str())),
+        ));
+        self.current_of = Some((cursor_id, span.start()..span.end()));
+    }
+
     fn parse_projection<M: Metadata>(
         &self,
         plan: &mut Plan,
@@ -5778,14 +7567,12 @@ impl AbstractSyntaxTree {
                 .first()
                 .expect("OrderByElement must have at least one child");
             let expr_pair = pairs_map.remove_pair(*order_by_element_expr_id);
-            let expr_plan_node_id = parse_scalar_expr(
+            let (expr_plan_node_id, collation) = parse_sort_key_expr(
                 Pairs::single(expr_pair),
                 type_analyzer,
-                DerivedType::unknown(),
                 &[referred_rel_id],
                 worker,
                 plan,
-                false,
             )?;
 
             // In case index is specified as ordering element, we have to check that
@@ -5809,21 +7596,17 @@ impl AbstractSyntaxTree {
                     }
 
                     if let Expression::Constant(Constant {value: Value::Integer(index)}) = expr {
-                        let index_usize = usize::try_from(*index).map_err(|_| {
-                            SbroadError::Invalid(
-                                Entity::Expression,
-                                Some(format_smolstr!(
-                            "Unable to convert OrderBy index ({index}) to usize"
-                        )),
-                            )
-                        })?;
+                        // Only a bare integer literal is a position, computed expressions
+                        // like `1 + 1` are handled as regular sort keys below.
+                        let not_in_select_list = || SbroadError::Invalid(
+                            Entity::Expression,
+                            Some(format_smolstr!("ORDER BY position {index} is not in select list"))
+                        );
+                        let index_usize = usize::try_from(*index).map_err(|_| not_in_select_list())?;
 
                         let output = plan.get_row_list(output_id)?;
                         let output_len = output.len();
-                        let output_idx = index_usize.checked_sub(1).ok_or(SbroadError::Invalid(
-                                Entity::Expression,
-                                Some(format_smolstr!("ORDER BY position 0 is not in select list"))
-                            ))?;
+                        let output_idx = index_usize.checked_sub(1).ok_or_else(not_in_select_list)?;
                         if let Some(alias_node_id) = output.get(output_idx) {
                             let alias_node = plan.get_expression_node(*alias_node_id)?;
                             if let Expression::Alias(Alias { child, .. }) = alias_node {
@@ -5881,6 +7664,13 @@ impl AbstractSyntaxTree {
                 _ => unreachable!("Unacceptable node as an ORDER BY element.")
             };
 
+            if let (OrderByEntity::Index { .. }, Some(_)) = (&entity, collation) {
+                return Err(SbroadError::Unsupported(
+                    Entity::Collation,
+                    Some("COLLATE is not allowed for ORDER BY positions".into()),
+                ));
+            }
+
             let mut order_type = None;
             let mut order_nulls = None;
 
@@ -5889,8 +7679,8 @@ impl AbstractSyntaxTree {
                 match node.rule {
                     Rule::Asc => order_type = Some(OrderByType::Asc),
                     Rule::Desc => order_type = Some(OrderByType::Desc),
-                    Rule::NullsFirst => order_nulls = Some(OrderNulls::First),
-                    Rule::NullsLast => order_nulls = Some(OrderNulls::Last),
+                    Rule::NullsFirst => order_nulls = Some(NullsOrder::First),
+                    Rule::NullsLast => order_nulls = Some(NullsOrder::Last),
                     rule => unreachable!(
                         "{}",
                         format!("Unexpected rule met under OrderByElement: {rule:?}")
@@ -5912,8 +7702,8 @@ impl AbstractSyntaxTree {
 
                 let is_null_expr_id = plan.add_unary(Unary::IsNull, entity_expr_id)?;
                 let top_expr_id = match order_nulls {
-                    OrderNulls::Last => is_null_expr_id,
-                    OrderNulls::First => plan.add_unary(Unary::Not, is_null_expr_id)?,
+                    NullsOrder::Last => is_null_expr_id,
+                    NullsOrder::First => plan.add_unary(Unary::Not, is_null_expr_id)?,
                 };
                 let new_entity_first = OrderByEntity::Expression {
                     expr_id: top_expr_id,
@@ -5921,12 +7711,21 @@ impl AbstractSyntaxTree {
                 order_by_elements.push(OrderByElement {
                     entity: new_entity_first,
                     order_type: None,
+                    collation: None,
+                });
+                order_by_elements.push(OrderByElement {
+                    entity,
+                    order_type,
+                    collation,
                 });
-                order_by_elements.push(OrderByElement { entity, order_type });
                 continue;
             }
 
-            order_by_elements.push(OrderByElement { entity, order_type });
+            order_by_elements.push(OrderByElement {
+                entity,
+                order_type,
+                collation,
+            });
         }
         Ok(order_by_elements)
     }
@@ -6169,6 +7968,7 @@ impl AbstractSyntaxTree {
         M: Metadata,
     {
         let mut plan = Plan::default();
+        plan.max_expression_depth = Some(self.max_expression_depth);
 
         let mut type_analyzer = type_system::new_analyzer(param_types);
 
@@ -6182,6 +7982,7 @@ impl AbstractSyntaxTree {
         let mut worker =
             ExpressionsWorker::new(metadata, sq_pair_to_ast_ids, tnt_parameters_positions);
         let mut ctes = CTEs::new();
+        let mut recursive_ctes = RecursiveCtes::collect(self)?;
         // This flag disables resolving of table names for DROP TABLE queries,
         // as it can be used with tables that are not presented in metadata.
         // Unresolved table names are handled in picodata depending in IF EXISTS options.
@@ -6210,15 +8011,22 @@ impl AbstractSyntaxTree {
                         .transpose()?
                         .flatten();
 
-                    let indexed_by_id = if alias.is_some() {
-                        node.children.get(2)
-                    } else {
-                        node.children.get(1)
-                    };
+                    let mut indexed_by_id = None;
+                    let mut table_sample_id = None;
+                    for ast_id in node.children.iter().skip(1) {
+                        match self.nodes.get_node(*ast_id)?.rule {
+                            Rule::IndexedByExpr => indexed_by_id = Some(*ast_id),
+                            Rule::TableSample => table_sample_id = Some(*ast_id),
+                            _ => {}
+                        }
+                    }
                     let indexed_by = indexed_by_id
-                        .map(|ast_id| parse_indexed_by_expr(self, *ast_id))
+                        .map(|ast_id| parse_indexed_by_expr(self, ast_id))
                         .transpose()?
                         .flatten();
+                    let sample = table_sample_id
+                        .map(|ast_id| parse_table_sample(self, ast_id))
+                        .transpose()?;
 
                     if indexed_by.is_some()
                         && !matches!(rel_child_node, Relational::ScanRelation(_))
@@ -6229,21 +8037,46 @@ impl AbstractSyntaxTree {
                         ));
                     }
 
+                    if sample.is_some() && !matches!(rel_child_node, Relational::ScanRelation(_)) {
+                        return Err(SbroadError::Invalid(
+                            Entity::Query,
+                            Some("TABLESAMPLE clause is only supported for tables".to_smolstr()),
+                        ));
+                    }
+
                     if let Some(alias_name) = alias {
                         used_aliases.insert(alias_name.clone());
                         // CTE scans can have different aliases, so clone the CTE scan node,
                         // preserving its subtree.
-                        if let Relational::ScanCte(ScanCte { child, .. }) = rel_child_node {
-                            let scan_id = plan.add_cte(*child, alias_name, vec![])?;
+                        if let Relational::ScanCte(ScanCte {
+                            child,
+                            is_recursive,
+                            ..
+                        }) = rel_child_node
+                        {
+                            let (child, is_recursive) = (*child, *is_recursive);
+                            let scan_id = plan.add_cte(child, alias_name, vec![])?;
+                            if let MutRelational::ScanCte(ScanCte {
+                                is_recursive: scan_is_recursive,
+                                ..
+                            }) = plan.get_mut_relation_node(scan_id)?
+                            {
+                                *scan_is_recursive = is_recursive;
+                            }
                             map.add(id, scan_id);
                         } else {
                             let mut scan = plan.get_mut_relation_node(rel_child_id_plan)?;
                             scan.set_scan_name(Some(alias_name.to_smolstr()))?;
                         }
-                    } else if matches!(rel_child_node, Relational::ScanSubQuery(_)) {
+                    } else if matches!(
+                        rel_child_node,
+                        Relational::ScanSubQuery(ScanSubQuery { alias: None, .. })
+                    ) {
                         unnamed_subqueries.push(rel_child_id_plan);
                     }
 
+                    worker.scans.push(map.get(id)?);
+
                     if let Some(index_name) = indexed_by {
                         let MutRelational::ScanRelation(ScanRelation {
                             relation,
@@ -6261,6 +8094,15 @@ impl AbstractSyntaxTree {
                         plan.indexes.insert(index_name, index);
                         plan.index_version_map.insert([table.id, index_id], 0);
                     }
+
+                    if let Some(table_sample) = sample {
+                        let MutRelational::ScanRelation(ScanRelation { sample, .. }) =
+                            plan.get_mut_relation_node(rel_child_id_plan)?
+                        else {
+                            unreachable!();
+                        };
+                        *sample = Some(Box::new(table_sample));
+                    }
                 }
                 Rule::ScanTable => {
                     let ast_table_id = node
@@ -6274,7 +8116,15 @@ impl AbstractSyntaxTree {
                     map.add(id, scan_id);
                 }
                 Rule::ScanCteOrTable => {
-                    parse_scan_cte_or_table(self, metadata, id, &mut map, &mut ctes, &mut plan)?;
+                    parse_scan_cte_or_table(
+                        self,
+                        metadata,
+                        id,
+                        &mut map,
+                        &mut ctes,
+                        &mut recursive_ctes,
+                        &mut plan,
+                    )?;
                 }
                 Rule::Cte => {
                     parse_cte(
@@ -6282,6 +8132,7 @@ impl AbstractSyntaxTree {
                         id,
                         &mut map,
                         &mut ctes,
+                        &mut recursive_ctes,
                         &mut plan,
                         &mut type_analyzer,
                         pairs_map,
@@ -6305,6 +8156,17 @@ impl AbstractSyntaxTree {
                     worker.sq_ast_to_plan_id.add(id, plan_sq_id);
                     map.add(id, plan_sq_id);
                 }
+                Rule::GenerateSeries => {
+                    let plan_sq_id = parse_generate_series(
+                        self,
+                        id,
+                        &mut type_analyzer,
+                        pairs_map,
+                        &mut worker,
+                        &mut plan,
+                    )?;
+                    map.add(id, plan_sq_id);
+                }
                 Rule::WindowBody => {
                     // WindowBody will be parsed under Projection.
                 }
@@ -6363,6 +8225,150 @@ impl AbstractSyntaxTree {
                         val,
                     });
                 }
+                Rule::RecursionMax => {
+                    let ast_child_id = node
+                        .children
+                        .first()
+                        .expect("no children for sql_recursion_max option");
+                    let val = parse_option(
+                        self,
+                        &mut type_analyzer,
+                        *ast_child_id,
+                        pairs_map,
+                        &mut worker,
+                        &mut plan,
+                    )?;
+                    plan.raw_options.push(OptionSpec {
+                        kind: OptionKind::RecursionMax,
+                        val,
+                    });
+                }
+                Rule::ReplicaLagMax => {
+                    let ast_child_id = node
+                        .children
+                        .first()
+                        .expect("no children for replica_lag_max option");
+                    let val = parse_option(
+                        self,
+                        &mut type_analyzer,
+                        *ast_child_id,
+                        pairs_map,
+                        &mut worker,
+                        &mut plan,
+                    )?;
+                    plan.raw_options.push(OptionSpec {
+                        kind: OptionKind::ReplicaLagMax,
+                        val,
+                    });
+                }
+                Rule::MaxBucketsPerQuery => {
+                    let ast_child_id = node
+                        .children
+                        .first()
+                        .expect("no children for sql_max_buckets_per_query option");
+                    let val = parse_option(
+                        self,
+                        &mut type_analyzer,
+                        *ast_child_id,
+                        pairs_map,
+                        &mut worker,
+                        &mut plan,
+                    )?;
+                    plan.raw_options.push(OptionSpec {
+                        kind: OptionKind::MaxBucketsPerQuery,
+                        val,
+                    });
+                }
+                Rule::PartialResults => {
+                    let ast_child_id = node
+                        .children
+                        .first()
+                        .expect("no children for sql_partial_results option");
+                    let val = parse_bool_option(
+                        self,
+                        &mut type_analyzer,
+                        *ast_child_id,
+                        pairs_map,
+                        &mut worker,
+                        &mut plan,
+                    )?;
+                    plan.raw_options.push(OptionSpec {
+                        kind: OptionKind::PartialResults,
+                        val,
+                    });
+                }
+                Rule::WaitIndex => {
+                    let ast_child_id = node
+                        .children
+                        .first()
+                        .expect("no children for wait_index option");
+                    let val = parse_option(
+                        self,
+                        &mut type_analyzer,
+                        *ast_child_id,
+                        pairs_map,
+                        &mut worker,
+                        &mut plan,
+                    )?;
+                    plan.raw_options.push(OptionSpec {
+                        kind: OptionKind::WaitIndex,
+                        val,
+                    });
+                }
+                Rule::WaitIndexTimeout => {
+                    let ast_child_id = node
+                        .children
+                        .first()
+                        .expect("no children for wait_index_timeout option");
+                    let val = parse_option(
+                        self,
+                        &mut type_analyzer,
+                        *ast_child_id,
+                        pairs_map,
+                        &mut worker,
+                        &mut plan,
+                    )?;
+                    plan.raw_options.push(OptionSpec {
+                        kind: OptionKind::WaitIndexTimeout,
+                        val,
+                    });
+                }
+                Rule::ResultRowMax => {
+                    let ast_child_id = node
+                        .children
+                        .first()
+                        .expect("no children for sql_result_row_max option");
+                    let val = parse_option(
+                        self,
+                        &mut type_analyzer,
+                        *ast_child_id,
+                        pairs_map,
+                        &mut worker,
+                        &mut plan,
+                    )?;
+                    plan.raw_options.push(OptionSpec {
+                        kind: OptionKind::ResultRowMax,
+                        val,
+                    });
+                }
+                Rule::DispatchConcurrencyMax => {
+                    let ast_child_id = node
+                        .children
+                        .first()
+                        .expect("no children for sql_dispatch_concurrency_max option");
+                    let val = parse_option(
+                        self,
+                        &mut type_analyzer,
+                        *ast_child_id,
+                        pairs_map,
+                        &mut worker,
+                        &mut plan,
+                    )?;
+                    plan.raw_options.push(OptionSpec {
+                        kind: OptionKind::DispatchConcurrencyMax,
+                        val,
+                    });
+                }
                 Rule::GroupBy => {
                     // Reminder: first GroupBy child in `node.children` is always a relational node.
                     let mut children: Vec<NodeId> = Vec::with_capacity(node.children.len());
@@ -6544,6 +8550,7 @@ impl AbstractSyntaxTree {
                         &node.children,
                         &mut type_analyzer,
                         &[],
+                        None,
                         pairs_map,
                         &mut worker,
                         &mut plan,
@@ -6664,6 +8671,15 @@ impl AbstractSyntaxTree {
 
                         match col.get_role() {
                             ColumnRole::User => {
+                                if relation.identity == Some(*pos) {
+                                    return Err(SbroadError::Invalid(
+                                        Entity::Query,
+                                        Some(format_smolstr!(
+                                            "it is illegal to update identity column: {}",
+                                            to_user(col_name)
+                                        )),
+                                    ));
+                                }
                                 if pk_positions.contains(pos) {
                                     return Err(SbroadError::Invalid(
                                         Entity::Query,
@@ -6703,14 +8719,24 @@ impl AbstractSyntaxTree {
                 }
                 Rule::Delete => {
                     // Get table name and selection plan node id.
-                    // Reminder: first child of Delete is a `ScanTable` or `DeleteFilter`
-                    //           (under which there must be a `ScanTable`).
+                    // Reminder: first child of Delete is a `ScanTable`, `DeleteFilter`
+                    //           (under which there must be a `ScanTable`) or a `Join`
+                    //           with the `USING` source (followed by a `ScanTable`).
                     let first_child_id = node
                         .children
                         .first()
                         .expect("Delete must have at least one child");
                     let first_child_node = self.nodes.get_node(*first_child_id)?;
                     let (proj_child_id, table_name) = match first_child_node.rule {
+                        Rule::Join => {
+                            let plan_join_id = map.get(*first_child_id)?;
+                            let ast_table_id = node
+                                .children
+                                .get(1)
+                                .expect("Table expected as a second child of Delete with USING");
+                            let plan_scan_id = map.get(*ast_table_id)?;
+                            (Some(plan_join_id), plan.get_scan_relation(plan_scan_id)?)
+                        }
                         Rule::ScanTable => {
                             let plan_scan_id = map.get(*first_child_id)?;
                             let plan_scan_node = plan.get_relation_node(plan_scan_id)?;
@@ -6804,6 +8830,8 @@ impl AbstractSyntaxTree {
                     let plan_proj_id = if let Some(proj_child_id) = proj_child_id {
                         // The projection in the delete operator contains only the primary key columns.
                         let mut pk_columns = Vec::with_capacity(table.primary_key.positions.len());
+                        let scan = matches!(first_child_node.rule, Rule::Join)
+                            .then_some(table.name.as_str());
                         for pos in &table.primary_key.positions {
                             let column: &Column = table.columns.get(*pos).ok_or_else(|| {
                                 SbroadError::Invalid(
@@ -6815,7 +8843,8 @@ impl AbstractSyntaxTree {
                                     )),
                                 )
                             })?;
-                            let col_with_scan = ColumnWithScan::new(column.name.as_str(), None);
+                            // Columns of the USING source may shadow the table ones.
+                            let col_with_scan = ColumnWithScan::new(column.name.as_str(), scan);
                             pk_columns.push(col_with_scan);
                         }
                         let pk_column_ids = plan.new_columns(
@@ -6875,6 +8904,9 @@ impl AbstractSyntaxTree {
                             plan.mark_as_explain(Some(ExplainQueryPlan));
                         }
                         explain_child_id = child_iter.next().expect("Explain has no children.");
+                    } else if let Rule::ExplainCosts = explain_child.rule {
+                        plan.mark_as_explain(Some(ExplainCosts));
+                        explain_child_id = child_iter.next().expect("Explain has no children.");
                     } else {
                         plan.mark_as_explain(Some(Explain));
                     }
@@ -7003,6 +9035,11 @@ impl AbstractSyntaxTree {
                     let plan_id = plan.nodes.push(rename_proc.into());
                     map.add(id, plan_id);
                 }
+                Rule::Comment => {
+                    let comment = parse_comment(self, node)?;
+                    let plan_id = plan.nodes.push(comment.into());
+                    map.add(id, plan_id);
+                }
                 Rule::DropSchema => {
                     let drop_schema = DdlOwned::DropSchema;
                     let plan_id = plan.nodes.push(drop_schema.into());
@@ -7297,12 +9334,15 @@ impl AbstractSyntaxTree {
                     map.add(id, plan_id);
                 }
                 Rule::SetTransaction => {
-                    let set_transaction_node = SetTransaction {
-                        timeout: get_default_timeout(),
-                    };
+                    let set_transaction_node = parse_set_transaction(self, node)?;
                     let plan_id = plan.nodes.push(set_transaction_node.into());
                     map.add(id, plan_id);
                 }
+                Rule::Listen | Rule::Unlisten | Rule::Notify => {
+                    let notification = parse_notification(self, node)?;
+                    let plan_id = plan.nodes.push(notification.into());
+                    map.add(id, plan_id);
+                }
                 Rule::Deallocate => {
                     let deallocate = parse_deallocate(self, node)?;
                     let plan_id = plan.nodes.push(deallocate.into());
@@ -7324,6 +9364,17 @@ impl AbstractSyntaxTree {
                     let plan_id = plan.nodes.push(rollback.into());
                     map.add(id, plan_id);
                 }
+                Rule::Savepoint | Rule::ReleaseSavepoint | Rule::RollbackToSavepoint => {
+                    let name_id = node.children.first().expect("savepoint name expected");
+                    let name = parse_identifier(self, *name_id)?;
+                    let tcl = match node.rule {
+                        Rule::Savepoint => Tcl::Savepoint(name),
+                        Rule::ReleaseSavepoint => Tcl::Release(name),
+                        _ => Tcl::RollbackTo(name),
+                    };
+                    let plan_id = plan.nodes.push(tcl.into());
+                    map.add(id, plan_id);
+                }
                 Rule::AuditPolicy => {
                     let audit_policy = parse_audit_policy(self, node)?;
                     let plan_id = plan.nodes.push(audit_policy.into());
@@ -7383,6 +9434,17 @@ impl AbstractSyntaxTree {
         plan.recalculate_ref_types()?;
         plan.explicit_cast_func_args(&type_analyzer)?;
 
+        if let Some((into_id, query)) = &self.select_into {
+            plan.select_into = Some(Box::new(parse_select_into(self, *into_id, query, &plan)?));
+        }
+        if let Some((cursor_id, span)) = &self.current_of {
+            plan.current_of = Some(CurrentOf {
+                cursor: parse_normalized_identifier(self, *cursor_id)?,
+                span: span.clone(),
+            });
+        }
+        plan.query_fingerprint.clone_from(&self.fingerprint);
+
         Ok(plan)
     }
 }
@@ -7412,6 +9474,8 @@ impl Ast for AbstractSyntaxTree {
         let mut sq_pair_to_ast_ids: PairToAstIdTranslation = HashMap::new();
 
         let mut ast = AbstractSyntaxTree::empty();
+        ast.identifier_case = metadata.identifier_case();
+        ast.max_expression_depth = metadata.max_expression_depth();
         let mut tnt_parameters_positions = Vec::new();
         ast.fill(
             query,
@@ -7468,15 +9532,94 @@ impl Plan {
 }
 
 /// Parse boolean values in text format.
-/// It supports the same formats as PostgreSQL (grep `parse_bool_with_len`).
+/// It supports the same formats as PostgreSQL (grep `parse_bool_with_len`),
+/// the leading and trailing whitespaces are ignored.
 pub fn try_parse_bool(s: &str) -> Option<bool> {
-    match s.to_lowercase_smolstr().as_str() {
+    match s.trim().to_lowercase_smolstr().as_str() {
         "t" | "true" | "yes" | "on" | "1" => Some(true),
         "f" | "false" | "no" | "off" | "0" => Some(false),
         _ => None,
     }
 }
 
+/// Parse `bytea` values in text format.
+///
+/// Both PostgreSQL input formats are supported:
+/// * hex format: `\x` followed by pairs of hex digits, pairs may be separated by whitespace;
+/// * escape format: bytes are taken as is, except for `\\` (a backslash)
+///   and `\nnn` (an octal byte value).
+pub fn try_parse_bytea(s: &str) -> Option<Vec<u8>> {
+    fn hex_digit(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            b'A'..=b'F' => Some(c - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    let bytes = s.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+
+    if let Some(hex) = s.strip_prefix("\\x") {
+        let mut digits = hex.bytes().filter(|c| !c.is_ascii_whitespace());
+        while let Some(high) = digits.next() {
+            let low = digits.next()?;
+            result.push((hex_digit(high)? << 4) | hex_digit(low)?);
+        }
+        return Some(result);
+    }
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'\\' {
+            result.push(bytes[i]);
+            i += 1;
+        } else if bytes.get(i + 1) == Some(&b'\\') {
+            result.push(b'\\');
+            i += 2;
+        } else {
+            let octal = bytes.get(i + 1..i + 4)?;
+            if !matches!(octal, [b'0'..=b'3', b'0'..=b'7', b'0'..=b'7']) {
+                return None;
+            }
+            result.push(((octal[0] - b'0') << 6) | ((octal[1] - b'0') << 3) | (octal[2] - b'0'));
+            i += 4;
+        }
+    }
+    Some(result)
+}
+
+/// Units `DATE_TRUNC` truncates a datetime to.
+const DATE_TRUNC_UNITS: [&str; 8] = [
+    "second", "minute", "hour", "day", "week", "month", "quarter", "year",
+];
+
+/// Check the unit of `DATE_TRUNC` if it is a constant, the other units
+/// are checked on execution.
+fn check_date_trunc_unit(plan: &Plan, arg_ids: &[NodeId]) -> Result<(), SbroadError> {
+    let Some(unit_id) = arg_ids.first() else {
+        return Ok(());
+    };
+    if let Expression::Constant(Constant {
+        value: Value::String(unit),
+    }) = plan.get_expression_node(*unit_id)?
+    {
+        let known = DATE_TRUNC_UNITS
+            .iter()
+            .any(|known| unit.eq_ignore_ascii_case(known));
+        if !known {
+            return Err(SbroadError::Invalid(
+                Entity::Expression,
+                Some(format_smolstr!(
+                    "date_trunc: unit \"{unit}\" not recognized"
+                )),
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Parse datetime values in text format.
 ///
 /// It tries to support the same formats as in PostgreSQL.
@@ -7555,10 +9698,85 @@ pub fn try_parse_datetime(s: &str) -> Option<Datetime> {
         return Some(datetime.into());
     }
 
+    if let Some(datetime) = try_from_timestamp(s) {
+        return Some(datetime.into());
+    }
+
     None
 }
 
+/// Parse `YYYY-MM-DD hh:mm[:ss[.fraction]]` (`T` may separate the date and
+/// the time) optionally followed by a time zone offset. Timestamps without
+/// an offset are in UTC, like `timestamptz` values in a PostgreSQL session
+/// with the UTC time zone.
+fn try_from_timestamp(s: &str) -> Option<time::OffsetDateTime> {
+    use time::macros::format_description;
+
+    let formats = [
+        format_description!("[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]"),
+        format_description!("[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond]"),
+        format_description!("[year]-[month]-[day] [hour]:[minute]:[second]"),
+        format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]"),
+        format_description!("[year]-[month]-[day] [hour]:[minute]"),
+        format_description!("[year]-[month]-[day]T[hour]:[minute]"),
+    ];
+
+    let (local, offset) = split_utc_offset(s.trim())?;
+    formats
+        .iter()
+        .find_map(|fmt| time::PrimitiveDateTime::parse(local, fmt).ok())
+        .map(|datetime| datetime.assume_offset(offset))
+}
+
+/// Split a timestamp into the local time and the time zone offset:
+/// `Z`, `+3`, `+03`, `+0300` or `+03:00`, possibly after a space.
+fn split_utc_offset(s: &str) -> Option<(&str, time::UtcOffset)> {
+    if let Some(local) = s.strip_suffix(['Z', 'z']) {
+        return Some((local.trim_end(), time::UtcOffset::UTC));
+    }
+
+    // The date contains dashes, so look for the offset sign after it.
+    const DATE_LEN: usize = "YYYY-MM-DD".len();
+    let Some(pos) = s.get(DATE_LEN..)?.rfind(['+', '-']) else {
+        return Some((s, time::UtcOffset::UTC));
+    };
+    let pos = pos + DATE_LEN;
+    let (local, offset) = (s[..pos].trim_end(), &s[pos + 1..]);
+    let (hours, minutes) = match offset.split_once(':') {
+        Some(parts) => parts,
+        None if offset.len() > 2 => offset.split_at(offset.len() - 2),
+        None => (offset, "0"),
+    };
+    let is_number = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    if !is_number(hours) || !is_number(minutes) {
+        return None;
+    }
+    let sign = if s.as_bytes()[pos] == b'-' { -1 } else { 1 };
+    let hours = sign * hours.parse::<i8>().ok()?;
+    let minutes = sign * minutes.parse::<i8>().ok()?;
+    let offset = time::UtcOffset::from_hms(hours, minutes, 0).ok()?;
+    Some((local, offset))
+}
+
+/// Parse a time of day `hh:mm[:ss[.fraction]]`. A time zone offset after
+/// the time is ignored like in PostgreSQL.
+pub fn try_parse_time(s: &str) -> Option<time::Time> {
+    use time::macros::format_description;
+
+    let formats = [
+        format_description!("[hour padding:none]:[minute]:[second].[subsecond]"),
+        format_description!("[hour padding:none]:[minute]:[second]"),
+        format_description!("[hour padding:none]:[minute]"),
+    ];
+
+    let local = s.trim().split(['+', '-', 'Z', 'z', ' ']).next()?;
+    formats
+        .iter()
+        .find_map(|fmt| time::Time::parse(local, fmt).ok())
+}
+
 pub mod ast;
+pub mod fingerprint;
 pub mod ir;
 pub mod tree;
 mod type_system;
@@ -7594,4 +9812,64 @@ mod tests {
         let datetime = super::try_parse_datetime("2025-10-18").unwrap();
         assert_eq!(datetime.to_string(), "2025-10-18 0:00:00.0 +00:00:00");
     }
+
+    #[test]
+    fn test_datetime_parse_without_offset() {
+        let datetime = super::try_parse_datetime("2024-01-01 00:00:00").unwrap();
+        assert_eq!(datetime.to_string(), "2024-01-01 0:00:00.0 +00:00:00");
+
+        let datetime = super::try_parse_datetime("2024-01-01T10:20:30.123456").unwrap();
+        assert_eq!(datetime.to_string(), "2024-01-01 10:20:30.123456 +00:00:00");
+
+        let datetime = super::try_parse_datetime("2024-01-01 10:20").unwrap();
+        assert_eq!(datetime.to_string(), "2024-01-01 10:20:00.0 +00:00:00");
+    }
+
+    #[test]
+    fn test_datetime_parse_offset() {
+        for (s, expected) in [
+            ("2024-01-01 10:00:00+03", "2024-01-01 10:00:00.0 +03:00:00"),
+            ("2024-01-01 10:00:00 +3", "2024-01-01 10:00:00.0 +03:00:00"),
+            (
+                "2024-01-01 10:00:00 -01:00",
+                "2024-01-01 10:00:00.0 -01:00:00",
+            ),
+            (
+                "2024-01-01 10:00:00 +1245",
+                "2024-01-01 10:00:00.0 +12:45:00",
+            ),
+            ("2024-01-01 10:00:00.5 Z", "2024-01-01 10:00:00.5 +00:00:00"),
+        ] {
+            let datetime = super::try_parse_datetime(s).unwrap();
+            assert_eq!(datetime.to_string(), expected, "{s}");
+        }
+
+        assert!(super::try_parse_datetime("2024-01-01 10:00:00 +25").is_none());
+        assert!(super::try_parse_datetime("2024-01-01 10:00:00 +ab").is_none());
+        assert!(super::try_parse_datetime("2024-13-01 10:00:00").is_none());
+    }
+
+    #[test]
+    fn test_bytea_parse_hex() {
+        let bytes = super::try_parse_bytea(r"\xDEADbeef").unwrap();
+        assert_eq!(bytes, [0xde, 0xad, 0xbe, 0xef]);
+
+        // Whitespace is allowed between pairs of digits.
+        let bytes = super::try_parse_bytea(r"\xde ad").unwrap();
+        assert_eq!(bytes, [0xde, 0xad]);
+
+        assert!(super::try_parse_bytea(r"\x").unwrap().is_empty());
+        assert!(super::try_parse_bytea(r"\xdea").is_none());
+        assert!(super::try_parse_bytea(r"\xzz").is_none());
+    }
+
+    #[test]
+    fn test_bytea_parse_escape() {
+        let bytes = super::try_parse_bytea(r"ab\\c\000\377").unwrap();
+        assert_eq!(bytes, [b'a', b'b', b'\\', b'c', 0x00, 0xff]);
+
+        assert!(super::try_parse_bytea(r"\4").is_none());
+        assert!(super::try_parse_bytea(r"\400").is_none());
+        assert!(super::try_parse_bytea(r"\n").is_none());
+    }
 }
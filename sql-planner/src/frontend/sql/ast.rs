@@ -7,11 +7,13 @@ extern crate pest;
 
 use std::collections::HashSet;
 use std::mem::swap;
+use std::ops::Range;
 
 use pest::iterators::Pair;
 use smol_str::{format_smolstr, SmolStr};
 
 use crate::errors::{Entity, SbroadError};
+use crate::executor::engine::helpers::IdentifierCase;
 
 /// Parse tree
 #[derive(Parser)]
@@ -219,6 +221,18 @@ pub struct AbstractSyntaxTree {
     pub(in crate::frontend::sql) nodes: ParseNodes,
     /// Index of top `ParseNode` in `nodes.arena`.
     pub(in crate::frontend::sql) top: Option<usize>,
+    /// Policy of normalizing unquoted identifiers.
+    pub(in crate::frontend::sql) identifier_case: IdentifierCase,
+    /// Maximum nesting depth of the expressions in the query.
+    pub(in crate::frontend::sql) max_expression_depth: usize,
+    /// Index of the `INTO` clause `ParseNode` of `SELECT ... INTO` and
+    /// the query text without the clause.
+    pub(in crate::frontend::sql) select_into: Option<(usize, SmolStr)>,
+    /// Index of the cursor name `ParseNode` of `WHERE CURRENT OF` and
+    /// the byte range of the clause in the query text.
+    pub(in crate::frontend::sql) current_of: Option<(usize, Range<usize>)>,
+    /// Fingerprint of the query shape, see `fingerprint::query_fingerprint`.
+    pub(in crate::frontend::sql) fingerprint: Option<SmolStr>,
 }
 
 impl PartialEq for AbstractSyntaxTree {
@@ -429,6 +443,70 @@ impl AbstractSyntaxTree {
         Ok(())
     }
 
+    /// Rewrite `delete t using s where expr` into
+    /// ```text
+    /// delete t
+    ///     join
+    ///         scan t
+    ///         s
+    ///         condition expr
+    /// ```
+    /// The condition is `true` when there is no `where` clause.
+    fn transform_delete_using(
+        &mut self,
+        delete_id: usize,
+        using_id: usize,
+    ) -> Result<(), SbroadError> {
+        let node = self.nodes.get_node(delete_id)?;
+        let indexed_scan_id = node.child_n(0);
+        let filter_id = node.children.get(2).copied();
+        let indexed_scan = self.nodes.get_node(indexed_scan_id)?;
+        let table_id = indexed_scan.child_n(0);
+        let table_scan = ParseNode {
+            children: indexed_scan.children.clone(),
+            rule: Rule::Scan,
+            value: None,
+        };
+        let table_scan_id = self.nodes.push_node(table_scan);
+
+        let expr_id = if let Some(filter_id) = filter_id {
+            let filter_node = self.nodes.get_node(filter_id)?;
+            if filter_node.rule != Rule::DeleteFilter {
+                return Err(SbroadError::Invalid(
+                    Entity::ParseNode,
+                    Some(format_smolstr!(
+                        "expected delete filter as a third child, got: {filter_node:?}"
+                    )),
+                ));
+            }
+            filter_node.child_n(0)
+        } else {
+            let true_literal_id = self.nodes.push_node(ParseNode {
+                children: vec![],
+                rule: Rule::True,
+                value: Some("true".into()),
+            });
+            self.nodes.push_node(ParseNode {
+                children: vec![true_literal_id],
+                rule: Rule::Expr,
+                value: None,
+            })
+        };
+        let inner_kind_id = self.nodes.push_node(ParseNode {
+            children: vec![],
+            rule: Rule::InnerJoinKind,
+            value: None,
+        });
+        let join_id = self.nodes.push_node(ParseNode {
+            children: vec![table_scan_id, inner_kind_id, using_id, expr_id],
+            rule: Rule::Join,
+            value: None,
+        });
+        self.nodes
+            .set_children(delete_id, vec![join_id, table_id])?;
+        Ok(())
+    }
+
     /// Put delete table under delete filter to support reference resolution.
     pub(super) fn transform_delete(&mut self) -> Result<(), SbroadError> {
         let arena_len = self.nodes.arena.len();
@@ -438,6 +516,14 @@ impl AbstractSyntaxTree {
                 continue;
             }
 
+            if let Some(using_id) = node.children.get(1) {
+                let using_id = *using_id;
+                if self.nodes.get_node(using_id)?.rule == Rule::Scan {
+                    self.transform_delete_using(id, using_id)?;
+                    continue;
+                }
+            }
+
             let indexed_scan_id = node.child_n(0);
             let indexed_scan = self.nodes.get_node(indexed_scan_id)?;
             let table_id = indexed_scan.child_n(0);
@@ -466,6 +552,72 @@ impl AbstractSyntaxTree {
         Ok(())
     }
 
+    /// Detach the `INTO` clause of `SELECT ... INTO` from its select.
+    /// Returns the id of the clause node if the query has one.
+    ///
+    /// # Errors
+    /// - `INTO` is used anywhere but in a single top-level select
+    pub(super) fn transform_select_into(&mut self) -> Result<Option<usize>, SbroadError> {
+        let mut clauses: Vec<(usize, usize)> = Vec::new();
+        for (id, node) in self.nodes.arena.iter().enumerate() {
+            if node.rule != Rule::Select {
+                continue;
+            }
+            for child_id in &node.children {
+                if self.nodes.get_node(*child_id)?.rule == Rule::SelectInto {
+                    clauses.push((id, *child_id));
+                }
+            }
+        }
+        let (select_id, into_id) = match clauses.as_slice() {
+            [] => return Ok(None),
+            [(select_id, into_id)] if self.top_level_select()? == Some(*select_id) => {
+                (*select_id, *into_id)
+            }
+            _ => {
+                return Err(SbroadError::Invalid(
+                    Entity::Query,
+                    Some(
+                        "INTO is allowed only in a top-level SELECT without set operations".into(),
+                    ),
+                ))
+            }
+        };
+
+        let select = self.nodes.get_mut_node(select_id)?;
+        select.children.retain(|id| *id != into_id);
+        Ok(Some(into_id))
+    }
+
+    /// Get the select of a query consisting of a single `SELECT`
+    /// (possibly with CTEs, `ORDER BY` and `LIMIT`).
+    fn top_level_select(&self) -> Result<Option<usize>, SbroadError> {
+        let mut id = self.get_top()?;
+        for rule in [
+            Rule::Query,
+            Rule::SelectFull,
+            Rule::SelectStatement,
+            Rule::SelectWithOptionalContinuation,
+        ] {
+            let node = self.nodes.get_node(id)?;
+            if node.rule != rule {
+                return Ok(None);
+            }
+            let child_id = match rule {
+                // CTEs go before the statement.
+                Rule::SelectFull => node.children.last(),
+                // Set operations.
+                Rule::SelectWithOptionalContinuation if node.children.len() > 1 => None,
+                _ => node.children.first(),
+            };
+            let Some(child_id) = child_id else {
+                return Ok(None);
+            };
+            id = *child_id;
+        }
+        Ok((self.nodes.get_node(id)?.rule == Rule::Select).then_some(id))
+    }
+
     /// Transform select AST to IR friendly one. At the end of transformation
     /// all `Select` nodes are replaced with their first children (always `Projection`).
     /// - When some node contains `Select` as a child, that child is replaced with
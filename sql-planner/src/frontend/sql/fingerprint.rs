@@ -0,0 +1,81 @@
+//! Fingerprint of a query shape.
+//!
+//! Statements which differ only in constant values, parameter placeholders,
+//! letter case of keywords and unquoted identifiers or whitespace get the
+//! same fingerprint. It is written to the SQL log as `query_id`, so that
+//! the log lines of the same query shape can be correlated.
+
+use pest::iterators::Pairs;
+use pest::Parser;
+use smol_str::{format_smolstr, SmolStr};
+use std::hash::Hasher;
+use twox_hash::XxHash3_64;
+
+use crate::errors::{Entity, SbroadError};
+use crate::frontend::sql::ast::{ParseTree, Rule};
+
+/// Compute the fingerprint of the query text.
+///
+/// # Errors
+/// - Failed to parse the query.
+pub fn query_fingerprint(query: &str) -> Result<SmolStr, SbroadError> {
+    let pairs = ParseTree::parse(Rule::Command, query)
+        .map_err(|e| SbroadError::ParsingError(Entity::Rule, format_smolstr!("{e}")))?;
+    Ok(fingerprint_pairs(query, pairs))
+}
+
+/// Compute the fingerprint of the query text from its already parsed pairs.
+pub(super) fn fingerprint_pairs(query: &str, pairs: Pairs<'_, Rule>) -> SmolStr {
+    // Byte ranges of the constants and parameters in the query text.
+    // Pairs are visited in the order of their positions.
+    let placeholders = pairs
+        .flatten()
+        .filter(|pair| matches!(pair.as_rule(), Rule::Literal | Rule::Parameter))
+        .map(|pair| (pair.as_span().start(), pair.as_span().end()));
+
+    let mut normalized = String::with_capacity(query.len());
+    let mut pos = 0;
+    for (start, end) in placeholders {
+        normalize_into(&mut normalized, &query[pos..start]);
+        normalized.push('?');
+        pos = end;
+    }
+    normalize_into(&mut normalized, &query[pos..]);
+
+    let mut hasher = XxHash3_64::default();
+    hasher.write(normalized.as_bytes());
+    format_smolstr!("{:016x}", hasher.finish())
+}
+
+/// Append the text to `out` with the letters outside of quotes lowercased
+/// and the whitespace outside of quotes dropped. A single space is kept
+/// between two words so that they are not glued together.
+fn normalize_into(out: &mut String, text: &str) {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let mut quote = None;
+    let mut pending_space = false;
+    for c in text.chars() {
+        if let Some(q) = quote {
+            out.push(c);
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        if c.is_whitespace() {
+            pending_space = true;
+            continue;
+        }
+        if pending_space && out.ends_with(is_word) && is_word(c) {
+            out.push(' ');
+        }
+        pending_space = false;
+        if c == '\'' || c == '"' {
+            quote = Some(c);
+        }
+        out.extend(c.to_lowercase());
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,37 @@
+use super::query_fingerprint;
+
+fn fingerprint(query: &str) -> String {
+    query_fingerprint(query).unwrap().to_string()
+}
+
+#[test]
+fn fingerprint_ignores_constants() {
+    assert_eq!(
+        fingerprint(r#"SELECT "a" FROM "t" WHERE "b" = 1 AND "c" = 'x'"#),
+        fingerprint(r#"SELECT "a" FROM "t" WHERE "b" = 42 AND "c" = 'another'"#),
+    );
+    assert_eq!(
+        fingerprint(r#"SELECT "a" FROM "t" WHERE "b" = 1"#),
+        fingerprint(
+            r#"select "a"  from "t"
+            where "b"=$1"#
+        ),
+    );
+    assert_eq!(
+        fingerprint(r#"INSERT INTO "t" VALUES (1, 2.5, true)"#),
+        fingerprint(r#"INSERT INTO "t" VALUES (?, ?, ?)"#),
+    );
+}
+
+#[test]
+fn fingerprint_of_different_shapes() {
+    let query = r#"SELECT "a" FROM "t" WHERE "b" = 1"#;
+    for other in [
+        r#"SELECT "a" FROM "t" WHERE "c" = 1"#,
+        r#"SELECT "a" FROM "t" WHERE "b" > 1"#,
+        r#"SELECT "a", "b" FROM "t" WHERE "b" = 1"#,
+        r#"SELECT "A" FROM "t" WHERE "b" = 1"#,
+    ] {
+        assert_ne!(fingerprint(query), fingerprint(other), "{other}");
+    }
+}
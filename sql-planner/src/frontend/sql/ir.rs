@@ -248,6 +248,7 @@ impl SubtreeCloner {
                 ref mut left,
                 ref mut right,
                 op: _,
+                collation: _,
             })
             | ExprOwned::Arithmetic(ArithmeticExpr {
                 ref mut left,
@@ -371,6 +372,7 @@ impl SubtreeCloner {
                 alias: _,
                 output: _,
                 child: _,
+                is_recursive: _,
             })
             | RelOwned::ScanSubQuery(ScanSubQuery {
                 alias: _,
@@ -381,11 +383,13 @@ impl SubtreeCloner {
                 left: _,
                 right: _,
                 output: _,
+                all: _,
             })
             | RelOwned::Intersect(Intersect {
                 left: _,
                 right: _,
                 output: _,
+                all: _,
             })
             | RelOwned::Union(Union {
                 left: _,
@@ -399,6 +403,8 @@ impl SubtreeCloner {
             })
             | RelOwned::Limit(Limit {
                 limit: _,
+                offset: _,
+                with_ties: _,
                 child: _,
                 output: _,
             }) => {}
@@ -446,7 +452,8 @@ impl SubtreeCloner {
                         MotionOpcode::PrimaryKey(_)
                         | MotionOpcode::RemoveDuplicates
                         | MotionOpcode::ReshardIfNeeded
-                        | MotionOpcode::SerializeAsEmptyTable(_) => {}
+                        | MotionOpcode::SerializeAsEmptyTable(_)
+                        | MotionOpcode::SortBy(..) => {}
                     }
                 }
             }
@@ -476,6 +483,7 @@ impl SubtreeCloner {
                     new_order_by_elements.push(OrderByElement {
                         entity: new_entity,
                         order_type: element.order_type.clone(),
+                        collation: element.collation,
                     });
                 }
                 *order_by_elements = new_order_by_elements;
@@ -650,6 +658,18 @@ impl SubtreeCloner {
         let mut helper = Self::new(subtree_capacity);
         helper.clone(plan, top_id, subtree_capacity)
     }
+
+    /// Clones the given subtree to the plan arena like [`SubtreeCloner::clone_subtree`],
+    /// but also returns the translation map of { old node id -> new node id }.
+    pub fn clone_subtree_with_map(
+        plan: &mut Plan,
+        top_id: NodeId,
+    ) -> Result<(NodeId, AHashMap<NodeId, NodeId>), SbroadError> {
+        let subtree_capacity = top_id.offset as usize;
+        let mut helper = Self::new(subtree_capacity);
+        let new_top_id = helper.clone(plan, top_id, subtree_capacity)?;
+        Ok((new_top_id, helper.old_new_map))
+    }
 }
 
 #[cfg(feature = "mock")]
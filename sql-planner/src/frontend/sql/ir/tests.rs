@@ -3,10 +3,12 @@ use crate::executor::engine::mock::RouterConfigurationMock;
 use crate::frontend::sql::ast::{AbstractSyntaxTree, ParseTree, Rule};
 use crate::frontend::sql::ParsingPairsMap;
 use crate::frontend::Ast;
+use crate::ir::node::expression::Expression;
 use crate::ir::node::relational::Relational;
+use crate::ir::node::Alias;
 use crate::ir::node::NodeId;
 use crate::ir::options::Options;
-use crate::ir::transformation::helpers::sql_to_optimized_ir;
+use crate::ir::transformation::helpers::{sql_to_ir_without_bind, sql_to_optimized_ir};
 use crate::ir::tree::traversal::PostOrder;
 use crate::ir::types::{DerivedType, UnrestrictedType as Type};
 use crate::ir::value::Value;
@@ -767,6 +769,73 @@ fn front_order_by_with_order_type_specification() {
     "#);
 }
 
+#[test]
+fn front_order_by_with_collation() {
+    let input = r#"select * from "test_space" order by "FIRST_NAME" collate "C" desc, "id""#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("id"::int -> "id", "sysFrom"::int -> "sysFrom", "FIRST_NAME"::string -> "FIRST_NAME", "sys_op"::int -> "sys_op")
+        order by ("FIRST_NAME"::string collate "binary" desc, "id"::int)
+            motion [policy: full, program: ReshardIfNeeded]
+                scan
+                    projection ("test_space"."id"::int -> "id", "test_space"."sysFrom"::int -> "sysFrom", "test_space"."FIRST_NAME"::string -> "FIRST_NAME", "test_space"."sys_op"::int -> "sys_op")
+                        scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn front_comparison_with_collation() {
+    let input = r#"SELECT "identification_number" FROM "hash_testing"
+        WHERE "product_code" COLLATE "unicode_ci" = 'a'"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("hash_testing"."identification_number"::int -> "identification_number")
+        selection "hash_testing"."product_code"::string collate "unicode_ci" = 'a'::string
+            scan "hash_testing"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn front_collation_errors() {
+    let metadata = &RouterConfigurationMock::new();
+
+    let input = r#"select * from "test_space" order by "FIRST_NAME" collate "foo""#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+    assert_eq!(err.to_string(), r#"collation "foo" not found"#);
+
+    let input =
+        r#"select * from "test_space" where "FIRST_NAME" collate "C" = 'a' collate "unicode""#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r#"invalid collation: mismatch between explicit collations "binary" and "unicode""#
+    );
+
+    let input = r#"select "FIRST_NAME" collate "C" from "test_space""#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unsupported collation: COLLATE is allowed only for ORDER BY keys and comparison operands"
+    );
+
+    let input = r#"select * from "test_space" order by 1 collate "C""#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unsupported collation: COLLATE is not allowed for ORDER BY positions"
+    );
+}
+
 #[test]
 fn front_order_by_with_indices() {
     let input = r#"select * from "test_space" order by 2, 1 desc"#;
@@ -819,6 +888,13 @@ fn front_order_by_with_indices_bigger_than_projection_output_length() {
         err.to_string()
             .contains("Ordering index (2) is bigger than child projection output length (1).")
     );
+
+    for position in [0, -1] {
+        let input = format!(r#"select "id" from "test_space" order by {position}"#);
+        let err = AbstractSyntaxTree::transform_into_plan(&input, &[], metadata).unwrap_err();
+        let expected = format!("ORDER BY position {position} is not in select list");
+        assert!(err.to_string().contains(&expected), "{input}: {err}");
+    }
 }
 
 #[test]
@@ -966,6 +1042,50 @@ fn front_case_nested() {
     "#);
 }
 
+#[test]
+fn front_case_common_type() {
+    let input = r#"select
+                            case
+                                when "id" = 1 then "id"
+                                when "id" = 2 then 2.5e0
+                                else null
+                            end as "case_result"
+                        from
+                        "test_space""#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection (case when "test_space"."id"::int = 1::int then "test_space"."id"::int::double when "test_space"."id"::int = 2::int then 2.5::double else NULL::unknown end -> "case_result")
+        scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+
+    let top = plan.get_top().unwrap();
+    let output = plan.get_relational_output(top).unwrap();
+    let column = plan.get_row_list(output).unwrap()[0];
+    let column_type = plan
+        .get_expression_node(column)
+        .unwrap()
+        .calculate_type(&plan)
+        .unwrap();
+    assert_eq!(column_type, DerivedType::new(Type::Double));
+}
+
+#[test]
+fn front_case_incompatible_types() {
+    let input = r#"select case when "id" = 1 then "id" else "FIRST_NAME" end from "test_space""#;
+
+    let metadata = &RouterConfigurationMock::new();
+    let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "CASE/THEN types int and text cannot be matched"
+    );
+}
+
 #[test]
 fn front_sql_subquery_column_duplicates() {
     let input = r#"SELECT "id" FROM "test_space" WHERE ("id", "id")
@@ -1275,18 +1395,71 @@ fn front_sql_exists_subquery_select_from_table_with_condition() {
     "#);
 }
 
+#[test]
+fn front_sql_exists_correlated_subquery() {
+    let metadata = &RouterConfigurationMock::new();
+
+    let input = r#"SELECT "id" FROM "test_space" WHERE EXISTS (SELECT 0 FROM "hash_testing" WHERE "identification_number" = "test_space"."id")"#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r#"column "test_space"."id" refers to an outer query: correlated subqueries are not implemented"#
+    );
+
+    let input = r#"SELECT "id" FROM "test_space" WHERE EXISTS (SELECT 0 FROM "hash_testing" WHERE "identification_number" = "id")"#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r#"column "id" refers to an outer query: correlated subqueries are not implemented"#
+    );
+}
+
 #[test]
 fn front_sql_groupby() {
     let input = r#"SELECT "identification_number", "product_code" FROM "hash_testing" group by "identification_number", "product_code""#;
 
     let plan = sql_to_optimized_ir(input, vec![]);
     insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
-    projection ("gr_expr_1"::int -> "identification_number", "gr_expr_2"::string -> "product_code")
-        group by ("gr_expr_1"::int, "gr_expr_2"::string) output: ("gr_expr_1"::int -> "gr_expr_1", "gr_expr_2"::string -> "gr_expr_2")
+    projection ("hash_testing"."identification_number"::int -> "identification_number", "hash_testing"."product_code"::string -> "product_code")
+        group by ("hash_testing"."identification_number"::int, "hash_testing"."product_code"::string) output: ("hash_testing"."identification_number"::int -> "identification_number", "hash_testing"."product_code"::string -> "product_code", "hash_testing"."product_units"::bool -> "product_units", "hash_testing"."sys_op"::int -> "sys_op", "hash_testing"."bucket_id"::int -> "bucket_id")
+            scan "hash_testing"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn front_sql_groupby_sharding_key() {
+    // Every group lives on a single bucket, so the aggregation is done
+    // on the storages without a motion.
+    let input = r#"SELECT "a", "b", count(*) FROM "t" GROUP BY "b", "a""#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("t"."a"::int -> "a", "t"."b"::int -> "b", count((*::int))::int -> "col_1")
+        group by ("t"."b"::int, "t"."a"::int) output: ("t"."a"::int -> "a", "t"."b"::int -> "b", "t"."c"::int -> "c", "t"."d"::int -> "d", "t"."bucket_id"::int -> "bucket_id")
+            scan "t"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn front_sql_groupby_sharding_key_subset() {
+    // Table "t" is sharded by ("a", "b"), so the rows of the same group
+    // can be spread across the cluster.
+    let input = r#"SELECT "a", count(*) FROM "t" GROUP BY "a""#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("gr_expr_1"::int -> "a", sum(("count_1"::int))::int -> "col_1")
+        group by ("gr_expr_1"::int) output: ("gr_expr_1"::int -> "gr_expr_1", "count_1"::int -> "count_1")
             motion [policy: full, program: ReshardIfNeeded]
-                projection ("hash_testing"."identification_number"::int -> "gr_expr_1", "hash_testing"."product_code"::string -> "gr_expr_2")
-                    group by ("hash_testing"."identification_number"::int, "hash_testing"."product_code"::string) output: ("hash_testing"."identification_number"::int -> "identification_number", "hash_testing"."product_code"::string -> "product_code", "hash_testing"."product_units"::bool -> "product_units", "hash_testing"."sys_op"::int -> "sys_op", "hash_testing"."bucket_id"::int -> "bucket_id")
-                        scan "hash_testing"
+                projection ("t"."a"::int -> "gr_expr_1", count((*::int))::int -> "count_1")
+                    group by ("t"."a"::int) output: ("t"."a"::int -> "a", "t"."b"::int -> "b", "t"."c"::int -> "c", "t"."d"::int -> "d", "t"."bucket_id"::int -> "bucket_id")
+                        scan "t"
     execution options:
         sql_vdbe_opcode_max = 45000
         sql_motion_row_max = 5000
@@ -1523,6 +1696,51 @@ fn front_sql_groupby_invalid() {
     assert_eq!(true, res.is_err());
 }
 
+#[test]
+fn front_sql_groupby_position_and_alias() {
+    // Grouping by a position or an alias of a computed column must produce
+    // the same plan as grouping by the expression itself.
+    let expected = sql_to_optimized_ir(
+        r#"select "a" + "b", count("c") from "t" group by "a" + "b""#,
+        vec![],
+    )
+    .as_explain()
+    .unwrap();
+    for input in [
+        r#"select "a" + "b", count("c") from "t" group by 1"#,
+        r#"select "a" + "b" as "col_1", count("c") from "t" group by "col_1""#,
+    ] {
+        let plan = sql_to_optimized_ir(input, vec![]);
+        assert_eq!(expected, plan.as_explain().unwrap(), "{input}");
+    }
+}
+
+#[test]
+fn front_sql_groupby_position_and_alias_invalid() {
+    let metadata = &RouterConfigurationMock::new();
+    for (input, expected) in [
+        (
+            r#"select count("c"), "b" from "t" group by 1"#,
+            "aggregate functions are not allowed in GROUP BY",
+        ),
+        (
+            r#"select count("c") + 1, "b" from "t" group by 1"#,
+            "aggregate functions are not allowed inside grouping expression",
+        ),
+        (
+            r#"select count("c") as "cnt", "b" from "t" group by "cnt""#,
+            "aggregate functions are not allowed in GROUP BY",
+        ),
+        (
+            r#"select "a" as "x", "b" as "x" from "t" group by "x""#,
+            "column name x is ambiguous",
+        ),
+    ] {
+        let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+        assert!(err.to_string().contains(expected), "{input}: {err}");
+    }
+}
+
 #[test]
 fn front_sql_distinct_invalid() {
     let input = r#"select "b", bucket_id(distinct cast("a" as string)) from "t" group by "b", bucket_id(distinct cast("a" as string))"#;
@@ -1564,19 +1782,16 @@ fn front_sql_distinct_asterisk() {
         join (select "id" from "test_space") on true"#;
     let plan = sql_to_optimized_ir(input, vec![]);
     insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
-    projection ("gr_expr_1"::int -> "id", "gr_expr_2"::int -> "id")
-        group by ("gr_expr_1"::int, "gr_expr_2"::int) output: ("gr_expr_1"::int -> "gr_expr_1", "gr_expr_2"::int -> "gr_expr_2")
-            motion [policy: full, program: ReshardIfNeeded]
-                projection ("unnamed_subquery"."id"::int -> "gr_expr_1", "unnamed_subquery_1"."id"::int -> "gr_expr_2")
-                    group by ("unnamed_subquery"."id"::int, "unnamed_subquery_1"."id"::int) output: ("unnamed_subquery"."id"::int -> "id", "unnamed_subquery_1"."id"::int -> "id")
-                        join on true::bool
-                            scan "unnamed_subquery"
-                                projection ("test_space_hist"."id"::int -> "id")
-                                    scan "test_space_hist"
-                            motion [policy: full, program: ReshardIfNeeded]
-                                scan "unnamed_subquery_1"
-                                    projection ("test_space"."id"::int -> "id")
-                                        scan "test_space"
+    projection ("unnamed_subquery"."id"::int -> "id", "unnamed_subquery_1"."id"::int -> "id")
+        group by ("unnamed_subquery"."id"::int, "unnamed_subquery_1"."id"::int) output: ("unnamed_subquery"."id"::int -> "id", "unnamed_subquery_1"."id"::int -> "id")
+            join on true::bool
+                scan "unnamed_subquery"
+                    projection ("test_space_hist"."id"::int -> "id")
+                        scan "test_space_hist"
+                motion [policy: full, program: ReshardIfNeeded]
+                    scan "unnamed_subquery_1"
+                        projection ("test_space"."id"::int -> "id")
+                            scan "test_space"
     execution options:
         sql_vdbe_opcode_max = 45000
         sql_motion_row_max = 5000
@@ -1691,6 +1906,25 @@ fn front_sql_group_concat_aggregate2() {
     "#);
 }
 
+#[test]
+fn front_sql_bool_or_aggregate() {
+    let input = r#"SELECT "product_code", bool_or("product_units") FROM "hash_testing" GROUP BY "product_code""#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("gr_expr_1"::string -> "product_code", bool_or(("bool_or_1"::bool))::bool -> "col_1")
+        group by ("gr_expr_1"::string) output: ("gr_expr_1"::string -> "gr_expr_1", "bool_or_1"::bool -> "bool_or_1")
+            motion [policy: full, program: ReshardIfNeeded]
+                projection ("hash_testing"."product_code"::string -> "gr_expr_1", bool_or(("hash_testing"."product_units"::bool::bool))::bool -> "bool_or_1")
+                    group by ("hash_testing"."product_code"::string) output: ("hash_testing"."identification_number"::int -> "identification_number", "hash_testing"."product_code"::string -> "product_code", "hash_testing"."product_units"::bool -> "product_units", "hash_testing"."sys_op"::int -> "sys_op", "hash_testing"."bucket_id"::int -> "bucket_id")
+                        scan "hash_testing"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
 #[test]
 fn front_sql_string_agg_alias_to_group_concat() {
     // Test 1
@@ -1710,18 +1944,160 @@ fn front_sql_string_agg_alias_to_group_concat() {
     let input = r#"SELECT "id", string_agg("FIRST_NAME", ',') FROM "test_space" GROUP BY "id""#;
     let plan = sql_to_optimized_ir(input, vec![]);
     insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
-    projection ("gr_expr_1"::int -> "id", group_concat(("group_concat_1"::string, ','::string))::string -> "col_1")
-        group by ("gr_expr_1"::int) output: ("gr_expr_1"::int -> "gr_expr_1", "group_concat_1"::string -> "group_concat_1")
-            motion [policy: full, program: ReshardIfNeeded]
-                projection ("test_space"."id"::int -> "gr_expr_1", group_concat(("test_space"."FIRST_NAME"::string::string, ','::string))::string -> "group_concat_1")
-                    group by ("test_space"."id"::int) output: ("test_space"."id"::int -> "id", "test_space"."sysFrom"::int -> "sysFrom", "test_space"."FIRST_NAME"::string -> "FIRST_NAME", "test_space"."sys_op"::int -> "sys_op", "test_space"."bucket_id"::int -> "bucket_id")
-                        scan "test_space"
+    projection ("test_space"."id"::int -> "id", group_concat(("test_space"."FIRST_NAME"::string::string, ','::string))::string -> "col_1")
+        group by ("test_space"."id"::int) output: ("test_space"."id"::int -> "id", "test_space"."sysFrom"::int -> "sysFrom", "test_space"."FIRST_NAME"::string -> "FIRST_NAME", "test_space"."sys_op"::int -> "sys_op", "test_space"."bucket_id"::int -> "bucket_id")
+            scan "test_space"
     execution options:
         sql_vdbe_opcode_max = 45000
         sql_motion_row_max = 5000
     "#);
 }
 
+#[test]
+fn front_sql_string_agg_order_by() {
+    let input = r#"SELECT "id", string_agg("FIRST_NAME", ',' ORDER BY "FIRST_NAME" DESC) FROM "test_space" GROUP BY "id""#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("test_space"."id"::int -> "id", group_concat(("test_space"."FIRST_NAME"::string::string, ','::string) order by "test_space"."FIRST_NAME"::string::string desc)::string -> "col_1")
+        group by ("test_space"."id"::int) output: ("test_space"."id"::int -> "id", "test_space"."sysFrom"::int -> "sysFrom", "test_space"."FIRST_NAME"::string -> "FIRST_NAME", "test_space"."sys_op"::int -> "sys_op", "test_space"."bucket_id"::int -> "bucket_id")
+            motion [policy: full, program: SortBy(2 desc)]
+                scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn front_sql_string_agg_order_by_nulls() {
+    let input = r#"SELECT "id", string_agg("FIRST_NAME", ',' ORDER BY "FIRST_NAME" DESC NULLS FIRST) FROM "test_space" GROUP BY "id""#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("test_space"."id"::int -> "id", group_concat(("test_space"."FIRST_NAME"::string::string, ','::string) order by "test_space"."FIRST_NAME"::string::string desc nulls first)::string -> "col_1")
+        group by ("test_space"."id"::int) output: ("test_space"."id"::int -> "id", "test_space"."sysFrom"::int -> "sysFrom", "test_space"."FIRST_NAME"::string -> "FIRST_NAME", "test_space"."sys_op"::int -> "sys_op", "test_space"."bucket_id"::int -> "bucket_id")
+            motion [policy: full, program: SortBy(2 desc nulls first)]
+                scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn front_sql_string_agg_order_by_collate() {
+    let input = r#"SELECT "id", string_agg("FIRST_NAME", ',' ORDER BY "FIRST_NAME" COLLATE "unicode_ci") FROM "test_space" GROUP BY "id""#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("test_space"."id"::int -> "id", group_concat(("test_space"."FIRST_NAME"::string::string, ','::string) order by "test_space"."FIRST_NAME"::string::string collate "unicode_ci" asc)::string -> "col_1")
+        group by ("test_space"."id"::int) output: ("test_space"."id"::int -> "id", "test_space"."sysFrom"::int -> "sysFrom", "test_space"."FIRST_NAME"::string -> "FIRST_NAME", "test_space"."sys_op"::int -> "sys_op", "test_space"."bucket_id"::int -> "bucket_id")
+            motion [policy: full, program: SortBy(2 collate "unicode_ci" asc)]
+                scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn front_sql_string_agg_order_by_other_key() {
+    let input = r#"SELECT string_agg("FIRST_NAME", ',' ORDER BY "id") FROM "test_space""#;
+
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, &[], metadata);
+    let err = plan.unwrap_err();
+
+    assert_eq!(
+        true,
+        err.to_string().contains(
+            "ORDER BY inside GROUP_CONCAT aggregate function must use the aggregated expression"
+        )
+    );
+}
+
+#[test]
+fn front_sql_percentile_within_group() {
+    let input = r#"SELECT "FIRST_NAME", percentile_cont(0.5) WITHIN GROUP (ORDER BY "id") FROM "test_space" GROUP BY "FIRST_NAME""#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    let explain = plan.as_explain().unwrap();
+    assert!(explain.contains(r#""percentile_cont"((group_concat("#));
+    assert!(explain.contains("motion [policy: full"));
+
+    let input =
+        r#"SELECT percentile_disc(0.9) WITHIN GROUP (ORDER BY "id" DESC) FROM "test_space""#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    let explain = plan.as_explain().unwrap();
+    assert!(explain.contains(r#""percentile_disc"((group_concat("#));
+    assert!(explain.contains("true::bool"));
+}
+
+#[test]
+fn front_sql_percentile_within_group_errors() {
+    let metadata = &RouterConfigurationMock::new();
+
+    let input = r#"SELECT percentile_cont(1.5) WITHIN GROUP (ORDER BY "id") FROM "test_space""#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid query: percentile value 1.5 is not between 0 and 1"
+    );
+
+    let input =
+        r#"SELECT percentile_cont(0.5) WITHIN GROUP (ORDER BY "FIRST_NAME") FROM "test_space""#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid query: ORDER BY inside WITHIN GROUP of \"percentile_cont\" must be numeric, got string"
+    );
+
+    let input = r#"SELECT max(0.5) WITHIN GROUP (ORDER BY "id") FROM "test_space""#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid query: WITHIN GROUP is not supported for function: \"max\""
+    );
+}
+
+#[test]
+fn front_sql_tablesample() {
+    let input = r#"SELECT "id" FROM "test_space" TABLESAMPLE BERNOULLI(10) REPEATABLE(42)"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+    let explain = plan.as_explain().unwrap();
+    assert!(
+        explain.contains(r#"scan "test_space" (tablesample bernoulli(10) repeatable(42))"#),
+        "{explain}"
+    );
+
+    let input = r#"SELECT "id" FROM "test_space" AS t TABLESAMPLE BERNOULLI(0.5)"#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    let explain = plan.as_explain().unwrap();
+    assert!(
+        explain.contains(r#"scan "test_space" -> "t" (tablesample bernoulli(0.5))"#),
+        "{explain}"
+    );
+}
+
+#[test]
+fn front_sql_tablesample_errors() {
+    let metadata = &RouterConfigurationMock::new();
+
+    for percent in ["0", "150"] {
+        let input = format!(r#"SELECT "id" FROM "test_space" TABLESAMPLE BERNOULLI({percent})"#);
+        let err = AbstractSyntaxTree::transform_into_plan(&input, &[], metadata).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("invalid query: TABLESAMPLE percentage {percent} is not in range (0, 100]")
+        );
+    }
+
+    let input = r#"SELECT * FROM (SELECT "id" FROM "test_space") TABLESAMPLE BERNOULLI(10)"#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid query: TABLESAMPLE clause is only supported for tables"
+    );
+}
+
 #[test]
 fn front_sql_count_asterisk1() {
     let input = r#"SELECT count(*), count(*) FROM "t""#;
@@ -1813,6 +2189,47 @@ fn front_sql_aggregates_with_distinct1() {
     "#);
 }
 
+#[test]
+fn front_sql_count_distinct_non_sharding_column() {
+    // "c" is not a part of the sharding key of "t", so the values
+    // must be gathered on the router before counting.
+    let input = r#"SELECT count(distinct "c") FROM "t""#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection (count(distinct ("gr_expr_1"::int))::int -> "col_1")
+        motion [policy: full, program: ReshardIfNeeded]
+            projection ("t"."c"::int::int -> "gr_expr_1")
+                group by ("t"."c"::int::int) output: ("t"."a"::int -> "a", "t"."b"::int -> "b", "t"."c"::int -> "c", "t"."d"::int -> "d", "t"."bucket_id"::int -> "bucket_id")
+                    scan "t"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn front_sql_sum_avg_distinct_with_count() {
+    // Distinct and non-distinct aggregates of the same column: the local
+    // stage groups by "c" to deduplicate it for sum and avg, while count
+    // is calculated per group and summed up on the router.
+    let input = r#"SELECT sum(distinct "c"), avg(distinct "c"), count("c") FROM "t""#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection (sum(distinct ("gr_expr_1"::decimal))::decimal -> "col_1", avg(distinct ("gr_expr_1"::decimal::double))::decimal -> "col_2", sum(("count_1"::int))::int -> "col_3")
+        motion [policy: full, program: ReshardIfNeeded]
+            projection ("t"."c"::int::int -> "gr_expr_1", count(("t"."c"::int::int))::int -> "count_1")
+                group by ("t"."c"::int::int) output: ("t"."a"::int -> "a", "t"."b"::int -> "b", "t"."c"::int -> "c", "t"."d"::int -> "d", "t"."bucket_id"::int -> "bucket_id")
+                    scan "t"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
 #[test]
 fn front_sql_aggregates_with_distinct2() {
     let input = r#"SELECT "b", sum(distinct "a" + "b" + 3) FROM "t"
@@ -1911,6 +2328,98 @@ fn front_sql_option_with_param() {
     "#);
 }
 
+#[test]
+fn front_sql_option_partial_results() {
+    let input = r#"select * from "t" option(sql_partial_results = ?)"#;
+
+    let plan = sql_to_optimized_ir(input, vec![Value::Boolean(true)]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("t"."a"::int -> "a", "t"."b"::int -> "b", "t"."c"::int -> "c", "t"."d"::int -> "d")
+        scan "t"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+        sql_partial_results = true
+    "#);
+
+    let input = r#"select * from "t" option(sql_partial_results = 1)"#;
+    let metadata = &RouterConfigurationMock::new();
+    let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+    assert!(err.to_string().contains("rule parsing error"));
+}
+
+#[test]
+fn front_sql_option_wait_index() {
+    let input = r#"select * from "global_t" option(wait_index = ?, wait_index_timeout = 100)"#;
+
+    let plan = sql_to_optimized_ir(input, vec![Value::Integer(42)]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("global_t"."a"::int -> "a", "global_t"."b"::int -> "b")
+        scan "global_t"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+        wait_index = 42
+        wait_index_timeout = 100
+    "#);
+}
+
+#[test]
+fn front_sql_option_wait_index_sharded() {
+    let input = r#"select * from "t" option(wait_index = 42)"#;
+    let err = sql_to_ir_without_bind(input, &[])
+        .check_raw_options()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid OptionSpec: wait_index option is supported only for reads of global tables"
+    );
+}
+
+#[test]
+fn front_sql_option_result_row_max() {
+    let input = r#"select * from "t" option(sql_result_row_max = ?)"#;
+
+    let plan = sql_to_optimized_ir(input, vec![Value::Integer(10)]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("t"."a"::int -> "a", "t"."b"::int -> "b", "t"."c"::int -> "c", "t"."d"::int -> "d")
+        scan "t"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+        sql_result_row_max = 10
+    "#);
+
+    let input = r#"insert into "t" values (1, 2, 3, 4) option(sql_result_row_max = 10)"#;
+    let err = sql_to_ir_without_bind(input, &[])
+        .check_raw_options()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid OptionSpec: sql_result_row_max option is supported only for DQL queries"
+    );
+}
+
+#[test]
+fn front_sql_option_dispatch_concurrency_max() {
+    let input = r#"select * from "t" option(sql_dispatch_concurrency_max = ?)"#;
+
+    let plan = sql_to_optimized_ir(input, vec![Value::Integer(4)]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("t"."a"::int -> "a", "t"."b"::int -> "b", "t"."c"::int -> "c", "t"."d"::int -> "d")
+        scan "t"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+        sql_dispatch_concurrency_max = 4
+    "#);
+
+    // The limit applies to DML dispatch as well.
+    let input = r#"delete from "t" option(sql_dispatch_concurrency_max = 2)"#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    assert_eq!(plan.effective_options.sql_dispatch_concurrency_max, 2);
+}
+
 #[test]
 fn front_sql_pg_style_params1() {
     let input = r#"select $1, $2, $1 from "t""#;
@@ -2203,7 +2712,7 @@ fn front_sql_union_single_left() {
 
     insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
     union all
-        projection ("t"."a"::int -> "a")
+        projection ("t"."a"::int::decimal -> "a")
             scan "t"
         motion [policy: segment([ref("col_1")]), program: ReshardIfNeeded]
             projection (sum(("sum_1"::decimal))::decimal -> "col_1")
@@ -2233,7 +2742,7 @@ fn front_sql_union_single_right() {
                 motion [policy: full, program: ReshardIfNeeded]
                     projection (sum(("t"."a"::int::int))::decimal -> "sum_1")
                         scan "t"
-        projection ("t"."a"::int -> "a")
+        projection ("t"."a"::int::decimal -> "a")
             scan "t"
     execution options:
         sql_vdbe_opcode_max = 45000
@@ -2296,7 +2805,7 @@ fn front_sql_except_single_right() {
     let plan = sql_to_optimized_ir(input, vec![]);
     insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
     except
-        projection ("t"."a"::int -> "a", "t"."b"::int -> "b")
+        projection ("t"."a"::int::decimal -> "a", "t"."b"::int -> "b")
             scan "t"
         motion [policy: segment([ref("col_1"), ref("col_2")]), program: ReshardIfNeeded]
             projection (sum(("sum_1"::decimal))::decimal -> "col_1", sum(("count_2"::int))::int -> "col_2")
@@ -2317,7 +2826,7 @@ fn front_sql_except_single_right() {
 
     insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
     except
-        projection ("t"."b"::int -> "b", "t"."a"::int -> "a")
+        projection ("t"."b"::int::decimal -> "b", "t"."a"::int -> "a")
             scan "t"
         motion [policy: segment([ref("col_2"), ref("col_1")]), program: ReshardIfNeeded]
             projection (sum(("sum_1"::decimal))::decimal -> "col_1", sum(("count_2"::int))::int -> "col_2")
@@ -2345,7 +2854,7 @@ fn front_sql_except_single_left() {
                 motion [policy: full, program: ReshardIfNeeded]
                     projection (sum(("t"."a"::int::int))::decimal -> "sum_1", count(("t"."b"::int::int))::int -> "count_2")
                         scan "t"
-        projection ("t"."a"::int -> "a", "t"."b"::int -> "b")
+        projection ("t"."a"::int::decimal -> "a", "t"."b"::int -> "b")
             scan "t"
     execution options:
         sql_vdbe_opcode_max = 45000
@@ -2364,7 +2873,7 @@ fn front_sql_except_single_both() {
     insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
     except
         motion [policy: segment([ref("col_1")]), program: ReshardIfNeeded]
-            projection (sum(("sum_1"::decimal))::decimal -> "col_1", sum(("count_2"::int))::int -> "col_2")
+            projection (sum(("sum_1"::decimal))::decimal -> "col_1", sum(("count_2"::int))::int::decimal -> "col_2")
                 motion [policy: full, program: ReshardIfNeeded]
                     projection (sum(("t"."a"::int::int))::decimal -> "sum_1", count(("t"."b"::int::int))::int -> "count_2")
                         scan "t"
@@ -2471,19 +2980,17 @@ fn front_sql_groupby_with_aggregates() {
     projection ("t1"."a"::int -> "a", "t1"."b"::int -> "b", "t1"."c"::decimal -> "c", "t2"."g"::int -> "g", "t2"."e"::int -> "e", "t2"."f"::decimal -> "f")
         join on ROW("t1"."a"::int, "t1"."b"::int) = ROW("t2"."e"::int, "t2"."g"::int)
             scan "t1"
-                projection ("gr_expr_1"::int -> "a", "gr_expr_2"::int -> "b", sum(("sum_1"::decimal))::decimal -> "c")
-                    group by ("gr_expr_1"::int, "gr_expr_2"::int) output: ("gr_expr_1"::int -> "gr_expr_1", "gr_expr_2"::int -> "gr_expr_2", "sum_1"::decimal -> "sum_1")
-                        motion [policy: full, program: ReshardIfNeeded]
-                            projection ("t"."a"::int -> "gr_expr_1", "t"."b"::int -> "gr_expr_2", sum(("t"."c"::int::int))::decimal -> "sum_1")
-                                group by ("t"."a"::int, "t"."b"::int) output: ("t"."a"::int -> "a", "t"."b"::int -> "b", "t"."c"::int -> "c", "t"."d"::int -> "d", "t"."bucket_id"::int -> "bucket_id")
-                                    scan "t"
-            scan "t2"
-                projection ("gr_expr_1"::int -> "g", "gr_expr_2"::int -> "e", sum(("sum_1"::decimal))::decimal -> "f")
-                    group by ("gr_expr_1"::int, "gr_expr_2"::int) output: ("gr_expr_1"::int -> "gr_expr_1", "gr_expr_2"::int -> "gr_expr_2", "sum_1"::decimal -> "sum_1")
-                        motion [policy: full, program: ReshardIfNeeded]
-                            projection ("t2"."g"::int -> "gr_expr_1", "t2"."e"::int -> "gr_expr_2", sum(("t2"."f"::int::int))::decimal -> "sum_1")
-                                group by ("t2"."g"::int, "t2"."e"::int) output: ("t2"."e"::int -> "e", "t2"."f"::int -> "f", "t2"."g"::int -> "g", "t2"."h"::int -> "h", "t2"."bucket_id"::int -> "bucket_id")
-                                    scan "t2"
+                projection ("t"."a"::int -> "a", "t"."b"::int -> "b", sum(("t"."c"::int::int))::decimal -> "c")
+                    group by ("t"."a"::int, "t"."b"::int) output: ("t"."a"::int -> "a", "t"."b"::int -> "b", "t"."c"::int -> "c", "t"."d"::int -> "d", "t"."bucket_id"::int -> "bucket_id")
+                        scan "t"
+            motion [policy: segment([ref("e"), ref("g")]), program: ReshardIfNeeded]
+                scan "t2"
+                    projection ("gr_expr_1"::int -> "g", "gr_expr_2"::int -> "e", sum(("sum_1"::decimal))::decimal -> "f")
+                        group by ("gr_expr_1"::int, "gr_expr_2"::int) output: ("gr_expr_1"::int -> "gr_expr_1", "gr_expr_2"::int -> "gr_expr_2", "sum_1"::decimal -> "sum_1")
+                            motion [policy: full, program: ReshardIfNeeded]
+                                projection ("t2"."g"::int -> "gr_expr_1", "t2"."e"::int -> "gr_expr_2", sum(("t2"."f"::int::int))::decimal -> "sum_1")
+                                    group by ("t2"."g"::int, "t2"."e"::int) output: ("t2"."e"::int -> "e", "t2"."f"::int -> "f", "t2"."g"::int -> "g", "t2"."h"::int -> "h", "t2"."bucket_id"::int -> "bucket_id")
+                                        scan "t2"
     execution options:
         sql_vdbe_opcode_max = 45000
         sql_motion_row_max = 5000
@@ -2732,7 +3239,7 @@ fn front_sql_having_with_sq() {
                         group by ("test_space"."sysFrom"::int, "test_space"."id"::int::int) output: ("test_space"."id"::int -> "id", "test_space"."sysFrom"::int -> "sysFrom", "test_space"."FIRST_NAME"::string -> "FIRST_NAME", "test_space"."sys_op"::int -> "sys_op", "test_space"."bucket_id"::int -> "bucket_id")
                             scan "test_space"
     subquery $0:
-    motion [policy: full, program: ReshardIfNeeded]
+    motion [policy: single, program: ReshardIfNeeded]
                 scan
                     projection ("test_space"."sysFrom"::int -> "sysFrom")
                         selection "test_space"."sysFrom"::int = 2::int
@@ -2922,12 +3429,9 @@ fn front_sql_select_distinct_asterisk() {
     let plan = sql_to_optimized_ir(input, vec![]);
 
     insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
-    projection ("gr_expr_1"::int -> "a", "gr_expr_2"::int -> "b", "gr_expr_3"::int -> "c", "gr_expr_4"::int -> "d")
-        group by ("gr_expr_1"::int, "gr_expr_2"::int, "gr_expr_3"::int, "gr_expr_4"::int) output: ("gr_expr_1"::int -> "gr_expr_1", "gr_expr_2"::int -> "gr_expr_2", "gr_expr_3"::int -> "gr_expr_3", "gr_expr_4"::int -> "gr_expr_4")
-            motion [policy: full, program: ReshardIfNeeded]
-                projection ("t"."a"::int -> "gr_expr_1", "t"."b"::int -> "gr_expr_2", "t"."c"::int -> "gr_expr_3", "t"."d"::int -> "gr_expr_4")
-                    group by ("t"."a"::int, "t"."b"::int, "t"."c"::int, "t"."d"::int) output: ("t"."a"::int -> "a", "t"."b"::int -> "b", "t"."c"::int -> "c", "t"."d"::int -> "d", "t"."bucket_id"::int -> "bucket_id")
-                        scan "t"
+    projection ("t"."a"::int -> "a", "t"."b"::int -> "b", "t"."c"::int -> "c", "t"."d"::int -> "d")
+        group by ("t"."a"::int, "t"."b"::int, "t"."c"::int, "t"."d"::int) output: ("t"."a"::int -> "a", "t"."b"::int -> "b", "t"."c"::int -> "c", "t"."d"::int -> "d", "t"."bucket_id"::int -> "bucket_id")
+            scan "t"
     execution options:
         sql_vdbe_opcode_max = 45000
         sql_motion_row_max = 5000
@@ -3582,6 +4086,51 @@ fn front_sql_arithmetic_with_parentheses() {
     "#);
 }
 
+#[test]
+fn front_sql_nested_parentheses() {
+    // Parentheses only affect the shape of the expression tree,
+    // redundant ones leave no trace in the plan.
+    let input = r#"SELECT ((("COLUMN_1"))), ((((1 + 2)))) * 3 FROM (values (1))"#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    let top = plan.get_top().unwrap();
+    let output = plan.get_relational_output(top).unwrap();
+    let columns = plan.get_row_list(output).unwrap();
+    let column = plan.get_expression_node(columns[0]).unwrap();
+    let Expression::Alias(Alias { child, .. }) = column else {
+        panic!("expected an alias, got {column:?}");
+    };
+    let child = plan.get_expression_node(*child).unwrap();
+    assert!(matches!(child, Expression::Reference(_)), "{child:?}");
+
+    // Meaningful parentheses are restored from the tree shape.
+    let explain = plan.as_explain().unwrap();
+    assert!(explain.contains("(1::int + 2::int) * 3::int -> \"col_1\""));
+}
+
+#[test]
+fn front_sql_table_shorthand() {
+    let explain = |sql: &str| sql_to_optimized_ir(sql, vec![]).as_explain().unwrap();
+
+    assert_eq!(explain(r#"TABLE "t""#), explain(r#"SELECT * FROM "t""#));
+    assert_eq!(
+        explain(r#"table "t1" union table "t1" order by 1"#),
+        explain(r#"SELECT * FROM "t1" UNION SELECT * FROM "t1" ORDER BY 1"#)
+    );
+    assert_eq!(
+        explain(r#"SELECT * FROM (TABLE "t") WHERE "a" = 1"#),
+        explain(r#"SELECT * FROM (SELECT * FROM "t") WHERE "a" = 1"#)
+    );
+
+    let metadata = &RouterConfigurationMock::new();
+    let err = AbstractSyntaxTree::transform_into_plan(r#"TABLE "nonexistent""#, &[], metadata)
+        .unwrap_err();
+    let select_err =
+        AbstractSyntaxTree::transform_into_plan(r#"SELECT * FROM "nonexistent""#, &[], metadata)
+            .unwrap_err();
+    assert_eq!(err.to_string(), select_err.to_string());
+}
+
 #[test]
 fn front_sql_to_date() {
     let input = r#"SELECT to_date("COLUMN_1", '%Y/%d/%m') FROM (values ('2010/10/10'))"#;
@@ -3705,6 +4254,126 @@ fn front_mock_set_param_transaction() {
     }
 }
 
+#[test]
+fn front_mock_set_transaction_isolation_level() {
+    use crate::ir::ddl::{SetParamScopeType, TransactionIsolationLevel as Level};
+    use crate::ir::node::ddl::Ddl;
+    use crate::ir::node::{Node, SetTransaction};
+
+    let queries_to_check = vec![
+        (
+            r#"set transaction isolation level serializable"#,
+            SetParamScopeType::Local,
+            Some(Level::Serializable),
+        ),
+        (
+            r#"SET TRANSACTION ISOLATION LEVEL READ COMMITTED"#,
+            SetParamScopeType::Local,
+            Some(Level::ReadCommitted),
+        ),
+        (
+            r#"set transaction isolation level read uncommitted"#,
+            SetParamScopeType::Local,
+            Some(Level::ReadUncommitted),
+        ),
+        (
+            r#"set session characteristics as transaction isolation level repeatable read"#,
+            SetParamScopeType::Session,
+            Some(Level::RepeatableRead),
+        ),
+        (
+            r#"set transaction read only"#,
+            SetParamScopeType::Local,
+            None,
+        ),
+    ];
+
+    let metadata = &RouterConfigurationMock::new();
+    for (query, expected_scope, expected_level) in queries_to_check {
+        let plan = AbstractSyntaxTree::transform_into_plan(query, &[], metadata).unwrap();
+        let top_id = plan.get_top().unwrap();
+        let Node::Ddl(Ddl::SetTransaction(SetTransaction {
+            scope_type,
+            isolation_level,
+            ..
+        })) = plan.get_node(top_id).unwrap()
+        else {
+            panic!("Expected SetTransaction node for {query}");
+        };
+        assert_eq!(scope_type, &expected_scope, "{query}");
+        assert_eq!(isolation_level, &expected_level, "{query}");
+    }
+}
+
+#[test]
+fn front_mock_set_application_name() {
+    use crate::ir::ddl::SetParamValue;
+    use crate::ir::node::ddl::Ddl;
+    use crate::ir::node::{Node, SetParam};
+
+    let queries_to_check = vec![
+        (r#"set application_name = 'my app'"#, "my app"),
+        (r#"SET APPLICATION_NAME TO 'it''s'"#, "it's"),
+        (r#"set application_name to etl"#, "etl"),
+        (r#"set application_name = default"#, ""),
+    ];
+
+    let metadata = &RouterConfigurationMock::new();
+    for (query, expected) in queries_to_check {
+        let plan = AbstractSyntaxTree::transform_into_plan(query, &[], metadata).unwrap();
+        let top_id = plan.get_top().unwrap();
+        let Node::Ddl(Ddl::SetParam(SetParam { param_value, .. })) = plan.get_node(top_id).unwrap()
+        else {
+            panic!("Expected SetParam node for {query}");
+        };
+        assert_eq!(
+            param_value,
+            &SetParamValue::ApplicationName {
+                value: expected.into()
+            },
+            "{query}"
+        );
+    }
+}
+
+#[test]
+fn front_mock_set_plan_cache() {
+    use crate::ir::ddl::SetParamValue;
+    use crate::ir::node::ddl::Ddl;
+    use crate::ir::node::{Node, SetParam};
+
+    let queries_to_check = vec![
+        (r#"set plan_cache = off"#, false),
+        (r#"SET PLAN_CACHE TO false"#, false),
+        (r#"set plan_cache = 'OFF'"#, false),
+        (r#"set plan_cache = on"#, true),
+        (r#"set plan_cache to true"#, true),
+        (r#"set plan_cache = default"#, true),
+    ];
+
+    let metadata = &RouterConfigurationMock::new();
+    for (query, expected) in queries_to_check {
+        let plan = AbstractSyntaxTree::transform_into_plan(query, &[], metadata).unwrap();
+        let top_id = plan.get_top().unwrap();
+        let Node::Ddl(Ddl::SetParam(SetParam { param_value, .. })) = plan.get_node(top_id).unwrap()
+        else {
+            panic!("Expected SetParam node for {query}");
+        };
+        assert_eq!(
+            param_value,
+            &SetParamValue::PlanCache { enabled: expected },
+            "{query}"
+        );
+    }
+
+    let err =
+        AbstractSyntaxTree::transform_into_plan("set plan_cache = 1", &[], metadata).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r#"invalid value: parameter "plan_cache" requires a boolean value, got: 1"#
+    );
+}
+
 #[test]
 fn front_mock_partition_by() {
     let metadata = &RouterConfigurationMock::new();
@@ -3911,7 +4580,7 @@ fn front_select_without_scan_2() {
     insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
     projection (ROW($1) -> "col_1", ROW($0) -> "col_2")
     subquery $0:
-    motion [policy: full, program: ReshardIfNeeded]
+    motion [policy: single, program: ReshardIfNeeded]
             scan
                 projection (sum(("count_1"::int))::int -> "col_1")
                     motion [policy: full, program: ReshardIfNeeded]
@@ -4018,6 +4687,30 @@ fn front_different_values_row_len() {
     assert_eq!("VALUES lists must all be the same length", err.to_string());
 }
 
+#[test]
+fn front_values_output_columns() {
+    let plan = sql_to_optimized_ir(r#"values (1, 'a', null), (2.5, null, 'b')"#, vec![]);
+    let output_id = plan.get_relational_output(plan.get_top().unwrap()).unwrap();
+    let columns: Vec<_> = plan
+        .get_row_list(output_id)
+        .unwrap()
+        .iter()
+        .map(|id| {
+            let column = plan.get_expression_node(*id).unwrap();
+            let name = column.get_alias_name().unwrap().to_string();
+            (name, column.calculate_type(&plan).unwrap())
+        })
+        .collect();
+    assert_eq!(
+        columns,
+        [
+            ("COLUMN_1".to_string(), DerivedType::new(Type::Decimal)),
+            ("COLUMN_2".to_string(), DerivedType::new(Type::String)),
+            ("COLUMN_3".to_string(), DerivedType::new(Type::String)),
+        ]
+    );
+}
+
 #[test]
 fn front_sql_whitespaces_are_not_ignored() {
     // Deletion of any WHITESPACE in those query will transform
@@ -4161,12 +4854,14 @@ mod coalesce;
 mod cte;
 mod ddl;
 mod funcs;
+mod generate_series;
 mod global;
 mod index;
 mod insert;
 mod join;
 mod like;
 mod limit;
+mod locking;
 mod params;
 mod single;
 mod subtree_cloner;
@@ -13,6 +13,7 @@ fn anonymous_blocks_parsing() {
         " DO $$ BEGIN RETURN QUERY SELECT 1  ; UPDATE t2 SET e = f ; END $$ ",
         " DO $$ BEGIN RETURN QUERY VALUES (1 ) ; UPDATE t2 SET e = f  ; END $$ ",
         "DO $$ BEGIN RETURN QUERY VALUES (1); RETURN QUERY SELECT b FROM t1; END$$",
+        "DO $$ BEGIN RETURN QUERY SELECT * FROM t1 GROUP BY a, b; END$$",
         "DO $$ BEGIN RETURN QUERY SELECT (VALUES (1)); RETURN QUERY SELECT b FROM t1 AS t; END$$",
         "DO $$ BEGIN RETURN QUERY VALUES (1); RETURN QUERY SELECT b FROM t1 AS t WHERE a::int = b; END$$",
         "DO $$ BEGIN RETURN QUERY VALUES (1); RETURN QUERY SELECT b FROM t1 WHERE a::int = b; UPDATE t2 SET e = f; END$$",
@@ -198,7 +199,7 @@ fn block_query_has_motions_errors() {
             "SELECT",
         ),
         (
-            "DO $$ BEGIN RETURN QUERY SELECT * FROM t1 GROUP BY a, b; END $$",
+            "DO $$ BEGIN RETURN QUERY SELECT b FROM t1 GROUP BY b; END $$",
             "SELECT",
         ),
         (
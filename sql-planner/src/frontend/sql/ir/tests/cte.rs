@@ -2,7 +2,10 @@ use crate::errors::{Entity, SbroadError};
 use crate::executor::engine::mock::RouterConfigurationMock;
 use crate::frontend::sql::ast::AbstractSyntaxTree;
 use crate::frontend::Ast;
+use crate::ir::node::relational::Relational;
+use crate::ir::node::ScanCte;
 use crate::ir::transformation::helpers::sql_to_optimized_ir;
+use crate::ir::transformation::redistribution::MotionPolicy;
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -421,3 +424,120 @@ fn cte_with_left_join() {
         sql_motion_row_max = 5000
     "#);
 }
+
+#[test]
+fn recursive_keyword_without_self_reference() {
+    let sql =
+        r#"WITH RECURSIVE cte (a) AS (SELECT "FIRST_NAME" FROM "test_space") SELECT * FROM cte"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("cte"."a"::string -> "a")
+        scan cte cte($0)
+    subquery $0:
+    motion [policy: full, program: ReshardIfNeeded]
+                projection ("test_space"."FIRST_NAME"::string -> "a")
+                    scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn recursive_cte() {
+    let sql = r#"
+        WITH RECURSIVE cte (a) AS (
+            SELECT "e" FROM "t2"
+            UNION ALL
+            SELECT "a" + 1 FROM cte WHERE "a" < 10
+        )
+        SELECT * FROM cte
+    "#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+
+    let top_id = plan.get_top().unwrap();
+    let cte_id = plan.get_first_rel_child(top_id).unwrap();
+    let Relational::ScanCte(ScanCte {
+        alias,
+        child: cte_motion_id,
+        is_recursive: true,
+        ..
+    }) = plan.get_relation_node(cte_id).unwrap()
+    else {
+        panic!("expected recursive CTE under projection");
+    };
+    assert_eq!(alias, "cte");
+    assert_eq!(
+        plan.get_motion_policy(*cte_motion_id).unwrap(),
+        &MotionPolicy::Full
+    );
+
+    // The body is a union of the working table and the recursive term,
+    // both of them are gathered on the router.
+    let union_id = plan.get_first_rel_child(*cte_motion_id).unwrap();
+    let union = plan.get_relation_node(union_id).unwrap();
+    assert!(matches!(union, Relational::UnionAll(_)));
+    let children = union.children().to_vec();
+    let Relational::ScanCte(ScanCte {
+        alias,
+        child: working_table_id,
+        is_recursive: false,
+        ..
+    }) = plan.get_relation_node(children[0]).unwrap()
+    else {
+        panic!("expected working table in the union");
+    };
+    assert_eq!(alias, "cte");
+    assert_eq!(
+        plan.get_motion_policy(*working_table_id).unwrap(),
+        &MotionPolicy::Full
+    );
+    assert_eq!(
+        plan.get_motion_policy(children[1]).unwrap(),
+        &MotionPolicy::Full
+    );
+}
+
+#[test]
+fn recursive_cte_without_union() {
+    let sql = r#"
+        WITH RECURSIVE cte (a) AS (SELECT "a" + 1 FROM cte)
+        SELECT * FROM cte
+    "#;
+    let metadata = &RouterConfigurationMock::new();
+    let plan_error = AbstractSyntaxTree::transform_into_plan(sql, &[], metadata);
+    assert_eq!(
+        plan_error,
+        Err(SbroadError::Invalid(
+            Entity::Cte,
+            Some(
+                r#"recursive CTE "cte" must have the form: non-recursive term UNION [ALL] recursive term"#
+                    .into()
+            )
+        ))
+    );
+}
+
+#[test]
+fn recursive_cte_self_reference_in_base() {
+    let sql = r#"
+        WITH RECURSIVE cte (a) AS (
+            SELECT "a" FROM cte
+            UNION
+            SELECT "e" FROM "t2"
+        )
+        SELECT * FROM cte
+    "#;
+    let metadata = &RouterConfigurationMock::new();
+    let plan_error = AbstractSyntaxTree::transform_into_plan(sql, &[], metadata);
+    assert_eq!(
+        plan_error,
+        Err(SbroadError::Invalid(
+            Entity::Cte,
+            Some(
+                r#"recursive CTE "cte" must not refer to itself in the non-recursive term"#.into()
+            )
+        ))
+    );
+}
@@ -1,6 +1,8 @@
 use crate::{
     frontend::Ast,
-    ir::node::{ddl::Ddl, CreateTable},
+    ir::node::{
+        ddl::Ddl, AlterColumn, AlterTable, AlterTableOp, Comment, CreateTable, Notification,
+    },
 };
 use pretty_assertions::assert_eq;
 use smol_str::{SmolStr, ToSmolStr};
@@ -8,7 +10,12 @@ use smol_str::{SmolStr, ToSmolStr};
 use crate::{
     executor::engine::mock::RouterConfigurationMock,
     frontend::sql::ast::AbstractSyntaxTree,
-    ir::{ddl::ColumnDef, types::DomainType as DataType},
+    ir::{
+        ddl::{CheckDef, ColumnDef, DefaultDef, NotificationCommand},
+        operator::Bool,
+        types::DomainType as DataType,
+        value::Value,
+    },
 };
 
 #[test]
@@ -117,6 +124,155 @@ fn infer_sk_from_pk() {
     );
 }
 
+#[test]
+fn identity_column() {
+    let input = r#"create table t (id int generated always as identity primary key, a text)"#;
+
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let top_node = plan.get_ddl_node(top_id).unwrap();
+
+    let Ddl::CreateTable(CreateTable {
+        format,
+        identity,
+        sharding_key,
+        ..
+    }) = top_node
+    else {
+        panic!("expected create table")
+    };
+
+    assert_eq!(identity.as_deref(), Some("id"));
+    assert!(!format[0].is_nullable);
+    assert_eq!(sharding_key.as_ref().unwrap(), &vec!["id".to_smolstr()]);
+}
+
+#[test]
+fn identity_column_errors() {
+    let metadata = &RouterConfigurationMock::new();
+    let cases = [
+        (
+            r#"create table t (id text generated always as identity primary key)"#,
+            "Identity column id must be of integer or unsigned type.",
+        ),
+        (
+            r#"create table t (id int generated always as identity null, a int primary key)"#,
+            "Identity column id mustn't be nullable.",
+        ),
+        (
+            r#"create table t (id int generated always as identity primary key,
+                b int generated always as identity)"#,
+            "Table can have only one identity column.",
+        ),
+    ];
+    for (input, expected) in cases {
+        let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
+
+#[test]
+fn column_check() {
+    let input = r#"create table t (
+        a int primary key check (a > 0),
+        b text constraint b_not_empty check (b <> ''),
+        c double check (c <= 1.5))"#;
+
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Ddl::CreateTable(CreateTable { checks, .. }) = plan.get_ddl_node(top_id).unwrap() else {
+        panic!("expected create table")
+    };
+
+    assert_eq!(
+        checks,
+        &vec![
+            CheckDef {
+                name: "t_a_check".into(),
+                column: "a".into(),
+                op: Bool::Gt,
+                value: Value::from(0_i64),
+            },
+            CheckDef {
+                name: "b_not_empty".into(),
+                column: "b".into(),
+                op: Bool::NotEq,
+                value: Value::from(""),
+            },
+            CheckDef {
+                name: "t_c_check".into(),
+                column: "c".into(),
+                op: Bool::LtEq,
+                value: Value::from(1.5_f64),
+            },
+        ]
+    );
+
+    let cases = [
+        (
+            r#"create table t (a int primary key, b int check (a > 0))"#,
+            "CHECK constraint t_b_check of column b refers to column a.",
+        ),
+        (
+            r#"create table t (a int primary key check (a > 'x'))"#,
+            "CHECK constraint t_a_check compares column a of type int with x.",
+        ),
+        (
+            r#"create table t (a int primary key constraint c check (a > 0),
+                b int constraint c check (b > 0))"#,
+            "Constraint c is specified more than once.",
+        ),
+    ];
+    for (input, expected) in cases {
+        let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
+
+#[test]
+fn column_default() {
+    let input =
+        r#"create table t (a int primary key, b text default 'x' not null, c double default 1)"#;
+
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Ddl::CreateTable(CreateTable { defaults, .. }) = plan.get_ddl_node(top_id).unwrap() else {
+        panic!("expected create table")
+    };
+
+    assert_eq!(
+        defaults,
+        &vec![
+            DefaultDef {
+                column: "b".into(),
+                value: Value::from("x"),
+            },
+            DefaultDef {
+                column: "c".into(),
+                value: Value::from(1_f64),
+            },
+        ]
+    );
+
+    let cases = [
+        (
+            r#"create table t (a int primary key default 'x')"#,
+            "Default value x doesn't match type int of column a.",
+        ),
+        (
+            r#"create table t (a int generated always as identity default 1 primary key)"#,
+            "Identity column a can't have a default value.",
+        ),
+    ];
+    for (input, expected) in cases {
+        let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
+
 #[test]
 fn infer_alias_int2_int4_int8_bigint_smallint() {
     let input =
@@ -300,3 +456,217 @@ fn infer_alias_number_zero_parameters() {
         assert_eq!(format, &vec![def_a]);
     }
 }
+
+#[test]
+fn comment_on_table_and_column() {
+    let metadata = &RouterConfigurationMock::new();
+
+    let input = r#"comment on table "global_t" is 'It''s a table'"#;
+    let plan = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Ddl::Comment(Comment {
+        table_name,
+        column_name,
+        comment,
+        ..
+    }) = plan.get_ddl_node(top_id).unwrap()
+    else {
+        panic!("expected comment")
+    };
+    assert_eq!(table_name, "global_t");
+    assert_eq!(column_name, &None);
+    assert_eq!(comment.as_deref(), Some("It's a table"));
+
+    let input = r#"comment on column "global_t"."b" is null"#;
+    let plan = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Ddl::Comment(Comment {
+        table_name,
+        column_name,
+        comment,
+        ..
+    }) = plan.get_ddl_node(top_id).unwrap()
+    else {
+        panic!("expected comment")
+    };
+    assert_eq!(table_name, "global_t");
+    assert_eq!(column_name.as_deref(), Some("b"));
+    assert_eq!(comment, &None);
+
+    let input = r#"comment on table "not_exists" is 'text'"#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+    assert!(err.to_string().contains("not_exists"));
+}
+
+#[test]
+fn listen_unlisten_notify() {
+    let metadata = &RouterConfigurationMock::new();
+    let cases = [
+        (
+            "LISTEN Events",
+            NotificationCommand::Listen {
+                channel: "events".into(),
+            },
+        ),
+        (
+            r#"unlisten "Events""#,
+            NotificationCommand::Unlisten {
+                channel: Some("Events".into()),
+            },
+        ),
+        (
+            "unlisten *",
+            NotificationCommand::Unlisten { channel: None },
+        ),
+        (
+            "notify events",
+            NotificationCommand::Notify {
+                channel: "events".into(),
+                payload: "".into(),
+            },
+        ),
+        (
+            "notify events, 'it''s done'",
+            NotificationCommand::Notify {
+                channel: "events".into(),
+                payload: "it's done".into(),
+            },
+        ),
+    ];
+    for (input, expected) in cases {
+        let plan = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap();
+        let top_id = plan.get_top().unwrap();
+        let Ddl::Notification(Notification { command, .. }) = plan.get_ddl_node(top_id).unwrap()
+        else {
+            panic!("expected notification for {input}")
+        };
+        assert_eq!(command, &expected, "{input}");
+    }
+
+    for input in ["listen", "notify", "notify events, 1", "listen *"] {
+        assert!(AbstractSyntaxTree::transform_into_plan(input, &[], metadata).is_err());
+    }
+}
+
+#[test]
+fn alter_table_add_and_drop_column() {
+    let metadata = &RouterConfigurationMock::new();
+
+    let input = r#"alter table "t" add column "e" int not null, drop column if exists "d""#;
+    let plan = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Ddl::AlterTable(AlterTable { name, op, .. }) = plan.get_ddl_node(top_id).unwrap() else {
+        panic!("expected alter table")
+    };
+    assert_eq!(name, "t");
+    assert_eq!(
+        op,
+        &AlterTableOp::AlterColumn(vec![
+            AlterColumn::Add {
+                column: ColumnDef {
+                    name: SmolStr::from("e"),
+                    data_type: DataType::Integer,
+                    is_nullable: false,
+                },
+                if_not_exists: false,
+            },
+            AlterColumn::Drop {
+                name: SmolStr::from("d"),
+                if_exists: true,
+            },
+        ])
+    );
+
+    let input = r#"alter table "t" alter column "d" drop not null"#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+    assert!(err.to_string().contains("ALTER COLUMN"));
+}
+
+#[test]
+fn select_into() {
+    let metadata = &RouterConfigurationMock::new();
+
+    let input = r#"select "b", "a" + 1 as "x", 'text' as "y" into table "t2" primary key ("b") from "t" where "c" = 1"#;
+    let plan = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap();
+    let select_into = plan.select_into.as_deref().unwrap();
+    let create_table = &select_into.create_table;
+    assert_eq!(create_table.name, "t2");
+    assert_eq!(
+        create_table.format,
+        vec![
+            ColumnDef {
+                name: "b".into(),
+                data_type: DataType::Integer,
+                is_nullable: false,
+            },
+            ColumnDef {
+                name: "x".into(),
+                data_type: DataType::Integer,
+                is_nullable: true,
+            },
+            ColumnDef {
+                name: "y".into(),
+                data_type: DataType::String,
+                is_nullable: true,
+            },
+        ]
+    );
+    let expected_key: Vec<SmolStr> = vec!["b".into()];
+    assert_eq!(create_table.primary_key, expected_key);
+    assert_eq!(create_table.sharding_key.as_ref().unwrap(), &expected_key);
+    assert!(create_table.identity.is_none());
+    // The table is filled by the query without the INTO clause.
+    assert_eq!(
+        select_into.insert,
+        r#"INSERT INTO "t2" ("b", "x", "y") select "b", "a" + 1 as "x", 'text' as "y"  from "t" where "c" = 1"#
+    );
+    // The plan itself is the query filling the new table.
+    assert!(plan.get_relation_node(plan.get_top().unwrap()).is_ok());
+
+    let input = r#"select "b", "a" as "x" into "t2" primary key ("x", "b") from "t""#;
+    let plan = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap();
+    let create_table = &plan.select_into.as_deref().unwrap().create_table;
+    let expected_key: Vec<SmolStr> = vec!["x".into(), "b".into()];
+    assert_eq!(create_table.primary_key, expected_key);
+    assert_eq!(create_table.sharding_key.as_ref().unwrap(), &expected_key);
+    assert!(create_table.format.iter().all(|c| !c.is_nullable));
+
+    let input = r#"select "a" from "t""#;
+    let plan = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap();
+    assert!(plan.select_into.is_none());
+}
+
+#[test]
+fn select_into_errors() {
+    let metadata = &RouterConfigurationMock::new();
+    let cases = [
+        (
+            r#"select "a" into "t2" primary key ("a") from "t" union all select "b" from "t""#,
+            "INTO is allowed only in a top-level SELECT without set operations",
+        ),
+        (
+            r#"select * from (select "a" into "t2" primary key ("a") from "t")"#,
+            "INTO is allowed only in a top-level SELECT without set operations",
+        ),
+        (
+            r#"select "a", "b" as "a" into "t2" primary key ("a") from "t""#,
+            "Column a is specified more than once.",
+        ),
+        (
+            r#"select "a" as "bucket_id" into "t2" primary key ("bucket_id") from "t""#,
+            "bucket_id is reserved for system use",
+        ),
+        (
+            r#"select "a" into "t2" from "t""#,
+            "SELECT INTO requires PRIMARY KEY of the created table.",
+        ),
+        (
+            r#"select "a" into "t2" primary key ("b") from "t""#,
+            "Primary key column b not found in the query output.",
+        ),
+    ];
+    for (input, expected) in cases {
+        let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
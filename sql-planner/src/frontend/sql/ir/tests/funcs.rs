@@ -1,4 +1,6 @@
-use crate::ir::transformation::helpers::sql_to_optimized_ir;
+use crate::ir::transformation::helpers::{expect_sql_to_ir_error, sql_to_optimized_ir};
+use crate::ir::types::UnrestrictedType;
+use crate::ir::value::Value;
 
 #[test]
 fn lower_upper() {
@@ -14,3 +16,320 @@ fn lower_upper() {
         sql_motion_row_max = 5000
     "#);
 }
+
+#[test]
+fn numeric_functions_types() {
+    let input = r#"select ceil(1.5), ceil(1), floor(1.5e0), round(2), round(1.55, 1),
+        round(1234, -2), sign(-1.5e0), sign(2.5) from t1"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    let top = plan.get_top().unwrap();
+    let output = plan.get_relational_output(top).unwrap();
+    let types: Vec<UnrestrictedType> = plan
+        .get_row_list(output)
+        .unwrap()
+        .iter()
+        .map(|col_id| {
+            plan.get_expression_node(*col_id)
+                .unwrap()
+                .calculate_type(&plan)
+                .unwrap()
+                .get()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            UnrestrictedType::Decimal,
+            UnrestrictedType::Integer,
+            UnrestrictedType::Double,
+            UnrestrictedType::Integer,
+            UnrestrictedType::Decimal,
+            UnrestrictedType::Decimal,
+            UnrestrictedType::Integer,
+            UnrestrictedType::Integer,
+        ]
+    );
+}
+
+#[test]
+fn rounding_of_double() {
+    let input = r#"select ceil(1.5e0), floor($1), round(a::double), round(1.5e0, 1), ceil(1)
+        from t1"#;
+
+    let plan = sql_to_optimized_ir(input, vec![Value::from(1.5_f64)]);
+
+    // Rounding of a double is planned as separate builtins.
+    let explain = plan.as_explain().unwrap();
+    assert_eq!(explain.matches("_pico_ceil_double").count(), 1);
+    assert_eq!(explain.matches("_pico_floor_double").count(), 1);
+    assert_eq!(explain.matches("_pico_round_double").count(), 1);
+    assert_eq!(explain.matches("round(").count(), 1);
+    assert_eq!(explain.matches("ceil(").count(), 1);
+}
+
+#[test]
+fn string_functions_types() {
+    let input = r#"select length('абв'), char_length('абв'), octet_length('абв'),
+        ascii('a'), chr(1078), lpad('a', 3), rpad('a', 3, '-'), repeat('a', 2),
+        reverse('abc'), string_to_array('a,b', ','), string_to_array('a,b', ',', 'b'),
+        array_to_string(string_to_array('a,b', ','), ';'), split_part('a,b', ',', 2) from t1"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    let top = plan.get_top().unwrap();
+    let output = plan.get_relational_output(top).unwrap();
+    let types: Vec<UnrestrictedType> = plan
+        .get_row_list(output)
+        .unwrap()
+        .iter()
+        .map(|col_id| {
+            plan.get_expression_node(*col_id)
+                .unwrap()
+                .calculate_type(&plan)
+                .unwrap()
+                .get()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            UnrestrictedType::Integer,
+            UnrestrictedType::Integer,
+            UnrestrictedType::Integer,
+            UnrestrictedType::Integer,
+            UnrestrictedType::String,
+            UnrestrictedType::String,
+            UnrestrictedType::String,
+            UnrestrictedType::String,
+            UnrestrictedType::String,
+            UnrestrictedType::Array,
+            UnrestrictedType::Array,
+            UnrestrictedType::String,
+            UnrestrictedType::String,
+        ]
+    );
+}
+
+#[test]
+fn formatting_functions() {
+    let input = r#"select to_char(1234.5, '999,999.99'), to_char(7, '000'),
+        to_char(to_date('2024-01-02', 'YYYY-MM-DD'), 'DD.MM.YYYY'),
+        to_number('1,234.5', '9,999.9') from t1"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    // TO_CHAR of a number is planned as a separate builtin.
+    let explain = plan.as_explain().unwrap();
+    assert_eq!(explain.matches("_pico_to_char_number").count(), 2);
+    let explain = explain.replace("_pico_to_char_number", "");
+    assert_eq!(explain.matches("to_char").count(), 1);
+
+    let top = plan.get_top().unwrap();
+    let output = plan.get_relational_output(top).unwrap();
+    let types: Vec<UnrestrictedType> = plan
+        .get_row_list(output)
+        .unwrap()
+        .iter()
+        .map(|col_id| {
+            plan.get_expression_node(*col_id)
+                .unwrap()
+                .calculate_type(&plan)
+                .unwrap()
+                .get()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            UnrestrictedType::String,
+            UnrestrictedType::String,
+            UnrestrictedType::String,
+            UnrestrictedType::Decimal,
+        ]
+    );
+}
+
+#[test]
+fn json_operators_types() {
+    let input = r#"select '{"a": {"b": "c"}}'::json -> 'a', '{"a": 1}'::jsonb ->> 'a',
+        '{"a": {"b": "c"}}'::json -> 'a' ->> 'b' from t1"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    let top = plan.get_top().unwrap();
+    let output = plan.get_relational_output(top).unwrap();
+    let types: Vec<UnrestrictedType> = plan
+        .get_row_list(output)
+        .unwrap()
+        .iter()
+        .map(|col_id| {
+            plan.get_expression_node(*col_id)
+                .unwrap()
+                .calculate_type(&plan)
+                .unwrap()
+                .get()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            UnrestrictedType::Any,
+            UnrestrictedType::String,
+            UnrestrictedType::String,
+        ]
+    );
+}
+
+#[test]
+fn malformed_json_literal() {
+    let err = expect_sql_to_ir_error(r#"select '{"a": 1'::json from t1"#, &[]);
+    assert_eq!(
+        err.to_string(),
+        r#"invalid input syntax for type json: "{"a": 1""#
+    );
+}
+
+#[test]
+fn json_predicates() {
+    let input = r#"select a is json, a is not json, '[1, 2]'::json @> '1'::json,
+        jsonb_path_query('{"a": [1]}'::json, '$.a[0]') from t1
+        where a is json and '{"a": 1}'::json -> 'a' @> '1'::json"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    let explain = plan.as_explain().unwrap();
+    assert_eq!(explain.matches("_pico_is_json").count(), 3);
+    assert_eq!(explain.matches("_pico_json_contains").count(), 2);
+
+    let top = plan.get_top().unwrap();
+    let output = plan.get_relational_output(top).unwrap();
+    let types: Vec<UnrestrictedType> = plan
+        .get_row_list(output)
+        .unwrap()
+        .iter()
+        .map(|col_id| {
+            plan.get_expression_node(*col_id)
+                .unwrap()
+                .calculate_type(&plan)
+                .unwrap()
+                .get()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            UnrestrictedType::Boolean,
+            UnrestrictedType::Boolean,
+            UnrestrictedType::Boolean,
+            UnrestrictedType::Any,
+        ]
+    );
+}
+
+#[test]
+fn json_contains_non_json() {
+    let err = expect_sql_to_ir_error(r#"select b @> '{}'::json from t1"#, &[]);
+    assert_eq!(
+        err.to_string(),
+        "could not resolve operator overload for @>(int, map)"
+    );
+}
+
+#[test]
+fn hash_functions() {
+    let input = r#"select md5(a), sha256(a), sha256(a::bytea) from t1"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    // SHA256 of a bytea is planned as a separate builtin.
+    let explain = plan.as_explain().unwrap();
+    assert_eq!(explain.matches("_pico_sha256_bytea").count(), 1);
+
+    let top = plan.get_top().unwrap();
+    let output = plan.get_relational_output(top).unwrap();
+    let types: Vec<UnrestrictedType> = plan
+        .get_row_list(output)
+        .unwrap()
+        .iter()
+        .map(|col_id| {
+            plan.get_expression_node(*col_id)
+                .unwrap()
+                .calculate_type(&plan)
+                .unwrap()
+                .get()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            UnrestrictedType::String,
+            UnrestrictedType::String,
+            UnrestrictedType::Bytea,
+        ]
+    );
+}
+
+#[test]
+fn sha256_bytea_param() {
+    let input = r#"select sha256($1), sha256($2) from t1"#;
+
+    let params = vec![Value::Bytea(b"abc".to_vec()), Value::from("abc")];
+    let plan = sql_to_optimized_ir(input, params);
+
+    // The overload is resolved by the parameter type.
+    let explain = plan.as_explain().unwrap();
+    assert_eq!(explain.matches("_pico_sha256_bytea").count(), 1);
+}
+
+#[test]
+fn to_char_number_params() {
+    let input = r#"select to_char($1, '000'), to_char($2 * 2, '9.99') from t1"#;
+
+    let params = vec![Value::from(7_i64), Value::from(1.5_f64)];
+    let plan = sql_to_optimized_ir(input, params);
+
+    // The overload is resolved by the inferred argument types.
+    let explain = plan.as_explain().unwrap();
+    assert_eq!(explain.matches("_pico_to_char_number").count(), 2);
+}
+
+#[test]
+fn date_trunc() {
+    let input = r#"select date_trunc('hour', a::datetime), count(*) from t1
+        group by date_trunc('hour', a::datetime)"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    let top = plan.get_top().unwrap();
+    let output = plan.get_relational_output(top).unwrap();
+    let types: Vec<UnrestrictedType> = plan
+        .get_row_list(output)
+        .unwrap()
+        .iter()
+        .map(|col_id| {
+            plan.get_expression_node(*col_id)
+                .unwrap()
+                .calculate_type(&plan)
+                .unwrap()
+                .get()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(
+        types,
+        vec![UnrestrictedType::Datetime, UnrestrictedType::Integer]
+    );
+
+    let err = expect_sql_to_ir_error(r#"select date_trunc('decade', a::datetime) from t1"#, &[]);
+    assert!(err
+        .to_string()
+        .contains(r#"date_trunc: unit "decade" not recognized"#));
+}
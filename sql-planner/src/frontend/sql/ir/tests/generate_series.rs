@@ -0,0 +1,151 @@
+use crate::executor::engine::helpers::generate_series;
+use crate::ir::node::relational::Relational;
+use crate::ir::node::NodeId;
+use crate::ir::transformation::helpers::sql_to_optimized_ir;
+use crate::ir::tree::traversal::PostOrder;
+use crate::ir::types::{DerivedType, UnrestrictedType as Type};
+use crate::ir::value::Value;
+use crate::ir::Plan;
+use pretty_assertions::assert_eq;
+use tarantool::datetime::Datetime;
+use time::macros::datetime;
+
+/// The VALUES node holding the series call.
+fn values_id(plan: &Plan) -> NodeId {
+    let top = plan.get_top().unwrap();
+    let dfs = PostOrder::with_capacity(|x| plan.nodes.rel_iter(x), 10);
+    dfs.into_iter(top)
+        .map(|level_node| level_node.1)
+        .find(|id| matches!(plan.get_relation_node(*id), Ok(Relational::Values(_))))
+        .expect("VALUES node not found")
+}
+
+/// Elements of the series as the executor expands them.
+fn try_series(plan: &Plan) -> Result<Vec<Value>, String> {
+    let values_id = values_id(plan);
+    let args = generate_series::series_args(plan, values_id)
+        .unwrap()
+        .expect("generate_series call not found");
+    let vtable = generate_series::materialize(plan, values_id, args).map_err(|e| e.to_string())?;
+    Ok(vtable
+        .get_tuples()
+        .iter()
+        .map(|tuple| tuple[0].clone())
+        .collect())
+}
+
+fn series(plan: &Plan) -> Vec<Value> {
+    try_series(plan).unwrap()
+}
+
+/// Type of the first column of the query result.
+fn result_type(plan: &Plan) -> DerivedType {
+    let top = plan.get_top().unwrap();
+    let output = plan.get_relational_output(top).unwrap();
+    let column_id = plan.get_row_list(output).unwrap()[0];
+    plan.get_expression_node(column_id)
+        .unwrap()
+        .calculate_type(plan)
+        .unwrap()
+}
+
+fn generate_series_error(sql: &str) -> String {
+    try_series(&sql_to_optimized_ir(sql, vec![])).unwrap_err()
+}
+
+#[test]
+fn generate_series_integers() {
+    let plan = sql_to_optimized_ir("select * from generate_series(1, 5)", vec![]);
+    assert_eq!(
+        series(&plan),
+        (1..=5).map(Value::Integer).collect::<Vec<_>>()
+    );
+    assert_eq!(result_type(&plan), DerivedType::new(Type::Integer));
+
+    let plan = sql_to_optimized_ir("select * from generate_series(10, 1, -4)", vec![]);
+    assert_eq!(
+        series(&plan),
+        vec![Value::Integer(10), Value::Integer(6), Value::Integer(2)]
+    );
+}
+
+#[test]
+fn generate_series_datetimes() {
+    let sql = r#"
+        select * from generate_series(
+            '2024-01-01T00:00:00Z'::datetime,
+            '2024-01-02T00:00:00Z'::datetime,
+            '12 hours'
+        )"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+    let dt = |inner| Value::Datetime(Datetime::from_inner(inner));
+    assert_eq!(
+        series(&plan),
+        vec![
+            dt(datetime!(2024-01-01 0:00 UTC)),
+            dt(datetime!(2024-01-01 12:00 UTC)),
+            dt(datetime!(2024-01-02 0:00 UTC)),
+        ]
+    );
+    assert_eq!(result_type(&plan), DerivedType::new(Type::Datetime));
+}
+
+#[test]
+fn generate_series_empty() {
+    let plan = sql_to_optimized_ir("select * from generate_series(5, 1)", vec![]);
+    assert_eq!(series(&plan), vec![]);
+    assert_eq!(result_type(&plan), DerivedType::new(Type::Integer));
+}
+
+#[test]
+fn generate_series_parameters() {
+    let sql = "select * from generate_series($1, $2, $3)";
+    let params = vec![Value::Integer(1), Value::Integer(9), Value::Integer(4)];
+    let plan = sql_to_optimized_ir(sql, params);
+    assert_eq!(
+        series(&plan),
+        vec![Value::Integer(1), Value::Integer(5), Value::Integer(9)]
+    );
+    assert_eq!(result_type(&plan), DerivedType::new(Type::Integer));
+
+    let params = vec![
+        Value::from("2024-01-01T00:00:00Z"),
+        Value::from("2024-01-03T00:00:00Z"),
+        Value::from("1 day"),
+    ];
+    let plan = sql_to_optimized_ir(sql, params);
+    assert_eq!(series(&plan).len(), 3);
+    assert_eq!(result_type(&plan), DerivedType::new(Type::Datetime));
+}
+
+#[test]
+fn generate_series_alias_and_join() {
+    let sql = r#"
+        select "a", g.generate_series from "t"
+        join generate_series(1, 3) as g on "a" = g.generate_series
+    "#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+    assert_eq!(series(&plan).len(), 3);
+}
+
+#[test]
+fn generate_series_errors() {
+    assert_eq!(
+        generate_series_error("select * from generate_series(1, 5, 0)"),
+        "invalid expression: generate_series: step size cannot equal zero"
+    );
+    assert_eq!(
+        generate_series_error("select * from generate_series(1, 2 + 3)"),
+        "invalid expression: generate_series: arguments must be constants or parameters"
+    );
+    assert_eq!(
+        generate_series_error(
+            "select * from generate_series('2024-01-01'::datetime, '2024-01-02'::datetime)"
+        ),
+        "invalid expression: generate_series: step is required for a datetime series"
+    );
+    assert_eq!(
+        generate_series_error("select * from generate_series(1.5, 2.5)"),
+        "invalid expression: generate_series: unsupported bounds 1.5 and 2.5, expected integers or datetimes"
+    );
+}
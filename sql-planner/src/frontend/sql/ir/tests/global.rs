@@ -210,7 +210,7 @@ fn front_sql_global_tbl_sq3() {
         selection (not (ROW("global_t"."a"::int, "global_t"."b"::int) in ROW($1, $1))) or (ROW("global_t"."a"::int, "global_t"."b"::int) < ROW($0, $0))
             scan "global_t"
     subquery $0:
-    motion [policy: full, program: ReshardIfNeeded]
+    motion [policy: single, program: ReshardIfNeeded]
                 scan
                     projection ("t"."a"::int -> "a1", "t"."b"::int -> "b1")
                         scan "t"
@@ -939,13 +939,13 @@ fn front_sql_global_union_all3() {
         projection ("unnamed_subquery"."a"::decimal -> "a")
             scan "unnamed_subquery"
                 union all
-                    projection ("global_t"."a"::int -> "a")
+                    projection ("global_t"."a"::int::decimal -> "a")
                         scan "global_t"
                     projection (sum(("sum_1"::decimal))::decimal -> "col_1")
                         motion [policy: full, program: ReshardIfNeeded]
                             projection (sum(("t2"."e"::int::int))::decimal -> "sum_1")
                                 scan "t2"
-        projection ("global_t"."b"::int -> "b")
+        projection ("global_t"."b"::int::decimal -> "b")
             scan "global_t"
     execution options:
         sql_vdbe_opcode_max = 45000
@@ -1038,7 +1038,7 @@ fn front_sql_global_union2() {
     insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
     motion [policy: full, program: RemoveDuplicates]
         union
-            projection ("global_t"."a"::int -> "a")
+            projection ("global_t"."a"::int::decimal -> "a")
                 scan "global_t"
             projection (sum(("sum_1"::decimal))::decimal -> "col_1")
                 motion [policy: full, program: ReshardIfNeeded]
@@ -1177,7 +1177,7 @@ fn check_plan_except_global_vs_single() {
 
     insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
     except
-        projection ("global_t"."a"::int -> "a")
+        projection ("global_t"."a"::int::decimal -> "a")
             scan "global_t"
         projection (sum(("sum_1"::decimal))::decimal -> "col_1")
             motion [policy: full, program: ReshardIfNeeded]
@@ -1205,7 +1205,7 @@ fn check_plan_except_single_vs_global() {
             motion [policy: full, program: ReshardIfNeeded]
                 projection (sum(("t2"."e"::int::int))::decimal -> "sum_1")
                     scan "t2"
-        projection ("global_t"."a"::int -> "a")
+        projection ("global_t"."a"::int::decimal -> "a")
             scan "global_t"
     execution options:
         sql_vdbe_opcode_max = 45000
@@ -1,4 +1,6 @@
-use crate::ir::transformation::helpers::sql_to_ir_without_bind;
+use crate::ir::node::{ddl::Ddl, CreateIndex, IndexColumn};
+use crate::ir::operator::Collation;
+use crate::ir::transformation::helpers::{expect_sql_to_ir_error, sql_to_ir_without_bind};
 
 #[test]
 fn create_index_parsing() {
@@ -14,3 +16,61 @@ fn create_index_parsing() {
     let sql = r#"CREATE INDEX idx ON t5 using tree(a)"#;
     let _ = sql_to_ir_without_bind(sql, &[]);
 }
+
+#[test]
+fn create_index_nulls_distinct() {
+    let nulls_distinct = |sql: &str| -> bool {
+        let plan = sql_to_ir_without_bind(sql, &[]);
+        let top_id = plan.get_top().unwrap();
+        let Ddl::CreateIndex(CreateIndex { nulls_distinct, .. }) =
+            plan.get_ddl_node(top_id).unwrap()
+        else {
+            panic!("expected create index");
+        };
+        *nulls_distinct
+    };
+
+    assert!(nulls_distinct(r#"CREATE UNIQUE INDEX idx ON t5 (a)"#));
+    assert!(nulls_distinct(
+        r#"CREATE UNIQUE INDEX idx ON t5 (a) NULLS DISTINCT"#
+    ));
+    assert!(!nulls_distinct(
+        r#"CREATE UNIQUE INDEX idx ON t5 (a) NULLS NOT DISTINCT"#
+    ));
+    assert!(!nulls_distinct(
+        r#"CREATE UNIQUE INDEX idx ON t5 USING tree (a) nulls not distinct WITH (hint = true)"#
+    ));
+
+    let err = expect_sql_to_ir_error(r#"CREATE INDEX idx ON t5 (a) NULLS NOT DISTINCT"#, &[]);
+    assert_eq!(
+        "invalid index: NULLS NOT DISTINCT is allowed only for unique indexes",
+        err.to_string()
+    );
+}
+
+#[test]
+fn create_index_collate() {
+    let plan = sql_to_ir_without_bind(
+        r#"CREATE INDEX idx ON t5 (a COLLATE "C", b, c collate unicode_ci)"#,
+        &[],
+    );
+    let top_id = plan.get_top().unwrap();
+    let Ddl::CreateIndex(CreateIndex { columns, .. }) = plan.get_ddl_node(top_id).unwrap() else {
+        panic!("expected create index");
+    };
+    let column = |name: &str, collation| IndexColumn {
+        name: name.into(),
+        collation,
+    };
+    assert_eq!(
+        columns,
+        &[
+            column("a", Some(Collation::Binary)),
+            column("b", None),
+            column("c", Some(Collation::UnicodeCi)),
+        ]
+    );
+
+    let err = expect_sql_to_ir_error(r#"CREATE INDEX idx ON t5 (a COLLATE "de_DE")"#, &[]);
+    assert_eq!(r#"collation "de_DE" not found"#, err.to_string());
+}
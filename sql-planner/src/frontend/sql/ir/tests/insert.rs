@@ -1,3 +1,6 @@
+use crate::executor::engine::mock::RouterConfigurationMock;
+use crate::frontend::sql::ast::AbstractSyntaxTree;
+use crate::frontend::Ast;
 use crate::ir::transformation::helpers::sql_to_optimized_ir;
 use crate::ir::value::Value;
 
@@ -49,3 +52,123 @@ fn insert3() {
         sql_motion_row_max = 5000
     "#);
 }
+
+#[test]
+fn insert_default_nullable() {
+    let pattern =
+        r#"INSERT INTO "test_space"("id", "FIRST_NAME", "sys_op") VALUES(1, DEFAULT, default)"#;
+    let plan = sql_to_optimized_ir(pattern, vec![]);
+
+    let expected = sql_to_optimized_ir(
+        r#"INSERT INTO "test_space"("id", "FIRST_NAME", "sys_op") VALUES(1, NULL, NULL)"#,
+        vec![],
+    );
+    assert_eq!(plan.as_explain().unwrap(), expected.as_explain().unwrap());
+}
+
+#[test]
+fn insert_default_multiple_rows() {
+    let pattern = r#"INSERT INTO "test_space"("id", "FIRST_NAME") VALUES(1, 'a'), (2, DEFAULT)"#;
+    let plan = sql_to_optimized_ir(pattern, vec![]);
+
+    let expected = sql_to_optimized_ir(
+        r#"INSERT INTO "test_space"("id", "FIRST_NAME") VALUES(1, 'a'), (2, NULL)"#,
+        vec![],
+    );
+    assert_eq!(plan.as_explain().unwrap(), expected.as_explain().unwrap());
+}
+
+#[test]
+fn insert_default_non_nullable() {
+    let pattern = r#"INSERT INTO "test_space"("id", "FIRST_NAME") VALUES(DEFAULT, 'a')"#;
+    let metadata = &RouterConfigurationMock::new();
+    let err = AbstractSyntaxTree::transform_into_plan(pattern, &[], metadata).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        r#"invalid column: NonNull column "id" has no default value"#
+    );
+}
+
+#[test]
+fn insert_declared_default() {
+    let pattern = r#"INSERT INTO "default_t"("id", "a") VALUES(1, DEFAULT), (2, 'y')"#;
+    let plan = sql_to_optimized_ir(pattern, vec![]);
+
+    let expected = sql_to_optimized_ir(
+        r#"INSERT INTO "default_t"("id", "a") VALUES(1, 'x'), (2, 'y')"#,
+        vec![],
+    );
+    assert_eq!(plan.as_explain().unwrap(), expected.as_explain().unwrap());
+}
+
+#[test]
+fn insert_omitted_column_with_default() {
+    // Non-nullable column with a declared default may be omitted.
+    let pattern = r#"INSERT INTO "default_t"("id") VALUES(1)"#;
+    let plan = sql_to_optimized_ir(pattern, vec![]);
+
+    let table = plan.dml_node_table(plan.get_top().unwrap()).unwrap();
+    assert_eq!(table.column_default(2), Value::from("x"));
+    assert_eq!(table.column_default(1), Value::Null);
+}
+
+#[test]
+fn insert_identity_omitted() {
+    let pattern = r#"INSERT INTO "identity_t"("a") VALUES('a'), ('b')"#;
+    let plan = sql_to_optimized_ir(pattern, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    insert "identity_t" on conflict: fail
+        motion [policy: segment([ref("id")]), program: ReshardIfNeeded]
+            values
+                value row (data=ROW('a'::string))
+                value row (data=ROW('b'::string))
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+
+    let expected = sql_to_optimized_ir(r#"INSERT INTO "identity_t" VALUES('a'), ('b')"#, vec![]);
+    assert_eq!(plan.as_explain().unwrap(), expected.as_explain().unwrap());
+}
+
+#[test]
+fn insert_identity_from_select() {
+    let pattern = r#"INSERT INTO "identity_t"("a") SELECT "a" FROM "identity_t""#;
+    let plan = sql_to_optimized_ir(pattern, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    insert "identity_t" on conflict: fail
+        motion [policy: segment([ref("id")]), program: ReshardIfNeeded]
+            projection ("identity_t"."a"::string -> "a")
+                scan "identity_t"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn insert_identity_explicit_value() {
+    let pattern = r#"INSERT INTO "identity_t"("id", "a") VALUES(1, 'a')"#;
+    let metadata = &RouterConfigurationMock::new();
+    let err = AbstractSyntaxTree::transform_into_plan(pattern, &[], metadata).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        r#"failed to insert column: cannot insert a non-DEFAULT value into identity column "id""#
+    );
+}
+
+#[test]
+fn update_identity() {
+    let pattern = r#"UPDATE "identity_t" SET "id" = 1"#;
+    let metadata = &RouterConfigurationMock::new();
+    let err = AbstractSyntaxTree::transform_into_plan(pattern, &[], metadata).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        r#"invalid query: it is illegal to update identity column: "id""#
+    );
+}
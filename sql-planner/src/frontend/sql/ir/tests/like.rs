@@ -1,6 +1,6 @@
 use crate::{
     executor::engine::mock::RouterConfigurationMock, frontend::sql::ast::AbstractSyntaxTree,
-    frontend::sql::Ast, ir::transformation::helpers::sql_to_optimized_ir,
+    frontend::sql::Ast, ir::transformation::helpers::sql_to_optimized_ir, ir::value::Value,
 };
 use pretty_assertions::assert_eq;
 
@@ -113,17 +113,17 @@ fn like_explain4() {
         selection ROW($2) LIKE ROW($1) ESCAPE ROW($0)
             scan "t1"
     subquery $0:
-    motion [policy: full, program: ReshardIfNeeded]
+    motion [policy: single, program: ReshardIfNeeded]
                 scan
                     projection ('\'::string -> "col_1")
                         scan "t1"
     subquery $1:
-    motion [policy: full, program: ReshardIfNeeded]
+    motion [policy: single, program: ReshardIfNeeded]
                 scan
                     projection ('hi'::string -> "col_1")
                         scan "t1"
     subquery $2:
-    motion [policy: full, program: ReshardIfNeeded]
+    motion [policy: single, program: ReshardIfNeeded]
                 scan
                     projection ('hi'::string -> "col_1")
                         scan "t1"
@@ -151,3 +151,84 @@ fn ilike_explain() {
         sql_motion_row_max = 5000
     "#);
 }
+
+#[test]
+fn like_any_explain() {
+    let input = r#"select a from t1 where a like any (array['a%', 'b%'])"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("t1"."a"::string -> "a")
+        selection ("t1"."a"::string LIKE 'a%'::string ESCAPE '\'::string) or ("t1"."a"::string LIKE 'b%'::string ESCAPE '\'::string)
+            scan "t1"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn ilike_any_pattern_list_explain() {
+    let input = r#"select a from t1 where a ilike any ('A%', 'b%')"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("t1"."a"::string -> "a")
+        selection (lower(("t1"."a"::string::string))::string LIKE lower(('A%'::string::string))::string ESCAPE '\'::string) or (lower(("t1"."a"::string::string))::string LIKE lower(('b%'::string::string))::string ESCAPE '\'::string)
+            scan "t1"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn like_any_empty_array_explain() {
+    let input = r#"select a from t1 where a like any (array[])"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("t1"."a"::string -> "a")
+        selection false::bool
+            scan "t1"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn like_any_array_param_explain() {
+    let input = r#"select a from t1 where a like any ($1)"#;
+    let patterns = Value::from(vec![Value::from("a%"), Value::from("b%")]);
+
+    let plan = sql_to_optimized_ir(input, vec![patterns]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("t1"."a"::string -> "a")
+        selection ("t1"."a"::string LIKE 'a%'::string ESCAPE '\'::string) or ("t1"."a"::string LIKE 'b%'::string ESCAPE '\'::string)
+            scan "t1"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn like_any_empty_array_param_explain() {
+    let input = r#"select a from t1 where a like any ($1)"#;
+
+    let plan = sql_to_optimized_ir(input, vec![Value::from(Vec::<Value>::new())]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("t1"."a"::string -> "a")
+        selection false::bool
+            scan "t1"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
@@ -1,3 +1,6 @@
+use crate::executor::engine::mock::RouterConfigurationMock;
+use crate::frontend::sql::ast::AbstractSyntaxTree;
+use crate::frontend::Ast;
 use crate::ir::transformation::helpers::sql_to_optimized_ir;
 
 #[test]
@@ -209,3 +212,91 @@ fn explicit_select_bucket_id_from_cte_under_limit() {
         sql_motion_row_max = 5000
     "#);
 }
+
+#[test]
+fn offset_fetch_first() {
+    let sql = r#"SELECT "id" FROM "test_space" OFFSET 5 ROWS FETCH FIRST 10 ROWS ONLY"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    limit 10 offset 5
+        motion [policy: full, program: ReshardIfNeeded]
+            limit 15
+                projection ("test_space"."id"::int -> "id")
+                    scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn limit_offset() {
+    let sql = r#"SELECT "id" FROM "test_space" LIMIT 3 OFFSET 2"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    limit 3 offset 2
+        motion [policy: full, program: ReshardIfNeeded]
+            limit 5
+                projection ("test_space"."id"::int -> "id")
+                    scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn offset_only() {
+    let sql = r#"SELECT "id" FROM "test_space" OFFSET 5"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    offset 5
+        motion [policy: full, program: ReshardIfNeeded]
+            projection ("test_space"."id"::int -> "id")
+                scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn fetch_first_with_ties() {
+    let sql = r#"SELECT "id" FROM "test_space" ORDER BY "id" FETCH FIRST ROW WITH TIES"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    limit 1 with ties
+        projection ("id"::int -> "id")
+            order by ("id"::int)
+                motion [policy: full, program: ReshardIfNeeded]
+                    scan
+                        projection ("test_space"."id"::int -> "id")
+                            scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn offset_fetch_errors() {
+    let metadata = &RouterConfigurationMock::new();
+    let cases = [
+        (
+            r#"SELECT "id" FROM "test_space" FETCH FIRST 2 ROWS WITH TIES"#,
+            "WITH TIES cannot be specified without ORDER BY clause",
+        ),
+        (
+            r#"SELECT "id" FROM "test_space" LIMIT 1 FETCH FIRST 2 ROWS ONLY"#,
+            "LIMIT and FETCH cannot be specified together",
+        ),
+    ];
+    for (input, expected) in cases {
+        let err = AbstractSyntaxTree::transform_into_plan(input, &[], metadata).unwrap_err();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
@@ -0,0 +1,99 @@
+use crate::executor::engine::mock::RouterConfigurationMock;
+use crate::frontend::sql::ast::AbstractSyntaxTree;
+use crate::frontend::Ast;
+use crate::ir::transformation::helpers::sql_to_optimized_ir;
+use crate::ir::RowLockMode;
+
+#[test]
+fn select_for_update() {
+    let sql = r#"SELECT "id" FROM "test_space" LIMIT 100 FOR UPDATE"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+    assert_eq!(plan.row_lock, Some(RowLockMode::Update));
+
+    // The locking clause doesn't change the plan.
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    limit 100
+        motion [policy: full, program: ReshardIfNeeded]
+            limit 100
+                projection ("test_space"."id"::int -> "id")
+                    scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn select_for_share_with_alias() {
+    let sql = r#"SELECT t."id" FROM "test_space" t for share"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+    assert_eq!(plan.row_lock, Some(RowLockMode::Share));
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("t"."id"::int -> "id")
+        scan "test_space" -> "t"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn select_for_update_row_keys() {
+    let sql = r#"SELECT "FIRST_NAME" FROM "test_space" WHERE "sys_op" = 1 FOR UPDATE"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+    let (keys, table) = plan.row_keys_plan().unwrap().unwrap();
+    assert_eq!(table.name, "test_space");
+    assert_eq!(keys.row_lock, None);
+
+    // The primary key is followed by the bucket id.
+    let output = keys.get_relational_output(keys.get_top().unwrap()).unwrap();
+    let names: Vec<_> = keys
+        .get_row_list(output)
+        .unwrap()
+        .iter()
+        .map(|id| keys.get_alias_name(*id).unwrap().to_string())
+        .collect();
+    assert_eq!(names, ["id", "bucket_id"]);
+
+    // Only the rows of a simple scan of a single table can be located.
+    for sql in [
+        r#"SELECT "id" FROM "test_space" LIMIT 1 FOR UPDATE"#,
+        r#"SELECT "id" FROM "test_space" ORDER BY "id" FOR UPDATE"#,
+        r#"SELECT t1."id" FROM "test_space" t1 JOIN "test_space_hist" t2 ON true FOR SHARE"#,
+        r#"SELECT "id" FROM "test_space" WHERE "id" IN (SELECT "id" FROM "test_space_hist") FOR UPDATE"#,
+    ] {
+        let plan = sql_to_optimized_ir(sql, vec![]);
+        assert!(plan.row_lock.is_some());
+        assert!(plan.row_keys_plan().unwrap().is_none(), "{sql}");
+    }
+}
+
+#[test]
+fn select_for_update_not_allowed() {
+    let metadata = &RouterConfigurationMock::new();
+    for (sql, error) in [
+        (
+            r#"SELECT DISTINCT "id" FROM "test_space" FOR UPDATE"#,
+            "FOR UPDATE is not allowed with DISTINCT clause",
+        ),
+        (
+            r#"SELECT "id" FROM "test_space" GROUP BY "id" FOR UPDATE"#,
+            "FOR UPDATE is not allowed with GROUP BY clause",
+        ),
+        (
+            r#"SELECT count(*) FROM "test_space" FOR SHARE"#,
+            "FOR SHARE is not allowed with aggregate functions",
+        ),
+        (
+            r#"SELECT "id" FROM "test_space" UNION ALL SELECT "id" FROM "test_space" FOR UPDATE"#,
+            "FOR UPDATE is not allowed with UNION/INTERSECT/EXCEPT",
+        ),
+    ] {
+        let err = AbstractSyntaxTree::transform_into_plan(sql, &[], metadata).unwrap_err();
+        assert!(
+            err.to_string().contains(error),
+            "unexpected error for {sql}: {err}"
+        );
+    }
+}
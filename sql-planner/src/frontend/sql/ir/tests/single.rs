@@ -263,7 +263,7 @@ fn front_sql_join_single_left_4() {
     // Inner child here will have distribution Full, outer child will have
     // Distribution::Single
     let input = r#"SELECT * from (select cast(sum("a") as integer) as a, cast(sum("b") as integer) as b from "t") as o 
-        inner join (select "a" as c, "b" as d from "t" group by "a", "b") as i
+        inner join (select "c" as c, "d" as d from "t" group by "c", "d") as i
         on (o.a, o.b) = (i.c, i.d) and o.a in (select "a" from "t")
     "#;
 
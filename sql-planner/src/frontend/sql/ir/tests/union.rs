@@ -1,4 +1,6 @@
-use crate::ir::transformation::helpers::sql_to_optimized_ir;
+use crate::ir::transformation::helpers::{expect_sql_to_ir_error, sql_to_optimized_ir};
+use crate::ir::types::{DerivedType, UnrestrictedType};
+use crate::ir::Plan;
 
 #[test]
 fn front_select_chaning_1() {
@@ -153,3 +155,111 @@ fn union_under_insert1() {
         sql_motion_row_max = 5000
     "#);
 }
+
+#[test]
+fn front_select_chaining_multiset() {
+    let input = r#"
+    select "product_code" from "hash_testing"
+    except all
+    select "a" from "t3"
+    intersect all
+    select "b"::text from "t3"
+    "#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    motion [policy: full, program: ReshardIfNeeded]
+        except all
+            motion [policy: full, program: ReshardIfNeeded]
+                projection ("hash_testing"."product_code"::string -> "product_code")
+                    scan "hash_testing"
+            motion [policy: full, program: ReshardIfNeeded]
+                intersect all
+                    motion [policy: full, program: ReshardIfNeeded]
+                        projection ("t3"."a"::string -> "a")
+                            scan "t3"
+                    motion [policy: full, program: ReshardIfNeeded]
+                        projection ("t3"."b"::int::string -> "col_1")
+                            scan "t3"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+fn top_column_types(plan: &Plan) -> Vec<DerivedType> {
+    let top_id = plan.get_top().unwrap();
+    let output_id = plan.get_relational_output(top_id).unwrap();
+    plan.get_row_list(output_id)
+        .unwrap()
+        .iter()
+        .map(|col_id| {
+            plan.get_expression_node(*col_id)
+                .unwrap()
+                .calculate_type(plan)
+                .unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn union_coerces_to_common_type() {
+    let plan = sql_to_optimized_ir("select 1 union select 2.5", vec![]);
+    assert_eq!(
+        top_column_types(&plan),
+        vec![DerivedType::new(UnrestrictedType::Decimal)]
+    );
+
+    // Nested set operations are coerced through all of their branches.
+    let input = r#"
+    select "a", "b" from "t"
+    union all
+    select "b", "a" from "t"
+    except
+    select 2.5e0, "c" from "t"
+    "#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    assert_eq!(
+        top_column_types(&plan),
+        vec![
+            DerivedType::new(UnrestrictedType::Double),
+            DerivedType::new(UnrestrictedType::Integer),
+        ]
+    );
+    let explain = plan.as_explain().unwrap();
+    assert!(explain.contains(r#"projection ("t"."a"::int::double -> "a", "t"."b"::int -> "b")"#));
+    assert!(explain.contains(r#"projection ("t"."b"::int::double -> "b", "t"."a"::int -> "a")"#));
+}
+
+#[test]
+fn union_string_literal_takes_other_type() {
+    let input = r#"select "e" from "t2" union select '5'"#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    assert_eq!(
+        top_column_types(&plan),
+        vec![DerivedType::new(UnrestrictedType::Integer)]
+    );
+
+    let input = r#"select '2024-01-01' union all select "a"::datetime from "t3""#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    assert_eq!(
+        top_column_types(&plan),
+        vec![DerivedType::new(UnrestrictedType::Datetime)]
+    );
+}
+
+#[test]
+fn union_types_cannot_be_matched() {
+    let err = expect_sql_to_ir_error(r#"select "a" from "t3" union select "e" from "t2""#, &[]);
+    assert_eq!(
+        err.to_string(),
+        r#"UNION types string and int cannot be matched for column "a""#
+    );
+
+    let err = expect_sql_to_ir_error(r#"select 1, true intersect select 2, 3"#, &[]);
+    assert_eq!(
+        err.to_string(),
+        r#"INTERSECT types bool and int cannot be matched for column "col_2""#
+    );
+}
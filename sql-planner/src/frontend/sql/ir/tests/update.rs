@@ -1,5 +1,6 @@
 use crate::ir::transformation::helpers::sql_to_optimized_ir;
 use crate::ir::value::Value;
+use crate::ir::CurrentOf;
 
 #[test]
 fn update1() {
@@ -34,3 +35,82 @@ fn update2() {
         sql_motion_row_max = 5000
     "#);
 }
+
+#[test]
+fn update_current_of() {
+    let pattern = r#"UPDATE "test_space" SET "FIRST_NAME" = 'test' WHERE CURRENT OF cur"#;
+    let plan = sql_to_optimized_ir(pattern, vec![]);
+    assert_eq!(
+        plan.current_of,
+        Some(CurrentOf {
+            cursor: "cur".into(),
+            span: 46..66,
+        })
+    );
+
+    // The filter is added when the cursor row is resolved.
+    let plain = sql_to_optimized_ir(r#"UPDATE "test_space" SET "FIRST_NAME" = 'test'"#, vec![]);
+    assert_eq!(plan.as_explain().unwrap(), plain.as_explain().unwrap());
+
+    let plan = sql_to_optimized_ir(r#"DELETE FROM "test_space" where current of "Cur""#, vec![]);
+    assert_eq!(
+        plan.current_of,
+        Some(CurrentOf {
+            cursor: "Cur".into(),
+            span: 25..47,
+        })
+    );
+
+    let pattern = r#"UPDATE "test_space" SET "FIRST_NAME" = 'test' WHERE "id" = 1"#;
+    assert_eq!(sql_to_optimized_ir(pattern, vec![]).current_of, None);
+}
+
+#[test]
+fn update_current_of_simple_scan() {
+    let is_simple_scan = |sql| {
+        sql_to_optimized_ir(sql, vec![])
+            .is_simple_scan_of("test_space")
+            .unwrap()
+    };
+    assert!(is_simple_scan(r#"SELECT * FROM "test_space""#));
+    assert!(is_simple_scan(
+        r#"SELECT "id", "FIRST_NAME" FROM "test_space" WHERE "id" > 1"#
+    ));
+    assert!(!is_simple_scan(
+        r#"SELECT DISTINCT "FIRST_NAME" FROM "test_space""#
+    ));
+    assert!(!is_simple_scan(r#"SELECT count(*) FROM "test_space""#));
+    assert!(!is_simple_scan(r#"SELECT * FROM "test_space_hist""#));
+    assert!(!is_simple_scan(
+        r#"SELECT * FROM "test_space" WHERE "id" IN (SELECT "id" FROM "test_space_hist")"#
+    ));
+}
+
+#[test]
+fn update_current_of_key_positions() {
+    // "id" is the first column of "test_space".
+    let positions = |sql| {
+        sql_to_optimized_ir(sql, vec![])
+            .simple_scan_positions("test_space", &[0])
+            .unwrap()
+    };
+    assert_eq!(
+        positions(r#"SELECT * FROM "test_space""#),
+        Some(vec![Some(0)])
+    );
+    assert_eq!(
+        positions(r#"SELECT "FIRST_NAME", "id" AS "key" FROM "test_space""#),
+        Some(vec![Some(1)])
+    );
+    // Neither an expression over the key nor another column named after it
+    // returns the key.
+    assert_eq!(
+        positions(r#"SELECT "id" + 1 AS "id" FROM "test_space""#),
+        Some(vec![None])
+    );
+    assert_eq!(
+        positions(r#"SELECT "sysFrom" AS "id" FROM "test_space""#),
+        Some(vec![None])
+    );
+    assert_eq!(positions(r#"SELECT count(*) FROM "test_space""#), None);
+}
@@ -1,11 +1,11 @@
-use crate::errors::SbroadError;
+use crate::errors::{Entity, SbroadError, TypeError};
 use crate::frontend::sql::get_real_function_name;
 use crate::ir::node::expression::{Expression, MutExpression};
 use crate::ir::node::relational::Relational;
 use crate::ir::node::{
-    Alias, ArithmeticExpr, BoolExpr, Bound, BoundType, Case, Cast, Concat, Constant, Frame,
-    FrameType, IndexExpr, Like, NodeId, Over, Parameter, Reference, Row, ScalarFunction,
-    SubQueryReference, Trim, UnaryExpr, ValuesRow, Window,
+    Alias, ArithmeticExpr, BoolExpr, Bound, BoundType, Case, Cast, Concat, Constant, Except, Frame,
+    FrameType, IndexExpr, Intersect, Like, NodeId, Over, Parameter, Reference, Row, ScalarFunction,
+    SubQueryReference, Trim, UnaryExpr, Union, UnionAll, ValuesRow, Window,
 };
 use crate::ir::operator::{Bool, OrderByElement, OrderByEntity, Unary};
 use crate::ir::tree::traversal::{LevelNode, PostOrderWithFilter};
@@ -13,7 +13,7 @@ use crate::ir::types::{CastType, DerivedType, UnrestrictedType as SbroadType};
 use crate::ir::value::Value;
 use crate::ir::Plan;
 use ahash::AHashMap;
-use smol_str::format_smolstr;
+use smol_str::{format_smolstr, SmolStr};
 use sql_type_system::error::Error as TypeSystemError;
 use sql_type_system::expr::{
     ComparisonOperator, Expr as GenericExpr, ExprKind as GenericExprKind, FrameKind, Type,
@@ -44,6 +44,7 @@ impl From<SbroadType> for Type {
             SbroadType::Datetime => Type::Datetime,
             SbroadType::Any => Type::Any,
             SbroadType::Uuid => Type::Uuid,
+            SbroadType::Bytea => Type::Bytea,
             SbroadType::Array => Type::Array,
             SbroadType::Map => Type::Map,
         }
@@ -60,6 +61,7 @@ impl From<Type> for DerivedType {
             Type::Boolean => DerivedType::new(SbroadType::Boolean),
             Type::Datetime => DerivedType::new(SbroadType::Datetime),
             Type::Uuid => DerivedType::new(SbroadType::Uuid),
+            Type::Bytea => DerivedType::new(SbroadType::Bytea),
             Type::Array => DerivedType::new(SbroadType::Array),
             Type::Map => DerivedType::new(SbroadType::Map),
             Type::Any => DerivedType::new(SbroadType::Any),
@@ -81,13 +83,14 @@ pub fn get_parameter_derived_types(analyzer: &TypeAnalyzer) -> Vec<DerivedType>
 impl From<CastType> for Type {
     fn from(value: CastType) -> Self {
         match value {
-            CastType::Integer => Type::Integer,
+            CastType::Integer | CastType::SmallInt | CastType::Int4 => Type::Integer,
             CastType::Decimal => Type::Numeric,
             CastType::Double => Type::Double,
-            CastType::String => Type::Text,
+            CastType::String | CastType::Time => Type::Text,
             CastType::Boolean => Type::Boolean,
-            CastType::Datetime => Type::Datetime,
+            CastType::Datetime | CastType::Date | CastType::Timestamp => Type::Datetime,
             CastType::Uuid => Type::Uuid,
+            CastType::Bytea => Type::Bytea,
             CastType::Json => Type::Map,
         }
     }
@@ -234,7 +237,9 @@ pub fn to_type_expr(
             let kind = TypeExprKind::Operator(op.as_str().into(), vec![left, right]);
             Ok(TypeExpr::new(node_id, kind))
         }
-        ref expr @ Expression::Bool(BoolExpr { op, left, right }) => match op {
+        ref expr @ Expression::Bool(BoolExpr {
+            op, left, right, ..
+        }) => match op {
             Bool::Between => {
                 // Note: lhs: `A >= B`, rhs: `A <= C`.
                 let ((_, lhs), (_, rhs)) =
@@ -302,6 +307,21 @@ pub fn to_type_expr(
                 let kind = TypeExprKind::JsonExtractPath(args);
                 Ok(TypeExpr::new(node_id, kind))
             }
+            "json_extract_path_text" => {
+                let args = to_type_expr_many(children, plan, subquery_map)?;
+                let kind = TypeExprKind::JsonExtractPathText(args);
+                Ok(TypeExpr::new(node_id, kind))
+            }
+            "_pico_json_contains" => {
+                let args = to_type_expr_many(children, plan, subquery_map)?;
+                let kind = TypeExprKind::JsonContains(args);
+                Ok(TypeExpr::new(node_id, kind))
+            }
+            "jsonb_path_query" => {
+                let args = to_type_expr_many(children, plan, subquery_map)?;
+                let kind = TypeExprKind::JsonPathQuery(args);
+                Ok(TypeExpr::new(node_id, kind))
+            }
             name => {
                 let args = to_type_expr_many(children, plan, subquery_map)?;
                 let kind = TypeExprKind::Function(name.to_string(), args);
@@ -398,6 +418,31 @@ pub fn to_type_expr(
             right,
             escape,
         }) => {
+            // An array parameter of `a LIKE ANY ($1)` is kept in a row until binding,
+            // for ILIKE ANY it is wrapped into `lower`.
+            if let Expression::Row(Row { list, .. }) = plan.get_expression_node(*right)? {
+                let [pattern_id] = list.as_slice() else {
+                    return Err(SbroadError::Invalid(
+                        Entity::Expression,
+                        Some("expected a single array in LIKE ANY".into()),
+                    ));
+                };
+                let array_id = match plan.get_expression_node(*pattern_id)? {
+                    Expression::ScalarFunction(ScalarFunction { children, .. }) => {
+                        let [array_id] = children.as_slice() else {
+                            return Err(SbroadError::Invalid(
+                                Entity::Expression,
+                                Some("expected a single argument of lower in ILIKE ANY".into()),
+                            ));
+                        };
+                        *array_id
+                    }
+                    _ => *pattern_id,
+                };
+                let args = to_type_expr_many(&[*left, array_id, *escape], plan, subquery_map)?;
+                let kind = TypeExprKind::Function("like_any".into(), args);
+                return Ok(TypeExpr::new(node_id, kind));
+            }
             let args = to_type_expr_many(&[*left, *right, *escape], plan, subquery_map)?;
             let kind = TypeExprKind::Function("like".into(), args);
             Ok(TypeExpr::new(node_id, kind))
@@ -512,18 +557,76 @@ fn default_type_system() -> TypeSystem {
             Text,
         ),
         Function::new_scalar("_pico_bucket", [Text], Array),
+        Function::new_scalar("_pico_json_parse", [Text], Map),
+        Function::new_scalar("_pico_is_json", [Text], Boolean),
+        Function::new_scalar("_pico_is_json", [Map], Boolean),
         Function::new_scalar("like", [Text, Text, Text], Boolean),
+        Function::new_scalar("like_any", [Text, Array, Text], Boolean),
         Function::new_scalar("trim", [Text], Text),
         Function::new_scalar("trim", [Text, Text], Text),
         Function::new_scalar("to_date", [Text, Text], Datetime),
         Function::new_scalar("to_char", [Datetime, Text], Text),
+        Function::new_scalar("date_trunc", [Text, Datetime], Datetime),
+        Function::new_scalar("to_char", [Numeric, Text], Text),
+        Function::new_scalar("to_char", [Integer, Text], Text),
+        Function::new_scalar("to_char", [Double, Text], Text),
+        // TO_CHAR of a number is planned as a separate builtin,
+        // see `rename_overloaded_builtin`.
+        Function::new_scalar("_pico_to_char_number", [Numeric, Text], Text),
+        Function::new_scalar("_pico_to_char_number", [Integer, Text], Text),
+        Function::new_scalar("_pico_to_char_number", [Double, Text], Text),
+        Function::new_scalar("to_number", [Text, Text], Numeric),
         Function::new_scalar("substr", [Text, Integer], Text),
         Function::new_scalar("substr", [Text, Integer, Integer], Text),
         Function::new_scalar("lower", [Text], Text),
         Function::new_scalar("upper", [Text], Text),
+        Function::new_scalar("length", [Text], Integer),
+        Function::new_scalar("char_length", [Text], Integer),
+        Function::new_scalar("octet_length", [Text], Integer),
+        Function::new_scalar("md5", [Text], Text),
+        Function::new_scalar("sha256", [Text], Text),
+        // SHA256 of a bytea is served by a separate builtin,
+        // see `rename_overloaded_builtin`.
+        Function::new_scalar("sha256", [Bytea], Bytea),
+        Function::new_scalar("_pico_sha256_bytea", [Bytea], Bytea),
+        Function::new_scalar("ascii", [Text], Integer),
+        Function::new_scalar("chr", [Integer], Text),
+        Function::new_scalar("lpad", [Text, Integer], Text),
+        Function::new_scalar("lpad", [Text, Integer, Text], Text),
+        Function::new_scalar("rpad", [Text, Integer], Text),
+        Function::new_scalar("rpad", [Text, Integer, Text], Text),
+        Function::new_scalar("repeat", [Text, Integer], Text),
+        Function::new_scalar("reverse", [Text], Text),
+        Function::new_scalar("split_part", [Text, Text, Integer], Text),
+        Function::new_scalar("string_to_array", [Text, Text], Array),
+        Function::new_scalar("string_to_array", [Text, Text, Text], Array),
+        Function::new_scalar("array_to_string", [Array, Text], Text),
+        Function::new_scalar("array_to_string", [Array, Text, Text], Text),
+        Function::new_scalar("percentile_cont", [Text, Numeric, Boolean], Numeric),
+        Function::new_scalar("percentile_disc", [Text, Numeric, Boolean], Text),
         Function::new_scalar("abs", [Numeric], Numeric),
         Function::new_scalar("abs", [Integer], Integer),
         Function::new_scalar("abs", [Double], Double),
+        Function::new_scalar("ceil", [Numeric], Numeric),
+        Function::new_scalar("ceil", [Integer], Integer),
+        Function::new_scalar("ceil", [Double], Double),
+        Function::new_scalar("floor", [Numeric], Numeric),
+        Function::new_scalar("floor", [Integer], Integer),
+        Function::new_scalar("floor", [Double], Double),
+        Function::new_scalar("round", [Numeric], Numeric),
+        Function::new_scalar("round", [Integer], Integer),
+        Function::new_scalar("round", [Double], Double),
+        Function::new_scalar("round", [Numeric, Integer], Numeric),
+        Function::new_scalar("round", [Integer, Integer], Numeric),
+        Function::new_scalar("round", [Double, Integer], Numeric),
+        // Rounding of a double is served by separate builtins,
+        // see `rename_overloaded_builtin`.
+        Function::new_scalar("_pico_ceil_double", [Double], Double),
+        Function::new_scalar("_pico_floor_double", [Double], Double),
+        Function::new_scalar("_pico_round_double", [Double], Double),
+        Function::new_scalar("sign", [Numeric], Integer),
+        Function::new_scalar("sign", [Integer], Integer),
+        Function::new_scalar("sign", [Double], Integer),
         Function::new_scalar("substring", [Text, Integer], Text),
         Function::new_scalar("substring", [Text, Integer, Integer], Text),
         Function::new_scalar("substring", [Text, Text], Text),
@@ -568,6 +671,9 @@ fn default_type_system() -> TypeSystem {
         Function::new_aggregate("string_agg", [Text, Text], Text),
         Function::new_aggregate("group_concat", [Text], Text),
         Function::new_aggregate("group_concat", [Text, Text], Text),
+        // - bool_and & bool_or
+        Function::new_aggregate("bool_and", [Boolean], Boolean),
+        Function::new_aggregate("bool_or", [Boolean], Boolean),
         // Windows.
         // - count
         // TODO: consider adding `any` type
@@ -649,7 +755,7 @@ fn coerce_scalar_expr(
     expr_id: NodeId,
     plan: &mut Plan,
 ) -> Result<(), SbroadError> {
-    // At the moment we only coerce string literals.
+    // At the moment we only coerce string literals and CASE results.
     fn collect_strings_to_be_coerced(
         expr_id: NodeId,
         plan: &Plan,
@@ -722,6 +828,149 @@ fn coerce_scalar_expr(
         }
     }
 
+    let cases = PostOrderWithFilter::with_capacity(
+        |node| plan.subtree_iter(node, false),
+        0,
+        Box::new(|id| matches!(plan.get_expression_node(id), Ok(Expression::Case(_)))),
+    )
+    .populate_nodes(expr_id);
+    for LevelNode(_, case_id) in cases {
+        coerce_case_results(report, case_id, plan)?;
+    }
+
+    let functions = PostOrderWithFilter::with_capacity(
+        |node| plan.subtree_iter(node, false),
+        0,
+        Box::new(|id| {
+            matches!(
+                plan.get_expression_node(id),
+                Ok(Expression::ScalarFunction(_))
+            )
+        }),
+    )
+    .populate_nodes(expr_id);
+    for LevelNode(_, func_id) in functions {
+        rename_overloaded_builtin(report, func_id, plan)?;
+    }
+
+    Ok(())
+}
+
+/// Some overloads of a function are served by separate builtins, e.g. SHA256
+/// of a bytea, TO_CHAR of a number or CEIL of a double. The overload depends on the argument types, which for parameters
+/// are known only after the analysis, so the function is renamed here.
+fn rename_overloaded_builtin(
+    report: &TypeReport,
+    func_id: NodeId,
+    plan: &mut Plan,
+) -> Result<(), SbroadError> {
+    let Expression::ScalarFunction(ScalarFunction { name, children, .. }) =
+        plan.get_expression_node(func_id)?
+    else {
+        return Ok(());
+    };
+    let builtin = match (name.as_str(), children.as_slice()) {
+        ("sha256", [arg_id]) => {
+            matches!(report.get_type(arg_id), Type::Bytea).then_some("_pico_sha256_bytea")
+        }
+        ("to_char", [arg_id, _]) => matches!(
+            report.get_type(arg_id),
+            Type::Integer | Type::Double | Type::Numeric
+        )
+        .then_some("_pico_to_char_number"),
+        ("ceil", [arg_id]) => {
+            matches!(report.get_type(arg_id), Type::Double).then_some("_pico_ceil_double")
+        }
+        ("floor", [arg_id]) => {
+            matches!(report.get_type(arg_id), Type::Double).then_some("_pico_floor_double")
+        }
+        ("round", [arg_id]) => {
+            matches!(report.get_type(arg_id), Type::Double).then_some("_pico_round_double")
+        }
+        _ => None,
+    };
+    let Some(builtin) = builtin else {
+        return Ok(());
+    };
+
+    let func_type = DerivedType::from(report.get_type(&func_id));
+    if let MutExpression::ScalarFunction(ScalarFunction {
+        name,
+        func_type: ty,
+        ..
+    }) = plan.get_mut_expression_node(func_id)?
+    {
+        *name = builtin.into();
+        *ty = func_type;
+    }
+    Ok(())
+}
+
+/// Cast CASE results to the common type chosen by the type analysis,
+/// e.g. `int` results are cast to `double` when another result is `double`.
+/// This way the column type is the same whichever branch is taken.
+fn coerce_case_results(
+    report: &TypeReport,
+    case_id: NodeId,
+    plan: &mut Plan,
+) -> Result<(), SbroadError> {
+    let Expression::Case(Case {
+        when_blocks,
+        else_expr,
+        ..
+    }) = plan.get_expression_node(case_id)?
+    else {
+        unreachable!("expected CASE expression");
+    };
+    let results: Vec<NodeId> = when_blocks
+        .iter()
+        .map(|(_, result)| *result)
+        .chain(*else_expr)
+        .collect();
+
+    let mut casts = Vec::new();
+    for result_id in results {
+        let Some(cast_type) = report.get_cast(&result_id) else {
+            continue;
+        };
+        let result = plan.get_expression_node(result_id)?;
+        // A cast would change the type deduced for the parameter.
+        if matches!(result, Expression::Parameter(_)) {
+            continue;
+        }
+        // NULL results fit any type.
+        let (Some(result_type), Some(cast_type)) = (
+            *result.calculate_type(plan)?.get(),
+            *DerivedType::from(cast_type).get(),
+        ) else {
+            continue;
+        };
+        if result_type != cast_type {
+            let cast_id = plan.add_cast(result_id, CastType::try_from(&cast_type)?)?;
+            casts.push((result_id, cast_id));
+        }
+    }
+    if casts.is_empty() {
+        return Ok(());
+    }
+
+    let MutExpression::Case(Case {
+        when_blocks,
+        else_expr,
+        ..
+    }) = plan.get_mut_expression_node(case_id)?
+    else {
+        unreachable!("expected CASE expression");
+    };
+    let results = when_blocks
+        .iter_mut()
+        .map(|(_, result)| result)
+        .chain(else_expr.iter_mut());
+    for result in results {
+        if let Some((_, cast_id)) = casts.iter().find(|(id, _)| *id == *result) {
+            *result = *cast_id;
+        }
+    }
     Ok(())
 }
 
@@ -830,3 +1079,126 @@ pub fn analyze_values_rows(
 
     Ok(())
 }
+
+/// Get the type both columns of the set operation can be coerced to.
+/// Similar to PostgreSQL, `int` is coerced to `double` and `decimal`, and `decimal`
+/// is coerced to `double`.
+fn set_operation_common_type(left: SbroadType, right: SbroadType) -> Option<SbroadType> {
+    use SbroadType::*;
+    match (left, right) {
+        _ if left == right => Some(left),
+        (Any, _) | (_, Any) => Some(Any),
+        (Integer, Double) | (Double, Integer) => Some(Double),
+        (Integer, Decimal) | (Decimal, Integer) => Some(Decimal),
+        (Double, Decimal) | (Decimal, Double) => Some(Double),
+        _ => None,
+    }
+}
+
+/// Whether the set operation column is a string literal. Like the literals
+/// of unknown type in PostgreSQL, it takes the type of the other branch.
+fn is_string_literal(plan: &Plan, col_id: NodeId) -> Result<bool, SbroadError> {
+    let Expression::Alias(Alias { child, .. }) = plan.get_expression_node(col_id)? else {
+        return Ok(false);
+    };
+    Ok(matches!(
+        plan.get_expression_node(*child)?,
+        Expression::Constant(Constant {
+            value: Value::String(_)
+        })
+    ))
+}
+
+/// Wrap the column at `position` of the set operation child into a cast to `ty`.
+/// Nested set operations are coerced through both of their children.
+fn coerce_set_operation_column(
+    plan: &mut Plan,
+    rel_id: NodeId,
+    position: usize,
+    ty: SbroadType,
+) -> Result<(), SbroadError> {
+    let output_id = plan.get_relational_output(rel_id)?;
+    if let Relational::Union(Union { left, right, .. })
+    | Relational::UnionAll(UnionAll { left, right, .. })
+    | Relational::Except(Except { left, right, .. })
+    | Relational::Intersect(Intersect { left, right, .. }) = plan.get_relation_node(rel_id)?
+    {
+        let (left, right) = (*left, *right);
+        coerce_set_operation_column(plan, left, position, ty)?;
+        coerce_set_operation_column(plan, right, position, ty)?;
+        return plan.update_reference_types_in_subtree(output_id);
+    }
+
+    let alias_id = plan.get_row_list(output_id)?[position];
+    let expr_id = plan.get_child_under_alias(alias_id)?;
+    let expr = plan.get_expression_node(expr_id)?;
+    // A cast would change the type deduced for the parameter.
+    if matches!(expr, Expression::Parameter(_)) {
+        return Ok(());
+    }
+    if *expr.calculate_type(plan)?.get() == Some(ty) {
+        return Ok(());
+    }
+    let cast_id = plan.add_cast(expr_id, CastType::try_from(&ty)?)?;
+    if let MutExpression::Alias(Alias { child, .. }) = plan.get_mut_expression_node(alias_id)? {
+        *child = cast_id;
+    }
+    Ok(())
+}
+
+/// Coerce the columns of the set operation (UNION, EXCEPT, INTERSECT) children
+/// to their common types, so that `SELECT 1 UNION SELECT 2.5` yields a decimal column.
+pub fn coerce_set_operation_columns(
+    plan: &mut Plan,
+    left: NodeId,
+    right: NodeId,
+    ctx: &'static str,
+) -> Result<(), SbroadError> {
+    let left_columns = plan
+        .get_row_list(plan.get_relational_output(left)?)?
+        .clone();
+    let right_columns = plan
+        .get_row_list(plan.get_relational_output(right)?)?
+        .clone();
+    // Mismatching number of columns is reported by the set operation itself.
+    if left_columns.len() != right_columns.len() {
+        return Ok(());
+    }
+
+    for (position, (left_col, right_col)) in left_columns.iter().zip(&right_columns).enumerate() {
+        let left_type = *plan
+            .get_expression_node(*left_col)?
+            .calculate_type(plan)?
+            .get();
+        let right_type = *plan
+            .get_expression_node(*right_col)?
+            .calculate_type(plan)?
+            .get();
+        let common_type = match (left_type, right_type) {
+            (Some(ty), Some(SbroadType::String)) if is_string_literal(plan, *right_col)? => ty,
+            (Some(SbroadType::String), Some(ty)) if is_string_literal(plan, *left_col)? => ty,
+            (Some(left_type), Some(right_type)) => set_operation_common_type(left_type, right_type)
+                .ok_or_else(|| {
+                    let name = match plan.get_expression_node(*left_col) {
+                        Ok(Expression::Alias(Alias { name, .. })) => name.clone(),
+                        _ => SmolStr::default(),
+                    };
+                    TypeError::ColumnTypesCannotBeMatched(ctx, name, left_type, right_type)
+                })?,
+            // NULL columns fit any type.
+            (Some(ty), None) | (None, Some(ty)) => ty,
+            (None, None) => continue,
+        };
+        // Columns of these types can't be cast, leave them as is.
+        if matches!(
+            common_type,
+            SbroadType::Any | SbroadType::Map | SbroadType::Array
+        ) {
+            continue;
+        }
+        coerce_set_operation_column(plan, left, position, common_type)?;
+        coerce_set_operation_column(plan, right, position, common_type)?;
+    }
+
+    Ok(())
+}
@@ -505,6 +505,13 @@ fn cast() {
     assert_ok("SELECT 1::numeric");
     assert_ok("SELECT '2024-03-11 12:33:14 UTC'::datetime");
     assert_ok("SELECT CAST('11111111-1111-1111-1111-111111111111' AS UUID)");
+    assert_ok("SELECT '2024-03-11 12:33:14'::timestamp");
+    assert_ok("SELECT '2024-03-11 12:33:14+03'::timestamptz");
+    assert_ok("SELECT CAST('2024-03-11' AS TIMESTAMP WITH TIME ZONE)");
+    assert_ok("SELECT CAST('2024-03-11' AS TIMESTAMP WITHOUT TIME ZONE)");
+    assert_ok("SELECT '2024-03-11 12:33:14'::date");
+    assert_ok("SELECT '12:33:14'::time");
+    assert_ok("SELECT CAST('12:33:14' AS TIME WITHOUT TIME ZONE) < '13:00'::time");
 
     assert_ok("SELECT $1::int");
     assert_ok("SELECT $1::double");
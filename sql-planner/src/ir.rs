@@ -14,10 +14,11 @@ use serde::{Deserialize, Serialize};
 use smol_str::{format_smolstr, SmolStr, ToSmolStr};
 use std::cell::{RefCell, RefMut};
 use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 use std::rc::Rc;
 use std::slice::{Iter, IterMut};
 use tree::traversal::LevelNode;
-use types::UnrestrictedType;
+use types::{DerivedType, UnrestrictedType};
 
 use self::relation::Relations;
 use self::transformation::redistribution::MotionPolicy;
@@ -29,10 +30,10 @@ use crate::ir::index::Indexes;
 use crate::ir::node::plugin::{MutPlugin, Plugin};
 use crate::ir::node::tcl::Tcl;
 use crate::ir::node::{
-    Alias, ArenaType, ArithmeticExpr, BoolExpr, Case, Cast, Concat, Constant, GroupBy, Having,
-    IndexExpr, Limit, Motion, MutNode, Node, Node136, Node232, Node32, Node64, Node96, NodeId,
-    NodeOwned, OrderBy, Projection, Reference, Row, ScalarFunction, ScanRelation, Selection,
-    SubQueryReference, Trim, UnaryExpr,
+    Alias, ArenaType, ArithmeticExpr, BoolExpr, Case, Cast, Concat, Constant, CreateTable, GroupBy,
+    Having, IndexExpr, Limit, Motion, MutNode, Node, Node136, Node232, Node32, Node64, Node96,
+    NodeId, NodeOwned, OrderBy, Projection, Reference, Row, ScalarFunction, ScanRelation,
+    Selection, SubQueryReference, Trim, UnaryExpr,
 };
 use crate::ir::operator::{Bool, OrderByEntity};
 use crate::ir::relation::Column;
@@ -56,16 +57,28 @@ pub mod index;
 pub mod node;
 pub mod operator;
 pub mod options;
+pub mod point_get;
 pub mod relation;
 pub mod sharding_key;
 pub mod transformation;
 pub mod tree;
 pub mod types;
 pub mod undo;
+pub mod validate;
 pub mod value;
 
 pub const DEFAULT_MAX_NUMBER_OF_CASTS: usize = 10;
 
+/// Maximum nesting depth of the expression tree built from the query text.
+/// Deeper trees would overflow the stack in the recursive routines
+/// working with expressions.
+pub const DEFAULT_MAX_EXPRESSION_DEPTH: usize = 10_000;
+
+/// Maximum nesting depth of brackets and prefix operators in the query text.
+/// The parser descends recursively into every such level and spends several
+/// stack frames on it, so this bound is much tighter than the one above.
+pub const MAX_PARSER_NESTING_DEPTH: usize = 128;
+
 /// Plan nodes storage.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub struct Nodes {
@@ -93,7 +106,6 @@ impl Nodes {
                 Node32::Except(except) => Node::Relational(Relational::Except(except)),
                 Node32::Intersect(intersect) => Node::Relational(Relational::Intersect(intersect)),
                 Node32::Invalid(inv) => Node::Invalid(inv),
-                Node32::Limit(limit) => Node::Relational(Relational::Limit(limit)),
                 Node32::SelectWithoutScan(select) => {
                     Node::Relational(Relational::SelectWithoutScan(select))
                 }
@@ -103,10 +115,13 @@ impl Nodes {
                 Node32::UnionAll(union_all) => Node::Relational(Relational::UnionAll(union_all)),
                 Node32::Values(values) => Node::Relational(Relational::Values(values)),
                 Node32::Deallocate(deallocate) => Node::Deallocate(deallocate),
-                Node32::Tcl(tcl) => match *tcl {
+                Node32::Tcl(tcl) => match tcl {
                     Tcl::Begin => Node::Tcl(Tcl::Begin),
                     Tcl::Commit => Node::Tcl(Tcl::Commit),
                     Tcl::Rollback => Node::Tcl(Tcl::Rollback),
+                    Tcl::Savepoint(name) => Node::Tcl(Tcl::Savepoint(name.clone())),
+                    Tcl::Release(name) => Node::Tcl(Tcl::Release(name.clone())),
+                    Tcl::RollbackTo(name) => Node::Tcl(Tcl::RollbackTo(name.clone())),
                 },
                 Node32::CreateSchema => Node::Ddl(Ddl::CreateSchema),
                 Node32::DropSchema => Node::Ddl(Ddl::DropSchema),
@@ -121,6 +136,7 @@ impl Nodes {
             ArenaType::Arena64 => self.arena64.get(id.offset as usize).map(|node| match node {
                 Node64::AnonymousBlock(block) => Node::Block(Block::Anonymous(block)),
                 Node64::Over(over) => Node::Expression(Expression::Over(over)),
+                Node64::Limit(limit) => Node::Relational(Relational::Limit(limit)),
                 Node64::Case(case) => Node::Expression(Expression::Case(case)),
                 Node64::Invalid(invalid) => Node::Invalid(invalid),
                 Node64::CreateRole(create_role) => Node::Acl(Acl::CreateRole(create_role)),
@@ -194,6 +210,10 @@ impl Nodes {
                         Node::Plugin(Plugin::ChangeConfig(change_config))
                     }
                     Node136::Window(window) => Node::Expression(Expression::Window(window)),
+                    Node136::Comment(comment) => Node::Ddl(Ddl::Comment(comment)),
+                    Node136::Notification(notification) => {
+                        Node::Ddl(Ddl::Notification(notification))
+                    }
                 }),
             ArenaType::Arena232 => self
                 .arena224
@@ -224,7 +244,6 @@ impl Nodes {
                         MutNode::Expression(MutExpression::Arithmetic(arithm))
                     }
                     Node32::Bool(bool) => MutNode::Expression(MutExpression::Bool(bool)),
-                    Node32::Limit(limit) => MutNode::Relational(MutRelational::Limit(limit)),
                     Node32::Concat(concat) => MutNode::Expression(MutExpression::Concat(concat)),
                     Node32::Index(index) => MutNode::Expression(MutExpression::Index(index)),
                     Node32::Cast(cast) => MutNode::Expression(MutExpression::Cast(cast)),
@@ -248,10 +267,13 @@ impl Nodes {
                     }
                     Node32::Values(values) => MutNode::Relational(MutRelational::Values(values)),
                     Node32::Deallocate(deallocate) => MutNode::Deallocate(deallocate),
-                    Node32::Tcl(tcl) => match *tcl {
+                    Node32::Tcl(tcl) => match tcl {
                         Tcl::Begin => MutNode::Tcl(node::tcl::Tcl::Begin),
                         Tcl::Commit => MutNode::Tcl(node::tcl::Tcl::Commit),
                         Tcl::Rollback => MutNode::Tcl(node::tcl::Tcl::Rollback),
+                        Tcl::Savepoint(name) => MutNode::Tcl(Tcl::Savepoint(name.clone())),
+                        Tcl::Release(name) => MutNode::Tcl(Tcl::Release(name.clone())),
+                        Tcl::RollbackTo(name) => MutNode::Tcl(Tcl::RollbackTo(name.clone())),
                     },
                     Node32::CreateSchema => MutNode::Ddl(MutDdl::CreateSchema),
                     Node32::DropSchema => MutNode::Ddl(MutDdl::DropSchema),
@@ -273,6 +295,7 @@ impl Nodes {
                 .map(|node| match node {
                     Node64::AnonymousBlock(block) => MutNode::Block(MutBlock::Anonymous(block)),
                     Node64::Over(over) => MutNode::Expression(MutExpression::Over(over)),
+                    Node64::Limit(limit) => MutNode::Relational(MutRelational::Limit(limit)),
                     Node64::Case(case) => MutNode::Expression(MutExpression::Case(case)),
                     Node64::Invalid(invalid) => MutNode::Invalid(invalid),
                     Node64::CreateRole(create_role) => {
@@ -383,6 +406,10 @@ impl Nodes {
                         Node136::Window(window) => {
                             MutNode::Expression(MutExpression::Window(window))
                         }
+                        Node136::Comment(comment) => MutNode::Ddl(MutDdl::Comment(comment)),
+                        Node136::Notification(notification) => {
+                            MutNode::Ddl(MutDdl::Notification(notification))
+                        }
                     })
             }
             ArenaType::Arena232 => {
@@ -631,6 +658,36 @@ impl Slices {
     }
 }
 
+/// `SELECT ... INTO table` creating a table from the query result.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelectInto {
+    /// Table to create.
+    pub create_table: CreateTable,
+    /// `INSERT INTO table (columns) SELECT ...` statement filling the
+    /// created table. It takes the same parameters as the query.
+    pub insert: SmolStr,
+    /// Parameter values of the query, set when it is bound.
+    pub params: Vec<Value>,
+}
+
+/// `WHERE CURRENT OF cursor` clause of UPDATE or DELETE.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CurrentOf {
+    /// Name of the cursor positioned on the row to modify.
+    pub cursor: SmolStr,
+    /// Byte range of the clause in the query text.
+    pub span: Range<usize>,
+}
+
+/// Strength of the row locks requested with `SELECT ... FOR UPDATE/FOR SHARE`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RowLockMode {
+    /// `FOR SHARE`: other transactions can lock the rows in the same mode.
+    Share,
+    /// `FOR UPDATE`: the rows are locked exclusively.
+    Update,
+}
+
 /// Logical plan tree structure.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub struct Plan {
@@ -684,6 +741,31 @@ pub struct Plan {
     /// global tables use `None`.
     #[serde(skip)]
     pub tier: Option<SmolStr>,
+    /// Table to create and fill with the query result
+    /// (`SELECT ... INTO`), the plan itself is the query.
+    #[serde(skip)]
+    pub select_into: Option<Box<SelectInto>>,
+    /// Cursor whose current row is the only row to modify
+    /// (`UPDATE/DELETE ... WHERE CURRENT OF`). The statement
+    /// has no filter until the row is resolved.
+    #[serde(skip)]
+    pub current_of: Option<CurrentOf>,
+    /// Row locks requested with `FOR UPDATE` or `FOR SHARE`. They are
+    /// taken only in the transaction blocks of the PostgreSQL protocol.
+    #[serde(skip)]
+    pub row_lock: Option<RowLockMode>,
+    /// Maximum nesting depth of the expression trees, taken from the
+    /// metadata. The transformations expanding the trees check it.
+    #[serde(skip)]
+    pub max_expression_depth: Option<usize>,
+    /// Fingerprint of the query shape, written to the SQL log
+    /// and passed to the dispatch hook as `query_id`.
+    #[serde(skip)]
+    pub query_fingerprint: Option<SmolStr>,
+    /// The plan may be served by a point get, so its optimization is
+    /// deferred until the parameters are bound (see `Plan::point_get`).
+    #[serde(skip)]
+    pub optimization_deferred: bool,
     /// Plan id stored for each motion subtree.
     /// Valid only for the original plan.
     /// Check out `materialize_motion` for more.
@@ -759,6 +841,8 @@ impl Default for Plan {
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub enum ExplainType {
     Explain,
+    /// Explain with estimated row counts and costs of the plan nodes.
+    ExplainCosts,
     ExplainQueryPlan,
     ExplainQueryPlanFmt,
 }
@@ -877,6 +961,12 @@ impl Plan {
             index_version_map: HashMap::with_hasher(RepeatableState),
             context: Some(RefCell::new(BuildContext::default())),
             tier: None,
+            select_into: None,
+            current_of: None,
+            row_lock: None,
+            max_expression_depth: None,
+            query_fingerprint: None,
+            optimization_deferred: false,
             plan_id_cache: Rc::new(RefCell::new(AHashMap::new())),
         }
     }
@@ -1158,6 +1248,28 @@ impl Plan {
         self.nodes.add_bool(left, op, right)
     }
 
+    /// Add comparison node with an explicit collation to the plan.
+    ///
+    /// # Errors
+    /// Returns `SbroadError` when the condition node can't append'.
+    pub fn add_collated_cond(
+        &mut self,
+        left: NodeId,
+        op: operator::Bool,
+        right: NodeId,
+        collation: Option<operator::Collation>,
+    ) -> Result<NodeId, SbroadError> {
+        let cond_id = self.nodes.add_bool(left, op, right)?;
+        if let MutExpression::Bool(BoolExpr {
+            collation: cond_collation,
+            ..
+        }) = self.get_mut_expression_node(cond_id)?
+        {
+            *cond_collation = collation;
+        }
+        Ok(cond_id)
+    }
+
     /// Add Like operator to the plan.
     ///
     /// # Errors
@@ -1256,7 +1368,16 @@ impl Plan {
     /// Checks that plan is explain query
     #[must_use]
     pub fn is_plain_explain(&self) -> bool {
-        self.explain_type == Some(ExplainType::Explain)
+        matches!(
+            self.explain_type,
+            Some(ExplainType::Explain | ExplainType::ExplainCosts)
+        )
+    }
+
+    /// Checks that plan is explain(costs) query
+    #[must_use]
+    pub fn is_costs_explain(&self) -> bool {
+        self.explain_type == Some(ExplainType::ExplainCosts)
     }
 
     /// Checks that plan is explain(raw, fmt) query
@@ -1410,6 +1531,110 @@ impl Plan {
             && !self.is_block()?)
     }
 
+    /// Checks that plan is a DQL query over a single scan of the table
+    /// without joins, aggregation, subqueries and other operators except
+    /// filtering. Every row of such a query comes from a table row, so
+    /// the row can be located again (e.g. for `WHERE CURRENT OF`).
+    ///
+    /// # Errors
+    /// - top node doesn't exist in the plan or is invalid.
+    pub fn is_simple_scan_of(&self, table: &str) -> Result<bool, SbroadError> {
+        if !self.is_dql()? {
+            return Ok(false);
+        }
+        let mut scans = 0;
+        let dfs = PostOrder::with_capacity(|x| self.nodes.rel_iter(x), REL_CAPACITY);
+        for LevelNode(_, id) in dfs.into_iter(self.get_top()?) {
+            match self.get_relation_node(id)? {
+                Relational::ScanRelation(ScanRelation { relation, .. }) if relation == table => {
+                    scans += 1;
+                }
+                Relational::Selection(_) => {}
+                Relational::Projection(Projection {
+                    windows,
+                    is_distinct: false,
+                    group_by: None,
+                    having: None,
+                    ..
+                }) if windows.is_empty() => {}
+                _ => return Ok(false),
+            }
+        }
+        Ok(scans == 1)
+    }
+
+    /// Get the positions of the top output columns returning the given
+    /// columns of the table as they are, if the plan is a simple scan of
+    /// the table (see [`Plan::is_simple_scan_of`]). A column is located
+    /// only by a plain reference, so neither an expression over it nor
+    /// an alias of another column can be taken for it.
+    ///
+    /// # Errors
+    /// - top node doesn't exist in the plan or is invalid.
+    pub fn simple_scan_positions(
+        &self,
+        table: &str,
+        columns: &[usize],
+    ) -> Result<Option<Vec<Option<usize>>>, SbroadError> {
+        if !self.is_simple_scan_of(table)? {
+            return Ok(None);
+        }
+        // The output of the scan and of the selection over it follows
+        // the columns of the table, so a reference position is a column one.
+        let output = self.get_relational_output(self.get_top()?)?;
+        let mut referred = Vec::new();
+        for alias_id in self.get_row_list(output)? {
+            let child_id = self.get_child_under_alias(*alias_id)?;
+            referred.push(match self.get_expression_node(child_id)? {
+                Expression::Reference(Reference { position, .. }) => Some(*position),
+                _ => None,
+            });
+        }
+        let positions = columns
+            .iter()
+            .map(|column| referred.iter().position(|r| *r == Some(*column)))
+            .collect();
+        Ok(Some(positions))
+    }
+
+    /// Build the plan returning the primary key and the bucket id of each
+    /// row of a simple scan of a sharded table (see [`Plan::is_simple_scan_of`]),
+    /// the bucket id follows the primary key columns. It is used to lock the
+    /// rows selected with `FOR UPDATE` or `FOR SHARE`. For the other plans
+    /// `None` is returned.
+    ///
+    /// # Errors
+    /// - top node doesn't exist in the plan or is invalid.
+    pub fn row_keys_plan(&self) -> Result<Option<(Plan, &Table)>, SbroadError> {
+        let mut tables = self.relations.tables.values();
+        let (Some(table), None) = (tables.next(), tables.next()) else {
+            return Ok(None);
+        };
+        if table.is_global() || !self.is_simple_scan_of(&table.name)? {
+            return Ok(None);
+        }
+        let Some(bucket_id) = table.get_bucket_id_position()? else {
+            return Ok(None);
+        };
+        let top_id = self.get_top()?;
+        let Relational::Projection(Projection { children, .. }) = self.get_relation_node(top_id)?
+        else {
+            return Ok(None);
+        };
+        let child_id = children[0];
+
+        let mut plan = self.clone();
+        // The output of the scan and of the selection over it follows
+        // the columns of the table.
+        let mut positions = table.primary_key.positions.clone();
+        positions.push(bucket_id);
+        let output = plan.add_row_by_indices(child_id, positions, true, None)?;
+        plan.set_distribution(output)?;
+        *plan.get_mut_relation_node(top_id)?.mut_output() = output;
+        plan.row_lock = None;
+        Ok(Some((plan, table)))
+    }
+
     /// Set top node of plan
     /// # Errors
     /// - top node doesn't exist in the plan.
@@ -1978,6 +2203,48 @@ impl Plan {
         ))
     }
 
+    /// Aliases of the output columns of the plan top along with their names.
+    fn output_aliases(&self) -> Result<Vec<(&SmolStr, Expression<'_>)>, SbroadError> {
+        let top_id = self.get_top()?;
+        let output_id = self.get_relation_node(top_id)?.output();
+        let columns = self.get_row_list(output_id)?;
+        let mut aliases = Vec::with_capacity(columns.len());
+        for col_id in columns {
+            let column = self.get_expression_node(*col_id)?;
+            let Expression::Alias(Alias { name, .. }) = column else {
+                return Err(SbroadError::Invalid(
+                    Entity::Expression,
+                    Some(format_smolstr!("expected alias, got {column:?}")),
+                ));
+            };
+            aliases.push((name, column));
+        }
+        Ok(aliases)
+    }
+
+    /// Names of the output columns of the plan top.
+    ///
+    /// # Errors
+    /// - the plan top is not set or is not a relational node
+    /// - an output column is not an alias
+    pub fn output_column_names(&self) -> Result<Vec<SmolStr>, SbroadError> {
+        let aliases = self.output_aliases()?;
+        Ok(aliases.into_iter().map(|(name, _)| name.clone()).collect())
+    }
+
+    /// Names and types of the output columns of the plan top.
+    ///
+    /// # Errors
+    /// - the plan top is not set or is not a relational node
+    /// - an output column is not an alias
+    /// - failed to calculate the type of a column
+    pub fn output_columns_with_types(&self) -> Result<Vec<(SmolStr, DerivedType)>, SbroadError> {
+        self.output_aliases()?
+            .into_iter()
+            .map(|(name, column)| Ok((name.clone(), column.calculate_type(self)?)))
+            .collect()
+    }
+
     /// Find whether we should cover the child expression with parentheses.
     /// For a pair of parent and child expression by default we cover
     /// all children with parentheses in order to save info about precedence
@@ -3,8 +3,9 @@ use smol_str::{format_smolstr, ToSmolStr};
 
 use crate::errors::{Entity, SbroadError};
 use crate::ir::helpers::RepeatableState;
-use crate::ir::node::{NodeId, ReferenceTarget, ScalarFunction};
-use crate::ir::operator::Arithmetic;
+use crate::ir::node::{Cast, NodeId, Reference, ReferenceTarget, ScalarFunction};
+use crate::ir::operator::{Arithmetic, Collation, NullsOrder, OrderByType};
+use crate::ir::transformation::redistribution::ColumnPosition;
 use crate::ir::types::{CastType, UnrestrictedType as RelType};
 use crate::ir::Plan;
 use std::collections::{HashMap, HashSet};
@@ -34,6 +35,8 @@ pub enum AggregateKind {
     MIN,
     MAX,
     GRCONCAT,
+    BOOLAND,
+    BOOLOR,
 }
 
 impl Display for AggregateKind {
@@ -46,6 +49,8 @@ impl Display for AggregateKind {
             AggregateKind::MIN => "min",
             AggregateKind::MAX => "max",
             AggregateKind::GRCONCAT => "group_concat",
+            AggregateKind::BOOLAND => "bool_and",
+            AggregateKind::BOOLOR => "bool_or",
         };
         write!(f, "{name}")
     }
@@ -64,6 +69,8 @@ impl AggregateKind {
             "min" => AggregateKind::MIN,
             "max" => AggregateKind::MAX,
             "group_concat" | "string_agg" => AggregateKind::GRCONCAT,
+            "bool_and" => AggregateKind::BOOLAND,
+            "bool_or" => AggregateKind::BOOLOR,
             _ => return None,
         };
         Some(kind)
@@ -76,6 +83,7 @@ impl AggregateKind {
                 AggregateKind::COUNT => RelType::Integer,
                 AggregateKind::TOTAL => RelType::Double,
                 AggregateKind::GRCONCAT => RelType::String,
+                AggregateKind::BOOLAND | AggregateKind::BOOLOR => RelType::Boolean,
                 AggregateKind::SUM | AggregateKind::AVG => RelType::Decimal,
                 AggregateKind::MIN | AggregateKind::MAX => {
                     let child_node = args.first().ok_or(SbroadError::UnexpectedNumberOfValues(
@@ -101,6 +109,8 @@ impl AggregateKind {
             AggregateKind::MIN => vec![AggregateKind::MIN],
             AggregateKind::MAX => vec![AggregateKind::MAX],
             AggregateKind::GRCONCAT => vec![AggregateKind::GRCONCAT],
+            AggregateKind::BOOLAND => vec![AggregateKind::BOOLAND],
+            AggregateKind::BOOLOR => vec![AggregateKind::BOOLOR],
         }
     }
 
@@ -137,6 +147,8 @@ impl AggregateKind {
             (AggregateKind::MIN, AggregateKind::MIN) => AggregateKind::MIN,
             (AggregateKind::MAX, AggregateKind::MAX) => AggregateKind::MAX,
             (AggregateKind::GRCONCAT, AggregateKind::GRCONCAT) => AggregateKind::GRCONCAT,
+            (AggregateKind::BOOLAND, AggregateKind::BOOLAND) => AggregateKind::BOOLAND,
+            (AggregateKind::BOOLOR, AggregateKind::BOOLOR) => AggregateKind::BOOLOR,
             (_, _) => {
                 return Err(SbroadError::Invalid(
                     Entity::Aggregate,
@@ -153,6 +165,10 @@ impl AggregateKind {
 /// Pair of (aggregate kind, its position in the output).
 pub(crate) type PositionKind = (Position, AggregateKind);
 
+/// Sort key of the ordered aggregates: position of the key column,
+/// order direction, placement of `NULL`s and collation.
+pub(crate) type OrderedAggregatesKey = (ColumnPosition, OrderByType, NullsOrder, Option<Collation>);
+
 /// Metadata about aggregates.
 #[derive(Clone, Debug)]
 pub struct Aggregate {
@@ -510,6 +526,58 @@ impl Plan {
         Ok(aggrs)
     }
 
+    /// Get the sort key of the ordered aggregates (`string_agg(a, ',' order by a)`)
+    /// from the final `Projection` and `Having` nodes.
+    ///
+    /// Returns the position of the aggregated column in the output of the
+    /// relational node under the aggregating nodes (`GroupBy` and `Having`
+    /// just pass their child's columns through), the order direction, the
+    /// placement of `NULL`s and the collation of the key.
+    /// Returns `None` if there are no ordered aggregates.
+    pub(crate) fn get_ordered_aggregates_key(
+        &self,
+        proj: NodeId,
+        having: Option<NodeId>,
+    ) -> Result<Option<OrderedAggregatesKey>, SbroadError> {
+        let mut sort_key: Option<OrderedAggregatesKey> = None;
+        for aggr in self.collect_aggregates(proj, having)? {
+            let Expression::ScalarFunction(ScalarFunction {
+                children,
+                feature: Some(FunctionFeature::OrderBy(order_type, nulls, collation)),
+                ..
+            }) = self.get_expression_node(aggr.fun_id)?
+            else {
+                continue;
+            };
+            let mut key_id = *children
+                .first()
+                .expect("ordered aggregate must have arguments");
+            while let Expression::Cast(Cast { child, .. }) = self.get_expression_node(key_id)? {
+                key_id = *child;
+            }
+            let Expression::Reference(Reference { position, .. }) =
+                self.get_expression_node(key_id)?
+            else {
+                return Err(SbroadError::NotImplemented(
+                    Entity::Aggregate,
+                    "ORDER BY over non-column expression".into(),
+                ));
+            };
+            let key = (*position, order_type.clone(), *nulls, *collation);
+            match &sort_key {
+                Some(sort_key) if *sort_key != key => {
+                    return Err(SbroadError::NotImplemented(
+                        Entity::Aggregate,
+                        "with different ORDER BY keys in one query".into(),
+                    ));
+                }
+                Some(_) => {}
+                None => sort_key = Some(key),
+            }
+        }
+        Ok(sort_key)
+    }
+
     pub fn create_local_aggregate(
         &mut self,
         kind: AggregateKind,
@@ -1,12 +1,15 @@
 use crate::errors::{Entity, SbroadError};
+use crate::frontend::sql::ir::SubtreeCloner;
 use crate::frontend::sql::is_negative_number;
 use crate::ir::expression::{FunctionFeature, Substring};
 use crate::ir::node::expression::{Expression, MutExpression};
 use crate::ir::node::relational::Relational;
 use crate::ir::node::{
-    Alias, Constant, MutNode, Node96, NodeId, Parameter, ScalarFunction, Timestamp, ValuesRow,
+    Alias, BoolExpr, Constant, Like, MutNode, Node96, NodeId, Parameter, Row, ScalarFunction,
+    Timestamp, ValuesRow,
 };
 use crate::ir::node::{Node32, TimeParameters};
+use crate::ir::operator::Bool;
 use crate::ir::tree::traversal::{LevelNode, PostOrder, PostOrderWithFilter, EXPR_CAPACITY};
 use crate::ir::types::{DerivedType, UnrestrictedType};
 use crate::ir::value::Value;
@@ -31,6 +34,173 @@ fn calculate_max_parameter_index(plan: &Plan) -> Result<usize, SbroadError> {
     Ok(max_index)
 }
 
+/// Expand arrays bound to the IN-list parameters (`a IN ($1)`) into lists of constants.
+/// Returns `true` if any IN-list was expanded.
+fn bind_in_list_params(plan: &mut Plan, values: &[Value]) -> Result<bool, SbroadError> {
+    let rows: Vec<NodeId> = plan
+        .nodes
+        .iter32()
+        .filter_map(|node| match node {
+            Node32::Bool(BoolExpr {
+                op: Bool::In,
+                right,
+                ..
+            }) => Some(*right),
+            _ => None,
+        })
+        .collect();
+
+    let mut expanded = false;
+    for row_id in rows {
+        let Some(index) = plan.get_in_list_array_param(row_id)? else {
+            continue;
+        };
+        let Some(Value::Tuple(array)) = values.get((index - 1) as usize) else {
+            continue;
+        };
+        if array.0.is_empty() {
+            return Err(SbroadError::Invalid(
+                Entity::Query,
+                Some(format_smolstr!(
+                    "array bound to IN-list parameter ${index} must not be empty"
+                )),
+            ));
+        }
+        let list: Vec<NodeId> = array
+            .0
+            .iter()
+            .map(|value| plan.add_const(value.clone()))
+            .collect();
+        *plan.get_mut_expression_node(row_id)?.get_row_list_mut()? = list;
+        expanded = true;
+    }
+
+    Ok(expanded)
+}
+
+/// Expand arrays bound to the LIKE ANY parameters (`a LIKE ANY ($1)`) into
+/// disjunctions of LIKE checks, the same as for a list of patterns.
+///
+/// Such LIKE keeps the bound array in a row, optionally wrapped into `lower`
+/// for ILIKE ANY, so it must be called after the parameters are bound.
+fn bind_like_any_params(plan: &mut Plan) -> Result<(), SbroadError> {
+    let mut like_ids = Vec::new();
+    for (offset, node) in plan.nodes.iter32().enumerate() {
+        if let Node32::Like(Like { right, .. }) = node {
+            if plan.is_row(*right)? {
+                like_ids.push(NodeId {
+                    offset: u32::try_from(offset).unwrap(),
+                    arena_type: ArenaType::Arena32,
+                });
+            }
+        }
+    }
+
+    for like_id in like_ids {
+        let Expression::Like(Like {
+            left,
+            right,
+            escape,
+        }) = plan.get_expression_node(like_id)?
+        else {
+            return Err(SbroadError::Invalid(
+                Entity::Expression,
+                Some(format_smolstr!("expected LIKE node with id {like_id:?}")),
+            ));
+        };
+        let (left_id, escape_id) = (*left, *escape);
+        let [pattern_id] = plan.get_row_list(*right)? else {
+            return Err(SbroadError::Invalid(
+                Entity::Expression,
+                Some("expected a single array in LIKE ANY".into()),
+            ));
+        };
+        let (lower, array_id) = match plan.get_expression_node(*pattern_id)? {
+            Expression::ScalarFunction(lower) => {
+                let [array_id] = lower.children.as_slice() else {
+                    return Err(SbroadError::Invalid(
+                        Entity::Expression,
+                        Some("expected a single argument of lower in ILIKE ANY".into()),
+                    ));
+                };
+                (Some(lower.clone()), *array_id)
+            }
+            _ => (None, *pattern_id),
+        };
+        let Expression::Constant(Constant { value }) = plan.get_expression_node(array_id)? else {
+            return Err(SbroadError::Invalid(
+                Entity::Expression,
+                Some("expected a bound array in LIKE ANY".into()),
+            ));
+        };
+        let patterns = match value {
+            Value::Tuple(array) => array.0.clone(),
+            value => {
+                return Err(SbroadError::Invalid(
+                    Entity::Query,
+                    Some(format_smolstr!(
+                        "LIKE ANY expects an array of patterns, got {value}"
+                    )),
+                ));
+            }
+        };
+
+        let mut likes = Vec::with_capacity(patterns.len());
+        for (i, pattern) in patterns.into_iter().enumerate() {
+            // Every comparison needs its own copy of the shared operands.
+            let (like_left_id, like_escape_id) = if i == 0 {
+                (left_id, escape_id)
+            } else {
+                (
+                    SubtreeCloner::clone_subtree(plan, left_id)?,
+                    SubtreeCloner::clone_subtree(plan, escape_id)?,
+                )
+            };
+            let mut pattern_id = plan.add_const(pattern);
+            if let Some(lower) = &lower {
+                let lower = ScalarFunction {
+                    children: vec![pattern_id],
+                    ..lower.clone()
+                };
+                pattern_id = plan.nodes.push(lower.into());
+            }
+            likes.push(Like {
+                left: like_left_id,
+                right: pattern_id,
+                escape: like_escape_id,
+            });
+        }
+
+        // An empty array yields FALSE, the LIKE node is replaced in place.
+        let Some(last_like) = likes.pop() else {
+            let value = Value::Boolean(false);
+            plan.nodes
+                .replace32(like_id, Node32::Constant(Constant { value }))?;
+            continue;
+        };
+        let mut disjunction_id = None;
+        for like in likes {
+            let next_id = plan.nodes.push(like.into());
+            disjunction_id = Some(match disjunction_id {
+                Some(prev_id) => plan.add_cond(prev_id, Bool::Or, next_id)?,
+                None => next_id,
+            });
+        }
+        let node = match disjunction_id {
+            Some(prev_id) => Node32::Bool(BoolExpr {
+                left: prev_id,
+                op: Bool::Or,
+                right: plan.nodes.push(last_like.into()),
+                collation: None,
+            }),
+            None => Node32::Like(last_like),
+        };
+        plan.nodes.replace32(like_id, node)?;
+    }
+
+    Ok(())
+}
+
 /// Replace parameters in the plan.
 fn bind_params(plan: &mut Plan, mut values: Vec<Value>) -> Result<(), SbroadError> {
     for node in plan.nodes.iter32_mut() {
@@ -49,7 +219,44 @@ fn bind_params(plan: &mut Plan, mut values: Vec<Value>) -> Result<(), SbroadErro
     Ok(())
 }
 
+/// Current local time with the UTC offset of the local timezone.
+pub fn local_now() -> OffsetDateTime {
+    // we use `time` crate to represent time, but it has trouble determining UTC offset on linux
+    // because it relies on libc and libc API is unsound in presence of more than one thread (fun™ :/)
+    // so we utilize `chrono` to get the actual local time and then convert it to `time::OffsetDateTime`
+    // (chrono reimplements the part of libc that parses the timezone database, so it doesn't have the issue)
+
+    let time = chrono::offset::Local::now().fixed_offset();
+    OffsetDateTime::from_unix_timestamp_nanos(time.timestamp_nanos_opt().unwrap() as i128)
+        .unwrap()
+        .to_offset(time::UtcOffset::from_whole_seconds(time.offset().local_minus_utc()).unwrap())
+}
+
 impl Plan {
+    /// Get the index of the array parameter the IN-list consists of (`a IN ($1)`).
+    pub fn get_in_list_array_param(&self, row_id: NodeId) -> Result<Option<u16>, SbroadError> {
+        let Expression::Row(Row { list, .. }) = self.get_expression_node(row_id)? else {
+            return Ok(None);
+        };
+        let [param_id] = list.as_slice() else {
+            return Ok(None);
+        };
+        self.get_array_param(*param_id)
+    }
+
+    /// Get the index of the parameter if the expression is a parameter of the array type.
+    pub fn get_array_param(&self, expr_id: NodeId) -> Result<Option<u16>, SbroadError> {
+        if let Expression::Parameter(Parameter {
+            index, param_type, ..
+        }) = self.get_expression_node(expr_id)?
+        {
+            if param_type.get() == &Some(UnrestrictedType::Array) {
+                return Ok(Some(*index));
+            }
+        }
+        Ok(None)
+    }
+
     pub fn add_param(&mut self, index: u16, param_type: DerivedType) -> NodeId {
         self.nodes.push(
             Parameter {
@@ -211,7 +418,13 @@ impl Plan {
             return Ok(());
         }
 
-        bind_params(self, values)
+        let in_lists_expanded = bind_in_list_params(self, &values)?;
+        bind_params(self, values)?;
+        bind_like_any_params(self)?;
+        if in_lists_expanded {
+            self.replace_bound_in_lists()?;
+        }
+        Ok(())
     }
 
     /// Marks parameter nodes as unique (appearing once) or non-unique (appearing multiple times).
@@ -239,20 +452,17 @@ impl Plan {
     }
 
     /// Replaces the timestamp functions with corresponding constants
-    pub fn update_timestamps(mut self) -> Result<Self, SbroadError> {
-        // we use `time` crate to represent time, but it has trouble determining UTC offset on linux
-        // because it relies on libc and libc API is unsound in presence of more than one thread (fun™ :/)
-        // so we utilize `chrono` to get the actual local time and then convert it to `time::OffsetDateTime`
-        // (chrono reimplements the part of libc that parses the timezone database, so it doesn't have the issue)
-
-        let time = chrono::offset::Local::now().fixed_offset();
-        let local_datetime =
-            OffsetDateTime::from_unix_timestamp_nanos(time.timestamp_nanos_opt().unwrap() as i128)
-                .unwrap()
-                .to_offset(
-                    time::UtcOffset::from_whole_seconds(time.offset().local_minus_utc()).unwrap(),
-                );
+    pub fn update_timestamps(self) -> Result<Self, SbroadError> {
+        self.update_timestamps_at(local_now())
+    }
 
+    /// Replaces the timestamp functions with constants of the given time.
+    /// The time is evaluated once per statement (or transaction), so all
+    /// the storages see the same value.
+    pub fn update_timestamps_at(
+        mut self,
+        local_datetime: OffsetDateTime,
+    ) -> Result<Self, SbroadError> {
         for node in self.nodes.arena32.iter_mut() {
             if let Node32::Timestamp(timestamp) = node {
                 *node = Node32::Constant(Constant {
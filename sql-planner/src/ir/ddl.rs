@@ -1,3 +1,4 @@
+use crate::ir::operator::Bool;
 use crate::ir::types::UnrestrictedType;
 use crate::ir::value::Value;
 use crate::{
@@ -18,6 +19,22 @@ pub struct ColumnDef {
     pub is_nullable: bool,
 }
 
+/// `CHECK (<column> <op> <value>)` constraint declared in `CREATE TABLE`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct CheckDef {
+    pub name: SmolStr,
+    pub column: SmolStr,
+    pub op: Bool,
+    pub value: Value,
+}
+
+/// `DEFAULT <value>` of a column declared in `CREATE TABLE`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct DefaultDef {
+    pub column: SmolStr,
+    pub value: Value,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub struct ParamDef {
     pub data_type: UnrestrictedType,
@@ -35,11 +52,45 @@ pub enum SetParamScopeType {
     Session,
 }
 
+/// Isolation level requested with `SET TRANSACTION ISOLATION LEVEL`.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum TransactionIsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl std::fmt::Display for TransactionIsolationLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            TransactionIsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            TransactionIsolationLevel::ReadCommitted => "READ COMMITTED",
+            TransactionIsolationLevel::RepeatableRead => "REPEATABLE READ",
+            TransactionIsolationLevel::Serializable => "SERIALIZABLE",
+        };
+        write!(f, "{name}")
+    }
+}
+
 // TODO: Fill with actual values.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub enum SetParamValue {
-    NamedParam { name: SmolStr },
+    NamedParam {
+        name: SmolStr,
+    },
     TimeZone,
+    /// `SET application_name`, the only parameter whose value is kept,
+    /// as it is applied to the client session.
+    ApplicationName {
+        value: SmolStr,
+    },
+    /// `SET plan_cache`, applied to the client session. When disabled,
+    /// the statements of the session are always planned anew and don't
+    /// touch the query plan cache.
+    PlanCache {
+        enabled: bool,
+    },
 }
 
 impl SetParamValue {
@@ -48,10 +99,23 @@ impl SetParamValue {
         match self {
             SetParamValue::NamedParam { name } => name.clone(),
             SetParamValue::TimeZone => SmolStr::from("TimeZone"),
+            SetParamValue::ApplicationName { .. } => SmolStr::from("application_name"),
+            SetParamValue::PlanCache { .. } => SmolStr::from("plan_cache"),
         }
     }
 }
 
+/// Asynchronous notification statements, see [`super::node::Notification`].
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum NotificationCommand {
+    /// `LISTEN channel`
+    Listen { channel: SmolStr },
+    /// `UNLISTEN channel`, `None` stands for `UNLISTEN *`.
+    Unlisten { channel: Option<SmolStr> },
+    /// `NOTIFY channel[, 'payload']`
+    Notify { channel: SmolStr, payload: SmolStr },
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub enum AlterSystemType {
     AlterSystemSet {
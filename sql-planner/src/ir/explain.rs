@@ -1,30 +1,33 @@
-use std::collections::HashMap;
-use std::fmt::{Display, Formatter, Write as _};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter, Write};
 
 use itertools::Itertools;
 use serde::Serialize;
 use smol_str::{format_smolstr, SmolStr, ToSmolStr};
 
-use crate::errors::{Entity, SbroadError};
+use crate::errors::{Action, Entity, SbroadError};
 use crate::executor::bucket::Buckets;
 use crate::executor::engine::helpers::to_user;
 use crate::executor::engine::Router;
 use crate::executor::ExecutingQuery;
+use crate::ir::explain::cost::{Cost, CostEstimator, TableStats};
 use crate::ir::explain::execution_info::BucketsInfo;
 use crate::ir::expression::TrimKind;
 use crate::ir::node::{
     Alias, ArithmeticExpr, BoolExpr, Case, Cast, Constant, Delete, Having, IndexExpr, Insert, Join,
-    Motion as MotionRel, NodeId, Reference, Row as RowExpr, ScalarFunction, ScanCte, ScanRelation,
-    ScanSubQuery, Selection, SubQueryReference, Timestamp, Trim, UnaryExpr, Update as UpdateRel,
-    Values, ValuesRow,
+    Motion as MotionRel, Node32, NodeId, Reference, Row as RowExpr, ScalarFunction, ScanCte,
+    ScanRelation, ScanSubQuery, Selection, SubQueryReference, Timestamp, Trim, UnaryExpr,
+    Update as UpdateRel, Values, ValuesRow,
+};
+use crate::ir::operator::{
+    Collation, ConflictStrategy, JoinKind, NullsOrder, OrderByElement, OrderByEntity, OrderByType,
 };
-use crate::ir::operator::{ConflictStrategy, JoinKind, OrderByElement, OrderByEntity, OrderByType};
 use crate::ir::options::OptionKind;
 use crate::ir::transformation::redistribution::{
     MotionKey as IrMotionKey, MotionPolicy as IrMotionPolicy, Program, Target as IrTarget,
 };
 use crate::ir::{node, Plan};
-use crate::utils::OrderedMap;
+use crate::utils::{MutexLike, OrderedMap};
 
 use super::expression::FunctionFeature;
 use super::helpers::RepeatableState;
@@ -41,7 +44,7 @@ enum ColExpr {
     Parentheses(Box<ColExpr>),
     Alias(Box<ColExpr>, SmolStr),
     Arithmetic(Box<ColExpr>, Arithmetic, Box<ColExpr>),
-    Bool(Box<ColExpr>, Bool, Box<ColExpr>),
+    Bool(Box<ColExpr>, Bool, Box<ColExpr>, Option<Collation>),
     Unary(Unary, Box<ColExpr>),
     Column(String, DerivedType),
     Index(Box<ColExpr>, Box<ColExpr>),
@@ -93,12 +96,15 @@ impl Display for ColExpr {
             ColExpr::Parentheses(child_expr) => format!("({child_expr})"),
             ColExpr::Alias(expr, name) => format!("{expr} -> \"{name}\""),
             ColExpr::Arithmetic(left, op, right) => format!("{left} {op} {right}"),
-            ColExpr::Bool(left, op, right) => format!("{left} {op} {right}"),
+            ColExpr::Bool(left, op, right, None) => format!("{left} {op} {right}"),
+            ColExpr::Bool(left, op, right, Some(collation)) => {
+                format!("{left} collate \"{collation}\" {op} {right}")
+            }
             ColExpr::Unary(op, expr) => match op {
                 Unary::IsNull => format!("{expr} {op}"),
                 Unary::Exists => format!("{op} {expr}"),
                 Unary::Not => {
-                    if let ColExpr::Bool(_, Bool::And, _) = **expr {
+                    if let ColExpr::Bool(_, Bool::And, _, _) = **expr {
                         format!("{op} ({expr})")
                     } else {
                         format!("{op} {expr}")
@@ -131,9 +137,23 @@ impl Display for ColExpr {
                 }
                 let is_distinct = matches!(feature, Some(FunctionFeature::Distinct));
                 let formatted_args = format!("({})", args.iter().format(", "));
+                let order_by = match (feature, args.first()) {
+                    (Some(FunctionFeature::OrderBy(order_type, nulls, collation)), Some(key)) => {
+                        let collation = collation
+                            .map(|c| format!(" collate \"{c}\""))
+                            .unwrap_or_default();
+                        let nulls = if *nulls == NullsOrder::default_for(order_type) {
+                            String::new()
+                        } else {
+                            format!(" {nulls}")
+                        };
+                        format!(" order by {key}{collation} {order_type}{nulls}")
+                    }
+                    _ => String::new(),
+                };
                 let func_type_name = func_type.to_string();
                 format!(
-                    "{name}({}{formatted_args})::{func_type_name}",
+                    "{name}({}{formatted_args}{order_by})::{func_type_name}",
                     if is_distinct { "distinct " } else { "" }
                 )
             }
@@ -248,6 +268,7 @@ impl ColExpr {
                             o_elems.push(OrderByPair {
                                 expr,
                                 order_type: o_elem.order_type.clone(),
+                                collation: o_elem.collation,
                             });
                         }
                         o_elems.reverse();
@@ -452,11 +473,12 @@ impl ColExpr {
                     let alias_expr = ColExpr::Alias(Box::new(expr), name.clone());
                     stack.push((alias_expr, id));
                 }
-                Expression::Bool(BoolExpr { op, .. }) => {
+                Expression::Bool(BoolExpr { op, collation, .. }) => {
                     let right_expr = stack.pop_expr(Some(id));
                     let left_expr = stack.pop_expr(Some(id));
 
-                    let bool_expr = ColExpr::Bool(Box::new(left_expr), *op, Box::new(right_expr));
+                    let bool_expr =
+                        ColExpr::Bool(Box::new(left_expr), *op, Box::new(right_expr), *collation);
 
                     stack.push((bool_expr, id));
                 }
@@ -727,15 +749,19 @@ impl Display for OrderByExpr {
 struct OrderByPair {
     expr: OrderByExpr,
     order_type: Option<OrderByType>,
+    collation: Option<Collation>,
 }
 
 impl Display for OrderByPair {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expr)?;
+        if let Some(collation) = &self.collation {
+            write!(f, " collate \"{collation}\"")?;
+        }
         if let Some(order_type) = &self.order_type {
-            write!(f, "{} {order_type}", self.expr)
-        } else {
-            write!(f, "{}", self.expr)
+            write!(f, " {order_type}")?;
         }
+        Ok(())
     }
 }
 
@@ -765,6 +791,7 @@ impl OrderBy {
             result.order_by_elements.push(OrderByPair {
                 expr,
                 order_type: order_by_element.order_type.clone(),
+                collation: order_by_element.collation,
             });
         }
         Ok(result)
@@ -886,15 +913,24 @@ struct Scan {
 
     /// Index used
     indexed_by: Option<SmolStr>,
+
+    /// `TABLESAMPLE` clause
+    sample: Option<SmolStr>,
 }
 
 impl Scan {
     #[allow(dead_code)]
-    fn new(table: SmolStr, alias: Option<SmolStr>, indexed_by: Option<SmolStr>) -> Self {
+    fn new(
+        table: SmolStr,
+        alias: Option<SmolStr>,
+        indexed_by: Option<SmolStr>,
+        sample: Option<SmolStr>,
+    ) -> Self {
         Scan {
             table,
             alias,
             indexed_by,
+            sample,
         }
     }
 }
@@ -913,6 +949,10 @@ impl Display for Scan {
             write!(s, " (indexed by \"{index_name}\")")?;
         }
 
+        if let Some(sample) = &self.sample {
+            write!(s, " ({sample})")?;
+        }
+
         write!(f, "{s}")
     }
 }
@@ -1075,6 +1115,9 @@ impl Display for Motion {
 enum MotionPolicy {
     None,
     Full,
+    /// Full motion of a scalar subquery: all the subquery rows
+    /// are gathered to be used as a single value.
+    Single,
     Segment(MotionKey),
     Local,
     LocalSegment(MotionKey),
@@ -1085,6 +1128,7 @@ impl Display for MotionPolicy {
         match &self {
             MotionPolicy::None => write!(f, "none"),
             MotionPolicy::Full => write!(f, "full"),
+            MotionPolicy::Single => write!(f, "single"),
             MotionPolicy::Segment(mk) => write!(f, "segment({mk})"),
             MotionPolicy::Local => write!(f, "local"),
             MotionPolicy::LocalSegment(mk) => write!(f, "local segment({mk})"),
@@ -1150,7 +1194,9 @@ impl Display for InnerJoin {
 enum ExplainNode {
     Delete(SmolStr),
     Except,
+    ExceptAll,
     Intersect,
+    IntersectAll,
     GroupBy(GroupBy),
     OrderBy(OrderBy),
     InnerJoin(InnerJoin),
@@ -1167,7 +1213,28 @@ enum ExplainNode {
     SubQuery(SubQuery),
     Motion(Motion),
     Cte(SmolStr, Ref),
-    Limit(u64),
+    Limit(ExplainLimit),
+}
+
+#[derive(Debug, PartialEq, Serialize, Clone)]
+struct ExplainLimit {
+    limit: Option<u64>,
+    offset: u64,
+    with_ties: bool,
+}
+
+impl Display for ExplainLimit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.limit {
+            Some(limit) if self.offset > 0 => write!(f, "limit {limit} offset {}", self.offset)?,
+            Some(limit) => write!(f, "limit {limit}")?,
+            None => write!(f, "offset {}", self.offset)?,
+        }
+        if self.with_ties {
+            write!(f, " with ties")?;
+        }
+        Ok(())
+    }
 }
 
 impl Display for ExplainNode {
@@ -1176,6 +1243,7 @@ impl Display for ExplainNode {
             ExplainNode::Cte(s, r) => format_smolstr!("scan cte {s}({r})"),
             ExplainNode::Delete(s) => format_smolstr!("delete \"{s}\""),
             ExplainNode::Except => "except".to_smolstr(),
+            ExplainNode::ExceptAll => "except all".to_smolstr(),
             ExplainNode::InnerJoin(i) => i.to_smolstr(),
             ExplainNode::ValueRow(r) => format_smolstr!("value row (data={r})"),
             ExplainNode::Value => "values".to_smolstr(),
@@ -1191,10 +1259,11 @@ impl Display for ExplainNode {
             ExplainNode::Union => "union".to_smolstr(),
             ExplainNode::UnionAll => "union all".to_smolstr(),
             ExplainNode::Intersect => "intersect".to_smolstr(),
+            ExplainNode::IntersectAll => "intersect all".to_smolstr(),
             ExplainNode::Update(u) => u.to_smolstr(),
             ExplainNode::SubQuery(s) => s.to_smolstr(),
             ExplainNode::Motion(m) => m.to_smolstr(),
-            ExplainNode::Limit(l) => format_smolstr!("limit {l}"),
+            ExplainNode::Limit(l) => l.to_smolstr(),
         };
 
         write!(f, "{s}")
@@ -1209,6 +1278,9 @@ struct ExplainTreePart {
     level: usize,
     /// Current node of sql query
     current: Option<ExplainNode>,
+    /// Estimated cost of the current node (only for `EXPLAIN (COSTS)`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cost: Option<Cost>,
     /// Children nodes of current sql node
     children: Vec<ExplainTreePart>,
 }
@@ -1221,19 +1293,21 @@ impl PartialEq for ExplainTreePart {
 
 impl Display for ExplainTreePart {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut s = String::new();
-        if let Some(c) = &self.current {
-            writeln!(s, "{}", &c.to_string())?;
+        match (&self.current, &self.cost) {
+            (Some(c), Some(cost)) => writeln!(f, "{c} {cost}")?,
+            (Some(c), None) => writeln!(f, "{c}")?,
+            _ => {}
         }
 
-        let ident = (0..=self.level).map(|_| "    ").collect::<String>();
-
+        // Children are written straight into the formatter, so that
+        // the whole tree is never materialized as a string.
         for child in &self.children {
-            s.push_str(&ident);
-            s.push_str(&child.to_string());
+            for _ in 0..=self.level {
+                f.write_str("    ")?;
+            }
+            write!(f, "{child}")?;
         }
-
-        write!(f, "{s}")
+        Ok(())
     }
 }
 
@@ -1242,6 +1316,7 @@ impl Default for ExplainTreePart {
         Self {
             level: 0,
             current: None,
+            cost: None,
             children: Vec::with_capacity(200),
         }
     }
@@ -1252,6 +1327,7 @@ impl ExplainTreePart {
         ExplainTreePart {
             level,
             current: None,
+            cost: None,
             children: Vec::with_capacity(100),
         }
     }
@@ -1310,40 +1386,78 @@ fn buckets_repr(buckets: &Buckets, bucket_count: u64) -> String {
 
 impl Display for FullExplain {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut s = self.main_query.to_string();
+        write!(f, "{}", self.main_query)?;
 
         for (pos, (_, sq)) in self.subqueries.iter().enumerate() {
-            writeln!(s, "subquery ${pos}:")?;
-            s.push_str(&sq.to_string());
+            writeln!(f, "subquery ${pos}:")?;
+            write!(f, "{sq}")?;
         }
         for (pos, window) in self.windows.iter().enumerate() {
-            writeln!(s, "window ${pos}:")?;
-            s.push_str(&window.to_string());
+            writeln!(f, "window ${pos}:")?;
+            write!(f, "{window}")?;
         }
         if !self.exec_options.is_empty() {
-            writeln!(s, "execution options:")?;
+            writeln!(f, "execution options:")?;
             for opt in &self.exec_options {
-                writeln!(s, "{:4}{} = {}", "", opt.0, opt.1)?;
+                writeln!(f, "{:4}{} = {}", "", opt.0, opt.1)?;
             }
         }
         if let Some(info) = &self.buckets_info {
             match info {
-                BucketsInfo::Unknown => writeln!(s, "buckets = unknown")?,
+                BucketsInfo::Unknown => writeln!(f, "buckets = unknown")?,
                 BucketsInfo::Calculated(calculated) => {
                     let repr = buckets_repr(&calculated.buckets, calculated.bucket_count);
                     // For buckets ANY and ALL there is no sense to handle in the
                     // output the case when bucket count is not exact.
                     match calculated.buckets {
-                        Buckets::Any | Buckets::All => writeln!(s, "buckets = {repr}",)?,
-                        _ if calculated.is_exact => writeln!(s, "buckets = {repr}",)?,
-                        _ => writeln!(s, "buckets <= {repr}",)?,
+                        Buckets::Any | Buckets::All => writeln!(f, "buckets = {repr}",)?,
+                        _ if calculated.is_exact => writeln!(f, "buckets = {repr}",)?,
+                        _ => writeln!(f, "buckets <= {repr}",)?,
                     }
                 }
             }
         }
+        Ok(())
+    }
+}
 
-        write!(f, "{s}")
+/// Collect subqueries used as scalar expressions, i.e. all the referenced
+/// subqueries except the ones under `IN` and `EXISTS` operators.
+fn scalar_subqueries(ir: &Plan) -> Result<HashSet<NodeId>, SbroadError> {
+    let mut scalar_sqs = HashSet::new();
+    let mut set_operands = Vec::new();
+    for node in ir.nodes.iter32() {
+        match node {
+            Node32::SubQueryReference(SubQueryReference { rel_id, .. }) => {
+                scalar_sqs.insert(*rel_id);
+            }
+            Node32::Bool(BoolExpr {
+                op: Bool::In,
+                right,
+                ..
+            }) => set_operands.push(*right),
+            Node32::Unary(UnaryExpr {
+                op: Unary::Exists,
+                child,
+            }) => set_operands.push(*child),
+            _ => {}
+        }
     }
+
+    for expr_id in set_operands {
+        let refs = match ir.get_expression_node(expr_id)? {
+            Expression::Row(RowExpr { list, .. }) => list.as_slice(),
+            _ => std::slice::from_ref(&expr_id),
+        };
+        for ref_id in refs {
+            if let Expression::SubQueryReference(SubQueryReference { rel_id, .. }) =
+                ir.get_expression_node(*ref_id)?
+            {
+                scalar_sqs.remove(rel_id);
+            }
+        }
+    }
+    Ok(scalar_sqs)
 }
 
 impl FullExplain {
@@ -1404,8 +1518,21 @@ impl FullExplain {
     }
 
     #[allow(dead_code)]
-    #[allow(clippy::too_many_lines)]
     pub fn new(ir: &Plan, top_id: NodeId) -> Result<Self, SbroadError> {
+        Self::build(ir, top_id, None)
+    }
+
+    /// Build explain with the estimated costs of the plan nodes.
+    pub fn with_costs(ir: &Plan, top_id: NodeId, stats: &TableStats) -> Result<Self, SbroadError> {
+        Self::build(ir, top_id, Some(CostEstimator::new(stats)))
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn build(
+        ir: &Plan,
+        top_id: NodeId,
+        mut estimator: Option<CostEstimator<'_>>,
+    ) -> Result<Self, SbroadError> {
         let mut stack: Vec<ExplainTreePart> = Vec::new();
         let mut result = FullExplain::empty();
         result.exec_options.push((
@@ -1416,14 +1543,49 @@ impl FullExplain {
             OptionKind::MotionRowMax,
             Value::Integer(ir.effective_options.sql_motion_row_max),
         ));
+        if ir.effective_options.sql_max_buckets_per_query > 0 {
+            result.exec_options.push((
+                OptionKind::MaxBucketsPerQuery,
+                Value::Integer(ir.effective_options.sql_max_buckets_per_query),
+            ));
+        }
+        if ir.effective_options.sql_partial_results {
+            result.exec_options.push((
+                OptionKind::PartialResults,
+                Value::Boolean(ir.effective_options.sql_partial_results),
+            ));
+        }
+        if ir.effective_options.wait_index > 0 {
+            result.exec_options.push((
+                OptionKind::WaitIndex,
+                Value::Integer(ir.effective_options.wait_index),
+            ));
+            result.exec_options.push((
+                OptionKind::WaitIndexTimeout,
+                Value::Integer(ir.effective_options.wait_index_timeout),
+            ));
+        }
+        if ir.effective_options.sql_result_row_max > 0 {
+            result.exec_options.push((
+                OptionKind::ResultRowMax,
+                Value::Integer(ir.effective_options.sql_result_row_max),
+            ));
+        }
+        if ir.effective_options.sql_dispatch_concurrency_max > 0 {
+            result.exec_options.push((
+                OptionKind::DispatchConcurrencyMax,
+                Value::Integer(ir.effective_options.sql_dispatch_concurrency_max),
+            ));
+        }
 
+        let scalar_sqs = scalar_subqueries(ir)?;
         let dft_post = PostOrder::with_capacity(|node| ir.nodes.rel_iter(node), REL_CAPACITY);
         for LevelNode(level, id) in dft_post.into_iter(top_id) {
             let mut current_node = ExplainTreePart::with_level(level);
             let node = ir.get_relation_node(id)?;
 
             current_node.current = match &node {
-                Relational::Intersect { .. } => {
+                Relational::Intersect(node::Intersect { all, .. }) => {
                     if let (Some(right), Some(left)) = (stack.pop(), stack.pop()) {
                         current_node.children.push(left);
                         current_node.children.push(right);
@@ -1432,9 +1594,13 @@ impl FullExplain {
                             "Intersect node must have exactly two children".into(),
                         ));
                     }
-                    Some(ExplainNode::Intersect)
+                    if *all {
+                        Some(ExplainNode::IntersectAll)
+                    } else {
+                        Some(ExplainNode::Intersect)
+                    }
                 }
-                Relational::Except { .. } => {
+                Relational::Except(node::Except { all, .. }) => {
                     if let (Some(right), Some(left)) = (stack.pop(), stack.pop()) {
                         current_node.children.push(left);
                         current_node.children.push(right);
@@ -1443,7 +1609,11 @@ impl FullExplain {
                             "Exception node must have exactly two children".into(),
                         ));
                     }
-                    Some(ExplainNode::Except)
+                    if *all {
+                        Some(ExplainNode::ExceptAll)
+                    } else {
+                        Some(ExplainNode::Except)
+                    }
                 }
                 Relational::GroupBy(node::GroupBy {
                     gr_exprs,
@@ -1519,12 +1689,14 @@ impl FullExplain {
                     relation,
                     alias,
                     indexed_by,
+                    sample,
                     ..
                 }) => {
                     let s = Scan::new(
                         relation.to_smolstr(),
                         alias.as_ref().map(ToSmolStr::to_smolstr),
                         indexed_by.as_ref().map(ToSmolStr::to_smolstr),
+                        sample.as_ref().map(ToSmolStr::to_smolstr),
                     );
                     Some(ExplainNode::Scan(s))
                 }
@@ -1608,6 +1780,27 @@ impl FullExplain {
                         let targets = (s.targets)
                             .iter()
                             .map(|r| match r {
+                                IrTarget::Reference(pos) if *pos == col_list.len() => {
+                                    // Identity values generated for INSERT are appended
+                                    // after the child columns, show the table column instead.
+                                    let top_id = ir.get_top()?;
+                                    let mut identity = None;
+                                    if let Relational::Insert(_) = ir.get_relation_node(top_id)? {
+                                        let table = ir.dml_node_table(top_id)?;
+                                        identity = ir
+                                            .insert_omitted_identity(top_id)?
+                                            .and_then(|pos| table.columns.get(pos));
+                                    }
+                                    let column = identity.ok_or_else(|| {
+                                        SbroadError::NotFound(
+                                            Entity::Target,
+                                            format_smolstr!("reference with position {pos}"),
+                                        )
+                                    })?;
+                                    Ok::<Target, SbroadError>(Target::Reference(
+                                        column.name.to_string(),
+                                    ))
+                                }
                                 IrTarget::Reference(pos) => {
                                     let col_id = *col_list.get(*pos).ok_or_else(|| {
                                         SbroadError::NotFound(
@@ -1634,6 +1827,7 @@ impl FullExplain {
                             let targets = collect_targets(s)?;
                             MotionPolicy::Segment(MotionKey { targets })
                         }
+                        IrMotionPolicy::Full if scalar_sqs.contains(&id) => MotionPolicy::Single,
                         IrMotionPolicy::Full => MotionPolicy::Full,
                         IrMotionPolicy::Local => MotionPolicy::Local,
                         IrMotionPolicy::LocalSegment(s) => {
@@ -1725,7 +1919,12 @@ impl FullExplain {
 
                     Some(ExplainNode::Delete(relation.to_smolstr()))
                 }
-                Relational::Limit(Limit { limit, .. }) => {
+                Relational::Limit(Limit {
+                    limit,
+                    offset,
+                    with_ties,
+                    ..
+                }) => {
                     let child = stack.pop().ok_or_else(|| {
                         SbroadError::UnexpectedNumberOfValues(
                             "Limit node must have exactly one child".into(),
@@ -1734,10 +1933,17 @@ impl FullExplain {
 
                     current_node.children.push(child);
 
-                    Some(ExplainNode::Limit(*limit))
+                    Some(ExplainNode::Limit(ExplainLimit {
+                        limit: *limit,
+                        offset: *offset,
+                        with_ties: *with_ties,
+                    }))
                 }
             };
 
+            if let Some(estimator) = estimator.as_mut() {
+                current_node.cost = Some(estimator.estimate(ir, id)?);
+            }
             stack.push(current_node);
         }
         result.main_query = stack
@@ -1751,29 +1957,76 @@ impl FullExplain {
     }
 }
 
+/// Write the explain into `w` without formatting it into a string first.
+fn write_explain(explain: &FullExplain, w: &mut impl Write) -> Result<(), SbroadError> {
+    write!(w, "{explain}").map_err(|_| {
+        SbroadError::FailedTo(
+            Action::Serialize,
+            Some(Entity::Explain),
+            "failed to write explain".into(),
+        )
+    })
+}
+
 impl Plan {
+    fn full_explain(&self) -> Result<FullExplain, SbroadError> {
+        let top_id = self.get_top()?;
+        if self.is_costs_explain() {
+            FullExplain::with_costs(self, top_id, &TableStats::default())
+        } else {
+            FullExplain::new(self, top_id)
+        }
+    }
+
     /// Display ir explain
     ///
     /// # Errors
     /// - Failed to get top node
     /// - Failed to build explain
     pub fn as_explain(&self) -> Result<SmolStr, SbroadError> {
-        let top_id = self.get_top()?;
-        let explain = FullExplain::new(self, top_id)?;
-        Ok(explain.to_smolstr())
+        Ok(self.full_explain()?.to_smolstr())
+    }
+
+    /// Write ir explain into `w` node by node. The output is the same as
+    /// of [`Plan::as_explain`], but it is never kept in memory as a whole.
+    ///
+    /// # Errors
+    /// - Failed to get top node
+    /// - Failed to build explain
+    /// - Writer failed
+    pub fn explain_to_writer(&self, w: &mut impl Write) -> Result<(), SbroadError> {
+        write_explain(&self.full_explain()?, w)
     }
 }
 
 impl<C: Router> ExecutingQuery<'_, C> {
     pub fn as_explain(&mut self) -> Result<SmolStr, SbroadError> {
+        Ok(self.full_explain()?.to_smolstr())
+    }
+
+    /// Streaming counterpart of [`ExecutingQuery::as_explain`],
+    /// see [`Plan::explain_to_writer`].
+    pub fn explain_to_writer(&mut self, w: &mut impl Write) -> Result<(), SbroadError> {
+        write_explain(&self.full_explain()?, w)
+    }
+
+    fn full_explain(&mut self) -> Result<FullExplain, SbroadError> {
         let plan = self.get_exec_plan().get_ir_plan();
         let top_id = plan.get_top()?;
-        let mut explain = FullExplain::new(plan, top_id)?;
+        let mut explain = if plan.is_costs_explain() {
+            let stats = {
+                let metadata = self.get_coordinator().metadata().lock();
+                TableStats::new(plan, &*metadata)
+            };
+            FullExplain::with_costs(plan, top_id, &stats)?
+        } else {
+            FullExplain::new(plan, top_id)?
+        };
 
         let info = BucketsInfo::new_from_query(self)?;
         explain.add_execution_info(info);
 
-        Ok(explain.to_smolstr())
+        Ok(explain)
     }
 }
 
@@ -1781,4 +2034,6 @@ impl<C: Router> ExecutingQuery<'_, C> {
 #[cfg(test)]
 mod tests;
 
+mod cost;
 mod execution_info;
+mod json;
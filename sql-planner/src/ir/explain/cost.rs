@@ -0,0 +1,311 @@
+//! Rough cost estimation of the plan nodes for `EXPLAIN (COSTS)`.
+//!
+//! Estimation relies only on the table row counts provided by the metadata
+//! and on the fixed selectivities of the filter operators. Nothing is
+//! executed to get the numbers, so they are an estimate and not ANALYZE.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use serde::Serialize;
+use smol_str::SmolStr;
+
+use crate::errors::SbroadError;
+use crate::executor::engine::Metadata;
+use crate::ir::node::expression::Expression;
+use crate::ir::node::relational::Relational;
+use crate::ir::node::{
+    BoolExpr, Constant, Delete, GroupBy, Join, Limit, NodeId, Projection, Row, ScanRelation,
+    UnaryExpr, Values,
+};
+use crate::ir::operator::{Bool, Unary};
+use crate::ir::tree::traversal::{PostOrder, EXPR_CAPACITY};
+use crate::ir::value::Value;
+use crate::ir::Plan;
+
+/// Row count assumed for a table without statistics.
+const DEFAULT_TABLE_ROWS: f64 = 1000.0;
+/// Selectivity of an equality predicate.
+const EQ_SELECTIVITY: f64 = 0.005;
+/// Selectivity of a range or an unknown predicate.
+const DEFAULT_SELECTIVITY: f64 = 1.0 / 3.0;
+/// Ratio of the number of groups to the number of grouped rows.
+const GROUP_BY_RATIO: f64 = 0.1;
+
+/// Row counts of the tables used for the estimation.
+#[derive(Debug, Default)]
+pub struct TableStats {
+    row_counts: HashMap<SmolStr, u64>,
+}
+
+impl TableStats {
+    /// Collect row counts of the tables referenced by the plan.
+    pub fn new(plan: &Plan, metadata: &impl Metadata) -> Self {
+        let row_counts = plan
+            .relations
+            .tables
+            .keys()
+            .filter_map(|name| Some((name.clone(), metadata.table_row_count(name)?)))
+            .collect();
+        Self { row_counts }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn rows(&self, table: &str) -> f64 {
+        self.row_counts
+            .get(table)
+            .map_or(DEFAULT_TABLE_ROWS, |count| *count as f64)
+    }
+}
+
+/// Estimated number of rows produced by a plan node and the total cost
+/// of producing them (including the cost of the node's children).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub(super) struct Cost {
+    rows: f64,
+    total: f64,
+}
+
+impl Display for Cost {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "(cost={:.2} rows={})",
+            self.total,
+            self.rows.round() as u64
+        )
+    }
+}
+
+/// Estimates the costs of the relational nodes in the post order,
+/// so the children of a node are always estimated before the node itself.
+pub(super) struct CostEstimator<'s> {
+    stats: &'s TableStats,
+    costs: HashMap<NodeId, Cost>,
+}
+
+impl<'s> CostEstimator<'s> {
+    pub(super) fn new(stats: &'s TableStats) -> Self {
+        Self {
+            stats,
+            costs: HashMap::new(),
+        }
+    }
+
+    fn child(&self, id: NodeId) -> Cost {
+        self.costs.get(&id).copied().unwrap_or_default()
+    }
+
+    #[allow(clippy::cast_precision_loss, clippy::too_many_lines)]
+    pub(super) fn estimate(&mut self, plan: &Plan, id: NodeId) -> Result<Cost, SbroadError> {
+        let node = plan.get_relation_node(id)?;
+        let cost = match &node {
+            Relational::ScanRelation(ScanRelation {
+                relation, sample, ..
+            }) => {
+                let mut rows = self.stats.rows(relation);
+                if let Some(sample) = sample {
+                    let percent: f64 = sample.percent.to_string().parse().unwrap_or(100.0);
+                    rows *= percent / 100.0;
+                }
+                Cost { rows, total: rows }
+            }
+            Relational::Selection(_) | Relational::Having(_) => {
+                let child = self.child(node.children()[0]);
+                let filter = match &node {
+                    Relational::Selection(s) => s.filter,
+                    Relational::Having(h) => h.filter,
+                    _ => unreachable!("expected Selection or Having"),
+                };
+                Cost {
+                    rows: child.rows * selectivity(plan, filter)?,
+                    total: child.total + child.rows,
+                }
+            }
+            Relational::Join(Join {
+                children,
+                condition,
+                ..
+            }) => {
+                let left = self.child(children[0]);
+                let right = self.child(children[1]);
+                let pairs = left.rows * right.rows;
+                Cost {
+                    rows: pairs * selectivity(plan, *condition)?,
+                    total: left.total + right.total + pairs,
+                }
+            }
+            Relational::Projection(Projection {
+                children,
+                output,
+                group_by,
+                having,
+                ..
+            }) => {
+                let child_id = having.or(*group_by).unwrap_or(children[0]);
+                let child = self.child(child_id);
+                let rows =
+                    if group_by.is_none() && having.is_none() && has_aggregates(plan, *output) {
+                        1.0
+                    } else {
+                        child.rows
+                    };
+                Cost {
+                    rows,
+                    total: child.total + child.rows,
+                }
+            }
+            Relational::GroupBy(GroupBy {
+                children, gr_exprs, ..
+            }) => {
+                let child = self.child(children[0]);
+                let rows = if gr_exprs.is_empty() {
+                    1.0
+                } else {
+                    (child.rows * GROUP_BY_RATIO).max(1.0)
+                };
+                Cost {
+                    rows,
+                    total: child.total + child.rows,
+                }
+            }
+            Relational::OrderBy(_) => {
+                let child = self.child(node.children()[0]);
+                let sort = child.rows * child.rows.max(2.0).log2();
+                Cost {
+                    rows: child.rows,
+                    total: child.total + sort,
+                }
+            }
+            Relational::Motion(_) => {
+                // Every row produced by the child is sent over the network.
+                let child = node.children().get(0).map(|id| self.child(*id));
+                let child = child.unwrap_or_default();
+                Cost {
+                    rows: child.rows,
+                    total: child.total + child.rows,
+                }
+            }
+            Relational::Union(_) | Relational::UnionAll(_) => {
+                let left = self.child(node.children()[0]);
+                let right = self.child(node.children()[1]);
+                Cost {
+                    rows: left.rows + right.rows,
+                    total: left.total + right.total,
+                }
+            }
+            Relational::Except(_) | Relational::Intersect(_) => {
+                let left = self.child(node.children()[0]);
+                let right = self.child(node.children()[1]);
+                let rows = if matches!(node, Relational::Except(_)) {
+                    left.rows
+                } else {
+                    left.rows.min(right.rows)
+                };
+                Cost {
+                    rows,
+                    total: left.total + right.total + left.rows + right.rows,
+                }
+            }
+            Relational::Limit(Limit {
+                limit,
+                offset,
+                child,
+                ..
+            }) => {
+                let child = self.child(*child);
+                let rows = (child.rows - *offset as f64).max(0.0);
+                Cost {
+                    rows: limit.map_or(rows, |limit| rows.min(limit as f64)),
+                    total: child.total,
+                }
+            }
+            Relational::Values(Values { children, .. }) => {
+                let rows = children.len() as f64;
+                Cost { rows, total: rows }
+            }
+            Relational::ValuesRow(_) | Relational::SelectWithoutScan(_) => Cost {
+                rows: 1.0,
+                total: 1.0,
+            },
+            Relational::Delete(Delete {
+                relation,
+                child: None,
+                ..
+            }) => {
+                let rows = self.stats.rows(relation);
+                Cost { rows, total: rows }
+            }
+            Relational::ScanCte(_)
+            | Relational::ScanSubQuery(_)
+            | Relational::Insert(_)
+            | Relational::Update(_)
+            | Relational::Delete(_) => {
+                let child = self.child(node.children()[0]);
+                let modified =
+                    if matches!(node, Relational::ScanCte(_) | Relational::ScanSubQuery(_)) {
+                        0.0
+                    } else {
+                        child.rows
+                    };
+                Cost {
+                    rows: child.rows,
+                    total: child.total + modified,
+                }
+            }
+        };
+        self.costs.insert(id, cost);
+        Ok(cost)
+    }
+}
+
+/// Share of the rows satisfying the predicate.
+fn selectivity(plan: &Plan, expr_id: NodeId) -> Result<f64, SbroadError> {
+    let selectivity = match plan.get_expression_node(expr_id)? {
+        Expression::Bool(BoolExpr { op, left, right }) => match op {
+            Bool::And => selectivity(plan, *left)? * selectivity(plan, *right)?,
+            Bool::Or => {
+                let left = selectivity(plan, *left)?;
+                let right = selectivity(plan, *right)?;
+                left + right - left * right
+            }
+            Bool::Eq => EQ_SELECTIVITY,
+            Bool::NotEq => 1.0 - EQ_SELECTIVITY,
+            Bool::In => {
+                #[allow(clippy::cast_precision_loss)]
+                let values = match plan.get_expression_node(*right)? {
+                    Expression::Row(Row { list, .. }) => list.len() as f64,
+                    _ => 1.0,
+                };
+                (values * EQ_SELECTIVITY).min(1.0)
+            }
+            Bool::Gt | Bool::GtEq | Bool::Lt | Bool::LtEq | Bool::Between => DEFAULT_SELECTIVITY,
+        },
+        Expression::Unary(UnaryExpr {
+            op: Unary::Not,
+            child,
+        }) => 1.0 - selectivity(plan, *child)?,
+        Expression::Row(Row { list, .. }) if list.len() == 1 => selectivity(plan, list[0])?,
+        Expression::Constant(Constant {
+            value: Value::Boolean(value),
+        }) => {
+            if *value {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        _ => DEFAULT_SELECTIVITY,
+    };
+    Ok(selectivity)
+}
+
+/// Checks whether the output row of a projection contains aggregate functions.
+fn has_aggregates(plan: &Plan, output: NodeId) -> bool {
+    let dfs = PostOrder::with_capacity(|node| plan.nodes.expr_iter(node, false), EXPR_CAPACITY);
+    dfs.into_iter(output).any(|level_node| {
+        plan.get_expression_node(level_node.1)
+            .is_ok_and(|expr| expr.is_aggregate_fun())
+    })
+}
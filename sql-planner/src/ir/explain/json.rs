@@ -0,0 +1,74 @@
+use serde_json::{json, Value as JsonValue};
+use smol_str::format_smolstr;
+
+use crate::errors::{Action, Entity, SbroadError};
+use crate::ir::node::expression::Expression;
+use crate::ir::node::{Alias, NodeId};
+use crate::ir::Plan;
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<JsonValue, SbroadError> {
+    serde_json::to_value(value).map_err(|e| {
+        SbroadError::FailedTo(
+            Action::Serialize,
+            Some(Entity::Plan),
+            format_smolstr!("{e}"),
+        )
+    })
+}
+
+impl Plan {
+    /// Structured counterpart of [`Plan::as_explain`] for external tooling.
+    ///
+    /// Each relational node is emitted as an object with its `id`, `type`,
+    /// `output` columns, `distribution` and nested `children` nodes.
+    /// Output column expressions use the serde representation of [`Expression`].
+    ///
+    /// # Errors
+    /// - Failed to get top node
+    /// - Plan tree is invalid
+    pub fn as_explain_json(&self) -> Result<JsonValue, SbroadError> {
+        let top_id = self.get_top()?;
+        self.rel_node_as_json(top_id)
+    }
+
+    fn rel_node_as_json(&self, rel_id: NodeId) -> Result<JsonValue, SbroadError> {
+        let rel = self.get_relation_node(rel_id)?;
+
+        let output_id = rel.output();
+        let mut output = Vec::new();
+        for col_id in self.get_row_list(output_id)? {
+            let col = self.get_expression_node(*col_id)?;
+            let col_type = col.calculate_type(self)?;
+            let (name, expr) = match col {
+                Expression::Alias(Alias { name, child }) => {
+                    (Some(name.as_str()), self.get_expression_node(*child)?)
+                }
+                expr => (None, expr),
+            };
+            output.push(json!({
+                "name": name,
+                "type": col_type.to_string(),
+                "expression": to_json(&expr)?,
+            }));
+        }
+
+        // Distribution is absent until it is calculated for the node.
+        let distribution = match self.get_distribution(output_id) {
+            Ok(dist) => to_json(&dist)?,
+            Err(_) => JsonValue::Null,
+        };
+
+        let mut children = Vec::new();
+        for child_id in rel.children().iter() {
+            children.push(self.rel_node_as_json(*child_id)?);
+        }
+
+        Ok(json!({
+            "id": rel_id.to_string(),
+            "type": rel.name(),
+            "output": output,
+            "distribution": distribution,
+            "children": children,
+        }))
+    }
+}
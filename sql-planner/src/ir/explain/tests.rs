@@ -232,6 +232,33 @@ fn motion_subquery_plan() {
     "#);
 }
 
+#[test]
+fn scalar_subquery_motion_plan() {
+    let query = r#"
+    SELECT (SELECT "identification_number" FROM "hash_testing" WHERE "product_code" = 'a')
+    FROM "test_space"
+"#;
+
+    let plan = sql_to_optimized_ir(query, vec![]);
+
+    let top = &plan.get_top().unwrap();
+    let explain_tree = FullExplain::new(&plan, *top).unwrap();
+
+    insta::assert_snapshot!(explain_tree.to_string(), @r#"
+    projection (ROW($0) -> "col_1")
+        scan "test_space"
+    subquery $0:
+    motion [policy: single, program: ReshardIfNeeded]
+                scan
+                    projection ("hash_testing"."identification_number"::int -> "identification_number")
+                        selection "hash_testing"."product_code"::string = 'a'::string
+                            scan "hash_testing"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
 #[test]
 fn motion_join_plan() {
     let query = r#"SELECT "t1"."FIRST_NAME"
@@ -498,6 +525,28 @@ execution options:
     assert_eq!(actual_explain, explain_tree.to_string());
 }
 
+#[test]
+fn simple_query_explain_json() {
+    let query = r#"SELECT "identification_number" as "c1" FROM "hash_testing""#;
+
+    let plan = sql_to_optimized_ir(query, vec![]);
+    let explain = plan.as_explain_json().unwrap();
+
+    assert_eq!(explain["type"], "Projection");
+    assert_eq!(explain["output"][0]["name"], "c1");
+    assert_eq!(explain["output"][0]["type"], "int");
+    assert!(explain["output"][0]["expression"]
+        .get("Reference")
+        .is_some());
+
+    let children = explain["children"].as_array().unwrap();
+    assert_eq!(children.len(), 1);
+    let scan = &children[0];
+    assert_eq!(scan["type"], "Scan");
+    assert_eq!(scan["children"].as_array().unwrap().len(), 0);
+    assert!(scan["distribution"].get("Segment").is_some());
+}
+
 #[test]
 fn check_buckets_repr() {
     let bc = 3000;
@@ -518,6 +567,38 @@ fn check_buckets_repr() {
     assert_eq!("[]", buckets_repr(&Buckets::Filtered(collection!()), bc));
 }
 
+#[test]
+fn explain_to_writer_matches_as_explain() {
+    /// Records the size of the largest single write.
+    #[derive(Default)]
+    struct ChunkWriter {
+        out: String,
+        max_chunk: usize,
+    }
+
+    impl std::fmt::Write for ChunkWriter {
+        fn write_str(&mut self, s: &str) -> std::fmt::Result {
+            self.max_chunk = self.max_chunk.max(s.len());
+            self.out.push_str(s);
+            Ok(())
+        }
+    }
+
+    let branch = |i: usize| {
+        format!(r#"SELECT "a" + {i} FROM "t" WHERE "b" IN (SELECT "e" FROM "t2" WHERE "f" = {i})"#)
+    };
+    let query = (0..50).map(branch).join(" UNION ALL ");
+    let plan = sql_to_optimized_ir(&query, vec![]);
+
+    let mut writer = ChunkWriter::default();
+    plan.explain_to_writer(&mut writer).unwrap();
+    let explain = plan.as_explain().unwrap();
+    assert_eq!(explain, writer.out);
+    assert!(explain.contains("subquery $0:"));
+    // The plan is written piece by piece, not as a single string.
+    assert!(writer.max_chunk < explain.len() / 10);
+}
+
 mod cast_constants;
 mod concat;
 mod delete;
@@ -48,3 +48,36 @@ fn delete3_test() {
         sql_motion_row_max = 5000
     "#);
 }
+
+#[test]
+fn delete_using_test() {
+    let sql = r#"DELETE FROM "t1" USING "t3" WHERE "t1"."a" = "t3"."a" AND "t3"."b" > 3"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+    let explain = plan.as_explain().unwrap();
+    assert!(explain.starts_with(
+        r#"delete "t1"
+    motion [policy: local, program: [PrimaryKey(0, 1), ReshardIfNeeded]]
+        projection ("t1"."a"::string -> "pk_col_0", "t1"."b"::int -> "pk_col_1")
+            join on "#
+    ));
+    assert!(explain.contains(r#"scan "t3""#));
+}
+
+#[test]
+fn delete_using_without_filter_test() {
+    let sql = r#"DELETE FROM "t1" USING "t3""#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+    let explain = plan.as_explain().unwrap();
+    assert!(explain.contains("join on true::bool"));
+}
+
+#[test]
+fn delete_using_global_test() {
+    let sql = r#"DELETE FROM "global_t" USING "t3" WHERE "global_t"."b" = "t3"."b""#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+    let explain = plan.as_explain().unwrap();
+    assert!(explain.starts_with(
+        r#"delete "global_t"
+    motion [policy: full, program: RemoveDuplicates]"#
+    ));
+}
@@ -364,3 +364,82 @@ fn test_query_explain_18() {
     buckets = any
     "#);
 }
+
+#[test]
+fn test_query_explain_19() {
+    // Point query on the sharding key with parameters is pruned to a single bucket.
+    let sql = r#"select e from t2 where e = $1 and f = $2"#;
+
+    let metadata = &RouterRuntimeMock::new();
+    let mut query = ExecutingQuery::from_text_and_params(
+        metadata,
+        sql,
+        vec![Value::Integer(1), Value::Integer(13)],
+    )
+    .unwrap();
+    insta::assert_snapshot!(query.to_explain().unwrap(), @r#"
+    projection ("t2"."e"::int -> "e")
+        selection ("t2"."e"::int = 1::int) and ("t2"."f"::int = 13::int)
+            scan "t2"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    buckets = [111]
+    "#);
+}
+
+#[test]
+fn test_query_explain_20() {
+    // Filter on a column outside of the sharding key doesn't prune buckets.
+    let sql = r#"select e from t2 where g = 1"#;
+
+    let metadata = &RouterRuntimeMock::new();
+    let mut query = ExecutingQuery::from_text_and_params(metadata, sql, vec![]).unwrap();
+    insta::assert_snapshot!(query.to_explain().unwrap(), @r#"
+    projection ("t2"."e"::int -> "e")
+        selection "t2"."g"::int = 1::int
+            scan "t2"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    buckets = [1-10000]
+    "#);
+}
+
+#[test]
+fn test_query_explain_costs() {
+    let sql = r#"explain (costs) select e from t2 where g = 1"#;
+
+    let mut metadata = RouterRuntimeMock::new();
+    metadata.set_table_row_count("t2", 2000);
+    let mut query = ExecutingQuery::from_text_and_params(&metadata, sql, vec![]).unwrap();
+    insta::assert_snapshot!(query.to_explain().unwrap(), @r#"
+    projection ("t2"."e"::int -> "e") (cost=4010.00 rows=10)
+        selection "t2"."g"::int = 1::int (cost=4000.00 rows=10)
+            scan "t2" (cost=2000.00 rows=2000)
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    buckets = [1-10000]
+    "#);
+}
+
+#[test]
+fn test_query_explain_costs_without_stats() {
+    // Tables without statistics are estimated with a default row count.
+    let sql = r#"explain (costs) select e from t2"#;
+
+    let metadata = &RouterRuntimeMock::new();
+    let mut query = ExecutingQuery::from_text_and_params(metadata, sql, vec![]).unwrap();
+    let explain = query.to_explain().unwrap();
+    assert!(
+        explain.contains(r#"scan "t2" (cost=1000.00 rows=1000)"#),
+        "{explain}"
+    );
+
+    // Plain EXPLAIN doesn't show costs.
+    let sql = r#"explain select e from t2"#;
+    let mut query = ExecutingQuery::from_text_and_params(metadata, sql, vec![]).unwrap();
+    let explain = query.to_explain().unwrap();
+    assert!(!explain.contains("cost="), "{explain}");
+}
@@ -18,7 +18,7 @@ use super::node::{
     Bound, BoundType, GroupBy, Having, Join, Like, OrderBy, Over, ReferenceTarget, Selection,
     Window,
 };
-use super::operator::OrderByEntity;
+use super::operator::{Collation, NullsOrder, OrderByEntity, OrderByType};
 use super::types::DerivedType;
 use super::{
     distribution, operator, Alias, ArithmeticExpr, BoolExpr, Case, Cast, Concat, Constant,
@@ -46,6 +46,9 @@ pub enum FunctionFeature {
     Distinct,
     /// Current function is a substring function and has one of 5 substring variants.
     Substring(Substring),
+    /// Current function is an aggregate function with `ORDER BY` over its first argument,
+    /// with the placement of `NULL`s and optionally an explicit collation of the key.
+    OrderBy(OrderByType, NullsOrder, Option<Collation>),
 }
 
 #[derive(Clone, Debug, Hash, Deserialize, PartialEq, Eq, Serialize, Copy)]
@@ -125,7 +128,15 @@ impl Nodes {
                 format_smolstr!("(right child of boolean node) from arena with index {right}"),
             )
         })?;
-        Ok(self.push(BoolExpr { left, op, right }.into()))
+        Ok(self.push(
+            BoolExpr {
+                left,
+                op,
+                right,
+                collation: None,
+            }
+            .into(),
+        ))
     }
 
     /// Adds arithmetic node.
@@ -345,7 +356,9 @@ impl<'plan> Comparator<'plan> {
                                     }
                                     for (l_elem, r_elem) in l_ordering.iter().zip(r_ordering.iter())
                                     {
-                                        if l_elem.order_type != r_elem.order_type {
+                                        if l_elem.order_type != r_elem.order_type
+                                            || l_elem.collation != r_elem.collation
+                                        {
                                             return Ok(false);
                                         }
                                         match (&l_elem.entity, &r_elem.entity) {
@@ -446,14 +459,17 @@ impl<'plan> Comparator<'plan> {
                         left: left_left,
                         op: op_left,
                         right: right_left,
+                        collation: collation_left,
                     }) => {
                         if let Expression::Bool(BoolExpr {
                             left: left_right,
                             op: op_right,
                             right: right_right,
+                            collation: collation_right,
                         }) = right
                         {
                             return Ok(*op_left == *op_right
+                                && collation_left == collation_right
                                 && self.are_subtrees_equal(*left_left, *left_right)?
                                 && self.are_subtrees_equal(*right_left, *right_right)?);
                         }
@@ -738,6 +754,7 @@ impl<'plan> Comparator<'plan> {
                 if let Some(ordering) = ordering {
                     for elem in ordering {
                         elem.order_type.hash(state);
+                        elem.collation.hash(state);
                     }
                 }
                 if let Some(ordering) = ordering {
@@ -838,8 +855,14 @@ impl<'plan> Comparator<'plan> {
                     self.hash_for_child_expr(*else_expr, depth);
                 }
             }
-            Expression::Bool(BoolExpr { op, left, right }) => {
+            Expression::Bool(BoolExpr {
+                op,
+                left,
+                right,
+                collation,
+            }) => {
                 op.hash(state);
+                collation.hash(state);
                 self.hash_for_child_expr(*left, depth);
                 self.hash_for_child_expr(*right, depth);
             }
@@ -1749,7 +1772,10 @@ impl Plan {
         let Ok(node) = self.get_expression_node(node_id) else {
             return false;
         };
-        if let Expression::Bool(BoolExpr { left, op, right }) = node {
+        if let Expression::Bool(BoolExpr {
+            left, op, right, ..
+        }) = node
+        {
             if *op != Bool::Eq {
                 return false;
             }
@@ -1798,6 +1824,9 @@ impl Plan {
                 return Ok(col_type_inner.is_none_or(|t| matches!(t, UnrestrictedType::Boolean)));
             }
             Expression::Parameter(_) => return Ok(true),
+            Expression::ScalarFunction(ScalarFunction { func_type, .. }) => {
+                return Ok(matches!(func_type.get(), Some(UnrestrictedType::Boolean)));
+            }
             _ => {}
         }
         Ok(false)
@@ -2008,6 +2037,73 @@ impl Plan {
         }
         Ok(())
     }
+
+    /// Recalculate `col_type` for all references in the expression subtree of the provided
+    /// node from the columns they currently point to. Should be called after a transformation
+    /// changes the output types of a relational node (e.g. wraps its column into a cast).
+    ///
+    /// References to Motion nodes are left untouched: the subtree under the motion may
+    /// already be truncated, so its output is the only source of truth.
+    pub fn update_reference_types_in_subtree(
+        &mut self,
+        node_id: NodeId,
+    ) -> Result<(), SbroadError> {
+        let filter = |node_id: NodeId| -> bool {
+            matches!(
+                self.get_node(node_id).expect("node in the plan must exist"),
+                Node::Expression(Expression::Reference { .. })
+                    | Node::Expression(Expression::SubQueryReference { .. })
+            )
+        };
+        let subtree = PostOrderWithFilter::with_capacity(
+            |node| self.nodes.expr_iter(node, false),
+            EXPR_CAPACITY,
+            Box::new(filter),
+        );
+        let references = subtree.populate_nodes(node_id);
+        let mut new_types: Vec<(NodeId, DerivedType)> = Vec::with_capacity(references.len());
+        for LevelNode(_, id) in references {
+            let (rel_id, position) = match self.get_expression_node(id)? {
+                // Leaf references point to the table columns and their types never change.
+                // For the union-like targets the column type is taken from the first child.
+                Expression::Reference(Reference {
+                    target, position, ..
+                }) => match target.first() {
+                    Some(rel_id) => (*rel_id, *position),
+                    None => continue,
+                },
+                Expression::SubQueryReference(SubQueryReference {
+                    rel_id, position, ..
+                }) => (*rel_id, *position),
+                _ => continue,
+            };
+            let rel_node = self.get_relation_node(rel_id)?;
+            if matches!(rel_node, Relational::Motion(_)) {
+                continue;
+            }
+            let col_id = *self
+                .get_row_list(rel_node.output())?
+                .get(position)
+                .ok_or_else(|| {
+                    SbroadError::NotFound(
+                        Entity::Column,
+                        format_smolstr!("at position {position} of node {rel_id}"),
+                    )
+                })?;
+            let col_type = self.get_expression_node(col_id)?.calculate_type(self)?;
+            new_types.push((id, col_type));
+        }
+        for (id, new_type) in new_types {
+            match self.get_mut_expression_node(id)? {
+                MutExpression::Reference(Reference { col_type, .. })
+                | MutExpression::SubQueryReference(SubQueryReference { col_type, .. }) => {
+                    *col_type = new_type;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Expression<'_> {
@@ -4,8 +4,11 @@ use pretty_assertions::assert_eq;
 use rand::random;
 use smol_str::SmolStr;
 
+use crate::ir::node::expression::MutExpression;
+use crate::ir::node::Alias;
 use crate::ir::relation::{Column, SpaceEngine, Table};
-use crate::ir::types::{DerivedType, UnrestrictedType};
+use crate::ir::transformation::redistribution::{MotionPolicy, Program};
+use crate::ir::types::{CastType, DerivedType, UnrestrictedType};
 use crate::ir::value::Value;
 use crate::ir::Plan;
 
@@ -168,3 +171,114 @@ fn derive_expr_type() {
         UnrestrictedType::Double
     );
 }
+
+#[test]
+fn update_reference_types_after_cast() {
+    // t(a int) [a]
+    // select a from (select a from t)
+    let mut plan = Plan::default();
+
+    let t = Table::new_sharded(
+        random(),
+        "t",
+        vec![column_integer_user_non_null(SmolStr::from("a"))],
+        &["a"],
+        &["a"],
+        SpaceEngine::Memtx,
+    )
+    .unwrap();
+    plan.add_rel(t);
+    let scan_id = plan.add_scan("t", None).unwrap();
+    let inner_proj_id = plan
+        .add_proj(scan_id, vec![], &["a"], false, false)
+        .unwrap();
+    let outer_proj_id = plan
+        .add_proj(inner_proj_id, vec![], &["a"], false, false)
+        .unwrap();
+
+    // Wrap the inner projection column into a cast to double.
+    let inner_output = plan.get_relational_output(inner_proj_id).unwrap();
+    let alias_id = plan.get_row_list(inner_output).unwrap()[0];
+    let ref_id = plan.get_child_under_alias(alias_id).unwrap();
+    let cast_id = plan.add_cast(ref_id, CastType::Double).unwrap();
+    if let MutExpression::Alias(Alias { child, .. }) =
+        plan.get_mut_expression_node(alias_id).unwrap()
+    {
+        *child = cast_id;
+    }
+
+    let outer_output = plan.get_relational_output(outer_proj_id).unwrap();
+    let outer_ref_id = plan
+        .get_child_under_alias(plan.get_row_list(outer_output).unwrap()[0])
+        .unwrap();
+    let outer_ref_type = |plan: &Plan| {
+        plan.get_expression_node(outer_ref_id)
+            .unwrap()
+            .calculate_type(plan)
+            .unwrap()
+    };
+    assert_eq!(
+        outer_ref_type(&plan),
+        DerivedType::new(UnrestrictedType::Integer)
+    );
+
+    plan.update_reference_types_in_subtree(outer_output)
+        .unwrap();
+    assert_eq!(
+        outer_ref_type(&plan),
+        DerivedType::new(UnrestrictedType::Double)
+    );
+}
+
+#[test]
+fn update_reference_types_skips_motion() {
+    // t(a int) [a]
+    // select a from (select a from t) with a motion in between
+    let mut plan = Plan::default();
+
+    let t = Table::new_sharded(
+        random(),
+        "t",
+        vec![column_integer_user_non_null(SmolStr::from("a"))],
+        &["a"],
+        &["a"],
+        SpaceEngine::Memtx,
+    )
+    .unwrap();
+    plan.add_rel(t);
+    let scan_id = plan.add_scan("t", None).unwrap();
+    let inner_proj_id = plan
+        .add_proj(scan_id, vec![], &["a"], false, false)
+        .unwrap();
+    let motion_id = plan
+        .add_motion(inner_proj_id, &MotionPolicy::Full, Program::default())
+        .unwrap();
+    let outer_proj_id = plan
+        .add_proj(motion_id, vec![], &["a"], false, false)
+        .unwrap();
+
+    // Change the motion output column type.
+    let motion_output = plan.get_relational_output(motion_id).unwrap();
+    let alias_id = plan.get_row_list(motion_output).unwrap()[0];
+    let ref_id = plan.get_child_under_alias(alias_id).unwrap();
+    let cast_id = plan.add_cast(ref_id, CastType::Double).unwrap();
+    if let MutExpression::Alias(Alias { child, .. }) =
+        plan.get_mut_expression_node(alias_id).unwrap()
+    {
+        *child = cast_id;
+    }
+
+    let outer_output = plan.get_relational_output(outer_proj_id).unwrap();
+    plan.update_reference_types_in_subtree(outer_output)
+        .unwrap();
+    let outer_ref_id = plan
+        .get_child_under_alias(plan.get_row_list(outer_output).unwrap()[0])
+        .unwrap();
+    assert_eq!(
+        plan.get_expression_node(outer_ref_id)
+            .unwrap()
+            .calculate_type(&plan)
+            .unwrap(),
+        DerivedType::new(UnrestrictedType::Integer)
+    );
+}
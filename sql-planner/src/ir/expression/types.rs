@@ -57,6 +57,24 @@ impl Plan {
     }
 }
 
+/// Common type of two CASE results. Unknown (NULL) types are skipped and
+/// numeric types are widened the same way the type system coerces them:
+/// `int` to `double` or `decimal` and `double` to `decimal`. Other types
+/// were already checked to match by the type analysis, so the first one wins.
+fn common_case_type(left: DerivedType, right: DerivedType) -> DerivedType {
+    use UnrestrictedType::{Decimal, Double, Integer};
+
+    match (left.get(), right.get()) {
+        (None, _) => right,
+        (Some(_), None) => left,
+        (Some(Integer), Some(Double)) | (Some(Double), Some(Integer)) => DerivedType::new(Double),
+        (Some(Integer | Double), Some(Decimal)) | (Some(Decimal), Some(Integer | Double)) => {
+            DerivedType::new(Decimal)
+        }
+        _ => left,
+    }
+}
+
 impl Expression<'_> {
     /// Calculate the type of the expression.
     pub fn calculate_type(&self, plan: &Plan) -> Result<DerivedType, SbroadError> {
@@ -71,30 +89,19 @@ impl Expression<'_> {
                 else_expr,
                 ..
             }) => {
-                // TODO: infer type using type system.
-
-                // Outer option -- for uninitialized type.
-                // Inner option -- for the case of Null met.
-                let mut case_ty_general: Option<DerivedType> = None;
-                let mut check_types_corresponds = |another_ty: &DerivedType| {
-                    if let Some(case_ty) = case_ty_general {
-                        if let (None, Some(_)) = (case_ty.get(), another_ty.get()) {
-                            case_ty_general = Some(*another_ty);
-                        }
-                    } else {
-                        case_ty_general = Some(*another_ty)
-                    }
-                };
-
-                for (_, ret_expr) in when_blocks {
-                    let ret_expr_type = plan.get_node_type(*ret_expr)?;
-                    check_types_corresponds(&ret_expr_type);
-                }
-                if let Some(else_expr) = else_expr {
-                    let else_expr_type = plan.get_node_type(*else_expr)?;
-                    check_types_corresponds(&else_expr_type);
+                // The type analysis coerces the results to a common type,
+                // but the plan may still mix numeric types (e.g. parameter
+                // branches), so the widest of them is taken.
+                let results = when_blocks
+                    .iter()
+                    .map(|(_, result)| result)
+                    .chain(else_expr.iter());
+                let mut case_ty = DerivedType::unknown();
+                for result in results {
+                    let result_ty = plan.get_node_type(*result)?;
+                    case_ty = common_case_type(case_ty, result_ty);
                 }
-                case_ty_general.expect("Case type must be known")
+                case_ty
             }
             Expression::Alias(Alias { child, .. }) => plan.get_node_type(*child)?,
             Expression::Bool(_) | Expression::Unary(_) | Expression::Like { .. } => {
@@ -171,11 +178,17 @@ impl Expression<'_> {
                         }
                         last_ty
                     }
-                    "abs" => {
-                        let child = children.first().ok_or(SbroadError::Invalid(
-                            Entity::SQLFunction,
-                            Some("abs function must have an argument".to_smolstr()),
-                        ))?;
+                    "abs" | "ceil" | "floor" | "round" => {
+                        // Rounding with the given number of digits always produces a decimal.
+                        if name.as_str() == "round" && children.len() == 2 {
+                            return Ok(DerivedType::new(UnrestrictedType::Decimal));
+                        }
+                        let child = children.first().ok_or_else(|| {
+                            SbroadError::Invalid(
+                                Entity::SQLFunction,
+                                Some(format_smolstr!("{name} function must have an argument")),
+                            )
+                        })?;
                         let child_ty = plan.get_expression_node(*child)?;
                         child_ty.calculate_type(plan)?
                     }
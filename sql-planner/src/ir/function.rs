@@ -3,14 +3,17 @@ use crate::executor::engine::helpers::to_user;
 use crate::ir::aggregates::AggregateKind;
 use crate::ir::node::expression::{Expression, MutExpression};
 use crate::ir::node::{ArenaType, Node96};
-use crate::ir::node::{Cast, NodeId, ScalarFunction};
+use crate::ir::node::{Cast, Constant, NodeId, ScalarFunction};
 use crate::ir::types::CastType;
+use crate::ir::value::{value_to_decimal_or_error, Value};
 use crate::ir::Plan;
 use serde::{Deserialize, Serialize};
 use smol_str::{format_smolstr, SmolStr, ToSmolStr};
 use sql_type_system::type_system::TypeAnalyzer;
+use tarantool::decimal::Decimal;
 
-use super::expression::{FunctionFeature, VolatilityType};
+use super::expression::{Comparator, FunctionFeature, VolatilityType};
+use super::operator::{Collation, NullsOrder, OrderByType};
 use super::types::{DerivedType, UnrestrictedType};
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
@@ -190,6 +193,109 @@ impl Plan {
         Ok(id)
     }
 
+    /// Add aggregate function with `ORDER BY` modifier to plan.
+    ///
+    /// Only `group_concat` (and its `string_agg` alias) depends on the order of the
+    /// aggregated rows. The ordering key must be the aggregated expression itself.
+    pub fn add_ordered_aggregate_function(
+        &mut self,
+        kind: AggregateKind,
+        children: Vec<NodeId>,
+        order_key: NodeId,
+        order_type: OrderByType,
+        nulls: NullsOrder,
+        collation: Option<Collation>,
+    ) -> Result<NodeId, SbroadError> {
+        if kind != AggregateKind::GRCONCAT {
+            return Err(SbroadError::Invalid(
+                Entity::Query,
+                Some(format_smolstr!(
+                    "ORDER BY modifier is not supported for aggregate: {}.",
+                    to_user(kind.to_string())
+                )),
+            ));
+        }
+        let is_same_key = match children.first() {
+            Some(first) => Comparator::new(self).are_subtrees_equal(*first, order_key)?,
+            None => false,
+        };
+        if !is_same_key {
+            return Err(SbroadError::Invalid(
+                Entity::Query,
+                Some(
+                    "ORDER BY inside GROUP_CONCAT aggregate function must use the aggregated expression."
+                        .into(),
+                ),
+            ));
+        }
+        let func_id = self.add_aggregate_function(kind, children, false)?;
+        if let MutExpression::ScalarFunction(ScalarFunction { feature, .. }) =
+            self.get_mut_expression_node(func_id)?
+        {
+            *feature = Some(FunctionFeature::OrderBy(order_type, nulls, collation));
+        }
+        Ok(func_id)
+    }
+
+    /// Add ordered-set aggregate `percentile_cont` or `percentile_disc` to plan.
+    ///
+    /// Tarantool SQL has no percentile aggregates, so the function is rewritten into
+    /// a stable Lua builtin over `group_concat` of the ordering key. All key values of
+    /// the group are gathered on the router, which is what makes the percentile exact.
+    /// `percentile_cont` interpolates in decimal arithmetic and returns a decimal,
+    /// `percentile_disc` returns a value of the key type.
+    pub fn add_percentile_function(
+        &mut self,
+        function: &Function,
+        fraction: NodeId,
+        key: NodeId,
+        order_type: OrderByType,
+    ) -> Result<NodeId, SbroadError> {
+        if let Expression::Constant(Constant { value }) = self.get_expression_node(fraction)? {
+            if !matches!(value, Value::Null) {
+                let decimal = value_to_decimal_or_error(value)?;
+                if decimal < Decimal::from(0) || decimal > Decimal::from(1) {
+                    return Err(SbroadError::Invalid(
+                        Entity::Query,
+                        Some(format_smolstr!(
+                            "percentile value {value} is not between 0 and 1"
+                        )),
+                    ));
+                }
+            }
+        }
+        let key_type = match self.calculate_expression_type(key)? {
+            None => UnrestrictedType::Double,
+            Some(
+                ty @ (UnrestrictedType::Integer
+                | UnrestrictedType::Decimal
+                | UnrestrictedType::Double),
+            ) => ty,
+            Some(other) => {
+                return Err(SbroadError::Invalid(
+                    Entity::Query,
+                    Some(format_smolstr!(
+                        "ORDER BY inside WITHIN GROUP of {} must be numeric, got {}",
+                        to_user(&function.name),
+                        other
+                    )),
+                ));
+            }
+        };
+
+        let key_str = self.add_cast(key, CastType::String)?;
+        let values = self.add_aggregate_function(AggregateKind::GRCONCAT, vec![key_str], false)?;
+        let fraction = self.add_cast(fraction, CastType::Decimal)?;
+        let is_desc = self.add_const(Value::Boolean(order_type == OrderByType::Desc));
+        let func_id = self.add_stable_function(function, vec![values, fraction, is_desc], None)?;
+        if function.name == "percentile_disc" {
+            // The discrete percentile is one of the key values, it is
+            // returned as text and cast back to the key type.
+            return self.add_cast(func_id, CastType::try_from(&key_type)?);
+        }
+        Ok(func_id)
+    }
+
     /// Add builtin window function to plan
     pub fn add_builtin_window_function(
         &mut self,
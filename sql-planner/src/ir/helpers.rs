@@ -162,8 +162,17 @@ impl Plan {
                         self.formatted_arena_node(buf, tabulation_number + 1, *else_expr)?;
                     }
                 }
-                Expression::Bool(BoolExpr { op, left, right }) => {
-                    writeln!(buf, "Bool [op: {op}]")?;
+                Expression::Bool(BoolExpr {
+                    op,
+                    left,
+                    right,
+                    collation,
+                }) => {
+                    if let Some(collation) = collation {
+                        writeln!(buf, "Bool [op: {op}, collation: {collation}]")?;
+                    } else {
+                        writeln!(buf, "Bool [op: {op}]")?;
+                    }
                     writeln_with_tabulation(buf, tabulation_number + 1, "Left child")?;
                     self.formatted_arena_node(buf, tabulation_number + 1, *left)?;
                     writeln_with_tabulation(buf, tabulation_number + 1, "Right child")?;
@@ -503,7 +512,12 @@ impl Plan {
                     Relational::Insert(_) => writeln!(buf, "Insert")?,
                     Relational::Intersect(_) => writeln!(buf, "Intersect")?,
                     Relational::Except(_) => writeln!(buf, "Except")?,
-                    Relational::Limit(Limit { limit, .. }) => writeln!(buf, "Limit {limit}")?,
+                    Relational::Limit(Limit {
+                        limit,
+                        offset,
+                        with_ties,
+                        ..
+                    }) => writeln!(buf, "Limit {limit:?} offset {offset} with ties {with_ties}")?,
                 }
                 // Print children.
                 match relation {
@@ -23,7 +23,10 @@ use super::{
 };
 use crate::ir::{
     acl::{AlterOption, AuditPolicyOption, GrantRevokeType},
-    ddl::{ColumnDef, Language, ParamDef, SetParamScopeType, SetParamValue},
+    ddl::{
+        CheckDef, ColumnDef, DefaultDef, Language, NotificationCommand, ParamDef,
+        SetParamScopeType, SetParamValue, TransactionIsolationLevel,
+    },
     distribution::Distribution,
     helpers::RepeatableState,
     transformation::redistribution::{ColumnPosition, MotionPolicy, Program},
@@ -124,6 +127,8 @@ pub struct BoolExpr {
     pub op: operator::Bool,
     /// Right branch expression node index in the plan node arena.
     pub right: NodeId,
+    /// Collation of the comparison set with `COLLATE`.
+    pub collation: Option<operator::Collation>,
 }
 
 impl From<BoolExpr> for NodeAligned {
@@ -532,6 +537,9 @@ pub struct ScanCte {
     pub child: NodeId,
     /// An output tuple with aliases.
     pub output: NodeId,
+    /// Whether CTE is recursive. The child of the recursive CTE is a union
+    /// of the working table (left) and the recursive term (right).
+    pub is_recursive: bool,
 }
 
 impl From<ScanCte> for NodeAligned {
@@ -548,6 +556,9 @@ pub struct Except {
     pub right: NodeId,
     /// Outputs tuple node index in the plan node arena.
     pub output: NodeId,
+    /// `EXCEPT ALL`: the duplicates are preserved, a row is output as many
+    /// times as its left count exceeds its right count.
+    pub all: bool,
 }
 
 impl From<Except> for NodeAligned {
@@ -599,6 +610,9 @@ pub struct Intersect {
     pub right: NodeId,
     // id of the output tuple
     pub output: NodeId,
+    /// `INTERSECT ALL`: the duplicates are preserved, a row is output
+    /// the minimum of its left and right counts times.
+    pub all: bool,
 }
 
 impl From<Intersect> for NodeAligned {
@@ -659,8 +673,14 @@ impl From<Join> for NodeAligned {
 pub struct Limit {
     /// Output tuple.
     pub output: NodeId,
-    // The limit value constant that comes after LIMIT keyword.
-    pub limit: u64,
+    // The limit value constant that comes after LIMIT keyword,
+    // `None` when only the rows to skip are specified.
+    pub limit: Option<u64>,
+    /// The number of rows to skip (`OFFSET`).
+    pub offset: u64,
+    /// Whether the rows tied with the last one according to `ORDER BY`
+    /// are returned as well (`FETCH FIRST ... WITH TIES`).
+    pub with_ties: bool,
     /// Select statement that is being limited.
     /// Note that it can be a complex statement, like SELECT .. UNION ALL SELECT .. LIMIT 100,
     /// in that case limit is applied to the result of union.
@@ -669,7 +689,7 @@ pub struct Limit {
 
 impl From<Limit> for NodeAligned {
     fn from(value: Limit) -> Self {
-        Self::Node32(Node32::Limit(value))
+        Self::Node64(Node64::Limit(value))
     }
 }
 
@@ -741,6 +761,44 @@ pub struct ScanRelation {
     pub relation: SmolStr,
     /// Index name.
     pub indexed_by: Option<SmolStr>,
+    /// `TABLESAMPLE` specification (boxed to keep the node small).
+    pub sample: Option<Box<TableSample>>,
+}
+
+/// Sampling method of the `TABLESAMPLE` clause.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum SampleMethod {
+    /// Every row is returned with the given probability.
+    Bernoulli,
+}
+
+impl Display for SampleMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SampleMethod::Bernoulli => write!(f, "bernoulli"),
+        }
+    }
+}
+
+/// Sampling of a table scan: `TABLESAMPLE method(percent) [REPEATABLE(seed)]`.
+/// The sampling is done by each storage over its local rows.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct TableSample {
+    pub method: SampleMethod,
+    /// Percentage of rows to return, in the range (0, 100].
+    pub percent: Decimal,
+    /// Seed making the sample reproducible.
+    pub seed: Option<i64>,
+}
+
+impl Display for TableSample {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "tablesample {}({})", self.method, self.percent)?;
+        if let Some(seed) = self.seed {
+            write!(f, " repeatable({seed})")?;
+        }
+        Ok(())
+    }
 }
 
 impl From<ScanRelation> for NodeAligned {
@@ -1005,6 +1063,13 @@ pub struct CreateTable {
     /// When this is enabled, there is no separate 'bucket_id' index in the table.
     /// Instead, 'bucket_id' is included as the first part of the primary key index.
     pub pk_contains_bucket_id: bool,
+    /// Name of the column filled from the cluster sequence
+    /// (`GENERATED ALWAYS AS IDENTITY`).
+    pub identity: Option<SmolStr>,
+    /// CHECK constraints of the table columns.
+    pub checks: Vec<CheckDef>,
+    /// Declared default values of the table columns.
+    pub defaults: Vec<DefaultDef>,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -1033,6 +1098,10 @@ pub enum AlterColumn {
         column: ColumnDef,
         if_not_exists: bool,
     },
+    Drop {
+        name: SmolStr,
+        if_exists: bool,
+    },
     Rename {
         from: SmolStr,
         to: SmolStr,
@@ -1131,12 +1200,22 @@ impl From<RenameRoutine> for NodeAligned {
     }
 }
 
+/// Column of the index being created.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct IndexColumn {
+    pub name: SmolStr,
+    /// Collation of the column set with `COLLATE`.
+    pub collation: Option<operator::Collation>,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub struct CreateIndex {
     pub name: SmolStr,
     pub table_name: SmolStr,
-    pub columns: Vec<SmolStr>,
+    pub columns: Vec<IndexColumn>,
     pub unique: bool,
+    /// False for `NULLS NOT DISTINCT`: NULL keys collide in the unique index.
+    pub nulls_distinct: bool,
     pub if_not_exists: bool,
     pub index_type: IndexType,
     pub bloom_fpr: Option<Decimal>,
@@ -1173,6 +1252,38 @@ impl From<RenameIndex> for NodeAligned {
     }
 }
 
+/// `COMMENT ON TABLE t IS '...'` or `COMMENT ON COLUMN t.a IS '...'`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct Comment {
+    pub table_name: SmolStr,
+    /// Column name, only for `COMMENT ON COLUMN`.
+    pub column_name: Option<SmolStr>,
+    /// New comment text, `None` for `IS NULL` (the comment is removed).
+    pub comment: Option<SmolStr>,
+    pub timeout: Decimal,
+}
+
+impl From<Comment> for NodeAligned {
+    fn from(value: Comment) -> Self {
+        Self::Node136(Node136::Comment(value))
+    }
+}
+
+/// `LISTEN`, `UNLISTEN` or `NOTIFY`. Listening is a state of a PostgreSQL
+/// protocol session, notifications are delivered to the sessions of all
+/// the instances of the cluster.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct Notification {
+    pub command: NotificationCommand,
+    pub timeout: Decimal,
+}
+
+impl From<Notification> for NodeAligned {
+    fn from(value: Notification) -> Self {
+        Self::Node136(Node136::Notification(value))
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Hash, Serialize)]
 pub enum FrameType {
     Range,
@@ -1299,9 +1410,13 @@ impl From<SetParam> for NodeAligned {
     }
 }
 
-// TODO: Fill with actual values.
+// TODO: Fill with other transaction modes.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub struct SetTransaction {
+    /// `Local` for `SET TRANSACTION`,
+    /// `Session` for `SET SESSION CHARACTERISTICS AS TRANSACTION`.
+    pub scope_type: SetParamScopeType,
+    pub isolation_level: Option<TransactionIsolationLevel>,
     pub timeout: Decimal,
 }
 
@@ -1455,7 +1570,6 @@ pub enum Node32 {
     Concat(Concat),
     Like(Like),
     Bool(BoolExpr),
-    Limit(Limit),
     Arithmetic(ArithmeticExpr),
     Trim(Trim),
     Index(IndexExpr),
@@ -1486,7 +1600,6 @@ impl Node32 {
             Node32::Alias(alias) => NodeOwned::Expression(ExprOwned::Alias(alias)),
             Node32::Arithmetic(arithm) => NodeOwned::Expression(ExprOwned::Arithmetic(arithm)),
             Node32::Bool(bool) => NodeOwned::Expression(ExprOwned::Bool(bool)),
-            Node32::Limit(limit) => NodeOwned::Relational(RelOwned::Limit(limit)),
             Node32::Index(index) => NodeOwned::Expression(ExprOwned::Index(index)),
             Node32::Cast(cast) => NodeOwned::Expression(ExprOwned::Cast(cast)),
             Node32::Concat(concat) => NodeOwned::Expression(ExprOwned::Concat(concat)),
@@ -1508,6 +1621,9 @@ impl Node32 {
                 Tcl::Begin => NodeOwned::Tcl(Tcl::Begin),
                 Tcl::Commit => NodeOwned::Tcl(Tcl::Commit),
                 Tcl::Rollback => NodeOwned::Tcl(Tcl::Rollback),
+                Tcl::Savepoint(name) => NodeOwned::Tcl(Tcl::Savepoint(name)),
+                Tcl::Release(name) => NodeOwned::Tcl(Tcl::Release(name)),
+                Tcl::RollbackTo(name) => NodeOwned::Tcl(Tcl::RollbackTo(name)),
             },
             Node32::CreateSchema => NodeOwned::Ddl(DdlOwned::CreateSchema),
             Node32::DropSchema => NodeOwned::Ddl(DdlOwned::DropSchema),
@@ -1548,6 +1664,7 @@ pub enum Node64 {
     Invalid(Invalid),
     Over(Over),
     TruncateTable(TruncateTable),
+    Limit(Limit),
 }
 
 impl Node64 {
@@ -1556,6 +1673,7 @@ impl Node64 {
         match self {
             Node64::AnonymousBlock(block) => NodeOwned::Block(BlockOwned::Anonymous(block)),
             Node64::Over(over) => NodeOwned::Expression(ExprOwned::Over(over)),
+            Node64::Limit(limit) => NodeOwned::Relational(RelOwned::Limit(limit)),
             Node64::Case(case) => NodeOwned::Expression(ExprOwned::Case(case)),
             Node64::Invalid(invalid) => NodeOwned::Invalid(invalid),
             Node64::CreateRole(create_role) => NodeOwned::Acl(AclOwned::CreateRole(create_role)),
@@ -1653,6 +1771,8 @@ pub enum Node136 {
     MigrateTo(MigrateTo),
     ChangeConfig(ChangeConfig),
     Window(Window),
+    Comment(Comment),
+    Notification(Notification),
 }
 
 impl Node136 {
@@ -1683,6 +1803,10 @@ impl Node136 {
                 NodeOwned::Plugin(PluginOwned::ChangeConfig(change_config))
             }
             Node136::Window(window) => NodeOwned::Expression(ExprOwned::Window(window)),
+            Node136::Comment(comment) => NodeOwned::Ddl(DdlOwned::Comment(comment)),
+            Node136::Notification(notification) => {
+                NodeOwned::Ddl(DdlOwned::Notification(notification))
+            }
         }
     }
 }
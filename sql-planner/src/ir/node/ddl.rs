@@ -1,6 +1,7 @@
 use super::{
-    AlterSystem, AlterTable, Backup, CreateIndex, CreateProc, CreateTable, DropIndex, DropProc,
-    DropTable, NodeAligned, RenameIndex, RenameRoutine, SetParam, SetTransaction, TruncateTable,
+    AlterSystem, AlterTable, Backup, Comment, CreateIndex, CreateProc, CreateTable, DropIndex,
+    DropProc, DropTable, NodeAligned, Notification, RenameIndex, RenameRoutine, SetParam,
+    SetTransaction, TruncateTable,
 };
 use crate::errors::{Entity, SbroadError};
 use crate::ir::Node32;
@@ -26,6 +27,8 @@ pub enum DdlOwned {
     SetTransaction(SetTransaction),
     Backup(Backup),
     RenameIndex(RenameIndex),
+    Comment(Comment),
+    Notification(Notification),
 }
 
 impl DdlOwned {
@@ -48,6 +51,8 @@ impl DdlOwned {
             | DdlOwned::CreateProc(CreateProc { ref timeout, .. })
             | DdlOwned::DropProc(DropProc { ref timeout, .. })
             | DdlOwned::RenameIndex(RenameIndex { ref timeout, .. })
+            | DdlOwned::Comment(Comment { ref timeout, .. })
+            | DdlOwned::Notification(Notification { ref timeout, .. })
             | DdlOwned::RenameRoutine(RenameRoutine { ref timeout, .. }) => {
                 timeout.to_smolstr().parse().map_err(|e| {
                     SbroadError::Invalid(
@@ -76,6 +81,8 @@ impl DdlOwned {
             | DdlOwned::CreateSchema
             | DdlOwned::SetParam(_)
             | DdlOwned::Backup(_)
+            | DdlOwned::Comment(_)
+            | DdlOwned::Notification(_)
             | DdlOwned::SetTransaction(_) => false,
 
             DdlOwned::DropTable(_)
@@ -151,6 +158,8 @@ impl From<DdlOwned> for NodeAligned {
             DdlOwned::SetTransaction(set_trans) => set_trans.into(),
             DdlOwned::Backup(backup) => backup.into(),
             DdlOwned::RenameIndex(rename_index) => rename_index.into(),
+            DdlOwned::Comment(comment) => comment.into(),
+            DdlOwned::Notification(notification) => notification.into(),
         }
     }
 }
@@ -174,6 +183,8 @@ pub enum MutDdl<'a> {
     SetTransaction(&'a mut SetTransaction),
     Backup(&'a mut Backup),
     RenameIndex(&'a mut RenameIndex),
+    Comment(&'a mut Comment),
+    Notification(&'a mut Notification),
 }
 
 #[allow(clippy::module_name_repetitions)]
@@ -195,6 +206,8 @@ pub enum Ddl<'a> {
     SetTransaction(&'a SetTransaction),
     Backup(&'a Backup),
     RenameIndex(&'a RenameIndex),
+    Comment(&'a Comment),
+    Notification(&'a Notification),
 }
 
 impl Ddl<'_> {
@@ -217,6 +230,8 @@ impl Ddl<'_> {
             | Ddl::CreateProc(CreateProc { ref timeout, .. })
             | Ddl::DropProc(DropProc { ref timeout, .. })
             | Ddl::RenameIndex(RenameIndex { ref timeout, .. })
+            | Ddl::Comment(Comment { ref timeout, .. })
+            | Ddl::Notification(Notification { ref timeout, .. })
             | Ddl::RenameRoutine(RenameRoutine { ref timeout, .. }) => {
                 timeout.to_smolstr().parse().map_err(|e| {
                     SbroadError::Invalid(
@@ -296,6 +311,8 @@ impl Ddl<'_> {
             Ddl::SetTransaction(set_trans) => DdlOwned::SetTransaction((*set_trans).clone()),
             Ddl::AlterTable(alter_table) => DdlOwned::AlterTable((*alter_table).clone()),
             Ddl::RenameIndex(rename_index) => DdlOwned::RenameIndex((*rename_index).clone()),
+            Ddl::Comment(comment) => DdlOwned::Comment((*comment).clone()),
+            Ddl::Notification(notification) => DdlOwned::Notification((*notification).clone()),
         }
     }
 }
@@ -1,13 +1,19 @@
 use super::{NodeAligned, NodeOwned};
 use crate::ir::{Entity, Node, Node32, NodeId, Plan, SbroadError};
 use serde::{Deserialize, Serialize};
-use smol_str::format_smolstr;
+use smol_str::{format_smolstr, SmolStr};
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Tcl {
     Begin,
     Commit,
     Rollback,
+    /// `SAVEPOINT <name>`
+    Savepoint(SmolStr),
+    /// `RELEASE [SAVEPOINT] <name>`
+    Release(SmolStr),
+    /// `ROLLBACK TO [SAVEPOINT] <name>`
+    RollbackTo(SmolStr),
 }
 
 impl Tcl {
@@ -17,6 +23,9 @@ impl Tcl {
             Tcl::Begin => "Begin",
             Tcl::Commit => "Commit",
             Tcl::Rollback => "Rollback",
+            Tcl::Savepoint(_) => "Savepoint",
+            Tcl::Release(_) => "Release",
+            Tcl::RollbackTo(_) => "RollbackTo",
         }
     }
 }
@@ -27,6 +36,9 @@ impl From<Tcl> for NodeAligned {
             Tcl::Begin => Self::Node32(Node32::Tcl(Tcl::Begin)),
             Tcl::Commit => Self::Node32(Node32::Tcl(Tcl::Commit)),
             Tcl::Rollback => Self::Node32(Node32::Tcl(Tcl::Rollback)),
+            Tcl::Savepoint(name) => Self::Node32(Node32::Tcl(Tcl::Savepoint(name))),
+            Tcl::Release(name) => Self::Node32(Node32::Tcl(Tcl::Release(name))),
+            Tcl::RollbackTo(name) => Self::Node32(Node32::Tcl(Tcl::RollbackTo(name))),
         }
     }
 }
@@ -37,6 +49,9 @@ impl From<Tcl> for NodeOwned {
             Tcl::Begin => NodeOwned::Tcl(Tcl::Begin),
             Tcl::Commit => NodeOwned::Tcl(Tcl::Commit),
             Tcl::Rollback => NodeOwned::Tcl(Tcl::Rollback),
+            Tcl::Savepoint(name) => NodeOwned::Tcl(Tcl::Savepoint(name)),
+            Tcl::Release(name) => NodeOwned::Tcl(Tcl::Release(name)),
+            Tcl::RollbackTo(name) => NodeOwned::Tcl(Tcl::RollbackTo(name)),
         }
     }
 }
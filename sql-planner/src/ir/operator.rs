@@ -11,7 +11,7 @@ use crate::ir::expression::PlanExpr;
 use crate::ir::node::{
     Alias, Delete, Except, GroupBy, Having, Insert, Intersect, Join, Motion, MutNode, NodeId,
     OrderBy, Projection, Reference, ReferenceTarget, Row, ScanCte, ScanRelation, ScanSubQuery,
-    Selection, SubQueryReference, Union, UnionAll, Update, Values, ValuesRow,
+    Selection, SubQueryReference, UnaryExpr, Union, UnionAll, Update, Values, ValuesRow,
 };
 use crate::ir::tree::traversal::{LevelNode, PostOrderWithFilter, EXPR_CAPACITY, REL_CAPACITY};
 use crate::ir::Plan;
@@ -349,10 +349,90 @@ impl Display for OrderByType {
     }
 }
 
+/// Placement of `NULL` values in a sorted sequence (`NULLS FIRST` or `NULLS LAST`).
+#[derive(Clone, Copy, Deserialize, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum NullsOrder {
+    First,
+    Last,
+}
+
+impl NullsOrder {
+    /// Placement used by the storages when it is not specified explicitly:
+    /// tarantool treats `NULL` as the smallest value.
+    #[must_use]
+    pub fn default_for(order_type: &OrderByType) -> Self {
+        match order_type {
+            OrderByType::Asc => NullsOrder::First,
+            OrderByType::Desc => NullsOrder::Last,
+        }
+    }
+}
+
+impl Display for NullsOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NullsOrder::First => write!(f, "nulls first"),
+            NullsOrder::Last => write!(f, "nulls last"),
+        }
+    }
+}
+
+/// String collation set explicitly with a `COLLATE` clause.
+#[derive(Clone, Copy, Deserialize, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum Collation {
+    /// Byte-wise comparison (`"C"`, `"POSIX"` or `"binary"`).
+    Binary,
+    /// Unicode comparison (`"unicode"`).
+    Unicode,
+    /// Case-insensitive unicode comparison (`"unicode_ci"`).
+    UnicodeCi,
+}
+
+impl Collation {
+    /// Resolve collation by its SQL name.
+    ///
+    /// # Errors
+    /// - collation with such name doesn't exist
+    pub fn from_name(name: &str) -> Result<Self, SbroadError> {
+        match name {
+            "C" | "POSIX" | "binary" => Ok(Collation::Binary),
+            "unicode" => Ok(Collation::Unicode),
+            "unicode_ci" => Ok(Collation::UnicodeCi),
+            _ => Err(SbroadError::NotFound(
+                Entity::Collation,
+                format_smolstr!("\"{name}\""),
+            )),
+        }
+    }
+}
+
+impl Display for Collation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Names of the corresponding tarantool collations.
+        match self {
+            Collation::Binary => write!(f, "binary"),
+            Collation::Unicode => write!(f, "unicode"),
+            Collation::UnicodeCi => write!(f, "unicode_ci"),
+        }
+    }
+}
+
+/// Sort key of `ORDER BY` expressed in terms of its output columns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    /// Column at the position.
+    Column(usize),
+    /// `IS NULL` check of the column at the position.
+    IsNull(usize),
+    /// `IS NOT NULL` check of the column at the position.
+    IsNotNull(usize),
+}
+
 #[derive(Clone, Deserialize, Debug, PartialEq, Eq, Serialize)]
 pub struct OrderByElement {
     pub entity: OrderByEntity,
     pub order_type: Option<OrderByType>,
+    pub collation: Option<Collation>,
 }
 
 impl Plan {
@@ -400,7 +480,54 @@ impl Plan {
     /// - children nodes are not relational
     /// - children tuples are invalid
     /// - children tuples have mismatching structure
-    pub fn add_except(&mut self, left: NodeId, right: NodeId) -> Result<NodeId, SbroadError> {
+    pub fn add_except(
+        &mut self,
+        left: NodeId,
+        right: NodeId,
+        all: bool,
+    ) -> Result<NodeId, SbroadError> {
+        self.check_set_op_children(left, right, "except")?;
+        let output = self.add_row_for_union_except(left, right)?;
+        let except = Except {
+            left,
+            right,
+            output,
+            all,
+        };
+
+        self.add_relational(except.into())
+    }
+
+    /// Adds intersect node.
+    ///
+    /// # Errors
+    /// - children nodes are not relational
+    /// - children tuples are invalid
+    /// - children tuples have mismatching structure
+    pub fn add_intersect(
+        &mut self,
+        left: NodeId,
+        right: NodeId,
+        all: bool,
+    ) -> Result<NodeId, SbroadError> {
+        self.check_set_op_children(left, right, "intersect")?;
+        let output = self.add_row_for_union_except(left, right)?;
+        let intersect = Intersect {
+            left,
+            right,
+            output,
+            all,
+        };
+
+        self.add_relational(intersect.into())
+    }
+
+    fn check_set_op_children(
+        &self,
+        left: NodeId,
+        right: NodeId,
+        op: &str,
+    ) -> Result<(), SbroadError> {
         let child_row_len = |child: NodeId, plan: &Plan| -> Result<usize, SbroadError> {
             let child_output = plan.get_relation_node(child)?.output();
             Ok(plan
@@ -413,18 +540,10 @@ impl Plan {
         let right_row_len = child_row_len(right, self)?;
         if left_row_len != right_row_len {
             return Err(SbroadError::UnexpectedNumberOfValues(format_smolstr!(
-                "children tuples have mismatching amount of columns in except node: left {left_row_len}, right {right_row_len}"
+                "children tuples have mismatching amount of columns in {op} node: left {left_row_len}, right {right_row_len}"
             )));
         }
-
-        let output = self.add_row_for_union_except(left, right)?;
-        let except = Except {
-            left,
-            right,
-            output,
-        };
-
-        self.add_relational(except.into())
+        Ok(())
     }
 
     /// Add `Update` relational node.
@@ -700,7 +819,7 @@ impl Plan {
             rel.columns
                 .iter()
                 .enumerate()
-                .filter(|(_, c)| ColumnRole::User.eq(c.get_role()))
+                .filter(|(i, c)| ColumnRole::User.eq(c.get_role()) && rel.identity != Some(*i))
                 .map(|(i, _)| i)
                 .collect()
         } else {
@@ -712,6 +831,16 @@ impl Plan {
             let mut cols: Vec<usize> = Vec::with_capacity(names.len());
             for name in columns {
                 match names.get(name.as_str()) {
+                    Some((&ColumnRole::User, pos)) if rel.identity == Some(*pos) => {
+                        return Err(SbroadError::FailedTo(
+                            Action::Insert,
+                            Some(Entity::Column),
+                            format_smolstr!(
+                                "cannot insert a non-DEFAULT value into identity column {}",
+                                to_user(name)
+                            ),
+                        ))
+                    }
                     Some((&ColumnRole::User, pos)) => cols.push(*pos),
                     Some((&ColumnRole::Sharding, _)) => {
                         return Err(SbroadError::FailedTo(
@@ -802,6 +931,7 @@ impl Plan {
                 relation: SmolStr::from(table),
                 alias: alias.map(SmolStr::from),
                 indexed_by: None,
+                sample: None,
             };
 
             return self.add_relational(scan.into());
@@ -1245,13 +1375,25 @@ impl Plan {
                 }
             };
 
-            let mut col_aliases = HashMap::new();
-            for col in self.get_row_list(proj_output_id)? {
-                let col_expr = self.get_expression_node(*col)?;
-                if let Expression::Alias(Alias { name, child }) = col_expr {
-                    col_aliases.insert(name.clone(), *child);
-                }
-            }
+            // Select list aliases are resolved with the same rules as column names,
+            // so an alias met on several positions of the projection is ambiguous.
+            let proj_columns = ColumnPositionMap::new(self, proj_id)?;
+            let proj_cols = self.get_row_list(proj_output_id)?.clone();
+            let alias_child = |plan: &Plan, name: &SmolStr| -> Result<NodeId, SbroadError> {
+                let pos = proj_columns.get(name).map_err(|e| match e {
+                    SbroadError::NotFound(..) => SbroadError::NotFound(
+                        Entity::Column,
+                        format_smolstr!("with name {name} in group by"),
+                    ),
+                    e => e,
+                })?;
+                let Expression::Alias(Alias { child, .. }) =
+                    plan.get_expression_node(proj_cols[pos])?
+                else {
+                    unreachable!("expected alias in projection output");
+                };
+                Ok(*child)
+            };
 
             // Case when the gr_expr[i] = Alias { .. }, i. e. simple group by "col_name"
             // E.g. `select a as a_1 from t group by a_1`
@@ -1267,22 +1409,12 @@ impl Plan {
 
                 let mut idx_to_new_child = HashMap::new();
                 for (name, idx) in gr_aliases {
-                    if let Some(alias_child) = col_aliases.get(&name) {
-                        let new_top_id = SubtreeCloner::clone_subtree(self, *alias_child)?;
+                    let child_id = alias_child(self, &name)?;
+                    let new_top_id = SubtreeCloner::clone_subtree(self, child_id)?;
 
-                        self.replace_target_in_subtree(
-                            new_top_id,
-                            proj_target_id,
-                            groupby_target_rel,
-                        )?;
+                    self.replace_target_in_subtree(new_top_id, proj_target_id, groupby_target_rel)?;
 
-                        idx_to_new_child.insert(idx, new_top_id);
-                    } else {
-                        return Err(SbroadError::NotFound(
-                            Entity::Column,
-                            format_smolstr!("with name {name} in group by"),
-                        ));
-                    }
+                    idx_to_new_child.insert(idx, new_top_id);
                 }
 
                 let group_by_node = self.get_mut_relation_node(group_by_id)?;
@@ -1352,20 +1484,10 @@ impl Plan {
                         Expression::Alias(_)
                     ));
 
-                    if let Some(target_id) = col_aliases.get(&name) {
-                        let new_top_id = SubtreeCloner::clone_subtree(self, *target_id)?;
-                        self.replace_target_in_subtree(
-                            new_top_id,
-                            proj_target_id,
-                            groupby_target_rel,
-                        )?;
-                        self.replace_expression(parent_id, alias_id, new_top_id)?;
-                    } else {
-                        return Err(SbroadError::NotFound(
-                            Entity::Column,
-                            format_smolstr!("with name {name} in group by"),
-                        ));
-                    }
+                    let target_id = alias_child(self, &name)?;
+                    let new_top_id = SubtreeCloner::clone_subtree(self, target_id)?;
+                    self.replace_target_in_subtree(new_top_id, proj_target_id, groupby_target_rel)?;
+                    self.replace_expression(parent_id, alias_id, new_top_id)?;
 
                     let Expression::Alias(Alias { child, .. }) =
                         self.get_expression_node(alias_id)?
@@ -1579,6 +1701,33 @@ impl Plan {
             alias,
             child: child_id,
             output,
+            is_recursive: false,
+        };
+        let cte_id = self.add_relational(cte.into())?;
+        Ok(cte_id)
+    }
+
+    /// Appends a new recursive CTE node to the plan arena. The working table is a CTE
+    /// over the non-recursive term, it is referenced by the recursive term and holds
+    /// the rows produced on the previous iteration. The child of the recursive CTE is
+    /// a union of the working table and the recursive term.
+    ///
+    /// # Errors
+    /// - working table and recursive term have different number of columns
+    pub fn add_recursive_cte(
+        &mut self,
+        working_table: NodeId,
+        recursive_term: NodeId,
+        alias: SmolStr,
+        remove_duplicates: bool,
+    ) -> Result<NodeId, SbroadError> {
+        let union_id = self.add_union(working_table, recursive_term, remove_duplicates)?;
+        let output = self.add_row_for_output(union_id, &[], true, None)?;
+        let cte = ScanCte {
+            alias,
+            child: union_id,
+            output,
+            is_recursive: true,
         };
         let cte_id = self.add_relational(cte.into())?;
         Ok(cte_id)
@@ -1636,17 +1785,107 @@ impl Plan {
     ///
     /// # Errors
     /// - Row node is not of a row type
-    pub fn add_limit(&mut self, select: NodeId, limit: u64) -> Result<NodeId, SbroadError> {
+    pub fn add_limit(
+        &mut self,
+        select: NodeId,
+        limit: Option<u64>,
+        offset: u64,
+        with_ties: bool,
+    ) -> Result<NodeId, SbroadError> {
         let output = self.add_row_for_output(select, &[], true, None)?;
         let limit = Limit {
             output,
             limit,
+            offset,
+            with_ties,
             child: select,
         };
 
         self.add_relational(limit.into())
     }
 
+    /// Get the sort keys of the `ORDER BY` under the limit node in terms of
+    /// the limit output. They define the rows tied with the last one for
+    /// `FETCH FIRST ... WITH TIES`.
+    ///
+    /// # Errors
+    /// - Limit child is not a projection over `ORDER BY`
+    /// - Sort key is neither a column nor a position
+    pub fn get_limit_sort_keys(
+        &self,
+        limit_id: NodeId,
+    ) -> Result<Vec<(SortKey, &OrderByElement)>, SbroadError> {
+        let Relational::Limit(Limit { child, .. }) = self.get_relation_node(limit_id)? else {
+            return Err(SbroadError::Invalid(
+                Entity::Node,
+                Some(format_smolstr!("expected Limit node with id {limit_id:?}")),
+            ));
+        };
+        // `ORDER BY` is always covered with a projection.
+        let order_by = match self.get_relation_node(*child)? {
+            Relational::Projection(Projection { children, .. }) => match children.first() {
+                Some(order_by_id) => Some(self.get_relation_node(*order_by_id)?),
+                None => None,
+            },
+            _ => None,
+        };
+        let Some(Relational::OrderBy(OrderBy {
+            order_by_elements, ..
+        })) = order_by
+        else {
+            return Err(SbroadError::Invalid(
+                Entity::Query,
+                Some("WITH TIES cannot be specified without ORDER BY clause".into()),
+            ));
+        };
+
+        let column = |expr_id: NodeId| -> Result<Option<usize>, SbroadError> {
+            match self.get_expression_node(expr_id)? {
+                Expression::Reference(Reference { position, .. }) => Ok(Some(*position)),
+                _ => Ok(None),
+            }
+        };
+        let is_null = |expr_id: NodeId| -> Result<Option<usize>, SbroadError> {
+            match self.get_expression_node(expr_id)? {
+                Expression::Unary(UnaryExpr {
+                    op: Unary::IsNull,
+                    child,
+                }) => column(*child),
+                _ => Ok(None),
+            }
+        };
+
+        let mut keys = Vec::with_capacity(order_by_elements.len());
+        for element in order_by_elements {
+            let key = match element.entity {
+                OrderByEntity::Index { value } => Some(SortKey::Column(value - 1)),
+                OrderByEntity::Expression { expr_id } => match self.get_expression_node(expr_id)? {
+                    Expression::Reference(Reference { position, .. }) => {
+                        Some(SortKey::Column(*position))
+                    }
+                    // Keys generated for `NULLS FIRST` and `NULLS LAST`.
+                    Expression::Unary(UnaryExpr {
+                        op: Unary::IsNull,
+                        child,
+                    }) => column(*child)?.map(SortKey::IsNull),
+                    Expression::Unary(UnaryExpr {
+                        op: Unary::Not,
+                        child,
+                    }) => is_null(*child)?.map(SortKey::IsNotNull),
+                    _ => None,
+                },
+            };
+            let Some(key) = key else {
+                return Err(SbroadError::Unsupported(
+                    Entity::Expression,
+                    Some("WITH TIES supports only columns and positions in ORDER BY".into()),
+                ));
+            };
+            keys.push((key, element));
+        }
+        Ok(keys)
+    }
+
     /// Adds a values row node.
     ///
     /// # Errors
@@ -188,7 +188,9 @@ fn except() {
     let scan_t2_id = valid_plan.add_scan("t2", None).unwrap();
 
     // Correct Except operator
-    valid_plan.add_except(scan_t1_id, scan_t2_id).unwrap();
+    valid_plan
+        .add_except(scan_t1_id, scan_t2_id, false)
+        .unwrap();
 
     let mut invalid_plan = Plan::default();
 
@@ -215,7 +217,9 @@ fn except() {
             "children tuples have mismatching amount of columns in except node: left 1, right 2"
                 .into()
         ),
-        invalid_plan.add_except(scan_t1_id, scan_t3_id).unwrap_err()
+        invalid_plan
+            .add_except(scan_t1_id, scan_t3_id, false)
+            .unwrap_err()
     );
 }
 
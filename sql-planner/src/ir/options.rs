@@ -10,6 +10,14 @@ use std::str::FromStr;
 
 pub const DEFAULT_SQL_MOTION_ROW_MAX: u64 = 5000;
 pub const DEFAULT_SQL_VDBE_OPCODE_MAX: u64 = 45000;
+pub const DEFAULT_SQL_RECURSION_MAX: u64 = 1000;
+pub const DEFAULT_REPLICA_LAG_MAX: u64 = 0;
+pub const DEFAULT_SQL_MAX_BUCKETS_PER_QUERY: u64 = 0;
+pub const DEFAULT_SQL_PARTIAL_RESULTS: bool = false;
+pub const DEFAULT_WAIT_INDEX: u64 = 0;
+pub const DEFAULT_WAIT_INDEX_TIMEOUT: u64 = 5000;
+pub const DEFAULT_SQL_RESULT_ROW_MAX: u64 = 0;
+pub const DEFAULT_SQL_DISPATCH_CONCURRENCY_MAX: u64 = 0;
 
 #[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize, Hash)]
 #[repr(u8)]
@@ -85,6 +93,44 @@ pub struct Options {
     ///   if there is only one node in the replicaset (leader), an error will be returned
     /// - `Any` reading is performed from any node in the replicaset
     pub read_preference: ReadPreference,
+    /// Maximum number of iterations of the recursive term of a `WITH RECURSIVE`
+    /// common table expression. It guards the router from the infinite loops
+    /// on the cyclic data. Value of `0` indicates that this limit is disabled.
+    pub sql_recursion_max: i64,
+    /// Maximum replication lag (in milliseconds) of a replica that is allowed
+    /// to serve the query with `read_preference` set to `replica` or `any`.
+    /// When every replica of a replicaset lags behind more, the query is sent
+    /// to the replicaset master. Value of `0` indicates that this limit is disabled.
+    pub replica_lag_max: i64,
+    /// Maximum number of buckets a single dispatch of the query may touch. It
+    /// guards the cluster from the accidental full scans, as the query is rejected
+    /// on the router before being sent to the storages. Value of `0` indicates
+    /// that this limit is disabled.
+    pub sql_max_buckets_per_query: i64,
+    /// Best-effort mode of the read queries. When some of the storages fail,
+    /// the query returns the rows of the storages that succeeded, the router
+    /// logs the errors and warns the client. It applies only to the single
+    /// stage reads: DML and queries with motions ignore this option and
+    /// always fail as a whole.
+    pub sql_partial_results: bool,
+    /// Raft index which must be applied on the router before it serves the read.
+    /// It provides the "read your writes" consistency of the global tables
+    /// (read on the router) after a DML on another instance, so queries reading
+    /// sharded tables reject it. Value of `0` indicates that the read doesn't wait.
+    pub wait_index: i64,
+    /// How long (in milliseconds) the read waits for `wait_index` to be applied.
+    /// When the timeout expires, the query fails instead of returning stale data.
+    pub wait_index_timeout: i64,
+    /// Maximum number of rows in the result of a read query. Unlike
+    /// `sql_motion_row_max`, it caps the rows sent to the client rather
+    /// than the intermediate virtual tables. The query fails when its result
+    /// exceeds the limit. Value of `0` indicates that this limit is disabled.
+    pub sql_result_row_max: i64,
+    /// Maximum number of requests the router keeps in flight when it sends
+    /// the query to several storages. A larger fan-out is sent in waves, so
+    /// a query over all the buckets of a large cluster doesn't flood the
+    /// network. Value of `0` indicates that this limit is disabled.
+    pub sql_dispatch_concurrency_max: i64,
 }
 
 impl Default for Options {
@@ -93,6 +139,14 @@ impl Default for Options {
             sql_motion_row_max: DEFAULT_SQL_MOTION_ROW_MAX as i64,
             sql_vdbe_opcode_max: DEFAULT_SQL_VDBE_OPCODE_MAX as i64,
             read_preference: ReadPreference::default(),
+            sql_recursion_max: DEFAULT_SQL_RECURSION_MAX as i64,
+            replica_lag_max: DEFAULT_REPLICA_LAG_MAX as i64,
+            sql_max_buckets_per_query: DEFAULT_SQL_MAX_BUCKETS_PER_QUERY as i64,
+            sql_partial_results: DEFAULT_SQL_PARTIAL_RESULTS,
+            wait_index: DEFAULT_WAIT_INDEX as i64,
+            wait_index_timeout: DEFAULT_WAIT_INDEX_TIMEOUT as i64,
+            sql_result_row_max: DEFAULT_SQL_RESULT_ROW_MAX as i64,
+            sql_dispatch_concurrency_max: DEFAULT_SQL_DISPATCH_CONCURRENCY_MAX as i64,
         }
     }
 }
@@ -136,6 +190,14 @@ pub struct PartialOptions {
     pub sql_motion_row_max: Option<i64>,
     pub sql_vdbe_opcode_max: Option<i64>,
     pub read_preference: Option<ReadPreference>,
+    pub sql_recursion_max: Option<i64>,
+    pub replica_lag_max: Option<i64>,
+    pub sql_max_buckets_per_query: Option<i64>,
+    pub sql_partial_results: Option<bool>,
+    pub wait_index: Option<i64>,
+    pub wait_index_timeout: Option<i64>,
+    pub sql_result_row_max: Option<i64>,
+    pub sql_dispatch_concurrency_max: Option<i64>,
 }
 
 impl PartialOptions {
@@ -152,6 +214,24 @@ impl PartialOptions {
                 .sql_vdbe_opcode_max
                 .unwrap_or(defaults.sql_vdbe_opcode_max),
             read_preference: self.read_preference.unwrap_or(defaults.read_preference),
+            sql_recursion_max: self.sql_recursion_max.unwrap_or(defaults.sql_recursion_max),
+            replica_lag_max: self.replica_lag_max.unwrap_or(defaults.replica_lag_max),
+            sql_max_buckets_per_query: self
+                .sql_max_buckets_per_query
+                .unwrap_or(defaults.sql_max_buckets_per_query),
+            sql_partial_results: self
+                .sql_partial_results
+                .unwrap_or(defaults.sql_partial_results),
+            wait_index: self.wait_index.unwrap_or(defaults.wait_index),
+            wait_index_timeout: self
+                .wait_index_timeout
+                .unwrap_or(defaults.wait_index_timeout),
+            sql_result_row_max: self
+                .sql_result_row_max
+                .unwrap_or(defaults.sql_result_row_max),
+            sql_dispatch_concurrency_max: self
+                .sql_dispatch_concurrency_max
+                .unwrap_or(defaults.sql_dispatch_concurrency_max),
         }
     }
 }
@@ -187,6 +267,22 @@ pub enum OptionKind {
     MotionRowMax,
     /// `read_preference`
     ReadPreference,
+    /// `sql_recursion_max`
+    RecursionMax,
+    /// `replica_lag_max`
+    ReplicaLagMax,
+    /// `sql_max_buckets_per_query`
+    MaxBucketsPerQuery,
+    /// `sql_partial_results`
+    PartialResults,
+    /// `wait_index`
+    WaitIndex,
+    /// `wait_index_timeout`
+    WaitIndexTimeout,
+    /// `sql_result_row_max`
+    ResultRowMax,
+    /// `sql_dispatch_concurrency_max`
+    DispatchConcurrencyMax,
 }
 
 impl Display for OptionKind {
@@ -195,6 +291,14 @@ impl Display for OptionKind {
             OptionKind::VdbeOpcodeMax => "sql_vdbe_opcode_max",
             OptionKind::MotionRowMax => "sql_motion_row_max",
             OptionKind::ReadPreference => "read_preference",
+            OptionKind::RecursionMax => "sql_recursion_max",
+            OptionKind::ReplicaLagMax => "replica_lag_max",
+            OptionKind::MaxBucketsPerQuery => "sql_max_buckets_per_query",
+            OptionKind::PartialResults => "sql_partial_results",
+            OptionKind::WaitIndex => "wait_index",
+            OptionKind::WaitIndexTimeout => "wait_index_timeout",
+            OptionKind::ResultRowMax => "sql_result_row_max",
+            OptionKind::DispatchConcurrencyMax => "sql_dispatch_concurrency_max",
         };
         write!(f, "{s}")
     }
@@ -260,6 +364,14 @@ pub(super) struct LoweredOptions {
     sql_motion_row_max: LoweredOptionValue<i64>,
     sql_vdbe_opcode_max: LoweredOptionValue<i64>,
     read_preference: LoweredOptionValue<ReadPreference>,
+    sql_recursion_max: LoweredOptionValue<i64>,
+    replica_lag_max: LoweredOptionValue<i64>,
+    sql_max_buckets_per_query: LoweredOptionValue<i64>,
+    sql_partial_results: LoweredOptionValue<bool>,
+    wait_index: LoweredOptionValue<i64>,
+    wait_index_timeout: LoweredOptionValue<i64>,
+    sql_result_row_max: LoweredOptionValue<i64>,
+    sql_dispatch_concurrency_max: LoweredOptionValue<i64>,
 }
 
 impl LoweredOptions {
@@ -268,6 +380,18 @@ impl LoweredOptions {
             sql_motion_row_max: self.sql_motion_row_max.unwrap(default.sql_motion_row_max),
             sql_vdbe_opcode_max: self.sql_vdbe_opcode_max.unwrap(default.sql_vdbe_opcode_max),
             read_preference: self.read_preference.unwrap(default.read_preference),
+            sql_recursion_max: self.sql_recursion_max.unwrap(default.sql_recursion_max),
+            replica_lag_max: self.replica_lag_max.unwrap(default.replica_lag_max),
+            sql_max_buckets_per_query: self
+                .sql_max_buckets_per_query
+                .unwrap(default.sql_max_buckets_per_query),
+            sql_partial_results: self.sql_partial_results.unwrap(default.sql_partial_results),
+            wait_index: self.wait_index.unwrap(default.wait_index),
+            wait_index_timeout: self.wait_index_timeout.unwrap(default.wait_index_timeout),
+            sql_result_row_max: self.sql_result_row_max.unwrap(default.sql_result_row_max),
+            sql_dispatch_concurrency_max: self
+                .sql_dispatch_concurrency_max
+                .unwrap(default.sql_dispatch_concurrency_max),
         }
     }
 }
@@ -297,6 +421,19 @@ pub(super) fn lower_options(
         }
     }
 
+    fn lower_bool(kind: OptionKind, val: &Value) -> Result<bool, SbroadError> {
+        match *val {
+            Value::Boolean(flag) => Ok(flag),
+            ref val => Err(SbroadError::Invalid(
+                Entity::OptionSpec,
+                Some(format_smolstr!(
+                    "expected option {} to be a boolean, got: {val:?}",
+                    kind
+                )),
+            )),
+        }
+    }
+
     fn lower_read_preference(val: &Value) -> Result<ReadPreference, SbroadError> {
         ReadPreference::try_from(val)
     }
@@ -326,6 +463,59 @@ pub(super) fn lower_options(
                 let value = val.as_ref().map(lower_read_preference).transpose()?;
                 result.read_preference.specify_opt(value);
             }
+            OptionKind::RecursionMax => {
+                let value = val
+                    .as_ref()
+                    .map(|val| lower_unsigned(kind, val))
+                    .transpose()?;
+                result.sql_recursion_max.specify_opt(value);
+            }
+            OptionKind::ReplicaLagMax => {
+                let value = val
+                    .as_ref()
+                    .map(|val| lower_unsigned(kind, val))
+                    .transpose()?;
+                result.replica_lag_max.specify_opt(value);
+            }
+            OptionKind::MaxBucketsPerQuery => {
+                let value = val
+                    .as_ref()
+                    .map(|val| lower_unsigned(kind, val))
+                    .transpose()?;
+                result.sql_max_buckets_per_query.specify_opt(value);
+            }
+            OptionKind::PartialResults => {
+                let value = val.as_ref().map(|val| lower_bool(kind, val)).transpose()?;
+                result.sql_partial_results.specify_opt(value);
+            }
+            OptionKind::WaitIndex => {
+                let value = val
+                    .as_ref()
+                    .map(|val| lower_unsigned(kind, val))
+                    .transpose()?;
+                result.wait_index.specify_opt(value);
+            }
+            OptionKind::WaitIndexTimeout => {
+                let value = val
+                    .as_ref()
+                    .map(|val| lower_unsigned(kind, val))
+                    .transpose()?;
+                result.wait_index_timeout.specify_opt(value);
+            }
+            OptionKind::ResultRowMax => {
+                let value = val
+                    .as_ref()
+                    .map(|val| lower_unsigned(kind, val))
+                    .transpose()?;
+                result.sql_result_row_max.specify_opt(value);
+            }
+            OptionKind::DispatchConcurrencyMax => {
+                let value = val
+                    .as_ref()
+                    .map(|val| lower_unsigned(kind, val))
+                    .transpose()?;
+                result.sql_dispatch_concurrency_max.specify_opt(value);
+            }
         }
     }
 
@@ -372,6 +562,41 @@ impl Plan {
             ));
         }
 
+        let replica_lag_max_specified =
+            !matches!(lowered.replica_lag_max, LoweredOptionValue::Default);
+        if replica_lag_max_specified && !self.is_dql()? {
+            return Err(SbroadError::Invalid(
+                Entity::OptionSpec,
+                Some("replica_lag_max option is supported only for DQL queries".into()),
+            ));
+        }
+
+        let wait_index_specified = !matches!(lowered.wait_index, LoweredOptionValue::Default)
+            || !matches!(lowered.wait_index_timeout, LoweredOptionValue::Default);
+        if wait_index_specified && !self.is_dql()? {
+            return Err(SbroadError::Invalid(
+                Entity::OptionSpec,
+                Some("wait_index option is supported only for DQL queries".into()),
+            ));
+        }
+        // Sharded tables are read on the storages, which don't wait for the
+        // router's raft index, so the option would give no guarantee there.
+        if wait_index_specified && self.relations.tables.values().any(|t| !t.is_global()) {
+            return Err(SbroadError::Invalid(
+                Entity::OptionSpec,
+                Some("wait_index option is supported only for reads of global tables".into()),
+            ));
+        }
+
+        let result_row_max_specified =
+            !matches!(lowered.sql_result_row_max, LoweredOptionValue::Default);
+        if result_row_max_specified && !self.is_dql()? {
+            return Err(SbroadError::Invalid(
+                Entity::OptionSpec,
+                Some("sql_result_row_max option is supported only for DQL queries".into()),
+            ));
+        }
+
         // We need to check if the plan has a top node and if it is an Insert with Values.
         // If it is, we can determine the number of values in the Values node and use it
         // to make an early decision about the maximum number of rows we can handle.
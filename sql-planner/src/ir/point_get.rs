@@ -0,0 +1,184 @@
+//! Recognition of single-row primary key reads.
+//!
+//! `SELECT <columns> FROM t WHERE pk = <constant>` on a table sharded by its
+//! single-column primary key reads at most one tuple from a single bucket.
+//! Such a query doesn't need the regular SQL execution on the storage and can
+//! be served by a point get by the primary key. The shape is recognized right
+//! after parsing, so these queries skip the optimization pipeline unless the
+//! bound key rules the point get out.
+
+use smol_str::SmolStr;
+
+use crate::errors::SbroadError;
+use crate::ir::node::expression::Expression;
+use crate::ir::node::relational::Relational;
+use crate::ir::node::{BoolExpr, Constant, NodeId, Projection, Reference, ScanRelation, Selection};
+use crate::ir::operator::Bool;
+use crate::ir::options::ReadPreference;
+use crate::ir::value::Value;
+use crate::ir::Plan;
+
+/// A read of a single tuple by its primary key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PointGet {
+    /// Name of the table to read from.
+    pub table: SmolStr,
+    /// Value of the primary key (also the sharding key).
+    pub key: Value,
+    /// Positions of the table columns in the query output.
+    pub columns: Vec<usize>,
+}
+
+impl Plan {
+    /// Check if the plan has the shape of a point get with the key given by
+    /// a constant or a parameter. Such plans are not optimized until binding.
+    ///
+    /// # Errors
+    /// - invalid plan nodes
+    pub fn is_point_get_candidate(&self) -> Result<bool, SbroadError> {
+        Ok(self.point_get_shape()?.is_some())
+    }
+
+    /// Recognize a bound DQL plan that reads a single tuple by its primary key.
+    ///
+    /// # Errors
+    /// - invalid plan nodes
+    pub fn point_get(&self) -> Result<Option<PointGet>, SbroadError> {
+        let Some((table, key_id, columns)) = self.point_get_shape()? else {
+            return Ok(None);
+        };
+        // Replication lag is checked by the regular dispatch only.
+        let options = &self.effective_options;
+        if options.read_preference != ReadPreference::Leader && options.replica_lag_max > 0 {
+            return Ok(None);
+        }
+        let Expression::Constant(Constant { value: key }) = self.get_expression_node(key_id)?
+        else {
+            return Ok(None);
+        };
+        if matches!(key, Value::Null) {
+            return Ok(None);
+        }
+        let table_def = self.get_relation_or_error(table)?;
+        let column = &table_def.columns[table_def.primary_key.positions[0]];
+        // A key of another type may need a cast that only SQL does.
+        if key.get_type() != column.r#type {
+            return Ok(None);
+        }
+
+        Ok(Some(PointGet {
+            table: table.clone(),
+            key: key.clone(),
+            columns,
+        }))
+    }
+
+    /// Match `SELECT <columns> FROM t WHERE pk = <constant or parameter>`
+    /// and return the table name, the key operand and the positions of the
+    /// table columns in the query output.
+    fn point_get_shape(&self) -> Result<Option<(&SmolStr, NodeId, Vec<usize>)>, SbroadError> {
+        if self.is_explain() || self.select_into.is_some() || !self.is_dql()? {
+            return Ok(None);
+        }
+
+        let top_id = self.get_top()?;
+        let Relational::Projection(Projection {
+            children,
+            windows,
+            output,
+            is_distinct: false,
+            group_by: None,
+            having: None,
+        }) = self.get_relation_node(top_id)?
+        else {
+            return Ok(None);
+        };
+        let ([selection_id], true) = (children.as_slice(), windows.is_empty()) else {
+            return Ok(None);
+        };
+        let Relational::Selection(Selection {
+            children, filter, ..
+        }) = self.get_relation_node(*selection_id)?
+        else {
+            return Ok(None);
+        };
+        let ([scan_id], Some((key_pos, key_id))) =
+            (children.as_slice(), self.key_equality(*filter)?)
+        else {
+            return Ok(None);
+        };
+        let Relational::ScanRelation(ScanRelation {
+            relation,
+            sample: None,
+            ..
+        }) = self.get_relation_node(*scan_id)?
+        else {
+            return Ok(None);
+        };
+
+        let table = self.get_relation_or_error(relation)?;
+        let Ok(sharding_key) = table.get_sk() else {
+            return Ok(None);
+        };
+        if sharding_key != [key_pos] || table.primary_key.positions != [key_pos] {
+            return Ok(None);
+        }
+
+        let mut columns = Vec::new();
+        for col_id in self.get_row_list(*output)? {
+            let child_id = self.get_child_under_alias(*col_id)?;
+            let Expression::Reference(Reference { position, .. }) =
+                self.get_expression_node(child_id)?
+            else {
+                return Ok(None);
+            };
+            columns.push(*position);
+        }
+
+        Ok(Some((relation, key_id, columns)))
+    }
+
+    /// Match `column = constant` or `column = parameter` (in any order)
+    /// and return the column position with the id of the other operand.
+    fn key_equality(&self, filter: NodeId) -> Result<Option<(usize, NodeId)>, SbroadError> {
+        let Expression::Bool(BoolExpr {
+            left,
+            op: Bool::Eq,
+            right,
+            collation: None,
+        }) = self.get_expression_node(filter)?
+        else {
+            return Ok(None);
+        };
+        let (left, right) = (self.single_row_item(*left)?, self.single_row_item(*right)?);
+        let is_key = |id| {
+            matches!(
+                self.get_expression_node(id),
+                Ok(Expression::Constant(_) | Expression::Parameter(_))
+            )
+        };
+        let (column, key_id) = match (
+            self.get_expression_node(left)?,
+            self.get_expression_node(right)?,
+        ) {
+            (Expression::Reference(column), _) if is_key(right) => (column, right),
+            (_, Expression::Reference(column)) if is_key(left) => (column, left),
+            _ => return Ok(None),
+        };
+        Ok(Some((column.position, key_id)))
+    }
+
+    /// Unwrap a single-element row, the optimizer turns operands of
+    /// comparisons into rows.
+    fn single_row_item(&self, id: NodeId) -> Result<NodeId, SbroadError> {
+        if let Expression::Row(row) = self.get_expression_node(id)? {
+            if let [item] = row.list.as_slice() {
+                return Ok(*item);
+            }
+        }
+        Ok(id)
+    }
+}
+
+#[cfg(test)]
+mod tests;
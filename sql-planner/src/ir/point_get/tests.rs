@@ -0,0 +1,68 @@
+use crate::ir::point_get::PointGet;
+use crate::ir::transformation::helpers::{sql_to_ir_without_bind, sql_to_optimized_ir};
+use crate::ir::types::{DerivedType, UnrestrictedType as Type};
+use crate::ir::value::Value;
+
+#[test]
+fn point_get_pk_equality() {
+    let query = r#"SELECT * FROM "test_space" WHERE "id" = 1"#;
+    let plan = sql_to_optimized_ir(query, vec![]);
+    assert_eq!(
+        plan.point_get().unwrap(),
+        Some(PointGet {
+            table: "test_space".into(),
+            key: Value::Integer(1),
+            columns: vec![0, 1, 2, 3],
+        })
+    );
+
+    let query = r#"SELECT "FIRST_NAME", "id" FROM "test_space" WHERE ? = "id""#;
+    let plan = sql_to_optimized_ir(query, vec![Value::Integer(2)]);
+    assert_eq!(
+        plan.point_get().unwrap(),
+        Some(PointGet {
+            table: "test_space".into(),
+            key: Value::Integer(2),
+            columns: vec![2, 0],
+        })
+    );
+}
+
+#[test]
+fn point_get_not_applicable() {
+    let queries = [
+        // A range predicate.
+        r#"SELECT * FROM "test_space" WHERE "id" > 1"#,
+        // Not a primary key column.
+        r#"SELECT * FROM "test_space" WHERE "sys_op" = 1"#,
+        // Another predicate besides the key equality.
+        r#"SELECT * FROM "test_space" WHERE "id" = 1 AND "sys_op" = 1"#,
+        // An expression in the projection.
+        r#"SELECT "id" + 1 FROM "test_space" WHERE "id" = 1"#,
+        // The key needs a cast.
+        r#"SELECT * FROM "test_space" WHERE "id" = 1.5"#,
+        r#"SELECT * FROM "test_space" WHERE "id" = NULL"#,
+        r#"SELECT DISTINCT * FROM "test_space" WHERE "id" = 1"#,
+        r#"EXPLAIN SELECT * FROM "test_space" WHERE "id" = 1"#,
+    ];
+    for query in queries {
+        let plan = sql_to_optimized_ir(query, vec![]);
+        assert_eq!(plan.point_get().unwrap(), None, "{query}");
+    }
+}
+
+#[test]
+fn point_get_candidate() {
+    let params_types = [DerivedType::new(Type::Integer)];
+    let query = r#"SELECT * FROM "test_space" WHERE "id" = ?"#;
+    let plan = sql_to_ir_without_bind(query, &params_types);
+    assert!(plan.is_point_get_candidate().unwrap());
+
+    let query = r#"SELECT * FROM "test_space" WHERE "id" = 1"#;
+    let plan = sql_to_ir_without_bind(query, &[]);
+    assert!(plan.is_point_get_candidate().unwrap());
+
+    let query = r#"SELECT * FROM "test_space" WHERE "id" > ?"#;
+    let plan = sql_to_ir_without_bind(query, &params_types);
+    assert!(!plan.is_point_get_candidate().unwrap());
+}
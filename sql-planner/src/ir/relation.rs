@@ -4,6 +4,7 @@
 //! * Column type (`Type`)
 //! * Table column (`Column`)
 //! * Engine (memtx/vinyl), used by a table (`SpaceEngine`)
+//! * CHECK constraint of a table column (`CheckConstraint`)
 //! * Table, representing unnamed tuples storage (`Table`)
 //! * Relation, representing named tables (`Relations` as a map of { name -> table })
 
@@ -22,6 +23,7 @@ use serde::ser::{Serialize as SerSerialize, SerializeMap, Serializer};
 use serde::{Deserialize, Deserializer, Serialize};
 
 use super::distribution::Key;
+use super::operator::Bool;
 use super::types::{DerivedType, UnrestrictedType};
 
 const DEFAULT_VALUE: Value = Value::Null;
@@ -72,6 +74,7 @@ impl From<Column> for Field {
                 UnrestrictedType::Integer => Field::integer(column.name),
                 UnrestrictedType::String => Field::string(column.name),
                 UnrestrictedType::Uuid => Field::uuid(column.name),
+                UnrestrictedType::Bytea => Field::varbinary(column.name),
                 UnrestrictedType::Array => Field::array(column.name),
                 UnrestrictedType::Any => Field::any(column.name),
                 UnrestrictedType::Map => Field::map(column.name),
@@ -118,6 +121,7 @@ impl SerSerialize for Column {
                 UnrestrictedType::Integer => "integer",
                 UnrestrictedType::String => "string",
                 UnrestrictedType::Uuid => "uuid",
+                UnrestrictedType::Bytea => "varbinary",
                 UnrestrictedType::Array => "array",
                 UnrestrictedType::Any => "any",
                 UnrestrictedType::Map => "map",
@@ -182,6 +186,7 @@ impl<'de> Visitor<'de> for ColumnVisitor {
             "string" | "text" | "varchar" => DerivedType::new(UnrestrictedType::String),
             "array" => DerivedType::new(UnrestrictedType::Array),
             "uuid" => DerivedType::new(UnrestrictedType::Uuid),
+            "varbinary" | "bytea" => DerivedType::new(UnrestrictedType::Bytea),
             "map" => DerivedType::new(UnrestrictedType::Map),
             "unknown" => DerivedType::unknown(),
             s => return Err(Error::custom(format!("unsupported column type: {s}"))),
@@ -369,6 +374,69 @@ fn table_new_impl<'column>(
     Ok((pos_map, Key::new(primary_positions)))
 }
 
+/// CHECK constraint comparing a table column with a constant:
+/// `CHECK (<column> <op> <value>)`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct CheckConstraint {
+    /// Constraint name.
+    pub name: SmolStr,
+    /// Position of the checked column in the table.
+    pub column: usize,
+    /// Comparison operator.
+    pub op: Bool,
+    /// Constant the column is compared with.
+    pub value: Value,
+}
+
+impl CheckConstraint {
+    /// Constraint constructor.
+    ///
+    /// # Errors
+    /// - operator is not a comparison.
+    pub fn new(name: &str, column: usize, op: Bool, value: Value) -> Result<Self, SbroadError> {
+        if !matches!(
+            op,
+            Bool::Eq | Bool::NotEq | Bool::Gt | Bool::GtEq | Bool::Lt | Bool::LtEq
+        ) {
+            return Err(SbroadError::Unsupported(
+                Entity::Operator,
+                Some(format_smolstr!("{op} in CHECK constraint {name}")),
+            ));
+        }
+        Ok(CheckConstraint {
+            name: name.into(),
+            column,
+            op,
+            value,
+        })
+    }
+
+    /// Check the value of the constrained column.
+    /// As in SQL, the constraint is violated only when
+    /// the comparison is false: `NULL` always satisfies it.
+    #[must_use]
+    pub fn is_satisfied_by(&self, value: &Value) -> bool {
+        use super::value::{Trivalent, TrivalentOrdering};
+
+        if let Bool::Eq | Bool::NotEq = self.op {
+            return match (value.eq(&self.value), self.op) {
+                (Trivalent::Unknown, _) => true,
+                (res, Bool::Eq) => res == Trivalent::True,
+                (res, _) => res == Trivalent::False,
+            };
+        }
+        let Some(ordering) = value.partial_cmp(&self.value) else {
+            return true;
+        };
+        match ordering {
+            TrivalentOrdering::Unknown => true,
+            TrivalentOrdering::Less => matches!(self.op, Bool::Lt | Bool::LtEq),
+            TrivalentOrdering::Equal => matches!(self.op, Bool::GtEq | Bool::LtEq),
+            TrivalentOrdering::Greater => matches!(self.op, Bool::Gt | Bool::GtEq),
+        }
+    }
+}
+
 /// Table is a tuple storage in the cluster.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub struct Table {
@@ -381,6 +449,17 @@ pub struct Table {
     pub name: SmolStr,
     pub kind: TableKind,
     pub tier: Option<SmolStr>,
+    /// CHECK constraints of the table columns.
+    #[serde(default)]
+    pub checks: Vec<CheckConstraint>,
+    /// Position of the identity column filled from the cluster sequence
+    /// when it is omitted in INSERT.
+    #[serde(default)]
+    pub identity: Option<usize>,
+    /// Declared default values of the columns as `(position, value)`.
+    /// Columns without a declared default are filled with `NULL`.
+    #[serde(default)]
+    pub defaults: Vec<(usize, Value)>,
 }
 
 impl Table {
@@ -433,6 +512,9 @@ impl Table {
             primary_key,
             kind,
             tier: None,
+            checks: Vec::new(),
+            identity: None,
+            defaults: Vec::new(),
         })
     }
 
@@ -456,6 +538,9 @@ impl Table {
             primary_key,
             kind,
             tier: None,
+            checks: Vec::new(),
+            identity: None,
+            defaults: Vec::new(),
         })
     }
 
@@ -479,6 +564,9 @@ impl Table {
             primary_key,
             kind,
             tier: None,
+            checks: Vec::new(),
+            identity: None,
+            defaults: Vec::new(),
         })
     }
 
@@ -552,6 +640,16 @@ impl Table {
         Ok(names)
     }
 
+    /// Value the column on the given position gets when it is omitted
+    /// in INSERT or set to `DEFAULT`.
+    #[must_use]
+    pub fn column_default(&self, pos: usize) -> Value {
+        self.defaults
+            .iter()
+            .find(|(default_pos, _)| *default_pos == pos)
+            .map_or_else(Column::default_value, |(_, value)| value.clone())
+    }
+
     /// Get sharding key if this table is sharded.
     ///
     /// # Errors
@@ -611,6 +709,48 @@ impl Table {
     pub fn is_global(&self) -> bool {
         matches!(self.kind, TableKind::GlobalSpace | TableKind::SystemSpace)
     }
+
+    /// Validate a row being written into the table against the NOT NULL
+    /// and CHECK constraints. The row is given as pairs of
+    /// `(column position, new value)` and only the columns present
+    /// in it are validated.
+    ///
+    /// # Errors
+    /// - NULL is written into a NOT NULL column;
+    /// - CHECK constraint of a written column is violated.
+    pub fn validate_row<'v>(
+        &self,
+        row: impl IntoIterator<Item = (usize, &'v Value)>,
+    ) -> Result<(), SbroadError> {
+        for (pos, value) in row {
+            let column = self.columns.get(pos).ok_or_else(|| {
+                SbroadError::NotFound(
+                    Entity::Column,
+                    format_smolstr!("at position {pos} for Table {}", self.name),
+                )
+            })?;
+            if column.role == ColumnRole::Sharding {
+                continue;
+            }
+            if !column.is_nullable && *value == Value::Null {
+                return Err(SbroadError::NotNullViolation(
+                    self.name.clone(),
+                    column.name.clone(),
+                ));
+            }
+            if let Some(check) = self
+                .checks
+                .iter()
+                .find(|check| check.column == pos && !check.is_satisfied_by(value))
+            {
+                return Err(SbroadError::CheckViolation(
+                    self.name.clone(),
+                    check.name.clone(),
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
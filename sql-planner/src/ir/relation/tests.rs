@@ -263,6 +263,73 @@ fn table_converting() {
     assert_eq!(t, seg_from_yaml(&s).unwrap());
 }
 
+#[test]
+fn table_validate_row_not_null() {
+    let t = Table::new_global(
+        random(),
+        "t",
+        vec![
+            column_user_non_null(SmolStr::from("a"), UnrestrictedType::Integer),
+            Column::new(
+                "b",
+                DerivedType::new(UnrestrictedType::Integer),
+                ColumnRole::User,
+                true,
+            ),
+        ],
+        &["a"],
+    )
+    .unwrap();
+
+    t.validate_row([(0, &Value::from(1_i64)), (1, &Value::Null)])
+        .unwrap();
+    assert_eq!(
+        t.validate_row([(0, &Value::Null), (1, &Value::from(1_i64))])
+            .unwrap_err(),
+        SbroadError::NotNullViolation("t".into(), "a".into())
+    );
+    assert_eq!(
+        t.validate_row([(0, &Value::Null)]).unwrap_err().to_string(),
+        r#"null value in column "a" of relation "t" violates not-null constraint"#
+    );
+}
+
+#[test]
+fn table_validate_row_check() {
+    let mut t = Table::new_global(
+        random(),
+        "t",
+        vec![
+            column_user_non_null(SmolStr::from("a"), UnrestrictedType::Integer),
+            Column::new(
+                "b",
+                DerivedType::new(UnrestrictedType::Integer),
+                ColumnRole::User,
+                true,
+            ),
+        ],
+        &["a"],
+    )
+    .unwrap();
+    t.checks
+        .push(CheckConstraint::new("b_positive", 1, Bool::Gt, Value::from(0_i64)).unwrap());
+
+    t.validate_row([(0, &Value::from(1_i64)), (1, &Value::from(5_i64))])
+        .unwrap();
+    // NULL doesn't violate CHECK constraint.
+    t.validate_row([(0, &Value::from(1_i64)), (1, &Value::Null)])
+        .unwrap();
+    assert_eq!(
+        t.validate_row([(0, &Value::from(1_i64)), (1, &Value::from(0_i64))])
+            .unwrap_err(),
+        SbroadError::CheckViolation("t".into(), "b_positive".into())
+    );
+    // The constrained column is not written (e.g. UPDATE doesn't touch it).
+    t.validate_row([(0, &Value::from(2_i64))]).unwrap();
+
+    assert!(CheckConstraint::new("b_or", 1, Bool::Or, Value::from(true)).is_err());
+}
+
 fn seg_from_yaml(s: &str) -> Result<Table, SbroadError> {
     let table: Table = match serde_yaml::from_str(s) {
         Ok(t) => t,
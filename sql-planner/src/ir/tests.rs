@@ -123,4 +123,29 @@ fn get_node_oor() {
     );
 }
 
+#[test]
+fn output_column_names() {
+    let plan = crate::ir::transformation::helpers::sql_to_optimized_ir(
+        r#"SELECT "a", "b"::text AS x FROM "t""#,
+        vec![],
+    );
+    assert_eq!(
+        plan.output_column_names().unwrap(),
+        vec![SmolStr::from("a"), SmolStr::from("x")]
+    );
+    assert_eq!(
+        plan.output_columns_with_types().unwrap(),
+        vec![
+            (
+                SmolStr::from("a"),
+                DerivedType::new(UnrestrictedType::Integer)
+            ),
+            (
+                SmolStr::from("x"),
+                DerivedType::new(UnrestrictedType::String)
+            ),
+        ]
+    );
+}
+
 //TODO: add relation test
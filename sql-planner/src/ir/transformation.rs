@@ -20,9 +20,10 @@ use super::node::expression::{Expression, MutExpression};
 use super::node::relational::{MutRelational, Relational};
 use super::node::{Bound, BoundType, Over, Window};
 use super::operator::OrderByEntity;
-use super::tree::traversal::{PostOrderWithFilter, EXPR_CAPACITY};
+use super::tree::traversal::{BreadthFirst, LevelNode, PostOrderWithFilter, EXPR_CAPACITY};
 use crate::errors::{Entity, SbroadError};
 use crate::frontend::sql::ir::SubtreeCloner;
+use crate::frontend::sql::nesting_depth_error;
 use crate::ir::node::{
     Alias, ArithmeticExpr, BoolExpr, Case, Cast, IndexExpr, Join, NodeId, Row, ScalarFunction,
     Selection, Trim, UnaryExpr,
@@ -208,6 +209,29 @@ impl Plan {
                     unreachable!("Selection or Join nodes expected for transformation application")
                 }
             }
+            self.check_expression_depth(new_id)?;
+        }
+        Ok(())
+    }
+
+    /// Check that the expression tree expanded by a transformation (e.g.
+    /// a long IN-list replaced with a chain of ORs) is not nested deeper than
+    /// [`Plan::max_expression_depth`]. The tree is traversed iteratively, as
+    /// the recursive routines working with it would overflow the stack.
+    fn check_expression_depth(&self, top_id: NodeId) -> Result<(), SbroadError> {
+        let Some(max_depth) = self.max_expression_depth else {
+            return Ok(());
+        };
+        let tree = BreadthFirst::with_capacity(
+            |node| self.nodes.expr_iter(node, false),
+            EXPR_CAPACITY,
+            EXPR_CAPACITY,
+        );
+        let exceeds = tree
+            .into_iter(top_id)
+            .any(|LevelNode(level, _)| level >= max_depth);
+        if exceeds {
+            return Err(nesting_depth_error(max_depth));
         }
         Ok(())
     }
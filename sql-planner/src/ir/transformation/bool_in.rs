@@ -9,6 +9,9 @@
 //! ```sql
 //! SELECT * FROM t WHERE (a = 1) or (a = 2) or (a = 3)
 //! ```
+//!
+//! IN-lists made of a single array parameter (`a IN ($1)`) are kept
+//! until the parameter is bound and expanded into a list of constants.
 
 use crate::errors::{Entity, SbroadError};
 use crate::ir::node::expression::Expression;
@@ -53,9 +56,11 @@ impl Plan {
             }
         };
 
-        // Do not apply current transformation to motion and sub-query nodes.
+        // Do not apply current transformation to motion and sub-query nodes
+        // and to the IN-lists that are not bound yet.
         if self.get_motion_from_row(right_id)?.is_some()
             || self.get_sub_query_from_row_node(right_id)?.is_some()
+            || self.get_in_list_array_param(right_id)?.is_some()
         {
             return Ok(top_id);
         }
@@ -86,6 +91,12 @@ impl Plan {
         self.transform_expr_trees(top_id, &call_expr_tree_replace_in)?;
         Ok(self)
     }
+
+    /// Replace IN operators whose array parameters were expanded on binding.
+    pub(crate) fn replace_bound_in_lists(&mut self) -> Result<(), SbroadError> {
+        let top_id = self.get_top()?;
+        self.transform_expr_trees(top_id, &call_expr_tree_replace_in)
+    }
 }
 
 #[cfg(feature = "mock")]
@@ -1,4 +1,8 @@
-use crate::ir::transformation::helpers::check_transformation;
+use crate::ir::node::{BoolExpr, Node32};
+use crate::ir::operator::Bool;
+use crate::ir::options::Options;
+use crate::ir::transformation::helpers::{check_transformation, sql_to_ir_without_bind};
+use crate::ir::types::{DerivedType, UnrestrictedType};
 use crate::ir::value::Value;
 use crate::ir::Plan;
 use pretty_assertions::assert_eq;
@@ -73,3 +77,55 @@ fn bool_in5() {
         @r#"SELECT "t"."a" FROM "t" WHERE TRIM (CAST ((("t"."a" = CAST($1 AS int)) or ("t"."a" = CAST($2 AS int))) as string)) < CAST($3 AS string)"#
     );
 }
+
+#[test]
+fn bool_in_array_param() {
+    let input = r#"SELECT "a" FROM "t" WHERE "a" IN ($1)"#;
+    let array = Value::from(vec![Value::from(1), Value::from(2), Value::from(3)]);
+    let actual_pattern_params = check_transformation(input, vec![array], &replace_in_operator);
+
+    assert_eq!(
+        actual_pattern_params.params,
+        vec![Value::from(1), Value::from(2), Value::from(3)]
+    );
+    insta::assert_snapshot!(
+        actual_pattern_params.pattern,
+        @r#"SELECT "t"."a" FROM "t" WHERE (("t"."a" = CAST($1 AS int)) or ("t"."a" = CAST($2 AS int))) or ("t"."a" = CAST($3 AS int))"#
+    );
+}
+
+#[test]
+fn bool_in_array_param_before_bind() {
+    // IN-list with an array parameter is kept until the array is bound.
+    let input = r#"SELECT "a" FROM "t" WHERE "a" IN ($1)"#;
+    let plan = sql_to_ir_without_bind(input, &[DerivedType::new(UnrestrictedType::Array)]);
+    let plan = replace_in_operator(plan);
+
+    let in_lists: Vec<_> = plan
+        .nodes
+        .iter32()
+        .filter_map(|node| match node {
+            Node32::Bool(BoolExpr {
+                op: Bool::In,
+                right,
+                ..
+            }) => Some(*right),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(1, in_lists.len());
+    assert_eq!(Some(1), plan.get_in_list_array_param(in_lists[0]).unwrap());
+}
+
+#[test]
+fn bool_in_empty_array_param() {
+    let input = r#"SELECT "a" FROM "t" WHERE "a" IN ($1)"#;
+    let mut plan = sql_to_ir_without_bind(input, &[DerivedType::new(UnrestrictedType::Array)]);
+    let err = plan
+        .bind_params(vec![Value::from(Vec::<Value>::new())], Options::default())
+        .unwrap_err();
+    assert_eq!(
+        "invalid query: array bound to IN-list parameter $1 must not be empty",
+        err.to_string()
+    );
+}
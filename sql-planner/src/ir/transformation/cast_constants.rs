@@ -9,24 +9,41 @@ use crate::{
     },
 };
 
-fn apply_cast(plan: &Plan, child_id: NodeId, target_type: CastType) -> Option<Value> {
-    match plan.get_expression_node(child_id).ok()? {
-        Expression::Constant(Constant { value }) => {
-            let value = value.clone();
-            value.cast(target_type.into()).ok()
-        }
+// Note: We don't throw errors if casting fails.
+// It's possible that some type and value combinations are missing,
+// but in such cases, we simply skip this evaluation and continue with other casts.
+// An optimization failure should not prevent the execution of the plan.
+// The exceptions are a malformed literal and a value out of range of an integer
+// type: they would fail on the storages anyway.
+fn try_cast(value: Value, target_type: CastType) -> Result<Option<Value>, SbroadError> {
+    match value.cast_to(target_type) {
+        Ok(value) => Ok(Some(value)),
+        Err(
+            e @ (SbroadError::InvalidTextRepresentation(..)
+            | SbroadError::NumericValueOutOfRange(..)),
+        ) => Err(e),
+        Err(_) => Ok(None),
+    }
+}
+
+fn apply_cast(
+    plan: &Plan,
+    child_id: NodeId,
+    target_type: CastType,
+) -> Result<Option<Value>, SbroadError> {
+    let Ok(child) = plan.get_expression_node(child_id) else {
+        return Ok(None);
+    };
+    match child {
+        Expression::Constant(Constant { value }) => try_cast(value.clone(), target_type),
         Expression::Cast(Cast {
             child: cast_child,
             to: cast_type,
-        }) => {
-            let value = apply_cast(plan, *cast_child, *cast_type);
-            // Note: We don't throw errors if casting fails.
-            // It's possible that some type and value combinations are missing,
-            // but in such cases, we simply skip this evaluation and continue with other casts.
-            // An optimization failure should not prevent the execution of the plan.
-            value.and_then(|x| x.cast(target_type.into()).ok())
-        }
-        _ => None,
+        }) => match apply_cast(plan, *cast_child, *cast_type)? {
+            Some(value) => try_cast(value, target_type),
+            None => Ok(None),
+        },
+        _ => Ok(None),
     }
 }
 
@@ -69,7 +86,7 @@ impl Plan {
                 to,
             }) = self.get_expression_node(cast_id)?
             {
-                apply_cast(&self, *cast_child, *to)
+                apply_cast(&self, *cast_child, *to)?
                     .map(|value| Node32::Constant(Constant { value }))
             } else {
                 None
@@ -208,10 +208,20 @@ impl Plan {
 
             for LevelNode(_, op_id) in op_nodes.iter() {
                 match self.get_mut_expression_node(*op_id)? {
-                    MutExpression::Bool(BoolExpr { left, op, right }) => {
+                    MutExpression::Bool(BoolExpr {
+                        left,
+                        op,
+                        right,
+                        collation,
+                    }) => {
                         *left = *const_map.get(left).unwrap_or(left);
                         *right = *const_map.get(right).unwrap_or(right);
 
+                        // Collated comparison is left to the storage engine.
+                        if collation.is_some() {
+                            continue;
+                        }
+
                         let op = *op;
                         let left = *left;
                         let right = *right;
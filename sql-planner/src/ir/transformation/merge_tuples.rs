@@ -78,7 +78,13 @@ impl Chain {
     /// - There is something wrong with our sub-queries.
     pub fn insert(&mut self, plan: &mut Plan, expr_id: NodeId) -> Result<(), SbroadError> {
         let bool_expr = plan.get_expression_node(expr_id)?;
-        if let Expression::Bool(BoolExpr { left, op, right }) = bool_expr {
+        if let Expression::Bool(BoolExpr {
+            left,
+            op,
+            right,
+            collation,
+        }) = bool_expr
+        {
             if let Bool::And | Bool::Or = op {
                 // We don't expect nested AND/OR expressions in DNF.
                 return Err(SbroadError::Unsupported(
@@ -90,7 +96,8 @@ impl Chain {
             }
 
             // Merge expression into tuples only for equality operators.
-            if let Bool::Eq = op {
+            // Row comparison can't keep the collation, so collated ones are left as is.
+            if let (Bool::Eq, None) = (op, collation) {
                 // Try to put expressions with references to the left side.
                 let (left_id, right_id, group_op) =
                     match (plan.is_ref(*left)?, plan.is_ref(*right)?) {
@@ -196,7 +196,12 @@ impl Plan {
                     }
                 }
             }
-            Expression::Bool(BoolExpr { op, left, right }) => {
+            Expression::Bool(BoolExpr {
+                op,
+                left,
+                right,
+                collation,
+            }) => {
                 let (remember_left, remember_right) = (*left, *right);
 
                 if let NotState::On { .. } = not_state {
@@ -205,7 +210,7 @@ impl Plan {
                         return self.cover_with_not(expr_id, &not_state);
                     };
                     if !should_proceed {
-                        return self.add_bool(*left, negated_op, *right);
+                        return self.add_collated_cond(*left, negated_op, *right, *collation);
                     }
                     let negated_left =
                         self.push_down_not_for_expression(remember_left, NotState::on(None), map)?;
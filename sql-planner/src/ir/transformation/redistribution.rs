@@ -15,7 +15,9 @@ use crate::ir::distribution::{Distribution, Key, KeySet};
 use crate::ir::expression::ColumnPositionMap;
 use crate::ir::node::expression::Expression;
 use crate::ir::node::relational::{MutRelational, RelOwned, Relational};
-use crate::ir::operator::{Bool, JoinKind, OrderByEntity, Unary, UpdateStrategy};
+use crate::ir::operator::{
+    Bool, Collation, JoinKind, NullsOrder, OrderByEntity, OrderByType, Unary, UpdateStrategy,
+};
 use std::io::Write;
 use tarantool::msgpack::{Context, Decode, DecodeError, Encode, EncodeError};
 
@@ -198,6 +200,11 @@ pub enum MotionOpcode {
     /// to `false`.
     SerializeAsEmptyTable(bool),
     RemoveDuplicates,
+    /// Stable sort of the virtual table tuples by the column on the given position.
+    /// Used for the aggregates that depend on the order of the rows
+    /// (`string_agg(a, ',' order by a)`) and are calculated on the router.
+    /// Strings are compared with the collation of the key, if it is set.
+    SortBy(ColumnPosition, OrderByType, NullsOrder, Option<Collation>),
 }
 
 impl Display for MotionOpcode {
@@ -223,6 +230,17 @@ impl Display for MotionOpcode {
             Self::RemoveDuplicates => {
                 write!(f, "RemoveDuplicates")
             }
+            Self::SortBy(position, order_type, nulls, collation) => {
+                write!(f, "SortBy({position}")?;
+                if let Some(collation) = collation {
+                    write!(f, " collate \"{collation}\"")?;
+                }
+                write!(f, " {order_type}")?;
+                if *nulls != NullsOrder::default_for(order_type) {
+                    write!(f, " {nulls}")?;
+                }
+                write!(f, ")")
+            }
             Self::ReshardIfNeeded => {
                 write!(f, "ReshardIfNeeded")
             }
@@ -238,20 +256,35 @@ struct BoolOp {
     left: NodeId,
     op: Bool,
     right: NodeId,
+    collation: Option<Collation>,
 }
 
 impl BoolOp {
     fn from_expr(plan: &Plan, expr_id: NodeId) -> Result<Self, SbroadError> {
         if let Expression::Bool(BoolExpr {
-            left, op, right, ..
+            left,
+            op,
+            right,
+            collation,
         }) = plan.get_expression_node(expr_id)?
         {
-            let (op, left, right) = (*op, *left, *right);
-            Ok(BoolOp { left, op, right })
+            let (op, left, right, collation) = (*op, *left, *right, *collation);
+            Ok(BoolOp {
+                left,
+                op,
+                right,
+                collation,
+            })
         } else {
             Err(SbroadError::Invalid(Entity::Operator, None))
         }
     }
+
+    /// Check the operator is an equality that can match the sharding keys of its operands.
+    /// Collated comparison may consider different strings equal, so it never does.
+    fn is_key_eq(&self) -> bool {
+        matches!(self.op, Bool::Eq | Bool::In) && self.collation.is_none()
+    }
 }
 
 /// Vec of opcodes that are executed in a `set_motion_vtable` function call.
@@ -704,14 +737,14 @@ impl Plan {
         &self,
         outer_id: NodeId,
         inner_id: NodeId,
-        op: &Bool,
+        op: &BoolOp,
     ) -> Result<MotionPolicy, SbroadError> {
         let outer_dist = self.get_distribution(outer_id)?;
         let inner_dist = self.get_distribution(inner_id)?;
         if let Distribution::Global = inner_dist {
             return Ok(MotionPolicy::None);
         }
-        if Bool::Eq == *op || Bool::In == *op {
+        if op.is_key_eq() {
             match outer_dist {
                 Distribution::Segment {
                     keys: ref keys_outer,
@@ -824,6 +857,7 @@ impl Plan {
         // If we eq/in where both rows contain bucket_id in same position
         // we don't need Motion nodes.
         if (left.is_some() || right.is_some())
+            && bool_op.collation.is_none()
             && self.has_eq_on_bucket_id(bool_op.left, bool_op.right, &bool_op.op)?
         {
             if let Some(left_sq) = left {
@@ -854,7 +888,7 @@ impl Plan {
                             self.choose_strategy_for_bool_op_inner_sq(
                                 bool_op.right,
                                 self.get_relational_output(left_sq)?,
-                                &bool_op.op,
+                                &bool_op,
                             )?,
                         ));
                     }
@@ -868,7 +902,7 @@ impl Plan {
                         self.choose_strategy_for_bool_op_inner_sq(
                             bool_op.left,
                             self.get_relational_output(right_sq)?,
-                            &bool_op.op,
+                            &bool_op,
                         )?,
                     ));
                 }
@@ -1592,7 +1626,7 @@ impl Plan {
             // Ok, we don't have any sub-queries.
             // Lets try to improve the motion policy for the inner join child.
             new_inner_policy = match bool_op.op {
-                Bool::Eq | Bool::In => {
+                Bool::Eq | Bool::In if bool_op.collation.is_none() => {
                     let left_id = self.get_child_under_cast(bool_op.left)?;
                     let right_id = self.get_child_under_cast(bool_op.right)?;
                     let left_expr = self.get_expression_node(left_id)?;
@@ -1728,7 +1762,7 @@ impl Plan {
                     op: Bool::Eq | Bool::In,
                     left,
                     right,
-                    ..
+                    collation: None,
                 }) = self.get_expression_node(node_id)?
                 {
                     for child_id in [left, right] {
@@ -2089,7 +2123,25 @@ impl Plan {
     fn resolve_delete_conflicts(&mut self, rel_id: NodeId) -> Result<Strategy, SbroadError> {
         let table = self.dml_node_table(rel_id)?;
         if table.is_global() {
-            return self.resolve_dml_node_conflict_for_global_table(rel_id);
+            let mut map = self.resolve_dml_node_conflict_for_global_table(rel_id)?;
+            // `DELETE ... USING` yields a primary key for every matched row
+            // of the source, so the same key may appear several times.
+            if let Some(child_id) = self.children(rel_id).get(0).copied() {
+                let is_using =
+                    matches!(self.get_relation_node(child_id)?, Relational::Projection(_))
+                        && matches!(
+                            self.get_relation_node(self.get_first_rel_child(child_id)?)?,
+                            Relational::Join(_)
+                        );
+                if is_using {
+                    map.upsert_child(
+                        child_id,
+                        MotionPolicy::Full,
+                        Program::new(vec![MotionOpcode::RemoveDuplicates]),
+                    );
+                }
+            }
+            return Ok(map);
         }
 
         let mut map = Strategy::new(rel_id);
@@ -2156,8 +2208,10 @@ impl Plan {
         let motion_key = self.insert_motion_key(rel_id)?;
         let child_dist = self.get_rel_distribution(child_id)?;
 
-        // Check that we can make a local segment motion.
-        if let Distribution::Segment { keys } = child_dist {
+        // Check that we can make a local segment motion. Identity values
+        // are generated on the router, so the data must be materialized there.
+        let has_identity = self.insert_omitted_identity(rel_id)?.is_some();
+        if let (Distribution::Segment { keys }, false) = (child_dist, has_identity) {
             for key in keys.iter() {
                 let insert_mkey = MotionKey::from(key);
                 if insert_mkey == motion_key {
@@ -2210,6 +2264,48 @@ impl Plan {
         Ok(map)
     }
 
+    /// Recursive CTE and its working table are always materialized on the router,
+    /// as the router iterates over the recursive term.
+    fn resolve_recursive_cte_conflicts(&mut self, cte_id: NodeId) -> Result<Strategy, SbroadError> {
+        let mut map = Strategy::new(cte_id);
+        let child_id = self.get_first_rel_child(cte_id)?;
+        if !self.get_relation_node(child_id)?.is_motion() {
+            map.upsert_child(child_id, MotionPolicy::Full, Program::default());
+        }
+        Ok(map)
+    }
+
+    /// The recursive term of the recursive CTE is gathered on the router on
+    /// every iteration, while the working table is already materialized there.
+    fn resolve_recursive_union_conflicts(
+        &mut self,
+        union_id: NodeId,
+    ) -> Result<Strategy, SbroadError> {
+        let mut map = Strategy::new(union_id);
+        let children = self.get_relation_children(union_id)?;
+        let recursive_term_id = *children.get(1).ok_or_else(|| {
+            SbroadError::UnexpectedNumberOfValues(
+                "recursive CTE union must have exactly two children".into(),
+            )
+        })?;
+        if !self.get_relation_node(recursive_term_id)?.is_motion() {
+            map.upsert_child(recursive_term_id, MotionPolicy::Full, Program::default());
+        }
+        Ok(map)
+    }
+
+    /// Storages don't support `INTERSECT ALL` and `EXCEPT ALL`, so both children
+    /// are gathered on the router and combined there.
+    fn resolve_multiset_conflicts(&mut self, rel_id: NodeId) -> Result<Strategy, SbroadError> {
+        let mut map = Strategy::new(rel_id);
+        for child_id in self.get_relation_children(rel_id)?.iter().copied() {
+            if !self.get_relation_node(child_id)?.is_motion() {
+                map.upsert_child(child_id, MotionPolicy::Full, Program::default());
+            }
+        }
+        Ok(map)
+    }
+
     /// Helper function to check whether except is done between
     /// sharded tables that both contain the `bucket_id` column
     /// at the same position in their outputs. In such case
@@ -2246,16 +2342,23 @@ impl Plan {
 
     #[allow(clippy::too_many_lines)]
     fn resolve_except_conflicts(&mut self, rel_id: NodeId) -> Result<Strategy, SbroadError> {
-        if !matches!(self.get_relation_node(rel_id)?, Relational::Except(_)) {
-            return Err(SbroadError::Invalid(
-                Entity::Relational,
-                Some("expected Except node".into()),
-            ));
-        }
+        let is_except = match self.get_relation_node(rel_id)? {
+            Relational::Except(_) => true,
+            Relational::Intersect(_) => false,
+            _ => {
+                return Err(SbroadError::Invalid(
+                    Entity::Relational,
+                    Some("expected Except or Intersect node".into()),
+                ))
+            }
+        };
 
         let mut map = Strategy::new(rel_id);
 
-        if self.resolve_except_global_vs_sharded(rel_id)? {
+        // Unlike except, the result of intersect with a global child
+        // is contained in the sharded one, so there is no need to
+        // prefilter the global child on the storages.
+        if is_except && self.resolve_except_global_vs_sharded(rel_id)? {
             return Ok(map);
         }
 
@@ -2394,6 +2497,7 @@ impl Plan {
             left: right_id,
             right: cloned_left_id,
             output: intersect_output_id,
+            all: false,
         };
         let intersect_id = self.add_relational(intersect.into())?;
 
@@ -2599,6 +2703,21 @@ impl Plan {
         let mut cte_motions: AHashMap<CteChildId, MotionId> = AHashMap::with_capacity(CTE_CAPACITY);
         let post_tree = PostOrder::with_capacity(|node| self.nodes.rel_iter(node), REL_CAPACITY);
         let nodes = post_tree.populate_nodes(top_id);
+        // Recursive CTEs are evaluated on the router, so their unions of the working
+        // table and the recursive term are never executed as a whole.
+        let mut recursive_unions: AHashSet<NodeId> = AHashSet::new();
+        let mut working_tables: AHashSet<NodeId> = AHashSet::new();
+        for LevelNode(_, id) in &nodes {
+            if let Relational::ScanCte(ScanCte {
+                child,
+                is_recursive: true,
+                ..
+            }) = self.get_relation_node(*id)?
+            {
+                recursive_unions.insert(*child);
+                working_tables.insert(self.get_first_rel_child(*child)?);
+            }
+        }
         // Set of already visited nodes. Used for the case of BETWEEN where two expressions may
         // refer to the same relational node.
         let mut visited = AHashSet::with_capacity(nodes.len());
@@ -2650,28 +2769,46 @@ impl Plan {
                 }
             }
 
+            if recursive_unions.contains(&id) {
+                let strategy = self.resolve_recursive_union_conflicts(id)?;
+                self.insert_motion_nodes(strategy)?;
+                self.set_dist(self.get_relational_output(id)?, Distribution::Single)?;
+                visited.insert(id);
+                continue;
+            }
+
             match node {
                 RelOwned::Motion { .. } => {
                     // We can apply this transformation only once,
                     // i.e. to the plan without any motion nodes.
                     panic!("IR mustn't contain Motion nodes at the stage of redistribution.")
                 }
-                RelOwned::Limit(Limit { output, limit, .. }) => {
+                RelOwned::Limit(Limit {
+                    output,
+                    limit,
+                    offset,
+                    with_ties,
+                    ..
+                }) => {
                     let rel_child_id = self.get_first_rel_child(id)?;
                     let child_dist = self.get_rel_distribution(rel_child_id)?.clone();
 
-                    match child_dist {
-                        Distribution::Single | Distribution::Global => {
+                    match (child_dist, limit) {
+                        (child_dist @ (Distribution::Single | Distribution::Global), _) => {
                             // All rows on a single node, no motion needed.
                             self.set_dist(output, child_dist)?;
                         }
-                        Distribution::Any | Distribution::Segment { .. } => {
+                        (child_dist, Some(limit)) if !with_ties => {
                             // Rows are distributed, so motion needed with full policy to
                             // bring them on a single node.
 
-                            // We don't need more than limit rows, so we can add a limit for the
-                            // queries sent during the map stage.
-                            let limit_id = self.add_limit(id, limit)?;
+                            // We don't need more than limit rows (plus the skipped ones),
+                            // so we can add a limit for the queries sent during the map stage.
+                            let limit_id = self.add_limit(id, Some(limit), offset, false)?;
+                            if let MutRelational::Limit(local) = self.get_mut_relation_node(id)? {
+                                local.limit = Some(limit.saturating_add(offset));
+                                local.offset = 0;
+                            }
                             self.set_dist(
                                 self.get_relational_output(limit_id)?,
                                 Distribution::Single,
@@ -2682,11 +2819,22 @@ impl Plan {
                             self.insert_motion_nodes(strategy)?;
                             self.set_dist(output, child_dist)?;
                         }
+                        _ => {
+                            // The number of rows needed from the storages is unknown,
+                            // so all of them are gathered on a single node.
+                            let mut strategy = Strategy::new(id);
+                            strategy.upsert_child(
+                                rel_child_id,
+                                MotionPolicy::Full,
+                                Program::default(),
+                            );
+                            self.insert_motion_nodes(strategy)?;
+                            self.set_dist(output, Distribution::Single)?;
+                        }
                     }
                 }
                 RelOwned::ScanRelation(ScanRelation { .. })
                 | RelOwned::ScanSubQuery(ScanSubQuery { .. })
-                | RelOwned::Intersect(Intersect { .. })
                 | RelOwned::Having(Having { .. }) => {
                     // Note: For `Having` true distribution is calculated
                     //       at the end of `add_two_stage_aggregation` function
@@ -2775,6 +2923,37 @@ impl Plan {
                     };
                     let target_dist = self.get_rel_distribution(target_dist_node)?;
 
+                    if let Some((position, order_type, nulls, collation)) =
+                        self.get_ordered_aggregates_key(id, having)?
+                    {
+                        if matches!(target_dist, Distribution::Single | Distribution::Global) {
+                            return Err(SbroadError::NotImplemented(
+                                Entity::Aggregate,
+                                "ORDER BY over non-sharded data".into(),
+                            ));
+                        }
+                        // Partial results of order dependent aggregates can't be merged
+                        // on the router, so we calculate them in one stage: all the rows
+                        // are moved to the router and sorted there by the aggregate key.
+                        // The motion is capped by `sql_motion_row_max` like any other.
+                        let lowest_id = group_by.or(having).unwrap_or(id);
+                        let lowest_child_id = self.get_first_rel_child(lowest_id)?;
+                        let mut strategy = Strategy::new(lowest_id);
+                        strategy.upsert_child(
+                            lowest_child_id,
+                            MotionPolicy::Full,
+                            Program::new(vec![MotionOpcode::SortBy(
+                                position, order_type, nulls, collation,
+                            )]),
+                        );
+                        self.insert_motion_nodes(strategy)?;
+                        for rel_id in [group_by, having, Some(id)].into_iter().flatten() {
+                            let rel_output = self.get_relational_output(rel_id)?;
+                            self.set_dist(rel_output, Distribution::Single)?;
+                        }
+                        continue;
+                    }
+
                     if matches!(target_dist, Distribution::Single | Distribution::Global) {
                         // The data is already on the current node, let's just set the
                         // distribution.
@@ -2868,10 +3047,21 @@ impl Plan {
                     self.insert_motion_nodes(strategy)?;
                     self.set_rel_output_distribution(id)?;
                 }
-                RelOwned::Except(Except { .. }) => {
-                    let strategy = self.resolve_except_conflicts(id)?;
-                    self.insert_motion_nodes(strategy)?;
-                    self.set_rel_output_distribution(id)?;
+                RelOwned::Except(Except { output, all, .. })
+                | RelOwned::Intersect(Intersect { output, all, .. }) => {
+                    if all {
+                        let strategy = self.resolve_multiset_conflicts(id)?;
+                        self.insert_motion_nodes(strategy)?;
+                        self.set_dist(output, Distribution::Single)?;
+                        // The children are combined on the router by this motion.
+                        let new_top_id =
+                            self.add_motion(id, &MotionPolicy::Full, Program::default())?;
+                        old_new.insert(id, new_top_id);
+                    } else {
+                        let strategy = self.resolve_except_conflicts(id)?;
+                        self.insert_motion_nodes(strategy)?;
+                        self.set_rel_output_distribution(id)?;
+                    }
                 }
                 RelOwned::Union(Union { .. }) => {
                     let strategy = self.resolve_union_conflicts(id)?;
@@ -2889,7 +3079,12 @@ impl Plan {
                     self.insert_motion_nodes(strategy)?;
                     self.set_rel_output_distribution(id)?;
                 }
-                RelOwned::ScanCte(ScanCte { output, child, .. }) => {
+                RelOwned::ScanCte(ScanCte {
+                    output,
+                    child,
+                    is_recursive,
+                    ..
+                }) => {
                     // Possible, current CTE subtree has already been resolved and we
                     // can just copy the corresponding motion node.
                     if let Some(motion_id) = cte_motions.get(&child) {
@@ -2900,7 +3095,11 @@ impl Plan {
                             *motion_id,
                         )?;
                     } else {
-                        let strategy = self.resolve_cte_conflicts(id)?;
+                        let strategy = if is_recursive || working_tables.contains(&id) {
+                            self.resolve_recursive_cte_conflicts(id)?
+                        } else {
+                            self.resolve_cte_conflicts(id)?
+                        };
                         self.insert_motion_nodes(strategy)?;
                         let new_child_id = self.get_first_rel_child(id)?;
                         let new_child_node = self.get_relation_node(new_child_id)?;
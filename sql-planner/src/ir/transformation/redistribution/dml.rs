@@ -2,7 +2,7 @@ use crate::errors::{Entity, SbroadError};
 use crate::ir::node::relational::{MutRelational, Relational};
 use crate::ir::node::{Delete, Insert, Motion, NodeId, Update};
 use crate::ir::operator::{ConflictStrategy, UpdateStrategy};
-use crate::ir::relation::{Column, Table};
+use crate::ir::relation::Table;
 use crate::ir::transformation::redistribution::MotionOpcode;
 use crate::ir::Plan;
 use ahash::AHashMap;
@@ -78,12 +78,17 @@ impl Plan {
 
         let mut motion_key: MotionKey = MotionKey::new();
         let table = self.dml_node_table(insert_id)?;
+        let identity = self.insert_omitted_identity(insert_id)?;
         for pos in table.get_sk()? {
             if let Some(child_pos) = columns_map.get(pos) {
                 // We can use insert column's position instead of
                 // the position in the child node as their lengths
                 // are the same.
                 motion_key.targets.push(Target::Reference(*child_pos));
+            } else if identity == Some(*pos) {
+                // Generated identity values are appended to the end
+                // of the virtual table before resharding.
+                motion_key.targets.push(Target::Reference(columns.len()));
             } else {
                 // Check that the column exists on the requested position.
                 table.columns.get(*pos).ok_or_else(|| {
@@ -95,12 +100,23 @@ impl Plan {
                 // We need a default value for the key column.
                 motion_key
                     .targets
-                    .push(Target::Value(Column::default_value()));
+                    .push(Target::Value(table.column_default(*pos)));
             }
         }
         Ok(motion_key)
     }
 
+    /// Return position of the table identity column if it is omitted
+    /// in the given `Insert` and must be filled from the sequence.
+    ///
+    /// # Errors
+    /// - node is not `Insert`
+    pub fn insert_omitted_identity(&self, insert_id: NodeId) -> Result<Option<usize>, SbroadError> {
+        let columns = self.insert_columns(insert_id)?;
+        let table = self.dml_node_table(insert_id)?;
+        Ok(table.identity.filter(|pos| !columns.contains(pos)))
+    }
+
     /// Return ids of `Insert` columns
     ///
     /// # Errors
@@ -399,7 +399,7 @@ impl EqualityCols {
             _ => return Ok(None),
         };
         let res = match op.op {
-            Bool::Eq | Bool::In => EqualityCols::eq_cols_for_eq(
+            Bool::Eq | Bool::In if op.collation.is_none() => EqualityCols::eq_cols_for_eq(
                 list_left, list_right, node_id, inner_id, outer_id, plan, refers_to,
             )?,
             _ => {
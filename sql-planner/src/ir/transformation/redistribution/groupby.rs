@@ -1,4 +1,4 @@
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
 use smol_str::{format_smolstr, ToSmolStr};
 
 use crate::errors::{Entity, SbroadError};
@@ -550,6 +550,38 @@ impl Plan {
         Ok(false)
     }
 
+    /// Check that grouping expressions contain all the columns of some
+    /// distribution key of the GroupBy child. In that case every group
+    /// lives on a single bucket and GroupBy can be done locally.
+    ///
+    /// Grouping by a part of the key doesn't guarantee that, e.g. for
+    /// a table sharded by `(a, b)` the rows of group `a = 1` can be spread
+    /// across all the buckets.
+    fn check_sharding_key_under_group_by(
+        &self,
+        group_by_id: NodeId,
+        grouping_exprs: &[NodeId],
+    ) -> Result<bool, SbroadError> {
+        let child_id = self.get_first_rel_child(group_by_id)?;
+        let Distribution::Segment { keys } = self.get_rel_distribution(child_id)? else {
+            return Ok(false);
+        };
+        let mut positions = AHashSet::with_capacity(grouping_exprs.len());
+        for expr_id in grouping_exprs {
+            let Expression::Reference(Reference { position, .. }) =
+                self.get_expression_node(*expr_id)?
+            else {
+                continue;
+            };
+            if self.get_relational_from_reference_node(*expr_id)? == child_id {
+                positions.insert(*position);
+            }
+        }
+        Ok(keys
+            .iter()
+            .any(|key| key.positions.iter().all(|pos| positions.contains(pos))))
+    }
+
     /// "Grouping exprs" are expressions that are used in GroupBy clause
     /// (on both Map and Reduce stages of the algorithm). In this function we try
     /// to identify which grouping exprs we have to add in order to
@@ -1331,6 +1363,11 @@ impl Plan {
             //
             // Currently it's the only case when GroupBy will be present
             // on a Map (local) stage, but will not be generated for Reduce (final) stage.
+            //
+            // Distinct aggregates computed on each storage can't be combined (e.g. the sum
+            // of per-storage `count(distinct a)` counts values present on several storages
+            // more than once), so the Map stage only deduplicates the arguments and the
+            // aggregate itself is calculated on the router after the Motion.
 
             let group_by_id = self.add_groupby(upper_local_node, &[])?;
             upper_local_node = group_by_id;
@@ -1351,6 +1388,17 @@ impl Plan {
             {
                 return Ok(false);
             }
+            // Arguments of distinct aggregates have already been added to the
+            // local GroupBy, so such queries still require the Reduce stage.
+            if !groupby_info.grouping_exprs.is_empty()
+                && !distinct_aggrs_are_present
+                && self.check_sharding_key_under_group_by(
+                    groupby_info.id,
+                    &groupby_info.grouping_exprs,
+                )?
+            {
+                return Ok(false);
+            }
         }
 
         // We have to move scalar subqueries from non distinct aggregates to local Projection.
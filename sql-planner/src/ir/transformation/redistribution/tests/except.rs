@@ -113,3 +113,49 @@ fn except5() {
     let no_other_motions = get_motion_id(&plan, 0, 1).is_none();
     assert_eq!(no_other_motions, true);
 }
+
+#[test]
+fn intersect1() {
+    let query = r#"SELECT "identification_number", "product_code" FROM "hash_testing"
+        INTERSECT
+        SELECT 1, '2' FROM "hash_testing_hist""#;
+
+    let plan = sql_to_ir(query, vec![]).add_motions().unwrap();
+    let motion_id = *get_motion_id(&plan, 0, 0).unwrap();
+    let motion = plan.get_relation_node(motion_id).unwrap();
+    if let Relational::Motion(Motion { policy, .. }) = motion {
+        assert_eq!(
+            *policy,
+            MotionPolicy::Segment(
+                (Key {
+                    positions: vec![0, 1]
+                })
+                .into()
+            )
+        );
+    } else {
+        panic!("Expected a motion node");
+    }
+}
+
+#[test]
+fn except_all1() {
+    let query = r#"SELECT "identification_number", "product_code" FROM "hash_testing"
+        EXCEPT ALL
+        SELECT "identification_number", "product_code" FROM "hash_testing_hist""#;
+
+    let plan = sql_to_ir(query, vec![]).add_motions().unwrap();
+    // Both children are gathered on the router ...
+    for idx in 0..2 {
+        let motion_id = *get_motion_id(&plan, 0, idx).unwrap();
+        let motion = plan.get_relation_node(motion_id).unwrap();
+        if let Relational::Motion(Motion { policy, .. }) = motion {
+            assert_eq!(*policy, MotionPolicy::Full);
+        } else {
+            panic!("Expected a motion node");
+        }
+    }
+    // ... and combined by the top motion.
+    let top_id = plan.get_top().unwrap();
+    assert_eq!(Some(&top_id), get_motion_id(&plan, 1, 0));
+}
@@ -54,10 +54,13 @@ impl Plan {
     /// - If the plan is invalid for some unknown reason.
     fn split_bool(&mut self, top_id: NodeId) -> Result<ExprId, SbroadError> {
         let top_expr = self.get_expression_node(top_id)?;
-        let (left_id, right_id, op) = match top_expr {
+        let (left_id, right_id, op, collation) = match top_expr {
             Expression::Bool(BoolExpr {
-                left, op, right, ..
-            }) => (*left, *right, *op),
+                left,
+                op,
+                right,
+                collation,
+            }) => (*left, *right, *op, *collation),
             _ => {
                 return Err(SbroadError::Invalid(
                     Entity::Expression,
@@ -103,12 +106,14 @@ impl Plan {
             if let Some((first, other)) = pairs.split_first() {
                 let left_col_id = first.0;
                 let right_col_id = first.1;
-                let mut new_top_id = self.add_cond(left_col_id, op, right_col_id)?;
+                let mut new_top_id =
+                    self.add_collated_cond(left_col_id, op, right_col_id, collation)?;
 
                 for (left_col_id, right_col_id) in other {
                     let left_col_id = *left_col_id;
                     let right_col_id = *right_col_id;
-                    let cond_id = self.add_cond(left_col_id, op, right_col_id)?;
+                    let cond_id =
+                        self.add_collated_cond(left_col_id, op, right_col_id, collation)?;
                     new_top_id = self.concat_and(new_top_id, cond_id)?;
                 }
 
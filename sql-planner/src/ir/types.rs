@@ -25,6 +25,7 @@ pub enum DomainType {
     Integer,
     String,
     Uuid,
+    Bytea,
     Unsigned,
     Any, // Not yet supported in grammar
 }
@@ -43,6 +44,7 @@ impl DomainType {
                 | Self::Integer
                 | Self::String
                 | Self::Uuid
+                | Self::Bytea
                 | Self::Unsigned
         )
     }
@@ -58,6 +60,7 @@ impl fmt::Display for DomainType {
             DomainType::Integer => write!(f, "int"),
             DomainType::String => write!(f, "string"),
             DomainType::Uuid => write!(f, "uuid"),
+            DomainType::Bytea => write!(f, "varbinary"),
             DomainType::Json => write!(f, "json"),
             DomainType::Unsigned => write!(f, "unsigned"),
             DomainType::Any => write!(f, "any"),
@@ -75,6 +78,7 @@ impl From<&DomainType> for FieldType {
             DomainType::Integer => FieldType::Integer,
             DomainType::Unsigned => FieldType::Unsigned,
             DomainType::Uuid => FieldType::Uuid,
+            DomainType::Bytea => FieldType::Varbinary,
             DomainType::String => FieldType::String,
             DomainType::Json => FieldType::Map,
             DomainType::Any => FieldType::Any,
@@ -93,6 +97,7 @@ impl From<&DomainType> for SpaceFieldType {
             DomainType::Unsigned => SpaceFieldType::Unsigned,
             DomainType::String => SpaceFieldType::String,
             DomainType::Uuid => SpaceFieldType::Uuid,
+            DomainType::Bytea => SpaceFieldType::Varbinary,
             DomainType::Json => SpaceFieldType::Map,
             DomainType::Any => SpaceFieldType::Any,
         }
@@ -109,6 +114,7 @@ impl From<&DomainType> for UnrestrictedType {
             DomainType::Integer | DomainType::Unsigned => UnrestrictedType::Integer,
             DomainType::String => UnrestrictedType::String,
             DomainType::Uuid => UnrestrictedType::Uuid,
+            DomainType::Bytea => UnrestrictedType::Bytea,
             DomainType::Json => UnrestrictedType::Map,
             DomainType::Any => UnrestrictedType::Any,
         }
@@ -122,10 +128,50 @@ pub enum CastType {
     Datetime,
     Decimal,
     Double,
+    /// 64-bit integer (`int`, `integer`, `bigint`, `int8`).
     Integer,
+    /// 16-bit integer (`smallint`, `int2`).
+    SmallInt,
+    /// 32-bit integer (`int4`).
+    Int4,
     Json,
     String,
     Uuid,
+    Bytea,
+    /// Date at midnight UTC (`date`), stored as a datetime.
+    Date,
+    /// Time of day (`time [without time zone]`), stored as text
+    /// `hh:mm:ss[.ffffff]` which orders like the time itself.
+    Time,
+    /// Local date and time (`timestamp [without time zone]`), stored
+    /// as a datetime in UTC.
+    Timestamp,
+}
+
+impl CastType {
+    /// Name of a temporal type stored as a value of another type.
+    /// The conversion is done by a builtin function on the storages.
+    #[must_use]
+    pub fn temporal_name(&self) -> Option<&'static str> {
+        match self {
+            CastType::Date => Some("date"),
+            CastType::Time => Some("time"),
+            CastType::Timestamp => Some("timestamp"),
+            _ => None,
+        }
+    }
+
+    /// Bounds and the name of an integer type narrower than 64 bits.
+    /// Storages know only 64-bit integers, so the bounds are checked
+    /// separately from the cast.
+    #[must_use]
+    pub fn integer_bounds(&self) -> Option<(i64, i64, &'static str)> {
+        match self {
+            CastType::SmallInt => Some((i16::MIN.into(), i16::MAX.into(), "smallint")),
+            CastType::Int4 => Some((i32::MIN.into(), i32::MAX.into(), "integer")),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for CastType {
@@ -133,11 +179,12 @@ impl fmt::Display for CastType {
         match self {
             CastType::Boolean => write!(f, "bool"),
             CastType::Decimal => write!(f, "decimal"),
-            CastType::Datetime => write!(f, "datetime"),
+            CastType::Datetime | CastType::Date | CastType::Timestamp => write!(f, "datetime"),
             CastType::Double => write!(f, "double"),
-            CastType::Integer => write!(f, "int"),
-            CastType::String => write!(f, "string"),
+            CastType::Integer | CastType::SmallInt | CastType::Int4 => write!(f, "int"),
+            CastType::String | CastType::Time => write!(f, "string"),
             CastType::Uuid => write!(f, "uuid"),
+            CastType::Bytea => write!(f, "varbinary"),
             CastType::Json => write!(f, "map"),
         }
     }
@@ -147,13 +194,14 @@ impl From<&CastType> for TypeSystemType {
     fn from(value: &CastType) -> Self {
         match value {
             CastType::Boolean => TypeSystemType::Boolean,
-            CastType::Datetime => TypeSystemType::Datetime,
+            CastType::Datetime | CastType::Date | CastType::Timestamp => TypeSystemType::Datetime,
             CastType::Double => TypeSystemType::Double,
             CastType::Decimal => TypeSystemType::Numeric,
-            CastType::Integer => TypeSystemType::Integer,
+            CastType::Integer | CastType::SmallInt | CastType::Int4 => TypeSystemType::Integer,
             CastType::Json => TypeSystemType::Map,
-            CastType::String => TypeSystemType::Text,
+            CastType::String | CastType::Time => TypeSystemType::Text,
             CastType::Uuid => TypeSystemType::Uuid,
+            CastType::Bytea => TypeSystemType::Bytea,
         }
     }
 }
@@ -169,6 +217,7 @@ impl TryFrom<&UnrestrictedType> for CastType {
             UnrestrictedType::Double => Ok(CastType::Double),
             UnrestrictedType::Integer => Ok(CastType::Integer),
             UnrestrictedType::Uuid => Ok(CastType::Uuid),
+            UnrestrictedType::Bytea => Ok(CastType::Bytea),
             UnrestrictedType::String => Ok(CastType::String),
             UnrestrictedType::Map | UnrestrictedType::Any | UnrestrictedType::Array => {
                 Err(Self::Error::Invalid(
@@ -192,6 +241,10 @@ impl TryFrom<&Rule> for CastType {
             Rule::TypeInt => Ok(Self::Integer),
             Rule::TypeString | Rule::TypeText | Rule::TypeVarchar => Ok(Self::String),
             Rule::TypeUuid => Ok(Self::Uuid),
+            Rule::TypeBytea => Ok(Self::Bytea),
+            Rule::TypeDate => Ok(Self::Date),
+            Rule::TypeTime => Ok(Self::Time),
+            Rule::TypeTimestamp => Ok(Self::Timestamp),
             _ => Err(SbroadError::Unsupported(
                 Entity::Type,
                 Some(format_smolstr!("{ast_type:?}")),
@@ -221,6 +274,7 @@ pub enum UnrestrictedType {
     Integer,
     String,
     Uuid,
+    Bytea,
 
     // Internal service types
     Any,
@@ -244,6 +298,7 @@ impl UnrestrictedType {
             "integer" | "unsigned" => Ok(UnrestrictedType::Integer),
             "string" | "text" => Ok(UnrestrictedType::String),
             "uuid" => Ok(UnrestrictedType::Uuid),
+            "varbinary" | "bytea" => Ok(UnrestrictedType::Bytea),
             "array" => Ok(UnrestrictedType::Array),
             "map" => Ok(UnrestrictedType::Map),
             v => Err(SbroadError::Invalid(
@@ -266,6 +321,7 @@ impl UnrestrictedType {
                 | UnrestrictedType::Integer
                 | UnrestrictedType::String
                 | UnrestrictedType::Uuid
+                | UnrestrictedType::Bytea
         )
     }
 
@@ -288,6 +344,10 @@ impl UnrestrictedType {
                     UnrestrictedType::String | UnrestrictedType::Uuid,
                     UnrestrictedType::String | UnrestrictedType::Uuid
                 )
+                | (
+                    UnrestrictedType::String | UnrestrictedType::Bytea,
+                    UnrestrictedType::Bytea
+                )
         )
     }
 }
@@ -296,13 +356,14 @@ impl From<CastType> for UnrestrictedType {
     fn from(cast_type: CastType) -> Self {
         match cast_type {
             CastType::Boolean => UnrestrictedType::Boolean,
-            CastType::Datetime => UnrestrictedType::Datetime,
+            CastType::Datetime | CastType::Date | CastType::Timestamp => UnrestrictedType::Datetime,
             CastType::Decimal => UnrestrictedType::Decimal,
             CastType::Double => UnrestrictedType::Double,
-            CastType::Integer => UnrestrictedType::Integer,
+            CastType::Integer | CastType::SmallInt | CastType::Int4 => UnrestrictedType::Integer,
             CastType::Json => UnrestrictedType::Map,
-            CastType::String => UnrestrictedType::String,
+            CastType::String | CastType::Time => UnrestrictedType::String,
             CastType::Uuid => UnrestrictedType::Uuid,
+            CastType::Bytea => UnrestrictedType::Bytea,
         }
     }
 }
@@ -316,10 +377,11 @@ impl TryFrom<Rule> for UnrestrictedType {
             Rule::TypeDatetime => Ok(Self::Datetime),
             Rule::TypeDecimal => Ok(Self::Decimal),
             Rule::TypeDouble => Ok(Self::Double),
-            Rule::TypeJSON => Ok(Self::Double),
+            Rule::TypeJSON => Ok(Self::Map),
             Rule::TypeInt => Ok(Self::Integer),
             Rule::TypeString | Rule::TypeText | Rule::TypeVarchar => Ok(Self::String),
             Rule::TypeUuid => Ok(Self::Uuid),
+            Rule::TypeBytea => Ok(Self::Bytea),
             _ => Err(SbroadError::Unsupported(
                 Entity::Type,
                 Some(format_smolstr!("{ast_type:?}")),
@@ -341,11 +403,13 @@ impl TryFrom<SpaceFieldType> for UnrestrictedType {
             SpaceFieldType::String => Ok(UnrestrictedType::String),
             SpaceFieldType::Array => Ok(UnrestrictedType::Array),
             SpaceFieldType::Uuid => Ok(UnrestrictedType::Uuid),
+            SpaceFieldType::Varbinary => Ok(UnrestrictedType::Bytea),
             SpaceFieldType::Any => Ok(UnrestrictedType::Any),
             SpaceFieldType::Map => Ok(UnrestrictedType::Map),
-            SpaceFieldType::Varbinary | SpaceFieldType::Interval => Err(
-                SbroadError::NotImplemented(Entity::Type, field_type.to_smolstr()),
-            ),
+            SpaceFieldType::Interval => Err(SbroadError::NotImplemented(
+                Entity::Type,
+                field_type.to_smolstr(),
+            )),
             SpaceFieldType::Number | SpaceFieldType::Scalar => Err(SbroadError::Unsupported(
                 Entity::Type,
                 Some(field_type.to_smolstr()),
@@ -363,6 +427,7 @@ impl From<&UnrestrictedType> for FieldType {
             UnrestrictedType::Double => FieldType::Double,
             UnrestrictedType::Integer => FieldType::Integer,
             UnrestrictedType::Uuid => FieldType::Uuid,
+            UnrestrictedType::Bytea => FieldType::Varbinary,
             UnrestrictedType::String => FieldType::String,
             UnrestrictedType::Array => FieldType::Array,
             UnrestrictedType::Map => FieldType::Map,
@@ -381,6 +446,7 @@ impl From<&UnrestrictedType> for SpaceFieldType {
             UnrestrictedType::Integer => SpaceFieldType::Integer,
             UnrestrictedType::String => SpaceFieldType::String,
             UnrestrictedType::Uuid => SpaceFieldType::Uuid,
+            UnrestrictedType::Bytea => SpaceFieldType::Varbinary,
             UnrestrictedType::Array => SpaceFieldType::Array,
             UnrestrictedType::Map => SpaceFieldType::Map,
             UnrestrictedType::Any => SpaceFieldType::Any,
@@ -398,6 +464,7 @@ impl fmt::Display for UnrestrictedType {
             UnrestrictedType::Integer => write!(f, "int"),
             UnrestrictedType::String => write!(f, "string"),
             UnrestrictedType::Uuid => write!(f, "uuid"),
+            UnrestrictedType::Bytea => write!(f, "varbinary"),
             UnrestrictedType::Map => write!(f, "map"),
             UnrestrictedType::Array => write!(f, "array"),
             UnrestrictedType::Any => write!(f, "any"),
@@ -465,6 +532,7 @@ impl From<&ColumnType> for DerivedType {
             ColumnType::Integer => Self::new(UnrestrictedType::Integer),
             ColumnType::String => Self::new(UnrestrictedType::String),
             ColumnType::Uuid => Self::new(UnrestrictedType::Uuid),
+            ColumnType::Bytea => Self::new(UnrestrictedType::Bytea),
             ColumnType::Any => Self::new(UnrestrictedType::Any),
             ColumnType::Array => Self::new(UnrestrictedType::Array),
             ColumnType::Scalar => Self::unknown(),
@@ -493,6 +561,7 @@ impl From<&DerivedType> for ColumnType {
             UnrestrictedType::Integer => ColumnType::Integer,
             UnrestrictedType::String => ColumnType::String,
             UnrestrictedType::Uuid => ColumnType::Uuid,
+            UnrestrictedType::Bytea => ColumnType::Bytea,
             UnrestrictedType::Any => ColumnType::Any,
             UnrestrictedType::Array => ColumnType::Array,
         }
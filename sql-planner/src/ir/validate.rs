@@ -0,0 +1,124 @@
+//! Consistency checks of the plan tree.
+//!
+//! A transformation may leave the plan broken in a way noticed much later
+//! (or not at all): a dangling node id, a reference to a node that isn't a
+//! child of its relational operator and so on. [`Plan::validate`] walks the
+//! tree and reports the first violated invariant. It is meant for tests and
+//! fuzzing, not for the query execution path.
+
+use ahash::{AHashMap, AHashSet};
+use smol_str::{format_smolstr, SmolStr};
+
+use crate::errors::{Entity, SbroadError};
+use crate::ir::node::expression::Expression;
+use crate::ir::node::{Alias, NodeId, Reference};
+use crate::ir::{Node, Plan};
+
+fn violation(msg: SmolStr) -> SbroadError {
+    SbroadError::Invalid(Entity::Plan, Some(msg))
+}
+
+impl Plan {
+    /// Check the invariants of the tree reachable from the plan top:
+    /// - every node id in the tree points to an existing node;
+    /// - reference targets are children of the relational node the
+    ///   reference belongs to, and the referred column exists in the
+    ///   target output;
+    /// - aliases don't wrap other aliases;
+    /// - once the motions are placed (the top has a distribution), every
+    ///   relational node of a DQL plan has a distribution.
+    ///
+    /// # Errors
+    /// - the plan violates one of the invariants
+    pub fn validate(&self) -> Result<(), SbroadError> {
+        let top_id = self.get_top()?;
+        let check_distribution = self.is_dql()? && self.get_rel_distribution(top_id).is_ok();
+
+        // Relational children of the visited relational nodes.
+        let mut rel_children: AHashMap<NodeId, Vec<NodeId>> = AHashMap::new();
+        let mut visited: AHashSet<NodeId> = AHashSet::new();
+        // Pairs of (node, relational node the node belongs to).
+        let mut stack = vec![(top_id, top_id)];
+        while let Some((id, owner_id)) = stack.pop() {
+            if self.get_node(id).is_err() {
+                return Err(violation(format_smolstr!("node {id} does not exist")));
+            }
+            if !visited.insert(id) {
+                // Subtrees like CTEs are shared by several nodes.
+                continue;
+            }
+
+            let children: Vec<NodeId> = self.subtree_iter(id, true).collect();
+            let owner_id = match self.get_node(id)? {
+                Node::Relational(_) => {
+                    if check_distribution && self.get_rel_distribution(id).is_err() {
+                        return Err(violation(format_smolstr!(
+                            "relational node {id} has no distribution"
+                        )));
+                    }
+                    let relational = children
+                        .iter()
+                        .filter(|child| matches!(self.get_node(**child), Ok(Node::Relational(_))))
+                        .copied()
+                        .collect();
+                    rel_children.insert(id, relational);
+                    id
+                }
+                Node::Expression(expr) => {
+                    self.validate_expression(id, &expr, owner_id, &rel_children)?;
+                    owner_id
+                }
+                _ => owner_id,
+            };
+            stack.extend(children.into_iter().map(|child| (child, owner_id)));
+        }
+        Ok(())
+    }
+
+    fn validate_expression(
+        &self,
+        id: NodeId,
+        expr: &Expression,
+        owner_id: NodeId,
+        rel_children: &AHashMap<NodeId, Vec<NodeId>>,
+    ) -> Result<(), SbroadError> {
+        match expr {
+            Expression::Alias(Alias { child, .. }) => {
+                if let Ok(Expression::Alias(_)) = self.get_expression_node(*child) {
+                    return Err(violation(format_smolstr!(
+                        "alias {id} wraps another alias {child}"
+                    )));
+                }
+            }
+            Expression::Reference(Reference {
+                target, position, ..
+            }) => {
+                let siblings = rel_children.get(&owner_id).map_or(&[][..], Vec::as_slice);
+                for target_id in target.iter() {
+                    // A motion of a dispatched plan refers to itself.
+                    if *target_id != owner_id && !siblings.contains(target_id) {
+                        return Err(violation(format_smolstr!(
+                            "reference {id} targets {target_id} which is not a child of {owner_id}"
+                        )));
+                    }
+                    let Ok(target) = self.get_relation_node(*target_id) else {
+                        return Err(violation(format_smolstr!(
+                            "reference {id} targets {target_id} which is not a relational node"
+                        )));
+                    };
+                    let columns = self.get_row_list(target.output())?.len();
+                    if *position >= columns {
+                        return Err(violation(format_smolstr!(
+                            "reference {id} refers to column {position} of {target_id} with {columns} columns"
+                        )));
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
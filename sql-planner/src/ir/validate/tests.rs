@@ -0,0 +1,131 @@
+use crate::ir::node::expression::{Expression, MutExpression};
+use crate::ir::node::{Alias, ArenaType, NodeId, ReferenceTarget};
+use crate::ir::transformation::helpers::{sql_to_ir, sql_to_optimized_ir};
+use crate::ir::Plan;
+
+const QUERY: &str = r#"SELECT "a", "b" FROM "t" WHERE "c" = 1"#;
+
+/// Projection, selection and scan of the [`QUERY`] plan.
+fn relational_nodes(plan: &Plan) -> (NodeId, NodeId, NodeId) {
+    let projection = plan.get_top().unwrap();
+    let selection = plan.children(projection)[0];
+    let scan = plan.children(selection)[0];
+    (projection, selection, scan)
+}
+
+/// The first column of the relational node output.
+fn first_column(plan: &Plan, rel_id: NodeId) -> NodeId {
+    let output = plan.get_relational_output(rel_id).unwrap();
+    plan.get_row_list(output).unwrap()[0]
+}
+
+/// The reference under the alias of the first projection column.
+fn first_reference(plan: &Plan) -> NodeId {
+    let (projection, _, _) = relational_nodes(plan);
+    let Expression::Alias(Alias { child, .. }) = plan
+        .get_expression_node(first_column(plan, projection))
+        .unwrap()
+    else {
+        panic!("expected an alias in the projection output");
+    };
+    *child
+}
+
+fn validation_error(plan: &Plan) -> String {
+    plan.validate().unwrap_err().to_string()
+}
+
+#[test]
+fn validate_correct_plans() {
+    let queries = [
+        QUERY,
+        r#"SELECT "a", count(*) FROM "t" GROUP BY "a" HAVING count(*) > 1"#,
+        r#"SELECT "c" FROM "t" UNION ALL SELECT "d" FROM "t""#,
+        r#"SELECT * FROM "t" AS t1 JOIN "t" AS t2 ON t1."c" = t2."d""#,
+        r#"SELECT "a" FROM "t" WHERE "c" IN (SELECT "d" FROM "t")"#,
+        r#"WITH cte (x) AS (SELECT "c" FROM "t") SELECT x FROM cte UNION ALL SELECT x FROM cte"#,
+        r#"INSERT INTO "t" SELECT * FROM "t""#,
+    ];
+    for query in queries {
+        let plan = sql_to_optimized_ir(query, vec![]);
+        plan.validate().unwrap_or_else(|e| panic!("{query}: {e}"));
+    }
+
+    // Distribution is not required before the motions are placed.
+    sql_to_ir(QUERY, vec![]).validate().unwrap();
+}
+
+#[test]
+fn validate_dangling_node() {
+    let mut plan = sql_to_optimized_ir(QUERY, vec![]);
+    let (projection, _, _) = relational_nodes(&plan);
+    let output = plan.get_relational_output(projection).unwrap();
+    let MutExpression::Row(row) = plan.get_mut_expression_node(output).unwrap() else {
+        panic!("expected a row");
+    };
+    row.list.push(NodeId {
+        offset: 100_000,
+        arena_type: ArenaType::Arena64,
+    });
+    assert!(validation_error(&plan).contains("does not exist"));
+}
+
+#[test]
+fn validate_reference_to_non_child() {
+    let mut plan = sql_to_optimized_ir(QUERY, vec![]);
+    let (_, _, scan) = relational_nodes(&plan);
+    let reference = first_reference(&plan);
+    let MutExpression::Reference(reference) = plan.get_mut_expression_node(reference).unwrap()
+    else {
+        panic!("expected a reference");
+    };
+    reference.target = ReferenceTarget::Single(scan);
+    assert!(validation_error(&plan).contains("is not a child of"));
+}
+
+#[test]
+fn validate_reference_position() {
+    let mut plan = sql_to_optimized_ir(QUERY, vec![]);
+    let reference = first_reference(&plan);
+    let MutExpression::Reference(reference) = plan.get_mut_expression_node(reference).unwrap()
+    else {
+        panic!("expected a reference");
+    };
+    reference.position = 100;
+    assert!(validation_error(&plan).contains("refers to column 100"));
+}
+
+#[test]
+fn validate_nested_alias() {
+    let mut plan = sql_to_optimized_ir(QUERY, vec![]);
+    let (projection, _, _) = relational_nodes(&plan);
+    let alias = first_column(&plan, projection);
+    let wrapper = plan.nodes.push(
+        Alias {
+            name: "x".into(),
+            child: alias,
+        }
+        .into(),
+    );
+    let output = plan.get_relational_output(projection).unwrap();
+    let MutExpression::Row(row) = plan.get_mut_expression_node(output).unwrap() else {
+        panic!("expected a row");
+    };
+    row.list[0] = wrapper;
+    assert!(validation_error(&plan).contains("wraps another alias"));
+}
+
+#[test]
+fn validate_missing_distribution() {
+    let mut plan = sql_to_optimized_ir(QUERY, vec![]);
+    let (_, selection, _) = relational_nodes(&plan);
+    let output = plan.get_relational_output(selection).unwrap();
+    let MutExpression::Row(row) = plan.get_mut_expression_node(output).unwrap() else {
+        panic!("expected a row");
+    };
+    row.distribution = None;
+    assert_eq!(
+        validation_error(&plan),
+        format!("invalid plan: relational node {selection} has no distribution")
+    );
+}
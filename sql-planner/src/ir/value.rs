@@ -18,12 +18,14 @@ use tarantool::msgpack::{Context, Decode, DecodeError, Encode, EncodeError, ExtS
 use tarantool::tlua;
 use tarantool::tuple::{FieldType, KeyDefPart};
 use tarantool::uuid::Uuid;
+use time::{PrimitiveDateTime, UtcOffset};
 
 use crate::error;
 use crate::errors::{Entity, SbroadError};
 use crate::executor::hash::ToHashString;
-use crate::frontend::sql::{try_parse_bool, try_parse_datetime};
-use crate::ir::types::{DerivedType, UnrestrictedType};
+use crate::frontend::sql::{try_parse_bool, try_parse_bytea, try_parse_datetime, try_parse_time};
+use crate::ir::operator::{Collation, NullsOrder, OrderByType};
+use crate::ir::types::{CastType, DerivedType, UnrestrictedType};
 use crate::ir::value::double::Double;
 
 #[derive(
@@ -144,6 +146,8 @@ pub enum Value {
     Tuple(Tuple),
     /// Uuid type
     Uuid(Uuid),
+    /// Binary string type.
+    Bytea(#[serde(with = "serde_bytes")] Vec<u8>),
 }
 
 impl<'de> Decode<'de> for Value {
@@ -215,12 +219,19 @@ impl<'de> Decode<'de> for Value {
                     ))),
                 }
             }
-            Marker::FixMap(_)
-            | Marker::Map16
-            | Marker::Map32
-            | Marker::Bin8
-            | Marker::Bin16
-            | Marker::Bin32 => {
+            Marker::Bin8 | Marker::Bin16 | Marker::Bin32 => {
+                let len = rmp::decode::read_bin_len(r).map_err(DecodeError::from_vre::<Self>)?;
+                let len = len as usize;
+                if r.len() < len {
+                    return Err(DecodeError::new::<Self>(
+                        "not enough bytes for binary value",
+                    ));
+                }
+                let (bytes, rest) = r.split_at(len);
+                *r = rest;
+                Ok(Value::Bytea(bytes.to_vec()))
+            }
+            Marker::FixMap(_) | Marker::Map16 | Marker::Map32 => {
                 let value = rmpv::decode::read_value(r).map_err(DecodeError::new::<Self>)?;
                 Err(DecodeError::new::<Self>(format_smolstr!(
                     "unexpected value: {value:?}"
@@ -246,10 +257,35 @@ impl Encode for Value {
             Value::String(v) => v.encode(w, context),
             Value::Tuple(v) => v.encode(w, context),
             Value::Uuid(v) => v.encode(w, context),
+            Value::Bytea(v) => rmp::encode::write_bin(w, v).map_err(Into::into),
         }
     }
 }
 
+/// Format bytes in the hex format of `bytea` (without the leading `\x`).
+pub fn bytea_to_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write as _;
+
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        // Writing to a string never fails.
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
+
+/// Format a time of day as `hh:mm:ss[.ffffff]` without trailing zeros of
+/// the fraction, like PostgreSQL does. Such strings order like the time.
+pub fn format_time(time: time::Time) -> String {
+    let (hour, minute, second, micro) = time.as_hms_micro();
+    let hms = format!("{hour:02}:{minute:02}:{second:02}");
+    if micro == 0 {
+        return hms;
+    }
+    let fraction = format!("{micro:06}");
+    format!("{hms}.{}", fraction.trim_end_matches('0'))
+}
+
 pub struct DisplayValues<'a>(pub &'a [Value]);
 impl Display for DisplayValues<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -319,6 +355,7 @@ impl fmt::Display for Value {
             Value::String(v) => write!(f, "'{v}'"),
             Value::Tuple(v) => write!(f, "{v}"),
             Value::Uuid(v) => fmt::Display::fmt(v, f),
+            Value::Bytea(v) => write!(f, "'\\x{}'", bytea_to_hex(v)),
         }
     }
 }
@@ -584,6 +621,7 @@ impl Value {
                 | Value::Double(_)
                 | Value::String(_)
                 | Value::Uuid(_)
+                | Value::Bytea(_)
                 | Value::Tuple(_) => Trivalent::False,
             },
             Value::Null => Trivalent::Unknown,
@@ -592,6 +630,7 @@ impl Value {
                 | Value::String(_)
                 | Value::Uuid(_)
                 | Value::Tuple(_)
+                | Value::Bytea(_)
                 | Value::Datetime(_) => Trivalent::False,
                 Value::Null => Trivalent::Unknown,
                 Value::Integer(o) => (s == o).into(),
@@ -606,6 +645,7 @@ impl Value {
                 | Value::String(_)
                 | Value::Tuple(_)
                 | Value::Uuid(_)
+                | Value::Bytea(_)
                 | Value::Datetime(_) => Trivalent::False,
                 Value::Null => Trivalent::Unknown,
                 Value::Integer(o) => (*s == Double::from(*o)).into(),
@@ -619,6 +659,7 @@ impl Value {
                 | Value::String(_)
                 | Value::Tuple(_)
                 | Value::Uuid(_)
+                | Value::Bytea(_)
                 | Value::Datetime(_) => Trivalent::False,
                 Value::Null => Trivalent::Unknown,
                 Value::Integer(o) => (**s == Decimal::from(*o)).into(),
@@ -633,6 +674,7 @@ impl Value {
                 | Value::Decimal(_)
                 | Value::Double(_)
                 | Value::Uuid(_)
+                | Value::Bytea(_)
                 | Value::Tuple(_) => Trivalent::False,
                 Value::Null => Trivalent::Unknown,
                 Value::String(o) => s.eq(o).into(),
@@ -645,6 +687,7 @@ impl Value {
                 | Value::Double(_)
                 | Value::String(_)
                 | Value::Uuid(_)
+                | Value::Bytea(_)
                 | Value::Tuple(_) => Trivalent::False,
                 Value::Null => Trivalent::Unknown,
             },
@@ -655,6 +698,7 @@ impl Value {
                 | Value::Decimal(_)
                 | Value::Double(_)
                 | Value::String(_)
+                | Value::Bytea(_)
                 | Value::Tuple(_) => Trivalent::False,
                 Value::Null => Trivalent::Unknown,
                 Value::Uuid(o) => s.eq(o).into(),
@@ -666,10 +710,23 @@ impl Value {
                 | Value::Decimal(_)
                 | Value::Double(_)
                 | Value::Uuid(_)
+                | Value::Bytea(_)
                 | Value::Tuple(_) => Trivalent::False,
                 Value::Null => Trivalent::Unknown,
                 Value::Datetime(o) => s.eq(o).into(),
             },
+            Value::Bytea(s) => match other {
+                Value::Boolean(_)
+                | Value::Integer(_)
+                | Value::Datetime(_)
+                | Value::Decimal(_)
+                | Value::Double(_)
+                | Value::String(_)
+                | Value::Uuid(_)
+                | Value::Tuple(_) => Trivalent::False,
+                Value::Null => Trivalent::Unknown,
+                Value::Bytea(o) => s.eq(o).into(),
+            },
         }
     }
 
@@ -684,6 +741,7 @@ impl Value {
             Value::String(_) => FieldType::String,
             Value::Tuple(_) => FieldType::Array,
             Value::Uuid(_) => FieldType::Uuid,
+            Value::Bytea(_) => FieldType::Varbinary,
             Value::Null => FieldType::Any,
         };
         KeyDefPart {
@@ -695,6 +753,62 @@ impl Value {
         }
     }
 
+    /// Compares two values for sorting with SQL semantics.
+    ///
+    /// Unlike the derived `Ord`, which orders values by their variants first,
+    /// numbers of different types are compared by their magnitude, strings
+    /// respect the collation and `NULL`s are placed according to `nulls`
+    /// regardless of the sort direction. Values that can't be compared with
+    /// each other (e.g. of mismatched types) fall back to the derived order
+    /// to keep the result deterministic.
+    ///
+    /// Note: unicode collations are approximated by a case-insensitive
+    /// comparison with lowercase letters going first. Virtual tables are
+    /// sorted with the real tarantool collations instead
+    /// (see `VirtualTable::sort_by_column`).
+    #[must_use]
+    pub fn sql_cmp(
+        &self,
+        other: &Value,
+        order_type: &OrderByType,
+        nulls: NullsOrder,
+        collation: Option<Collation>,
+    ) -> Ordering {
+        let ord = match (self, other) {
+            (Value::Null, Value::Null) => return Ordering::Equal,
+            (Value::Null, _) => {
+                return match nulls {
+                    NullsOrder::First => Ordering::Less,
+                    NullsOrder::Last => Ordering::Greater,
+                }
+            }
+            (_, Value::Null) => {
+                return match nulls {
+                    NullsOrder::First => Ordering::Greater,
+                    NullsOrder::Last => Ordering::Less,
+                }
+            }
+            (Value::String(s), Value::String(o)) => match collation {
+                None | Some(Collation::Binary) => s.cmp(o),
+                Some(Collation::Unicode) => s
+                    .to_lowercase()
+                    .cmp(&o.to_lowercase())
+                    .then_with(|| s.cmp(o).reverse()),
+                Some(Collation::UnicodeCi) => s.to_lowercase().cmp(&o.to_lowercase()),
+            },
+            _ => match self.partial_cmp(other) {
+                Some(TrivalentOrdering::Less) => Ordering::Less,
+                Some(TrivalentOrdering::Equal) => Ordering::Equal,
+                Some(TrivalentOrdering::Greater) => Ordering::Greater,
+                Some(TrivalentOrdering::Unknown) | None => Ord::cmp(self, other),
+            },
+        };
+        match order_type {
+            OrderByType::Asc => ord,
+            OrderByType::Desc => ord.reverse(),
+        }
+    }
+
     /// Compares two values.
     /// The result uses four-valued logic (standard `Ordering` variants and
     /// `Unknown` in case `Null` was met).
@@ -715,6 +829,7 @@ impl Value {
                 | Value::Double(_)
                 | Value::String(_)
                 | Value::Uuid(_)
+                | Value::Bytea(_)
                 | Value::Tuple(_) => None,
             },
             Value::Null => TrivalentOrdering::Unknown.into(),
@@ -723,6 +838,7 @@ impl Value {
                 | Value::Datetime(_)
                 | Value::String(_)
                 | Value::Uuid(_)
+                | Value::Bytea(_)
                 | Value::Tuple(_) => None,
                 Value::Null => TrivalentOrdering::Unknown.into(),
                 Value::Integer(o) => TrivalentOrdering::from(s.cmp(o)).into(),
@@ -744,6 +860,7 @@ impl Value {
                 | Value::Double(_)
                 | Value::Uuid(_)
                 | Value::String(_)
+                | Value::Bytea(_)
                 | Value::Tuple(_) => None,
                 Value::Null => TrivalentOrdering::Unknown.into(),
                 Value::Datetime(o) => TrivalentOrdering::from(s.cmp(o)).into(),
@@ -753,6 +870,7 @@ impl Value {
                 | Value::Datetime(_)
                 | Value::String(_)
                 | Value::Tuple(_)
+                | Value::Bytea(_)
                 | Value::Uuid(_) => None,
                 Value::Null => TrivalentOrdering::Unknown.into(),
                 Value::Integer(o) => {
@@ -783,6 +901,7 @@ impl Value {
                 | Value::Datetime(_)
                 | Value::String(_)
                 | Value::Uuid(_)
+                | Value::Bytea(_)
                 | Value::Tuple(_) => None,
                 Value::Null => TrivalentOrdering::Unknown.into(),
                 Value::Integer(o) => TrivalentOrdering::from((**s).cmp(&Decimal::from(*o))).into(),
@@ -803,6 +922,7 @@ impl Value {
                 | Value::Decimal(_)
                 | Value::Double(_)
                 | Value::Uuid(_)
+                | Value::Bytea(_)
                 | Value::Tuple(_) => None,
                 Value::Null => TrivalentOrdering::Unknown.into(),
                 Value::String(o) => TrivalentOrdering::from(s.cmp(o)).into(),
@@ -814,10 +934,23 @@ impl Value {
                 | Value::Decimal(_)
                 | Value::Double(_)
                 | Value::String(_)
+                | Value::Bytea(_)
                 | Value::Tuple(_) => None,
                 Value::Null => TrivalentOrdering::Unknown.into(),
                 Value::Uuid(o) => TrivalentOrdering::from(u.cmp(o)).into(),
             },
+            Value::Bytea(b) => match other {
+                Value::Boolean(_)
+                | Value::Integer(_)
+                | Value::Datetime(_)
+                | Value::Decimal(_)
+                | Value::Double(_)
+                | Value::String(_)
+                | Value::Uuid(_)
+                | Value::Tuple(_) => None,
+                Value::Null => TrivalentOrdering::Unknown.into(),
+                Value::Bytea(o) => TrivalentOrdering::from(b.cmp(o)).into(),
+            },
             Value::Tuple(_) => match other {
                 Value::Boolean(_)
                 | Value::Integer(_)
@@ -826,6 +959,7 @@ impl Value {
                 | Value::Double(_)
                 | Value::String(_)
                 | Value::Uuid(_)
+                | Value::Bytea(_)
                 | Value::Tuple(_) => None,
                 Value::Null => TrivalentOrdering::Unknown.into(),
             },
@@ -851,9 +985,9 @@ impl Value {
             UnrestrictedType::Boolean => match self {
                 Value::Boolean(_) => Ok(self),
                 Value::Null => Ok(Value::Null),
-                Value::String(ref s) => try_parse_bool(s)
-                    .ok_or_else(|| cast_error(&self, column_type))
-                    .map(Value::Boolean),
+                Value::String(ref s) => try_parse_bool(s).map(Value::Boolean).ok_or_else(|| {
+                    SbroadError::InvalidTextRepresentation("boolean".into(), s.to_smolstr())
+                }),
                 _ => Err(cast_error(&self, column_type)),
             },
             UnrestrictedType::Datetime => match self {
@@ -908,6 +1042,7 @@ impl Value {
             },
             UnrestrictedType::String => match self {
                 Value::String(_) => Ok(self),
+                Value::Boolean(v) => Ok(Value::String(v.to_string())),
                 Value::Null => Ok(Value::Null),
                 _ => Err(cast_error(&self, column_type)),
             },
@@ -919,7 +1054,100 @@ impl Value {
                 Value::Null => Ok(Value::Null),
                 _ => Err(cast_error(&self, column_type)),
             },
+            UnrestrictedType::Bytea => match self {
+                Value::Bytea(_) => Ok(self),
+                Value::String(ref v) => Ok(Value::Bytea(
+                    try_parse_bytea(v).ok_or_else(|| cast_error(&self, column_type))?,
+                )),
+                Value::Null => Ok(Value::Null),
+                _ => Err(cast_error(&self, column_type)),
+            },
+        }
+    }
+
+    /// Cast a value to the type of a `CAST` expression. Unlike [`Value::cast`],
+    /// fractional values cast to integers are rounded like in PostgreSQL and
+    /// the result is checked to fit into integer types narrower than 64 bits.
+    /// `NULL` is kept as is.
+    ///
+    /// # Errors
+    /// - the value cannot be cast to the given type;
+    /// - the value is out of range of the given type.
+    pub fn cast_to(self, cast_type: CastType) -> Result<Self, SbroadError> {
+        if cast_type.temporal_name().is_some() {
+            return self.cast_to_temporal(cast_type);
+        }
+        if !matches!(
+            cast_type,
+            CastType::Integer | CastType::SmallInt | CastType::Int4
+        ) {
+            return self.cast(cast_type.into());
         }
+        let (min, max, name) = cast_type
+            .integer_bounds()
+            .unwrap_or((i64::MIN, i64::MAX, "bigint"));
+        let out_of_range = || SbroadError::NumericValueOutOfRange(name.into());
+        let value = match self {
+            // Half away from zero for decimals and half to even for doubles.
+            Value::Decimal(v) => Value::Integer(v.round().to_i64().ok_or_else(out_of_range)?),
+            Value::Double(Double { value }) => {
+                let rounded = value.round_ties_even();
+                // `i64::MAX as f64` is 2^63, which doesn't fit into i64.
+                if rounded.is_nan() || rounded < i64::MIN as f64 || rounded >= i64::MAX as f64 {
+                    return Err(out_of_range());
+                }
+                Value::Integer(rounded as i64)
+            }
+            value => value.cast(UnrestrictedType::Integer)?,
+        };
+        if let Value::Integer(v) = value {
+            if !(min..=max).contains(&v) {
+                return Err(out_of_range());
+            }
+        }
+        Ok(value)
+    }
+
+    /// Cast a value to `date`, `time` or `timestamp`. Text keeps its local
+    /// date and time ignoring the time zone offset, while datetimes are taken
+    /// in UTC, like in a PostgreSQL session with the UTC time zone.
+    fn cast_to_temporal(self, cast_type: CastType) -> Result<Self, SbroadError> {
+        let local = match &self {
+            Value::Null => return Ok(Value::Null),
+            Value::Datetime(v) => {
+                let v = v.into_inner().to_offset(UtcOffset::UTC);
+                Some(PrimitiveDateTime::new(v.date(), v.time()))
+            }
+            Value::String(v) => {
+                if let (CastType::Time, Some(time)) = (cast_type, try_parse_time(v)) {
+                    return Ok(Value::String(format_time(time)));
+                }
+                try_parse_datetime(v).map(|v| {
+                    let v = v.into_inner();
+                    PrimitiveDateTime::new(v.date(), v.time())
+                })
+            }
+            _ => None,
+        };
+        let name = cast_type.temporal_name().unwrap_or_default();
+        let Some(local) = local else {
+            if let Value::String(v) = self {
+                return Err(SbroadError::InvalidTextRepresentation(
+                    name.into(),
+                    v.into(),
+                ));
+            }
+            return Err(SbroadError::Invalid(
+                Entity::Value,
+                Some(format_smolstr!("Failed to cast {self} to {name}.")),
+            ));
+        };
+        let value = match cast_type {
+            CastType::Date => Value::Datetime(local.date().midnight().assume_utc().into()),
+            CastType::Time => Value::String(format_time(local.time())),
+            _ => Value::Datetime(local.assume_utc().into()),
+        };
+        Ok(value)
     }
 
     /// Cast a value to a different type and wrap into encoded value.
@@ -947,6 +1175,7 @@ impl Value {
             (UnrestrictedType::Integer, Value::Integer(_)) => return Ok(self.into()),
             (UnrestrictedType::String, Value::String(_)) => return Ok(self.into()),
             (UnrestrictedType::Uuid, Value::Uuid(_)) => return Ok(self.into()),
+            (UnrestrictedType::Bytea, Value::Bytea(_)) => return Ok(self.into()),
             _ => (),
         }
 
@@ -965,6 +1194,7 @@ impl Value {
             Value::String(_) => UnrestrictedType::String,
             Value::Tuple(_) => UnrestrictedType::Array,
             Value::Uuid(_) => UnrestrictedType::Uuid,
+            Value::Bytea(_) => UnrestrictedType::Bytea,
             Value::Null => return DerivedType::unknown(),
         };
         DerivedType::new(ty)
@@ -987,6 +1217,7 @@ impl ToHashString for Value {
             Value::String(v) => v.to_string(),
             Value::Tuple(v) => v.to_string(),
             Value::Uuid(v) => v.to_string(),
+            Value::Bytea(v) => bytea_to_hex(v),
             Value::Null => "NULL".to_string(),
         }
     }
@@ -1016,6 +1247,7 @@ impl Serialize for EncodedValue<'_> {
             EncodedValue::Owned(Value::String(v)) => v.serialize(serializer),
             EncodedValue::Owned(Value::Tuple(v)) => v.serialize(serializer),
             EncodedValue::Owned(Value::Uuid(v)) => v.serialize(serializer),
+            EncodedValue::Owned(Value::Bytea(v)) => serde_bytes::serialize(v, serializer),
         }
     }
 }
@@ -1086,6 +1318,8 @@ pub enum MsgPackValue<'v> {
     String(&'v String),
     Tuple(&'v Tuple),
     Uuid(&'v Uuid),
+    #[serde(with = "serde_bytes")]
+    Bytea(&'v [u8]),
     Null(()),
 }
 
@@ -1101,6 +1335,7 @@ impl<'v> From<&'v Value> for MsgPackValue<'v> {
             Value::String(v) => MsgPackValue::String(v),
             Value::Tuple(v) => MsgPackValue::Tuple(v),
             Value::Uuid(v) => MsgPackValue::Uuid(v),
+            Value::Bytea(v) => MsgPackValue::Bytea(v),
         }
     }
 }
@@ -1116,6 +1351,7 @@ impl Encode for MsgPackValue<'_> {
             MsgPackValue::String(v) => v.encode(w, context),
             MsgPackValue::Tuple(v) => v.encode(w, context),
             MsgPackValue::Uuid(v) => v.encode(w, context),
+            MsgPackValue::Bytea(v) => rmp::encode::write_bin(w, v).map_err(Into::into),
             MsgPackValue::Null(v) => v.encode(w, context),
         }
     }
@@ -1132,6 +1368,7 @@ impl From<Value> for String {
             Value::String(v) => v,
             Value::Tuple(v) => v.to_string(),
             Value::Uuid(v) => v.to_string(),
+            Value::Bytea(v) => format!("\\x{}", bytea_to_hex(&v)),
             Value::Null => "NULL".to_string(),
         }
     }
@@ -1150,6 +1387,7 @@ impl<L: tlua::AsLua> tlua::Push<L> for Value {
             Value::String(v) => v.push_to_lua(lua),
             Value::Tuple(v) => v.push_to_lua(lua),
             Value::Uuid(v) => v.push_to_lua(lua),
+            Value::Bytea(v) => tlua::AnyLuaString(v.clone()).push_to_lua(lua),
             Value::Null => tlua::Null.push_to_lua(lua),
         }
     }
@@ -1171,6 +1409,7 @@ where
             Value::String(v) => v.push_into_lua(lua),
             Value::Tuple(v) => v.push_into_lua(lua),
             Value::Uuid(v) => v.push_into_lua(lua),
+            Value::Bytea(v) => tlua::AnyLuaString(v).push_into_lua(lua),
             Value::Null => tlua::Null.push_into_lua(lua),
         }
     }
@@ -55,6 +55,58 @@ fn uuid_negative() {
     );
 }
 
+#[test]
+fn bytea() {
+    let v_bytes = Value::Bytea(vec![0xde, 0xad, 0xbe, 0xef]);
+
+    assert_eq!(format!("{}", v_bytes), r"'\xdeadbeef'");
+    assert_eq!(
+        v_bytes.get_type(),
+        DerivedType::new(UnrestrictedType::Bytea)
+    );
+    assert_eq!(v_bytes.to_hash_string(), "deadbeef");
+    assert_eq!(
+        v_bytes.eq(&Value::Bytea(vec![0xde, 0xad, 0xbe, 0xef])),
+        Trivalent::True
+    );
+    assert_eq!(v_bytes.eq(&Value::Bytea(vec![0xde])), Trivalent::False);
+    assert_eq!(
+        v_bytes.partial_cmp(&Value::Bytea(vec![0xff])),
+        Some(TrivalentOrdering::Less)
+    );
+    assert_eq!(
+        Value::String(r"\xDEADBEEF".to_string())
+            .cast(UnrestrictedType::Bytea)
+            .unwrap(),
+        v_bytes
+    );
+    assert_eq!(
+        Value::String("hello".to_string())
+            .cast(UnrestrictedType::Bytea)
+            .unwrap(),
+        Value::Bytea(b"hello".to_vec())
+    );
+
+    // Bytea values are encoded as msgpack binary strings.
+    let encoded = tarantool::msgpack::encode(&v_bytes);
+    assert_eq!(encoded, [0xc4, 0x04, 0xde, 0xad, 0xbe, 0xef]);
+    let decoded: Value = tarantool::msgpack::decode(&encoded).unwrap();
+    assert_eq!(decoded, v_bytes);
+}
+
+#[test]
+fn bytea_negative() {
+    assert_eq!(
+        Value::String(r"\xdea".to_string())
+            .cast(UnrestrictedType::Bytea)
+            .unwrap_err(),
+        SbroadError::Invalid(
+            Entity::Value,
+            Some(SmolStr::from(r"Failed to cast '\xdea' to varbinary."))
+        )
+    );
+}
+
 #[test]
 fn decimal() {
     assert_eq!(Value::from(decimal!(0)), Value::from(Decimal::from(0)));
@@ -433,3 +485,188 @@ fn trivalent_ordering() {
         TrivalentOrdering::from("b".cmp(""))
     );
 }
+
+#[test]
+fn sql_cmp() {
+    let asc = OrderByType::Asc;
+    let desc = OrderByType::Desc;
+
+    // NULL placement doesn't depend on the sort direction.
+    let null = Value::Null;
+    let one = Value::Integer(1);
+    assert_eq!(
+        null.sql_cmp(&one, &asc, NullsOrder::First, None),
+        Ordering::Less
+    );
+    assert_eq!(
+        null.sql_cmp(&one, &desc, NullsOrder::First, None),
+        Ordering::Less
+    );
+    assert_eq!(
+        null.sql_cmp(&one, &asc, NullsOrder::Last, None),
+        Ordering::Greater
+    );
+    assert_eq!(
+        one.sql_cmp(&null, &desc, NullsOrder::Last, None),
+        Ordering::Less
+    );
+    assert_eq!(
+        null.sql_cmp(&null, &asc, NullsOrder::Last, None),
+        Ordering::Equal
+    );
+
+    // Numbers of different types are compared by magnitude.
+    let half = Value::from(decimal!(0.5));
+    assert_eq!(
+        one.sql_cmp(&half, &asc, NullsOrder::First, None),
+        Ordering::Greater
+    );
+    assert_eq!(
+        one.sql_cmp(&half, &desc, NullsOrder::First, None),
+        Ordering::Less
+    );
+
+    // Strings respect the collation.
+    let a = Value::from("a");
+    let upper_b = Value::from("B");
+    assert_eq!(
+        a.sql_cmp(&upper_b, &asc, NullsOrder::First, None),
+        Ordering::Greater
+    );
+    assert_eq!(
+        a.sql_cmp(&upper_b, &asc, NullsOrder::First, Some(Collation::Unicode)),
+        Ordering::Less
+    );
+    assert_eq!(
+        Value::from("A").sql_cmp(&a, &asc, NullsOrder::First, Some(Collation::UnicodeCi)),
+        Ordering::Equal
+    );
+}
+
+#[test]
+fn cast_to_narrow_integer() {
+    let cast = |v: Value, ty| v.cast_to(ty);
+
+    assert_eq!(
+        cast(Value::Integer(32767), CastType::SmallInt),
+        Ok(Value::Integer(32767))
+    );
+    assert_eq!(
+        cast(Value::Integer(-32768), CastType::SmallInt),
+        Ok(Value::Integer(-32768))
+    );
+    assert_eq!(
+        cast(Value::Integer(32768), CastType::SmallInt),
+        Err(SbroadError::NumericValueOutOfRange("smallint".into()))
+    );
+    assert_eq!(
+        cast(Value::Integer(i64::from(i32::MAX)), CastType::Int4),
+        Ok(Value::Integer(i64::from(i32::MAX)))
+    );
+    assert_eq!(
+        cast(Value::Integer(i64::from(i32::MIN) - 1), CastType::Int4),
+        Err(SbroadError::NumericValueOutOfRange("integer".into()))
+    );
+    assert_eq!(
+        cast(Value::Integer(i64::MAX), CastType::Integer),
+        Ok(Value::Integer(i64::MAX))
+    );
+    assert_eq!(cast(Value::Null, CastType::SmallInt), Ok(Value::Null));
+}
+
+#[test]
+fn cast_fractional_to_integer() {
+    let cast = |v: Value, ty| v.cast_to(ty);
+    let double = |v: f64| Value::Double(Double::from(v));
+    let decimal = |v: &str| Value::from(Decimal::from_str(v).unwrap());
+
+    // Doubles are rounded half to even, decimals are rounded half away from zero.
+    assert_eq!(cast(double(2.5), CastType::Integer), Ok(Value::Integer(2)));
+    assert_eq!(
+        cast(double(-1.7), CastType::Integer),
+        Ok(Value::Integer(-2))
+    );
+    assert_eq!(
+        cast(decimal("2.5"), CastType::Integer),
+        Ok(Value::Integer(3))
+    );
+    assert_eq!(
+        cast(decimal("-2.5"), CastType::Integer),
+        Ok(Value::Integer(-3))
+    );
+
+    assert_eq!(
+        cast(double(32767.4), CastType::SmallInt),
+        Ok(Value::Integer(32767))
+    );
+    assert_eq!(
+        cast(double(32767.5), CastType::SmallInt),
+        Err(SbroadError::NumericValueOutOfRange("smallint".into()))
+    );
+    assert_eq!(
+        cast(double(1e19), CastType::Integer),
+        Err(SbroadError::NumericValueOutOfRange("bigint".into()))
+    );
+    assert_eq!(
+        cast(double(f64::NAN), CastType::Integer),
+        Err(SbroadError::NumericValueOutOfRange("bigint".into()))
+    );
+    assert_eq!(
+        cast(decimal("1e30"), CastType::Int4),
+        Err(SbroadError::NumericValueOutOfRange("integer".into()))
+    );
+}
+
+#[test]
+fn cast_to_temporal() {
+    let cast = |v: Value, ty| v.cast_to(ty);
+    let text = |v: &str| Value::from(v);
+    let datetime = |v: &str| Value::Datetime(try_parse_datetime(v).unwrap());
+
+    // Text keeps its local date and time, datetimes are taken in UTC.
+    assert_eq!(
+        cast(text("2024-01-01 23:30:00.5-03"), CastType::Timestamp),
+        Ok(datetime("2024-01-01T23:30:00.5Z"))
+    );
+    assert_eq!(
+        cast(datetime("2024-01-01T23:30:00-03:00"), CastType::Timestamp),
+        Ok(datetime("2024-01-02T02:30:00Z"))
+    );
+    assert_eq!(
+        cast(text("2024-01-01 23:30:00-03"), CastType::Date),
+        Ok(datetime("2024-01-01T00:00:00Z"))
+    );
+    assert_eq!(
+        cast(datetime("2024-01-01T23:30:00-03:00"), CastType::Date),
+        Ok(datetime("2024-01-02T00:00:00Z"))
+    );
+    assert_eq!(
+        cast(datetime("2024-01-01T23:30:00-03:00"), CastType::Time),
+        Ok(text("02:30:00"))
+    );
+    assert_eq!(cast(text("7:05"), CastType::Time), Ok(text("07:05:00")));
+    assert_eq!(
+        cast(text("10:20:30.123456789+03"), CastType::Time),
+        Ok(text("10:20:30.123456"))
+    );
+    assert_eq!(
+        cast(text("2024-01-01 10:20:30.50"), CastType::Time),
+        Ok(text("10:20:30.5"))
+    );
+    assert_eq!(cast(Value::Null, CastType::Date), Ok(Value::Null));
+
+    assert_eq!(
+        cast(text("25:00"), CastType::Time),
+        Err(SbroadError::InvalidTextRepresentation(
+            "time".into(),
+            "25:00".into()
+        ))
+    );
+    assert_eq!(
+        cast(text("yesterday"), CastType::Date),
+        Err(SbroadError::InvalidTextRepresentation(
+            "date".into(),
+            "yesterday".into()
+        ))
+    );
+}
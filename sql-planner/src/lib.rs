@@ -7,8 +7,10 @@ use crate::errors::SbroadError;
 use crate::executor::engine::{query_id, Metadata, Router, VersionMap};
 use crate::executor::lru::Cache;
 use crate::frontend::Ast;
+use crate::ir::api::parameter::local_now;
 use crate::ir::helpers::RepeatableState;
 use crate::ir::options::Options;
+use crate::ir::relation::Table;
 use crate::ir::types::{DerivedType, UnrestrictedType};
 use crate::ir::value::Value;
 use crate::ir::Plan;
@@ -17,6 +19,7 @@ use ir::node::block::Block;
 use smol_str::SmolStr;
 use std::collections::HashMap;
 use std::rc::Rc;
+use time::OffsetDateTime;
 
 pub mod backend;
 pub mod cbo;
@@ -59,24 +62,51 @@ impl PreparedStatement {
         let cache_key = query_id(query_text, param_types);
 
         if let Some(cached_plan) = router.with_admin_su(|| cache.get(&cache_key))?? {
-            let query_for_audit = if router.is_audit_enabled(cached_plan)? {
-                Some(query_text.to_string())
-            } else {
-                None
-            };
-            let query_for_logging = if router.is_sql_log_enabled(cached_plan)? {
-                Some(query_text.to_string())
-            } else {
-                None
-            };
-            return Ok(PreparedStatement {
-                plan: cached_plan.clone(),
-                query_for_audit,
-                query_for_logging,
-            });
+            return Self::from_plan(router, cached_plan.clone(), query_text);
         }
 
-        let new_plan = router.with_admin_su(|| -> Result<Plan, SbroadError> {
+        let new_plan = Rc::new(Self::build_plan(router, query_text, param_types)?);
+
+        // Only DQL and DML is cached, because it is on the hot path
+        // other types of queries are much less likely to be queried again
+        //
+        // EXPLAIN (RAW) queries contain DQL and DML, we don't want them to be cached
+        if new_plan.is_dql_or_dml()? && !new_plan.is_raw_explain() {
+            cache.put(cache_key, new_plan.clone())?;
+        }
+
+        Self::from_plan(router, new_plan, query_text)
+    }
+
+    /// Like [`PreparedStatement::parse`], but the router's query plan cache
+    /// is neither looked up nor populated, so the query is always planned anew.
+    /// The cache contents are left untouched.
+    pub fn parse_uncached<R>(
+        router: &R,
+        query_text: &str,
+        param_types: &[DerivedType],
+    ) -> Result<PreparedStatement, SbroadError>
+    where
+        R: Router,
+        R::MetadataProvider: Metadata,
+        R::ParseTree: Ast,
+    {
+        let new_plan = Rc::new(Self::build_plan(router, query_text, param_types)?);
+        Self::from_plan(router, new_plan, query_text)
+    }
+
+    /// Parse the query and optimize its plan.
+    fn build_plan<R>(
+        router: &R,
+        query_text: &str,
+        param_types: &[DerivedType],
+    ) -> Result<Plan, SbroadError>
+    where
+        R: Router,
+        R::MetadataProvider: Metadata,
+        R::ParseTree: Ast,
+    {
+        router.with_admin_su(|| -> Result<Plan, SbroadError> {
             let metadata = router.metadata().lock();
             let mut plan = R::ParseTree::transform_into_plan(query_text, param_types, &*metadata)?;
             if router.provides_versions() {
@@ -105,7 +135,11 @@ impl PreparedStatement {
 
             if plan.is_dql_or_dml()? {
                 plan.check_raw_options()?;
-                plan = plan.optimize()?;
+                if router.serves_point_gets() && plan.is_point_get_candidate()? {
+                    plan.optimization_deferred = true;
+                } else {
+                    plan = plan.optimize()?;
+                }
             } else if plan.is_block()? {
                 plan.check_raw_options()?;
                 let top = plan.get_top().expect("must be set");
@@ -115,31 +149,27 @@ impl PreparedStatement {
             }
 
             Ok(plan)
-        })??;
-
-        let new_plan = Rc::new(new_plan);
-
-        // Only DQL and DML is cached, because it is on the hot path
-        // other types of queries are much less likely to be queried again
-        //
-        // EXPLAIN (RAW) queries contain DQL and DML, we don't want them to be cached
-        if new_plan.is_dql_or_dml()? && !new_plan.is_raw_explain() {
-            cache.put(cache_key, new_plan.clone())?;
-        }
+        })?
+    }
 
-        let query_for_audit = if router.is_audit_enabled(&new_plan)? {
+    fn from_plan<R: Router>(
+        router: &R,
+        plan: Rc<Plan>,
+        query_text: &str,
+    ) -> Result<PreparedStatement, SbroadError> {
+        let query_for_audit = if router.is_audit_enabled(&plan)? {
             Some(query_text.to_string())
         } else {
             None
         };
-        let query_for_logging = if router.is_sql_log_enabled(&new_plan)? {
+        let query_for_logging = if router.is_sql_log_enabled(&plan)? {
             Some(query_text.to_string())
         } else {
             None
         };
 
         Ok(PreparedStatement {
-            plan: new_plan,
+            plan,
             query_for_audit,
             query_for_logging,
         })
@@ -165,11 +195,28 @@ impl PreparedStatement {
         self.query_for_logging.as_deref()
     }
 
+    /// Gets the fingerprint of the query shape for output to the SQL log.
+    pub fn query_fingerprint(&self) -> Option<&str> {
+        self.plan.query_fingerprint.as_deref()
+    }
+
     /// Provide concrete values for query parameters, creating a [`BoundStatement`] as a result.
     pub fn bind(
         &self,
         params: Vec<Value>,
         default_options: Options,
+    ) -> Result<BoundStatement, SbroadError> {
+        self.bind_at(params, default_options, local_now())
+    }
+
+    /// Like [`PreparedStatement::bind`], but the time functions (`CURRENT_TIMESTAMP`,
+    /// `NOW()`, `CURRENT_DATE`, etc.) are evaluated to the given time. It is used
+    /// to keep the time of the statements in a transaction equal to its start time.
+    pub fn bind_at(
+        &self,
+        params: Vec<Value>,
+        default_options: Options,
+        statement_time: OffsetDateTime,
     ) -> Result<BoundStatement, SbroadError> {
         let mut plan = Box::new(self.plan.as_ref().clone());
 
@@ -183,11 +230,18 @@ impl PreparedStatement {
         if plan.is_empty() {
             // Empty query, do nothing
         } else if plan.is_dql_or_dml()? || plan.is_block()? {
+            if let Some(select_into) = &mut plan.select_into {
+                select_into.params.clone_from(&params);
+            }
             plan.bind_params(params, default_options)?;
             *plan = plan
-                .update_timestamps()?
+                .update_timestamps_at(statement_time)?
                 .cast_constants()?
                 .fold_boolean_tree()?;
+            if plan.optimization_deferred && plan.point_get()?.is_none() {
+                plan.optimization_deferred = false;
+                *plan = plan.optimize()?;
+            }
         }
 
         Ok(BoundStatement {
@@ -236,4 +290,23 @@ impl BoundStatement {
     pub fn params_for_audit(&self) -> Option<&[Value]> {
         self.params_for_audit.as_deref()
     }
+
+    /// Gets the fingerprint of the query shape for output to the SQL log.
+    pub fn query_fingerprint(&self) -> Option<&str> {
+        self.plan.query_fingerprint.as_deref()
+    }
+
+    /// Get the statement returning the primary keys and the bucket ids of the
+    /// rows to lock with `FOR UPDATE` or `FOR SHARE`, and the locked table.
+    /// See [`Plan::row_keys_plan`].
+    pub fn row_keys(&self) -> Result<Option<(BoundStatement, &Table)>, SbroadError> {
+        let Some((plan, table)) = self.plan.row_keys_plan()? else {
+            return Ok(None);
+        };
+        let statement = BoundStatement {
+            plan: Box::new(plan),
+            params_for_audit: None,
+        };
+        Ok(Some((statement, table)))
+    }
 }
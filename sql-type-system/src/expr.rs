@@ -9,6 +9,7 @@ pub enum Type {
     Boolean,
     Datetime,
     Uuid,
+    Bytea,
     Array,
     Map,
     // Any is a compound type that can be basically any other type.
@@ -38,6 +39,7 @@ impl Type {
             Type::Boolean => "bool",
             Type::Datetime => "datetime",
             Type::Uuid => "uuid",
+            Type::Bytea => "bytea",
             Type::Array => "array",
             Type::Map => "map",
             Type::Any => "any",
@@ -192,6 +194,15 @@ pub enum ExprKind<Id> {
     /// Json extract expression.
     /// Examples: `json_extract_path(field, 'key', 'subkey')`.
     JsonExtractPath(Vec<Expr<Id>>),
+    /// Json extract expression returning text.
+    /// Examples: `json_extract_path_text(field, 'key')`, `field->>'key'`.
+    JsonExtractPathText(Vec<Expr<Id>>),
+    /// Json containment check.
+    /// Examples: `field @> '{"key": 1}'::json`.
+    JsonContains(Vec<Expr<Id>>),
+    /// Json path query.
+    /// Examples: `jsonb_path_query(field, '$.key[0]')`.
+    JsonPathQuery(Vec<Expr<Id>>),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -528,32 +528,24 @@ impl<'a, Id: Hash + Eq + Clone> TypeAnalyzerCore<'a, Id> {
                 Ok(report)
             }
             ExprKind::JsonExtractPath(ref args) => {
-                if args.len() < 2 {
-                    return Err(self.could_not_resolve_function_overload_error(
-                        FunctionKind::Scalar,
-                        "json_extract_path",
-                        args,
-                    ));
-                };
-
-                let mut report = TypeReport::new();
-                let arg_types = iter::once(Type::Map).chain(iter::repeat(Type::Text));
-                for (arg, ty) in args.iter().zip(arg_types) {
-                    let r = self.analyze(arg, ty)?;
-                    report.extend(r);
-                }
-
-                let arg_types = iter::once(Type::Map).chain(iter::repeat(Type::Text));
-                for (arg, ty) in args.iter().zip(arg_types) {
-                    if report.get_type(&arg.id) != ty {
-                        return Err(self.could_not_resolve_function_overload_error(
-                            FunctionKind::Scalar,
-                            "json_extract_path",
-                            args,
-                        ));
-                    }
-                }
-
+                let mut report = self.analyze_json_extract_path("json_extract_path", args)?;
+                report.report(&expr.id, Type::Any);
+                Ok(report)
+            }
+            ExprKind::JsonExtractPathText(ref args) => {
+                let mut report = self.analyze_json_extract_path("json_extract_path_text", args)?;
+                report.report(&expr.id, Type::Text);
+                Ok(report)
+            }
+            ExprKind::JsonContains(ref args) => {
+                let mut report =
+                    self.analyze_json_args(FunctionKind::Operator, "@>", args, 2, 2)?;
+                report.report(&expr.id, Type::Boolean);
+                Ok(report)
+            }
+            ExprKind::JsonPathQuery(ref args) => {
+                let mut report =
+                    self.analyze_json_args(FunctionKind::Scalar, "jsonb_path_query", args, 2, 1)?;
                 report.report(&expr.id, Type::Any);
                 Ok(report)
             }
@@ -753,6 +745,61 @@ impl<'a, Id: Hash + Eq + Clone> TypeAnalyzerCore<'a, Id> {
         Ok(None)
     }
 
+    /// Analyze arguments of `json_extract_path` and `json_extract_path_text`:
+    /// a json document followed by at least one text key.
+    fn analyze_json_extract_path(
+        &mut self,
+        name: &str,
+        args: &[Expr<Id>],
+    ) -> Result<TypeReport<Id>, Error> {
+        if args.len() < 2 {
+            return Err(self.could_not_resolve_function_overload_error(
+                FunctionKind::Scalar,
+                name,
+                args,
+            ));
+        };
+        self.analyze_json_args(FunctionKind::Scalar, name, args, args.len(), 1)
+    }
+
+    /// Analyze arguments of a json function: `documents` json documents
+    /// followed by text arguments, `count` arguments in total.
+    /// A document can also be of type any, as it is the result
+    /// of extracting a nested value, e.g. in `data->'a'->'b'`.
+    fn analyze_json_args(
+        &mut self,
+        kind: FunctionKind,
+        name: &str,
+        args: &[Expr<Id>],
+        count: usize,
+        documents: usize,
+    ) -> Result<TypeReport<Id>, Error> {
+        if args.len() != count {
+            return Err(self.could_not_resolve_function_overload_error(kind, name, args));
+        };
+
+        let mut report = TypeReport::new();
+        let arg_types = iter::repeat(Type::Map)
+            .take(documents)
+            .chain(iter::repeat(Type::Text));
+        for (arg, ty) in args.iter().zip(arg_types) {
+            let r = self.analyze(arg, ty)?;
+            report.extend(r);
+        }
+
+        let documents_are_json = args[..documents]
+            .iter()
+            .all(|arg| matches!(report.get_type(&arg.id), Type::Map | Type::Any));
+        let rest_are_text = args[documents..]
+            .iter()
+            .all(|arg| report.get_type(&arg.id) == Type::Text);
+        if !documents_are_json || !rest_are_text {
+            return Err(self.could_not_resolve_function_overload_error(kind, name, args));
+        }
+
+        Ok(report)
+    }
+
     fn could_not_resolve_function_overload_error(
         &mut self,
         kind: FunctionKind,
@@ -809,6 +856,19 @@ impl<'a, Id: Hash + Eq + Clone> TypeAnalyzerCore<'a, Id> {
             if let ExprKind::Row(_) = left.kind {
                 return Err(Error::Other("IN operator for rows is not supported".into()));
             }
+            // `a IN ($1)` with an array bound to `$1` is a membership test
+            // against the array elements, so the parameter keeps its type.
+            if let [param @ Expr {
+                kind: ExprKind::Parameter(idx),
+                ..
+            }] = right_row.as_slice()
+            {
+                if self.get_parameter_type(*idx) == Some(Type::Array) {
+                    let mut report = self.analyze(left, Type::Text)?;
+                    report.extend(self.analyze(param, Type::Array)?);
+                    return Ok(report);
+                }
+            }
             let mut exprs = vec![left];
             exprs.append(&mut right_row.iter().collect::<Vec<_>>());
             let (_, report) = self.analyze_homogeneous_exprs("IN", &exprs, Type::Text)?;
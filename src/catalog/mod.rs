@@ -1,5 +1,6 @@
 pub mod governor_queue;
 pub mod pico_bucket;
 pub mod pico_resharding_state;
+pub mod pico_sequence;
 pub mod pico_table;
 pub mod user_audit_policy;
@@ -0,0 +1,158 @@
+use crate::schema::IndexDef;
+use crate::schema::IndexOption;
+use crate::schema::INITIAL_SCHEMA_VERSION;
+use crate::storage::index_by_ids_unchecked;
+use crate::storage::space_by_id_unchecked;
+use crate::storage::SystemTable;
+use crate::storage::ToEntryIter;
+use crate::storage::MP_SERDE;
+use serde::Deserialize;
+use serde::Serialize;
+use tarantool::index::FieldType as IndexFieldType;
+use tarantool::index::Index;
+use tarantool::index::IndexIterator;
+use tarantool::index::IndexType;
+use tarantool::index::IteratorType;
+use tarantool::index::Part;
+use tarantool::space::Field;
+use tarantool::space::FieldType;
+use tarantool::space::Space;
+use tarantool::space::SpaceId;
+use tarantool::space::SpaceType;
+use tarantool::tuple::Encode;
+
+////////////////////////////////////////////////////////////////////////////////
+// SequenceRecord
+////////////////////////////////////////////////////////////////////////////////
+
+/// Describes contents of the `_pico_sequence` system table record.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SequenceRecord {
+    /// Id of the table whose identity column is backed by the sequence.
+    pub table_id: SpaceId,
+
+    /// The last value reserved by any instance of the cluster.
+    /// Instances reserve the values in batches, so the values up to this one
+    /// may still be unused.
+    pub allocated: u64,
+}
+
+impl Encode for SequenceRecord {}
+
+impl SequenceRecord {
+    /// Format of the `_pico_sequence` global table.
+    #[inline]
+    pub fn format() -> Vec<::tarantool::space::Field> {
+        vec![
+            Field::from(("table_id", FieldType::Unsigned)).is_nullable(false),
+            Field::from(("allocated", FieldType::Unsigned)).is_nullable(false),
+        ]
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PicoSequence
+////////////////////////////////////////////////////////////////////////////////
+
+/// A struct for accessing `_pico_sequence` system table contents.
+#[derive(Debug, Clone)]
+pub struct PicoSequence {
+    pub space: Space,
+    pub index_primary: Index,
+}
+
+impl SystemTable for PicoSequence {
+    const TABLE_NAME: &'static str = "_pico_sequence";
+    const TABLE_ID: SpaceId = 535;
+
+    fn format() -> Vec<tarantool::space::Field> {
+        SequenceRecord::format()
+    }
+
+    fn index_definitions() -> Vec<IndexDef> {
+        vec![IndexDef {
+            table_id: Self::TABLE_ID,
+            // Primary index
+            id: 0,
+            name: "_pico_sequence_index_primary".into(),
+            ty: IndexType::Tree,
+            opts: vec![IndexOption::Unique(true)],
+            parts: vec![Part::from(("table_id", IndexFieldType::Unsigned)).is_nullable(false)],
+            operable: true,
+            // This means the local schema is already up to date and main loop doesn't need to do anything
+            schema_version: INITIAL_SCHEMA_VERSION,
+        }]
+    }
+}
+
+impl PicoSequence {
+    pub const SQL_CREATE: &'static str = "
+        CREATE TABLE _pico_sequence (
+            table_id   UNSIGNED  NOT NULL,
+            allocated  UNSIGNED  NOT NULL,
+
+            PRIMARY KEY (table_id)
+        )
+        DISTRIBUTED GLOBALLY
+    ";
+
+    pub const fn new() -> Self {
+        Self {
+            space: space_by_id_unchecked(Self::TABLE_ID),
+            index_primary: index_by_ids_unchecked(Self::TABLE_ID, 0),
+        }
+    }
+
+    pub fn create(&self) -> tarantool::Result<()> {
+        let space = Space::builder(Self::TABLE_NAME)
+            .id(Self::TABLE_ID)
+            .space_type(SpaceType::DataLocal)
+            .format(Self::format())
+            .if_not_exists(true)
+            .create()?;
+
+        let index_primary = space
+            .index_builder("_pico_sequence_index_primary")
+            .unique(true)
+            .part("table_id")
+            .if_not_exists(true)
+            .create()?;
+
+        debug_assert_eq!(self.space.id(), space.id());
+        debug_assert_eq!(self.index_primary.id(), index_primary.id());
+
+        Ok(())
+    }
+
+    /// Returns the sequence record of the table, if any value has
+    /// been already reserved for it.
+    #[inline]
+    pub fn get(&self, table_id: SpaceId) -> tarantool::Result<Option<SequenceRecord>> {
+        let Some(tuple) = self.space.get(&[table_id])? else {
+            return Ok(None);
+        };
+        tuple.decode().map(Some)
+    }
+
+    /// Deletes the sequence of the dropped table.
+    #[inline]
+    pub fn delete(&self, table_id: SpaceId) -> tarantool::Result<()> {
+        self.space.delete(&[table_id])?;
+        Ok(())
+    }
+}
+
+impl Default for PicoSequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToEntryIter<MP_SERDE> for PicoSequence {
+    type Entry = SequenceRecord;
+
+    #[inline(always)]
+    fn index_iter(&self) -> tarantool::Result<IndexIterator> {
+        self.space.select(IteratorType::All, &())
+    }
+}
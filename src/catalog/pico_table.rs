@@ -178,8 +178,9 @@ impl PicoTable {
     ) -> tarantool::Result<()> {
         // We can't use UpdateOps as we use custom encoding
         let mut table_def = self.get(id)?.expect("should exist");
-        // apply renames to the distribution
+        // apply renames to the distribution and the column options
         column_renames.transform_distribution_columns(&mut table_def.distribution);
+        column_renames.transform_option_columns(&mut table_def.opts);
         table_def.format = format.to_vec();
         self.put(&table_def)?;
         Ok(())
@@ -1,6 +1,6 @@
 use crate::cli::args;
 use crate::cli::console::{Command, Console, ConsoleLanguage, ReplError, SpecialCommand};
-use crate::cli::util::{OutputFormat, ResultSet};
+use crate::cli::util::{self, OutputFormat, ResultSet};
 
 use std::cell::RefCell;
 use std::io::{self, ErrorKind, Read, Write};
@@ -250,20 +250,26 @@ fn admin_repl(args: args::Admin) -> Result<(), ReplError> {
     // Quiet mode: suppress decorative output when using machine-readable format,
     // or when not a tty (unless --prompts is specified)
     let quiet = is_machine_readable || (!is_terminal && !args.prompts);
-    let mut console = Console::with_completer(helper, quiet)?;
+    let connection_info = format!(
+        "Connected to admin console by socket path \"{}\"",
+        args.socket_path
+    );
+    let mut console = Console::with_completer(helper, connection_info, quiet)?;
 
     // Only show greeting in interactive mode with table format
     if !quiet {
-        console.greet(&format!(
-            "Connected to admin console by socket path \"{}\"",
-            args.socket_path
-        ));
+        console.greet();
     }
 
     const HELP_MESSAGE: &'static str = "
     Available backslash commands:
         \\e                              Open the editor specified by the EDITOR environment variable
         \\help                           Show this screen
+        \\conninfo                       Show information about the current connection
+        \\copy (query) TO 'file' CSV [HEADER]
+                                        Export the SQL query result to a CSV file
+        \\d table                        Describe the table columns and primary key
+        \\d+ table                       Also show the table distribution and bucket count
         \\sql                            Switch console language to SQL (default)
         \\lua                            Switch console language to Lua (deprecated)
         \\set delimiter shiny-delimiter  Set console delimiter to 'shiny-delimiter'
@@ -290,6 +296,42 @@ fn admin_repl(args: args::Admin) -> Result<(), ReplError> {
                 SpecialCommand::PrintHelp => {
                     console.write(HELP_MESSAGE);
                 }
+
+                SpecialCommand::CopyTo(copy) => {
+                    if temp_client.current_language != ConsoleLanguage::Sql {
+                        console.write("\\copy is available only for SQL language");
+                        continue;
+                    }
+
+                    temp_client.write(&copy.query)?;
+                    let raw_response = temp_client.read()?;
+                    let res = serde_yaml::from_str::<ResultSet>(&raw_response).map_err(|err| {
+                        ReplError::Other(format!("Error occurred while processing output: {err}"))
+                    })?;
+
+                    match res.export_csv(&copy) {
+                        Ok(count) => console.write(&format!("COPY {count}")),
+                        Err(message) => console.write(&message),
+                    }
+                }
+
+                SpecialCommand::DescribeTable(describe) => {
+                    if temp_client.current_language != ConsoleLanguage::Sql {
+                        console.write("\\d is available only for SQL language");
+                        continue;
+                    }
+
+                    let description = util::describe_table(&describe, |query| {
+                        temp_client.write(query)?;
+                        let raw_response = temp_client.read()?;
+                        serde_yaml::from_str::<ResultSet>(&raw_response).map_err(|err| {
+                            ReplError::Other(format!(
+                                "Error occurred while processing output: {err}"
+                            ))
+                        })
+                    })?;
+                    console.write(&description);
+                }
             },
             Command::Expression(line) => {
                 temp_client.write(&line)?;
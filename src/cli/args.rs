@@ -569,6 +569,7 @@ fn try_parse_kv_uppercase(s: &str) -> Result<(Uppercase, Uppercase), String> {
 #[clap(after_help = "SPECIAL COMMANDS:
     \\e            Open the editor specified by the EDITOR environment variable
     \\help         Show this screen
+    \\conninfo     Show information about the current connection
 
 HOTKEYS:
     Enter         Submit the request
@@ -631,6 +632,7 @@ impl Connect {
 #[clap(after_help = "SPECIAL COMMANDS:
     \\e            Open the editor specified by the EDITOR environment variable
     \\help         Show this screen
+    \\conninfo     Show information about the current connection
     \\sql          Switch console language to SQL (default)
     \\lua          Switch console language to Lua (deprecated)
 
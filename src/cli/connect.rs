@@ -1,14 +1,15 @@
 use crate::cli::args;
-use crate::cli::console::{Command, Console, ReplError, SpecialCommand};
-use crate::cli::util::Credentials;
+use crate::cli::console::{Command, Console, ReplError, SpecialCommand, Verbosity};
+use crate::cli::util::{self, Credentials};
 
-use std::fmt::{Debug, Display};
+use std::fmt::{Debug, Display, Write};
 use std::time::Duration;
 
 use comfy_table::{ContentArrangement, Table};
 use nix::unistd::isatty;
 use serde::{Deserialize, Serialize};
-use tarantool::network::AsClient;
+use tarantool::error::BoxError;
+use tarantool::network::{AsClient, Client};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ColumnDesc {
@@ -120,6 +121,58 @@ impl Display for ResultSet {
     }
 }
 
+/// Formats an error returned by the server. In verbose mode the message
+/// is followed by the details the server attached to the error.
+fn format_error(error: &BoxError, verbosity: Verbosity) -> String {
+    let mut message = error.to_string();
+    if verbosity == Verbosity::Terse {
+        return message;
+    }
+
+    for (field, title) in [
+        ("sqlstate", "SQLSTATE"),
+        ("entity", "ENTITY"),
+        ("hint", "HINT"),
+    ] {
+        if let Some(value) = error.field(field).and_then(|value| value.as_str()) {
+            _ = write!(message, "\n{title}: {value}");
+        }
+    }
+    if let (Some(file), Some(line)) = (error.file(), error.line()) {
+        _ = write!(message, "\nLOCATION: {file}:{line}");
+    }
+    message
+}
+
+/// Executes a query for a console command. Errors returned by the server
+/// are converted into [`util::ResultSet::Error`].
+fn dispatch(
+    client: &Client,
+    query: &str,
+    verbosity: Verbosity,
+) -> Result<util::ResultSet, ReplError> {
+    let response =
+        ::tarantool::fiber::block_on(client.call(".proc_sql_dispatch", &(query, Vec::<()>::new())));
+
+    match response {
+        Ok(tuple) => tuple
+            .decode::<Vec<util::ResultSet>>()
+            .map_err(|err| {
+                ReplError::Other(format!("Error occurred while decoding response: {err}"))
+            })?
+            .into_iter()
+            .next()
+            .ok_or_else(|| ReplError::Other("Invalid form of response".to_string())),
+        Err(tarantool::network::ClientError::ErrorResponse(err)) => {
+            Ok(util::ResultSet::Error(None, format_error(&err, verbosity)))
+        }
+        Err(tarantool::network::ClientError::ConnectionClosed(err)) => {
+            Err(ReplError::LostConnectionToServer(err.into()))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
 fn sql_repl(args: args::Connect) -> Result<(), ReplError> {
     // setup credentials and options for the connection
     let credentials = Credentials::try_from(&args).map_err(ReplError::other)?;
@@ -128,20 +181,27 @@ fn sql_repl(args: args::Connect) -> Result<(), ReplError> {
         .connect(&args.address, &args.tls, timeout)
         .map_err(ReplError::other)?;
 
-    let mut console = Console::new()?;
     let username = args.address.user.unwrap_or(args.user);
-    console.greet(&format!(
+    let mut console = Console::new(format!(
         "Connected to interactive console by address \"{}:{}\" under \"{}\" user",
         args.address.host, args.address.port, username,
-    ));
+    ))?;
+    console.greet();
 
     const HELP_MESSAGE: &'static str = "
     Available backslash commands:
         \\e                              Open the editor specified by the EDITOR environment variable
         \\help                           Show this screen
+        \\conninfo                       Show information about the current connection
+        \\copy (query) TO 'file' CSV [HEADER]
+                                        Export the query result to a CSV file
+        \\d table                        Describe the table columns and primary key
+        \\d+ table                       Also show the table distribution and bucket count
         \\set delimiter shiny-delimiter  Set console delimiter to 'shiny-delimiter'
         \\set delimiter default          Reset console delimiter to default (;)
         \\set delimiter enter            Reset console delimiter to enter
+        \\set verbosity verbose          Show SQLSTATE and other details of errors
+        \\set verbosity terse            Show only error messages (default)
 
     Available hotkeys:
         Enter                           Submit the request
@@ -161,6 +221,20 @@ fn sql_repl(args: args::Connect) -> Result<(), ReplError> {
                         // picodata connect doesn't know about language switching
                         console.write("Unknown special sequence")
                     }
+                    SpecialCommand::CopyTo(copy) => {
+                        let res = dispatch(&client, &copy.query, console.verbosity())?;
+                        match res.export_csv(&copy) {
+                            Ok(count) => console.write(&format!("COPY {count}")),
+                            Err(message) => console.write(&message),
+                        }
+                    }
+                    SpecialCommand::DescribeTable(describe) => {
+                        let verbosity = console.verbosity();
+                        let description = util::describe_table(&describe, |query| {
+                            dispatch(&client, query, verbosity)
+                        })?;
+                        console.write(&description);
+                    }
                 }
             }
             Command::Expression(line) => {
@@ -186,12 +260,13 @@ fn sql_repl(args: args::Connect) -> Result<(), ReplError> {
 
                     Err(err) => match err {
                         tarantool::network::ClientError::ErrorResponse(err) => {
+                            let message = format_error(&err, console.verbosity());
                             let is_terminal = isatty(0).unwrap_or(false);
                             if !is_terminal {
-                                return Err(ReplError::Other(err.to_string()));
+                                return Err(ReplError::Other(message));
                             }
 
-                            err.to_string()
+                            message
                         }
                         tarantool::network::ClientError::ConnectionClosed(err) => {
                             return Err(ReplError::LostConnectionToServer(err.into()));
@@ -74,9 +74,46 @@ impl std::fmt::Display for ConsoleLanguage {
     }
 }
 
+/// Amount of details printed for a failed request.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verbosity {
+    /// Only the error message.
+    Terse,
+    /// The message along with the SQLSTATE, the entity and the hint if any.
+    Verbose,
+}
+
+impl std::fmt::Display for Verbosity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verbosity::Terse => write!(f, "terse"),
+            Verbosity::Verbose => write!(f, "verbose"),
+        }
+    }
+}
+
 pub enum SpecialCommand {
     SwitchLanguage(ConsoleLanguage),
     PrintHelp,
+    CopyTo(CopyCommand),
+    DescribeTable(DescribeCommand),
+}
+
+/// Client-side export of a query result: `\copy (<query>) TO '<path>' CSV [HEADER]`.
+#[derive(Debug, PartialEq)]
+pub struct CopyCommand {
+    pub query: String,
+    pub path: PathBuf,
+    /// Whether to write the column names as the first line.
+    pub header: bool,
+}
+
+/// Description of a table: `\d <table>` or the verbose `\d+ <table>`.
+#[derive(Debug, PartialEq)]
+pub struct DescribeCommand {
+    pub table: String,
+    /// Whether to show the distribution of the table as well.
+    pub verbose: bool,
 }
 
 pub enum Command {
@@ -90,6 +127,12 @@ enum ConsoleCommand {
     SetLanguage(ConsoleLanguage),
     // None represent default delimiter (pressing enter in console and eof in case of pipe)
     SetDelimiter(Option<String>),
+    SetVerbosity(Verbosity),
+    PrintConnectionInfo,
+    CopyTo(CopyCommand),
+    DescribeTable(DescribeCommand),
+    // Recognized command with invalid arguments, holds the error message
+    Malformed(String),
     Invalid,
 }
 
@@ -105,7 +148,11 @@ pub struct Console<H: Helper> {
     history_file_path: PathBuf,
     delimiter: Option<String>,
     current_language: ConsoleLanguage,
+    verbosity: Verbosity,
     pub mode: Mode,
+    /// Description of the connection, e.g. the instance address and user
+    /// or the admin socket path. Printed on start and by `\conninfo`.
+    connection_info: String,
     // Queue of separated by delimiter statements
     separated_statements: VecDeque<String>,
     uncompleted_statement: String,
@@ -138,6 +185,20 @@ impl<T: Helper> Console<T> {
                     self.update_delimiter(delimiter);
                     None
                 }
+                ConsoleCommand::SetVerbosity(verbosity) => {
+                    self.update_verbosity(verbosity);
+                    None
+                }
+                ConsoleCommand::PrintConnectionInfo => {
+                    self.write(&self.describe_connection());
+                    None
+                }
+                ConsoleCommand::CopyTo(copy) => Some(CopyTo(copy)),
+                ConsoleCommand::DescribeTable(describe) => Some(DescribeTable(describe)),
+                ConsoleCommand::Malformed(message) => {
+                    self.write(&message);
+                    None
+                }
                 ConsoleCommand::Invalid => {
                     self.write("Unknown special sequence");
                     None
@@ -156,7 +217,9 @@ impl<T: Helper> Console<T> {
                         Ok(ControlFlow::Break(Command::Control(command)))
                     }
                 }
-                PrintHelp => Ok(ControlFlow::Break(Command::Control(command))),
+                PrintHelp | CopyTo(_) | DescribeTable(_) => {
+                    Ok(ControlFlow::Break(Command::Control(command)))
+                }
             },
             None => Ok(ControlFlow::Continue(())),
         }
@@ -198,9 +261,56 @@ impl<T: Helper> Console<T> {
         }
     }
 
+    fn update_verbosity(&mut self, verbosity: Verbosity) {
+        // Admin console receives errors as plain text, there are no details to show.
+        if self.mode == Mode::Admin {
+            self.write("Verbosity cannot be changed in this console");
+            return;
+        }
+        self.write(&format!("Verbosity changed to '{verbosity}'"));
+        self.verbosity = verbosity;
+    }
+
+    /// Amount of details to print for a failed request.
+    pub fn verbosity(&self) -> Verbosity {
+        self.verbosity
+    }
+
     fn parse_special_command(&self, command: &str) -> ConsoleCommand {
+        if let Some(args) = command.strip_prefix("\\copy") {
+            if args.starts_with(|c: char| c.is_whitespace() || c == '(') {
+                return match parse_copy_command(args) {
+                    Ok(copy) => ConsoleCommand::CopyTo(copy),
+                    Err(err) => ConsoleCommand::Malformed(format!(
+                        "Invalid \\copy command: {err}. \
+                         Expected: \\copy (<query>) TO '<file>' CSV [HEADER]"
+                    )),
+                };
+            }
+        }
+
         let parts: Vec<&str> = command.split_whitespace().collect();
 
+        if let ["\\conninfo" | "\\conninfo;"] = parts.as_slice() {
+            return ConsoleCommand::PrintConnectionInfo;
+        }
+
+        if let [command @ ("\\d" | "\\d+"), table] = parts.as_slice() {
+            let table = table.trim_end_matches(';');
+            // Unquoted names are case insensitive, as in SQL.
+            let table = match table.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+                Some(quoted) => quoted.to_string(),
+                None => table.to_lowercase(),
+            };
+            if table.is_empty() {
+                return ConsoleCommand::Invalid;
+            }
+            return ConsoleCommand::DescribeTable(DescribeCommand {
+                table,
+                verbose: *command == "\\d+",
+            });
+        }
+
         if parts.len() < 3 || !["\\s", "\\set"].contains(&parts[0]) {
             return ConsoleCommand::Invalid;
         }
@@ -217,6 +327,11 @@ impl<T: Helper> Console<T> {
                 Some(custom) => ConsoleCommand::SetDelimiter(Some(custom.to_string())),
                 None => ConsoleCommand::Invalid,
             },
+            "verbosity" | "VERBOSITY" => match parts.get(2).copied() {
+                Some("default" | "terse") => ConsoleCommand::SetVerbosity(Verbosity::Terse),
+                Some("verbose") => ConsoleCommand::SetVerbosity(Verbosity::Verbose),
+                _ => ConsoleCommand::Invalid,
+            },
             _ => ConsoleCommand::Invalid,
         }
     }
@@ -382,14 +497,22 @@ impl<T: Helper> Console<T> {
     }
 
     /// Prints information about connection and help hint
-    pub fn greet(&self, connection_info: &str) {
-        self.write(connection_info);
+    pub fn greet(&self) {
+        self.write(&self.connection_info);
         self.write("type '\\help' for interactive help");
     }
+
+    /// Describes the connection along with the current console language.
+    fn describe_connection(&self) -> String {
+        format!(
+            "{}\nCurrent language is {}",
+            self.connection_info, self.current_language
+        )
+    }
 }
 
 impl Console<LuaHelper> {
-    pub fn with_completer(helper: LuaHelper, quiet: bool) -> Result<Self> {
+    pub fn with_completer(helper: LuaHelper, connection_info: String, quiet: bool) -> Result<Self> {
         let (mut editor, history_file_path) = Self::editor_with_history()?;
 
         editor.set_helper(Some(helper));
@@ -404,6 +527,8 @@ impl Console<LuaHelper> {
             uncompleted_statement: String::new(),
             eof_received: false,
             current_language: ConsoleLanguage::Sql,
+            verbosity: Verbosity::Terse,
+            connection_info,
             mode: Mode::Admin,
             quiet,
         })
@@ -411,7 +536,7 @@ impl Console<LuaHelper> {
 }
 
 impl Console<()> {
-    pub fn new() -> Result<Self> {
+    pub fn new(connection_info: String) -> Result<Self> {
         let (editor, history_file_path) = Self::editor_with_history()?;
 
         Ok(Console {
@@ -422,8 +547,265 @@ impl Console<()> {
             uncompleted_statement: String::new(),
             eof_received: false,
             current_language: ConsoleLanguage::Sql,
+            verbosity: Verbosity::Terse,
+            connection_info,
             mode: Mode::Connection,
             quiet: false,
         })
     }
 }
+
+/// Parses the arguments of `\copy (<query>) TO '<file>' CSV [HEADER]`.
+fn parse_copy_command(args: &str) -> std::result::Result<CopyCommand, String> {
+    let args = args.trim().trim_end_matches(';').trim_end();
+    let Some(rest) = args.strip_prefix('(') else {
+        return Err("query must be enclosed in parentheses".into());
+    };
+
+    // Find the parenthesis closing the query, skipping quoted literals and identifiers.
+    let mut depth = 1;
+    let mut quote = None;
+    let mut query_end = None;
+    for (pos, c) in rest.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => {
+                depth -= 1;
+                if depth == 0 {
+                    query_end = Some(pos);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let Some(query_end) = query_end else {
+        return Err("unbalanced parentheses".into());
+    };
+    let query = rest[..query_end].trim();
+    if query.is_empty() {
+        return Err("query is empty".into());
+    }
+
+    let tail = rest[query_end + 1..].trim_start();
+    let tail = match tail.split_once(char::is_whitespace) {
+        Some((keyword, tail)) if keyword.eq_ignore_ascii_case("to") => tail.trim_start(),
+        _ => return Err("expected TO after the query".into()),
+    };
+
+    let (path, tail) = if let Some(quoted) = tail.strip_prefix('\'') {
+        // Quotes inside the path are escaped by doubling them, as in SQL literals.
+        let mut path = String::new();
+        let mut chars = quoted.char_indices().peekable();
+        let mut end = None;
+        while let Some((pos, c)) = chars.next() {
+            if c != '\'' {
+                path.push(c);
+            } else if let Some((_, '\'')) = chars.peek() {
+                path.push(c);
+                chars.next();
+            } else {
+                end = Some(pos);
+                break;
+            }
+        }
+        let Some(end) = end else {
+            return Err("unterminated file name".into());
+        };
+        (path, &quoted[end + 1..])
+    } else {
+        let end = tail.find(char::is_whitespace).unwrap_or(tail.len());
+        (tail[..end].to_string(), &tail[end..])
+    };
+    if path.is_empty() {
+        return Err("file name is empty".into());
+    }
+
+    let mut csv = false;
+    let mut header = false;
+    for option in tail.split_whitespace() {
+        if option.eq_ignore_ascii_case("csv") {
+            csv = true;
+        } else if option.eq_ignore_ascii_case("header") {
+            header = true;
+        } else {
+            return Err(format!("unknown option '{option}'"));
+        }
+    }
+    if !csv {
+        return Err("only CSV format is supported".into());
+    }
+
+    Ok(CopyCommand {
+        query: query.to_string(),
+        path: PathBuf::from(path),
+        header,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Console, ConsoleCommand, CopyCommand, DescribeCommand, Mode, Verbosity};
+    use std::path::PathBuf;
+
+    fn admin_console() -> Console<()> {
+        let connection_info = "Connected to admin console by socket path \"./admin.sock\"";
+        let mut console = Console::new(connection_info.into()).unwrap();
+        console.mode = Mode::Admin;
+        console
+    }
+
+    #[test]
+    fn parse_conninfo() {
+        let console = admin_console();
+        for command in ["\\conninfo", "\\conninfo;"] {
+            assert!(matches!(
+                console.parse_special_command(command),
+                ConsoleCommand::PrintConnectionInfo
+            ));
+        }
+        assert!(matches!(
+            console.parse_special_command("\\conninfo all"),
+            ConsoleCommand::Invalid
+        ));
+    }
+
+    #[test]
+    fn conninfo_shows_language() {
+        let mut console = admin_console();
+        assert_eq!(
+            console.describe_connection(),
+            "Connected to admin console by socket path \"./admin.sock\"\nCurrent language is sql"
+        );
+
+        console.handle_special_command("\\lua").unwrap();
+        assert!(console
+            .describe_connection()
+            .ends_with("Current language is lua"));
+
+        console.handle_special_command("\\sql;").unwrap();
+        assert!(console
+            .describe_connection()
+            .ends_with("Current language is sql"));
+    }
+
+    #[test]
+    fn set_verbosity() {
+        let mut console = admin_console();
+        for (command, expected) in [
+            ("\\set verbosity verbose", Verbosity::Verbose),
+            ("\\set VERBOSITY terse", Verbosity::Terse),
+            ("\\s verbosity default", Verbosity::Terse),
+        ] {
+            assert!(
+                matches!(
+                    console.parse_special_command(command),
+                    ConsoleCommand::SetVerbosity(verbosity) if verbosity == expected
+                ),
+                "{command}"
+            );
+        }
+        assert!(matches!(
+            console.parse_special_command("\\set verbosity loud"),
+            ConsoleCommand::Invalid
+        ));
+
+        // Admin console has no error details to show.
+        console
+            .handle_special_command("\\set verbosity verbose")
+            .unwrap();
+        assert_eq!(console.verbosity(), Verbosity::Terse);
+
+        console.mode = Mode::Connection;
+        console
+            .handle_special_command("\\set verbosity verbose")
+            .unwrap();
+        assert_eq!(console.verbosity(), Verbosity::Verbose);
+        console
+            .handle_special_command("\\set verbosity terse")
+            .unwrap();
+        assert_eq!(console.verbosity(), Verbosity::Terse);
+    }
+
+    #[test]
+    fn parse_copy() {
+        let console = admin_console();
+        let ConsoleCommand::CopyTo(copy) = console.parse_special_command(
+            "\\copy (SELECT a, ')' FROM t WHERE (a > 1)) TO 'out.csv' CSV HEADER",
+        ) else {
+            panic!("expected \\copy command");
+        };
+        assert_eq!(
+            copy,
+            CopyCommand {
+                query: "SELECT a, ')' FROM t WHERE (a > 1)".into(),
+                path: PathBuf::from("out.csv"),
+                header: true,
+            }
+        );
+
+        let ConsoleCommand::CopyTo(copy) =
+            console.parse_special_command("\\copy(values (1)) to '/tmp/it''s.csv' csv;")
+        else {
+            panic!("expected \\copy command");
+        };
+        assert_eq!(copy.query, "values (1)");
+        assert_eq!(copy.path, PathBuf::from("/tmp/it's.csv"));
+        assert!(!copy.header);
+
+        for command in [
+            "\\copy SELECT 1 TO 'out.csv' CSV",
+            "\\copy (SELECT 1 TO 'out.csv' CSV",
+            "\\copy (SELECT 1) 'out.csv' CSV",
+            "\\copy (SELECT 1) TO 'out.csv'",
+            "\\copy (SELECT 1) TO 'out.csv' JSON",
+        ] {
+            assert!(
+                matches!(
+                    console.parse_special_command(command),
+                    ConsoleCommand::Malformed(_)
+                ),
+                "{command}"
+            );
+        }
+        assert!(matches!(
+            console.parse_special_command("\\copyright"),
+            ConsoleCommand::Invalid
+        ));
+    }
+
+    #[test]
+    fn parse_describe() {
+        let console = admin_console();
+        for (command, table, verbose) in [
+            ("\\d t", "t", false),
+            ("\\d+ T;", "t", true),
+            ("\\d+ \"Warehouse\"", "Warehouse", true),
+        ] {
+            let ConsoleCommand::DescribeTable(describe) = console.parse_special_command(command)
+            else {
+                panic!("expected \\d command: {command}");
+            };
+            assert_eq!(
+                describe,
+                DescribeCommand {
+                    table: table.into(),
+                    verbose,
+                }
+            );
+        }
+
+        for command in ["\\d", "\\d+", "\\d t1 t2", "\\d+ \"\""] {
+            assert!(
+                matches!(
+                    console.parse_special_command(command),
+                    ConsoleCommand::Invalid
+                ),
+                "{command}"
+            );
+        }
+    }
+}
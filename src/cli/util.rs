@@ -1,6 +1,7 @@
 use crate::address::IprotoAddress;
 use crate::cli;
 use crate::cli::args;
+use crate::cli::console::{CopyCommand, DescribeCommand, ReplError};
 use crate::config::DEFAULT_USERNAME;
 use crate::schema::PICO_SERVICE_USER_NAME;
 use crate::traft;
@@ -8,7 +9,7 @@ use crate::traft::error::Error;
 
 use std::fmt::Display;
 use std::fs;
-use std::io::{BufRead as _, BufReader, Write as _};
+use std::io::{self, BufRead as _, BufReader, BufWriter, Write};
 use std::path::Path;
 use std::time::Duration;
 
@@ -112,7 +113,7 @@ impl RowSet {
 
     /// Escape a value for CSV output.
     fn escape_csv(value: &str, separator: char) -> String {
-        if value.contains(separator) || value.contains('"') || value.contains('\n') {
+        if value.contains([separator, '"', '\n', '\r']) {
             format!("\"{}\"", value.replace('"', "\"\""))
         } else {
             value.to_string()
@@ -143,6 +144,23 @@ impl RowSet {
         lines.append(&mut rows);
         lines.join("\n")
     }
+
+    /// Write the rows to `out` as comma-separated CSV row by row.
+    /// Returns the number of written rows (not counting the header).
+    pub fn write_csv(&self, mut out: impl Write, header: bool) -> io::Result<usize> {
+        if header {
+            let names = self.metadata.iter().map(|c| Self::escape_csv(&c.name, ','));
+            writeln!(out, "{}", names.collect::<Vec<_>>().join(","))?;
+        }
+        for row in &self.rows {
+            let fields = row
+                .iter()
+                .map(|v| Self::escape_csv(&Self::format_value(v), ','));
+            writeln!(out, "{}", fields.collect::<Vec<_>>().join(","))?;
+        }
+
+        Ok(self.rows.len())
+    }
 }
 
 impl Display for RowSet {
@@ -239,6 +257,29 @@ impl ResultSet {
                 .to_string(),
         }
     }
+
+    /// Export the result of a `\copy` query into a CSV file and return the
+    /// number of written rows. The file is not created if the query failed.
+    pub fn export_csv(&self, copy: &CopyCommand) -> Result<usize, String> {
+        let row_set = match self {
+            ResultSet::RowSet(s) => s.first().expect(
+                "RowSet is represented as a Vec<Vec<Rows>> where outer vec always has lenghth equal to 1"
+            ),
+            ResultSet::Error(_, message) => return Err(message.clone()),
+            ResultSet::RowCount(_) | ResultSet::Explain(_) => {
+                return Err("\\copy supports only queries returning rows".into())
+            }
+        };
+
+        let path = copy.path.display();
+        let file = fs::File::create(&copy.path)
+            .map_err(|e| format!("could not open file \"{path}\" for writing: {e}"))?;
+        let mut writer = BufWriter::new(file);
+        row_set
+            .write_csv(&mut writer, copy.header)
+            .and_then(|count| writer.flush().map(|_| count))
+            .map_err(|e| format!("could not write to file \"{path}\": {e}"))
+    }
 }
 
 impl Display for ResultSet {
@@ -247,6 +288,297 @@ impl Display for ResultSet {
     }
 }
 
+//////////////////////////////////////////////////////////////////////
+// Table description
+//////////////////////////////////////////////////////////////////////
+
+/// Builds the output of `\d <table>` from the system tables. `query` executes
+/// an SQL query on the server. Errors of these queries are returned as the
+/// output, only the connection errors are propagated.
+pub fn describe_table(
+    describe: &DescribeCommand,
+    mut query: impl FnMut(&str) -> Result<ResultSet, ReplError>,
+) -> Result<String, ReplError> {
+    let mut fetch = |sql: String| -> Result<Result<Vec<Vec<rmpv::Value>>, String>, ReplError> {
+        let rows = match query(&sql)? {
+            ResultSet::RowSet(mut s) => s.pop().map(|s| s.rows).unwrap_or_default(),
+            ResultSet::Error(_, message) => return Ok(Err(message)),
+            ResultSet::RowCount(_) | ResultSet::Explain(_) => {
+                return Ok(Err("unexpected response to the system table query".into()))
+            }
+        };
+        Ok(Ok(rows))
+    };
+
+    let name = describe.table.replace('\'', "''");
+    let rows = match fetch(format!(
+        r#"SELECT "id", "distribution", "format", "opts" FROM "_pico_table" WHERE "name" = '{name}'"#
+    ))? {
+        Ok(rows) => rows,
+        Err(message) => return Ok(message),
+    };
+    let Some([id, distribution, columns, opts]) = rows
+        .into_iter()
+        .next()
+        .and_then(|row| <[rmpv::Value; 4]>::try_from(row).ok())
+    else {
+        let table = &describe.table;
+        return Ok(format!("Did not find any table named \"{table}\""));
+    };
+
+    let mut description = TableDescription {
+        name: describe.table.clone(),
+        columns: TableDescription::decode_columns(&columns),
+        primary_key: vec![],
+        indexes: vec![],
+        distribution,
+        bucket_count: None,
+        defaults: vec![],
+        identity: None,
+        checks: vec![],
+    };
+    description.decode_options(&opts);
+
+    let rows = match fetch(format!(
+        r#"SELECT "id", "name", "parts" FROM "_pico_index" WHERE "table_id" = {id} ORDER BY "id""#
+    ))? {
+        Ok(rows) => rows,
+        Err(message) => return Ok(message),
+    };
+    for row in rows {
+        let Ok([index_id, index_name, parts]) = <[rmpv::Value; 3]>::try_from(row) else {
+            continue;
+        };
+        if index_id.as_u64() == Some(0) {
+            description.primary_key = TableDescription::decode_key(&parts);
+        } else {
+            let index_name = index_name.as_str().unwrap_or_default().to_string();
+            let parts = TableDescription::decode_index_parts(&parts);
+            description.indexes.push((index_name, parts));
+        }
+    }
+
+    if describe.verbose {
+        if let Some(tier) = description.tier() {
+            let tier = tier.replace('\'', "''");
+            let rows = match fetch(format!(
+                r#"SELECT "bucket_count" FROM "_pico_tier" WHERE "name" = '{tier}'"#
+            ))? {
+                Ok(rows) => rows,
+                Err(message) => return Ok(message),
+            };
+            description.bucket_count = rows
+                .first()
+                .and_then(|row| row.first())
+                .and_then(rmpv::Value::as_u64);
+        }
+    }
+
+    Ok(description.render(describe.verbose))
+}
+
+/// Table definition decoded from the system tables.
+#[derive(Debug)]
+struct TableDescription {
+    name: String,
+    /// Name, type and nullability of the columns.
+    columns: Vec<(String, String, bool)>,
+    primary_key: Vec<String>,
+    /// Names and rendered parts of the secondary indexes.
+    indexes: Vec<(String, Vec<String>)>,
+    /// `distribution` field of `_pico_table`, e.g. `{"Global": null}`.
+    distribution: rmpv::Value,
+    bucket_count: Option<u64>,
+    /// Column names and declared `DEFAULT` values.
+    defaults: Vec<(String, String)>,
+    /// Name of the `GENERATED ALWAYS AS IDENTITY` column.
+    identity: Option<String>,
+    /// Rendered `CHECK` constraints, e.g. `"qty_positive" CHECK (qty > 0)`.
+    checks: Vec<String>,
+}
+
+impl TableDescription {
+    fn field<'a>(map: &'a rmpv::Value, key: &str) -> Option<&'a rmpv::Value> {
+        map.as_map()?
+            .iter()
+            .find(|(k, _)| k.as_str() == Some(key))
+            .map(|(_, v)| v)
+    }
+
+    fn decode_columns(format: &rmpv::Value) -> Vec<(String, String, bool)> {
+        let Some(fields) = format.as_array() else {
+            return vec![];
+        };
+        fields
+            .iter()
+            .map(|field| {
+                let text = |key| {
+                    Self::field(field, key)
+                        .and_then(rmpv::Value::as_str)
+                        .unwrap_or_default()
+                        .to_string()
+                };
+                let is_nullable = Self::field(field, "is_nullable")
+                    .and_then(rmpv::Value::as_bool)
+                    .unwrap_or(true);
+                (text("name"), text("field_type"), is_nullable)
+            })
+            .collect()
+    }
+
+    /// Reads the defaults and the constraints from the `opts` field of `_pico_table`,
+    /// e.g. `[{"default": ["qty", "0"]}, {"check": ["qty_positive", "qty", ">", "0"]}]`.
+    fn decode_options(&mut self, opts: &rmpv::Value) {
+        let Some(opts) = opts.as_array() else {
+            return;
+        };
+        for opt in opts {
+            let Some((kind, params)) = opt.as_map().and_then(|opt| opt.first()) else {
+                continue;
+            };
+            let params: Vec<_> = params
+                .as_array()
+                .map(|params| params.iter().filter_map(rmpv::Value::as_str).collect())
+                .unwrap_or_default();
+            match (kind.as_str(), params.as_slice()) {
+                (Some("default"), [column, value]) => {
+                    self.defaults.push((column.to_string(), value.to_string()));
+                }
+                (Some("identity"), [column]) => self.identity = Some(column.to_string()),
+                (Some("check"), [name, column, op, value]) => {
+                    self.checks
+                        .push(format!("\"{name}\" CHECK ({column} {op} {value})"));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the column names of the index parts, e.g. `[["id", "integer", ...]]`.
+    fn decode_key(parts: &rmpv::Value) -> Vec<String> {
+        let Some(parts) = parts.as_array() else {
+            return vec![];
+        };
+        parts
+            .iter()
+            .filter_map(|part| part.as_array()?.first()?.as_str().map(String::from))
+            .collect()
+    }
+
+    /// Renders the index parts as `column [COLLATE collation]`.
+    fn decode_index_parts(parts: &rmpv::Value) -> Vec<String> {
+        let Some(parts) = parts.as_array() else {
+            return vec![];
+        };
+        parts
+            .iter()
+            .filter_map(|part| {
+                let part = part.as_array()?;
+                let column = part.first()?.as_str()?;
+                match part.get(2).and_then(rmpv::Value::as_str) {
+                    Some(collation) => Some(format!("{column} COLLATE {collation}")),
+                    None => Some(column.to_string()),
+                }
+            })
+            .collect()
+    }
+
+    /// Splits the distribution into its kind and parameters.
+    fn distribution(&self) -> Option<(&str, &[rmpv::Value])> {
+        let (kind, params) = self.distribution.as_map()?.first()?;
+        Some((
+            kind.as_str()?,
+            params.as_array().map_or(&[][..], Vec::as_slice),
+        ))
+    }
+
+    fn tier(&self) -> Option<&str> {
+        match self.distribution()? {
+            ("ShardedImplicitly", [_, _, tier]) => tier.as_str(),
+            ("ShardedByField", [_, tier]) => tier.as_str(),
+            _ => None,
+        }
+    }
+
+    fn render(&self, verbose: bool) -> String {
+        let mut table = Table::new();
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        if verbose {
+            table.set_header(["Column", "Type", "Nullable", "Default"]);
+        } else {
+            table.set_header(["Column", "Type", "Nullable"]);
+        }
+        for (name, ty, is_nullable) in &self.columns {
+            let nullable = if *is_nullable { "" } else { "not null" };
+            let mut row = vec![name.as_str(), ty.as_str(), nullable];
+            if verbose {
+                let default = if self.identity.as_ref() == Some(name) {
+                    "generated always as identity"
+                } else {
+                    self.defaults
+                        .iter()
+                        .find(|(column, _)| column == name)
+                        .map_or("", |(_, value)| value.as_str())
+                };
+                row.push(default);
+            }
+            table.add_row(row);
+        }
+
+        let mut lines = vec![format!("Table \"{}\"", self.name), table.to_string()];
+        if !self.primary_key.is_empty() {
+            lines.push(format!("Primary key: ({})", self.primary_key.join(", ")));
+        }
+        if !self.indexes.is_empty() {
+            lines.push("Indexes:".into());
+            for (name, parts) in &self.indexes {
+                lines.push(format!("    \"{name}\" ({})", parts.join(", ")));
+            }
+        }
+        if !verbose {
+            return lines.join("\n");
+        }
+
+        if !self.checks.is_empty() {
+            lines.push("Check constraints:".into());
+            for check in &self.checks {
+                lines.push(format!("    {check}"));
+            }
+        }
+
+        match self.distribution() {
+            Some(("Global", _)) => {
+                lines.push("Distribution: global (replicated to every instance)".into());
+            }
+            Some(("ShardedImplicitly", [key, sharding_fn, _])) => {
+                let key: Vec<_> = key
+                    .as_array()
+                    .map(|columns| columns.iter().filter_map(rmpv::Value::as_str).collect())
+                    .unwrap_or_default();
+                lines.push(format!("Distribution: sharded by ({})", key.join(", ")));
+                lines.push(format!(
+                    "Sharding function: {}",
+                    sharding_fn.as_str().unwrap_or_default()
+                ));
+            }
+            Some(("ShardedByField", [field, _])) => {
+                lines.push(format!(
+                    "Distribution: sharded by bucket id column \"{}\"",
+                    field.as_str().unwrap_or_default()
+                ));
+            }
+            _ => lines.push(format!("Distribution: {}", self.distribution)),
+        }
+        if let Some(tier) = self.tier() {
+            lines.push(format!("Tier: {tier}"));
+        }
+        if let Some(bucket_count) = self.bucket_count {
+            lines.push(format!("Bucket count: {bucket_count}"));
+        }
+        lines.join("\n")
+    }
+}
+
 //////////////////////////////////////////////////////////////////////
 // Credentials
 //////////////////////////////////////////////////////////////////////
@@ -754,4 +1086,129 @@ mod tests {
             "some error"
         );
     }
+
+    #[test]
+    fn test_export_csv() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.csv");
+        let copy = CopyCommand {
+            query: "SELECT id, name FROM t".into(),
+            path: path.clone(),
+            header: true,
+        };
+
+        let mut rowset = sample_rowset();
+        rowset.rows.push(vec![
+            rmpv::Value::Integer(3.into()),
+            rmpv::Value::String("a,\"b\"\r\nc".into()),
+        ]);
+        let result = ResultSet::RowSet(vec![rowset]);
+        assert_eq!(result.export_csv(&copy), Ok(3));
+        assert_eq!(
+            fs::read(&path).unwrap(),
+            b"id,name\n1,Alice\n2,Bob\n3,\"a,\"\"b\"\"\r\nc\"\n"
+        );
+
+        // A failed query doesn't create the file.
+        let missing = dir.path().join("missing.csv");
+        let copy = CopyCommand {
+            path: missing.clone(),
+            ..copy
+        };
+        let error = ResultSet::Error(None, "sbroad: table t not found".into());
+        assert_eq!(
+            error.export_csv(&copy),
+            Err("sbroad: table t not found".into())
+        );
+        assert!(!missing.exists());
+
+        // An unwritable path is reported as an error.
+        let copy = CopyCommand {
+            path: dir.path().join("no/such/dir/out.csv"),
+            ..copy
+        };
+        let err = result.export_csv(&copy).unwrap_err();
+        assert!(err.starts_with("could not open file"), "{err}");
+    }
+
+    fn describe_value(json: serde_json::Value) -> rmpv::Value {
+        rmpv::ext::to_value(json).unwrap()
+    }
+
+    fn sample_table(distribution: serde_json::Value) -> TableDescription {
+        let columns = describe_value(serde_json::json!([
+            {"name": "id", "field_type": "integer", "is_nullable": false},
+            {"name": "bucket_id", "field_type": "unsigned", "is_nullable": true},
+            {"name": "name", "field_type": "string", "is_nullable": true},
+        ]));
+        let parts = describe_value(serde_json::json!([["id", "integer", null, false, null]]));
+        TableDescription {
+            name: "t".into(),
+            columns: TableDescription::decode_columns(&columns),
+            primary_key: TableDescription::decode_key(&parts),
+            indexes: vec![],
+            distribution: describe_value(distribution),
+            bucket_count: Some(3000),
+        }
+    }
+
+    #[test]
+    fn test_describe_table() {
+        let table = sample_table(serde_json::json!({
+            "ShardedImplicitly": [["id", "name"], "murmur3", "default"]
+        }));
+        assert_eq!(table.tier(), Some("default"));
+
+        let output = table.render(false);
+        assert!(output.starts_with("Table \"t\"\n"), "{output}");
+        assert!(output.contains("not null"), "{output}");
+        assert!(output.ends_with("Primary key: (id)"), "{output}");
+        assert!(!output.contains("Distribution"), "{output}");
+
+        let output = table.render(true);
+        assert!(
+            output.ends_with(
+                "Primary key: (id)\n\
+                 Distribution: sharded by (id, name)\n\
+                 Sharding function: murmur3\n\
+                 Tier: default\n\
+                 Bucket count: 3000"
+            ),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_describe_global_table() {
+        let mut table = sample_table(serde_json::json!({"Global": null}));
+        table.bucket_count = None;
+        assert_eq!(table.tier(), None);
+
+        let output = table.render(true);
+        assert!(
+            output.ends_with("Distribution: global (replicated to every instance)"),
+            "{output}"
+        );
+        assert!(!output.contains("Sharding function"), "{output}");
+    }
+
+    #[test]
+    fn test_describe_table_indexes() {
+        let mut table = sample_table(serde_json::json!({"Global": null}));
+        let parts = describe_value(serde_json::json!([
+            ["name", "string", "unicode_ci", true, null],
+            ["id", "integer", null, false, null],
+        ]));
+        let parts = TableDescription::decode_index_parts(&parts);
+        table.indexes.push(("t_name".into(), parts));
+
+        let output = table.render(false);
+        assert!(
+            output.ends_with(
+                "Primary key: (id)\n\
+                 Indexes:\n    \"t_name\" (name COLLATE unicode_ci, id)"
+            ),
+            "{output}"
+        );
+    }
 }
@@ -29,8 +29,10 @@ use crate::util::NoYieldsRefCell;
 use crate::util::{cast_and_encode, edit_distance};
 use crate::{config_parameter_path, sql};
 use crate::{pgproto, traft};
+use ::sql::executor::engine::helpers::IdentifierCase;
 use ::sql::ir::options;
 use ::sql::ir::value::{EncodedValue, Value};
+use ::sql::ir::DEFAULT_MAX_EXPRESSION_DEPTH;
 use observer::AtomicObserverProvider;
 use rand::TryRng;
 use serde_yaml::Value as YamlValue;
@@ -2049,6 +2051,20 @@ pub struct AlterSystemParameters {
     #[introspection(sbroad_type = SbroadType::Boolean)]
     #[introspection(config_default = DEFAULT_SQL_LOG)]
     pub sql_log: bool,
+
+    /// Policy of normalizing unquoted SQL identifiers.
+    /// Allowed values: "fold", "preserve".
+    /// Default value: "fold".
+    #[introspection(sbroad_type = SbroadType::String)]
+    #[introspection(config_default = IdentifierCase::default().to_string())]
+    pub sql_identifier_case: String,
+
+    /// Maximum nesting depth of the expressions in an SQL query, including
+    /// the expressions expanded by the planner (e.g. an `IN` list replaced
+    /// with a chain of `OR`). Deeper queries are rejected with an error.
+    #[introspection(sbroad_type = SbroadType::Unsigned)]
+    #[introspection(config_default = DEFAULT_MAX_EXPRESSION_DEPTH as u64)]
+    pub sql_expression_depth_max: u64,
 }
 
 fn generate_secure_token() -> String {
@@ -2190,6 +2206,8 @@ pub struct DynamicConfigProviders {
     pub sql_preemption_interval_us: AtomicObserverProvider<u64>,
     pub sql_preemption_opcode_max: AtomicObserverProvider<u64>,
     pub sql_log: AtomicObserverProvider<bool>,
+    pub sql_identifier_case: AtomicObserverProvider<u8>,
+    pub sql_expression_depth_max: AtomicObserverProvider<u64>,
 }
 
 impl DynamicConfigProviders {
@@ -2205,6 +2223,8 @@ impl DynamicConfigProviders {
             sql_preemption_interval_us: AtomicObserverProvider::new(),
             sql_preemption_opcode_max: AtomicObserverProvider::new(),
             sql_log: AtomicObserverProvider::new(),
+            sql_identifier_case: AtomicObserverProvider::new(),
+            sql_expression_depth_max: AtomicObserverProvider::new(),
         }
     }
 
@@ -2219,8 +2239,29 @@ impl DynamicConfigProviders {
                     options::ReadPreference::try_from(raw).expect("invalid read_preference value")
                 })
                 .unwrap_or_default(),
+            sql_recursion_max: options::DEFAULT_SQL_RECURSION_MAX as i64,
+            replica_lag_max: options::DEFAULT_REPLICA_LAG_MAX as i64,
+            sql_max_buckets_per_query: options::DEFAULT_SQL_MAX_BUCKETS_PER_QUERY as i64,
+            sql_partial_results: options::DEFAULT_SQL_PARTIAL_RESULTS,
+            wait_index: options::DEFAULT_WAIT_INDEX as i64,
+            wait_index_timeout: options::DEFAULT_WAIT_INDEX_TIMEOUT as i64,
+            sql_result_row_max: options::DEFAULT_SQL_RESULT_ROW_MAX as i64,
+            sql_dispatch_concurrency_max: options::DEFAULT_SQL_DISPATCH_CONCURRENCY_MAX as i64,
         })
     }
+
+    pub fn current_identifier_case(&self) -> IdentifierCase {
+        self.sql_identifier_case
+            .try_current_value()
+            .map(|raw| IdentifierCase::try_from(raw).expect("invalid sql_identifier_case value"))
+            .unwrap_or_default()
+    }
+
+    pub fn current_sql_expression_depth_max(&self) -> usize {
+        self.sql_expression_depth_max
+            .try_current_value()
+            .map_or(DEFAULT_MAX_EXPRESSION_DEPTH, |depth| depth as usize)
+    }
 }
 
 pub static DYNAMIC_CONFIG: DynamicConfigProviders = DynamicConfigProviders::new();
@@ -2343,6 +2384,18 @@ pub fn validate_alter_system_parameter_value<'v>(
         }
     }
 
+    if name == system_parameter_name!(sql_identifier_case) {
+        if let Value::String(s) = value {
+            let _ = IdentifierCase::from_str(s).map_err(|_| {
+                Error::other(format!(
+                    "invalid value for '{name}': expected 'fold' or 'preserve'"
+                ))
+            })?;
+        } else {
+            panic!("invalid value for '{name}'")
+        }
+    }
+
     if name == system_parameter_name!(sql_preemption) {
         let _ = casted_value
             .bool()
@@ -2375,6 +2428,18 @@ pub fn validate_alter_system_parameter_value<'v>(
         }
     }
 
+    if name == system_parameter_name!(sql_expression_depth_max) {
+        let depth = casted_value
+            .integer()
+            .expect("invalid value for sql_expression_depth_max");
+
+        if depth < 1 {
+            return Err(Error::other(format!(
+                "invalid value for '{name}': value must be greater than 0",
+            )));
+        }
+    }
+
     Ok(casted_value)
 }
 
@@ -2623,6 +2688,19 @@ pub fn apply_parameter(
         let value = v.as_bool().expect("type is already checked");
         // Cache the value.
         DYNAMIC_CONFIG.sql_log.update(value);
+    } else if name == system_parameter_name!(sql_identifier_case) {
+        let value = v.as_str().expect("type is already checked");
+        let value = IdentifierCase::from_str(value).expect("value is already checked");
+        // Cache the value.
+        DYNAMIC_CONFIG.sql_identifier_case.update(value as u8);
+        // Cached plans were built with the previous policy.
+        crate::sql::router::invalidate_plans()?;
+    } else if name == system_parameter_name!(sql_expression_depth_max) {
+        let value = v.as_u64().expect("type is already checked");
+        // Cache the value.
+        DYNAMIC_CONFIG.sql_expression_depth_max.update(value);
+        // Cached plans were checked against the previous limit.
+        crate::sql::router::invalidate_plans()?;
     }
 
     Ok(())
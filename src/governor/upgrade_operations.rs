@@ -1,5 +1,6 @@
 use crate::catalog::pico_bucket::PicoBucket;
 use crate::catalog::pico_resharding_state::PicoReshardingState;
+use crate::catalog::pico_sequence::PicoSequence;
 use crate::catalog::pico_table::PicoTable;
 use crate::storage::schema::ddl_change_format_on_master;
 use crate::storage::{Instances, Replicasets, SystemTable, Tiers};
@@ -102,11 +103,33 @@ pub const CATALOG_UPGRADE_LIST: &'static [(
         "26.1.0",
         &[
             ("exec_script", InternalScript::AlterPicoTableAddOptsField.as_str()),
+            // Creation of Lua stored functions CEIL, FLOOR, ROUND, SIGN,
+            // OCTET_LENGTH, ASCII, CHR, PERCENTILE_CONT, PERCENTILE_DISC,
+            // _PICO_TABLESAMPLE, JSON_EXTRACT_PATH_TEXT, _PICO_JSON_PARSE,
+            // LPAD, RPAD, REPEAT, REVERSE, TO_NUMBER, _PICO_TO_CHAR_NUMBER,
+            // STRING_TO_ARRAY, ARRAY_TO_STRING, SPLIT_PART, _PICO_IS_JSON,
+            // _PICO_JSON_CONTAINS, JSONB_PATH_QUERY, MD5, SHA256,
+            // _PICO_SHA256_BYTEA, _PICO_CEIL_DOUBLE, _PICO_FLOOR_DOUBLE,
+            // _PICO_ROUND_DOUBLE and DATE_TRUNC.
+            ("exec_script", InternalScript::CreateIfNotExistSqlBuiltins.as_str()),
 
             // Make sure single-tier clusters have the single tier marked as default.
             // This fixes a bug in 25.4.1 migration.
             // See https://git.picodata.io/core/picodata/-/issues/2683
-            ("sql", "UPDATE _pico_tier SET is_default = true WHERE 1 in (SELECT count(*) FROM _pico_tier)")
+            ("sql", "UPDATE _pico_tier SET is_default = true WHERE 1 in (SELECT count(*) FROM _pico_tier)"),
+
+            // Cluster sequences of the identity columns.
+            ("sql", PicoSequence::SQL_CREATE),
+
+            ("proc_name", "proc_sql_dispatch_arrow"),
+            ("proc_name", "proc_sql_estimate_fanout"),
+            ("proc_name", "proc_sql_point_get"),
+            ("proc_name", "proc_pg_notify"),
+            ("proc_name", "proc_sql_execute_dml_batch"),
+            ("proc_name", "proc_sql_lock_rows"),
+            ("proc_name", "proc_sql_unlock_rows"),
+            ("proc_name", "proc_sql_text_to_bool"),
+            ("proc_name", "proc_sql_bool_to_text"),
         ]
     )
 ];
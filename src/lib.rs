@@ -35,16 +35,15 @@ use crate::traft::Result;
 use crate::util::effective_user_id;
 use ::raft::prelude as raft;
 use ::raft::Storage;
+use ::sql::frontend::sql::sql_procedure_parameter_list;
 use ::sql::frontend::sql::transform_to_regex_pattern;
-use ::sql::frontend::sql::FUNCTION_NAME_MAPPINGS;
 use ::tarantool::datetime::Datetime;
 use ::tarantool::error::Error as TntError;
-use ::tarantool::fiber::r#async::timeout::{self, IntoTimeout};
+use ::tarantool::fiber::r#async::timeout::IntoTimeout;
 use ::tarantool::time::Instant;
 use ::tarantool::tlua;
 use ::tarantool::transaction::transaction;
 use ::tarantool::{fiber, session};
-use backoff::SimpleBackoffManager;
 use config::apply_parameter;
 use config::AlterSystemParametersRef;
 use config::PicodataConfig;
@@ -635,10 +634,7 @@ fn init_stored_procedures() {
     let lua = ::tarantool::lua_state();
     for proc in ::tarantool::proc::all_procs().iter() {
         let proc_name = proc.name();
-        let proc_opt = FUNCTION_NAME_MAPPINGS
-            .iter()
-            .find(|mapping| mapping.rust_procedure == proc_name)
-            .map(|mapping| mapping.parameter_list);
+        let proc_opt = sql_procedure_parameter_list(proc_name);
         let proc_params = proc_opt.unwrap_or_default();
 
         let mut proc_exports = vec!["LUA"];
@@ -1779,50 +1775,12 @@ fn start_pre_join(
         uuid: instance_uuid,
     };
 
-    const INITIAL_TIMEOUT: Duration = Duration::from_secs(1);
-    const MAX_TIMEOUT: Duration = Duration::from_secs(60);
-    let mut backoff = SimpleBackoffManager::new("proc_raft_join RPC", INITIAL_TIMEOUT, MAX_TIMEOUT);
-
-    let resp: rpc::join::Response = loop {
-        let current_timeout = backoff.timeout();
-        let f = rpc::network_call(
-            &instance_address,
-            proc_name!(rpc::join::proc_raft_join),
-            &req,
-        )
-        .timeout(current_timeout);
-        let res = fiber::block_on(f);
-        match res {
-            Ok(resp) => {
-                crate::error_injection!(exit "EXIT_AFTER_RPC_PROC_RAFT_JOIN");
-                break resp;
-            }
-            Err(timeout::Error::Expired) => {
-                tlog!(
-                    Warning,
-                    "join request timed out after {:?}, retrying...",
-                    current_timeout
-                );
-                backoff.handle_failure();
-                continue;
-            }
-            Err(timeout::Error::Failed(e @ (TntError::ConnectionClosed(_) | TntError::IO(_)))) => {
-                tlog!(
-                    Warning,
-                    "join request failed: {e}, retrying in {:?}...",
-                    current_timeout
-                );
-                fiber::sleep(current_timeout);
-                backoff.handle_failure();
-                continue;
-            }
-            Err(e) => {
-                return Err(Error::other(format!(
-                    "join request failed: {e}, shutting down..."
-                )));
-            }
-        }
-    };
+    let resp = rpc::join::send_join_request(&instance_address, |address, timeout| {
+        let f = rpc::network_call(address, proc_name!(rpc::join::proc_raft_join), &req)
+            .timeout(timeout);
+        fiber::block_on(f)
+    })?;
+    crate::error_injection!(exit "EXIT_AFTER_RPC_PROC_RAFT_JOIN");
 
     let compressed = compress_join_response(&resp)?;
 
@@ -1743,6 +1743,44 @@ pub(crate) fn setup() {
         },
     );
 
+    luamod_set(
+        &l,
+        "_trace_sql_dispatch",
+        indoc! {"
+        pico._trace_sql_dispatch(enable)
+
+        Internal API. Log every SQL sub-plan the router of this instance
+        sends to the storages: the query_id of the statement, the replicaset,
+        the buckets, the top node of the sub-plan and the request size.
+        Disabled by default.
+
+        Params:
+
+            1. enable (bool)
+        "},
+        tlua::Function::new(|enable: bool| {
+            use ::sql::executor::engine::helpers::trace::{
+                set_dispatch_hook, DispatchHook, DispatchTrace,
+            };
+            if !enable {
+                set_dispatch_hook(None);
+                return;
+            }
+            let hook: DispatchHook = std::rc::Rc::new(|trace: &DispatchTrace| {
+                tlog!(
+                    Info,
+                    "sql dispatch: replicaset {}, buckets {:?}, top {}, payload {} bytes",
+                    trace.replicaset,
+                    trace.buckets,
+                    trace.top,
+                    trace.payload_size;
+                    "query_id" => %trace.query_id.as_deref().unwrap_or_default()
+                );
+            });
+            set_dispatch_hook(Some(hook));
+        }),
+    );
+
     #[cfg(feature = "error_injection")]
     luamod_set(
         &l,
@@ -81,6 +81,17 @@ static SQL_REPLICAS_READ_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
     .expect("Failed to create pico_sql_replicas_read_total counter")
 });
 
+static SQL_POINT_GET_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "pico_sql_point_get_total",
+            "Total number of single-row primary key SELECTs served by a point get",
+        ),
+        &["tier", "replicaset"],
+    )
+    .expect("Failed to create pico_sql_point_get_total counter")
+});
+
 static SQL_QUERY_ERRORS_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
     IntCounterVec::new(
         Opts::new(
@@ -404,6 +415,12 @@ pub fn record_sql_replicas_read_total() {
         .inc();
 }
 
+pub fn record_sql_point_get_total() {
+    SQL_POINT_GET_TOTAL
+        .with_label_values(&[my_tier(), my_replicaset()])
+        .inc();
+}
+
 pub fn record_sql_global_dml_query_total() {
     SQL_GLOBAL_DML_QUERY_TOTAL.inc();
 }
@@ -535,6 +552,7 @@ pub fn register_metrics(registry: &prometheus::Registry) -> prometheus::Result<(
     registry.register(Box::new(SQL_QUERY_ERRORS_TOTAL.clone()))?;
     registry.register(Box::new(SQL_QUERY_TOTAL.clone()))?;
     registry.register(Box::new(SQL_REPLICAS_READ_TOTAL.clone()))?;
+    registry.register(Box::new(SQL_POINT_GET_TOTAL.clone()))?;
     registry.register(Box::new(SQL_GLOBAL_DML_QUERY_TOTAL.clone()))?;
     registry.register(Box::new(SQL_GLOBAL_DML_QUERY_RETRIES_TOTAL.clone()))?;
     registry.register(Box::new(INFO_UPTIME.clone()))?;
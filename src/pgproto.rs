@@ -1,6 +1,10 @@
 #![warn(clippy::or_fun_call)]
 
-use self::{client::PgClient, error::PgResult, tls::TlsAcceptor};
+use self::{
+    client::{IdleTimeout, PgClient},
+    error::PgResult,
+    tls::TlsAcceptor,
+};
 use crate::{
     address::PgprotoAddress, introspection::Introspection, static_ref, storage::Catalog, tlog,
     traft::error::Error,
@@ -10,25 +14,30 @@ use smol_str::{format_smolstr, SmolStr};
 #[cfg(target_os = "linux")]
 use std::os::linux::net::SocketAddrExt;
 use std::{
+    cell::RefCell,
     io,
     path::{Path, PathBuf},
+    rc::Rc,
     sync::{
         atomic::{AtomicBool, Ordering},
         LazyLock,
     },
+    time::Duration,
 };
 use stream::PgStream;
 use tarantool::coio::{CoIOListener, CoIOStream};
 
 pub mod backend;
 mod client;
-mod error;
+pub(crate) mod error;
 mod messages;
 mod server;
 mod stream;
 mod tls;
 mod value;
 
+pub use client::{AuthExchange, Authenticator, CleartextAuthenticator, Md5Authenticator};
+
 static PGPROTO_CONNECTIONS_OPENED_TOTAL: LazyLock<IntCounter> = LazyLock::new(|| {
     IntCounter::with_opts(prometheus::Opts::new(
         "pico_pgproto_connections_opened_total",
@@ -65,6 +74,19 @@ pub fn register_metrics(registry: &prometheus::Registry) -> prometheus::Result<(
     Ok(())
 }
 
+thread_local! {
+    /// Authenticator replacing the built-in authentication of clients.
+    static AUTHENTICATOR: RefCell<Option<Rc<dyn Authenticator>>> = const { RefCell::new(None) };
+}
+
+/// Make pgproto authenticate clients with the given [`Authenticator`]
+/// instead of the auth methods of the users from the catalog, e.g. to plug
+/// an external authentication service. `None` restores the built-in
+/// authentication. Only the clients connected afterwards are affected.
+pub fn set_authenticator(authenticator: Option<Rc<dyn Authenticator>>) {
+    AUTHENTICATOR.with(|cell| *cell.borrow_mut() = authenticator);
+}
+
 /// Initialized to enable PostgreSQL server protocol.
 /// WARNING: if it is initialized, it does not directly mean
 /// that PostgreSQL server protocol is currently running.
@@ -89,6 +111,21 @@ pub struct Config {
     pub key_file: Option<PathBuf>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ca_file: Option<PathBuf>,
+
+    /// Number of seconds a client connection may stay idle waiting for
+    /// a query before it's closed. Zero disables the timeout.
+    #[introspection(config_default = 0.)]
+    pub idle_timeout: Option<f64>,
+
+    /// Whether [`Self::idle_timeout`] applies to connections with an open
+    /// transaction. If it does, the transaction is rolled back.
+    #[introspection(config_default = true)]
+    pub idle_timeout_in_transaction: Option<bool>,
+
+    /// Number of seconds a connection stays idle before TCP keepalive
+    /// probes are sent. Zero disables TCP keepalive.
+    #[introspection(config_default = 0.)]
+    pub tcp_keepalive: Option<f64>,
 }
 
 impl Config {
@@ -107,6 +144,18 @@ impl Config {
     pub fn ssl(&self) -> bool {
         self.ssl.expect("set by default")
     }
+
+    pub fn idle_timeout(&self) -> f64 {
+        self.idle_timeout.expect("set by default")
+    }
+
+    pub fn idle_timeout_in_transaction(&self) -> bool {
+        self.idle_timeout_in_transaction.expect("set by default")
+    }
+
+    pub fn tcp_keepalive(&self) -> f64 {
+        self.tcp_keepalive.expect("set by default")
+    }
 }
 
 /// Format arbitrary byte sequence as a somewhat readable string.
@@ -157,9 +206,16 @@ fn server_start(context: &'static Context) {
             tlog!(Error, "failed to enable TCP_NODELAY on socket: {e:?}");
         }
 
+        if let Some(time) = context.tcp_keepalive {
+            let keepalive = socket2::TcpKeepalive::new().with_time(time);
+            if let Err(e) = socket2::SockRef::from(&raw).set_tcp_keepalive(&keepalive) {
+                tlog!(Error, "failed to enable TCP keepalive on socket: {e:?}");
+            }
+        }
+
         let stream = PgStream::new(raw);
         let tls_acceptor = context.tls_acceptor.clone();
-        if let Err(e) = handle_client(stream, tls_acceptor, context.storage) {
+        if let Err(e) = handle_client(stream, tls_acceptor, context.storage, context.idle_timeout) {
             tlog!(Error, "failed to handle client: {e}");
         }
     }
@@ -171,6 +227,7 @@ fn handle_client(
     client: PgStream<CoIOStream>,
     tls_acceptor: Option<TlsAcceptor>,
     storage: &'static Catalog,
+    idle_timeout: IdleTimeout,
 ) -> tarantool::Result<()> {
     let peer_addr = get_peer_address(client.as_ref())?;
 
@@ -178,7 +235,7 @@ fn handle_client(
         .name(format!("pgproto::client[{peer_addr}]"))
         .func(move || {
             tlog!(Info, "spawned a fiber for postgres client connection");
-            let res = do_handle_client(client, tls_acceptor, storage);
+            let res = do_handle_client(client, tls_acceptor, storage, idle_timeout);
             if let Err(e) = res {
                 tlog!(Error, "connection has {e}");
             }
@@ -193,8 +250,16 @@ fn do_handle_client(
     stream: PgStream<CoIOStream>,
     tls_acceptor: Option<TlsAcceptor>,
     storage: &Catalog,
+    idle_timeout: IdleTimeout,
 ) -> PgResult<()> {
-    let mut client = PgClient::accept(stream, tls_acceptor, storage)?;
+    let authenticator = AUTHENTICATOR.with(|cell| cell.borrow().clone());
+    let mut client = PgClient::accept(
+        stream,
+        tls_acceptor,
+        storage,
+        authenticator.as_deref(),
+        idle_timeout,
+    )?;
 
     // Having two distinct counters lets us have both the number
     // of active connections and the rate at which connections
@@ -223,6 +288,8 @@ struct Context {
     server: CoIOListener,
     tls_acceptor: Option<TlsAcceptor>,
     storage: &'static Catalog,
+    idle_timeout: IdleTimeout,
+    tcp_keepalive: Option<Duration>,
 }
 
 impl Context {
@@ -247,6 +314,21 @@ impl Context {
             .map_err(Error::invalid_configuration)?
             .inspect(|tls| tlog!(Info, "configured {} for pgproto", tls.kind()));
 
+        // Zero disables the corresponding timeout.
+        let seconds = |name: &str, value: f64| {
+            if !value.is_finite() || value < 0. {
+                return Err(Error::invalid_configuration(format!(
+                    "pg.{name} must be a non-negative number of seconds, got {value}"
+                )));
+            }
+            Ok((value > 0.).then(|| Duration::from_secs_f64(value)))
+        };
+        let idle_timeout = IdleTimeout {
+            timeout: seconds("idle_timeout", config.idle_timeout())?,
+            in_transaction: config.idle_timeout_in_transaction(),
+        };
+        let tcp_keepalive = seconds("tcp_keepalive", config.tcp_keepalive())?;
+
         let addr = (host, port);
         tlog!(Info, "starting postgres server at {:?}...", addr);
         let server = server::new_listener(addr).map_err(|err| {
@@ -259,6 +341,8 @@ impl Context {
             server,
             tls_acceptor,
             storage,
+            idle_timeout,
+            tcp_keepalive,
         })
     }
 }
@@ -1,32 +1,58 @@
 use self::{
-    describe::{PortalDescribe, StatementDescribe},
-    result::ExecuteResult,
-    storage::{Portal, Statement, PG_PORTALS, PG_STATEMENTS},
+    copy::CopyToStdout,
+    describe::{CommandTag, PortalDescribe, QueryType, StatementDescribe},
+    result::{ExecuteResult, Rows},
+    storage::{
+        LocalParams, PendingNotification, Portal, RowLocks, Savepoint, Statement, TransactionBlock,
+        PG_APPLICATION_NAMES, PG_LISTENERS, PG_PLAN_CACHE_DISABLED, PG_PORTALS, PG_STATEMENTS,
+        PG_TRANSACTIONS, PG_WARNINGS,
+    },
 };
 use super::{
     client::{ClientId, ClientParams},
-    error::{PgError, PgResult},
+    error::{PedanticError, PgError, PgErrorCode, PgResult},
     value::PgValue,
 };
 use crate::config::DYNAMIC_CONFIG;
-use crate::sql::router::RouterRuntime;
+use crate::rpc::dml_batch::execute_dml_batch;
+use crate::rpc::{pg_notify, row_lock};
+use crate::sql::dispatch::{collect_dml_batch, BatchedDml};
+use crate::sql::port::PicoPortOwned;
+use crate::sql::router::{RouterRuntime, DEFAULT_QUERY_TIMEOUT};
+use crate::sql::row_lock::LockOwner;
 use crate::tlog;
+use crate::traft::node;
 use crate::{
     pgproto::value::{FieldFormat, RawFormat},
     schema::ADMIN_ID,
 };
 use bytes::Bytes;
-use postgres_types::Oid;
-use smol_str::format_smolstr;
+use pgwire::api::results::FieldInfo;
+use pgwire::messages::response::TransactionStatus;
+use postgres_types::{Oid, Type};
+use serde_bytes::ByteBuf;
+use smol_str::{format_smolstr, SmolStr};
+use sql::ir::api::parameter::local_now;
+use sql::ir::ddl::{SetParamScopeType, SetParamValue};
+use sql::ir::node::tcl::Tcl;
+use sql::ir::types::DerivedType;
 use sql::ir::value::Value as SbroadValue;
-use sql::PreparedStatement;
+use sql::ir::{CurrentOf, RowLockMode};
+use sql::{BoundStatement, PreparedStatement};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 use storage::param_oid_to_derived_type;
+use tarantool::error::Error as TarantoolError;
+use tarantool::fiber::{self, FiberId};
+use tarantool::msgpack;
 use tarantool::session::with_su;
+use time::OffsetDateTime;
 
 mod pgproc;
 mod well_known_queries;
 
+pub mod copy;
 pub mod describe;
 pub mod result;
 pub mod storage;
@@ -88,7 +114,7 @@ pub fn bind(
     id: ClientId,
     stmt_name: String,
     portal_name: String,
-    params: Vec<SbroadValue>,
+    mut params: Vec<SbroadValue>,
     result_format: Vec<FieldFormat>,
     client_params: &ClientParams,
 ) -> PgResult<()> {
@@ -112,9 +138,16 @@ pub fn bind(
     };
     let effective_options = client_params.execution_options().unwrap_or(sql_options);
 
-    let bound_statement = statement
-        .prepared_statement()
-        .bind(params, effective_options)?;
+    let resolved;
+    let mut prepared_statement = statement.prepared_statement();
+    if let Some(current_of) = &prepared_statement.as_plan().current_of {
+        resolved = resolve_current_of(id, &statement, current_of, &mut params)?;
+        prepared_statement = &resolved;
+    }
+    let bound_statement = match transaction_start_time(id) {
+        Some(time) => prepared_statement.bind_at(params, effective_options, time)?,
+        None => prepared_statement.bind(params, effective_options)?,
+    };
 
     let portal_key = storage::Key(id, portal_name.into());
     let portal = Portal::new(
@@ -128,6 +161,111 @@ pub fn bind(
     Ok(())
 }
 
+/// Resolve `WHERE CURRENT OF cursor` of the statement into a filter by
+/// the key of the row the cursor is positioned on, appending the key
+/// values to the parameters. The filter includes the sharding key, so
+/// the statement is routed to the bucket of the row.
+fn resolve_current_of(
+    id: ClientId,
+    statement: &Statement,
+    current_of: &CurrentOf,
+    params: &mut Vec<SbroadValue>,
+) -> PgResult<PreparedStatement> {
+    let cursor_name = &current_of.cursor;
+    let cursor_key = storage::Key(id, cursor_name.as_str().into());
+    let cursor: Portal = PG_PORTALS
+        .with(|storage| storage.borrow().get(&cursor_key).cloned())
+        .ok_or_else(|| {
+            PedanticError::new(
+                PgErrorCode::InvalidCursorName,
+                format!("cursor \"{cursor_name}\" does not exist"),
+            )
+        })?;
+
+    let plan = statement.prepared_statement().as_plan();
+    let table = plan.dml_node_table(plan.get_top()?)?;
+    let cursor_statement = cursor.statement();
+    let mut key_positions = table.primary_key.positions.clone();
+    if !table.is_global() {
+        for pos in table.get_sk()? {
+            if !key_positions.contains(pos) {
+                key_positions.push(*pos);
+            }
+        }
+    }
+
+    let cursor_plan = cursor_statement.prepared_statement().as_plan();
+    let Some(row_positions) = cursor_plan.simple_scan_positions(&table.name, &key_positions)?
+    else {
+        return Err(PedanticError::new(
+            PgErrorCode::InvalidCursorState,
+            format!(
+                "cursor \"{cursor_name}\" is not a simply updatable scan of table \"{}\"",
+                table.name
+            ),
+        )
+        .into());
+    };
+    let Some(row) = cursor.current_row() else {
+        return Err(PedanticError::new(
+            PgErrorCode::InvalidCursorState,
+            format!("cursor \"{cursor_name}\" is not positioned on a row"),
+        )
+        .into());
+    };
+
+    let mut param_types: Vec<_> = statement
+        .prepared_statement()
+        .collect_parameter_types()
+        .into_iter()
+        .map(DerivedType::new)
+        .collect();
+    let mut filter = Vec::with_capacity(key_positions.len());
+    for (pos, row_pos) in key_positions.into_iter().zip(row_positions) {
+        let column = &table.columns[pos].name;
+        let Some(idx) = row_pos else {
+            return Err(PedanticError::new(
+                PgErrorCode::InvalidCursorState,
+                format!(
+                    "cursor \"{cursor_name}\" does not return key column \"{column}\" of table \"{}\"",
+                    table.name
+                ),
+            )
+            .into());
+        };
+        let value: SbroadValue = row[idx].clone().try_into()?;
+        param_types.push(value.get_type());
+        params.push(value);
+        let column = column.replace('"', "\"\"");
+        filter.push(format!("\"{column}\" = ${}", params.len()));
+    }
+
+    let query = statement.query();
+    let span = &current_of.span;
+    let query = format!(
+        "{}WHERE {}{}",
+        &query[..span.start],
+        filter.join(" AND "),
+        &query[span.end..]
+    );
+    prepare_statement(id, &query, &param_types)
+}
+
+/// Parse the query, bypassing the plan cache if the client disabled it.
+fn prepare_statement(
+    id: ClientId,
+    query: &str,
+    param_types: &[DerivedType],
+) -> PgResult<PreparedStatement> {
+    let router = RouterRuntime::new();
+    let prepared_statement = if plan_cache_enabled(id) {
+        PreparedStatement::parse(&router, query, param_types)?
+    } else {
+        PreparedStatement::parse_uncached(&router, query, param_types)?
+    };
+    Ok(prepared_statement)
+}
+
 pub fn execute(id: ClientId, name: String, max_rows: i64) -> PgResult<ExecuteResult> {
     let key = storage::Key(id, name.into());
 
@@ -144,16 +282,14 @@ pub fn execute(id: ClientId, name: String, max_rows: i64) -> PgResult<ExecuteRes
 pub fn parse(id: ClientId, name: String, query: &str, param_oids: Vec<Oid>) -> PgResult<()> {
     let key = storage::Key(id, name.into());
 
-    let router = RouterRuntime::new();
-
     let param_types: Vec<_> = param_oids
         .iter()
         .map(|oid| param_oid_to_derived_type(*oid))
         .collect::<Result<_, _>>()?;
 
-    let prepared_statement = PreparedStatement::parse(&router, query, &param_types)?;
+    let prepared_statement = prepare_statement(id, query, &param_types)?;
 
-    let statement = Statement::new(key.clone(), prepared_statement, param_oids)?;
+    let statement = Statement::new(key.clone(), prepared_statement, query, param_oids)?;
     PG_STATEMENTS.with(|storage| storage.borrow_mut().put(key, statement.into()))?;
 
     Ok(())
@@ -207,6 +343,650 @@ pub fn deallocate_statement(id: ClientId, name: &str) -> PgResult<()> {
     Ok(())
 }
 
+/// List the named statements of the client like the `pg_prepared_statements` view.
+/// `columns` are the indexes of the projected view columns.
+pub fn prepared_statements(id: ClientId, columns: &[usize]) -> ExecuteResult {
+    let rows: Vec<_> = PG_STATEMENTS.with(|storage| {
+        storage
+            .borrow()
+            .by_client_id(id)
+            .filter(|(name, _)| !name.is_empty())
+            .map(|(name, holder)| {
+                let row = [name.as_ref(), holder.statement().query()];
+                columns
+                    .iter()
+                    .map(|&i| PgValue::Text(row[i].to_string()))
+                    .collect()
+            })
+            .collect()
+    });
+    let row_desc = columns
+        .iter()
+        .map(|&i| {
+            let name = well_known_queries::PREPARED_STATEMENTS_COLUMNS[i];
+            FieldInfo::new(name.into(), None, None, Type::TEXT, FieldFormat::Text)
+        })
+        .collect();
+    ExecuteResult::FinishedDql {
+        tag: CommandTag::Select,
+        row_count: rows.len(),
+        rows: Rows::new(rows, row_desc),
+    }
+}
+
+pub fn close_client_transaction(id: ClientId) {
+    let block = PG_TRANSACTIONS.with(|storage| storage.borrow_mut().remove(&id));
+    if let Some(row_locks) = block.and_then(|block| block.row_locks) {
+        release_row_locks(row_locks);
+    }
+}
+
+/// Longer `application_name` values are truncated, like in Postgres (`NAMEDATALEN - 1`).
+const APPLICATION_NAME_MAX_LEN: usize = 63;
+
+/// Make `application_name` safe for the logs: control characters are replaced
+/// with `?` and the value is truncated to [`APPLICATION_NAME_MAX_LEN`] bytes.
+fn sanitize_application_name(name: &str) -> SmolStr {
+    let mut sanitized = String::with_capacity(name.len().min(APPLICATION_NAME_MAX_LEN));
+    for c in name.chars() {
+        let c = if c.is_control() { '?' } else { c };
+        if sanitized.len() + c.len_utf8() > APPLICATION_NAME_MAX_LEN {
+            break;
+        }
+        sanitized.push(c);
+    }
+    sanitized.into()
+}
+
+/// Remember `application_name` of the client, an empty name resets it.
+pub fn set_application_name(id: ClientId, name: &str) {
+    let name = sanitize_application_name(name);
+    PG_APPLICATION_NAMES.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        if name.is_empty() {
+            storage.remove(&id);
+        } else {
+            storage.insert(id, name);
+        }
+    });
+}
+
+/// Get `application_name` of the client, if it was provided.
+pub fn application_name(id: ClientId) -> Option<SmolStr> {
+    PG_APPLICATION_NAMES.with(|storage| storage.borrow().get(&id).cloned())
+}
+
+/// Enable or disable the query plan cache for the statements of the client.
+pub fn set_plan_cache(id: ClientId, enabled: bool) {
+    PG_PLAN_CACHE_DISABLED.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        if enabled {
+            storage.remove(&id);
+        } else {
+            storage.insert(id);
+        }
+    });
+}
+
+/// Check whether the statements of the client use the query plan cache.
+pub fn plan_cache_enabled(id: ClientId) -> bool {
+    PG_PLAN_CACHE_DISABLED.with(|storage| !storage.borrow().contains(&id))
+}
+
+/// Apply `SET` of a parameter kept in the client session.
+///
+/// Like in Postgres, the value set with `SET LOCAL` is restored when the
+/// transaction block ends, see [`restore_local_params`], and the command has
+/// no effect outside of a transaction block. `SET` in the block overrides an
+/// earlier `SET LOCAL` of the parameter, so its value is kept.
+pub fn set_param(id: ClientId, scope_type: &SetParamScopeType, value: &SetParamValue) {
+    let in_block = PG_TRANSACTIONS.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        let Some(block) = storage.get_mut(&id) else {
+            return false;
+        };
+        let saved = &mut block.local_params;
+        match (scope_type, value) {
+            (SetParamScopeType::Local, SetParamValue::ApplicationName { .. }) => {
+                saved
+                    .application_name
+                    .get_or_insert_with(|| application_name(id));
+            }
+            (SetParamScopeType::Local, SetParamValue::PlanCache { .. }) => {
+                saved
+                    .plan_cache
+                    .get_or_insert_with(|| plan_cache_enabled(id));
+            }
+            (SetParamScopeType::Session, SetParamValue::ApplicationName { .. }) => {
+                saved.application_name = None;
+            }
+            (SetParamScopeType::Session, SetParamValue::PlanCache { .. }) => {
+                saved.plan_cache = None;
+            }
+            _ => {}
+        }
+        true
+    });
+    if *scope_type == SetParamScopeType::Local && !in_block {
+        tlog!(Warning, "SET LOCAL can only be used in transaction blocks");
+        return;
+    }
+    match value {
+        SetParamValue::ApplicationName { value } => set_application_name(id, value),
+        SetParamValue::PlanCache { enabled } => set_plan_cache(id, *enabled),
+        _ => {}
+    }
+}
+
+/// Restore the parameters changed with `SET LOCAL` in the ended transaction block.
+fn restore_local_params(id: ClientId, saved: LocalParams) {
+    if let Some(name) = saved.application_name {
+        set_application_name(id, name.as_deref().unwrap_or_default());
+    }
+    if let Some(enabled) = saved.plan_cache {
+        set_plan_cache(id, enabled);
+    }
+}
+
+/// Maximum number of notifications kept for a busy client, the oldest ones
+/// are dropped once it's exceeded.
+const MAX_PENDING_NOTIFICATIONS: usize = 10_000;
+
+/// Start listening to the notification channel.
+pub fn listen(id: ClientId, channel: &str) {
+    PG_LISTENERS.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        let listener = storage.entry(id).or_default();
+        listener.channels.insert(channel.into());
+    });
+}
+
+/// Stop listening to the notification channel, `None` stops listening to
+/// all the channels. Pending notifications of the channel are discarded.
+pub fn unlisten(id: ClientId, channel: Option<&str>) {
+    PG_LISTENERS.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        let Some(listener) = storage.get_mut(&id) else {
+            return;
+        };
+        match channel {
+            Some(channel) => {
+                listener.channels.remove(channel);
+            }
+            None => listener.channels.clear(),
+        }
+        if listener.channels.is_empty() {
+            storage.remove(&id);
+        } else {
+            let channels = &listener.channels;
+            listener
+                .pending
+                .retain(|notification| channels.contains(&notification.channel));
+        }
+    });
+}
+
+/// Check whether the client listens to any notification channel.
+pub fn is_listening(id: ClientId) -> bool {
+    PG_LISTENERS.with(|storage| storage.borrow().contains_key(&id))
+}
+
+/// Queue the notification for every client listening to its channel.
+pub fn deliver_notification(channel: &str, payload: &str) {
+    PG_LISTENERS.with(|storage| {
+        for (id, listener) in storage.borrow_mut().iter_mut() {
+            if !listener.channels.contains(channel) {
+                continue;
+            }
+            if listener.pending.len() >= MAX_PENDING_NOTIFICATIONS {
+                tlog!(
+                    Warning,
+                    "too many pending notifications for client {id}, dropping the oldest one"
+                );
+                listener.pending.pop_front();
+            }
+            listener.pending.push_back(PendingNotification {
+                channel: channel.into(),
+                payload: payload.into(),
+            });
+            if let Some(waiter) = listener.waiter {
+                fiber::wakeup(waiter);
+            }
+        }
+    });
+}
+
+/// Set the fiber to wake up when a notification for the client arrives,
+/// `None` stops waking it up.
+pub fn set_notification_waiter(id: ClientId, waiter: Option<FiberId>) {
+    PG_LISTENERS.with(|storage| {
+        if let Some(listener) = storage.borrow_mut().get_mut(&id) {
+            listener.waiter = waiter;
+        }
+    });
+}
+
+/// Take the notifications which haven't been sent to the client yet.
+pub fn take_notifications(id: ClientId) -> Vec<PendingNotification> {
+    PG_LISTENERS.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        storage
+            .get_mut(&id)
+            .map(|listener| listener.pending.drain(..).collect())
+            .unwrap_or_default()
+    })
+}
+
+/// Remember the warnings about a query result to send them to the client.
+pub fn add_warnings(id: ClientId, warnings: Vec<String>) {
+    if warnings.is_empty() {
+        return;
+    }
+    PG_WARNINGS.with(|storage| storage.borrow_mut().entry(id).or_default().extend(warnings));
+}
+
+/// Take the warnings which haven't been sent to the client yet.
+pub fn take_warnings(id: ClientId) -> Vec<String> {
+    PG_WARNINGS.with(|storage| storage.borrow_mut().remove(&id).unwrap_or_default())
+}
+
+/// Check whether the client is inside a transaction block started with `BEGIN`.
+pub fn in_transaction_block(id: ClientId) -> bool {
+    PG_TRANSACTIONS.with(|storage| storage.borrow().contains_key(&id))
+}
+
+/// Check whether a command has failed in the transaction block of the client.
+pub fn in_failed_transaction_block(id: ClientId) -> bool {
+    PG_TRANSACTIONS.with(|storage| storage.borrow().get(&id).is_some_and(|block| block.failed))
+}
+
+/// Status of the client transaction reported in `ReadyForQuery`.
+pub fn transaction_status(id: ClientId) -> TransactionStatus {
+    PG_TRANSACTIONS.with(|storage| match storage.borrow().get(&id) {
+        None => TransactionStatus::Idle,
+        Some(block) if block.failed => TransactionStatus::Error,
+        Some(_) => TransactionStatus::Transaction,
+    })
+}
+
+/// Mark the transaction block of the client, if any, as failed.
+pub fn fail_transaction(id: ClientId) {
+    PG_TRANSACTIONS.with(|storage| {
+        if let Some(block) = storage.borrow_mut().get_mut(&id) {
+            block.failed = true;
+        }
+    });
+}
+
+/// Timeout of executing the modifications of a transaction block on the storage.
+const DML_BATCH_TIMEOUT: Duration = Duration::from_secs(DEFAULT_QUERY_TIMEOUT);
+
+/// Execute a modification in the transaction block of the client.
+///
+/// The modification is prepared for the storages, but nothing is changed
+/// until `COMMIT`: the statement alone is executed in a storage transaction
+/// which is rolled back, so it reports its errors and the number of the
+/// changed rows right away. `COMMIT` executes all the modifications of the
+/// block in a single storage transaction, that's why they all must be applied
+/// on the same replicaset. Transactions spanning several replicasets are not
+/// supported.
+pub fn execute_in_transaction_block(
+    router: &RouterRuntime,
+    id: ClientId,
+    statement: BoundStatement,
+) -> PgResult<u64> {
+    let mut port = PicoPortOwned::new();
+    let (result, batch) = collect_dml_batch(|| {
+        crate::sql::dispatch_bound_statement(router, statement, None, None, &mut port)
+    });
+    result?;
+
+    let Some(first) = batch.first() else {
+        return Ok(0);
+    };
+    let replicaset = PG_TRANSACTIONS
+        .with(|storage| {
+            let storage = storage.borrow();
+            let block = storage.get(&id)?;
+            block.pending.first().map(|dml| dml.replicaset.clone())
+        })
+        .unwrap_or_else(|| first.replicaset.clone());
+    if batch.iter().any(|dml| dml.replicaset != replicaset) {
+        return Err(PgError::FeatureNotSupported(
+            "modifications of a transaction block must be applied on a single replicaset".into(),
+        ));
+    }
+
+    let messages = batch.iter().map(dml_message).collect();
+    let row_counts = execute_dml_batch(&replicaset, messages, true, DML_BATCH_TIMEOUT)?;
+    let row_count = row_counts.iter().sum();
+
+    PG_TRANSACTIONS.with(|storage| {
+        if let Some(block) = storage.borrow_mut().get_mut(&id) {
+            block.pending.extend(batch);
+        }
+    });
+    Ok(row_count)
+}
+
+/// Timeout of waiting for the rows locked by other transaction blocks.
+const ROW_LOCK_TIMEOUT: Duration = Duration::from_secs(DEFAULT_QUERY_TIMEOUT);
+
+/// Lock the rows selected with `FOR UPDATE` or `FOR SHARE` in the transaction
+/// block of the client until the block ends.
+///
+/// The primary keys of the selected rows are queried first, then the rows are
+/// locked on the masters of their replicasets. Only the rows of a single sharded
+/// table read without joins, subqueries, `ORDER BY` and `LIMIT` can be located
+/// this way.
+pub fn lock_rows_in_transaction_block(
+    router: &RouterRuntime,
+    id: ClientId,
+    statement: &BoundStatement,
+    mode: RowLockMode,
+) -> PgResult<()> {
+    let Some((keys_statement, table)) = statement.row_keys()? else {
+        return Err(PgError::FeatureNotSupported(
+            "FOR UPDATE and FOR SHARE in transaction blocks are supported only \
+             for the queries reading a single sharded table without joins, \
+             subqueries, ORDER BY and LIMIT"
+                .into(),
+        ));
+    };
+    let mut port = PicoPortOwned::new();
+    crate::sql::dispatch_bound_statement(router, keys_statement, None, None, &mut port)?;
+
+    let node = node::global()?;
+    let tier = match &table.tier {
+        Some(tier) => tier.as_str(),
+        None => node.topology_cache.my_tier_name(),
+    };
+    let mut keys_by_replicaset: HashMap<SmolStr, Vec<ByteBuf>> = HashMap::new();
+    // The first msgpack in DQL response is metadata, the remaining ones are tuples.
+    for tuple in port.port_c().iter().skip(1) {
+        let mut row: Vec<SbroadValue> = msgpack::decode(tuple).map_err(TarantoolError::from)?;
+        // The bucket id follows the primary key columns.
+        let Some(SbroadValue::Integer(bucket_id)) = row.pop() else {
+            let message = "bucket_id is missing in the row keys";
+            return Err(PedanticError::new(PgErrorCode::InternalError, message).into());
+        };
+        let replicaset = crate::vshard::get_replicaset_uuid_by_bucket_id(tier, bucket_id as u64)?;
+        let key = ByteBuf::from(msgpack::encode(&row));
+        keys_by_replicaset.entry(replicaset).or_default().push(key);
+    }
+
+    let owner = PG_TRANSACTIONS.with(|storage| {
+        let storage = storage.borrow();
+        let row_locks = storage.get(&id)?.row_locks.as_ref()?;
+        Some(row_locks.owner.clone())
+    });
+    let owner = owner.unwrap_or_else(|| LockOwner {
+        instance: node.topology_cache.my_instance_name().into(),
+        incarnation: node.topology_cache.my_current_state().incarnation,
+        client_id: id,
+    });
+    for (replicaset, keys) in keys_by_replicaset {
+        let master = node
+            .topology_cache
+            .clone_replicaset_by_uuid(&replicaset)?
+            .current_master_name;
+        // The master is remembered before the call, so that the rows
+        // locked by it are released even if the call fails.
+        PG_TRANSACTIONS.with(|storage| {
+            let mut storage = storage.borrow_mut();
+            let Some(block) = storage.get_mut(&id) else {
+                return;
+            };
+            let row_locks = block.row_locks.get_or_insert_with(|| RowLocks {
+                owner: owner.clone(),
+                holders: vec![],
+            });
+            if !row_locks.holders.contains(&master) {
+                row_locks.holders.push(master.clone());
+            }
+        });
+        let exclusive = mode == RowLockMode::Update;
+        let locked = row_lock::lock_rows(
+            &master,
+            owner.clone(),
+            table.id,
+            keys,
+            exclusive,
+            ROW_LOCK_TIMEOUT,
+        )?;
+        if !locked {
+            return Err(PedanticError::new(
+                PgErrorCode::LockNotAvailable,
+                format!(
+                    "could not obtain lock on row in relation \"{}\"",
+                    table.name
+                ),
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Release the row locks of a transaction block which has ended.
+///
+/// The locks are released on a best effort basis: the locks of a master
+/// which can't be reached stay until the router instance is restarted.
+fn release_row_locks(row_locks: RowLocks) {
+    let RowLocks { owner, holders } = row_locks;
+    for holder in holders {
+        if let Err(e) = row_lock::unlock_rows(&holder, owner.clone(), ROW_LOCK_TIMEOUT) {
+            tlog!(Warning, "failed to release row locks on {holder}: {e}");
+        }
+    }
+}
+
+/// Timeout of delivering the notifications of a transaction block.
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(DEFAULT_QUERY_TIMEOUT);
+
+/// Queue the notification sent in the transaction block of the client.
+/// Like in Postgres, the duplicates within a transaction are sent once.
+pub fn queue_notification(id: ClientId, channel: &str, payload: &str) -> PgResult<()> {
+    pg_notify::check_payload(payload)?;
+    PG_TRANSACTIONS.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        let Some(block) = storage.get_mut(&id) else {
+            return;
+        };
+        let notification = PendingNotification {
+            channel: channel.into(),
+            payload: payload.into(),
+        };
+        if !block.notifications.contains(&notification) {
+            block.notifications.push(notification);
+        }
+    });
+    Ok(())
+}
+
+/// Changes of a transaction block to apply on `COMMIT` and the row locks
+/// to release once the block ends.
+#[derive(Debug, Default)]
+pub struct CommittedBlock {
+    pub modifications: Vec<BatchedDml>,
+    pub notifications: Vec<PendingNotification>,
+    pub row_locks: Option<RowLocks>,
+}
+
+/// Commit the modifications of a transaction block returned by `COMMIT`.
+/// The notifications of the block are sent once the modifications are committed.
+/// The row locks of the block are released after the modifications either way.
+pub fn commit_transaction(committed: CommittedBlock) -> PgResult<()> {
+    let CommittedBlock {
+        modifications,
+        notifications,
+        row_locks,
+    } = committed;
+    let result = match modifications.first() {
+        Some(first) => {
+            let messages = modifications.iter().map(dml_message).collect();
+            execute_dml_batch(&first.replicaset, messages, false, DML_BATCH_TIMEOUT).map(|_| ())
+        }
+        None => Ok(()),
+    };
+    if let Some(row_locks) = row_locks {
+        release_row_locks(row_locks);
+    }
+    result?;
+    let reqs = notifications
+        .into_iter()
+        .map(|notification| pg_notify::Request {
+            channel: notification.channel,
+            payload: notification.payload,
+        })
+        .collect();
+    pg_notify::broadcast_notifications(reqs, NOTIFY_TIMEOUT);
+    Ok(())
+}
+
+fn dml_message(dml: &BatchedDml) -> ByteBuf {
+    ByteBuf::from(dml.message.data().to_vec())
+}
+
+/// Remember that a query was executed in the transaction block of the client, if any.
+pub fn mark_transaction_query(id: ClientId) {
+    PG_TRANSACTIONS.with(|storage| {
+        if let Some(block) = storage.borrow_mut().get_mut(&id) {
+            block.has_queries = true;
+        }
+    });
+}
+
+/// Start time of the transaction block of the client, if any.
+fn transaction_start_time(id: ClientId) -> Option<OffsetDateTime> {
+    PG_TRANSACTIONS.with(|storage| storage.borrow().get(&id).and_then(|block| block.started_at))
+}
+
+/// Check `SET TRANSACTION ISOLATION LEVEL` against the transaction block of the client.
+///
+/// Every statement runs with READ COMMITTED isolation and the router rejects
+/// stronger levels, so the level itself is not kept. Like in Postgres, the level
+/// can't be changed after the first query of the transaction, and the command has
+/// no effect outside of a transaction block.
+pub fn check_transaction_isolation(id: ClientId) -> PgResult<()> {
+    PG_TRANSACTIONS.with(|storage| {
+        let storage = storage.borrow();
+        let Some(block) = storage.get(&id) else {
+            tlog!(
+                Warning,
+                "SET TRANSACTION can only be used in transaction blocks"
+            );
+            return Ok(());
+        };
+        if block.has_queries {
+            return Err(PedanticError::new(
+                PgErrorCode::ActiveSqlTransaction,
+                "SET TRANSACTION ISOLATION LEVEL must be called before any query",
+            )
+            .into());
+        }
+        Ok(())
+    })
+}
+
+/// Track the transaction block and the savepoints of the client.
+///
+/// Modifications and notifications of a transaction block are not applied
+/// until `COMMIT`, so `ROLLBACK` and `ROLLBACK TO SAVEPOINT` just discard them.
+/// The parameters set with `SET LOCAL` are restored on `COMMIT` and `ROLLBACK`.
+/// On `COMMIT` they are returned to be applied with [`commit_transaction`],
+/// which also releases the row locks returned on `COMMIT` and `ROLLBACK`.
+pub fn transaction_control(id: ClientId, tcl: &Tcl) -> PgResult<CommittedBlock> {
+    fn no_transaction(command: &str) -> PedanticError {
+        PedanticError::new(
+            PgErrorCode::NoActiveSqlTransaction,
+            format!("{command} can only be used in transaction blocks"),
+        )
+    }
+
+    fn savepoint_position(savepoints: &[Savepoint], name: &str) -> PgResult<usize> {
+        // Savepoints with the same name are allowed, the latest one is used.
+        let position = savepoints
+            .iter()
+            .rposition(|savepoint| savepoint.name == name);
+        let position = position.ok_or_else(|| {
+            PedanticError::new(
+                PgErrorCode::InvalidSavepointSpecification,
+                format!("savepoint \"{name}\" does not exist"),
+            )
+        })?;
+        Ok(position)
+    }
+
+    PG_TRANSACTIONS.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        match tcl {
+            Tcl::Begin => {
+                storage.entry(id).or_insert_with(|| TransactionBlock {
+                    started_at: Some(local_now()),
+                    ..Default::default()
+                });
+            }
+            Tcl::Commit => {
+                if let Some(block) = storage.remove(&id) {
+                    restore_local_params(id, block.local_params);
+                    if !block.failed {
+                        return Ok(CommittedBlock {
+                            modifications: block.pending,
+                            notifications: block.notifications,
+                            row_locks: block.row_locks,
+                        });
+                    }
+                    return Ok(CommittedBlock {
+                        row_locks: block.row_locks,
+                        ..Default::default()
+                    });
+                }
+            }
+            Tcl::Rollback => {
+                if let Some(block) = storage.remove(&id) {
+                    restore_local_params(id, block.local_params);
+                    return Ok(CommittedBlock {
+                        row_locks: block.row_locks,
+                        ..Default::default()
+                    });
+                }
+            }
+            Tcl::Savepoint(name) => {
+                let block = storage
+                    .get_mut(&id)
+                    .ok_or_else(|| no_transaction("SAVEPOINT"))?;
+                block.savepoints.push(Savepoint {
+                    name: name.clone(),
+                    pending_len: block.pending.len(),
+                    notifications_len: block.notifications.len(),
+                });
+            }
+            Tcl::Release(name) => {
+                let block = storage
+                    .get_mut(&id)
+                    .ok_or_else(|| no_transaction("RELEASE SAVEPOINT"))?;
+                // Releasing a savepoint also releases all the later ones.
+                let position = savepoint_position(&block.savepoints, name)?;
+                block.savepoints.truncate(position);
+            }
+            Tcl::RollbackTo(name) => {
+                let block = storage
+                    .get_mut(&id)
+                    .ok_or_else(|| no_transaction("ROLLBACK TO SAVEPOINT"))?;
+                // The savepoint itself remains valid after the rollback.
+                let position = savepoint_position(&block.savepoints, name)?;
+                let savepoint = &block.savepoints[position];
+                block.pending.truncate(savepoint.pending_len);
+                block.notifications.truncate(savepoint.notifications_len);
+                block.savepoints.truncate(position + 1);
+                block.failed = false;
+            }
+        }
+        Ok(CommittedBlock::default())
+    })
+}
+
 /// Each postgres client uses its own backend to handle incoming messages.
 pub struct Backend {
     /// A unique identificator of a postgres client. It is used as a part of a key in the portal
@@ -224,10 +1004,9 @@ impl Backend {
             ID_COUNTER.fetch_add(1, Ordering::Relaxed)
         }
 
-        Self {
-            client_id: unique_id(),
-            params,
-        }
+        let client_id = unique_id();
+        set_application_name(client_id, &params.application_name);
+        Self { client_id, params }
     }
 
     pub fn client_id(&self) -> ClientId {
@@ -242,6 +1021,10 @@ impl Backend {
     ///
     /// Note that it closes the unnamed portal and statement even in case of a failure.
     pub fn simple_query(&self, sql: &str) -> PgResult<ExecuteResult> {
+        if let Some(copy) = copy::parse(sql) {
+            return self.copy_to_stdout(copy?);
+        }
+
         let do_simple_query = || {
             let close_unnamed = || {
                 self.close_statement(None);
@@ -265,6 +1048,18 @@ impl Backend {
             Ok(_) => false,
         };
 
+        // Prepared statements are stored by the backend, so the view listing
+        // them is answered without the SQL engine that doesn't know it.
+        let table_not_found = match &result {
+            Err(e) => e.is_sbroad_table_not_found_error(),
+            Ok(_) => false,
+        };
+        if table_not_found {
+            if let Some(columns) = well_known_queries::parse_list_of_prepared_statements(sql) {
+                return Ok(prepared_statements(self.client_id, &columns));
+            }
+        }
+
         if parsing_failed {
             // In case of parsing error, we can try to parse and adjust some well known queries
             // from PostgreSQL that our SQL doesn't support. Recognizing these common queries
@@ -286,6 +1081,48 @@ impl Backend {
         result
     }
 
+    /// Execute the query of `COPY (query) TO STDOUT` like a simple query.
+    /// Its rows are returned to be sent as `CopyData` messages, the first
+    /// batch of them, the next ones are fetched with [`Self::copy_batch`].
+    fn copy_to_stdout(&self, copy: CopyToStdout) -> PgResult<ExecuteResult> {
+        let close_unnamed = || {
+            self.close_statement(None);
+            self.close_portal(None);
+        };
+
+        let CopyToStdout { query, encoder } = copy;
+        let do_copy = || {
+            close_unnamed();
+            self.parse(None, &query, vec![])?;
+            // Check the query before the execution, so that it doesn't modify anything.
+            if !matches!(self.describe_statement(None)?.query_type(), QueryType::Dql) {
+                return Err(PgError::FeatureNotSupported(format_smolstr!(
+                    "COPY supports only SELECT queries"
+                )));
+            }
+            self.bind(None, None, vec![], &[], &[FieldFormat::Text as RawFormat])?;
+            let (rows, is_finished) = self.copy_batch()?;
+            Ok(ExecuteResult::CopyOut {
+                tag: CommandTag::Copy,
+                rows,
+                encoder,
+                is_finished,
+            })
+        };
+
+        do_copy().inspect_err(|_| close_unnamed())
+    }
+
+    /// Fetch the next batch of rows of `COPY (query) TO STDOUT`.
+    /// Returns the rows and whether they are the last ones.
+    pub fn copy_batch(&self) -> PgResult<(Rows, bool)> {
+        match self.execute(None, copy::COPY_BATCH_ROWS)? {
+            ExecuteResult::SuspendedDql { rows } => Ok((rows, false)),
+            ExecuteResult::FinishedDql { rows, .. } => Ok((rows, true)),
+            _ => unreachable!("COPY executes only DQL"),
+        }
+    }
+
     /// This function is similar to `simple_query`, but the query can be parameterized.
     fn execute_query(&self, sql: &str, params: Vec<SbroadValue>) -> PgResult<ExecuteResult> {
         let close_unnamed = || {
@@ -394,6 +1231,41 @@ impl Backend {
         close_statement(self.client_id, name)
     }
 
+    /// Status of the client transaction reported in `ReadyForQuery`.
+    pub fn transaction_status(&self) -> TransactionStatus {
+        transaction_status(self.client_id)
+    }
+
+    /// Check whether the client is inside a transaction block.
+    pub fn in_transaction_block(&self) -> bool {
+        in_transaction_block(self.client_id)
+    }
+
+    /// Mark the client transaction block, if any, as failed after an error.
+    pub fn fail_transaction(&self) {
+        fail_transaction(self.client_id)
+    }
+
+    /// Check whether the client listens to any notification channel.
+    pub fn is_listening(&self) -> bool {
+        is_listening(self.client_id)
+    }
+
+    /// Take the notifications which haven't been sent to the client yet.
+    pub fn take_notifications(&self) -> Vec<PendingNotification> {
+        take_notifications(self.client_id)
+    }
+
+    /// Set the fiber to wake up when a notification for the client arrives.
+    pub fn set_notification_waiter(&self, waiter: Option<FiberId>) {
+        set_notification_waiter(self.client_id, waiter)
+    }
+
+    /// Take the warnings which haven't been sent to the client yet.
+    pub fn take_warnings(&self) -> Vec<String> {
+        take_warnings(self.client_id)
+    }
+
     /// Close all the client's portals. It should be called at the end of the transaction.
     pub fn close_all_portals(&self) {
         close_client_portals(self.client_id)
@@ -402,6 +1274,11 @@ impl Backend {
     fn on_disconnect(&self) {
         close_client_statements(self.client_id);
         close_client_portals(self.client_id);
+        close_client_transaction(self.client_id);
+        set_application_name(self.client_id, "");
+        set_plan_cache(self.client_id, true);
+        unlisten(self.client_id, None);
+        take_warnings(self.client_id);
     }
 
     pub fn params(&self) -> &ClientParams {
@@ -414,3 +1291,164 @@ impl Drop for Backend {
         self.on_disconnect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[::tarantool::test]
+    fn savepoints() {
+        let id = ClientId::MAX;
+        let savepoint = |name: &str| Tcl::Savepoint(name.into());
+        let release = |name: &str| Tcl::Release(name.into());
+        let rollback_to = |name: &str| Tcl::RollbackTo(name.into());
+
+        let e = transaction_control(id, &savepoint("a")).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "SAVEPOINT can only be used in transaction blocks"
+        );
+
+        transaction_control(id, &Tcl::Begin).unwrap();
+        transaction_control(id, &savepoint("a")).unwrap();
+        transaction_control(id, &savepoint("b")).unwrap();
+        transaction_control(id, &rollback_to("a")).unwrap();
+        // Rolling back to "a" destroys the later savepoints.
+        let e = transaction_control(id, &rollback_to("b")).unwrap_err();
+        assert_eq!(e.to_string(), r#"savepoint "b" does not exist"#);
+        // But "a" itself is still there.
+        transaction_control(id, &rollback_to("a")).unwrap();
+
+        transaction_control(id, &release("a")).unwrap();
+        let e = transaction_control(id, &rollback_to("a")).unwrap_err();
+        assert_eq!(e.to_string(), r#"savepoint "a" does not exist"#);
+
+        transaction_control(id, &savepoint("c")).unwrap();
+        transaction_control(id, &Tcl::Commit).unwrap();
+        let e = transaction_control(id, &release("c")).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "RELEASE SAVEPOINT can only be used in transaction blocks"
+        );
+
+        close_client_transaction(id);
+    }
+
+    #[::tarantool::test]
+    fn application_name_is_sanitized() {
+        let id = ClientId::MAX;
+        assert_eq!(application_name(id), None);
+
+        set_application_name(id, "psql");
+        assert_eq!(application_name(id).as_deref(), Some("psql"));
+
+        set_application_name(id, "etl\n2026-01-01 I> fake log line\x07");
+        assert_eq!(
+            application_name(id).as_deref(),
+            Some("etl?2026-01-01 I> fake log line?")
+        );
+
+        set_application_name(id, &"ы".repeat(APPLICATION_NAME_MAX_LEN));
+        let name = application_name(id).unwrap();
+        assert_eq!(name.len(), APPLICATION_NAME_MAX_LEN - 1);
+        assert!(name.chars().all(|c| c == 'ы'));
+
+        set_application_name(id, "");
+        assert_eq!(application_name(id), None);
+    }
+
+    #[::tarantool::test]
+    fn transaction_block() {
+        let id = ClientId::MAX;
+        assert!(!in_transaction_block(id));
+
+        transaction_control(id, &Tcl::Begin).unwrap();
+        assert!(in_transaction_block(id));
+        transaction_control(id, &Tcl::Commit).unwrap();
+        assert!(!in_transaction_block(id));
+
+        transaction_control(id, &Tcl::Begin).unwrap();
+        transaction_control(id, &Tcl::Rollback).unwrap();
+        assert!(!in_transaction_block(id));
+
+        transaction_control(id, &Tcl::Begin).unwrap();
+        close_client_transaction(id);
+        assert!(!in_transaction_block(id));
+    }
+
+    #[::tarantool::test]
+    fn transaction_block_status() {
+        let id = ClientId::MAX;
+        let status = || transaction_status(id);
+
+        // Errors outside of a transaction block don't affect the status.
+        fail_transaction(id);
+        assert!(matches!(status(), TransactionStatus::Idle));
+
+        transaction_control(id, &Tcl::Begin).unwrap();
+        transaction_control(id, &Tcl::Savepoint("a".into())).unwrap();
+        assert!(matches!(status(), TransactionStatus::Transaction));
+        fail_transaction(id);
+        assert!(matches!(status(), TransactionStatus::Error));
+        assert!(in_failed_transaction_block(id));
+
+        // Rolling back to a savepoint recovers the transaction.
+        transaction_control(id, &Tcl::RollbackTo("a".into())).unwrap();
+        assert!(matches!(status(), TransactionStatus::Transaction));
+
+        // Nothing is committed from a failed transaction.
+        fail_transaction(id);
+        let committed = transaction_control(id, &Tcl::Commit).unwrap();
+        assert!(committed.modifications.is_empty());
+        assert!(matches!(status(), TransactionStatus::Idle));
+    }
+
+    #[::tarantool::test]
+    fn transaction_notifications() {
+        let id = ClientId::MAX;
+        let notification = |payload: &str| PendingNotification {
+            channel: "events".into(),
+            payload: payload.into(),
+        };
+
+        transaction_control(id, &Tcl::Begin).unwrap();
+        queue_notification(id, "events", "a").unwrap();
+        queue_notification(id, "events", "a").unwrap();
+        transaction_control(id, &Tcl::Savepoint("s".into())).unwrap();
+        queue_notification(id, "events", "b").unwrap();
+        transaction_control(id, &Tcl::RollbackTo("s".into())).unwrap();
+        queue_notification(id, "events", "c").unwrap();
+
+        let committed = transaction_control(id, &Tcl::Commit).unwrap();
+        assert_eq!(
+            committed.notifications,
+            vec![notification("a"), notification("c")]
+        );
+    }
+
+    #[::tarantool::test]
+    fn transaction_isolation() {
+        let id = ClientId::MAX;
+
+        // No effect outside of a transaction block.
+        check_transaction_isolation(id).unwrap();
+        assert!(!in_transaction_block(id));
+
+        transaction_control(id, &Tcl::Begin).unwrap();
+        check_transaction_isolation(id).unwrap();
+
+        mark_transaction_query(id);
+        let e = check_transaction_isolation(id).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "SET TRANSACTION ISOLATION LEVEL must be called before any query"
+        );
+
+        // A new transaction starts with a fresh state.
+        transaction_control(id, &Tcl::Commit).unwrap();
+        transaction_control(id, &Tcl::Begin).unwrap();
+        check_transaction_isolation(id).unwrap();
+
+        close_client_transaction(id);
+    }
+}
@@ -0,0 +1,333 @@
+//! `COPY (query) TO STDOUT` support.
+//!
+//! The SQL engine doesn't know `COPY`, so the statement is recognized by the
+//! backend: the query in parentheses is executed as usual and its rows are
+//! sent to the client as `CopyData` messages, a line per row, in the text or
+//! CSV format. The rows are fetched and flushed in batches of
+//! [`COPY_BATCH_ROWS`].
+
+use super::result::Rows;
+use crate::pgproto::error::{
+    DynError, EncodingError, PedanticError, PgError, PgErrorCode, PgResult,
+};
+use bytes::{BufMut, Bytes, BytesMut};
+use pgwire::types::{format::FormatOptions, ToSqlText};
+use postgres_types::IsNull;
+use smol_str::{format_smolstr, SmolStr};
+use sqlparser::ast::{CopyLegacyCsvOption, CopyLegacyOption, CopyOption, CopyTarget, Statement};
+use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::parser::Parser;
+
+/// Number of rows fetched from the portal and sent to the client at once.
+pub const COPY_BATCH_ROWS: i64 = 1000;
+
+/// Parsed `COPY (query) TO STDOUT` statement.
+#[derive(Debug)]
+pub struct CopyToStdout {
+    /// The query which rows are exported.
+    pub query: String,
+    pub encoder: CopyEncoder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CopyFormat {
+    Text,
+    Csv,
+}
+
+/// Encoder of the rows into the lines of the `COPY` output.
+#[derive(Debug)]
+pub struct CopyEncoder {
+    format: CopyFormat,
+    header: bool,
+    delimiter: u8,
+    null: String,
+}
+
+fn syntax_error(e: impl Into<Box<DynError>>) -> PgError {
+    PedanticError::new(PgErrorCode::SyntaxError, e).into()
+}
+
+fn unsupported(what: impl Into<SmolStr>) -> PgError {
+    PgError::FeatureNotSupported(what.into())
+}
+
+/// Recognize `COPY (query) TO STDOUT [[WITH] options]`.
+///
+/// Returns `None` if the statement is not `COPY`, such statements are
+/// executed by the SQL engine as usual.
+pub fn parse(sql: &str) -> Option<PgResult<CopyToStdout>> {
+    let sql = sql.trim_start();
+    let prefix = sql.get(..4)?;
+    let rest = &sql[4..];
+    if !prefix.eq_ignore_ascii_case("copy") || rest.starts_with(|c: char| !is_separator(c)) {
+        return None;
+    }
+    Some(parse_copy(rest))
+}
+
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || c == '('
+}
+
+fn parse_copy(rest: &str) -> PgResult<CopyToStdout> {
+    let Some(rest) = rest.trim_start().strip_prefix('(') else {
+        return Err(unsupported("only COPY (query) TO STDOUT is supported"));
+    };
+    let end = closing_parenthesis(rest).ok_or_else(|| syntax_error("unterminated COPY query"))?;
+    let query = rest[..end].trim().to_string();
+
+    // The query may use the syntax only the SQL engine knows,
+    // so the options are parsed with a stub query in its place.
+    let stub = format!("COPY (SELECT 1) {}", &rest[end + 1..]);
+    let statements = Parser::parse_sql(&PostgreSqlDialect {}, &stub)
+        .map_err(|e| syntax_error(format!("failed to parse COPY: {e}")))?;
+    let [Statement::Copy {
+        to,
+        target,
+        options,
+        legacy_options,
+        ..
+    }] = statements.as_slice()
+    else {
+        return Err(syntax_error("expected a single COPY statement"));
+    };
+    if !*to || !matches!(target, CopyTarget::Stdout) {
+        return Err(unsupported("only COPY (query) TO STDOUT is supported"));
+    }
+
+    let encoder = CopyEncoder::new(options, legacy_options)?;
+    Ok(CopyToStdout { query, encoder })
+}
+
+/// Position of the parenthesis closing the query, quoted strings and
+/// identifiers are skipped.
+fn closing_parenthesis(s: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    for (pos, c) in s.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') if depth == 0 => return Some(pos),
+            (None, ')') => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+impl CopyEncoder {
+    fn new(options: &[CopyOption], legacy_options: &[CopyLegacyOption]) -> PgResult<Self> {
+        let mut format = CopyFormat::Text;
+        let mut header = false;
+        let mut delimiter = None;
+        let mut null = None;
+
+        for option in options {
+            match option {
+                CopyOption::Format(name) => {
+                    format = match name.value.to_lowercase().as_str() {
+                        "text" => CopyFormat::Text,
+                        "csv" => CopyFormat::Csv,
+                        "binary" => return Err(unsupported("COPY in binary format")),
+                        other => {
+                            return Err(syntax_error(format!(
+                                "COPY format \"{other}\" not recognized"
+                            )))
+                        }
+                    }
+                }
+                CopyOption::Header(value) => header = *value,
+                CopyOption::Delimiter(c) => delimiter = Some(*c),
+                CopyOption::Null(s) => null = Some(s.clone()),
+                other => return Err(unsupported(format_smolstr!("COPY option {other}"))),
+            }
+        }
+        for option in legacy_options {
+            match option {
+                CopyLegacyOption::Csv(csv_options) => {
+                    format = CopyFormat::Csv;
+                    for csv_option in csv_options {
+                        match csv_option {
+                            CopyLegacyCsvOption::Header => header = true,
+                            other => {
+                                return Err(unsupported(format_smolstr!("COPY option {other}")))
+                            }
+                        }
+                    }
+                }
+                CopyLegacyOption::Delimiter(c) => delimiter = Some(*c),
+                CopyLegacyOption::Null(s) => null = Some(s.clone()),
+                CopyLegacyOption::Binary => return Err(unsupported("COPY in binary format")),
+            }
+        }
+
+        let (default_delimiter, default_null) = match format {
+            CopyFormat::Text => (b'\t', "\\N"),
+            CopyFormat::Csv => (b',', ""),
+        };
+        let delimiter = match delimiter {
+            None => default_delimiter,
+            Some(c) if c.is_ascii() && !matches!(c, '\n' | '\r' | '\\' | '"') => c as u8,
+            Some(_) => return Err(syntax_error("COPY delimiter is not a valid character")),
+        };
+
+        Ok(Self {
+            format,
+            header,
+            delimiter,
+            null: null.unwrap_or_else(|| default_null.into()),
+        })
+    }
+
+    /// The line with the column names if the header is requested.
+    pub fn header(&self, rows: &Rows) -> Option<Bytes> {
+        if !self.header {
+            return None;
+        }
+        let mut line = BytesMut::new();
+        for (i, field) in rows.fields().iter().enumerate() {
+            if i > 0 {
+                line.put_u8(self.delimiter);
+            }
+            self.put_value(&mut line, field.name());
+        }
+        line.put_u8(b'\n');
+        Some(line.freeze())
+    }
+
+    /// Encode the next row into a line, `None` is returned if there are no rows left.
+    pub fn encode_next(&self, rows: &mut Rows) -> Result<Option<Bytes>, EncodingError> {
+        let Some(values) = rows.next_values() else {
+            return Ok(None);
+        };
+
+        let options = FormatOptions::default();
+        let mut line = BytesMut::new();
+        let mut text = BytesMut::new();
+        for (i, (value, field)) in values.iter().zip(rows.fields()).enumerate() {
+            if i > 0 {
+                line.put_u8(self.delimiter);
+            }
+            text.clear();
+            let is_null = value
+                .to_sql_text(field.datatype(), &mut text, &options)
+                .map_err(EncodingError::new)?;
+            match is_null {
+                IsNull::Yes => line.put_slice(self.null.as_bytes()),
+                IsNull::No => {
+                    let text = std::str::from_utf8(&text).map_err(EncodingError::new)?;
+                    self.put_value(&mut line, text);
+                }
+            }
+        }
+        line.put_u8(b'\n');
+        Ok(Some(line.freeze()))
+    }
+
+    /// Put a non-null value escaped the same way as Postgres does.
+    fn put_value(&self, out: &mut BytesMut, value: &str) {
+        let delimiter = char::from(self.delimiter);
+        match self.format {
+            CopyFormat::Text => {
+                for c in value.chars() {
+                    let escaped = match c {
+                        '\\' => "\\\\",
+                        '\n' => "\\n",
+                        '\r' => "\\r",
+                        '\t' => "\\t",
+                        '\u{8}' => "\\b",
+                        '\u{b}' => "\\v",
+                        '\u{c}' => "\\f",
+                        c if c == delimiter => {
+                            out.put_u8(b'\\');
+                            out.put_u8(self.delimiter);
+                            continue;
+                        }
+                        c => {
+                            let mut buf = [0; 4];
+                            out.put_slice(c.encode_utf8(&mut buf).as_bytes());
+                            continue;
+                        }
+                    };
+                    out.put_slice(escaped.as_bytes());
+                }
+            }
+            CopyFormat::Csv => {
+                // A value equal to the null string is quoted to tell it from NULL.
+                let needs_quotes = value == self.null
+                    || value == "\\."
+                    || value.contains([delimiter, '"', '\n', '\r']);
+                if needs_quotes {
+                    out.put_u8(b'"');
+                    out.put_slice(value.replace('"', "\"\"").as_bytes());
+                    out.put_u8(b'"');
+                } else {
+                    out.put_slice(value.as_bytes());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoder(sql: &str) -> CopyEncoder {
+        parse(sql).unwrap().unwrap().encoder
+    }
+
+    #[test]
+    fn parse_copy_to_stdout() {
+        let copy = parse("copy (SELECT a FROM t WHERE b = ')') TO STDOUT WITH CSV HEADER")
+            .unwrap()
+            .unwrap();
+        assert_eq!(copy.query, "SELECT a FROM t WHERE b = ')'");
+        assert_eq!(copy.encoder.format, CopyFormat::Csv);
+        assert!(copy.encoder.header);
+
+        let encoder = encoder("COPY (SELECT (1)) TO STDOUT (FORMAT csv, DELIMITER ';')");
+        assert_eq!(encoder.format, CopyFormat::Csv);
+        assert_eq!(encoder.delimiter, b';');
+        assert!(!encoder.header);
+
+        let encoder = encoder("COPY(SELECT 1) TO STDOUT");
+        assert_eq!(encoder.format, CopyFormat::Text);
+        assert_eq!(encoder.null, "\\N");
+
+        assert!(parse("SELECT 1").is_none());
+        assert!(parse("copyright").is_none());
+        assert!(parse("COPY t TO STDOUT").unwrap().is_err());
+        assert!(parse("COPY (SELECT 1) TO '/tmp/file'").unwrap().is_err());
+        assert!(parse("COPY (SELECT 1 TO STDOUT").unwrap().is_err());
+        assert!(parse("COPY (SELECT 1) TO STDOUT (FORMAT binary)")
+            .unwrap()
+            .is_err());
+    }
+
+    #[test]
+    fn escape_copy_values() {
+        let mut out = BytesMut::new();
+        let csv = encoder("COPY (SELECT 1) TO STDOUT WITH CSV");
+        for value in ["plain", "a,b", "say \"hi\"", "", "line\nbreak"] {
+            csv.put_value(&mut out, value);
+            out.put_u8(b'|');
+        }
+        assert_eq!(
+            &out[..],
+            b"plain|\"a,b\"|\"say \"\"hi\"\"\"|\"\"|\"line\nbreak\"|"
+        );
+
+        let mut out = BytesMut::new();
+        let text = encoder("COPY (SELECT 1) TO STDOUT");
+        for value in ["plain", "a\tb", "back\\slash", "line\nbreak"] {
+            text.put_value(&mut out, value);
+            out.put_u8(b'|');
+        }
+        assert_eq!(&out[..], b"plain|a\\tb|back\\\\slash|line\\nbreak|");
+    }
+}
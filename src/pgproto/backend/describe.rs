@@ -13,10 +13,10 @@ use sql::{
     errors::{Entity, SbroadError},
     ir::{
         acl::GrantRevokeType,
+        ddl::NotificationCommand,
         node::{
-            acl::Acl, block::Block, ddl::Ddl, expression::Expression, plugin::Plugin,
-            relational::Relational, tcl::Tcl, Alias, AnonymousBlock, GrantPrivilege, Node,
-            RevokePrivilege,
+            acl::Acl, block::Block, ddl::Ddl, plugin::Plugin, relational::Relational, tcl::Tcl,
+            AnonymousBlock, GrantPrivilege, Node, Notification, RevokePrivilege,
         },
         types::{DerivedType, UnrestrictedType as SbroadType},
         Plan,
@@ -51,6 +51,13 @@ pub enum CommandTag {
     Backup = 56,
     Begin = 52,
     CallProcedure = 16,
+    Comment = 59,
+    Savepoint = 60,
+    Release = 61,
+    Listen = 62,
+    Unlisten = 63,
+    Notify = 64,
+    Copy = 65,
     CreateProcedure = 14,
     CreateRole = 1,
     CreateTable = 2,
@@ -128,6 +135,7 @@ impl CommandTag {
             Self::CreateProcedure => "CREATE PROCEDURE",
             Self::DropProcedure => "DROP PROCEDURE",
             Self::CallProcedure => "CALL",
+            Self::Comment => "COMMENT",
             Self::RenameIndex => "RENAME INDEX",
             Self::RenameRoutine => "RENAME ROUTINE",
             Self::SetParam | Self::SetTransaction => "SET",
@@ -142,6 +150,12 @@ impl CommandTag {
             Self::Begin => "BEGIN",
             Self::Commit => "COMMIT",
             Self::Rollback => "ROLLBACK",
+            Self::Savepoint => "SAVEPOINT",
+            Self::Release => "RELEASE",
+            Self::Listen => "LISTEN",
+            Self::Unlisten => "UNLISTEN",
+            Self::Notify => "NOTIFY",
+            Self::Copy => "COPY",
             // Response on an empty query is EmptyQueryResponse with no tag.
             // https://www.postgresql.org/docs/current/protocol-message-formats.html#PROTOCOL-MESSAGE-FORMATS-EMPTYQUERYRESPONSE
             Self::EmptyQuery => "",
@@ -165,6 +179,7 @@ impl From<CommandTag> for QueryType {
             CommandTag::DropTable
             | CommandTag::TruncateTable
             | CommandTag::Backup
+            | CommandTag::Comment
             | CommandTag::AlterTable
             | CommandTag::CreateTable
             | CommandTag::CreateProcedure
@@ -184,15 +199,22 @@ impl From<CommandTag> for QueryType {
             | CommandTag::AddTrier
             | CommandTag::RemoveTier
             | CommandTag::ChangeConfig
+            | CommandTag::Listen
+            | CommandTag::Unlisten
+            | CommandTag::Notify
             | CommandTag::DropProcedure => QueryType::Ddl,
             CommandTag::Delete
             | CommandTag::Insert
             | CommandTag::Update
             | CommandTag::CallProcedure => QueryType::Dml,
             CommandTag::Explain => QueryType::Explain,
-            CommandTag::Select => QueryType::Dql,
+            CommandTag::Select | CommandTag::Copy => QueryType::Dql,
             CommandTag::Deallocate | CommandTag::DeallocateAll => QueryType::Deallocate,
-            CommandTag::Begin | CommandTag::Commit | CommandTag::Rollback => QueryType::Tcl,
+            CommandTag::Begin
+            | CommandTag::Commit
+            | CommandTag::Rollback
+            | CommandTag::Savepoint
+            | CommandTag::Release => QueryType::Tcl,
             CommandTag::Do => QueryType::Tcl,
             CommandTag::EmptyQuery => QueryType::Empty,
         }
@@ -238,11 +260,20 @@ impl TryFrom<&Node<'_>> for CommandTag {
                 Ddl::SetParam { .. } => Ok(CommandTag::SetParam),
                 Ddl::SetTransaction { .. } => Ok(CommandTag::SetTransaction),
                 Ddl::AlterTable(..) => Ok(CommandTag::AlterTable),
+                Ddl::Comment { .. } => Ok(CommandTag::Comment),
+                Ddl::Notification(Notification { command, .. }) => match command {
+                    NotificationCommand::Listen { .. } => Ok(CommandTag::Listen),
+                    NotificationCommand::Unlisten { .. } => Ok(CommandTag::Unlisten),
+                    NotificationCommand::Notify { .. } => Ok(CommandTag::Notify),
+                },
             },
             Node::Tcl(tcl) => match tcl {
                 Tcl::Begin => Ok(CommandTag::Begin),
                 Tcl::Commit => Ok(CommandTag::Commit),
-                Tcl::Rollback => Ok(CommandTag::Rollback),
+                // Postgres uses the same tag for ROLLBACK TO SAVEPOINT.
+                Tcl::Rollback | Tcl::RollbackTo(_) => Ok(CommandTag::Rollback),
+                Tcl::Savepoint(_) => Ok(CommandTag::Savepoint),
+                Tcl::Release(_) => Ok(CommandTag::Release),
             },
             Node::Plugin(plugin) => match plugin {
                 Plugin::Create { .. } => Ok(CommandTag::CreatePlugin),
@@ -325,6 +356,7 @@ fn pg_type_from_sbroad(sbroad: &DerivedType) -> Type {
             SbroadType::Double => Type::FLOAT8,
             SbroadType::Decimal => Type::NUMERIC,
             SbroadType::Uuid => Type::UUID,
+            SbroadType::Bytea => Type::BYTEA,
             SbroadType::Datetime => Type::TIMESTAMPTZ,
         }
     } else {
@@ -346,24 +378,11 @@ fn dql_output_format(ir: &Plan) -> PgResult<Vec<MetadataColumn>> {
             return Ok(metadata);
         }
     }
-    let top_output_id = ir.get_relation_node(top_id)?.output();
-    let columns = ir.get_row_list(top_output_id)?;
-    let mut metadata = Vec::with_capacity(columns.len());
-    for col_id in columns {
-        let column = ir.get_expression_node(*col_id)?;
-        let column_type = column.calculate_type(ir)?;
-        let column_name = if let Expression::Alias(Alias { name, .. }) = column {
-            name.to_string()
-        } else {
-            return Err(SbroadError::Invalid(
-                Entity::Expression,
-                Some(smol_str::format_smolstr!("expected alias, got {column:?}")),
-            )
-            .into());
-        };
-        let ty = pg_type_from_sbroad(&column_type);
-        metadata.push(MetadataColumn::new(column_name, ty));
-    }
+    let metadata = ir
+        .output_columns_with_types()?
+        .into_iter()
+        .map(|(name, ty)| MetadataColumn::new(name.to_string(), pg_type_from_sbroad(&ty)))
+        .collect();
     Ok(metadata)
 }
 
@@ -420,7 +439,10 @@ impl Describe {
             let node = plan.get_node(top)?;
             CommandTag::try_from(&node)?
         };
-        let query_type = if plan.is_block()? && !plan.is_raw_explain() {
+        let query_type = if plan.select_into.is_some() {
+            // `SELECT ... INTO` creates a table and returns no rows.
+            QueryType::Ddl
+        } else if plan.is_block()? && !plan.is_raw_explain() {
             let top = plan.get_top()?;
             let block = plan.get_block_node(top)?;
             match block {
@@ -594,3 +616,87 @@ impl PortalDescribe {
         self.describe.command_tag()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{CommandTag, Describe, QueryType};
+    use crate::sql::router::RouterMetadata;
+    use crate::storage::Catalog;
+    use sql::frontend::{sql::ast::AbstractSyntaxTree, Ast};
+
+    #[::tarantool::test]
+    fn describe_comment() {
+        let _storage = Catalog::for_tests();
+        let metadata = RouterMetadata::default();
+
+        for query in [
+            r#"COMMENT ON TABLE "_pico_table" IS 'cluster tables'"#,
+            r#"COMMENT ON COLUMN "_pico_table"."name" IS 'table name'"#,
+            r#"COMMENT ON TABLE "_pico_table" IS NULL"#,
+        ] {
+            let plan = AbstractSyntaxTree::transform_into_plan(query, &[], &metadata).unwrap();
+            let describe = Describe::new(&plan).unwrap();
+            assert!(matches!(describe.command_tag(), CommandTag::Comment));
+            assert!(matches!(describe.query_type(), QueryType::Ddl));
+            assert_eq!(describe.command_tag().as_str(), "COMMENT");
+            assert!(describe.row_description().is_none());
+        }
+    }
+
+    #[::tarantool::test]
+    fn describe_alter_table() {
+        let _storage = Catalog::for_tests();
+        let metadata = RouterMetadata::default();
+
+        for query in [
+            r#"ALTER TABLE "_pico_table" ADD COLUMN "kek" INT"#,
+            r#"ALTER TABLE "_pico_table" DROP COLUMN IF EXISTS "kek""#,
+        ] {
+            let plan = AbstractSyntaxTree::transform_into_plan(query, &[], &metadata).unwrap();
+            let describe = Describe::new(&plan).unwrap();
+            assert!(matches!(describe.command_tag(), CommandTag::AlterTable));
+            assert!(matches!(describe.query_type(), QueryType::Ddl));
+            assert_eq!(describe.command_tag().as_str(), "ALTER TABLE");
+            assert!(describe.row_description().is_none());
+        }
+    }
+
+    #[::tarantool::test]
+    fn describe_savepoint() {
+        let _storage = Catalog::for_tests();
+        let metadata = RouterMetadata::default();
+
+        for (query, tag) in [
+            ("SAVEPOINT sp", "SAVEPOINT"),
+            ("RELEASE SAVEPOINT sp", "RELEASE"),
+            ("RELEASE sp", "RELEASE"),
+            ("ROLLBACK TO SAVEPOINT sp", "ROLLBACK"),
+            ("ROLLBACK TO sp", "ROLLBACK"),
+        ] {
+            let plan = AbstractSyntaxTree::transform_into_plan(query, &[], &metadata).unwrap();
+            let describe = Describe::new(&plan).unwrap();
+            assert!(matches!(describe.query_type(), QueryType::Tcl));
+            assert_eq!(describe.command_tag().as_str(), tag);
+            assert!(describe.row_description().is_none());
+        }
+    }
+
+    #[::tarantool::test]
+    fn describe_notification() {
+        let _storage = Catalog::for_tests();
+        let metadata = RouterMetadata::default();
+
+        for (query, tag) in [
+            ("LISTEN events", "LISTEN"),
+            ("UNLISTEN events", "UNLISTEN"),
+            ("UNLISTEN *", "UNLISTEN"),
+            ("NOTIFY events, 'payload'", "NOTIFY"),
+        ] {
+            let plan = AbstractSyntaxTree::transform_into_plan(query, &[], &metadata).unwrap();
+            let describe = Describe::new(&plan).unwrap();
+            assert!(matches!(describe.query_type(), QueryType::Ddl));
+            assert_eq!(describe.command_tag().as_str(), tag);
+            assert!(describe.row_description().is_none());
+        }
+    }
+}
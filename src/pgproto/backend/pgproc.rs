@@ -64,6 +64,7 @@ pub fn proc_pg_bind(args: BindArgs) -> PgResult<()> {
         options: PartialOptions::default(),
         is_statement_invalidation: false,
         is_query_metadata: false,
+        application_name: String::new(),
         _rest: Default::default(),
     };
 
@@ -129,6 +130,9 @@ pub fn proc_pg_execute(id: ClientId, name: String, max_rows: i64) -> PgResult<Tu
 
             Ok(msgpack::encode(&vec![result]))
         }
+        ExecuteResult::CopyOut { .. } => {
+            unreachable!("COPY is supported only in simple query")
+        }
     };
 
     let bytes = bytes.map_err(EncodingError::new)?;
@@ -1,10 +1,12 @@
-use crate::pgproto::{backend::describe::CommandTag, error::EncodingError, value::PgValue};
+use crate::pgproto::{
+    backend::{copy::CopyEncoder, describe::CommandTag},
+    error::EncodingError,
+    value::PgValue,
+};
 use pgwire::{
     api::results::{DataRowEncoder, FieldInfo},
     messages::data::{DataRow, RowDescription},
-    types::ToSqlText,
 };
-use postgres_types::ToSql;
 use std::{sync::Arc, vec::IntoIter};
 
 pub struct Rows {
@@ -29,30 +31,26 @@ impl Rows {
             return Ok(None);
         };
 
-        fn encode_value<V>(encoder: &mut DataRowEncoder, value: &V) -> Result<(), EncodingError>
-        where
-            V: ToSql + ToSqlText + Sized,
-        {
-            encoder.encode_field(value).map_err(EncodingError::new)
-        }
-
+        // Binary fields are encoded with `PgValue::encode_binary`,
+        // text ones with the `ToSqlText` implementation.
         for value in &values {
-            match value {
-                PgValue::Float(v) => encode_value(&mut self.encoder, v)?,
-                PgValue::Integer(v) => encode_value(&mut self.encoder, v)?,
-                PgValue::Boolean(v) => encode_value(&mut self.encoder, v)?,
-                PgValue::Text(v) => encode_value(&mut self.encoder, v)?,
-                PgValue::Timestamptz(v) => encode_value(&mut self.encoder, v)?,
-                PgValue::Json(v) => encode_value(&mut self.encoder, v)?,
-                PgValue::Uuid(v) => encode_value(&mut self.encoder, v)?,
-                PgValue::Numeric(v) => encode_value(&mut self.encoder, v)?,
-                PgValue::Null => encode_value(&mut self.encoder, &None::<i64>)?,
-            }
+            self.encoder
+                .encode_field(value)
+                .map_err(EncodingError::new)?;
         }
 
         Ok(Some(self.encoder.take_row()))
     }
 
+    /// Take the values of the next row without encoding them.
+    pub fn next_values(&mut self) -> Option<Vec<PgValue>> {
+        self.rows.next()
+    }
+
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.desc
+    }
+
     pub fn describe(&self) -> RowDescription {
         RowDescription::new(self.desc.iter().map(Into::into).collect())
     }
@@ -90,6 +88,16 @@ pub enum ExecuteResult {
         /// necessary to cache the number of rows before retrieving them.
         row_count: usize,
     },
+    /// Result of `COPY (query) TO STDOUT`.
+    CopyOut {
+        /// Tag of the command.
+        tag: CommandTag,
+        /// The first batch of rows we'll send to the client as `CopyData` messages.
+        rows: Rows,
+        encoder: CopyEncoder,
+        /// Whether there are no more batches to fetch.
+        is_finished: bool,
+    },
     /// Result of an empty query.
     Empty,
 }
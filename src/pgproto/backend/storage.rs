@@ -1,11 +1,20 @@
 use super::{
-    close_client_statements, deallocate_statement,
-    describe::{Describe, MetadataColumn, PortalDescribe, QueryType, StatementDescribe},
+    add_warnings, application_name, check_transaction_isolation, close_client_statements,
+    commit_transaction, deallocate_statement,
+    describe::{
+        CommandTag, Describe, MetadataColumn, PortalDescribe, QueryType, StatementDescribe,
+    },
+    execute_in_transaction_block, in_failed_transaction_block, in_transaction_block, listen,
+    lock_rows_in_transaction_block, mark_transaction_query, queue_notification,
     result::{ExecuteResult, Rows},
+    set_param, transaction_control, unlisten,
 };
 use crate::config::observer::AtomicObserver;
+use crate::instance::InstanceName;
+use crate::sql::dispatch::BatchedDml;
 use crate::sql::port::PicoPortOwned;
 use crate::sql::router::{get_table_version, RouterRuntime};
+use crate::sql::row_lock::LockOwner;
 use crate::{audit, schema::ADMIN_ID};
 use crate::{
     pgproto::{
@@ -21,11 +30,15 @@ use prometheus::IntCounter;
 use serde::Serialize;
 use smol_str::{format_smolstr, SmolStr};
 use sql::executor::Port;
+use sql::ir::ddl::{NotificationCommand, SetParamScopeType};
+use sql::ir::node::ddl::Ddl;
+use sql::ir::node::tcl::Tcl;
+use sql::ir::node::{Node, Notification, SetParam, SetTransaction};
 use sql::ir::types::{DerivedType, UnrestrictedType as SbroadType};
 use sql_protocol::iterators::ExplainIter;
 use std::{
     cell::RefCell,
-    collections::{btree_map::Entry, BTreeMap},
+    collections::{btree_map::Entry, BTreeMap, HashMap, HashSet, VecDeque},
     io::Cursor,
     ops::Bound,
     os::raw::c_int,
@@ -34,10 +47,12 @@ use std::{
     vec::IntoIter,
 };
 use tarantool::{
+    fiber::FiberId,
     proc::{Return, ReturnMsgpack},
     session::with_su,
     tuple::FunctionCtx,
 };
+use time::OffsetDateTime;
 
 /// Used to store portals in [`PG_PORTALS`] and statements in [`PG_STATEMENTS`].
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -165,14 +180,20 @@ impl<S> PgStorage<S> {
     }
 
     pub fn names_by_client_id(&self, id: ClientId) -> Vec<Rc<str>> {
+        self.by_client_id(id)
+            .map(|(name, _)| Rc::clone(name))
+            .collect()
+    }
+
+    /// Iterate over the elements of the client with the given id ordered by name.
+    pub fn by_client_id(&self, id: ClientId) -> impl Iterator<Item = (&Rc<str>, &S)> {
         let range = (
             Bound::Included(Key(id, Rc::clone(&self.empty_name))),
             Bound::Excluded(Key(id + 1, Rc::clone(&self.empty_name))),
         );
         self.map
             .range(range)
-            .map(|(Key(_, name), _)| Rc::clone(name))
-            .collect()
+            .map(|(Key(_, name), value)| (name, value))
     }
 
     #[inline(always)]
@@ -218,6 +239,84 @@ impl PortalStorage {
 thread_local! {
     pub static PG_STATEMENTS: RefCell<StatementStorage> = RefCell::new(StatementStorage::new());
     pub static PG_PORTALS: Rc<RefCell<PortalStorage>> = Rc::new(RefCell::new(PortalStorage::new()));
+    /// State of the clients which are inside a transaction block.
+    pub static PG_TRANSACTIONS: RefCell<HashMap<ClientId, TransactionBlock>> = RefCell::new(HashMap::new());
+    /// `application_name` of the clients which have set it.
+    pub static PG_APPLICATION_NAMES: RefCell<HashMap<ClientId, SmolStr>> = RefCell::new(HashMap::new());
+    /// Clients which have disabled the query plan cache with `SET plan_cache = off`.
+    pub static PG_PLAN_CACHE_DISABLED: RefCell<HashSet<ClientId>> = RefCell::new(HashSet::new());
+    /// Clients which have executed `LISTEN`.
+    pub static PG_LISTENERS: RefCell<HashMap<ClientId, Listener>> = RefCell::new(HashMap::new());
+    /// Warnings about the query results which haven't been sent to the clients yet.
+    pub static PG_WARNINGS: RefCell<HashMap<ClientId, Vec<String>>> = RefCell::new(HashMap::new());
+}
+
+/// A notification sent with `NOTIFY`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingNotification {
+    pub channel: SmolStr,
+    pub payload: SmolStr,
+}
+
+/// Asynchronous notifications state of a client.
+#[derive(Debug, Default)]
+pub struct Listener {
+    /// Channels from `LISTEN`.
+    pub channels: HashSet<SmolStr>,
+    /// Notifications received while the client was busy, they are sent
+    /// once it becomes idle.
+    pub pending: VecDeque<PendingNotification>,
+    /// Fiber of the idle client waiting for its next query,
+    /// it is woken up to send a new notification.
+    pub waiter: Option<FiberId>,
+}
+
+/// A savepoint of a transaction block.
+#[derive(Debug)]
+pub struct Savepoint {
+    pub name: SmolStr,
+    /// Number of modifications made before the savepoint was set.
+    pub pending_len: usize,
+    /// Number of notifications sent before the savepoint was set.
+    pub notifications_len: usize,
+}
+
+/// Row locks of a transaction block taken with `SELECT ... FOR UPDATE/FOR SHARE`.
+#[derive(Clone, Debug)]
+pub struct RowLocks {
+    pub owner: LockOwner,
+    /// Replicaset masters holding the locks.
+    pub holders: Vec<InstanceName>,
+}
+
+/// Session parameters changed with `SET LOCAL` in a transaction block,
+/// with their values before the change. They are restored when the block ends.
+#[derive(Debug, Default)]
+pub struct LocalParams {
+    pub application_name: Option<Option<SmolStr>>,
+    pub plan_cache: Option<bool>,
+}
+
+/// State of a transaction block started with `BEGIN`.
+#[derive(Debug, Default)]
+pub struct TransactionBlock {
+    pub savepoints: Vec<Savepoint>,
+    /// Whether a query was already executed in the transaction.
+    pub has_queries: bool,
+    /// Modifications to commit on `COMMIT`, in the order they were received.
+    pub pending: Vec<BatchedDml>,
+    /// Notifications to deliver on `COMMIT`, in the order they were sent.
+    pub notifications: Vec<PendingNotification>,
+    /// Whether a command of the transaction has failed. Only the commands
+    /// ending the block or rolling back to a savepoint are accepted then.
+    pub failed: bool,
+    /// Time of `BEGIN`. Like in Postgres, the time functions of the statements
+    /// in the block return the start time of the transaction.
+    pub started_at: Option<OffsetDateTime>,
+    /// Row locks released when the block ends, not on rollback to a savepoint.
+    pub row_locks: Option<RowLocks>,
+    /// Parameters to restore when the block ends.
+    pub local_params: LocalParams,
 }
 
 /// Eagerly initialize storages for prepared statements and portals.
@@ -247,6 +346,8 @@ pub struct StatementInner {
     key: Key,
     statement: sql::PreparedStatement,
     describe: StatementDescribe,
+    /// Query text the statement was prepared from.
+    query: String,
 }
 
 impl Drop for StatementInner {
@@ -268,6 +369,7 @@ impl Statement {
     pub fn new(
         key: Key,
         statement: sql::PreparedStatement,
+        query: &str,
         specified_param_oids: Vec<u32>,
     ) -> PgResult<Self> {
         // generate pgproto metadata
@@ -280,6 +382,7 @@ impl Statement {
             key,
             statement,
             describe,
+            query: query.into(),
         };
 
         PGPROTO_STATEMENTS_OPENED_TOTAL.inc();
@@ -303,6 +406,11 @@ impl Statement {
         &self.0.describe
     }
 
+    #[inline(always)]
+    pub fn query(&self) -> &str {
+        &self.0.query
+    }
+
     #[inline(always)]
     fn ptr_eq(&self, other: &Statement) -> bool {
         Rc::ptr_eq(&self.0, &other.0)
@@ -377,6 +485,7 @@ pub(super) fn sbroad_type_to_pg(ty: &SbroadType) -> postgres_types::Type {
         SbroadType::Integer => PgType::INT8,
         SbroadType::String => PgType::TEXT,
         SbroadType::Uuid => PgType::UUID,
+        SbroadType::Bytea => PgType::BYTEA,
         SbroadType::Map | SbroadType::Array | SbroadType::Any => PgType::JSON,
         SbroadType::Datetime => PgType::TIMESTAMPTZ,
     }
@@ -390,6 +499,7 @@ pub(super) fn pg_type_to_sbroad(ty: &PgType) -> Option<SbroadType> {
         &PgType::INT8 | &PgType::INT4 | &PgType::INT2 => Some(SbroadType::Integer),
         &PgType::TEXT | &PgType::VARCHAR => Some(SbroadType::String),
         &PgType::UUID => Some(SbroadType::Uuid),
+        &PgType::BYTEA => Some(SbroadType::Bytea),
         &PgType::TIMESTAMPTZ => Some(SbroadType::Datetime),
         _unsupported_type => None,
     }
@@ -580,6 +690,8 @@ struct PortalInner {
     statement: Statement,
     describe: PortalDescribe,
     state: RefCell<PortalState>,
+    /// The last row sent to the client, if the portal is positioned on it.
+    current_row: RefCell<Option<Vec<PgValue>>>,
 }
 
 impl Drop for PortalInner {
@@ -600,34 +712,139 @@ impl PortalInner {
         router: &RouterRuntime,
         statement: sql::BoundStatement,
     ) -> PgResult<PortalState> {
+        if in_failed_transaction_block(self.key.0) && !self.ends_failed_transaction()? {
+            return Err(PedanticError::new(
+                PgErrorCode::InFailedSqlTransaction,
+                "current transaction is aborted, commands ignored until end of transaction block",
+            )
+            .into());
+        }
+
         if let QueryType::Dml = self.describe.query_type() {
             if let Some(query) = self.statement.prepared_statement().query_for_audit() {
                 audit::policy::log_dml_for_user(query, statement.params_for_audit());
             }
         }
-        if let Some(query) = self.statement.prepared_statement().query_for_logging() {
+        let prepared_statement = self.statement.prepared_statement();
+        if let Some(query) = prepared_statement.query_for_logging() {
             // Logs only the SQL text, not its parameters.
-            tlog!(Info, "sql-log: {query}");
+            let query_id = prepared_statement.query_fingerprint().unwrap_or_default();
+            match application_name(self.key.0) {
+                Some(name) => tlog!(Info, "sql-log: {query}";
+                    "application_name" => %name,
+                    "query_id" => %query_id,
+                ),
+                None => tlog!(Info, "sql-log: {query}"; "query_id" => %query_id),
+            }
+        }
+
+        // Modifications of a transaction block are committed on `COMMIT`,
+        // so that `ROLLBACK` leaves the data untouched.
+        if let QueryType::Dml = self.describe.query_type() {
+            if in_transaction_block(self.key.0) {
+                mark_transaction_query(self.key.0);
+                let row_count = execute_in_transaction_block(router, self.key.0, statement)?;
+                let tag = self.describe.command_tag();
+                return Ok(PortalState::ResultReady(ExecuteResult::Dml {
+                    row_count,
+                    tag,
+                }));
+            }
+        }
+
+        // Transaction blocks are tracked by pgproto, the storages don't
+        // take part in them until `COMMIT`.
+        if let QueryType::Tcl = self.describe.query_type() {
+            let ir_plan = self.statement.prepared_statement().as_plan();
+            let top_id = ir_plan.get_top()?;
+            let mut tag = self.describe.command_tag();
+            if let Node::Tcl(tcl) = ir_plan.get_node(top_id)? {
+                // Like in Postgres, committing a failed transaction rolls it back.
+                if matches!(tcl, Tcl::Commit) && in_failed_transaction_block(self.key.0) {
+                    tag = CommandTag::Rollback;
+                }
+                let committed = transaction_control(self.key.0, &tcl)?;
+                commit_transaction(committed)?;
+            }
+            return Ok(PortalState::ResultReady(ExecuteResult::Tcl { tag }));
+        }
+
+        // Like in Postgres, notifications of a transaction block are
+        // delivered on `COMMIT` and discarded on `ROLLBACK`.
+        if let QueryType::Ddl = self.describe.query_type() {
+            if in_transaction_block(self.key.0) {
+                let ir_plan = self.statement.prepared_statement().as_plan();
+                if let Node::Ddl(Ddl::Notification(Notification {
+                    command: NotificationCommand::Notify { channel, payload },
+                    ..
+                })) = ir_plan.get_node(ir_plan.get_top()?)?
+                {
+                    mark_transaction_query(self.key.0);
+                    queue_notification(self.key.0, channel, payload)?;
+                    let tag = self.describe.command_tag();
+                    return Ok(PortalState::ResultReady(ExecuteResult::AclOrDdl { tag }));
+                }
+            }
+        }
+
+        // Like in Postgres, the rows selected with `FOR UPDATE` or `FOR SHARE`
+        // in a transaction block stay locked until the end of the block.
+        if let QueryType::Dql = self.describe.query_type() {
+            if let Some(mode) = statement.as_plan().row_lock {
+                if in_transaction_block(self.key.0) {
+                    lock_rows_in_transaction_block(router, self.key.0, &statement, mode)?;
+                }
+            }
         }
 
         let mut port = PicoPortOwned::new();
         crate::sql::dispatch_bound_statement(router, statement, None, None, &mut port)?;
+        add_warnings(self.key.0, port.take_warnings());
 
         let state = match self.describe.query_type() {
             QueryType::Acl | QueryType::Ddl => {
+                let ir_plan = self.statement.prepared_statement().as_plan();
+                let top_id = ir_plan.get_top()?;
+                match ir_plan.get_node(top_id)? {
+                    Node::Ddl(Ddl::SetParam(SetParam {
+                        scope_type,
+                        param_value,
+                        ..
+                    })) => set_param(self.key.0, scope_type, param_value),
+                    Node::Ddl(Ddl::SetTransaction(SetTransaction {
+                        scope_type: SetParamScopeType::Local,
+                        isolation_level: Some(_),
+                        ..
+                    })) => check_transaction_isolation(self.key.0)?,
+                    Node::Ddl(Ddl::Notification(Notification {
+                        command: NotificationCommand::Listen { channel },
+                        ..
+                    })) => listen(self.key.0, channel),
+                    Node::Ddl(Ddl::Notification(Notification {
+                        command: NotificationCommand::Unlisten { channel },
+                        ..
+                    })) => unlisten(self.key.0, channel.as_deref()),
+                    _ => {}
+                }
+
                 let tag = self.describe.command_tag();
-                PortalState::ResultReady(ExecuteResult::AclOrDdl { tag })
-            }
-            QueryType::Tcl => {
-                let tag = self.describe.command_tag();
-                PortalState::ResultReady(ExecuteResult::Tcl { tag })
+                if ir_plan.select_into.is_some() {
+                    // Like in Postgres, `SELECT ... INTO` reports the number of rows.
+                    let row_count = port_read_changed(port.iter())?;
+                    PortalState::ResultReady(ExecuteResult::Dml { row_count, tag })
+                } else {
+                    PortalState::ResultReady(ExecuteResult::AclOrDdl { tag })
+                }
             }
+            QueryType::Tcl => unreachable!("handled above"),
             QueryType::Dml => {
+                mark_transaction_query(self.key.0);
                 let row_count = port_read_changed(port.iter())?;
                 let tag = self.describe.command_tag();
                 PortalState::ResultReady(ExecuteResult::Dml { row_count, tag })
             }
             QueryType::Dql => {
+                mark_transaction_query(self.key.0);
                 let rows = port_read_tuples(
                     port.iter().skip(1),
                     port.size() as usize,
@@ -667,6 +884,16 @@ impl PortalInner {
         Ok(state)
     }
 
+    /// Check whether the portal ends a failed transaction block or recovers it.
+    fn ends_failed_transaction(&self) -> PgResult<bool> {
+        let ir_plan = self.statement.prepared_statement().as_plan();
+        let top_id = ir_plan.get_top()?;
+        Ok(matches!(
+            ir_plan.get_node(top_id)?,
+            Node::Tcl(Tcl::Commit | Tcl::Rollback | Tcl::RollbackTo(_))
+        ))
+    }
+
     fn execute(&self, runtime: &RouterRuntime, max_rows: usize) -> PgResult<ExecuteResult> {
         let mut state = self.state.borrow_mut();
 
@@ -700,6 +927,13 @@ impl PortalInner {
                 PortalState::StreamingRows(mut stored_rows) => {
                     let taken: Vec<_> = (&mut stored_rows).take(max_rows).collect();
                     let row_count = taken.len();
+                    // Like a cursor, the portal is positioned after the last row
+                    // if fewer rows than requested are left.
+                    *self.current_row.borrow_mut() = if row_count == max_rows {
+                        taken.last().cloned()
+                    } else {
+                        None
+                    };
                     let rows = Rows::new(taken, self.describe.row_info());
 
                     Ok(match stored_rows.len() {
@@ -747,6 +981,7 @@ impl Portal {
             statement,
             describe,
             state,
+            current_row: RefCell::new(None),
         };
 
         PGPROTO_PORTALS_OPENED_TOTAL.inc();
@@ -760,6 +995,17 @@ impl Portal {
         &self.0.describe
     }
 
+    #[inline(always)]
+    pub fn statement(&self) -> &Statement {
+        &self.0.statement
+    }
+
+    /// The row the portal is positioned on (see `WHERE CURRENT OF`).
+    #[inline(always)]
+    pub fn current_row(&self) -> Option<Vec<PgValue>> {
+        self.0.current_row.borrow().clone()
+    }
+
     #[inline(always)]
     pub fn contains_statement(&self, statement: &Statement) -> bool {
         Statement::ptr_eq(&self.0.statement, statement)
@@ -838,6 +1084,7 @@ mod test {
             (SbroadType::Integer, PgType::INT8),
             (SbroadType::String, PgType::TEXT),
             (SbroadType::Uuid, PgType::UUID),
+            (SbroadType::Bytea, PgType::BYTEA),
             (SbroadType::Any, PgType::JSON),
             (SbroadType::Array, PgType::JSON),
             (SbroadType::Map, PgType::JSON),
@@ -857,6 +1104,7 @@ mod test {
             (PgType::INT2, SbroadType::Integer),
             (PgType::TEXT, SbroadType::String),
             (PgType::UUID, SbroadType::Uuid),
+            (PgType::BYTEA, SbroadType::Bytea),
         ] {
             assert!(pg_type_to_sbroad(&pg).unwrap() == expected_sbroad)
         }
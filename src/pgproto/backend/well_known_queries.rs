@@ -1,4 +1,7 @@
-use sqlparser::ast::{Expr, Query, SetExpr, SetOperator, SetQuantifier, Value, Visit, Visitor};
+use sqlparser::ast::{
+    Expr, GroupByExpr, Ident, Query, SelectItem, SetExpr, SetOperator, SetQuantifier, Statement,
+    TableFactor, TableWithJoins, Value, Visit, Visitor,
+};
 use sqlparser::dialect::PostgreSqlDialect;
 use sqlparser::parser::Parser;
 use std::ops::ControlFlow;
@@ -134,7 +137,7 @@ fn is_select_from_pg_class(expr: &SetExpr) -> bool {
     };
 
     for table_with_joins in &select.from {
-        if let sqlparser::ast::TableFactor::Table { name, .. } = &table_with_joins.relation {
+        if let TableFactor::Table { name, .. } = &table_with_joins.relation {
             if name.to_string().to_lowercase() == "pg_catalog.pg_class" {
                 return true;
             }
@@ -184,3 +187,89 @@ pub fn parse(sql: &str) -> Option<WellKnownQuery> {
     }
     None
 }
+
+/// Columns of the `pg_prepared_statements` view we provide.
+pub const PREPARED_STATEMENTS_COLUMNS: [&str; 2] = ["name", "statement"];
+
+/// Check whether the query reads the `pg_prepared_statements` view and return
+/// the indexes of the projected view columns.
+///
+/// Only the plain projection of the view is recognized, e.g.
+/// `SELECT name, statement FROM pg_catalog.pg_prepared_statements`: any other
+/// clause (WHERE, ORDER BY, LIMIT, joins, expressions, ...) would be silently
+/// ignored, so such queries fall through to the usual "table not found" error.
+///
+/// NOTE: Like in Postgres, the view lists the named statements of the current client.
+pub fn parse_list_of_prepared_statements(query: &str) -> Option<Vec<usize>> {
+    let dialect = PostgreSqlDialect {};
+    let ast = Parser::parse_sql(&dialect, query).ok()?;
+    let [Statement::Query(query)] = ast.as_slice() else {
+        return None;
+    };
+    if query.with.is_some()
+        || !query.order_by.is_empty()
+        || query.limit.is_some()
+        || !query.limit_by.is_empty()
+        || query.offset.is_some()
+        || query.fetch.is_some()
+        || !query.locks.is_empty()
+    {
+        return None;
+    }
+    let SetExpr::Select(select) = query.body.as_ref() else {
+        return None;
+    };
+    if select.distinct.is_some()
+        || select.top.is_some()
+        || select.into.is_some()
+        || !select.lateral_views.is_empty()
+        || select.selection.is_some()
+        || select.group_by != GroupByExpr::Expressions(vec![])
+        || !select.cluster_by.is_empty()
+        || !select.distribute_by.is_empty()
+        || !select.sort_by.is_empty()
+        || select.having.is_some()
+        || !select.named_window.is_empty()
+        || select.qualify.is_some()
+    {
+        return None;
+    }
+    let [TableWithJoins { relation, joins }] = select.from.as_slice() else {
+        return None;
+    };
+    let TableFactor::Table {
+        name, args: None, ..
+    } = relation
+    else {
+        return None;
+    };
+    if !joins.is_empty()
+        || !matches!(
+            name.to_string().to_lowercase().as_str(),
+            "pg_prepared_statements" | "pg_catalog.pg_prepared_statements"
+        )
+    {
+        return None;
+    }
+
+    let column_index = |ident: &Ident| {
+        PREPARED_STATEMENTS_COLUMNS
+            .iter()
+            .position(|column| ident.value.eq_ignore_ascii_case(column))
+    };
+    let mut columns = Vec::with_capacity(select.projection.len());
+    for item in &select.projection {
+        match item {
+            SelectItem::Wildcard(_) => columns.extend(0..PREPARED_STATEMENTS_COLUMNS.len()),
+            SelectItem::UnnamedExpr(Expr::Identifier(ident)) => columns.push(column_index(ident)?),
+            SelectItem::UnnamedExpr(Expr::CompoundIdentifier(idents)) => {
+                let [.., ident] = idents.as_slice() else {
+                    return None;
+                };
+                columns.push(column_index(ident)?)
+            }
+            _ => return None,
+        }
+    }
+    Some(columns)
+}
@@ -9,17 +9,32 @@ use crate::{storage::Catalog, tlog};
 use pgwire::messages::startup::*;
 use smol_str::format_smolstr;
 use std::io;
+use std::os::fd::AsRawFd;
+use std::time::Duration;
+use tarantool::clock;
+use tarantool::fiber;
+use tarantool::time::Instant;
 
 mod auth;
 mod extended_query;
 mod simple_query;
 mod startup;
 
+pub use auth::{AuthExchange, Authenticator, CleartextAuthenticator, Md5Authenticator};
 pub use startup::ClientParams;
 
 /// We generate those sequentially for every client connection.
 pub type ClientId = u64;
 
+/// Settings for closing connections of idle clients.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IdleTimeout {
+    /// How long a client may wait before sending a query, `None` means forever.
+    pub timeout: Option<Duration>,
+    /// Whether the timeout applies to clients with an open transaction.
+    pub in_transaction: bool,
+}
+
 /// Postgres client representation.
 pub struct PgClient<S> {
     /// Postgres backend that handles queries.
@@ -28,6 +43,8 @@ pub struct PgClient<S> {
     stream: PgStream<S>,
 
     loop_state: MessageLoopState,
+
+    idle_timeout: IdleTimeout,
 }
 
 impl<S: io::Read + io::Write> PgClient<S> {
@@ -36,11 +53,14 @@ impl<S: io::Read + io::Write> PgClient<S> {
         stream: PgStream<S>,
         tls_acceptor: Option<TlsAcceptor>,
         storage: &Catalog,
+        authenticator: Option<&dyn Authenticator>,
+        idle_timeout: IdleTimeout,
     ) -> PgResult<PgClient<S>> {
         let (mut stream, params) = startup::handshake(stream, tls_acceptor.as_ref())?;
         tlog!(Info, "processed startup");
 
-        auth::authenticate(&mut stream, &params.username, storage).map_err(|error| {
+        let username = &params.username;
+        auth::authenticate(&mut stream, username, storage, authenticator).map_err(|error| {
             tlog!(Info, "failed to establish client connection: {error}");
             // At this point we don't care about failed writes (best effort approach).
             let _ = stream.write_message(messages::error_response(error.info()));
@@ -52,6 +72,7 @@ impl<S: io::Read + io::Write> PgClient<S> {
             backend: Backend::new(params),
             loop_state: MessageLoopState::ReadyForQuery,
             stream,
+            idle_timeout,
         })
     }
 
@@ -89,14 +110,86 @@ impl PgError {
             // We list them here just in case somebody uses this function inappropriately.
             Self::SslRequired | Self::AuthError(_) => Err(self),
 
+            // The client has been idle for too long, close the connection.
+            Self::IdleSessionTimeout | Self::IdleInTransactionSessionTimeout => Err(self),
+
             _otherwise => Ok(()),
         }
     }
 }
 
-impl<S: io::Read + io::Write> PgClient<S> {
+impl<S: io::Read + io::Write + AsRawFd> PgClient<S> {
+    /// Wait for the next query from a client that is ready for it.
+    /// Fail if the client stays idle for longer than the idle timeout.
+    ///
+    /// Notifications received meanwhile are sent to a listening client
+    /// outside of a transaction block.
+    fn wait_for_query(&mut self) -> PgResult<()> {
+        let in_transaction = self.backend.in_transaction_block();
+        let timeout = match self.idle_timeout.timeout {
+            Some(_) if in_transaction && !self.idle_timeout.in_transaction => None,
+            timeout => timeout,
+        };
+        let deadline = timeout.map(|timeout| Instant::now_fiber().saturating_add(timeout));
+        let listening = !in_transaction && self.backend.is_listening();
+
+        loop {
+            if listening {
+                // Sending yields and more notifications may arrive meanwhile,
+                // so send them until none is left to not miss any before the wait.
+                while self.send_notifications()? {}
+            }
+            let wait = match deadline {
+                Some(deadline) => deadline.duration_since(Instant::now_fiber()),
+                None if listening => clock::INFINITY,
+                None => return Ok(()),
+            };
+
+            // A new notification wakes the fiber up, which interrupts the wait.
+            if listening {
+                self.backend.set_notification_waiter(Some(fiber::id()));
+            }
+            let readable = self.stream.wait_readable(wait);
+            if listening {
+                self.backend.set_notification_waiter(None);
+            }
+            if readable? {
+                return Ok(());
+            }
+            if deadline.is_some_and(|deadline| Instant::now_fiber() >= deadline) {
+                break;
+            }
+        }
+
+        tlog!(Info, "closing the connection of an idle client");
+        if in_transaction {
+            return Err(PgError::IdleInTransactionSessionTimeout);
+        }
+        Err(PgError::IdleSessionTimeout)
+    }
+
+    /// Send the pending notifications to the client.
+    /// Returns whether there were any.
+    fn send_notifications(&mut self) -> PgResult<bool> {
+        let notifications = self.backend.take_notifications();
+        if notifications.is_empty() {
+            return Ok(false);
+        }
+        for notification in &notifications {
+            let message =
+                messages::notification_response(&notification.channel, &notification.payload);
+            self.stream.write_message_noflush(message)?;
+        }
+        self.stream.flush()?;
+        Ok(true)
+    }
+
     /// Receive a single message, process it, then send a proper response.
     fn process_message(&mut self) -> PgResult<()> {
+        if let MessageLoopState::ReadyForQuery = self.loop_state {
+            self.wait_for_query()?;
+        }
+
         let message = self.stream.read_message()?;
         tlog!(Debug, "received {message:?}");
 
@@ -195,6 +288,7 @@ impl<S: io::Read + io::Write> PgClient<S> {
 
     fn process_error(&mut self, error: PgError) -> PgResult<()> {
         tlog!(Debug, "processing error: {error:?}");
+        self.backend.fail_transaction();
 
         // First and foremost, try sending the error to client.
         // True IO errors and stream-level protocol violations are treated the same;
@@ -232,7 +326,17 @@ impl<S: io::Read + io::Write> PgClient<S> {
         tlog!(Info, "entering the message handling loop");
         while !self.is_terminated() {
             if let MessageLoopState::ReadyForQuery = self.loop_state {
-                self.stream.write_message(messages::ready_for_query())?;
+                for warning in self.backend.take_warnings() {
+                    self.stream
+                        .write_message_noflush(messages::warning(warning))?;
+                }
+                // Like in Postgres, notifications are sent between transactions.
+                if !self.backend.in_transaction_block() {
+                    self.send_notifications()?;
+                }
+                let status = self.backend.transaction_status();
+                self.stream
+                    .write_message(messages::ready_for_query(status))?;
             }
 
             match self.process_message() {
@@ -1,8 +1,8 @@
 use crate::pgproto::error::{AuthError, PgError};
-use crate::pgproto::stream::{FeMessage, PgStream};
+use crate::pgproto::stream::{BeMessage, FeMessage, PgStream};
 use crate::pgproto::{error::PgResult, messages};
 use crate::sasl::{Mechanism, Step};
-use crate::scram::ServerSecret;
+use crate::scram::{ServerSecret, SCRAM_RAW_NONCE_LEN};
 use crate::storage::Catalog;
 use crate::tlog;
 use pgwire::messages::startup::PasswordMessageFamily;
@@ -36,15 +36,145 @@ fn read_password_message(
     Ok(message)
 }
 
+/// Authentication scheme consulted by the startup path to check the
+/// credentials of a client. Besides the built-in [`CleartextAuthenticator`]
+/// and [`Md5Authenticator`], it may be implemented by an embedder to plug
+/// LDAP or an external authentication service, see
+/// [`crate::pgproto::set_authenticator`].
+pub trait Authenticator {
+    /// Start the authentication of a new client. Every exchange has its own
+    /// state, e.g. the salt of MD5 must never be reused by different clients.
+    fn start(&self) -> Box<dyn AuthExchange>;
+}
+
+/// Authentication of a single client started by [`Authenticator::start`].
+pub trait AuthExchange {
+    /// Authentication request asking the client for its credential.
+    fn request(&self) -> BeMessage;
+
+    /// Check the credential message the client has sent in response to
+    /// [`Self::request`]. A failure is reported to the client as `28P01`.
+    fn authenticate(&self, user: &str, message: PasswordMessageFamily) -> Result<(), AuthError>;
+}
+
+/// Extract the password from a client's credential message.
+fn into_password(user: &str, message: PasswordMessageFamily) -> Result<String, AuthError> {
+    message
+        .into_password()
+        .map(|x| x.password)
+        .map_err(|error| AuthError {
+            user: user.into(),
+            extra: Some(format_smolstr!("unexpected credential message: {error}")),
+        })
+}
+
+/// Check a password with tarantool against the auth method of the user.
+fn check_password(
+    user: &str,
+    password: String,
+    salt: [u8; 4],
+    method: AuthMethod,
+) -> Result<(), AuthError> {
+    let mut salt_buf = [0u8; crate::auth::SALT_MIN_LEN];
+    salt_buf[0..4].copy_from_slice(&salt);
+
+    crate::auth::do_authenticate(user, password, &salt_buf, method)
+        // Raise a proper auth error with an explanation as needed.
+        .map_err(|_| AuthError {
+            user: user.into(),
+            extra: explain_box_error(method),
+        })
+}
+
+/// Asks the client for its password in open form and checks it with the
+/// given auth method, e.g. LDAP.
+#[derive(Clone, Copy)]
+pub struct CleartextAuthenticator {
+    method: AuthMethod,
+}
+
+impl CleartextAuthenticator {
+    /// Returns `None` if the auth method can't check a password in open form,
+    /// e.g. MD5 and chap-sha1 expect a salted hash from the client.
+    pub fn new(method: AuthMethod) -> Option<Self> {
+        match method {
+            AuthMethod::Ldap => Some(Self { method }),
+            AuthMethod::Md5 | AuthMethod::ChapSha1 | AuthMethod::ScramSha256 => None,
+        }
+    }
+}
+
+impl Authenticator for CleartextAuthenticator {
+    fn start(&self) -> Box<dyn AuthExchange> {
+        Box::new(*self)
+    }
+}
+
+impl AuthExchange for CleartextAuthenticator {
+    fn request(&self) -> BeMessage {
+        messages::cleartext_auth_request()
+    }
+
+    fn authenticate(&self, user: &str, message: PasswordMessageFamily) -> Result<(), AuthError> {
+        let password = into_password(user, message)?;
+        check_password(user, password, [0; 4], self.method)
+    }
+}
+
+/// Asks the client for the MD5 hash of its password salted with a random
+/// salt generated anew for every client.
+#[derive(Default)]
+pub struct Md5Authenticator;
+
+impl Authenticator for Md5Authenticator {
+    fn start(&self) -> Box<dyn AuthExchange> {
+        Box::new(Md5Exchange {
+            salt: rand::random(),
+        })
+    }
+}
+
+struct Md5Exchange {
+    salt: [u8; 4],
+}
+
+impl AuthExchange for Md5Exchange {
+    fn request(&self) -> BeMessage {
+        messages::md5_auth_request(&self.salt)
+    }
+
+    fn authenticate(&self, user: &str, message: PasswordMessageFamily) -> Result<(), AuthError> {
+        let password = into_password(user, message)?;
+        check_password(user, password, self.salt, AuthMethod::Md5)
+    }
+}
+
+fn auth_exchange(
+    stream: &mut PgStream<impl io::Read + io::Write>,
+    user: &str,
+    authenticator: &dyn Authenticator,
+) -> PgResult<()> {
+    let exchange = authenticator.start();
+    stream.write_message(exchange.request())?;
+    let message = read_password_message(stream)?;
+    exchange.authenticate(user, message)?;
+    stream.write_message_noflush(messages::auth_ok())?;
+
+    Ok(())
+}
+
 fn auth_exchange_classic(
     stream: &mut PgStream<impl io::Read + io::Write>,
     user: &str,
     auth: &AuthDef,
-    salt: [u8; 4],
 ) -> PgResult<()> {
-    stream.write_message(match auth.method {
-        AuthMethod::Md5 => messages::md5_auth_request(&salt),
-        AuthMethod::Ldap => messages::cleartext_auth_request(),
+    match auth.method {
+        AuthMethod::Md5 => auth_exchange(stream, user, &Md5Authenticator),
+        AuthMethod::Ldap => {
+            let authenticator = CleartextAuthenticator::new(AuthMethod::Ldap)
+                .expect("LDAP checks passwords in open form");
+            auth_exchange(stream, user, &authenticator)
+        }
         AuthMethod::ChapSha1 => {
             tlog!(
                 Warning,
@@ -53,31 +183,12 @@ fn auth_exchange_classic(
 
             // We cannot return a more specific error message because
             // it'll allow an attacker to brute force user names.
-            return Err(AuthError::for_username(user).into());
+            Err(AuthError::for_username(user).into())
         }
         AuthMethod::ScramSha256 => {
             unreachable!("auth_exchange cannot handle scram-sha256");
         }
-    })?;
-
-    let password = read_password_message(stream)?
-        .into_password()
-        .map(|x| x.password)
-        .map_err(io::Error::other)?;
-
-    let mut salt_buf = [0u8; crate::auth::SALT_MIN_LEN];
-    salt_buf[0..4].copy_from_slice(&salt);
-
-    crate::auth::do_authenticate(user, password, &salt_buf, auth.method)
-        // Raise a proper auth error with an explanation as needed.
-        .map_err(|_| AuthError {
-            user: user.into(),
-            extra: explain_box_error(auth.method),
-        })?;
-
-    stream.write_message_noflush(messages::auth_ok())?;
-
-    Ok(())
+    }
 }
 
 fn read_sasl_initial(
@@ -103,17 +214,22 @@ fn read_sasl_initial(
     Ok(bytes)
 }
 
+/// Perform the SCRAM-SHA-256 exchange: advertise the mechanism, then
+/// process client-first and client-final messages replying with
+/// server-first and server-final ones. `nonce` generates the server's
+/// part of the nonce.
 fn do_auth_exchange_sasl(
     stream: &mut PgStream<impl io::Read + io::Write>,
     user: &str,
     secret: &ServerSecret,
+    nonce: fn() -> [u8; SCRAM_RAW_NONCE_LEN],
 ) -> PgResult<()> {
     stream.write_message(messages::sasl_auth_request())?;
     let mut input_bytes = read_sasl_initial(stream, user)?;
 
     let mut method = crate::scram::Exchange::new(
         secret,
-        rand::random,
+        nonce,
         // TODO: provide server certificate hash for channel binding.
         crate::scram::TlsServerEndpoint::Undefined,
     );
@@ -155,7 +271,7 @@ fn auth_exchange_sasl(
     // checks, execute triggers and update the credentials.
     let main_res = crate::auth::authenticate_ext(user, || {
         // Now do the exchange itself and return the result.
-        do_auth_exchange_sasl(stream, user, secret)
+        do_auth_exchange_sasl(stream, user, secret, rand::random)
     });
 
     // The 2nd error is from `authenticate_ext` itself.
@@ -165,18 +281,51 @@ fn auth_exchange_sasl(
     Ok(())
 }
 
+/// Perform the exchange of a custom `authenticator` the same way as the
+/// built-in ones: tarantool checks the user, runs the auth triggers and
+/// switches the session's credentials.
+fn auth_exchange_custom(
+    stream: &mut PgStream<impl io::Read + io::Write>,
+    user: &str,
+    authenticator: &dyn Authenticator,
+) -> PgResult<()> {
+    let main_res =
+        crate::auth::authenticate_ext(user, || auth_exchange(stream, user, authenticator));
+
+    // The 2nd error is from `authenticate_ext` itself.
+    // We have to map it in order to not leak any details.
+    main_res?.map_err(|_| AuthError::for_username(user))?;
+
+    Ok(())
+}
+
 /// Perform exchange of authentication messages and authentication.
 /// Authentication failure is treated as an error.
+///
+/// If an `authenticator` is given, it replaces the built-in authentication
+/// based on the auth method of the user from the catalog.
 pub fn authenticate(
     stream: &mut PgStream<impl io::Read + io::Write>,
     user: &str,
     storage: &Catalog,
+    authenticator: Option<&dyn Authenticator>,
 ) -> PgResult<()> {
     // Do not allow attackers to detect which users exist through returned error.
     // Futhermore, do not throw any errors too early -- we can use scram's mock
     // secret to make "missing user" (almost) indistinguishable from "bad password"
     // from the standpoint of timings (at least for scram).
     let maybe_auth = crate::auth::try_get_auth_def(storage, user);
+
+    if let Some(authenticator) = authenticator {
+        if maybe_auth.is_none() {
+            // Still go through the exchange so that a missing user looks
+            // like a bad password to the client.
+            let _ = auth_exchange(stream, user, authenticator);
+            return Err(AuthError::for_username(user).into());
+        }
+        return auth_exchange_custom(stream, user, authenticator);
+    }
+
     match maybe_auth {
         Some(auth) if auth.method == AuthMethod::ScramSha256 => {
             let secret = ServerSecret::parse(&auth.data).expect("invalid AuthDef in catalog");
@@ -190,10 +339,176 @@ pub fn authenticate(
             auth_exchange_sasl(stream, user, &secret)?;
         }
         Some(auth) => {
-            let salt = rand::random();
-            auth_exchange_classic(stream, user, &auth, salt)?;
+            auth_exchange_classic(stream, user, &auth)?;
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BytesMut;
+    use pgwire::messages::startup::{Password, SASLInitialResponse, SASLResponse, Startup};
+    use pgwire::messages::Message;
+    use std::io::{Read, Write};
+
+    /// Socket replaying the messages of a client.
+    struct MockSocket {
+        input: io::Cursor<Vec<u8>>,
+        output: Vec<u8>,
+    }
+
+    impl io::Read for MockSocket {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl io::Write for MockSocket {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.output.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Stream of a client that has sent a startup message followed by
+    /// the messages written by `encode`.
+    fn client_stream(encode: impl FnOnce(&mut BytesMut)) -> PgStream<MockSocket> {
+        let mut input = BytesMut::new();
+        Startup::new().encode(&mut input).unwrap();
+        encode(&mut input);
+        let socket = MockSocket {
+            input: io::Cursor::new(input.to_vec()),
+            output: Vec::new(),
+        };
+        let mut stream = PgStream::new(socket);
+        let FeMessage::Startup(_) = stream.read_message().unwrap() else {
+            panic!("expected a startup message");
+        };
+        stream
+    }
+
+    fn password_stream(password: &str) -> PgStream<MockSocket> {
+        client_stream(|input| Password::new(password.into()).encode(input).unwrap())
+    }
+
+    fn sent_by_server(stream: &PgStream<MockSocket>, message: &str) -> bool {
+        let output = &stream.as_ref().output;
+        output
+            .windows(message.len())
+            .any(|window| window == message.as_bytes())
+    }
+
+    /// Accepts only the user "alice" with the password "secret".
+    struct MockAuthenticator;
+
+    impl Authenticator for MockAuthenticator {
+        fn start(&self) -> Box<dyn AuthExchange> {
+            Box::new(MockAuthenticator)
+        }
+    }
+
+    impl AuthExchange for MockAuthenticator {
+        fn request(&self) -> BeMessage {
+            messages::cleartext_auth_request()
+        }
+
+        fn authenticate(
+            &self,
+            user: &str,
+            message: PasswordMessageFamily,
+        ) -> Result<(), AuthError> {
+            let password = into_password(user, message)?;
+            if user != "alice" || password != "secret" {
+                return Err(AuthError::for_username(user));
+            }
+            Ok(())
+        }
+    }
+
+    #[::tarantool::test]
+    fn custom_authenticator() {
+        let mut stream = password_stream("secret");
+        auth_exchange(&mut stream, "alice", &MockAuthenticator).unwrap();
+
+        let mut stream = password_stream("secret");
+        let error = auth_exchange(&mut stream, "bob", &MockAuthenticator).unwrap_err();
+        assert!(matches!(error, PgError::AuthError(_)));
+        assert_eq!(error.code().as_str(), "28P01");
+        assert_eq!(error.to_string(), "authentication failed for user 'bob'");
+
+        let mut stream = password_stream("wrong");
+        let error = auth_exchange(&mut stream, "alice", &MockAuthenticator).unwrap_err();
+        assert_eq!(error.code().as_str(), "28P01");
+    }
+
+    #[::tarantool::test]
+    fn md5_salt_per_exchange() {
+        // The request carrying the salt is sent before the password is checked.
+        let request = || {
+            let mut stream = password_stream("md5");
+            let _ = auth_exchange(&mut stream, "alice", &Md5Authenticator);
+            stream.as_ref().output.clone()
+        };
+        assert_ne!(request(), request());
+    }
+
+    #[::tarantool::test]
+    fn cleartext_methods() {
+        assert!(CleartextAuthenticator::new(AuthMethod::Ldap).is_some());
+        assert!(CleartextAuthenticator::new(AuthMethod::Md5).is_none());
+        assert!(CleartextAuthenticator::new(AuthMethod::ChapSha1).is_none());
+        assert!(CleartextAuthenticator::new(AuthMethod::ScramSha256).is_none());
+    }
+
+    #[::tarantool::test]
+    fn scram_sha256_handshake() {
+        // Taken from the snapshot tests of `crate::scram`.
+        const NONCE: [u8; SCRAM_RAW_NONCE_LEN] = [
+            1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18,
+        ];
+        let password = "великолепный пароль";
+        let secret = ServerSecret::generate(password.as_ref(), &NONCE, 128);
+
+        let client_first = "n,,n=,r=0LTU/4V9YVBnmS+JfES49pSv";
+        let client_final_without_proof =
+            "c=biws,r=0LTU/4V9YVBnmS+JfES49pSvAQIDBAUGBwgJCgsMDQ4PEBES";
+        let proof = "U2jUhAgLixSn2NqeQH7wX1myjSzr4rcDG/Bn1Rimgk0=";
+        let server_first =
+            "r=0LTU/4V9YVBnmS+JfES49pSvAQIDBAUGBwgJCgsMDQ4PEBES,s=AQIDBAUGBwgJCgsMDQ4PEBES,i=128";
+        let server_final = "v=Gqo+LpAxGT3lNRYBkWOVfXwcmTgHCY2gijoqGLk1AFw=";
+
+        let scram_stream = |proof: &str| {
+            let client_final = format!("{client_final_without_proof},p={proof}");
+            client_stream(|input| {
+                let method = "SCRAM-SHA-256".to_string();
+                let data = Some(client_first.as_bytes().to_vec().into());
+                SASLInitialResponse::new(method, data)
+                    .encode(input)
+                    .unwrap();
+                SASLResponse::new(client_final.into_bytes().into())
+                    .encode(input)
+                    .unwrap();
+            })
+        };
+
+        let mut stream = scram_stream(proof);
+        do_auth_exchange_sasl(&mut stream, "", &secret, || NONCE).unwrap();
+        assert!(sent_by_server(&stream, "SCRAM-SHA-256"));
+        assert!(sent_by_server(&stream, server_first));
+        assert!(sent_by_server(&stream, server_final));
+
+        // A proof of a wrong password.
+        let mut stream = scram_stream("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=");
+        let error = do_auth_exchange_sasl(&mut stream, "", &secret, || NONCE).unwrap_err();
+        assert!(matches!(error, PgError::AuthError(_)));
+        assert_eq!(error.code().as_str(), "28P01");
+        assert!(sent_by_server(&stream, server_first));
+        assert!(!sent_by_server(&stream, server_final));
+    }
+}
@@ -99,6 +99,9 @@ pub fn process_execute_message(
         ExecuteResult::Empty => {
             stream.write_message(messages::empty_query_response())?;
         }
+        ExecuteResult::CopyOut { .. } => {
+            unreachable!("COPY is supported only in simple query")
+        }
     }
 
     Ok(())
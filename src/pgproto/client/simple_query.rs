@@ -30,6 +30,32 @@ pub fn process_query_message(
             }
             stream.write_message(messages::command_complete_with_row_count(&tag, row_count))?;
         }
+        ExecuteResult::CopyOut {
+            tag,
+            mut rows,
+            encoder,
+            mut is_finished,
+        } => {
+            stream.write_message_noflush(messages::copy_out_response(rows.fields().len()))?;
+            if let Some(header) = encoder.header(&rows) {
+                stream.write_message_noflush(messages::copy_data(header))?;
+            }
+            // An error aborts the copy, the client gets ErrorResponse instead of CopyDone.
+            let mut row_count = 0;
+            loop {
+                while let Some(line) = encoder.encode_next(&mut rows)? {
+                    stream.write_message_noflush(messages::copy_data(line))?;
+                    row_count += 1;
+                }
+                if is_finished {
+                    break;
+                }
+                stream.flush()?;
+                (rows, is_finished) = backend.copy_batch()?;
+            }
+            stream.write_message_noflush(messages::copy_done())?;
+            stream.write_message(messages::command_complete_with_row_count(&tag, row_count))?;
+        }
         ExecuteResult::Empty => {
             stream.write_message(messages::empty_query_response())?;
         }
@@ -22,6 +22,8 @@ pub struct ClientParams {
     pub options: PartialOptions,
     pub is_statement_invalidation: bool,
     pub is_query_metadata: bool,
+    /// Name of the client application, attributed to its queries in the logs.
+    pub application_name: String,
     pub _rest: BTreeMap<String, String>,
     // NB: add more params as needed.
     // Keep in mind that a client is required to send only "user".
@@ -135,11 +137,14 @@ impl ClientParams {
             is_statement_invalidation = value
         }
 
+        let application_name = parameters.remove("application_name").unwrap_or_default();
+
         Ok(Self {
             username,
             options: options_accumulator,
             is_query_metadata,
             is_statement_invalidation,
+            application_name,
             _rest: parameters,
         })
     }
@@ -17,8 +17,23 @@ pub enum PgErrorCode {
     InvalidBinaryRepresentation,
     InvalidPassword,
     InvalidTextRepresentation,
+    NumericValueOutOfRange,
     IoError,
     ProtocolViolation,
+    NotNullViolation,
+    CheckViolation,
+    NoActiveSqlTransaction,
+    ActiveSqlTransaction,
+    InFailedSqlTransaction,
+    InvalidSavepointSpecification,
+    UndefinedTable,
+    UndefinedColumn,
+    IdleInTransactionSessionTimeout,
+    IdleSessionTimeout,
+    InvalidCursorName,
+    InvalidCursorState,
+    SyntaxError,
+    LockNotAvailable,
 }
 
 impl PgErrorCode {
@@ -37,10 +52,54 @@ impl PgErrorCode {
             PgErrorCode::InvalidBinaryRepresentation => "22P03",
             PgErrorCode::InvalidPassword => "28P01",
             PgErrorCode::InvalidTextRepresentation => "22P02",
+            PgErrorCode::NumericValueOutOfRange => "22003",
             PgErrorCode::IoError => "58030",
             PgErrorCode::ProtocolViolation => "08P01",
+            PgErrorCode::NotNullViolation => "23502",
+            PgErrorCode::CheckViolation => "23514",
+            PgErrorCode::NoActiveSqlTransaction => "25P01",
+            PgErrorCode::ActiveSqlTransaction => "25001",
+            PgErrorCode::InFailedSqlTransaction => "25P02",
+            PgErrorCode::InvalidSavepointSpecification => "3B001",
+            PgErrorCode::UndefinedTable => "42P01",
+            PgErrorCode::UndefinedColumn => "42703",
+            PgErrorCode::IdleInTransactionSessionTimeout => "25P03",
+            PgErrorCode::IdleSessionTimeout => "57P05",
+            PgErrorCode::InvalidCursorName => "34000",
+            PgErrorCode::InvalidCursorState => "24000",
+            PgErrorCode::SyntaxError => "42601",
+            PgErrorCode::LockNotAvailable => "55P03",
         }
     }
+
+    /// Get the code corresponding to an SQL error.
+    pub fn from_sbroad(e: &sql::errors::SbroadError) -> Self {
+        use sql::errors::{Entity, SbroadError};
+
+        match e {
+            SbroadError::NotNullViolation(..) => PgErrorCode::NotNullViolation,
+            SbroadError::CheckViolation(..) => PgErrorCode::CheckViolation,
+            SbroadError::InvalidTextRepresentation(..) => PgErrorCode::InvalidTextRepresentation,
+            SbroadError::NumericValueOutOfRange(..) => PgErrorCode::NumericValueOutOfRange,
+            SbroadError::DispatchError(msg) | SbroadError::VdbeError(msg)
+                if is_integer_out_of_range(msg) =>
+            {
+                PgErrorCode::NumericValueOutOfRange
+            }
+            SbroadError::Unsupported(..) => PgErrorCode::FeatureNotSupported,
+            SbroadError::NotFound(Entity::Table, _) => PgErrorCode::UndefinedTable,
+            SbroadError::NotFound(Entity::Column, _) => PgErrorCode::UndefinedColumn,
+            _ => PgErrorCode::InternalError,
+        }
+    }
+}
+
+/// Storages check the range of narrow integer types in `_PICO_INT_RANGE`
+/// builtin, its error only comes back to the router as a message.
+fn is_integer_out_of_range(msg: &str) -> bool {
+    ["smallint out of range", "integer out of range"]
+        .iter()
+        .any(|pattern| msg.contains(pattern))
 }
 
 pub type DynError = dyn std::error::Error + Send + Sync;
@@ -172,6 +231,14 @@ pub enum PgError {
     #[error(transparent)]
     DecodingError(#[from] DecodingError),
 
+    // Error message format is compatible with Postgres.
+    #[error("terminating connection due to idle-session timeout")]
+    IdleSessionTimeout,
+
+    // Error message format is compatible with Postgres.
+    #[error("terminating connection due to idle-in-transaction timeout")]
+    IdleInTransactionSessionTimeout,
+
     // Generic IO error (TLS/SSL errors also go here).
     #[error("IO error: {0}")]
     IoError(#[from] io::Error),
@@ -236,15 +303,24 @@ impl IntoBoxError for PgError {}
 
 impl PgError {
     /// Convert the error into a corresponding postgres error code.
-    fn code(&self) -> PgErrorCode {
+    pub(crate) fn code(&self) -> PgErrorCode {
         match self {
             Self::SslRequired => PgErrorCode::InvalidAuthorizationSpecification,
             Self::ProtocolViolation(_) => PgErrorCode::ProtocolViolation,
             Self::FeatureNotSupported(_) => PgErrorCode::FeatureNotSupported,
             Self::AuthError(_) => PgErrorCode::InvalidPassword,
+            Self::IdleSessionTimeout => PgErrorCode::IdleSessionTimeout,
+            Self::IdleInTransactionSessionTimeout => PgErrorCode::IdleInTransactionSessionTimeout,
             Self::WithExplicitCode(PedanticError(code, _)) => *code,
             Self::DecodingError(DecodingError(code, _)) => *code,
             Self::IoError(_) => PgErrorCode::IoError,
+            Self::PicodataError(e) => match **e {
+                crate::traft::error::Error::Sbroad(ref e) => PgErrorCode::from_sbroad(e),
+                ref e if is_integer_out_of_range(&e.to_string()) => {
+                    PgErrorCode::NumericValueOutOfRange
+                }
+                _ => PgErrorCode::InternalError,
+            },
 
             // TODO: make the code depending on the error kind
             _otherwise => PgErrorCode::InternalError,
@@ -253,8 +329,12 @@ impl PgError {
 
     /// Build [`pgwire`]'s error info from [`PgError`].
     pub fn info(&self) -> pgwire::error::ErrorInfo {
+        let severity = match self {
+            Self::IdleSessionTimeout | Self::IdleInTransactionSessionTimeout => "FATAL",
+            _ => "ERROR",
+        };
         pgwire::error::ErrorInfo::new(
-            "ERROR".to_string(),
+            severity.to_string(),
             self.code().as_str().to_string(),
             self.to_string(),
         )
@@ -272,4 +352,17 @@ impl PgError {
 
         false
     }
+
+    pub fn is_sbroad_table_not_found_error(&self) -> bool {
+        use crate::traft::error::Error;
+        use sql::errors::{Entity, SbroadError};
+
+        if let Self::PicodataError(e) = self {
+            if let Error::Sbroad(SbroadError::NotFound(Entity::Table, _)) = **e {
+                return true;
+            }
+        }
+
+        false
+    }
 }
@@ -2,11 +2,14 @@ use super::backend::describe::CommandTag;
 use super::stream::BeMessage;
 use bytes::Bytes;
 use pgwire::error::ErrorInfo;
+use pgwire::messages::copy::{CopyData, CopyDone, CopyOutResponse};
 use pgwire::messages::data::{self, DataRow, ParameterDescription, RowDescription};
 use pgwire::messages::extendedquery::{
     BindComplete, CloseComplete, ParseComplete, PortalSuspended,
 };
-use pgwire::messages::response::{NoticeResponse, ReadyForQuery, SslResponse, TransactionStatus};
+use pgwire::messages::response::{
+    NoticeResponse, NotificationResponse, ReadyForQuery, SslResponse, TransactionStatus,
+};
 use pgwire::messages::{response, startup::*};
 use postgres_types::Oid;
 
@@ -21,6 +24,26 @@ pub fn notice(message: String) -> BeMessage {
     ]))
 }
 
+/// Warning for the frontend.
+pub fn warning(message: String) -> BeMessage {
+    BeMessage::NoticeResponse(NoticeResponse::new(vec![
+        (b'S', "WARNING".to_string()),
+        (b'C', "01000".to_string()),
+        (b'M', message),
+    ]))
+}
+
+/// Notification sent with `NOTIFY` to a channel the frontend listens to.
+pub fn notification_response(channel: &str, payload: &str) -> BeMessage {
+    // The notifying session may belong to another instance,
+    // so there is no meaningful process id to report.
+    BeMessage::NotificationResponse(NotificationResponse::new(
+        0,
+        channel.to_string(),
+        payload.to_string(),
+    ))
+}
+
 /// MD5AuthRequest requests md5 password from the frontend.
 pub fn md5_auth_request(salt: &[u8; 4]) -> BeMessage {
     BeMessage::Authentication(Authentication::MD5Password(salt.to_vec()))
@@ -58,8 +81,9 @@ pub const fn auth_ok() -> BeMessage {
 }
 
 /// ReadyForQuery informs the frontend that it can safely send a new command.
-pub fn ready_for_query() -> BeMessage {
-    BeMessage::ReadyForQuery(ReadyForQuery::new(TransactionStatus::Idle))
+/// The status tells whether the frontend is in a transaction block and whether it has failed.
+pub fn ready_for_query(status: TransactionStatus) -> BeMessage {
+    BeMessage::ReadyForQuery(ReadyForQuery::new(status))
 }
 
 /// ErrorResponse informs the client about the error.
@@ -122,3 +146,19 @@ pub fn close_complete() -> BeMessage {
 pub fn parameter_description(type_ids: Vec<Oid>) -> BeMessage {
     BeMessage::ParameterDescription(ParameterDescription::new(type_ids))
 }
+
+/// CopyOutResponse starts `COPY TO STDOUT` of rows with the given number
+/// of columns, all of them in text format.
+pub fn copy_out_response(columns: usize) -> BeMessage {
+    BeMessage::CopyOutResponse(CopyOutResponse::new(0, columns as i16, vec![0; columns]))
+}
+
+/// CopyData carries a line of `COPY TO STDOUT` output.
+pub fn copy_data(data: Bytes) -> BeMessage {
+    BeMessage::CopyData(CopyData::new(data))
+}
+
+/// CopyDone finishes `COPY TO STDOUT` output.
+pub fn copy_done() -> BeMessage {
+    BeMessage::CopyDone(CopyDone::new())
+}
@@ -2,7 +2,15 @@ use super::tls::{TlsAcceptor, TlsStream};
 use crate::tlog;
 use bytes::{BufMut, BytesMut};
 use pgwire::messages::{DecodeContext, SslNegotiationMetaMessage};
-use std::io::{self, ErrorKind::UnexpectedEof, Write};
+use std::io::{
+    self,
+    ErrorKind::{TimedOut, UnexpectedEof},
+    Write,
+};
+use std::os::fd::AsRawFd;
+use std::time::Duration;
+use tarantool::coio::coio_wait;
+use tarantool::ffi::tarantool::CoIOFlags;
 
 // Public re-exports.
 pub use pgwire::messages::{PgWireBackendMessage as BeMessage, PgWireFrontendMessage as FeMessage};
@@ -158,6 +166,28 @@ impl<S: io::Read + io::Write> PgStream<S> {
     }
 }
 
+impl<S: io::Read + io::Write + AsRawFd> PgStream<S> {
+    /// Wait until the client sends more data. Return `false` on timeout
+    /// or when the fiber is woken up.
+    pub fn wait_readable(&self, timeout: Duration) -> io::Result<bool> {
+        // Buffered data must be processed before we wait for the socket.
+        let has_pending_data = match &self.socket {
+            PgSocket::Plain(_) => false,
+            PgSocket::Secure(ssl_stream) => ssl_stream.ssl().pending() > 0,
+        };
+        if !self.ibuf.is_empty() || has_pending_data {
+            return Ok(true);
+        }
+
+        let fd = self.as_ref().as_raw_fd();
+        match coio_wait(fd, CoIOFlags::READ, timeout.as_secs_f64()) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == TimedOut => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
 /// Write part of the stream.
 impl<S: io::Read + io::Write> PgStream<S> {
     /// Flush all buffered messages to an underlying byte stream.
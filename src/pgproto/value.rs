@@ -5,8 +5,8 @@ use pgwire::types::{format::FormatOptions, ToSqlText};
 use postgres_types::{FromSql, IsNull, Oid, ToSql, Type};
 use smol_str::{format_smolstr, ToSmolStr};
 use sql::{
-    frontend::sql::{try_parse_bool, try_parse_datetime},
-    ir::value::Value as SbroadValue,
+    frontend::sql::{try_parse_bool, try_parse_bytea, try_parse_datetime},
+    ir::value::{bytea_to_hex, Value as SbroadValue},
 };
 use std::{
     fmt::Debug,
@@ -113,6 +113,55 @@ impl ToSql for Uuid {
     postgres_types::to_sql_checked!();
 }
 
+/// Bytea wrapper for smooth encoding & decoding.
+#[derive(Debug, Clone)]
+#[repr(transparent)]
+pub struct Bytea(Vec<u8>);
+
+impl FromStr for Bytea {
+    type Err = Box<DynError>;
+
+    #[inline(always)]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        try_parse_bytea(s)
+            .map(Self)
+            .ok_or_else(|| DecodingError::bad_lit_of_type(s, "bytea").into())
+    }
+}
+
+impl<'a> FromSql<'a> for Bytea {
+    #[inline(always)]
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<DynError>> {
+        <Vec<u8>>::from_sql(ty, raw).map(Bytea)
+    }
+
+    postgres_types::accepts!(BYTEA);
+}
+
+impl ToSqlText for Bytea {
+    /// Bytea values are sent in the hex format, as PostgreSQL does by default.
+    #[inline(always)]
+    fn to_sql_text(
+        &self,
+        _ty: &Type,
+        out: &mut BytesMut,
+        _options: &FormatOptions,
+    ) -> Result<IsNull, Box<DynError>> {
+        write!(out, "\\x{}", bytea_to_hex(&self.0))?;
+        Ok(IsNull::No)
+    }
+}
+
+impl ToSql for Bytea {
+    #[inline(always)]
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<DynError>> {
+        self.0.to_sql(ty, out)
+    }
+
+    postgres_types::accepts!(BYTEA);
+    postgres_types::to_sql_checked!();
+}
+
 /// Decimal wrapper for smooth encoding & decoding.
 #[derive(Debug, Copy, Clone, serde::Deserialize)]
 #[repr(transparent)]
@@ -295,9 +344,58 @@ pub enum PgValue {
     Json(Json),
     Uuid(Uuid),
     Numeric(Decimal),
+    Bytea(Bytea),
     Null,
 }
 
+impl ToSqlText for PgValue {
+    fn to_sql_text(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+        options: &FormatOptions,
+    ) -> Result<IsNull, Box<DynError>> {
+        match self {
+            PgValue::Float(v) => v.to_sql_text(ty, out, options),
+            PgValue::Integer(v) => v.to_sql_text(ty, out, options),
+            PgValue::Boolean(v) => v.to_sql_text(ty, out, options),
+            PgValue::Text(v) => v.to_sql_text(ty, out, options),
+            PgValue::Timestamptz(v) => v.to_sql_text(ty, out, options),
+            PgValue::Json(v) => v.to_sql_text(ty, out, options),
+            PgValue::Uuid(v) => v.to_sql_text(ty, out, options),
+            PgValue::Numeric(v) => v.to_sql_text(ty, out, options),
+            PgValue::Bytea(v) => v.to_sql_text(ty, out, options),
+            PgValue::Null => Ok(IsNull::Yes),
+        }
+    }
+}
+
+impl ToSql for PgValue {
+    #[inline(always)]
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<DynError>> {
+        Ok(self.encode_binary(ty, out)?)
+    }
+
+    postgres_types::accepts!(
+        INT2,
+        INT4,
+        INT8,
+        FLOAT4,
+        FLOAT8,
+        BOOL,
+        TEXT,
+        VARCHAR,
+        BYTEA,
+        TIMESTAMP,
+        TIMESTAMPTZ,
+        JSON,
+        JSONB,
+        UUID,
+        NUMERIC
+    );
+    postgres_types::to_sql_checked!();
+}
+
 impl TryFrom<PgValue> for SbroadValue {
     type Error = PgError;
 
@@ -310,6 +408,7 @@ impl TryFrom<PgValue> for SbroadValue {
             PgValue::Numeric(v) => Ok(SbroadValue::from(v.0)),
             PgValue::Uuid(v) => Ok(SbroadValue::from(v.0)),
             PgValue::Timestamptz(v) => Ok(SbroadValue::from(v.0)),
+            PgValue::Bytea(v) => Ok(SbroadValue::Bytea(v.0)),
             PgValue::Null => Ok(SbroadValue::Null),
             PgValue::Json(_) => {
                 // Anyhow, currently Sbroad cannot work with these types.
@@ -404,6 +503,7 @@ impl PgValue {
                     rmpv::ext::from_value(Value::Ext(4, v)).map_err(EncodingError::new)?;
                 Ok(PgValue::Timestamptz(datetime))
             }
+            (Value::Binary(v), Type::BYTEA) => Ok(PgValue::Bytea(Bytea(v))),
             (any, Type::JSON | Type::JSONB) => Ok(PgValue::Json(Json(any))),
 
             (value, ty) => Err(PgError::FeatureNotSupported(format_smolstr!(
@@ -412,6 +512,42 @@ impl PgValue {
         }
     }
 
+    /// Encode the value in the PostgreSQL binary format of the given type.
+    /// It is used for the `DataRow` fields with binary format.
+    pub fn encode_binary(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, EncodingError> {
+        let res = match (self, ty.clone()) {
+            (PgValue::Null, _) => Ok(IsNull::Yes),
+            (PgValue::Integer(v), Type::INT8) => v.to_sql(ty, out),
+            (PgValue::Integer(v), Type::INT4) => i32::try_from(*v)
+                .map_err(EncodingError::new)?
+                .to_sql(ty, out),
+            (PgValue::Integer(v), Type::INT2) => i16::try_from(*v)
+                .map_err(EncodingError::new)?
+                .to_sql(ty, out),
+            (PgValue::Float(v), Type::FLOAT8) => v.to_sql(ty, out),
+            (PgValue::Float(v), Type::FLOAT4) => (*v as f32).to_sql(ty, out),
+            (PgValue::Boolean(v), Type::BOOL) => v.to_sql(ty, out),
+            (PgValue::Text(v), Type::TEXT | Type::VARCHAR) => v.to_sql(ty, out),
+            (PgValue::Text(v), Type::BYTEA) => v.as_bytes().to_sql(ty, out),
+            (PgValue::Bytea(v), Type::BYTEA) => v.to_sql(ty, out),
+            (PgValue::Timestamptz(v), Type::TIMESTAMPTZ) => v.to_sql(ty, out),
+            (PgValue::Timestamptz(v), Type::TIMESTAMP) => {
+                // Timestamp without time zone is sent as UTC time.
+                let datetime = v.0.into_inner().to_offset(time::UtcOffset::UTC);
+                time::PrimitiveDateTime::new(datetime.date(), datetime.time()).to_sql(ty, out)
+            }
+            (PgValue::Json(v), Type::JSON | Type::JSONB) => v.to_sql(ty, out),
+            (PgValue::Uuid(v), Type::UUID) => v.to_sql(ty, out),
+            (PgValue::Numeric(v), Type::NUMERIC) => v.to_sql(ty, out),
+            (value, ty) => {
+                return Err(EncodingError::new(format!(
+                    "{value:?} cannot be encoded as a value of type {ty}"
+                )))
+            }
+        };
+        res.map_err(EncodingError::new)
+    }
+
     fn decode_text(bytes: &[u8], ty: Type) -> Result<Self, DecodingError> {
         // TODO: rewrite this once rust supports generic closures
         fn do_parse<T: FromStr>(ty: Type, s: &str) -> Result<T, DecodingError>
@@ -433,6 +569,7 @@ impl PgValue {
             Type::UUID => PgValue::Uuid(do_parse(ty, s)?),
             Type::JSON | Type::JSONB => PgValue::Json(do_parse(ty, s)?),
             Type::TIMESTAMPTZ => PgValue::Timestamptz(do_parse(ty, s)?),
+            Type::BYTEA => PgValue::Bytea(do_parse(ty, s)?),
             _ => return Err(DecodingError::unsupported_type(ty)),
         })
     }
@@ -454,6 +591,7 @@ impl PgValue {
             Type::UUID => PgValue::Uuid(do_decode(ty, bytes)?),
             Type::JSON | Type::JSONB => PgValue::Json(do_decode(ty, bytes)?),
             Type::TIMESTAMPTZ => PgValue::Timestamptz(do_decode(ty, bytes)?),
+            Type::BYTEA => PgValue::Bytea(do_decode(ty, bytes)?),
             _ => return Err(DecodingError::unsupported_type(ty)),
         })
     }
@@ -475,3 +613,134 @@ impl PgValue {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Bool, Bytea, FieldFormat, PgValue, Timestamptz};
+    use bytes::BytesMut;
+    use pgwire::types::{format::FormatOptions, ToSqlText};
+    use postgres_types::{FromSql, IsNull, Type};
+
+    fn encode(value: &PgValue, ty: &Type) -> BytesMut {
+        let mut buf = BytesMut::new();
+        let is_null = value.encode_binary(ty, &mut buf).unwrap();
+        assert!(matches!(is_null, IsNull::No));
+        buf
+    }
+
+    #[test]
+    fn encode_binary_integers() {
+        let buf = encode(&PgValue::Integer(-42), &Type::INT2);
+        assert_eq!(&buf[..], &[0xff, 0xd6]);
+        assert_eq!(i16::from_sql(&Type::INT2, &buf).unwrap(), -42);
+
+        let buf = encode(&PgValue::Integer(1 << 20), &Type::INT4);
+        assert_eq!(&buf[..], &[0x00, 0x10, 0x00, 0x00]);
+        assert_eq!(i32::from_sql(&Type::INT4, &buf).unwrap(), 1 << 20);
+
+        let buf = encode(&PgValue::Integer(i64::MIN), &Type::INT8);
+        assert_eq!(i64::from_sql(&Type::INT8, &buf).unwrap(), i64::MIN);
+
+        // Value doesn't fit into the requested type.
+        let mut buf = BytesMut::new();
+        assert!(PgValue::Integer(1 << 20)
+            .encode_binary(&Type::INT2, &mut buf)
+            .is_err());
+    }
+
+    #[test]
+    fn encode_binary_floats() {
+        let buf = encode(&PgValue::Float(1.5), &Type::FLOAT4);
+        assert_eq!(&buf[..], &[0x3f, 0xc0, 0x00, 0x00]);
+        assert_eq!(f32::from_sql(&Type::FLOAT4, &buf).unwrap(), 1.5);
+
+        let buf = encode(&PgValue::Float(-0.25), &Type::FLOAT8);
+        assert_eq!(f64::from_sql(&Type::FLOAT8, &buf).unwrap(), -0.25);
+    }
+
+    #[test]
+    fn encode_binary_bool() {
+        let buf = encode(&PgValue::Boolean(Bool(true)), &Type::BOOL);
+        assert_eq!(&buf[..], &[1]);
+        assert!(bool::from_sql(&Type::BOOL, &buf).unwrap());
+
+        let buf = encode(&PgValue::Boolean(Bool(false)), &Type::BOOL);
+        assert!(!bool::from_sql(&Type::BOOL, &buf).unwrap());
+    }
+
+    #[test]
+    fn encode_binary_text_and_bytea() {
+        let value = PgValue::Text("привет".into());
+
+        let buf = encode(&value, &Type::TEXT);
+        assert_eq!(String::from_sql(&Type::TEXT, &buf).unwrap(), "привет");
+
+        let buf = encode(&value, &Type::VARCHAR);
+        assert_eq!(String::from_sql(&Type::VARCHAR, &buf).unwrap(), "привет");
+
+        let buf = encode(&value, &Type::BYTEA);
+        assert_eq!(
+            <Vec<u8>>::from_sql(&Type::BYTEA, &buf).unwrap(),
+            "привет".as_bytes()
+        );
+    }
+
+    #[test]
+    fn bytea_round_trip() {
+        let value = PgValue::Bytea(Bytea(vec![0xde, 0xad, 0xbe, 0xef]));
+
+        let buf = encode(&value, &Type::BYTEA);
+        assert_eq!(
+            <Vec<u8>>::from_sql(&Type::BYTEA, &buf).unwrap(),
+            [0xde, 0xad, 0xbe, 0xef]
+        );
+
+        let mut buf = BytesMut::new();
+        let options = FormatOptions::default();
+        value.to_sql_text(&Type::BYTEA, &mut buf, &options).unwrap();
+        assert_eq!(&buf[..], br"\xdeadbeef");
+
+        // Both hex and escape text formats are accepted.
+        let hex = PgValue::decode(Some(br"\xDEADBEEF"), Type::BYTEA.oid(), FieldFormat::Text);
+        let PgValue::Bytea(Bytea(hex)) = hex.unwrap() else {
+            panic!("expected bytea");
+        };
+        assert_eq!(hex, [0xde, 0xad, 0xbe, 0xef]);
+
+        let escape = PgValue::decode(Some(br"a\\\000"), Type::BYTEA.oid(), FieldFormat::Text);
+        let PgValue::Bytea(Bytea(escape)) = escape.unwrap() else {
+            panic!("expected bytea");
+        };
+        assert_eq!(escape, [b'a', b'\\', 0x00]);
+    }
+
+    #[test]
+    fn encode_binary_timestamp() {
+        let datetime = time::macros::datetime!(2024-02-29 23:30:00.123456 +03:00);
+        let value = PgValue::Timestamptz(Timestamptz(datetime.into()));
+
+        let buf = encode(&value, &Type::TIMESTAMPTZ);
+        assert_eq!(
+            time::OffsetDateTime::from_sql(&Type::TIMESTAMPTZ, &buf).unwrap(),
+            datetime
+        );
+
+        let buf = encode(&value, &Type::TIMESTAMP);
+        assert_eq!(
+            time::PrimitiveDateTime::from_sql(&Type::TIMESTAMP, &buf).unwrap(),
+            time::macros::datetime!(2024-02-29 20:30:00.123456)
+        );
+    }
+
+    #[test]
+    fn encode_binary_null_and_mismatch() {
+        let mut buf = BytesMut::new();
+        let is_null = PgValue::Null.encode_binary(&Type::INT8, &mut buf).unwrap();
+        assert!(matches!(is_null, IsNull::Yes));
+        assert!(buf.is_empty());
+
+        assert!(PgValue::Text("1".into())
+            .encode_binary(&Type::INT8, &mut buf)
+            .is_err());
+    }
+}
@@ -234,6 +234,23 @@ fn resolve_rpc_target(
             return Ok(instance_name.into());
         }
 
+        Target::RaftLeader => {
+            //
+            // Request to the raft leader, single candidate
+            //
+            let leader_id = node.status().leader_id.ok_or(Error::LeaderUnknown)?;
+            let leader = node.storage.instances.get(&leader_id)?;
+            let instance_name: &str = &leader.name;
+
+            if let Err(code) =
+                check_route_to_instance(&topology.get(), plugin, service, instance_name)
+            {
+                return Err(make_route_check_error(code, plugin, service, instance_name).into());
+            }
+
+            return Ok(leader.name);
+        }
+
         &Target::Replicaset {
             replicaset_name,
             to_master,
@@ -8,6 +8,7 @@ use crate::storage::schema::ddl_create_tt_proc_on_master;
 use crate::storage::schema::ddl_drop_function_on_master;
 use crate::storage::schema::ddl_drop_index_on_master;
 use crate::storage::schema::ddl_drop_space_on_master;
+use crate::storage::schema::ddl_drop_trailing_fields_on_master;
 use crate::storage::schema::ddl_rename_function_on_master;
 use crate::storage::schema::ddl_rename_index_on_master;
 use crate::storage::schema::ddl_rename_table_on_master;
@@ -149,8 +150,9 @@ pub enum Error {
 /// changes via tarantool replication.
 ///
 /// In case of successful schema change the local schema version will be set to
-/// `version`. In case of [`Ddl::DropTable`] and [`Ddl::DropIndex`] schema is
-/// only changed if `is_commit` is `true`.
+/// `version`. In case of [`Ddl::DropTable`], [`Ddl::DropIndex`] and
+/// [`Ddl::ChangeFormat`] dropping columns schema is only changed if `is_commit`
+/// is `true`.
 ///
 /// The space and index definitions are extracted from picodata storage via
 /// `storage`.
@@ -255,12 +257,29 @@ pub fn apply_schema_change(
         Ddl::ChangeFormat {
             table_id,
             ref new_format,
+            ref old_format,
             // we don't need to care about column renames here because tarantool operates on column indices under the hood, yay
             ..
         } => {
+            // Like a table, the columns are only dropped on commit, so that
+            // their values are kept if the change is aborted.
+            let drops_columns = new_format.len() < old_format.len();
+            if drops_columns && !is_commit {
+                // Don't change local_schema_version because the change will be
+                // applied later in raft_main_loop.
+                return Ok(());
+            }
+
             if let Err(e) = ddl_change_format_on_master(table_id, new_format) {
                 return Err(Error::Aborted(e.into()));
             }
+            // Values of the dropped columns are removed together with the format
+            // change, so that they don't reappear when a column is added later.
+            if drops_columns {
+                if let Err(e) = ddl_drop_trailing_fields_on_master(table_id, new_format.len()) {
+                    return Err(Error::Aborted(e.into()));
+                }
+            }
         }
 
         Ddl::CreateProcedure { id, .. } => {
@@ -0,0 +1,57 @@
+use crate::proc_name;
+use crate::sql::storage::execute_dml_batch_locally;
+use crate::traft::node;
+use crate::traft::Result;
+use serde_bytes::ByteBuf;
+use std::time::Duration;
+use tarantool::fiber;
+
+crate::define_rpc_request! {
+    /// Executes the modifications of a PostgreSQL protocol transaction block
+    /// on the replicaset master in a single transaction.
+    fn proc_sql_execute_dml_batch(req: Request) -> crate::traft::Result<Response> {
+        let timeout = req.timeout.as_secs_f64();
+        let row_counts = execute_dml_batch_locally(&req.messages, req.dry_run, timeout)?;
+        Ok(Response { row_counts })
+    }
+
+    pub struct Request {
+        /// DML messages of the statements in the order of execution.
+        pub messages: Vec<ByteBuf>,
+        /// Roll the transaction back instead of committing it.
+        pub dry_run: bool,
+        pub timeout: Duration,
+    }
+
+    pub struct Response {
+        /// Number of rows changed by each of the messages.
+        pub row_counts: Vec<u64>,
+    }
+}
+
+/// Executes the DML messages on the master of the replicaset with the
+/// given UUID and returns the number of rows changed by each of them.
+pub fn execute_dml_batch(
+    replicaset_uuid: &str,
+    messages: Vec<ByteBuf>,
+    dry_run: bool,
+    timeout: Duration,
+) -> Result<Vec<u64>> {
+    let node = node::global()?;
+    let master = node
+        .topology_cache
+        .clone_replicaset_by_uuid(replicaset_uuid)?
+        .current_master_name;
+    let req = Request {
+        messages,
+        dry_run,
+        timeout,
+    };
+    let resp = node.pool.call(
+        &master,
+        proc_name!(proc_sql_execute_dml_batch),
+        &req,
+        timeout,
+    )?;
+    Ok(fiber::block_on(resp)?.row_counts)
+}
@@ -1,4 +1,6 @@
+use crate::backoff::SimpleBackoffManager;
 use crate::cas;
+use crate::error_code::ErrorCode;
 use crate::failure_domain::FailureDomain;
 use crate::has_states;
 use crate::instance::State;
@@ -22,7 +24,10 @@ use smol_str::ToSmolStr;
 use std::collections::HashSet;
 use std::time::Duration;
 use tarantool::datetime::Datetime;
+use tarantool::error::Error as TntError;
+use tarantool::error::IntoBoxError;
 use tarantool::fiber;
+use tarantool::fiber::r#async::timeout;
 
 const TIMEOUT: Duration = Duration::from_secs(10);
 
@@ -590,6 +595,89 @@ fn choose_replicaset(
     }
 }
 
+/// Maximum number of `NotALeader` redirects [`send_join_request`] follows in
+/// a row before falling back to the initially configured peer.
+pub const MAX_LEADER_REDIRECTS: usize = 3;
+
+/// Sends the join request to `instance_address` until it succeeds.
+///
+/// `call` makes a single attempt to call [`proc_raft_join`] on the given
+/// address with the given timeout.
+///
+/// If the peer responds with a `NotALeader` error containing the
+/// `leader_address` field, the request is immediately resent to the leader,
+/// but no more than [`MAX_LEADER_REDIRECTS`] times in a row. If the leader
+/// address is unknown (e.g. an election is in progress), the request is resent
+/// to `instance_address` after a backoff timeout.
+///
+/// Network errors and timeouts are retried as well, any other error is returned.
+pub fn send_join_request<T>(
+    instance_address: &str,
+    mut call: impl FnMut(&str, Duration) -> std::result::Result<T, timeout::Error<TntError>>,
+) -> Result<T> {
+    const INITIAL_TIMEOUT: Duration = Duration::from_secs(1);
+    const MAX_TIMEOUT: Duration = Duration::from_secs(60);
+    let mut backoff = SimpleBackoffManager::new("proc_raft_join RPC", INITIAL_TIMEOUT, MAX_TIMEOUT);
+
+    let mut address = SmolStr::from(instance_address);
+    let mut redirects = 0;
+    loop {
+        let current_timeout = backoff.timeout();
+        let error_message = match call(&address, current_timeout) {
+            Ok(resp) => return Ok(resp),
+            Err(timeout::Error::Expired) => {
+                tlog!(
+                    Warning,
+                    "join request timed out after {:?}, retrying...",
+                    current_timeout
+                );
+                backoff.handle_failure();
+                continue;
+            }
+            Err(timeout::Error::Failed(e @ (TntError::ConnectionClosed(_) | TntError::IO(_)))) => {
+                e.to_string()
+            }
+            Err(timeout::Error::Failed(TntError::Remote(e)))
+                if e.error_code() == ErrorCode::NotALeader as u32 =>
+            {
+                let leader_address = e.field("leader_address").and_then(|v| v.as_str());
+                if let Some(leader_address) = leader_address {
+                    if redirects < MAX_LEADER_REDIRECTS {
+                        tlog!(
+                            Info,
+                            "{address} is not a leader, redirecting join request to {leader_address}"
+                        );
+                        address = leader_address.into();
+                        redirects += 1;
+                        continue;
+                    }
+                }
+                e.to_string()
+            }
+            Err(timeout::Error::Failed(e)) if e.error_code() == ErrorCode::LeaderUnknown as u32 => {
+                // The peer doesn't know who the raft leader is. This is
+                // possible for example if a leader election is in progress.
+                e.to_string()
+            }
+            Err(e) => {
+                return Err(Error::other(format!(
+                    "join request failed: {e}, shutting down..."
+                )));
+            }
+        };
+
+        tlog!(
+            Warning,
+            "join request failed: {error_message}, retrying in {:?}...",
+            current_timeout
+        );
+        address = instance_address.into();
+        redirects = 0;
+        fiber::sleep(current_timeout);
+        backoff.handle_failure();
+    }
+}
+
 pub fn compress_join_response(join_response: &Response) -> Result<Vec<u8>> {
     let res = rmp_serde::to_vec(&join_response);
     let encoded = match res {
@@ -619,3 +707,40 @@ pub fn decompress_join_response(compressed: &[u8]) -> Result<Response> {
 
     Ok(join_response)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tarantool::error::BoxError;
+
+    #[::tarantool::test]
+    fn send_join_request_follows_leader_redirect() {
+        let mut called = vec![];
+        let res = send_join_request("follower:3301", |address, _| {
+            called.push(address.to_smolstr());
+            if address == "leader:3301" {
+                return Ok("joined");
+            }
+            let mut e = BoxError::new(ErrorCode::NotALeader, "not a leader, actual leader: 1");
+            e.set_field("leader_address", "leader:3301".into());
+            Err(timeout::Error::Failed(TntError::Remote(e)))
+        })
+        .unwrap();
+
+        assert_eq!(res, "joined");
+        assert_eq!(called, ["follower:3301", "leader:3301"]);
+    }
+
+    #[::tarantool::test]
+    fn send_join_request_fails_on_other_errors() {
+        let mut attempts = 0;
+        let res: Result<()> = send_join_request("peer:3301", |_, _| {
+            attempts += 1;
+            let e = BoxError::new(ErrorCode::Other, "cluster name mismatch");
+            Err(timeout::Error::Failed(TntError::Remote(e)))
+        });
+
+        assert!(res.is_err());
+        assert_eq!(attempts, 1);
+    }
+}
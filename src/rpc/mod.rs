@@ -20,13 +20,16 @@ pub mod before_online;
 pub mod ddl_apply;
 pub mod ddl_backup;
 pub mod disable_service;
+pub mod dml_batch;
 pub mod enable_all_plugins;
 pub mod enable_plugin;
 pub mod enable_service;
 pub mod expel;
 pub mod join;
 pub mod load_plugin_dry_run;
+pub mod pg_notify;
 pub mod replication;
+pub mod row_lock;
 pub mod sharding;
 pub mod snapshot;
 pub mod update_instance;
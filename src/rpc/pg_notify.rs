@@ -0,0 +1,109 @@
+use crate::has_states;
+use crate::pgproto::backend;
+use crate::proc_name;
+use crate::tlog;
+use crate::traft::error::Error;
+use crate::traft::node::{self, Node as TraftNode};
+use futures::future::join_all;
+use smol_str::{format_smolstr, SmolStr};
+use sql::errors::{Entity, SbroadError};
+use std::time::Duration;
+use tarantool::fiber;
+
+/// Maximum size of a notification payload in bytes, the same as in Postgres.
+pub const MAX_PAYLOAD_LEN: usize = 8000;
+
+crate::define_rpc_request! {
+    /// Delivers a notification sent with `NOTIFY` to the PostgreSQL protocol
+    /// sessions of the instance which listen to its channel.
+    fn proc_pg_notify(req: Request) -> crate::traft::Result<Response> {
+        backend::deliver_notification(&req.channel, &req.payload);
+        Ok(Response {})
+    }
+
+    pub struct Request {
+        pub channel: SmolStr,
+        pub payload: SmolStr,
+    }
+
+    pub struct Response {}
+}
+
+/// Check that the notification payload is not too long.
+pub fn check_payload(payload: &str) -> crate::traft::Result<()> {
+    if payload.len() >= MAX_PAYLOAD_LEN {
+        return Err(Error::Sbroad(SbroadError::Invalid(
+            Entity::Value,
+            Some(format_smolstr!(
+                "notification payload must be shorter than {MAX_PAYLOAD_LEN} bytes"
+            )),
+        )));
+    }
+    Ok(())
+}
+
+/// Sends the notifications to every online instance of the cluster, the
+/// current one included. The notifications are sent in the given order
+/// from a separate fiber, so the caller doesn't wait for the delivery.
+///
+/// The delivery is at most once: an instance which doesn't respond in
+/// `timeout` just misses the notification, it is not an error of `NOTIFY`.
+pub fn broadcast_notifications(reqs: Vec<Request>, timeout: Duration) {
+    if reqs.is_empty() {
+        return;
+    }
+    let res = fiber::Builder::new()
+        .name("pg_notify")
+        .func(move || {
+            let node = match node::global() {
+                Ok(node) => node,
+                Err(e) => {
+                    tlog!(Warning, "failed sending notifications: {e}");
+                    return;
+                }
+            };
+            broadcast(node, &reqs, timeout);
+        })
+        .start_non_joinable();
+    if let Err(e) = res {
+        tlog!(Warning, "failed sending notifications: {e}");
+    }
+}
+
+fn broadcast(node: &TraftNode, reqs: &[Request], timeout: Duration) {
+    let targets: Vec<_> = node
+        .topology_cache
+        .get()
+        .all_instances()
+        .filter(|instance| has_states!(instance, * -> Online))
+        .map(|instance| instance.name.clone())
+        .collect();
+
+    let mut fs = vec![];
+    // Requests to the same instance are sent over the same connection,
+    // so the instance receives the notifications in order.
+    for req in reqs {
+        for instance_name in &targets {
+            let resp = match node
+                .pool
+                .call(instance_name, proc_name!(proc_pg_notify), req, timeout)
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    tlog!(Warning, "failed sending notification: {e}";
+                        "instance_name" => %instance_name,
+                    );
+                    continue;
+                }
+            };
+            fs.push(async move {
+                if let Err(e) = resp.await {
+                    tlog!(Warning, "failed sending notification: {e}";
+                        "instance_name" => %instance_name,
+                    );
+                }
+            });
+        }
+    }
+    fiber::block_on(join_all(fs));
+}
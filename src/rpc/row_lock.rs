@@ -0,0 +1,94 @@
+use crate::instance::InstanceName;
+use crate::proc_name;
+use crate::sql::row_lock::{self, LockOwner};
+use crate::traft::node;
+use crate::traft::Result;
+use serde_bytes::ByteBuf;
+use std::time::Duration;
+use tarantool::fiber;
+use tarantool::space::SpaceId;
+use tarantool::time::Instant;
+
+crate::define_rpc_request! {
+    /// Locks the rows selected with `FOR UPDATE` or `FOR SHARE` in a
+    /// PostgreSQL protocol transaction block on the replicaset master.
+    fn proc_sql_lock_rows(req: LockRequest) -> crate::traft::Result<LockResponse> {
+        let deadline = Instant::now_fiber().saturating_add(req.timeout);
+        let LockRequest { owner, space_id, keys, exclusive, .. } = req;
+        let locked = row_lock::lock_rows(&owner, space_id, keys, exclusive, deadline);
+        Ok(LockResponse { locked })
+    }
+
+    pub struct LockRequest {
+        pub owner: LockOwner,
+        pub space_id: SpaceId,
+        /// Primary keys of the rows encoded as msgpack arrays.
+        pub keys: Vec<ByteBuf>,
+        /// `FOR UPDATE` lock, otherwise `FOR SHARE` one.
+        pub exclusive: bool,
+        /// Time to wait for the rows locked by other transactions.
+        pub timeout: Duration,
+    }
+
+    pub struct LockResponse {
+        /// Whether all the rows were locked in time.
+        pub locked: bool,
+    }
+}
+
+crate::define_rpc_request! {
+    /// Releases the row locks of a PostgreSQL protocol transaction block
+    /// when the block ends.
+    fn proc_sql_unlock_rows(req: UnlockRequest) -> crate::traft::Result<UnlockResponse> {
+        row_lock::unlock_rows(&req.owner);
+        Ok(UnlockResponse {})
+    }
+
+    pub struct UnlockRequest {
+        pub owner: LockOwner,
+    }
+
+    pub struct UnlockResponse {}
+}
+
+/// Extra time for the response of the master which has stopped waiting
+/// for the row locks.
+const LOCK_RESPONSE_MARGIN: Duration = Duration::from_secs(1);
+
+/// Locks the rows of the space on the replicaset master. Returns whether
+/// all the rows were locked before the timeout.
+pub fn lock_rows(
+    master: &InstanceName,
+    owner: LockOwner,
+    space_id: SpaceId,
+    keys: Vec<ByteBuf>,
+    exclusive: bool,
+    timeout: Duration,
+) -> Result<bool> {
+    let node = node::global()?;
+    let req = LockRequest {
+        owner,
+        space_id,
+        keys,
+        exclusive,
+        timeout,
+    };
+    let resp = node.pool.call(
+        master,
+        proc_name!(proc_sql_lock_rows),
+        &req,
+        timeout.saturating_add(LOCK_RESPONSE_MARGIN),
+    )?;
+    Ok(fiber::block_on(resp)?.locked)
+}
+
+/// Releases the row locks of the transaction block held by the instance.
+pub fn unlock_rows(instance: &InstanceName, owner: LockOwner, timeout: Duration) -> Result<()> {
+    let node = node::global()?;
+    let req = UnlockRequest { owner };
+    let resp = node
+        .pool
+        .call(instance, proc_name!(proc_sql_unlock_rows), &req, timeout)?;
+    fiber::block_on(resp)?;
+    Ok(())
+}
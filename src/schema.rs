@@ -90,6 +90,36 @@ pub enum TableOption {
     #[serde(rename = "pk_contains_bucket_id")]
     #[encode(rename = "pk_contains_bucket_id")]
     PkContainsBucketId(bool),
+
+    /// Comment on a table column set with `COMMENT ON COLUMN`.
+    /// Comment on the table itself is stored in [`TableDef::description`].
+    #[serde(rename = "column_comment")]
+    #[encode(rename = "column_comment")]
+    ColumnComment { column: String, comment: String },
+
+    /// Column declared as `GENERATED ALWAYS AS IDENTITY`. Its values are
+    /// allocated from the cluster sequence stored in `_pico_sequence`.
+    #[serde(rename = "identity")]
+    #[encode(rename = "identity")]
+    Identity { column: String },
+
+    /// `CHECK (<column> <op> <value>)` constraint declared in `CREATE TABLE`.
+    /// The constant is kept as text and is cast to the column type
+    /// when the table metadata is loaded by the SQL router.
+    #[serde(rename = "check")]
+    #[encode(rename = "check")]
+    Check {
+        name: String,
+        column: String,
+        op: String,
+        value: String,
+    },
+
+    /// `DEFAULT <value>` of a column declared in `CREATE TABLE`.
+    /// Like in [`TableOption::Check`], the constant is kept as text.
+    #[serde(rename = "default")]
+    #[encode(rename = "default")]
+    Default { column: String, value: String },
 }
 
 /// Database table definition.
@@ -181,6 +211,38 @@ impl TableDef {
     pub fn is_unlogged(&self) -> bool {
         self.opts.contains(&TableOption::Unlogged(true))
     }
+
+    /// Name of the identity column of the table, if any.
+    #[inline]
+    pub fn identity_column(&self) -> Option<&str> {
+        self.opts.iter().find_map(|opt| match opt {
+            TableOption::Identity { column } => Some(column.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Declared column defaults of the table as `(column, value)`.
+    #[inline]
+    pub fn defaults(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.opts.iter().filter_map(|opt| match opt {
+            TableOption::Default { column, value } => Some((column.as_str(), value.as_str())),
+            _ => None,
+        })
+    }
+
+    /// CHECK constraints of the table as `(name, column, op, value)`.
+    #[inline]
+    pub fn checks(&self) -> impl Iterator<Item = (&str, &str, &str, &str)> {
+        self.opts.iter().filter_map(|opt| match opt {
+            TableOption::Check {
+                name,
+                column,
+                op,
+                value,
+            } => Some((name.as_str(), column.as_str(), op.as_str(), value.as_str())),
+            _ => None,
+        })
+    }
 }
 
 /// Definitions of builtin tables & their respective indexes.
@@ -213,6 +275,7 @@ pub fn system_table_definitions() -> Vec<(TableDef, Vec<IndexDef>)> {
     use crate::catalog::governor_queue::GovernorQueue;
     use crate::catalog::pico_bucket::PicoBucket;
     use crate::catalog::pico_resharding_state::PicoReshardingState;
+    use crate::catalog::pico_sequence::PicoSequence;
     use crate::catalog::pico_table::PicoTable;
     use crate::catalog::user_audit_policy::PicoUserAuditPolicy;
     use crate::storage::*;
@@ -238,6 +301,7 @@ pub fn system_table_definitions() -> Vec<(TableDef, Vec<IndexDef>)> {
         PicoUserAuditPolicy,
         PicoBucket,
         PicoReshardingState,
+        PicoSequence,
     );
 
     // TODO: there's also "_raft_log" & "_raft_state" spaces, but we don't treat
@@ -308,6 +372,16 @@ impl Distribution {
             Distribution::ShardedImplicitly { tier, .. } => Some(tier),
         }
     }
+
+    /// For sharded distribution returns the name of the column storing
+    /// the bucket id. For global distribution returns `None`.
+    pub fn bucket_id_column(&self) -> Option<&str> {
+        match self {
+            Distribution::Global => None,
+            Distribution::ShardedByField { field, .. } => Some(field),
+            Distribution::ShardedImplicitly { .. } => Some(DEFAULT_BUCKET_ID_COLUMN_NAME),
+        }
+    }
 }
 
 const fn default_bucket_id_field() -> SmolStr {
@@ -373,6 +447,10 @@ pub enum IndexOption {
     /// Specify whether the index is unique. When true, the index cannot contain duplicate values.
     #[serde(rename = "unique")]
     Unique(bool),
+    /// Specify whether NULLs are distinct in the unique index. When false, keys with
+    /// NULLs collide as well. Tarantool has no such option, so picodata checks it itself.
+    #[serde(rename = "nulls_distinct")]
+    NullsDistinct(bool),
 }
 
 impl IndexOption {
@@ -397,6 +475,9 @@ impl IndexOption {
                 ("compression_level".into(), Value::Num((*level).into()))
             }
             IndexOption::Unique(unique) => ("unique".into(), Value::Bool(*unique)),
+            IndexOption::NullsDistinct(distinct) => {
+                ("nulls_distinct".into(), Value::Bool(*distinct))
+            }
         }
     }
 
@@ -420,6 +501,11 @@ impl IndexOption {
         matches!(self, IndexOption::Hint(_))
     }
 
+    /// Options that are not passed to tarantool.
+    pub fn is_picodata_only(&self) -> bool {
+        matches!(self, IndexOption::NullsDistinct(_))
+    }
+
     pub fn type_name(&self) -> &str {
         match self {
             IndexOption::BloomFalsePositiveRate(_) => "bloom_fpr",
@@ -432,6 +518,7 @@ impl IndexOption {
             IndexOption::RunSizeRatio(_) => "run_size_ratio",
             IndexOption::CompressionLevel(_) => "compression_level",
             IndexOption::Unique(_) => "unique",
+            IndexOption::NullsDistinct(_) => "nulls_distinct",
         }
     }
 }
@@ -492,6 +579,9 @@ impl IndexDef {
     pub fn to_index_metadata(&self, table_def: &TableDef) -> IndexMetadata<'_> {
         let mut opts = BTreeMap::new();
         for opt in &self.opts {
+            if opt.is_picodata_only() {
+                continue;
+            }
             let (key, value) = opt.as_kv();
             opts.insert(key, value);
         }
@@ -573,6 +663,9 @@ impl From<IndexDef> for IndexOptions {
                 IndexOption::Hint(_) => {
                     // FIXME: `hint` option is disabled in Tarantool module.
                 }
+                IndexOption::NullsDistinct(_) => {
+                    // Checked by picodata on DML, tarantool doesn't know about it.
+                }
             }
         }
         opts
@@ -1899,6 +1992,14 @@ pub enum CreateIndexError {
     NonUniqueIndexType { ty: String },
     #[error("index type {ty} does not support multiple columns")]
     IncompatibleIndexMultipleColumns { ty: String },
+    #[error("NULLS NOT DISTINCT is not supported for global tables")]
+    NullsNotDistinctGlobalTable,
+    #[error("collation is not supported for column {name} of type {ctype}")]
+    IncompatibleCollationColumnType { name: SmolStr, ctype: String },
+    #[error(
+        "unique index for the sharded table cannot use collation {collation} for sharding key column {name}"
+    )]
+    IncompatibleUniqueIndexCollation { name: SmolStr, collation: SmolStr },
 }
 
 impl From<CreateIndexError> for Error {
@@ -1985,6 +2086,8 @@ pub struct CreateIndexParams {
     pub(crate) name: SmolStr,
     pub(crate) space_name: SmolStr,
     pub(crate) columns: Vec<SmolStr>,
+    /// Explicit collations of the columns, by position.
+    pub(crate) collations: Vec<Option<SmolStr>>,
     pub(crate) ty: IndexType,
     pub(crate) opts: Vec<IndexOption>,
     pub(crate) initiator: UserId,
@@ -2038,7 +2141,7 @@ impl CreateIndexParams {
         let table = self.table(storage)?;
         let mut parts = Vec::with_capacity(self.columns.len());
 
-        for column_name in &self.columns {
+        for (i, column_name) in self.columns.iter().enumerate() {
             let found = table.format.iter().find(|c| &c.name == column_name);
             let Some(column) = found else {
                 return Err(CreateIndexError::FieldUndefined {
@@ -2051,10 +2154,18 @@ impl CreateIndexParams {
                     ty: self.ty.to_string(),
                     ctype: column.field_type.to_string(),
                 })?;
+            let collation = self.collations.get(i).cloned().flatten();
+            if collation.is_some() && column.field_type != FieldType::String {
+                return Err(CreateIndexError::IncompatibleCollationColumnType {
+                    name: column_name.clone(),
+                    ctype: column.field_type.to_string(),
+                }
+                .into());
+            }
             let part = Part {
                 field: (&**column_name).into(),
                 r#type: Some(index_field_type),
-                collation: None,
+                collation: collation.map(String::from),
                 is_nullable: Some(column.is_nullable),
                 path: None,
             };
@@ -2124,6 +2235,11 @@ impl CreateIndexParams {
                     })?;
                 }
             }
+            if let &IndexOption::NullsDistinct(false) = opt {
+                if matches!(table.distribution, Distribution::Global) {
+                    return Err(CreateIndexError::NullsNotDistinctGlobalTable)?;
+                }
+            }
             if let &IndexOption::Unique(true) = opt {
                 if self.ty == IndexType::Rtree || self.ty == IndexType::Bitset {
                     return Err(CreateIndexError::UniqueIndexType {
@@ -2140,6 +2256,17 @@ impl CreateIndexParams {
                             return Err(CreateIndexError::IncompatibleUniqueIndexColumns)?;
                         }
                     }
+                    // Keys equal by a non-binary collation may have different
+                    // bucket ids, so the uniqueness couldn't be checked locally.
+                    let collations = self.collations.iter().take(sharding_key.len());
+                    for (column, collation) in self.columns.iter().zip(collations) {
+                        if let Some(collation) = collation.as_ref().filter(|c| *c != "binary") {
+                            return Err(CreateIndexError::IncompatibleUniqueIndexCollation {
+                                name: column.clone(),
+                                collation: collation.clone(),
+                            })?;
+                        }
+                    }
                 }
             }
         }
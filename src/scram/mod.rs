@@ -35,6 +35,7 @@ use base64::prelude::BASE64_STANDARD;
 use base64::Engine as _;
 pub use exchange::Exchange;
 pub use key::ScramKey;
+pub(crate) use messages::SCRAM_RAW_NONCE_LEN;
 pub use secret::ServerSecret;
 
 const SCRAM_SHA_256: &str = "SCRAM-SHA-256";
@@ -5,18 +5,20 @@ use crate::access_control::{validate_password, UserMetadataKind};
 use crate::backoff::SimpleBackoffManager;
 use crate::cas::Predicate;
 use crate::catalog::governor_queue;
+use crate::catalog::pico_bucket::DEFAULT_BUCKET_ID_COLUMN_NAME;
 use crate::column_name;
 use crate::config::{AlterSystemParameters, DYNAMIC_CONFIG};
 use crate::metrics::{self, STORAGE_1ST_REQUESTS_TOTAL};
 use crate::plugin::{InheritOpts, PluginIdentifier, TopologyUpdateOpKind};
+use crate::rpc::pg_notify;
 use crate::schema::{
     wait_for_ddl_commit, CreateIndexParams, CreateProcParams, CreateTableParams, DdlError,
-    DistributionParam, Field, IndexOption, PrivilegeDef, PrivilegeType, RenameRoutineParams,
-    RoutineDef, RoutineLanguage, RoutineParamDef, RoutineParams, RoutineSecurity, SchemaObjectType,
-    ShardingFn, TableOption, UserDef, ADMIN_ID,
+    Distribution, DistributionParam, Field, IndexOption, PrivilegeDef, PrivilegeType,
+    RenameRoutineParams, RoutineDef, RoutineLanguage, RoutineParamDef, RoutineParams,
+    RoutineSecurity, SchemaObjectType, ShardingFn, TableDef, TableOption, UserDef, ADMIN_ID,
 };
 use crate::sql::concurrency::{runtime_owner_key, with_sql_runtime_limit};
-use crate::sql::router::RouterRuntime;
+use crate::sql::router::{get_table_version_by_id, RouterRuntime};
 use crate::sql::storage::{FullDeleteInfo, StorageRuntime};
 use crate::storage::Catalog;
 use crate::storage::{get_backup_dir_name, space_by_name, DbConfig, SystemTable, ToEntryIter};
@@ -24,7 +26,7 @@ use crate::sync::wait_for_index_globally;
 use crate::traft::error::{self, Error};
 use crate::traft::node::Node as TraftNode;
 use crate::traft::op::{Acl as OpAcl, Ddl as OpDdl, Dml, DmlKind, Op, RenameMappingBuilder};
-use crate::traft::{self, node};
+use crate::traft::{self, node, RaftIndex};
 use crate::util::{duration_from_secs_f64_clamped, effective_user_id};
 use crate::version::Version;
 use crate::{audit, schema};
@@ -50,17 +52,20 @@ use rmp::encode::{write_bin, write_str, write_uint};
 use smallvec::SmallVec;
 use smol_str::{format_smolstr, SmolStr, ToSmolStr};
 use sql::errors::{Entity, SbroadError};
+use sql::executor::bucket::FanOut;
 use sql::executor::engine::helpers::{
-    build_delete_args, build_insert_args, build_update_args, init_delete_tuple_builder,
-    init_insert_tuple_builder, init_local_update_tuple_builder,
+    build_delete_args, build_insert_args, build_update_args, check_dml_constraints,
+    init_delete_tuple_builder, init_insert_tuple_builder, init_local_update_tuple_builder,
 };
 use sql::executor::engine::Router;
 use sql::executor::protocol::SchemaInfo;
-use sql::executor::result::ConsumerResult;
+use sql::executor::result::{ConsumerResult, MetadataColumn};
 use sql::executor::ExecutingQuery;
 use sql::executor::{Port, PortType};
 use sql::ir::acl::{AlterOption, AuditPolicyOption, GrantRevokeType, Privilege as SqlPrivilege};
-use sql::ir::ddl::{AlterSystemType, ParamDef};
+use sql::ir::ddl::{
+    AlterSystemType, NotificationCommand, ParamDef, SetParamValue, TransactionIsolationLevel,
+};
 use sql::ir::node::acl::AclOwned;
 use sql::ir::node::block::{Block, BlockOwned};
 use sql::ir::node::ddl::{Ddl, DdlOwned};
@@ -70,18 +75,20 @@ use sql::ir::node::plugin::{
     MigrateTo, Plugin, RemoveServiceFromTier, SettingsPair,
 };
 use sql::ir::node::relational::Relational;
+use sql::ir::node::tcl::Tcl;
 use sql::ir::node::{
     AlterColumn, AlterSystem, AlterTableOp, AlterUser, AnonymousBlock, ArenaType, AuditPolicy,
-    CallProcedure, Constant, CreateIndex, CreateProc, CreateRole, CreateTable, CreateUser, Delete,
-    DropIndex, DropProc, DropRole, DropTable, DropUser, GrantPrivilege, Insert, Node as IrNode,
-    Node136, Node64, Node96, NodeOwned, RenameIndex, RenameRoutine, RevokePrivilege, ScanRelation,
-    SetParam, Update,
+    CallProcedure, Comment, Constant, CreateIndex, CreateProc, CreateRole, CreateTable, CreateUser,
+    Delete, DropIndex, DropProc, DropRole, DropTable, DropUser, GrantPrivilege, Insert,
+    Node as IrNode, Node136, Node64, Node96, NodeOwned, Notification, RenameIndex, RenameRoutine,
+    RevokePrivilege, ScanRelation, SetParam, SetTransaction, Update,
 };
 use sql::ir::node::{NodeId, TruncateTable};
 use sql::ir::operator::ConflictStrategy;
+use sql::ir::options::Options;
 use sql::ir::types::UnrestrictedType;
 use sql::ir::value::Value;
-use sql::ir::Plan as IrPlan;
+use sql::ir::{Plan as IrPlan, SelectInto};
 use sql_protocol::decode::{
     execute_args_split, query_meta_args_split, ProtocolMessage, ProtocolMessageType, QueryMetaArgs,
 };
@@ -94,6 +101,7 @@ use std::rc::Rc;
 use std::str::from_utf8_unchecked;
 use std::time::Duration;
 
+pub mod arrow;
 pub mod concurrency;
 pub mod dispatch;
 pub mod execute;
@@ -101,13 +109,16 @@ pub mod lock;
 pub mod lua;
 pub mod port;
 pub mod router;
+pub mod row_lock;
+pub mod sequence;
 pub mod storage;
 
 use self::lua::{escape_bytes, reference_add, reference_del, reference_use};
-use self::port::PicoPortC;
+use self::port::{PicoPortC, PicoPortOwned};
 use self::router::DEFAULT_QUERY_TIMEOUT;
-use crate::sql::dispatch::build_cache_miss_dql_packet;
+use crate::sql::dispatch::{build_cache_miss_dql_packet, point_get_dispatch};
 use serde::Serialize;
+use serde_bytes::ByteBuf;
 
 use sql::BoundStatement;
 
@@ -260,6 +271,56 @@ fn port_write_dml_response<'p>(port: &mut impl Port<'p>, changed: u64) {
     port.add_mp(&mp[..pos]);
 }
 
+/// [`std::fmt::Write`] adapter passing the text to `f` line by line
+/// as soon as each line is complete.
+struct LineWriter<F: FnMut(&str) -> traft::Result<()>> {
+    line: String,
+    f: F,
+    error: Option<Error>,
+}
+
+impl<F: FnMut(&str) -> traft::Result<()>> LineWriter<F> {
+    fn new(f: F) -> Self {
+        Self {
+            line: String::new(),
+            f,
+            error: None,
+        }
+    }
+
+    fn emit_line(&mut self) -> std::fmt::Result {
+        if let Err(e) = (self.f)(&self.line) {
+            self.error = Some(e);
+            return Err(std::fmt::Error);
+        }
+        self.line.clear();
+        Ok(())
+    }
+
+    /// Emit the last line if it doesn't end with a newline.
+    fn finish(mut self, res: Result<(), SbroadError>) -> traft::Result<()> {
+        if let Err(e) = res {
+            return Err(self.error.take().unwrap_or(Error::Sbroad(e)));
+        }
+        if !self.line.is_empty() && self.emit_line().is_err() {
+            return Err(self.error.take().expect("set on failure"));
+        }
+        Ok(())
+    }
+}
+
+impl<F: FnMut(&str) -> traft::Result<()>> std::fmt::Write for LineWriter<F> {
+    fn write_str(&mut self, mut s: &str) -> std::fmt::Result {
+        while let Some(pos) = s.find('\n') {
+            self.line.push_str(&s[..pos]);
+            self.emit_line()?;
+            s = &s[pos + 1..];
+        }
+        self.line.push_str(s);
+        Ok(())
+    }
+}
+
 /// Same as [`dispatch_bound_statement`], but does not collect any metrics
 fn dispatch_bound_statement_impl<'p>(
     runtime: &RouterRuntime,
@@ -275,6 +336,34 @@ fn dispatch_bound_statement_impl<'p>(
         return Ok(());
     }
 
+    // Without the cursor row resolved the statement would modify the whole table.
+    if let Some(current_of) = &query.get_exec_plan().get_ir_plan().current_of {
+        return Err(Error::Sbroad(SbroadError::Unsupported(
+            Entity::Query,
+            Some(format_smolstr!(
+                "WHERE CURRENT OF {} requires a cursor of a PostgreSQL protocol session",
+                current_of.cursor
+            )),
+        )));
+    }
+
+    let select_into = query
+        .get_mut_exec_plan()
+        .get_mut_ir_plan()
+        .select_into
+        .take();
+    if let Some(select_into) = select_into {
+        port.set_type(PortType::DispatchDml);
+        return dispatch_select_into(
+            runtime,
+            query,
+            *select_into,
+            override_deadline,
+            governor_op_id,
+            port,
+        );
+    }
+
     if query.get_exec_plan().get_ir_plan().is_raw_explain() {
         port.set_type(PortType::DispatchQueryPlan);
     } else if query.is_explain() {
@@ -305,6 +394,17 @@ fn dispatch_bound_statement_impl<'p>(
         let ir_plan = query.get_exec_plan().get_ir_plan();
         let top_id = ir_plan.get_top()?;
         let tcl = ir_plan.get_tcl_node(top_id)?;
+        // Unlike `BEGIN` and `COMMIT`, silently ignoring `ROLLBACK TO SAVEPOINT`
+        // would keep the modifications the client expects to be discarded.
+        if matches!(
+            tcl,
+            Tcl::Savepoint(_) | Tcl::Release(_) | Tcl::RollbackTo(_)
+        ) {
+            return Err(Error::Sbroad(SbroadError::Unsupported(
+                Entity::Transaction,
+                Some("savepoints are supported only in pgproto transaction blocks".into()),
+            )));
+        }
         tlog!(
             Warning,
             "Transactions are currently unsupported. Empty query response provided for {}.",
@@ -585,7 +685,8 @@ fn dispatch_bound_statement_impl<'p>(
                     audit::policy::log_dml_for_user(&pattern, bound_statement.params_for_audit());
                 }
                 if runtime.is_sql_log_enabled(bound_statement.as_plan())? {
-                    tlog!(Info, "sql-log: {pattern}");
+                    let query_id = bound_statement.query_fingerprint().unwrap_or_default();
+                    tlog!(Info, "sql-log: {pattern}"; "query_id" => %query_id);
                 }
 
                 dispatch_bound_statement_impl(
@@ -609,13 +710,17 @@ fn dispatch_bound_statement_impl<'p>(
 
         if query.is_explain() {
             port.set_type(PortType::DispatchExplain);
+            // Huge plans are sent line by line without formatting
+            // the whole explain into a string.
             let mut mp: Vec<u8> = Vec::new();
-            for line in query.as_explain()?.lines() {
+            let mut writer = LineWriter::new(|line| {
                 write_str(&mut mp, line).map_err(Error::other)?;
                 port.add_mp(&mp);
                 mp.clear();
-            }
-            return Ok(());
+                Ok(())
+            });
+            let res = query.explain_to_writer(&mut writer);
+            return writer.finish(res);
         }
 
         // check if table is operable
@@ -638,11 +743,24 @@ fn dispatch_bound_statement_impl<'p>(
             Ok::<(), Error>(())
         })??;
 
+        wait_index_for_read(&plan.effective_options)?;
+
+        let result_row_max = plan
+            .is_dql()?
+            .then_some(plan.effective_options.sql_result_row_max);
         let is_dml_on_global = plan.is_dml_on_global_table()? && !plan.is_raw_explain();
         let request_id =
             runtime_owner_key(query.get_exec_plan().get_request_id()).map_err(Error::Sbroad)?;
         with_sql_runtime_limit(request_id, || -> traft::Result<()> {
             if is_dml_on_global {
+                // Global tables are modified through raft, which can't
+                // hold the modification until the end of the transaction.
+                if dispatch::is_collecting_dml_batch() {
+                    return Err(Error::Sbroad(SbroadError::Unsupported(
+                        Entity::Query,
+                        Some("modification of global tables in a transaction block".into()),
+                    )));
+                }
                 let plan = query.get_exec_plan().get_ir_plan();
                 let top_id = plan.get_top()?;
                 if let Relational::Delete(node) = plan.get_relation_node(top_id)? {
@@ -655,13 +773,129 @@ fn dispatch_bound_statement_impl<'p>(
                 port_write_dml_response(port, row_count);
                 return Ok(());
             }
+            let ex_plan = query.get_exec_plan();
+            if let Some(point_get) = ex_plan.get_ir_plan().point_get()? {
+                let vshard =
+                    runtime.get_vshard_object_by_tier(ex_plan.get_ir_plan().tier.as_ref())?;
+                point_get_dispatch(port, &vshard, ex_plan, &point_get)?;
+                return Ok(());
+            }
             query.dispatch(port).map_err(Error::Sbroad)?;
             Ok(())
         })??;
+
+        if let Some(max_rows) = result_row_max {
+            check_result_row_max(port, max_rows)?;
+        }
         Ok(())
     }
 }
 
+/// Result-set guard of the `sql_result_row_max` option: fail the query
+/// instead of sending more rows to the client than allowed. Unlike
+/// `sql_motion_row_max`, it checks the final result of the query on the
+/// router, so a query with a `LIMIT` below the cap is never affected.
+/// Distributed reads are checked while the port is filled, while this
+/// catches the results produced locally, e.g. of global tables.
+fn check_result_row_max<'p>(port: &impl Port<'p>, max_rows: i64) -> traft::Result<()> {
+    if max_rows <= 0 {
+        return Ok(());
+    }
+    // The first tuple of a DQL port is the metadata.
+    let rows = port.size().saturating_sub(1);
+    if u64::from(rows) > max_rows as u64 {
+        return Err(Error::Sbroad(SbroadError::UnexpectedNumberOfValues(
+            format_smolstr!("Exceeded maximum number of rows ({max_rows}) in query result: {rows}"),
+        )));
+    }
+    Ok(())
+}
+
+/// Read barrier of the `wait_index` option: wait until the raft log is
+/// applied on the router up to the given index, so that the read sees
+/// the writes committed before it. Fails when the timeout expires rather
+/// than serving a stale read.
+fn wait_index_for_read(options: &Options) -> traft::Result<()> {
+    if options.wait_index <= 0 {
+        return Ok(());
+    }
+    let index = options.wait_index as RaftIndex;
+    let timeout_ms = options.wait_index_timeout as u64;
+    let node = node::global()?;
+    if node
+        .wait_index(index, Duration::from_millis(timeout_ms))
+        .is_err()
+    {
+        return Err(BoxError::new(
+            TarantoolErrorCode::Timeout,
+            format!(
+                "raft index {index} was not applied within {timeout_ms} ms, applied index: {}",
+                node.get_index()
+            ),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Execute `SELECT ... INTO`: create the table and fill it with a single
+/// `INSERT INTO ... SELECT` statement. The table is dropped if the insertion
+/// fails.
+fn dispatch_select_into<'p>(
+    runtime: &RouterRuntime,
+    query: ExecutingQuery<'_, RouterRuntime>,
+    select_into: SelectInto,
+    override_deadline: Option<Instant>,
+    governor_op_id: Option<u64>,
+    port: &mut impl Port<'p>,
+) -> traft::Result<()> {
+    check_table_privileges(query.get_exec_plan().get_ir_plan())?;
+    let SelectInto {
+        create_table,
+        insert,
+        params,
+    } = select_into;
+    let table = format!("\"{}\"", create_table.name.replace('"', "\"\""));
+    let ir_node = NodeOwned::Ddl(DdlOwned::CreateTable(create_table));
+    reenterable_schema_change_request(node::global()?, ir_node, override_deadline, governor_op_id)?;
+
+    if let Err(e) =
+        dispatch_select_into_statement(runtime, &insert, params, override_deadline, port)
+    {
+        let drop = format!("DROP TABLE {table}");
+        let mut drop_port = PicoPortOwned::new();
+        if let Err(drop_err) = dispatch_select_into_statement(
+            runtime,
+            &drop,
+            vec![],
+            override_deadline,
+            &mut drop_port,
+        ) {
+            tlog!(
+                Warning,
+                "failed to drop table {table} filled by SELECT INTO: {drop_err}"
+            );
+        }
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Execute a statement issued by `SELECT ... INTO`.
+fn dispatch_select_into_statement<'p>(
+    runtime: &RouterRuntime,
+    pattern: &str,
+    params: Vec<Value>,
+    override_deadline: Option<Instant>,
+    port: &mut impl Port<'p>,
+) -> traft::Result<()> {
+    let Some(sql_options) = DYNAMIC_CONFIG.current_sql_options() else {
+        return Err(Error::Uninitialized);
+    };
+    let statement = BoundStatement::parse_and_bind(runtime, pattern, params, sql_options)?;
+    dispatch_bound_statement_impl(runtime, statement, override_deadline, None, port)
+}
+
 /// Execute the cluster SQL query.
 ///
 /// `override_deadline` if provided is used to override the timeout provided in
@@ -778,26 +1012,125 @@ pub unsafe extern "C" fn proc_sql_dispatch(
     }
 }
 
-pub fn parse_and_dispatch<'p>(
-    query_text: &str,
-    params: Vec<Value>,
-    override_deadline: Option<Instant>,
-    governor_op_id: Option<u64>,
-    port: &mut impl Port<'p>,
-) -> traft::Result<()> {
+/// Dispatches an SQL query to the cluster and returns its result
+/// as an Apache Arrow IPC stream.
+/// Part of public RPC API.
+#[tarantool::proc(packed_args)]
+pub fn proc_sql_dispatch_arrow(args: DispatchArgs) -> traft::Result<ByteBuf> {
     let router = RouterRuntime::new();
+    let bound_statement = parse_and_bind(&router, &args.pattern, args.params)?;
+    let plan = bound_statement.as_plan();
+    if !plan.is_dql()? || plan.is_explain() || plan.select_into.is_some() {
+        return Err(Error::other(
+            "only results of SELECT and VALUES can be encoded as Arrow",
+        ));
+    }
 
+    let mut port = PicoPortOwned::new();
+    dispatch_bound_statement(&router, bound_statement, None, None, &mut port)?;
+
+    let port = port.port_c();
+    // The first msgpack in DQL response is metadata, the remaining ones are tuples.
+    let mut mps = port.iter();
+    let metadata: Vec<MetadataColumn> = match mps.next() {
+        Some(mp) => msgpack::decode(mp).map_err(TarantoolError::from)?,
+        None => Vec::new(),
+    };
+    let rows = mps
+        .map(msgpack::decode::<Vec<Value>>)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(TarantoolError::from)?;
+    let stream = arrow::encode_ipc_stream(&metadata, &rows)?;
+    Ok(ByteBuf::from(stream))
+}
+
+/// Estimates how many buckets and storages an SQL query would be
+/// dispatched to, without executing it.
+/// Part of public RPC API.
+#[tarantool::proc(packed_args)]
+pub fn proc_sql_estimate_fanout(args: DispatchArgs) -> traft::Result<FanOut> {
+    let router = RouterRuntime::new();
     let Some(sql_options) = DYNAMIC_CONFIG.current_sql_options() else {
         return Err(Error::Uninitialized);
     };
-    let bound_statement = BoundStatement::parse_and_bind(&router, query_text, params, sql_options)?;
+    let bound_statement =
+        BoundStatement::parse_and_bind(&router, &args.pattern, args.params, sql_options)?;
+    Ok(router.estimate_fanout(bound_statement)?)
+}
+
+/// Reads a tuple by its primary key for a point get of the router.
+/// Fails if the table schema differs from the one the query was planned for.
+#[tarantool::proc]
+pub fn proc_sql_point_get(
+    space_id: SpaceId,
+    schema_version: u64,
+    key: Vec<rmpv::Value>,
+) -> traft::Result<Option<rmpv::Value>> {
+    if get_table_version_by_id(space_id)? != schema_version {
+        return Err(SbroadError::OutdatedStorageSchema.into());
+    }
+    let space = unsafe { Space::from_id_unchecked(space_id) };
+    let Some(tuple) = space.get(&key)? else {
+        return Ok(None);
+    };
+    Ok(Some(tuple.decode()?))
+}
+
+/// Casts text to boolean on the storages, e.g. `CAST(s AS bool)` of a column.
+/// Uses the same conversion as the router does for the constants.
+#[tarantool::proc]
+pub fn proc_sql_text_to_bool(text: Option<String>) -> traft::Result<Option<bool>> {
+    let Some(text) = text else {
+        return Ok(None);
+    };
+    match Value::String(text).cast(UnrestrictedType::Boolean)? {
+        Value::Boolean(v) => Ok(Some(v)),
+        _ => Ok(None),
+    }
+}
+
+/// Casts boolean to text on the storages, e.g. `CAST(b AS text)` of a column.
+/// Uses the same conversion as the router does for the constants.
+#[tarantool::proc]
+pub fn proc_sql_bool_to_text(value: Option<bool>) -> traft::Result<Option<String>> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    match Value::Boolean(value).cast(UnrestrictedType::String)? {
+        Value::String(v) => Ok(Some(v)),
+        _ => Ok(None),
+    }
+}
+
+/// Parses and binds an SQL query, logging it to the audit and SQL logs.
+fn parse_and_bind(
+    router: &RouterRuntime,
+    query_text: &str,
+    params: Vec<Value>,
+) -> traft::Result<BoundStatement> {
+    let Some(sql_options) = DYNAMIC_CONFIG.current_sql_options() else {
+        return Err(Error::Uninitialized);
+    };
+    let bound_statement = BoundStatement::parse_and_bind(router, query_text, params, sql_options)?;
     if bound_statement.params_for_audit().is_some() {
         audit::policy::log_dml_for_user(query_text, bound_statement.params_for_audit());
     }
     if router.is_sql_log_enabled(bound_statement.as_plan())? {
-        tlog!(Info, "sql-log: {query_text}");
+        let query_id = bound_statement.query_fingerprint().unwrap_or_default();
+        tlog!(Info, "sql-log: {query_text}"; "query_id" => %query_id);
     }
+    Ok(bound_statement)
+}
 
+pub fn parse_and_dispatch<'p>(
+    query_text: &str,
+    params: Vec<Value>,
+    override_deadline: Option<Instant>,
+    governor_op_id: Option<u64>,
+    port: &mut impl Port<'p>,
+) -> traft::Result<()> {
+    let router = RouterRuntime::new();
+    let bound_statement = parse_and_bind(&router, query_text, params)?;
     dispatch_bound_statement(
         &router,
         bound_statement,
@@ -1522,6 +1855,9 @@ fn ddl_ir_node_to_op_or_result(
             if_not_exists,
             unlogged,
             pk_contains_bucket_id,
+            identity,
+            checks,
+            defaults,
             ..
         }) => {
             let format = format
@@ -1547,6 +1883,25 @@ fn ddl_ir_node_to_op_or_result(
             if *pk_contains_bucket_id {
                 opts.push(TableOption::PkContainsBucketId(*pk_contains_bucket_id));
             }
+            if let Some(column) = identity {
+                opts.push(TableOption::Identity {
+                    column: column.to_string(),
+                });
+            }
+            for check in checks {
+                opts.push(TableOption::Check {
+                    name: check.name.to_string(),
+                    column: check.column.to_string(),
+                    op: check.op.as_str().into(),
+                    value: check.value.clone().into(),
+                });
+            }
+            for default in defaults {
+                opts.push(TableOption::Default {
+                    column: default.column.to_string(),
+                    value: default.value.clone().into(),
+                });
+            }
 
             let topology_cache = node.topology_cache.get();
 
@@ -1761,6 +2116,7 @@ fn ddl_ir_node_to_op_or_result(
             table_name,
             columns,
             unique,
+            nulls_distinct,
             index_type,
             bloom_fpr,
             page_size,
@@ -1774,8 +2130,11 @@ fn ddl_ir_node_to_op_or_result(
             if_not_exists,
             ..
         }) => {
-            let mut opts: Vec<IndexOption> = Vec::with_capacity(9);
+            let mut opts: Vec<IndexOption> = Vec::with_capacity(10);
             opts.push(IndexOption::Unique(*unique));
+            if !*nulls_distinct {
+                opts.push(IndexOption::NullsDistinct(false));
+            }
             if let Some(bloom_fpr) = bloom_fpr {
                 opts.push(IndexOption::BloomFalsePositiveRate(*bloom_fpr));
             }
@@ -1805,12 +2164,19 @@ fn ddl_ir_node_to_op_or_result(
             }
             opts.shrink_to_fit();
 
-            let columns = columns.clone();
+            let (columns, collations) = columns
+                .iter()
+                .map(|column| {
+                    let collation = column.collation.map(|c| c.to_smolstr());
+                    (column.name.clone(), collation)
+                })
+                .unzip();
 
             let params = CreateIndexParams {
                 name: name.clone(),
                 space_name: table_name.clone(),
                 columns,
+                collations,
                 ty: *index_type,
                 opts,
                 initiator: current_user,
@@ -1903,6 +2269,7 @@ fn ddl_ir_node_to_op_or_result(
                         column_name_set.insert(SmolStr::new(&f.name), i);
                     }
                     let mut num_skipped_ops = 0;
+                    let mut has_drops = false;
 
                     for op in columns.iter() {
                         match op {
@@ -1910,6 +2277,18 @@ fn ddl_ir_node_to_op_or_result(
                                 ref column,
                                 if_not_exists,
                             } => {
+                                // The new column would take the place of a dropped one,
+                                // and its values are removed only after the format change.
+                                if has_drops {
+                                    return Err(Error::Sbroad(SbroadError::Unsupported(
+                                        Entity::Column,
+                                        Some(format_smolstr!(
+                                            "{}: can't add a column after dropping one in the same statement",
+                                            column.name
+                                        )),
+                                    )));
+                                }
+
                                 // do not add this column with the same name
                                 if column_name_set
                                     .insert(column.name.clone(), new_table_format.len())
@@ -1932,6 +2311,29 @@ fn ddl_ir_node_to_op_or_result(
                                 };
                                 new_table_format.push(field);
                             }
+                            &AlterColumn::Drop {
+                                ref name,
+                                if_exists,
+                            } => {
+                                let Some(index) = column_name_set.remove(name.as_str()) else {
+                                    if if_exists {
+                                        num_skipped_ops += 1;
+                                        continue;
+                                    }
+                                    return Err(error::DoesNotExist::Column(name.clone()).into());
+                                };
+                                check_column_can_be_dropped(storage, table, index, name)?;
+                                if index + 1 != new_table_format.len() {
+                                    return Err(Error::Sbroad(SbroadError::Invalid(
+                                        Entity::Column,
+                                        Some(format_smolstr!(
+                                            "{name}: only the last column of a table can be dropped"
+                                        )),
+                                    )));
+                                }
+                                new_table_format.pop();
+                                has_drops = true;
+                            }
                             AlterColumn::Rename { from, to } => {
                                 let Some(index) = column_name_set.remove(from.as_str()) else {
                                     return Err(error::DoesNotExist::Column(from.clone()).into());
@@ -1982,6 +2384,56 @@ fn ddl_ir_node_to_op_or_result(
                 }
             }
         }
+        DdlOwned::Comment(Comment {
+            table_name,
+            column_name,
+            comment,
+            ..
+        }) => {
+            let Some(mut table_def) = storage.pico_table.by_name(table_name)? else {
+                return Err(error::DoesNotExist::Table(table_name.clone()).into());
+            };
+
+            // Like in PostgreSQL, an empty comment is the same as NULL: it drops the comment.
+            let comment = comment.as_deref().unwrap_or_default();
+            match column_name {
+                None => table_def.description = comment.to_string(),
+                Some(column_name) => {
+                    if !table_def
+                        .format
+                        .iter()
+                        .any(|f| f.name == column_name.as_str())
+                    {
+                        return Err(error::DoesNotExist::Column(column_name.clone()).into());
+                    }
+                    table_def.opts.retain(|opt| match opt {
+                        TableOption::ColumnComment { column, .. } => column != column_name,
+                        _ => true,
+                    });
+                    if !comment.is_empty() {
+                        table_def.opts.push(TableOption::ColumnComment {
+                            column: column_name.to_string(),
+                            comment: comment.to_string(),
+                        });
+                    }
+                }
+            }
+
+            let dml = Dml::replace_raw(
+                crate::catalog::pico_table::PicoTable::TABLE_ID,
+                msgpack::encode(&table_def),
+                current_user,
+            )?;
+            Ok(Continue(Op::Dml(dml)))
+        }
+        DdlOwned::SetParam(SetParam {
+            param_value: SetParamValue::ApplicationName { .. } | SetParamValue::PlanCache { .. },
+            ..
+        }) => {
+            // Applied to the client session by pgproto, which also restores
+            // the values set with `SET LOCAL` when the transaction block ends.
+            Ok(Break(ConsumerResult { row_count: 0 }))
+        }
         DdlOwned::SetParam(SetParam { param_value, .. }) => {
             tlog!(
                 Warning,
@@ -1990,11 +2442,52 @@ fn ddl_ir_node_to_op_or_result(
             );
             Ok(Break(ConsumerResult { row_count: 0 }))
         }
-        DdlOwned::SetTransaction { .. } => {
-            tlog!(
-                Warning,
-                "Transaction setting is currently disabled. Skipping."
-            );
+        DdlOwned::SetTransaction(SetTransaction {
+            isolation_level, ..
+        }) => {
+            match isolation_level {
+                // Every statement is executed with read committed isolation,
+                // stronger levels can't be provided without silently downgrading them.
+                Some(
+                    level @ (TransactionIsolationLevel::RepeatableRead
+                    | TransactionIsolationLevel::Serializable),
+                ) => {
+                    return Err(Error::Sbroad(SbroadError::Unsupported(
+                        Entity::Transaction,
+                        Some(format_smolstr!(
+                            "isolation level {level}, only READ COMMITTED is available"
+                        )),
+                    )));
+                }
+                // Like in Postgres, READ UNCOMMITTED behaves as READ COMMITTED.
+                Some(
+                    TransactionIsolationLevel::ReadCommitted
+                    | TransactionIsolationLevel::ReadUncommitted,
+                ) => {}
+                None => {
+                    tlog!(
+                        Warning,
+                        "Transaction setting is currently disabled. Skipping."
+                    );
+                }
+            }
+            Ok(Break(ConsumerResult { row_count: 0 }))
+        }
+        DdlOwned::Notification(Notification {
+            command: NotificationCommand::Notify { channel, payload },
+            ..
+        }) => {
+            pg_notify::check_payload(payload)?;
+            let req = pg_notify::Request {
+                channel: channel.clone(),
+                payload: payload.clone(),
+            };
+            let timeout = duration_from_secs_f64_clamped(ddl.timeout()?);
+            pg_notify::broadcast_notifications(vec![req], timeout);
+            Ok(Break(ConsumerResult { row_count: 0 }))
+        }
+        DdlOwned::Notification(_) => {
+            // Applied to the client session by pgproto.
             Ok(Break(ConsumerResult { row_count: 0 }))
         }
         DdlOwned::CreateSchema | DdlOwned::DropSchema => {
@@ -2005,6 +2498,77 @@ fn ddl_ir_node_to_op_or_result(
     }
 }
 
+/// Check that the column at `index` of the sharded table format is neither
+/// a part of the sharding key, nor used by any index of the table, nor an
+/// identity column.
+fn check_column_can_be_dropped(
+    storage: &Catalog,
+    table: &TableDef,
+    index: usize,
+    name: &str,
+) -> traft::Result<()> {
+    // Columns added by the same statement are not in the stored metadata yet.
+    let Some(field) = table.format.get(index) else {
+        return Ok(());
+    };
+    // Values of the dropped column are removed on the replicaset masters,
+    // while global tables are stored by every instance on its own.
+    if table.distribution.is_global() {
+        return Err(Error::Sbroad(SbroadError::Unsupported(
+            Entity::Column,
+            Some(format_smolstr!(
+                "{name}: columns of global tables can't be dropped"
+            )),
+        )));
+    }
+    let column = field.name.as_str();
+    let is_sharding_column = match &table.distribution {
+        Distribution::Global => unreachable!("checked above"),
+        Distribution::ShardedImplicitly { sharding_key, .. } => {
+            column == DEFAULT_BUCKET_ID_COLUMN_NAME || sharding_key.iter().any(|k| k == column)
+        }
+        Distribution::ShardedByField { field, .. } => field == column,
+    };
+    if is_sharding_column {
+        return Err(Error::Sbroad(SbroadError::Invalid(
+            Entity::Column,
+            Some(format_smolstr!(
+                "{name}: a sharding key column can't be dropped"
+            )),
+        )));
+    }
+    for index_def in storage.indexes.by_space_id(table.id)? {
+        if index_def.parts.iter().any(|p| p.field == column) {
+            return Err(Error::Sbroad(SbroadError::Invalid(
+                Entity::Column,
+                Some(format_smolstr!(
+                    "{name}: column is used by index {} and can't be dropped",
+                    index_def.name
+                )),
+            )));
+        }
+    }
+    if table.identity_column() == Some(column) {
+        return Err(Error::Sbroad(SbroadError::Invalid(
+            Entity::Column,
+            Some(format_smolstr!(
+                "{name}: an identity column can't be dropped"
+            )),
+        )));
+    }
+    let has_column_options = table.checks().any(|(_, c, _, _)| c == column)
+        || table.defaults().any(|(c, _)| c == column);
+    if has_column_options {
+        return Err(Error::Sbroad(SbroadError::Invalid(
+            Entity::Column,
+            Some(format_smolstr!(
+                "{name}: a column with a default value or a CHECK constraint can't be dropped"
+            )),
+        )));
+    }
+    Ok(())
+}
+
 /// Check if operation is applied to the storage.
 /// There three possible outcomes:
 /// - `Ok(true)` - operation was applied successfully.
@@ -2922,6 +3486,9 @@ fn create_dml_ops(
 
     let ir = query.get_exec_plan().get_ir_plan();
     let top = ir.get_top()?;
+    if let DmlKind::Insert | DmlKind::Update = dml_kind {
+        check_dml_constraints(ir, top, &vtable)?;
+    }
     let builder = match dml_kind {
         DmlKind::Insert => init_insert_tuple_builder(ir, vtable.get_columns(), top)?,
         DmlKind::Update => init_local_update_tuple_builder(ir, vtable.get_columns(), top)?,
@@ -3039,5 +3606,6 @@ pub(crate) fn value_type_str(value: &Value) -> &'static str {
         Value::String { .. } => "string",
         Value::Tuple { .. } => "tuple",
         Value::Uuid { .. } => "uuid",
+        Value::Bytea { .. } => "varbinary",
     }
 }
@@ -0,0 +1,584 @@
+//! Serialization of DQL results into the Apache Arrow IPC stream format.
+//!
+//! A result is encoded as a schema message, a single record batch and the
+//! end-of-stream marker. Only a handful of the Arrow metadata tables are
+//! needed, so their flatbuffers are built by hand instead of pulling in the
+//! whole Arrow implementation.
+//!
+//! See <https://arrow.apache.org/docs/format/Columnar.html#serialization-and-interprocess-communication-ipc>.
+
+use smol_str::format_smolstr;
+use sql::errors::{Entity, SbroadError};
+use sql::executor::result::MetadataColumn;
+use sql::ir::value::Value;
+use std::cmp::Reverse;
+
+/// Marker preceding the length of every encapsulated message.
+const CONTINUATION: [u8; 4] = [0xff; 4];
+
+/// `MetadataVersion.V5`.
+const METADATA_VERSION: i16 = 4;
+
+/// `MessageHeader` union members.
+const HEADER_SCHEMA: u8 = 1;
+const HEADER_RECORD_BATCH: u8 = 3;
+
+/// Arrow type of a result column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ArrowType {
+    /// Column of `NULL` literals, its SQL type is unknown.
+    Null,
+    Bool,
+    Int64,
+    Float64,
+    Utf8,
+    Binary,
+    /// Microseconds since the Unix epoch in UTC.
+    Timestamp,
+}
+
+impl ArrowType {
+    fn from_column(column: &MetadataColumn) -> Result<Self, SbroadError> {
+        let ty = match column.r#type.as_str() {
+            "unknown" => Self::Null,
+            "bool" => Self::Bool,
+            "int" => Self::Int64,
+            "double" => Self::Float64,
+            "string" => Self::Utf8,
+            "varbinary" => Self::Binary,
+            "datetime" => Self::Timestamp,
+            ty => {
+                return Err(SbroadError::Unsupported(
+                    Entity::Column,
+                    Some(format_smolstr!(
+                        "type {ty} of column \"{}\" can't be encoded as Arrow",
+                        column.name
+                    )),
+                ))
+            }
+        };
+        Ok(ty)
+    }
+
+    /// Id of the type in the `Type` union.
+    fn union_id(self) -> u8 {
+        match self {
+            Self::Null => 1,
+            Self::Int64 => 2,
+            Self::Float64 => 3,
+            Self::Binary => 4,
+            Self::Utf8 => 5,
+            Self::Bool => 6,
+            Self::Timestamp => 10,
+        }
+    }
+
+    fn type_table(self) -> Fb {
+        match self {
+            // Int { bitWidth, is_signed }
+            Self::Int64 => Fb::Table(vec![(0, Field::I32(64)), (1, Field::U8(1))]),
+            // FloatingPoint { precision: DOUBLE }
+            Self::Float64 => Fb::Table(vec![(0, Field::I16(2))]),
+            // Timestamp { unit: MICROSECOND, timezone }
+            Self::Timestamp => Fb::Table(vec![
+                (0, Field::I16(2)),
+                (1, Field::Offset(Fb::String("UTC".into()))),
+            ]),
+            Self::Null | Self::Bool | Self::Utf8 | Self::Binary => Fb::Table(vec![]),
+        }
+    }
+}
+
+/// Encode a DQL result as an Arrow IPC stream.
+pub fn encode_ipc_stream(
+    metadata: &[MetadataColumn],
+    rows: &[Vec<Value>],
+) -> Result<Vec<u8>, SbroadError> {
+    let types = metadata
+        .iter()
+        .map(ArrowType::from_column)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let fields = metadata
+        .iter()
+        .zip(&types)
+        .map(|(column, ty)| {
+            Fb::Table(vec![
+                (0, Field::Offset(Fb::String(column.name.clone()))),
+                // nullable
+                (1, Field::U8(1)),
+                (2, Field::U8(ty.union_id())),
+                (3, Field::Offset(ty.type_table())),
+                // children
+                (5, Field::Offset(Fb::Tables(vec![]))),
+            ])
+        })
+        .collect();
+    let schema = Fb::Table(vec![(1, Field::Offset(Fb::Tables(fields)))]);
+
+    let mut nodes = Vec::with_capacity(metadata.len());
+    let mut buffers = Vec::new();
+    let mut body = Vec::new();
+    for (pos, (column, ty)) in metadata.iter().zip(&types).enumerate() {
+        let data = ColumnData::build(column, *ty, rows.iter().map(|row| row.get(pos)))?;
+        nodes.push((rows.len() as i64, data.null_count as i64));
+        for buffer in data.buffers(*ty) {
+            buffers.push((body.len() as i64, buffer.len() as i64));
+            body.extend_from_slice(buffer);
+            pad(&mut body, 8);
+        }
+    }
+    let batch = Fb::Table(vec![
+        (0, Field::I64(rows.len() as i64)),
+        (1, Field::Offset(Fb::Pairs(nodes))),
+        (2, Field::Offset(Fb::Pairs(buffers))),
+    ]);
+
+    let mut stream = Vec::new();
+    write_message(&mut stream, HEADER_SCHEMA, schema, &[]);
+    write_message(&mut stream, HEADER_RECORD_BATCH, batch, &body);
+    // End-of-stream marker.
+    stream.extend_from_slice(&CONTINUATION);
+    stream.extend_from_slice(&0_i32.to_le_bytes());
+    Ok(stream)
+}
+
+fn write_message(stream: &mut Vec<u8>, header_type: u8, header: Fb, body: &[u8]) {
+    let message = Fb::Table(vec![
+        (0, Field::I16(METADATA_VERSION)),
+        (1, Field::U8(header_type)),
+        (2, Field::Offset(header)),
+        (3, Field::I64(body.len() as i64)),
+    ]);
+    let mut metadata = FbWriter::finish(message);
+    // The 8-byte prefix keeps the body aligned.
+    pad(&mut metadata, 8);
+    stream.extend_from_slice(&CONTINUATION);
+    stream.extend_from_slice(&(metadata.len() as i32).to_le_bytes());
+    stream.extend_from_slice(&metadata);
+    stream.extend_from_slice(body);
+}
+
+fn pad(buf: &mut Vec<u8>, align: usize) {
+    buf.resize(buf.len().next_multiple_of(align), 0);
+}
+
+fn set_bit(bitmap: &mut [u8], i: usize) {
+    bitmap[i / 8] |= 1 << (i % 8);
+}
+
+/// Buffers of a single column.
+struct ColumnData {
+    null_count: usize,
+    validity: Vec<u8>,
+    offsets: Vec<u8>,
+    values: Vec<u8>,
+}
+
+impl ColumnData {
+    fn build<'v>(
+        column: &MetadataColumn,
+        ty: ArrowType,
+        values: impl ExactSizeIterator<Item = Option<&'v Value>>,
+    ) -> Result<Self, SbroadError> {
+        let len = values.len();
+        let mut data = Self {
+            null_count: 0,
+            validity: vec![0; len.div_ceil(8)],
+            offsets: 0_i32.to_le_bytes().to_vec(),
+            values: Vec::new(),
+        };
+        if ty == ArrowType::Bool {
+            data.values.resize(len.div_ceil(8), 0);
+        }
+
+        for (i, value) in values.enumerate() {
+            let value = value.unwrap_or(&Value::Null);
+            match (ty, value) {
+                // Fixed-width values of nulls still take their place.
+                (ArrowType::Int64 | ArrowType::Float64 | ArrowType::Timestamp, Value::Null) => {
+                    data.null_count += 1;
+                    data.values.extend_from_slice(&[0; 8]);
+                }
+                (_, Value::Null) => data.null_count += 1,
+                (ArrowType::Bool, Value::Boolean(v)) => {
+                    if *v {
+                        set_bit(&mut data.values, i);
+                    }
+                }
+                (ArrowType::Int64, Value::Integer(v)) => {
+                    data.values.extend_from_slice(&v.to_le_bytes())
+                }
+                (ArrowType::Float64, Value::Double(v)) => {
+                    data.values.extend_from_slice(&v.value.to_le_bytes())
+                }
+                (ArrowType::Float64, Value::Integer(v)) => {
+                    data.values.extend_from_slice(&(*v as f64).to_le_bytes())
+                }
+                (ArrowType::Utf8, Value::String(v)) => data.values.extend_from_slice(v.as_bytes()),
+                (ArrowType::Binary, Value::Bytea(v)) => data.values.extend_from_slice(v),
+                (ArrowType::Timestamp, Value::Datetime(v)) => {
+                    let micros = (v.into_inner().unix_timestamp_nanos() / 1000) as i64;
+                    data.values.extend_from_slice(&micros.to_le_bytes());
+                }
+                (ty, value) => {
+                    return Err(SbroadError::Invalid(
+                        Entity::Value,
+                        Some(format_smolstr!(
+                            "{value} of column \"{}\" doesn't match Arrow type {ty:?}",
+                            column.name
+                        )),
+                    ))
+                }
+            }
+            if !matches!(value, Value::Null) {
+                set_bit(&mut data.validity, i);
+            }
+            if matches!(ty, ArrowType::Utf8 | ArrowType::Binary) {
+                let offset = i32::try_from(data.values.len()).map_err(|_| {
+                    SbroadError::Invalid(
+                        Entity::Column,
+                        Some(format_smolstr!(
+                            "column \"{}\" is too large for Arrow",
+                            column.name
+                        )),
+                    )
+                })?;
+                data.offsets.extend_from_slice(&offset.to_le_bytes());
+            }
+        }
+        Ok(data)
+    }
+
+    fn buffers(&self, ty: ArrowType) -> Vec<&[u8]> {
+        // The validity bitmap may be omitted when there are no nulls.
+        let validity = if self.null_count == 0 {
+            &[][..]
+        } else {
+            &self.validity[..]
+        };
+        match ty {
+            ArrowType::Null => vec![],
+            ArrowType::Bool | ArrowType::Int64 | ArrowType::Float64 | ArrowType::Timestamp => {
+                vec![validity, &self.values]
+            }
+            ArrowType::Utf8 | ArrowType::Binary => vec![validity, &self.offsets, &self.values],
+        }
+    }
+}
+
+/// A flatbuffer object.
+enum Fb {
+    /// Table fields by their ids.
+    Table(Vec<(u16, Field)>),
+    String(String),
+    /// Vector of tables.
+    Tables(Vec<Fb>),
+    /// Vector of structs of two longs (`FieldNode` and `Buffer`).
+    Pairs(Vec<(i64, i64)>),
+}
+
+enum Field {
+    U8(u8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    Offset(Fb),
+}
+
+impl Field {
+    fn size(&self) -> usize {
+        match self {
+            Self::U8(_) => 1,
+            Self::I16(_) => 2,
+            Self::I32(_) | Self::Offset(_) => 4,
+            Self::I64(_) => 8,
+        }
+    }
+}
+
+/// Flatbuffer writer. Unlike the usual flatbuffer builders, it writes
+/// objects front to back: offsets are unsigned, so the referenced objects
+/// are written after the referencing ones and the offsets are patched.
+#[derive(Default)]
+struct FbWriter {
+    buf: Vec<u8>,
+}
+
+impl FbWriter {
+    fn finish(root: Fb) -> Vec<u8> {
+        let mut writer = Self::default();
+        writer.buf.extend_from_slice(&[0; 4]);
+        let root_pos = writer.write(root);
+        writer.patch_offset(0, root_pos);
+        writer.buf
+    }
+
+    fn patch_offset(&mut self, slot: usize, target: usize) {
+        let offset = (target - slot) as u32;
+        self.buf[slot..slot + 4].copy_from_slice(&offset.to_le_bytes());
+    }
+
+    /// Pad the buffer so that the next object starts `skip` bytes
+    /// before an `align` boundary.
+    fn align(&mut self, align: usize, skip: usize) {
+        while (self.buf.len() + skip) % align != 0 {
+            self.buf.push(0);
+        }
+    }
+
+    /// Write an object and return its position.
+    fn write(&mut self, object: Fb) -> usize {
+        match object {
+            Fb::Table(fields) => self.write_table(fields),
+            Fb::String(s) => {
+                self.align(4, 0);
+                let pos = self.buf.len();
+                self.buf.extend_from_slice(&(s.len() as u32).to_le_bytes());
+                self.buf.extend_from_slice(s.as_bytes());
+                self.buf.push(0);
+                pos
+            }
+            Fb::Tables(tables) => {
+                self.align(4, 0);
+                let pos = self.buf.len();
+                self.buf
+                    .extend_from_slice(&(tables.len() as u32).to_le_bytes());
+                let first_slot = self.buf.len();
+                self.buf.resize(first_slot + 4 * tables.len(), 0);
+                for (i, table) in tables.into_iter().enumerate() {
+                    let target = self.write(table);
+                    self.patch_offset(first_slot + 4 * i, target);
+                }
+                pos
+            }
+            Fb::Pairs(pairs) => {
+                // Structs of longs are aligned to 8 bytes after the length.
+                self.align(8, 4);
+                let pos = self.buf.len();
+                self.buf
+                    .extend_from_slice(&(pairs.len() as u32).to_le_bytes());
+                for (a, b) in pairs {
+                    self.buf.extend_from_slice(&a.to_le_bytes());
+                    self.buf.extend_from_slice(&b.to_le_bytes());
+                }
+                pos
+            }
+        }
+    }
+
+    fn write_table(&mut self, mut fields: Vec<(u16, Field)>) -> usize {
+        // Larger fields go first: the table starts 4 bytes before an 8-byte
+        // boundary, so every field after the vtable offset stays aligned.
+        fields.sort_by_key(|(_, field)| Reverse(field.size()));
+        let vtable_len = fields.iter().map(|(id, _)| *id as usize + 1).max();
+        let vtable_len = vtable_len.unwrap_or(0);
+        let table_size = 4 + fields.iter().map(|(_, f)| f.size()).sum::<usize>();
+
+        self.align(2, 0);
+        let vtable_pos = self.buf.len();
+        self.buf
+            .extend_from_slice(&((4 + 2 * vtable_len) as u16).to_le_bytes());
+        self.buf
+            .extend_from_slice(&(table_size as u16).to_le_bytes());
+        self.buf.resize(self.buf.len() + 2 * vtable_len, 0);
+
+        self.align(8, 4);
+        let table_pos = self.buf.len();
+        self.buf
+            .extend_from_slice(&((table_pos - vtable_pos) as i32).to_le_bytes());
+        let mut children = Vec::new();
+        for (id, field) in fields {
+            let entry = vtable_pos + 4 + 2 * id as usize;
+            let field_offset = (self.buf.len() - table_pos) as u16;
+            self.buf[entry..entry + 2].copy_from_slice(&field_offset.to_le_bytes());
+            match field {
+                Field::U8(v) => self.buf.push(v),
+                Field::I16(v) => self.buf.extend_from_slice(&v.to_le_bytes()),
+                Field::I32(v) => self.buf.extend_from_slice(&v.to_le_bytes()),
+                Field::I64(v) => self.buf.extend_from_slice(&v.to_le_bytes()),
+                Field::Offset(child) => {
+                    children.push((self.buf.len(), child));
+                    self.buf.extend_from_slice(&[0; 4]);
+                }
+            }
+        }
+        for (slot, child) in children {
+            let target = self.write(child);
+            self.patch_offset(slot, target);
+        }
+        table_pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal reader of the flatbuffers written above.
+    struct Reader<'a>(&'a [u8]);
+
+    impl Reader<'_> {
+        fn u16(&self, pos: usize) -> usize {
+            u16::from_le_bytes(self.0[pos..pos + 2].try_into().unwrap()) as usize
+        }
+
+        fn u32(&self, pos: usize) -> usize {
+            u32::from_le_bytes(self.0[pos..pos + 4].try_into().unwrap()) as usize
+        }
+
+        fn i64(&self, pos: usize) -> i64 {
+            i64::from_le_bytes(self.0[pos..pos + 8].try_into().unwrap())
+        }
+
+        fn deref(&self, pos: usize) -> usize {
+            pos + self.u32(pos)
+        }
+
+        /// Position of a table field, if present.
+        fn field(&self, table: usize, id: usize) -> Option<usize> {
+            let soffset = i32::from_le_bytes(self.0[table..table + 4].try_into().unwrap());
+            let vtable = (table as i64 - soffset as i64) as usize;
+            if 4 + 2 * id >= self.u16(vtable) {
+                return None;
+            }
+            match self.u16(vtable + 4 + 2 * id) {
+                0 => None,
+                offset => Some(table + offset),
+            }
+        }
+
+        fn string(&self, pos: usize) -> &str {
+            let len = self.u32(pos);
+            std::str::from_utf8(&self.0[pos + 4..pos + 4 + len]).unwrap()
+        }
+
+        fn vector(&self, pos: usize) -> (usize, usize) {
+            (self.u32(pos), pos + 4)
+        }
+    }
+
+    /// Split a stream into messages: their header type, header table
+    /// position inside the metadata, metadata and body.
+    fn messages(stream: &[u8]) -> Vec<(u8, usize, &[u8], &[u8])> {
+        let mut messages = Vec::new();
+        let mut pos = 0;
+        loop {
+            assert_eq!(stream[pos..pos + 4], CONTINUATION);
+            let len = Reader(stream).u32(pos + 4);
+            pos += 8;
+            if len == 0 {
+                break;
+            }
+            assert_eq!(pos % 8, 0);
+            let metadata = &stream[pos..pos + len];
+            pos += len;
+            let meta = Reader(metadata);
+            let root = meta.deref(0);
+            let version = meta.field(root, 0).unwrap();
+            assert_eq!(meta.u16(version), METADATA_VERSION as usize);
+            let header_type = metadata[meta.field(root, 1).unwrap()];
+            let header = meta.deref(meta.field(root, 2).unwrap());
+            let body_len = meta.i64(meta.field(root, 3).unwrap()) as usize;
+            messages.push((header_type, header, metadata, &stream[pos..pos + body_len]));
+            pos += body_len;
+        }
+        assert_eq!(pos, stream.len());
+        messages
+    }
+
+    fn column(name: &str, ty: &str) -> MetadataColumn {
+        MetadataColumn::new(name.into(), ty.into())
+    }
+
+    #[test]
+    fn encode_int_and_string() {
+        let metadata = [column("id", "int"), column("name", "string")];
+        let rows = [
+            vec![Value::Integer(1), Value::String("one".into())],
+            vec![Value::Integer(-2), Value::Null],
+            vec![Value::Integer(3), Value::String("three".into())],
+        ];
+        let stream = encode_ipc_stream(&metadata, &rows).unwrap();
+        let messages = messages(&stream);
+        assert_eq!(messages.len(), 2);
+
+        // Schema.
+        let (header_type, schema, metadata, _) = messages[0];
+        assert_eq!(header_type, HEADER_SCHEMA);
+        let meta = Reader(metadata);
+        let (count, fields) = meta.vector(meta.deref(meta.field(schema, 1).unwrap()));
+        assert_eq!(count, 2);
+        let mut names = Vec::new();
+        for i in 0..count {
+            let field = meta.deref(fields + 4 * i);
+            names.push(meta.string(meta.deref(meta.field(field, 0).unwrap())));
+            let type_type = metadata[meta.field(field, 2).unwrap()];
+            let ty = meta.deref(meta.field(field, 3).unwrap());
+            match i {
+                0 => {
+                    assert_eq!(type_type, 2);
+                    assert_eq!(meta.u32(meta.field(ty, 0).unwrap()), 64);
+                    assert_eq!(metadata[meta.field(ty, 1).unwrap()], 1);
+                }
+                _ => assert_eq!(type_type, 5),
+            }
+        }
+        assert_eq!(names, ["id", "name"]);
+
+        // Record batch.
+        let (header_type, batch, metadata, body) = messages[1];
+        assert_eq!(header_type, HEADER_RECORD_BATCH);
+        let meta = Reader(metadata);
+        assert_eq!(meta.i64(meta.field(batch, 0).unwrap()), 3);
+        let (count, nodes) = meta.vector(meta.deref(meta.field(batch, 1).unwrap()));
+        assert_eq!(count, 2);
+        assert_eq!(nodes % 8, 0);
+        let nodes: Vec<_> = (0..count)
+            .map(|i| (meta.i64(nodes + 16 * i), meta.i64(nodes + 16 * i + 8)))
+            .collect();
+        assert_eq!(nodes, [(3, 0), (3, 1)]);
+        let (count, buffers) = meta.vector(meta.deref(meta.field(batch, 2).unwrap()));
+        assert_eq!(count, 5);
+        let buffers: Vec<&[u8]> = (0..count)
+            .map(|i| {
+                let offset = meta.i64(buffers + 16 * i) as usize;
+                assert_eq!(offset % 8, 0);
+                let len = meta.i64(buffers + 16 * i + 8) as usize;
+                &body[offset..offset + len]
+            })
+            .collect();
+
+        // No nulls in the first column, so its validity bitmap is omitted.
+        assert!(buffers[0].is_empty());
+        let ids: Vec<i64> = buffers[1]
+            .chunks(8)
+            .map(|v| i64::from_le_bytes(v.try_into().unwrap()))
+            .collect();
+        assert_eq!(ids, [1, -2, 3]);
+
+        let validity = buffers[2];
+        let offsets: Vec<usize> = buffers[3]
+            .chunks(4)
+            .map(|v| i32::from_le_bytes(v.try_into().unwrap()) as usize)
+            .collect();
+        let names: Vec<Option<&str>> = (0..3)
+            .map(|i| {
+                let valid = validity[i / 8] & (1 << (i % 8)) != 0;
+                let value = &buffers[4][offsets[i]..offsets[i + 1]];
+                valid.then(|| std::str::from_utf8(value).unwrap())
+            })
+            .collect();
+        assert_eq!(names, [Some("one"), None, Some("three")]);
+    }
+
+    #[test]
+    fn encode_unsupported_type() {
+        let metadata = [column("id", "int"), column("price", "decimal")];
+        let err = encode_ipc_stream(&metadata, &[]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unsupported column: type decimal of column \"price\" can't be encoded as Arrow"
+        );
+    }
+}
@@ -6,6 +6,7 @@ local vrs = require('vshard.replicaset')
 local table = require('table')
 local fiber = require('fiber')
 local buffer = require('buffer')
+local log = require('log')
 local ref_id = 0
 local session_id = require('uuid').str()
 local SQL_MIN_TIMEOUT = 10
@@ -102,6 +103,88 @@ local function future_wait(cond, timeout)
     return nil, res
 end
 
+--
+-- Keep at most `limit` requests of a dispatch in flight: before sending
+-- one more request, wait for the oldest ones until there is a free slot.
+-- The results of the waited requests are kept in `done` and are picked
+-- up later by `collect`. Only the already sent requests are waited for,
+-- so a limit above the number of the replicasets is never reached.
+--
+-- @param inflight queue of the replicaset uuids with in-flight requests.
+-- @param futures mapping between a replicaset uuid and its future.
+-- @param done mapping between a replicaset uuid and the result of its
+-- finished request.
+-- @param limit maximum number of the in-flight requests, 0 disables it.
+-- @param timeout timeout in seconds.
+--
+local function throttle(inflight, futures, done, limit, timeout)
+    if limit == nil or limit <= 0 then
+        return
+    end
+    local deadline = fiber.clock() + timeout
+    while #inflight >= limit do
+        local uuid = table.remove(inflight, 1)
+        local res, err = future_wait(futures[uuid], deadline - fiber.clock())
+        done[uuid] = { res = res, err = err }
+    end
+end
+
+--
+-- Wait for the request result, unless it was already received by `throttle`.
+--
+local function collect(uuid, future, done, timeout)
+    local result = done[uuid]
+    if result ~= nil then
+        return result.res, result.err
+    end
+    return future_wait(future, timeout)
+end
+
+--
+-- Build an error naming every failed replicaset. When the responses
+-- were already collected, the number of the succeeded replicasets is
+-- reported as well.
+--
+-- @param failed mapping between a replicaset uuid and its error.
+-- @param total number of the replicasets the request was sent to.
+-- @param succeeded number of the succeeded replicasets or nil.
+--
+local function dispatch_error(failed, total, succeeded)
+    local msgs = {}
+    for uuid, err in pairs(failed) do
+        local msg = "Unknown error"
+        if err and err.message ~= nil then
+            msg = err.message
+        end
+        table.insert(msgs, "Error on replicaset " .. uuid .. ": " .. msg)
+    end
+    table.sort(msgs)
+    local msg = table.concat(msgs, "; ")
+    if total > 1 then
+        msg = msg .. string.format(" (failed on %d of %d replicasets", #msgs, total)
+        if succeeded ~= nil then
+            msg = msg .. string.format(", %d succeeded", succeeded)
+        end
+        msg = msg .. ")"
+    end
+    return lerror.make(msg)
+end
+
+--
+-- Best-effort mode of the read queries: drop the results of the failed
+-- replicasets and keep the rest with a warning in the log.
+--
+-- @return mapping between replicaset uuid and ibuf containing result
+--
+local function partial_results(res_map, failed, total, succeeded)
+    for uuid, _ in pairs(failed) do
+        res_map[uuid] = nil
+    end
+    local err = dispatch_error(failed, total, succeeded)
+    log.warn("returning partial results: %s", err.message)
+    return res_map
+end
+
 --
 -- Helper function to execute SQL request on multiple storages,
 -- without buckets being moved between the storages by vhard
@@ -132,6 +215,10 @@ end
 --  outdated configuration (some buckets were added/deleted on storages that router
 --  does not know about). This option should be used only if you intend to execute
 --  the function on all replicasets and want to ensure that all buckets were covered.
+--  3. partial_results - return the results of the succeeded replicasets
+--  when some of them fail.
+--  4. concurrency_max - maximum number of the in-flight requests of a
+--  stage, 0 disables the limit.
 --  @param tier Name of the vshard tier to use.
 --
 -- @return mapping between replicaset uuid and ibuf containing result
@@ -141,8 +228,10 @@ local function two_step_dispatch(uuid_to_args, opts, tier)
     local replicasets = router:routeall()
     local timeout
     local res_map = {}
+    local total = 0
     for uuid, _ in pairs(uuid_to_args) do
         res_map[uuid] = buffer.ibuf()
+        total = total + 1
     end
     if opts then
         timeout = opts.timeout or SQL_MIN_TIMEOUT
@@ -151,9 +240,12 @@ local function two_step_dispatch(uuid_to_args, opts, tier)
     end
 
     local err, err_uuid, res
+    local failed, succeeded = {}, 0
     local futures = {}
     local opts_ref = { is_async = true }
     local opts_map = { is_async = true, skip_header = true }
+    local concurrency_max = opts and opts.concurrency_max or 0
+    local inflight, done = {}, {}
     local rs_count = 0
     local rid = ref_new()
     local sid = session_current()
@@ -171,6 +263,8 @@ local function two_step_dispatch(uuid_to_args, opts, tier)
     --
     for uuid, _ in pairs(uuid_to_args) do
         local rs = replicasets[uuid]
+        throttle(inflight, futures, done, concurrency_max, timeout)
+        timeout = deadline - fiber.clock()
         res, err = rs:callrw('pico.dispatch.lref.add',
             { rid, sid, timeout }, opts_ref)
         if res == nil then
@@ -178,13 +272,14 @@ local function two_step_dispatch(uuid_to_args, opts, tier)
             goto fail
         end
         futures[uuid] = res
+        table.insert(inflight, uuid)
         rs_count = rs_count + 1
     end
     --
     -- Ref stage: collect.
     --
     for uuid, future in pairs(futures) do
-        res, err = future_wait(future, timeout)
+        res, err = collect(uuid, future, done, timeout)
         -- Handle netbox error first.
         if res == nil then
             err_uuid = uuid
@@ -201,8 +296,11 @@ local function two_step_dispatch(uuid_to_args, opts, tier)
 
     -- Map stage: send.
     --
+    inflight, done = {}, {}
     for uuid, rs_args in pairs(uuid_to_args) do
         local rs = replicasets[uuid]
+        throttle(inflight, futures, done, concurrency_max, timeout)
+        timeout = deadline - fiber.clock()
         opts_map['buffer'] = res_map[uuid]
         res, err = rs:callrw(
             '.proc_sql_execute',
@@ -214,23 +312,34 @@ local function two_step_dispatch(uuid_to_args, opts, tier)
             goto fail
         end
         futures[uuid] = res
+        table.insert(inflight, uuid)
     end
     --
     -- Map stage: collect.
     --
     for uuid, f in pairs(futures) do
-        res, err = future_wait(f, timeout)
-        if res == nil then
-            err_uuid = uuid
-            goto fail
-        end
-        if err ~= nil then
-            err_uuid = uuid
-            goto fail
+        res, err = collect(uuid, f, done, timeout)
+        if res == nil or err ~= nil then
+            failed[uuid] = err or false
+        else
+            succeeded = succeeded + 1
         end
         timeout = deadline - fiber.clock()
     end
-    do return res_map end
+    if next(failed) == nil then
+        return res_map
+    end
+    if opts and opts.partial_results and succeeded > 0 then
+        for uuid, _ in pairs(failed) do
+            -- Best effort to remove the refs left on the failed storages.
+            local f = replicasets[uuid]:callrw('pico.dispatch.lref.del',
+                { rid, sid }, opts_ref)
+            if f ~= nil then
+                f:discard()
+            end
+        end
+        return partial_results(res_map, failed, total, succeeded)
+    end
 
     ::fail::
     for uuid, f in pairs(futures) do
@@ -245,11 +354,71 @@ local function two_step_dispatch(uuid_to_args, opts, tier)
             f:discard()
         end
     end
-    local msg = "Unknown error"
-    if err ~= nil and err.message ~= nil then
-        msg = err.message
+    if next(failed) ~= nil then
+        error(dispatch_error(failed, total, succeeded))
     end
-    error(lerror.make("Error on replicaset " .. err_uuid .. ": " .. msg))
+    error(dispatch_error({ [err_uuid] = err or false }, total))
+end
+
+--
+-- Replication lags of the replicas by uuid: the lag (in seconds) and the
+-- time it was reported at. They are refreshed in the background, so that
+-- routing a read doesn't wait for an extra round trip to every replica.
+--
+local replica_lags = {}
+local REPLICA_LAG_REFRESH_INTERVAL = 0.5
+
+local function refresh_replica_lag(replica)
+    local entry = replica_lags[replica.uuid]
+    if entry == nil then
+        entry = {}
+        replica_lags[replica.uuid] = entry
+    end
+    if entry.pending or (entry.clock ~= nil
+            and fiber.clock() - entry.clock < REPLICA_LAG_REFRESH_INTERVAL) then
+        return
+    end
+    entry.pending = true
+    fiber.create(function()
+        local ok, future = replica:call('pico.dispatch.replication_lag', {},
+            { is_async = true })
+        local res
+        if ok then
+            res = future_wait(future, REPLICA_LAG_REFRESH_INTERVAL)
+        end
+        if res ~= nil then
+            entry.lag = res[1]
+            entry.clock = fiber.clock()
+        end
+        entry.pending = false
+    end)
+end
+
+--
+-- Find a replica (not a master) of the replicaset with the replication lag
+-- not greater than `lag_max` seconds. The lag is taken from the cache and
+-- grows with the age of the cached value: a stale replica may have stopped
+-- replicating since then. A replica with an unknown lag is skipped.
+--
+-- @return replica object or nil if there is no such replica.
+--
+local function fresh_replica(rs, lag_max)
+    local now = fiber.clock()
+    for _, replica in pairs(rs.replicas) do
+        if replica == rs.master or not replica.conn
+            or not replica.conn:is_connected() then
+            goto continue
+        end
+        refresh_replica_lag(replica)
+        do
+            local entry = replica_lags[replica.uuid]
+            if entry.lag ~= nil and entry.lag + (now - entry.clock) <= lag_max then
+                return replica
+            end
+        end
+        ::continue::
+    end
+    return nil
 end
 
 local function one_step_dispatch(uuid_to_args, opts, tier)
@@ -257,8 +426,10 @@ local function one_step_dispatch(uuid_to_args, opts, tier)
     local replicasets = router:routeall()
     local timeout
     local res_map = {}
+    local total = 0
     for uuid, _ in pairs(uuid_to_args) do
         res_map[uuid] = buffer.ibuf()
+        total = total + 1
     end
     if opts then
         timeout = opts.timeout or SQL_MIN_TIMEOUT
@@ -267,13 +438,17 @@ local function one_step_dispatch(uuid_to_args, opts, tier)
     end
 
     local err, err_uuid, res
+    local failed, succeeded = {}, 0
     local futures = {}
     local opts_ref = { is_async = true }
     local opts_map = { is_async = true, skip_header = true }
     local rid = ref_new()
     local sid = session_current()
     local deadline = fiber.clock() + timeout
-    local read_preference = opts.read_preference;
+    local read_preference = opts and opts.read_preference or "leader"
+    local replica_lag_max = opts and opts.replica_lag_max or 0
+    local concurrency_max = opts and opts.concurrency_max or 0
+    local inflight, done = {}, {}
     -- Nil checks are done explicitly here (== nil instead of 'not'), because
     -- netbox requests return box.NULL instead of nils.
 
@@ -299,8 +474,33 @@ local function one_step_dispatch(uuid_to_args, opts, tier)
     --
     for uuid, rs_args in pairs(uuid_to_args) do
         local rs = replicasets[uuid]
+        throttle(inflight, futures, done, concurrency_max, timeout)
+        timeout = deadline - fiber.clock()
         opts_map['buffer'] = res_map[uuid]
-        if read_preference == "leader" then
+        if read_preference ~= "leader" and replica_lag_max > 0 then
+            -- The lag limit is in milliseconds, while tarantool reports
+            -- the lag in seconds.
+            local replica = fresh_replica(rs, replica_lag_max / 1000)
+            if replica ~= nil then
+                local ok
+                ok, res, err = replica:call(
+                    '.proc_sql_execute',
+                    prepare_args(rs_args, rid, sid, timeout, false),
+                    opts_map
+                )
+                if not ok then
+                    res = nil
+                end
+            else
+                -- All the replicas lag behind too much or their lag is not
+                -- known yet, fall back to the master.
+                res, err = rs:callrw(
+                    '.proc_sql_execute',
+                    prepare_args(rs_args, rid, sid, timeout, true),
+                    opts_map
+                )
+            end
+        elseif read_preference == "leader" then
             res, err = rs:callrw(
                 '.proc_sql_execute',
                 prepare_args(rs_args, rid, sid, timeout, true),
@@ -326,23 +526,26 @@ local function one_step_dispatch(uuid_to_args, opts, tier)
             goto fail
         end
         futures[uuid] = res
+        table.insert(inflight, uuid)
     end
     --
     -- Collect.
     --
     for uuid, f in pairs(futures) do
-        res, err = future_wait(f, timeout)
-        if res == nil then
-            err_uuid = uuid
-            goto fail
-        end
-        if err ~= nil then
-            err_uuid = uuid
-            goto fail
+        res, err = collect(uuid, f, done, timeout)
+        if res == nil or err ~= nil then
+            failed[uuid] = err or false
+        else
+            succeeded = succeeded + 1
         end
         timeout = deadline - fiber.clock()
     end
-    do return res_map end
+    if next(failed) == nil then
+        return res_map
+    end
+    if opts and opts.partial_results and succeeded > 0 then
+        return partial_results(res_map, failed, total, succeeded)
+    end
 
     ::fail::
     for uuid, f in pairs(futures) do
@@ -357,11 +560,10 @@ local function one_step_dispatch(uuid_to_args, opts, tier)
             f:discard()
         end
     end
-    local msg = "Unknown error"
-    if err ~= nil and err.message ~= nil then
-        msg = err.message
+    if next(failed) ~= nil then
+        error(dispatch_error(failed, total, succeeded))
     end
-    error(lerror.make("Error on replicaset " .. err_uuid .. ": " .. msg))
+    error(dispatch_error({ [err_uuid] = err or false }, total))
 end
 
 dispatch.bucket_into_rs = function(bucket_id, tier)
@@ -373,8 +575,59 @@ dispatch.bucket_into_rs = function(bucket_id, tier)
     return rs.uuid
 end
 
-dispatch.custom_plan_dispatch = function(uuid_to_args, timeout, tier, read_preference, do_two_step)
-    local opts = { timeout = timeout, read_preference = read_preference }
+dispatch.replicaset_count = function(tier)
+    local count = 0
+    for _, _ in pairs(get_replicasets_from_tier(tier)) do
+        count = count + 1
+    end
+    return count
+end
+
+--
+-- Read a single tuple by its primary key from the replicaset
+-- owning the bucket.
+--
+-- @param bucket_id bucket of the key;
+-- @param space_id space to read from;
+-- @param schema_version schema version of the table the query was planned for;
+-- @param key primary key of the tuple;
+-- @param timeout timeout in seconds;
+-- @param tier name of the vshard tier to use;
+-- @param read_preference one of "leader", "replica" or "any".
+--
+-- @return the tuple or nil if there is no tuple with such key.
+--
+dispatch.point_get = function(bucket_id, space_id, schema_version, key, timeout, tier,
+                              read_preference)
+    local router = get_router_for_tier(tier)
+    local call = router.callrw
+    if read_preference == 'replica' then
+        call = router.callre
+    elseif read_preference == 'any' then
+        call = router.callbre
+    end
+    local res, err = call(router, bucket_id, '.proc_sql_point_get',
+                          { space_id, schema_version, key }, { timeout = timeout })
+    if err ~= nil then
+        error(err)
+    end
+    if res == nil then
+        -- Also converts box.NULL to nil.
+        return nil
+    end
+    return res
+end
+
+dispatch.custom_plan_dispatch = function(uuid_to_args, timeout, tier, read_preference,
+                                         replica_lag_max, do_two_step, partial_results,
+                                         concurrency_max)
+    local opts = {
+        timeout = timeout,
+        read_preference = read_preference,
+        replica_lag_max = replica_lag_max,
+        partial_results = partial_results,
+        concurrency_max = concurrency_max,
+    }
 
     if do_two_step then
         return two_step_dispatch(uuid_to_args, opts, tier)
@@ -390,11 +643,21 @@ end
 -- on each replicaset;
 -- @param uuids replicasets UUIDs on which to execute plan;
 -- @param timeout timeout in seconds for whole function;
--- @param tier name of the vshard tier to use.
+-- @param tier name of the vshard tier to use;
+-- @param read_preference one of "leader", "replica" or "any";
+-- @param replica_lag_max maximum replication lag (in milliseconds) of
+-- a replica to read from, 0 disables the limit.
+-- @param do_two_step whether to reference the storages before execution;
+-- @param partial_results whether to return the results of the succeeded
+-- replicasets when some of them fail.
+-- @param concurrency_max maximum number of the in-flight requests,
+-- 0 disables the limit.
 --
 -- @return mapping between a replicaset UUID and am ibuf with result.
 --
-dispatch.single_plan_dispatch = function(args, uuids, timeout, tier, read_preference, do_two_step)
+dispatch.single_plan_dispatch = function(args, uuids, timeout, tier, read_preference,
+                                         replica_lag_max, do_two_step, partial_results,
+                                         concurrency_max)
     if not next(uuids) then
         -- An empty list of UUIDs means execution on all replicasets.
         local uuid_to_rs = get_replicasets_from_tier(tier)
@@ -408,7 +671,13 @@ dispatch.single_plan_dispatch = function(args, uuids, timeout, tier, read_prefer
         uuid_to_args[uuid] = args
     end
 
-    local opts = { timeout = timeout, read_preference = read_preference }
+    local opts = {
+        timeout = timeout,
+        read_preference = read_preference,
+        replica_lag_max = replica_lag_max,
+        partial_results = partial_results,
+        concurrency_max = concurrency_max,
+    }
 
     if do_two_step then
         return two_step_dispatch(uuid_to_args, opts, tier)
@@ -471,6 +740,27 @@ dispatch.query_metadata = function(tier, replicaset, instance, req_id, plan_id,
     error(lerror.make("Error on replicaset " .. replicaset .. " (instance: " .. instance .. "): " .. msg))
 end
 
+--
+-- Replication lag (in seconds) of the current instance. It is the maximum
+-- lag among the instance upstreams. An upstream which doesn't follow its
+-- peer is considered to be infinitely lagging.
+--
+dispatch.replication_lag = function()
+    local lag = 0
+    for _, r in pairs(box.info.replication) do
+        local upstream = r.upstream
+        if upstream ~= nil then
+            if upstream.status ~= 'follow' then
+                return math.huge
+            end
+            if upstream.lag > lag then
+                lag = upstream.lag
+            end
+        end
+    end
+    return lag
+end
+
 local function init()
     if rawget(_G, 'pico') == nil then
         error("dispatch must be initialized after pico module was set!")
@@ -1,22 +1,29 @@
 use super::storage::execute_block_locally;
 use crate::catalog::pico_table::PicoTable;
 use crate::config::{DEFAULT_SQL_PREEMPTION, DYNAMIC_CONFIG};
+use crate::metrics;
 use crate::sql::lua::{
-    bucket_into_rs, escape_bytes, lua_custom_plan_dispatch, lua_decode_rs_ibufs,
-    lua_single_plan_dispatch, IbufTable,
+    bucket_into_rs, escape_bytes, lua_custom_plan_dispatch, lua_decode_rs_ibufs, lua_point_get,
+    lua_replicaset_count, lua_single_plan_dispatch, IbufTable,
 };
+use crate::sql::router::DEFAULT_QUERY_TIMEOUT;
 use crate::sql::storage::explain_execute_block;
 use crate::traft::node;
 use ahash::{AHashMap, AHashSet};
 use rmp::decode::{read_array_len, read_bool};
 use rmp::encode::{write_array_len, write_uint};
+use rmp::Marker;
 use smol_str::{format_smolstr, SmolStr, ToSmolStr};
 use sql::errors::{Action, Entity, SbroadError};
 use sql::executor::bucket::Buckets;
+use sql::executor::engine::helpers::trace::{
+    finish_trace, finish_traces, start_trace, start_traces,
+};
 use sql::executor::engine::helpers::vshard::prepare_rs_to_ir_map;
 use sql::executor::engine::helpers::{
-    init_delete_tuple_builder, init_insert_tuple_builder, init_local_update_tuple_builder,
-    init_sharded_update_tuple_builder, try_get_metadata_from_plan, vtable_columns,
+    check_dml_constraints, init_delete_tuple_builder, init_insert_tuple_builder,
+    init_local_update_tuple_builder, init_sharded_update_tuple_builder, try_get_metadata_from_plan,
+    vtable_columns,
 };
 use sql::executor::engine::protocol::{
     build_dql_data_source, DeleteCoreData, ExecutionCacheMissData, ExecutionData,
@@ -32,7 +39,9 @@ use sql::ir::node::relational::Relational;
 use sql::ir::node::{Delete, Insert, Motion, Update};
 use sql::ir::operator::UpdateStrategy;
 use sql::ir::options::ReadPreference;
+use sql::ir::point_get::PointGet;
 use sql::ir::transformation::redistribution::MotionPolicy;
+use sql::ir::value::MsgPackValue;
 use sql::ir::{ExplainType, Plan};
 use sql::utils::ByteCounter;
 use sql_protocol::block::write_block_packet;
@@ -43,14 +52,14 @@ use sql_protocol::dml::update::{write_update_packet, write_update_shared_key_pac
 use sql_protocol::dql::write_dql_packet;
 use sql_protocol::dql_encoder::DQLDataSource;
 use sql_protocol::encode::write_metadata;
-use std::cell::LazyCell;
+use std::cell::{LazyCell, RefCell};
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::io::{Cursor, Error as IoError, Result as IoResult};
 use std::rc::{Rc, Weak};
-use tarantool::fiber::Mutex;
+use tarantool::fiber::{self, FiberId, Mutex};
 use tarantool::tlua::LuaThread;
-use tarantool::tuple::{Tuple, TupleBuilder};
+use tarantool::tuple::{RawBytes, Tuple, TupleBuilder};
 
 pub type SqlResult<T> = Result<T, SbroadError>;
 
@@ -65,6 +74,74 @@ const QUERY_METADATA_CAPACITY: usize = 100;
 
 thread_local! {
     static QUERY_METADATA: Rc<Mutex<MetadataHashMap>> = Rc::new(Mutex::new(HashMap::with_capacity(QUERY_METADATA_CAPACITY)));
+    /// DML messages put aside by [`collect_dml_batch`] instead of being sent,
+    /// by the fiber collecting them. Other fibers dispatch their DML as usual.
+    static DML_BATCH: RefCell<HashMap<FiberId, Vec<BatchedDml>>> = RefCell::new(HashMap::new());
+}
+
+/// A DML message prepared for a replicaset, but not sent to it.
+/// See [`collect_dml_batch`].
+pub struct BatchedDml {
+    /// UUID of the replicaset.
+    pub replicaset: String,
+    pub message: Tuple,
+    /// Keeps the plan of the storage-side part of the statement
+    /// available for the cache misses on the storage.
+    _plan: Option<CacheGuard>,
+}
+
+impl std::fmt::Debug for BatchedDml {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchedDml")
+            .field("replicaset", &self.replicaset)
+            .field("message", &self.message)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Run `f` collecting the DML messages it dispatches instead of sending
+/// them to the storages. Each dispatched statement reports no changed rows.
+///
+/// A statement modifying several replicasets can't be collected and fails.
+pub(crate) fn collect_dml_batch<T>(f: impl FnOnce() -> T) -> (T, Vec<BatchedDml>) {
+    let id = fiber::id();
+    DML_BATCH.with(|batches| batches.borrow_mut().insert(id, Vec::new()));
+    let result = f();
+    let batch = DML_BATCH.with(|batches| batches.borrow_mut().remove(&id));
+    (result, batch.unwrap_or_default())
+}
+
+/// Whether the DML messages of the current fiber are being collected
+/// by [`collect_dml_batch`].
+pub(crate) fn is_collecting_dml_batch() -> bool {
+    let id = fiber::id();
+    DML_BATCH.with(|batches| batches.borrow().contains_key(&id))
+}
+
+/// Put the DML message aside, see [`collect_dml_batch`].
+fn batch_dml<'p>(
+    port: &mut impl Port<'p>,
+    replicasets: &[String],
+    message: Tuple,
+    plan: Option<CacheGuard>,
+) -> SqlResult<()> {
+    let [replicaset] = replicasets else {
+        return Err(SbroadError::Unsupported(
+            Entity::Query,
+            Some("modification of several replicasets in a transaction block".into()),
+        ));
+    };
+    let id = fiber::id();
+    DML_BATCH.with(|batches| {
+        if let Some(batch) = batches.borrow_mut().get_mut(&id) {
+            batch.push(BatchedDml {
+                replicaset: replicaset.clone(),
+                message,
+                _plan: plan,
+            });
+        }
+    });
+    port_write_row_count(port, 0)
 }
 
 pub struct CacheGuard {
@@ -158,7 +235,7 @@ pub(crate) fn single_plan_dispatch<'p>(
     match &query_type {
         QueryType::DQL => {
             port_write_metadata(port, &ex_plan)?;
-            let max_rows = ex_plan.get_sql_motion_row_max();
+            let max_rows = RowMax::new(&ex_plan);
             // For read_preference = 'replica' | 'any', we follow an optimistic scenario.
             // Plan will be routed to RO replica, so references from vshard will not help.
             let is_on_leader =
@@ -203,7 +280,7 @@ pub(crate) fn custom_plan_dispatch<'p>(
             // All custom plans must return the same metadata,
             // so we can use the original plan to write it to the port.
             port_write_metadata(port, &ex_plan)?;
-            let max_rows = ex_plan.get_sql_motion_row_max();
+            let max_rows = RowMax::new(&ex_plan);
             // For read_preference = 'replica' | 'any', we follow an optimistic scenario.
             // Plan will be routed to RO replica, so references from vshard will not help.
             let is_on_leader =
@@ -227,6 +304,60 @@ pub(crate) fn custom_plan_dispatch<'p>(
     Ok(())
 }
 
+/// Serve a single-row primary key read with a point get on the
+/// replicaset owning the key bucket. The storage checks that the
+/// table schema is the one the plan was built for.
+pub(crate) fn point_get_dispatch<'p>(
+    port: &mut impl Port<'p>,
+    runtime: &impl Vshard,
+    ex_plan: &ExecutionPlan,
+    point_get: &PointGet,
+) -> SqlResult<()> {
+    let plan = ex_plan.get_ir_plan();
+    let read_preference = effective_read_preference(ex_plan)?;
+    let space_id = plan.get_relation_or_error(&point_get.table)?.id;
+    let schema_version = *plan.table_version_map.get(&space_id).ok_or_else(|| {
+        SbroadError::NotFound(
+            Entity::SpaceMetadata,
+            format_smolstr!("schema version of table {}", point_get.table),
+        )
+    })?;
+    let bucket_id = runtime.determine_bucket_id(&[&point_get.key])?;
+    let key = Tuple::new(&vec![MsgPackValue::from(&point_get.key)]).map_err(|e| {
+        SbroadError::FailedTo(Action::Create, Some(Entity::Tuple), format_smolstr!("{e}"))
+    })?;
+
+    let lua = tarantool::lua_state();
+    let tuple = lua_point_get(
+        &lua,
+        bucket_id,
+        space_id,
+        schema_version,
+        key,
+        DEFAULT_QUERY_TIMEOUT,
+        plan.tier.as_deref(),
+        read_preference,
+    )
+    .map_err(|e| SbroadError::DispatchError(format_smolstr!("{e}")))?;
+
+    port_write_metadata(port, ex_plan)?;
+    if let Some(tuple) = tuple {
+        let mut mp = Vec::new();
+        write_array_len(&mut mp, point_get.columns.len() as u32)
+            .map_err(|e| SbroadError::DispatchError(format_smolstr!("{e}")))?;
+        for position in &point_get.columns {
+            match tuple.field::<&RawBytes>(*position as u32) {
+                Ok(Some(field)) => mp.extend_from_slice(field),
+                Ok(None) => mp.push(Marker::Null.to_u8()),
+                Err(e) => return Err(SbroadError::DispatchError(format_smolstr!("{e}"))),
+            }
+        }
+        port.add_mp(&mp);
+    }
+    metrics::record_sql_point_get_total();
+    Ok(())
+}
+
 pub(crate) fn block_dispatch<'p>(
     port: &mut impl Port<'p>,
     metadata: Vec<MetadataColumn>,
@@ -237,7 +368,10 @@ pub(crate) fn block_dispatch<'p>(
     tier: Option<&str>,
 ) -> Result<(), SbroadError> {
     if let Some(explain_type) = block.explain_type {
-        if matches!(explain_type, ExplainType::Explain) {
+        if matches!(
+            explain_type,
+            ExplainType::Explain | ExplainType::ExplainCosts
+        ) {
             return Err(SbroadError::NotImplemented(
                 Entity::Explain,
                 "for blocks".to_smolstr(),
@@ -314,12 +448,15 @@ pub(crate) fn block_dispatch<'p>(
                 timeout,
                 tier,
                 String::from("leader"),
+                0,
                 false,
+                false,
+                0,
             )
             .map_err(|e| SbroadError::DispatchError(format_smolstr!("{e}")))?;
 
             // Block cannot have any motions.
-            let motion_max_rows = 0;
+            let motion_max_rows = RowMax::default();
 
             if !metadata.is_empty() {
                 dql_execution_result_process(
@@ -412,16 +549,23 @@ fn single_plan_dispatch_dql<'lua, 'p>(
     lua: &'lua LuaThread,
     ex_plan: ExecutionPlan,
     replicasets: &[String],
-    max_rows: u64,
+    max_rows: RowMax,
     timeout: u64,
     tier: Option<&str>,
     do_two_step: bool,
 ) -> SqlResult<()> {
     let row_len = row_len(&ex_plan)?;
     let read_preference = effective_read_preference(&ex_plan)?;
+    let replica_lag_max = ex_plan.get_ir_plan().effective_options.replica_lag_max as u64;
+    let partial_results = ex_plan.get_ir_plan().effective_options.sql_partial_results;
+    let concurrency_max = ex_plan
+        .get_ir_plan()
+        .effective_options
+        .sql_dispatch_concurrency_max as u64;
     let raft_id = node::global()
         .map_err(|e| SbroadError::DispatchError(e.to_smolstr()))?
         .raft_id;
+    let traces = start_traces(replicasets, &ex_plan)?;
     let data_source = build_dql_data_source(ex_plan, raft_id)
         .map_err(|e| SbroadError::DispatchError(e.to_smolstr()))?;
 
@@ -435,11 +579,20 @@ fn single_plan_dispatch_dql<'lua, 'p>(
     let tuple = tb
         .into_tuple()
         .map_err(|e| SbroadError::DispatchError(e.to_smolstr()))?;
+    finish_traces(traces, tuple.bsize());
     let key = data_source.get_plan_id();
 
     let query_meta_storage = QueryMetaStorage::new();
     let _guard = query_meta_storage.put(key, data_source)?;
 
+    // An empty list means all the replicasets of the tier. Their number
+    // is needed only to tell the partial results from the full ones.
+    let table_len = if replicasets.is_empty() && partial_results {
+        lua_replicaset_count(lua, tier)
+            .map_err(|e| SbroadError::DispatchError(format_smolstr!("{e}")))?
+    } else {
+        replicasets.len()
+    };
     let lua_table = lua_single_plan_dispatch(
         lua,
         &tuple,
@@ -447,11 +600,14 @@ fn single_plan_dispatch_dql<'lua, 'p>(
         timeout,
         tier,
         read_preference,
+        replica_lag_max,
         do_two_step,
+        partial_results,
+        concurrency_max,
     )
     .map_err(|e| SbroadError::DispatchError(format_smolstr!("{e}")))?;
 
-    dql_execution_result_process(port, lua_table, replicasets.len(), row_len, max_rows)?;
+    dql_execution_result_process(port, lua_table, table_len, row_len, max_rows)?;
 
     Ok(())
 }
@@ -480,13 +636,19 @@ fn custom_plan_dispatch_dql<'lua, 'p>(
     lua: &'lua LuaThread,
     ex_plan: ExecutionPlan,
     rs_buckets: Vec<(String, Vec<u64>)>,
-    max_rows: u64,
+    max_rows: RowMax,
     timeout: u64,
     tier: Option<&str>,
     do_two_step: bool,
 ) -> SqlResult<()> {
     let row_len = row_len(&ex_plan)?;
     let read_preference = effective_read_preference(&ex_plan)?;
+    let replica_lag_max = ex_plan.get_ir_plan().effective_options.replica_lag_max as u64;
+    let partial_results = ex_plan.get_ir_plan().effective_options.sql_partial_results;
+    let concurrency_max = ex_plan
+        .get_ir_plan()
+        .effective_options
+        .sql_dispatch_concurrency_max as u64;
     let (rs_plan, extra_plan_id) = prepare_rs_to_ir_map(&rs_buckets, ex_plan)?;
     let plans = rs_plan.len();
     let mut first_args = HashMap::with_capacity(rs_plan.len());
@@ -496,6 +658,7 @@ fn custom_plan_dispatch_dql<'lua, 'p>(
     let mut exec_plan = None;
     let mut extra_exec_plan = None;
     for (rs, ex_plan) in rs_plan {
+        let trace = start_trace(&rs, rs_bucket_ids(&rs_buckets, &rs), &ex_plan)?;
         let temp_exec_plan = build_dql_data_source(ex_plan, raft_id)?;
         let mut bc = ByteCounter::default();
         write_dql_packet(&mut bc, &temp_exec_plan)
@@ -507,6 +670,7 @@ fn custom_plan_dispatch_dql<'lua, 'p>(
         let tuple = tb
             .into_tuple()
             .map_err(|e| SbroadError::DispatchError(e.to_smolstr()))?;
+        finish_trace(trace, tuple.bsize());
         first_args.insert(rs, tuple);
         if Some(temp_exec_plan.get_plan_id()) != extra_plan_id {
             exec_plan = Some(temp_exec_plan);
@@ -536,7 +700,10 @@ fn custom_plan_dispatch_dql<'lua, 'p>(
         timeout,
         tier,
         read_preference,
+        replica_lag_max,
         do_two_step,
+        partial_results,
+        concurrency_max,
     )
     .map_err(|e| SbroadError::DispatchError(format_smolstr!("{e}")))?;
 
@@ -545,6 +712,13 @@ fn custom_plan_dispatch_dql<'lua, 'p>(
     Ok(())
 }
 
+fn rs_bucket_ids<'b>(rs_buckets: &'b [(String, Vec<u64>)], rs: &str) -> &'b [u64] {
+    rs_buckets
+        .iter()
+        .find_map(|(name, buckets)| (name == rs).then_some(buckets.as_slice()))
+        .unwrap_or(&[])
+}
+
 fn row_len(ex_plan: &ExecutionPlan) -> SqlResult<u32> {
     let ir_plan = ex_plan.get_ir_plan();
     let columns_len = ir_plan
@@ -563,7 +737,7 @@ fn dql_execution_result_process<'lua, 'p>(
     table: Rc<IbufTable<'lua>>,
     table_len: usize,
     row_len: u32,
-    max_rows: u64,
+    max_rows: RowMax,
 ) -> SqlResult<()> {
     let mut row_count: u64 = 0;
     let rs_ibufs = lua_decode_rs_ibufs(&table, table_len).map_err(|e| {
@@ -571,6 +745,13 @@ fn dql_execution_result_process<'lua, 'p>(
             "Failed to decode ibufs from DQL first round: {e}"
         ))
     })?;
+    // With `sql_partial_results` the failed replicasets are left out.
+    if rs_ibufs.len() < table_len {
+        port.add_warning(format!(
+            "partial results: {} of {table_len} replicasets failed",
+            table_len - rs_ibufs.len()
+        ));
+    }
 
     // First we should check that we don't have any MISS responses.
     // Otherwise we should forget ALL the data in ibufs and re-dispatch
@@ -629,11 +810,48 @@ fn dql_execution_result_process<'lua, 'p>(
     Ok(())
 }
 
+/// Limits of the number of rows a read dispatch writes to the port.
+/// They are checked while the port is filled, so that a huge result
+/// fails before it is materialized on the router.
+#[derive(Clone, Copy, Debug, Default)]
+struct RowMax {
+    /// `sql_motion_row_max`: the rows are materialized in a virtual table.
+    motion: u64,
+    /// `sql_result_row_max`: the rows are the final result of the query.
+    result: u64,
+}
+
+impl RowMax {
+    fn new(ex_plan: &ExecutionPlan) -> Self {
+        let result = ex_plan.get_ir_plan().effective_options.sql_result_row_max;
+        Self {
+            motion: ex_plan.get_sql_motion_row_max(),
+            result: result.max(0) as u64,
+        }
+    }
+
+    fn check(&self, row_count: u64) -> SqlResult<()> {
+        if self.result > 0 && row_count > self.result {
+            return Err(SbroadError::UnexpectedNumberOfValues(format_smolstr!(
+                "Exceeded maximum number of rows ({}) in query result: {row_count}",
+                self.result
+            )));
+        }
+        if self.motion > 0 && row_count > self.motion {
+            return Err(SbroadError::DispatchError(format_smolstr!(
+                "Exceeded maximum number of rows ({}) in virtual table: {row_count}",
+                self.motion
+            )));
+        }
+        Ok(())
+    }
+}
+
 #[inline(always)]
 fn port_write_tuples<'tuples, 'p>(
     port: &mut impl Port<'p>,
     tuples: TupleIter<'tuples>,
-    max_rows: u64,
+    max_rows: RowMax,
     row_count: &mut u64,
     row_len: u32,
     rs: &str,
@@ -645,11 +863,7 @@ fn port_write_tuples<'tuples, 'p>(
             ))
         })?;
         *row_count += 1;
-        if max_rows > 0 && *row_count > max_rows {
-            return Err(SbroadError::DispatchError(format_smolstr!(
-                "Exceeded maximum number of rows ({max_rows}) in virtual table: {row_count}"
-            )));
-        }
+        max_rows.check(*row_count)?;
 
         port_append_mp(port, mp, row_len).map_err(|e| {
             SbroadError::DispatchError(format_smolstr!(
@@ -727,6 +941,31 @@ fn replicasets_from_buckets(
     Ok(replicasets)
 }
 
+/// Count replicasets owning the given buckets. All the buckets are owned by
+/// the replicasets of the tier with a non-zero weight.
+pub(crate) fn storage_count(buckets: &Buckets, tier: Option<&str>) -> SqlResult<u64> {
+    match buckets {
+        Buckets::Any => Ok(1),
+        Buckets::All => {
+            let node = node::global().map_err(|e| {
+                SbroadError::FailedTo(Action::Get, None, format_smolstr!("raft node: {e}"))
+            })?;
+            let topology_ref = node.topology_cache.get();
+            let tier = tier.unwrap_or_else(|| node.topology_cache.my_tier_name());
+            let count = topology_ref
+                .all_replicasets()
+                .filter(|rs| rs.tier == tier && rs.weight > 0.0)
+                .count();
+            Ok(count as u64)
+        }
+        Buckets::Filtered(_) => {
+            let lua = tarantool::lua_state();
+            let replicasets = replicasets_from_buckets(&lua, buckets, tier)?;
+            Ok(replicasets.len() as u64)
+        }
+    }
+}
+
 fn buckets_by_replicasets(
     lua: &LuaThread,
     buckets: &Buckets,
@@ -882,6 +1121,7 @@ fn build_dml_message(ex_plan: ExecutionPlan) -> SqlResult<(Tuple, Option<Executi
 
             if !with_dql {
                 let table = ex_plan.get_motion_vtable(*child)?;
+                check_dml_constraints(plan, top_id, &table)?;
                 let pattern = init_insert_tuple_builder(plan, table.get_columns(), top_id)?;
                 let data = TupleInsertData::new(core, table, pattern);
                 let tuple = encode_with_reservation!(&data, write_insert_packet);
@@ -920,6 +1160,7 @@ fn build_dml_message(ex_plan: ExecutionPlan) -> SqlResult<(Tuple, Option<Executi
                     let delete_tuple_len =
                         delete_tuple_len.expect("ShardedUpdate must have delete_tuple_len");
                     let table = ex_plan.get_motion_vtable(*child)?;
+                    check_dml_constraints(plan, top_id, &table)?;
                     let pattern =
                         init_sharded_update_tuple_builder(plan, table.get_columns(), top_id)?;
                     let data = SharedUpdateData::new(core, delete_tuple_len, table, pattern);
@@ -957,15 +1198,24 @@ fn single_plan_dispatch_dml<'lua, 'p>(
 ) -> SqlResult<()> {
     // This option is available only for DQL.
     let read_preference = ReadPreference::default().to_string();
+    let concurrency_max = ex_plan
+        .get_ir_plan()
+        .effective_options
+        .sql_dispatch_concurrency_max as u64;
+    let traces = start_traces(replicasets, &ex_plan)?;
     let (message, new_plan) = build_dml_message(ex_plan)?;
+    finish_traces(traces, message.bsize());
 
-    let _guard = if let Some(new_plan) = new_plan {
+    let guard = if let Some(new_plan) = new_plan {
         let key = new_plan.get_plan_id();
         let query_meta_storage = QueryMetaStorage::new();
         Some(query_meta_storage.put(key, new_plan)?)
     } else {
         None
     };
+    if is_collecting_dml_batch() {
+        return batch_dml(port, replicasets, message, guard);
+    }
 
     let lua_table = lua_single_plan_dispatch(
         lua,
@@ -974,7 +1224,10 @@ fn single_plan_dispatch_dml<'lua, 'p>(
         timeout,
         tier,
         read_preference,
+        0,
+        false,
         false,
+        concurrency_max,
     )
     .map_err(|e| SbroadError::DispatchError(format_smolstr!("{e}")))?;
     // TODO: all buckets will allocate nothing, because it is empty
@@ -991,24 +1244,47 @@ fn custom_plan_dispatch_dml<'lua, 'p>(
     tier: Option<&str>,
 ) -> SqlResult<()> {
     let read_preference = ReadPreference::default().to_string();
+    let concurrency_max = ex_plan
+        .get_ir_plan()
+        .effective_options
+        .sql_dispatch_concurrency_max as u64;
     let (rs_plan, _) = prepare_rs_to_ir_map(&rs_buckets, ex_plan)?;
     let mut dql_encoder = None;
     let mut args = HashMap::with_capacity(rs_plan.len());
     for (rs, ex_plan) in rs_plan {
+        let trace = start_trace(&rs, rs_bucket_ids(&rs_buckets, &rs), &ex_plan)?;
         let (message, new_plan) = build_dml_message(ex_plan)?;
+        finish_trace(trace, message.bsize());
         dql_encoder = new_plan;
         args.insert(rs, message);
     }
-    let _guard = if let Some(new_plan) = dql_encoder {
+    let guard = if let Some(new_plan) = dql_encoder {
         let key = new_plan.get_plan_id();
         let query_meta_storage = QueryMetaStorage::new();
         Some(query_meta_storage.put(key, new_plan)?)
     } else {
         None
     };
+    if is_collecting_dml_batch() {
+        let (replicasets, mut messages): (Vec<_>, Vec<_>) = args.into_iter().unzip();
+        let Some(message) = messages.pop() else {
+            return port_write_row_count(port, 0);
+        };
+        return batch_dml(port, &replicasets, message, guard);
+    }
     let len = args.len();
-    let lua_table = lua_custom_plan_dispatch(lua, args, timeout, tier, read_preference, false)
-        .map_err(|e| SbroadError::DispatchError(format_smolstr!("{e}")))?;
+    let lua_table = lua_custom_plan_dispatch(
+        lua,
+        args,
+        timeout,
+        tier,
+        read_preference,
+        0,
+        false,
+        false,
+        concurrency_max,
+    )
+    .map_err(|e| SbroadError::DispatchError(format_smolstr!("{e}")))?;
     dml_process(port, lua_table, len)?;
     Ok(())
 }
@@ -1053,6 +1329,10 @@ fn dml_process<'lua, 'p>(
             }
         }
     }
+    port_write_row_count(port, row_count)
+}
+
+fn port_write_row_count<'p>(port: &mut impl Port<'p>, row_count: u64) -> SqlResult<()> {
     let mut mp = [0_u8; 9];
     let pos = {
         let mut cur = Cursor::new(&mut mp[..]);
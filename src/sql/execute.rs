@@ -2,19 +2,24 @@ use crate::metrics::{
     report_storage_cache_hit, report_storage_cache_miss, STORAGE_2ND_REQUESTS_TOTAL,
 };
 use crate::preemption::scheduler_options;
+use crate::schema::{IndexDef, IndexOption};
 use crate::sql::lock::{lock_temp_table, TempTableLockRef};
 use crate::sql::lua::{lua_decode_ibufs, lua_query_metadata};
 use crate::sql::port::PicoPortOwned;
-use crate::sql::router::{get_index_version_by_pk, get_table_version_by_id, VersionMap};
+use crate::sql::router::{
+    get_index_version_by_pk, get_table_version_by_id, table_checks, table_columns, VersionMap,
+};
 use crate::sql::storage::{ExpandedPlanInfo, FullDeleteInfo, PlanInfo};
 use crate::sql::PicoPortC;
+use crate::storage::{cached_key_def, index_by_ids_unchecked};
+use crate::tarantool::box_schema_version;
 use crate::tlog;
 use crate::traft::node;
 use ahash::HashMapExt;
 use comfy_table::{Cell, ContentArrangement, Row, Table};
 use rmp::decode::read_array_len;
 use rmp::encode::{write_array_len, write_uint};
-use smol_str::{format_smolstr, ToSmolStr};
+use smol_str::{format_smolstr, SmolStr, ToSmolStr};
 use sql::backend::sql::ADMIN_ID;
 use sql::errors::{Action, Entity, SbroadError};
 use sql::executor::engine::helpers::{build_insert_args, TupleBuilderCommand, TupleBuilderPattern};
@@ -29,8 +34,12 @@ use sql::executor::vtable::{
 };
 use sql::executor::{Port, PortType};
 use sql::ir::helpers::RepeatableState;
-use sql::ir::options::Options;
-use sql::ir::relation::SpaceEngine;
+use sql::ir::options::{
+    Options, DEFAULT_REPLICA_LAG_MAX, DEFAULT_SQL_DISPATCH_CONCURRENCY_MAX,
+    DEFAULT_SQL_MAX_BUCKETS_PER_QUERY, DEFAULT_SQL_PARTIAL_RESULTS, DEFAULT_SQL_RECURSION_MAX,
+    DEFAULT_SQL_RESULT_ROW_MAX, DEFAULT_WAIT_INDEX, DEFAULT_WAIT_INDEX_TIMEOUT,
+};
+use sql::ir::relation::{CheckConstraint, SpaceEngine};
 use sql::ir::relation::{Column, ColumnRole};
 use sql::ir::value::{EncodedValue, MsgPackValue, Value};
 use sql::ir::ExplainType;
@@ -51,17 +60,20 @@ use sql_protocol::dql_encoder::MsgpackEncode;
 use sql_protocol::dql_encoder::{ColumnType, DQLOptions};
 use sql_protocol::error::ProtocolError;
 use sql_protocol::iterators::{MsgpackArrayIterator, MsgpackMapIterator, TupleIterator};
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::io::{Cursor, Write};
+use std::rc::Rc;
 use std::sync::OnceLock;
 use tarantool::error::{Error, TarantoolErrorCode};
 use tarantool::ffi::sql::Port as TarantoolPort;
-use tarantool::index::{FieldType, IndexOptions, IndexType, Part};
+use tarantool::index::{FieldType, Index, IndexOptions, IndexType, IteratorType, Part};
 use tarantool::msgpack;
 use tarantool::session::with_su;
-use tarantool::space::{Field, Space, SpaceCreateOptions, SpaceType};
-use tarantool::transaction::transaction;
-use tarantool::tuple::RawBytes;
+use tarantool::space::{Field, Space, SpaceCreateOptions, SpaceId, SpaceType};
+use tarantool::transaction::{is_in_transaction, transaction};
+use tarantool::tuple::{KeyDef, RawBytes, Tuple};
 
 const LINE_WIDTH: usize = 80;
 
@@ -751,6 +763,7 @@ fn table_create(
                 ColumnType::Integer => Field::integer(*name),
                 ColumnType::String => Field::string(*name),
                 ColumnType::Uuid => Field::uuid(*name),
+                ColumnType::Bytea => Field::varbinary(*name),
                 ColumnType::Any => Field::any(*name),
                 ColumnType::Array => Field::array(*name),
                 ColumnType::Scalar => Field::scalar(*name),
@@ -938,6 +951,208 @@ fn delete_args<'t>(
     Ok(delete_tuple)
 }
 
+/// Per-table data derived from the picodata schema. It's built on the first
+/// DML of the table and dropped when the tarantool schema version changes,
+/// as every DDL changing the tables and the indexes bumps it.
+struct SchemaCache<T> {
+    schema_version: u64,
+    tables: HashMap<SpaceId, Rc<T>>,
+}
+
+impl<T> SchemaCache<T> {
+    fn new() -> Self {
+        Self {
+            schema_version: 0,
+            tables: HashMap::new(),
+        }
+    }
+
+    fn get_or_try_insert(
+        &mut self,
+        table_id: SpaceId,
+        f: impl FnOnce() -> Result<T, SbroadError>,
+    ) -> Result<Rc<T>, SbroadError> {
+        let schema_version = box_schema_version();
+        if self.schema_version != schema_version {
+            self.tables.clear();
+            self.schema_version = schema_version;
+        }
+        if let Some(value) = self.tables.get(&table_id) {
+            return Ok(Rc::clone(value));
+        }
+        let value = Rc::new(f()?);
+        self.tables.insert(table_id, Rc::clone(&value));
+        Ok(value)
+    }
+}
+
+thread_local! {
+    static NULLS_NOT_DISTINCT_INDEXES: RefCell<SchemaCache<NullsNotDistinctIndexes>> =
+        RefCell::new(SchemaCache::new());
+    static TABLE_CHECKS: RefCell<SchemaCache<TableChecks>> = RefCell::new(SchemaCache::new());
+}
+
+/// Unique indexes of a table created with `NULLS NOT DISTINCT`.
+///
+/// Tarantool considers NULLs distinct in unique indexes, so duplicate keys
+/// containing NULLs are looked up manually before a tuple is written.
+struct NullsNotDistinctIndexes {
+    pk: Option<Rc<KeyDef>>,
+    indexes: Vec<(IndexDef, Index, Rc<KeyDef>)>,
+}
+
+impl NullsNotDistinctIndexes {
+    fn cached(table_id: SpaceId) -> Result<Rc<Self>, SbroadError> {
+        NULLS_NOT_DISTINCT_INDEXES.with(|cache| {
+            cache
+                .borrow_mut()
+                .get_or_try_insert(table_id, || Self::new(table_id))
+        })
+    }
+
+    fn new(table_id: SpaceId) -> Result<Self, SbroadError> {
+        let node = node::global().expect("should be init");
+        let defs = with_su(ADMIN_ID, || node.storage.indexes.by_space_id(table_id))??;
+        let mut indexes = Vec::new();
+        let mut table_def = None;
+        for def in defs {
+            if !def.opts.contains(&IndexOption::NullsDistinct(false)) {
+                continue;
+            }
+            let table_def = match &mut table_def {
+                Some(table_def) => table_def,
+                None => {
+                    let found = with_su(ADMIN_ID, || node.storage.pico_table.get(table_id))??;
+                    let found = found.ok_or_else(|| {
+                        SbroadError::NotFound(Entity::Table, format_smolstr!("{table_id}"))
+                    })?;
+                    table_def.insert(found)
+                }
+            };
+            let index = index_by_ids_unchecked(table_id, def.id);
+            // Tarantool keeps collation ids in the index parts, which the index
+            // metadata decoder doesn't understand, so the key definition is
+            // built from the picodata one with the collation names.
+            let key_def = Rc::new(def.to_index_metadata(table_def).to_key_def());
+            indexes.push((def, index, key_def));
+        }
+        let pk = if indexes.is_empty() {
+            None
+        } else {
+            Some(cached_key_def(table_id, 0)?)
+        };
+        Ok(Self { pk, indexes })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.indexes.is_empty()
+    }
+
+    /// Returns an error if another tuple has the same key with NULLs
+    /// in any of the indexes.
+    fn check(&self, tuple: &Tuple) -> Result<(), SbroadError> {
+        let Some(pk_def) = &self.pk else {
+            return Ok(());
+        };
+        for (def, index, key_def) in &self.indexes {
+            let key = key_def.extract_key(tuple)?;
+            let parts: Vec<rmpv::Value> = rmp_serde::from_slice(key.as_ref()).map_err(|e| {
+                SbroadError::FailedTo(Action::Decode, Some(Entity::Tuple), format_smolstr!("{e}"))
+            })?;
+            // Keys without NULLs are checked by tarantool itself.
+            if !parts.iter().any(rmpv::Value::is_nil) {
+                continue;
+            }
+            let pk = pk_def.extract_key(tuple)?;
+            for other in index.select(IteratorType::Eq, &key)? {
+                if pk_def.compare_with_key(&other, &pk) != Ordering::Equal {
+                    return Err(SbroadError::Other(format_smolstr!(
+                        "Duplicate key exists in unique index \"{}\" with NULLS NOT DISTINCT",
+                        def.name,
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run the modification in a transaction. A transaction already opened by
+/// the caller, e.g. for a batch of modifications, is joined instead, so the
+/// caller decides whether to commit it.
+fn dml_transaction(f: impl FnOnce() -> Result<(), SbroadError>) -> Result<(), SbroadError> {
+    if is_in_transaction() {
+        return f();
+    }
+    transaction(f)?;
+    Ok(())
+}
+
+/// CHECK constraints of a table. The router validates the rows it sends
+/// to the storages itself, while the rows produced by the local DML
+/// (`INSERT .. SELECT` and `UPDATE` of the non-sharding columns) are
+/// validated here, before the transaction is committed.
+struct TableChecks {
+    table: SmolStr,
+    checks: Vec<CheckConstraint>,
+}
+
+impl TableChecks {
+    fn cached(table_id: SpaceId) -> Result<Rc<Self>, SbroadError> {
+        TABLE_CHECKS.with(|cache| {
+            cache
+                .borrow_mut()
+                .get_or_try_insert(table_id, || Self::new(table_id))
+        })
+    }
+
+    fn new(table_id: SpaceId) -> Result<Self, SbroadError> {
+        let node = node::global().expect("should be init");
+        let table_def = with_su(ADMIN_ID, || node.storage.pico_table.get(table_id))??;
+        let table_def = table_def
+            .ok_or_else(|| SbroadError::NotFound(Entity::Table, format_smolstr!("{table_id}")))?;
+        if table_def.checks().next().is_none() {
+            return Ok(Self {
+                table: table_def.name,
+                checks: Vec::new(),
+            });
+        }
+        let columns = table_columns(&table_def.format, table_def.distribution.bucket_id_column())?;
+        let checks = table_checks(&table_def, &columns)?;
+        Ok(Self {
+            table: table_def.name,
+            checks,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.checks.is_empty()
+    }
+
+    /// Returns an error if the tuple violates any of the constraints.
+    fn check(&self, tuple: &Tuple) -> Result<(), SbroadError> {
+        for check in &self.checks {
+            let value = match tuple.field::<&RawBytes>(check.column as u32)? {
+                Some(raw) => msgpack::decode::<Value>(raw).map_err(|e| {
+                    SbroadError::FailedTo(
+                        Action::Decode,
+                        Some(Entity::Tuple),
+                        format_smolstr!("{e}"),
+                    )
+                })?,
+                None => Value::Null,
+            };
+            if !check.is_satisfied_by(&value) {
+                return Err(SbroadError::CheckViolation(
+                    self.table.clone(),
+                    check.name.clone(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 fn insert_execute<'p, 'ip, R: Vshard + QueryCache>(
     runtime: &R,
     mut iter: InsertIterator<'ip>,
@@ -960,9 +1175,19 @@ where
 
     let conflict_strategy = protocol_get!(iter, InsertResult::ConflictPolicy);
     let tuples = protocol_get!(iter, InsertResult::Tuples);
-    transaction(|| -> Result<(), SbroadError> {
+    let nulls_check = NullsNotDistinctIndexes::cached(space_id)?;
+    dml_transaction(|| -> Result<(), SbroadError> {
         for tuple in tuples {
             let insert_tuple = RawBytes::new(tuple?);
+            if !nulls_check.is_empty() {
+                if let Err(e) = nulls_check.check(&Tuple::new(insert_tuple)?) {
+                    if !matches!(conflict_strategy, ConflictPolicy::DoNothing) {
+                        return Err(e);
+                    }
+                    tlog!(Debug, "{e}. Skipping according to conflict strategy");
+                    continue;
+                }
+            }
             // TODO: should we care of default and so on
             let insert_result = space.insert(insert_tuple);
             if let Err(Error::Tarantool(tnt_err)) = &insert_result {
@@ -1036,8 +1261,9 @@ where
     let space = unsafe { Space::from_id_unchecked(table_id) };
 
     let _ = protocol_get!(iter, UpdateSharedKeyResult::UpdateType);
+    let nulls_check = NullsNotDistinctIndexes::cached(table_id)?;
 
-    transaction(|| -> Result<(), SbroadError> {
+    dml_transaction(|| -> Result<(), SbroadError> {
         let del_tuples = protocol_get!(iter, UpdateSharedKeyResult::DelTuples);
         for tuple in del_tuples {
             let tuple = RawBytes::new(tuple?);
@@ -1054,13 +1280,14 @@ where
 
         for tuple in tuples {
             let tuple = RawBytes::new(tuple?);
-            space.replace(tuple).map_err(|e| {
+            let new_tuple = space.replace(tuple).map_err(|e| {
                 SbroadError::FailedTo(
                     Action::Insert,
                     Some(Entity::Tuple),
                     format_smolstr!("{e:?}"),
                 )
             })?;
+            nulls_check.check(&new_tuple)?;
             result.row_count += 1;
         }
 
@@ -1103,8 +1330,10 @@ where
     let dql = protocol_get!(iter, InsertMaterializedResult::DqlInfo);
 
     let vtable = virtual_table_materialize(runtime, request_id, columns, &builder, dql, timeout)?;
+    let nulls_check = NullsNotDistinctIndexes::cached(table_id)?;
+    let checks = TableChecks::cached(table_id)?;
 
-    transaction(|| -> Result<(), SbroadError> {
+    dml_transaction(|| -> Result<(), SbroadError> {
         for (bucket_id, positions) in vtable.get_bucket_index() {
             for pos in positions {
                 let vt_tuple = vtable.get_tuples().get(*pos).ok_or_else(|| {
@@ -1116,6 +1345,18 @@ where
                     )
                 })?;
                 let insert_tuple = build_insert_args(vt_tuple, &builder, Some(bucket_id))?;
+                if !checks.is_empty() {
+                    checks.check(&Tuple::new(&insert_tuple)?)?;
+                }
+                if !nulls_check.is_empty() {
+                    if let Err(e) = nulls_check.check(&Tuple::new(&insert_tuple)?) {
+                        if !matches!(conflict_strategy, ConflictPolicy::DoNothing) {
+                            return Err(e);
+                        }
+                        tlog!(Debug, "{e}. Skipping according to conflict strategy");
+                        continue;
+                    }
+                }
                 let insert_result = space.insert(&insert_tuple);
                 if let Err(Error::Tarantool(tnt_err)) = &insert_result {
                     if tnt_err.error_code() == TarantoolErrorCode::TupleFound as u32 {
@@ -1203,14 +1444,26 @@ where
     let dql = protocol_get!(iter, UpdateResult::DqlInfo);
 
     let vtable = virtual_table_materialize(runtime, request_id, columns, &builder, dql, timeout)?;
+    let nulls_check = NullsNotDistinctIndexes::cached(table_id)?;
+    let checks = TableChecks::cached(table_id)?;
 
-    transaction(|| -> Result<(), SbroadError> {
+    dml_transaction(|| -> Result<(), SbroadError> {
+        // `UPDATE .. FROM` may join a target row with several source rows,
+        // the first match wins.
+        let mut updated_keys = HashSet::with_capacity(vtable.get_tuples().len());
         for vt_tuple in vtable.get_tuples() {
             let args = update_args(vt_tuple, &builder)?;
+            if !updated_keys.insert(rmp_serde::to_vec(&args.key_tuple)?) {
+                continue;
+            }
             let update_res = space.update(&args.key_tuple, &args.ops);
-            update_res.map_err(|e| {
+            let new_tuple = update_res.map_err(|e| {
                 SbroadError::FailedTo(Action::Update, Some(Entity::Space), format_smolstr!("{e}"))
             })?;
+            if let Some(new_tuple) = new_tuple {
+                checks.check(&new_tuple)?;
+                nulls_check.check(&new_tuple)?;
+            }
             result.row_count += 1;
         }
 
@@ -1253,17 +1506,26 @@ where
 
     let vtable = virtual_table_materialize(runtime, request_id, columns, &builder, dql, timeout)?;
 
-    transaction(|| -> Result<(), SbroadError> {
+    dml_transaction(|| -> Result<(), SbroadError> {
         for vt_tuple in vtable.get_tuples() {
             let delete_tuple = delete_args(vt_tuple, &builder)?;
-            if let Err(Error::Tarantool(tnt_err)) = space.delete(&delete_tuple) {
-                return Err(SbroadError::FailedTo(
-                    Action::Delete,
-                    Some(Entity::Tuple),
-                    format_smolstr!("{tnt_err:?}"),
-                ));
+            match space.delete(&delete_tuple) {
+                // The key may repeat in the virtual table (e.g. `DELETE ... USING`
+                // matched the row several times), so count only removed tuples.
+                Ok(None) => {}
+                Ok(Some(_)) => result.row_count += 1,
+                Err(e) => {
+                    let msg = match e {
+                        Error::Tarantool(tnt_err) => format_smolstr!("{tnt_err:?}"),
+                        e => format_smolstr!("{e:?}"),
+                    };
+                    return Err(SbroadError::FailedTo(
+                        Action::Delete,
+                        Some(Entity::Tuple),
+                        msg,
+                    ));
+                }
             }
-            result.row_count += 1;
         }
         Ok(())
     })?;
@@ -1304,6 +1566,14 @@ where
             sql_motion_row_max: options.sql_motion_row_max as i64,
             sql_vdbe_opcode_max: options.sql_vdbe_opcode_max as i64,
             read_preference: Default::default(),
+            sql_recursion_max: DEFAULT_SQL_RECURSION_MAX as i64,
+            replica_lag_max: DEFAULT_REPLICA_LAG_MAX as i64,
+            sql_max_buckets_per_query: DEFAULT_SQL_MAX_BUCKETS_PER_QUERY as i64,
+            sql_partial_results: DEFAULT_SQL_PARTIAL_RESULTS,
+            wait_index: DEFAULT_WAIT_INDEX as i64,
+            wait_index_timeout: DEFAULT_WAIT_INDEX_TIMEOUT as i64,
+            sql_result_row_max: DEFAULT_SQL_RESULT_ROW_MAX as i64,
+            sql_dispatch_concurrency_max: DEFAULT_SQL_DISPATCH_CONCURRENCY_MAX as i64,
         },
         table_name.as_str(),
     );
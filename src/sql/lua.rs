@@ -9,6 +9,7 @@ use ::tarantool::tlua::{
     CDataOnStack, LuaError, LuaFunction, LuaRead, LuaState, LuaTable, LuaThread, PushGuard,
     PushInto, StringInLua, Void,
 };
+use ::tarantool::tuple::Tuple;
 use rmp::decode::{read_array_len, read_int, read_marker, RmpRead};
 use rmp::Marker;
 use sql_protocol::iterators::ExplainIter;
@@ -576,7 +577,10 @@ pub(crate) fn lua_single_plan_dispatch<'lua, T>(
     timeout: u64,
     tier: Option<&str>,
     read_preference: String,
+    replica_lag_max: u64,
     do_two_step: bool,
+    partial_results: bool,
+    concurrency_max: u64,
 ) -> Result<Rc<IbufTable<'lua>>>
 where
     T: PushInto<LuaState>,
@@ -593,7 +597,10 @@ where
         timeout,
         tier,
         read_preference,
+        replica_lag_max,
         do_two_step,
+        partial_results,
+        concurrency_max,
     ));
     match call_res {
         Ok(v) => Ok(Rc::new(v)),
@@ -611,7 +618,10 @@ pub(crate) fn lua_custom_plan_dispatch<'lua, T>(
     timeout: u64,
     tier: Option<&str>,
     read_preference: String,
+    replica_lag_max: u64,
     do_two_step: bool,
+    partial_results: bool,
+    concurrency_max: u64,
 ) -> Result<Rc<IbufTable<'lua>>>
 where
     T: PushInto<LuaState>,
@@ -627,7 +637,10 @@ where
         timeout,
         tier,
         read_preference,
+        replica_lag_max,
         do_two_step,
+        partial_results,
+        concurrency_max,
     ));
     match call_res {
         Ok(v) => Ok(Rc::new(v)),
@@ -639,6 +652,20 @@ where
     }
 }
 
+pub(crate) fn lua_replicaset_count(lua: &LuaThread, tier: Option<&str>) -> Result<usize> {
+    let name = "replicaset_count";
+    let func = dispatch_get_func(lua, name)?;
+
+    match func.call_with_args(tier) {
+        Ok(count) => Ok(count),
+        Err(e) => Err(TarantoolError::new(
+            TarantoolErrorCode::ProcLua,
+            format!("{}", LuaError::from(e)),
+        )
+        .into()),
+    }
+}
+
 pub(crate) fn bucket_into_rs(
     lua: &LuaThread,
     bucket_id: u64,
@@ -657,6 +684,37 @@ pub(crate) fn bucket_into_rs(
     }
 }
 
+pub(crate) fn lua_point_get(
+    lua: &LuaThread,
+    bucket_id: u64,
+    space_id: u32,
+    schema_version: u64,
+    key: Tuple,
+    timeout: u64,
+    tier: Option<&str>,
+    read_preference: String,
+) -> Result<Option<Tuple>> {
+    let name = "point_get";
+    let func = dispatch_get_func(lua, name)?;
+
+    match func.call_with_args((
+        bucket_id,
+        space_id,
+        schema_version,
+        key,
+        timeout,
+        tier,
+        read_preference,
+    )) {
+        Ok(tuple) => Ok(tuple),
+        Err(e) => Err(TarantoolError::new(
+            TarantoolErrorCode::ProcLua,
+            format!("{}", LuaError::from(e)),
+        )
+        .into()),
+    }
+}
+
 pub(crate) fn reference_add(rid: i64, sid: &str, timeout: f64) -> Result<()> {
     let lua = tarantool::lua_state();
     let func = storage_get_func(&lua, "add")?;
@@ -145,6 +145,8 @@ fn pcall_write_ok(wr: &mut impl Write) -> IoResult<()> {
 
 pub struct PicoPortOwned {
     port: Port,
+    /// Warnings about the result to send to the client.
+    warnings: Vec<String>,
 }
 
 impl Default for PicoPortOwned {
@@ -157,9 +159,15 @@ impl PicoPortOwned {
     pub fn new() -> Self {
         Self {
             port: Port::new_port_c(),
+            warnings: Vec::new(),
         }
     }
 
+    /// Take the warnings attached to the result.
+    pub fn take_warnings(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.warnings)
+    }
+
     #[inline]
     pub fn port_c(&self) -> &PortC {
         unsafe { &*(self.port.as_ptr() as *const PortC) }
@@ -217,6 +225,10 @@ impl SqlPort<'_> for PicoPortOwned {
     fn size(&self) -> u32 {
         self.port_c().size() as u32
     }
+
+    fn add_warning(&mut self, message: String) {
+        self.warnings.push(message);
+    }
 }
 
 impl Write for PicoPortOwned {
@@ -4,7 +4,7 @@
 
 use smol_str::{format_smolstr, SmolStr, ToSmolStr};
 use sql::errors::{Action, Entity, SbroadError};
-use sql::executor::bucket::Buckets;
+use sql::executor::bucket::{Buckets, FanOut};
 use sql::executor::engine::helpers::vshard::get_random_bucket;
 use sql::executor::engine::helpers::{
     dispatch_impl, empty_plan_write, explain_format, materialize_motion, materialize_values,
@@ -19,26 +19,33 @@ use sql::frontend::sql::ast::AbstractSyntaxTree;
 use sql::ir::helpers::RepeatableState;
 use sql::ir::node::NodeId;
 use sql::ir::value::{MsgPackValue, Value};
-use sql::ir::Plan;
+use sql::ir::{Plan, DEFAULT_MAX_EXPRESSION_DEPTH};
 use sql::utils::MutexLike;
+use tarantool::datetime::Datetime;
 use tarantool::fiber::Mutex;
 use tarantool::session::with_su;
+use tarantool::time::Instant;
+use time::UtcOffset;
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::time::Duration;
 
 use crate::audit;
 use crate::catalog::pico_bucket::DEFAULT_BUCKET_ID_COLUMN_NAME;
 use crate::preemption::scheduler_options;
-use crate::schema::{Distribution, ShardingFn, ADMIN_ID};
+use crate::schema::{Distribution, ShardingFn, TableDef, ADMIN_ID};
 use crate::storage::{self, Catalog};
 
-use sql::executor::engine::helpers::normalize_name_from_sql;
+use sql::executor::engine::helpers::{normalize_name_from_sql, IdentifierCase};
 use sql::executor::engine::Metadata;
-use sql::executor::Port;
+use sql::executor::{ExecutingQuery, Port};
 use sql::ir::function::Function;
-use sql::ir::relation::{space_pk_columns, Column, ColumnRole, Table};
+use sql::ir::operator::Bool;
+use sql::ir::relation::{space_pk_columns, CheckConstraint, Column, ColumnRole, Table};
 use sql::ir::types::{DerivedType, UnrestrictedType};
+use sql::BoundStatement;
 
 use crate::sql::storage::StorageRuntime;
 use crate::traft::node;
@@ -48,11 +55,12 @@ use crate::metrics::{
     ROUTER_CACHE_STATEMENTS_EVICTED_TOTAL,
 };
 use ::tarantool::tuple::{KeyDef, Tuple};
-use tarantool::space::SpaceId;
+use tarantool::space::{Space, SpaceId};
 
 use super::dispatch::{custom_plan_dispatch, single_plan_dispatch};
 use super::port::PicoPortOwned;
-use crate::sql::dispatch::block_dispatch;
+use super::sequence;
+use crate::sql::dispatch::{block_dispatch, storage_count};
 use sql::executor::result::MetadataColumn;
 
 pub type VersionMap = HashMap<u32, u64, RepeatableState>;
@@ -245,7 +253,11 @@ impl RouterRuntime {
     /// - If the cache cannot be initialized.
     #[expect(clippy::new_without_default)]
     pub fn new() -> Self {
-        let metadata = RouterMetadata::default();
+        let metadata = RouterMetadata::default()
+            .with_identifier_case(crate::config::DYNAMIC_CONFIG.current_identifier_case())
+            .with_max_expression_depth(
+                crate::config::DYNAMIC_CONFIG.current_sql_expression_depth_max(),
+            );
         let runtime = PLAN_CACHE.with(|cache| RouterRuntime {
             metadata: Mutex::new(metadata),
             ir_cache: cache.clone(),
@@ -253,6 +265,26 @@ impl RouterRuntime {
         });
         runtime
     }
+
+    /// Estimate how many buckets and storages the statement would be
+    /// dispatched to, without executing it.
+    pub fn estimate_fanout(&self, statement: BoundStatement) -> Result<FanOut, SbroadError> {
+        ExecutingQuery::from_bound_statement(self, statement).estimate_fanout()
+    }
+
+    /// Evict cached plans that reference the given table, so the next
+    /// query re-reads its metadata. Called when the table schema changes.
+    pub fn invalidate_schema(&self, space_name: &str) -> Result<(), SbroadError> {
+        self.ir_cache.lock().invalidate_table(space_name)?;
+        Ok(())
+    }
+}
+
+/// Evict all plans from the plan cache of the current thread.
+/// Called when a setting affecting query parsing changes.
+pub fn invalidate_plans() -> Result<(), SbroadError> {
+    PLAN_CACHE.with(|cache| cache.lock().clear())?;
+    Ok(())
 }
 
 pub type PlanCache = LRUCache<SmolStr, Rc<Plan>>;
@@ -278,6 +310,21 @@ impl PicoRouterCache {
     pub fn capacity(&self) -> usize {
         self.inner.capacity()
     }
+
+    /// Evict plans referencing the given table. A plan references all the
+    /// tables read by the query, including the ones in its subqueries and
+    /// CTEs, so such plans are evicted as well.
+    /// Returns the number of evicted plans.
+    pub fn invalidate_table(&mut self, table_name: &str) -> Result<usize, SbroadError> {
+        self.inner
+            .retain(|_, plan| plan.relations.get(table_name).is_none())
+    }
+
+    /// Evict all plans.
+    /// Returns the number of evicted plans.
+    pub fn clear(&mut self) -> Result<usize, SbroadError> {
+        self.inner.retain(|_, _| false)
+    }
 }
 
 impl Cache<SmolStr, Rc<Plan>> for PicoRouterCache {
@@ -462,6 +509,10 @@ impl Router for RouterRuntime {
         }
     }
 
+    fn serves_point_gets(&self) -> bool {
+        true
+    }
+
     fn materialize_values(
         &self,
         exec_plan: &mut ExecutionPlan,
@@ -470,6 +521,26 @@ impl Router for RouterRuntime {
         materialize_values(self, exec_plan, values_id)
     }
 
+    fn next_identity_values(&self, table: &Table, count: usize) -> Result<Vec<i64>, SbroadError> {
+        let timeout = Duration::from_secs(DEFAULT_QUERY_TIMEOUT);
+        let deadline = Instant::now_fiber().saturating_add(timeout);
+        let values = sequence::next_values(table.id, count, deadline).map_err(|e| {
+            SbroadError::FailedTo(Action::Insert, Some(Entity::Column), e.to_smolstr())
+        })?;
+        values
+            .into_iter()
+            .map(|value| {
+                i64::try_from(value).map_err(|_| {
+                    SbroadError::FailedTo(
+                        Action::Insert,
+                        Some(Entity::Column),
+                        format_smolstr!("identity sequence of table {} is exhausted", table.name),
+                    )
+                })
+            })
+            .collect()
+    }
+
     fn is_audit_enabled(&self, plan: &Plan) -> Result<bool, SbroadError> {
         (self.is_audit_enabled_func)(plan)
     }
@@ -496,9 +567,20 @@ pub(crate) fn calculate_bucket_id(tuple: &[&Value], bucket_count: u64) -> Result
             "cannot calculate bucket id: tier has bucket_count=0 (no sharded data)".to_smolstr(),
         ));
     }
-    let wrapped_tuple = tuple
+    // Datetimes of the same instant are equal regardless of their time zone
+    // offset, so they are hashed in UTC to get into the same bucket.
+    let utc_tuple = tuple
+        .iter()
+        .map(|v| match v {
+            Value::Datetime(dt) => Cow::Owned(Value::Datetime(Datetime::from_inner(
+                dt.into_inner().to_offset(UtcOffset::UTC),
+            ))),
+            _ => Cow::Borrowed(*v),
+        })
+        .collect::<Vec<_>>();
+    let wrapped_tuple = utc_tuple
         .iter()
-        .map(|v| MsgPackValue::from(*v))
+        .map(|v| MsgPackValue::from(v.as_ref()))
         .collect::<Vec<_>>();
     let tnt_tuple = Tuple::new(&wrapped_tuple).map_err(|e| {
         SbroadError::FailedTo(
@@ -554,6 +636,10 @@ impl Vshard for Tier {
         get_random_bucket(self)
     }
 
+    fn storage_count(&self, buckets: &Buckets) -> Result<u64, SbroadError> {
+        storage_count(buckets, self.name().as_deref())
+    }
+
     fn determine_bucket_id(&self, s: &[&Value]) -> Result<u64, SbroadError> {
         calculate_bucket_id(s, self.bucket_count())
     }
@@ -598,6 +684,10 @@ impl Vshard for &Tier {
         get_random_bucket(self)
     }
 
+    fn storage_count(&self, buckets: &Buckets) -> Result<u64, SbroadError> {
+        storage_count(buckets, self.name().as_deref())
+    }
+
     fn determine_bucket_id(&self, s: &[&Value]) -> Result<u64, SbroadError> {
         calculate_bucket_id(s, self.bucket_count())
     }
@@ -665,6 +755,12 @@ pub struct RouterMetadata {
 
     /// IR functions
     pub functions: HashMap<SmolStr, Function>,
+
+    /// Policy of normalizing unquoted identifiers.
+    pub identifier_case: IdentifierCase,
+
+    /// Maximum nesting depth of the expressions in the query.
+    pub max_expression_depth: usize,
 }
 
 impl Default for RouterMetadata {
@@ -689,8 +785,99 @@ impl RouterMetadata {
             cache_capacity: DEFAULT_CAPACITY,
             sharding_column: DEFAULT_BUCKET_ID_COLUMN_NAME.to_string(),
             functions,
+            identifier_case: IdentifierCase::default(),
+            max_expression_depth: DEFAULT_MAX_EXPRESSION_DEPTH,
         }
     }
+
+    /// Set the policy of normalizing unquoted identifiers.
+    #[must_use]
+    pub fn with_identifier_case(mut self, case: IdentifierCase) -> Self {
+        self.identifier_case = case;
+        self
+    }
+
+    /// Set the maximum nesting depth of the expressions in the query.
+    #[must_use]
+    pub fn with_max_expression_depth(mut self, depth: usize) -> Self {
+        self.max_expression_depth = depth;
+        self
+    }
+}
+
+/// Build IR columns from the space format. The column storing the bucket id
+/// (if any) gets the sharding role.
+pub(crate) fn table_columns(
+    format: &[tarantool::space::Field],
+    bucket_id_column: Option<&str>,
+) -> Result<Vec<Column>, SbroadError> {
+    let mut columns: Vec<Column> = Vec::with_capacity(format.len());
+    for column_meta in format {
+        let col_name = &column_meta.name;
+        let is_nullable = column_meta.is_nullable;
+        let col_type = UnrestrictedType::new(column_meta.field_type.as_str())?;
+        let role = if bucket_id_column == Some(col_name.as_str()) {
+            ColumnRole::Sharding
+        } else {
+            ColumnRole::User
+        };
+        let column = Column {
+            name: col_name.to_smolstr(),
+            r#type: DerivedType::new(col_type),
+            role,
+            is_nullable,
+        };
+        columns.push(column);
+    }
+    Ok(columns)
+}
+
+/// Build CHECK constraints of the table from its options. The constants are
+/// stored as text and are cast to the types of the checked columns.
+pub(crate) fn table_checks(
+    table: &TableDef,
+    columns: &[Column],
+) -> Result<Vec<CheckConstraint>, SbroadError> {
+    let mut checks = Vec::new();
+    for (name, column, op, value) in table.checks() {
+        let pos = columns
+            .iter()
+            .position(|c| c.name.as_str() == column)
+            .ok_or_else(|| {
+                SbroadError::NotFound(
+                    Entity::Column,
+                    format_smolstr!("{column} of CHECK constraint {name}"),
+                )
+            })?;
+        let value = match columns[pos].r#type.get() {
+            Some(ty) => Value::from(value).cast(*ty)?,
+            None => Value::from(value),
+        };
+        checks.push(CheckConstraint::new(name, pos, Bool::from(op)?, value)?);
+    }
+    Ok(checks)
+}
+
+/// Declared column defaults of the table with the constants cast to the column types.
+fn table_defaults(
+    table: &TableDef,
+    columns: &[Column],
+) -> Result<Vec<(usize, Value)>, SbroadError> {
+    let mut defaults = Vec::new();
+    for (column, value) in table.defaults() {
+        let pos = columns
+            .iter()
+            .position(|c| c.name.as_str() == column)
+            .ok_or_else(|| {
+                SbroadError::NotFound(Entity::Column, format_smolstr!("{column} with default"))
+            })?;
+        let value = match columns[pos].r#type.get() {
+            Some(ty) => Value::from(value).cast(*ty)?,
+            None => Value::from(value),
+        };
+        defaults.push((pos, value));
+    }
+    Ok(defaults)
 }
 
 impl Metadata for RouterMetadata {
@@ -707,25 +894,15 @@ impl Metadata for RouterMetadata {
             .ok_or_else(|| SbroadError::NotFound(Entity::Space, name.to_smolstr()))?;
 
         let engine = table.engine;
-        let is_sharded = matches!(table.distribution, Distribution::ShardedImplicitly { .. });
-        let mut columns: Vec<Column> = Vec::with_capacity(table.format.len());
-        for column_meta in &table.format {
-            let col_name = &column_meta.name;
-            let is_nullable = column_meta.is_nullable;
-            let col_type = UnrestrictedType::new(column_meta.field_type.as_str())?;
-            let role = if is_sharded && col_name == DEFAULT_BUCKET_ID_COLUMN_NAME {
-                ColumnRole::Sharding
-            } else {
-                ColumnRole::User
-            };
-            let column = Column {
-                name: col_name.to_smolstr(),
-                r#type: DerivedType::new(col_type),
-                role,
-                is_nullable,
-            };
-            columns.push(column);
-        }
+        let columns = table_columns(&table.format, table.distribution.bucket_id_column())?;
+        let identity = table.identity_column().and_then(|identity| {
+            columns
+                .iter()
+                .position(|column| column.name.as_str() == identity)
+        });
+
+        let checks = table_checks(&table, &columns)?;
+        let defaults = table_defaults(&table, &columns)?;
 
         let pk_cols = space_pk_columns(&name, &columns)?;
         let pk_cols_str: &[&str] = &pk_cols.iter().map(SmolStr::as_str).collect::<Vec<_>>();
@@ -738,7 +915,7 @@ impl Metadata for RouterMetadata {
         // Try to find the sharding columns of the space in "_pico_table".
         // If nothing found then the space is local and we can't query it with
         // distributed SQL.
-        match table.distribution {
+        let mut sql_table = match table.distribution {
             Distribution::Global => Table::new_global(table.id, &name, columns, pk_cols_str),
             Distribution::ShardedImplicitly {
                 sharding_key,
@@ -777,7 +954,11 @@ impl Metadata for RouterMetadata {
                 Entity::Distribution,
                 format_smolstr!("explicitly by field '{field}'"),
             )),
-        }
+        }?;
+        sql_table.identity = identity;
+        sql_table.checks = checks;
+        sql_table.defaults = defaults;
+        Ok(sql_table)
     }
 
     fn get_index_id(&self, index_name: &str, table_name: &str) -> Result<u32, SbroadError> {
@@ -785,6 +966,8 @@ impl Metadata for RouterMetadata {
     }
 
     fn function(&self, fn_name: &str) -> Result<&Function, SbroadError> {
+        // Functions are registered lowercase, so unquoted names are
+        // folded regardless of the identifier case policy.
         let name = normalize_name_from_sql(fn_name);
         match self.functions.get(&name) {
             Some(v) => Ok(v),
@@ -811,6 +994,39 @@ impl Metadata for RouterMetadata {
         let table = self.table(space)?;
         Ok(table.get_sk()?.to_vec())
     }
+
+    fn identifier_case(&self) -> IdentifierCase {
+        self.identifier_case
+    }
+
+    fn max_expression_depth(&self) -> usize {
+        self.max_expression_depth
+    }
+
+    fn table_row_count(&self, table_name: &str) -> Option<u64> {
+        let storage = Catalog::try_get(false).ok()?;
+        let table = storage.pico_table.by_name(table_name).ok()??;
+        // SAFETY: the space exists as the table is present in "_pico_table".
+        let space = unsafe { Space::from_id_unchecked(table.id) };
+        let local_count = space.len().ok()? as u64;
+        let Some(tier_name) = table.distribution.in_tier() else {
+            // Global tables are fully replicated to every instance.
+            return Some(local_count);
+        };
+
+        // Rows of a sharded table are spread over the buckets of its tier,
+        // so extrapolate the local row count by the share of buckets stored
+        // on the current instance.
+        if tier_name != get_current_tier_name().ok()? {
+            return None;
+        }
+        let local_buckets = Space::find("_bucket")?.len().ok()? as u64;
+        if local_buckets == 0 {
+            return None;
+        }
+        let tier = get_tier_info(tier_name).ok()?;
+        Some(local_count.saturating_mul(tier.bucket_count) / local_buckets)
+    }
 }
 
 fn bucket_dispatch<'p>(
@@ -836,3 +1052,48 @@ fn bucket_dispatch<'p>(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tarantool::space::Field;
+
+    #[test]
+    fn table_columns_custom_bucket_id_column() {
+        let format = [
+            Field::integer("id"),
+            Field::unsigned("bucket_id"),
+            Field::unsigned("my_bucket"),
+        ];
+        let distribution = Distribution::ShardedByField {
+            field: "my_bucket".into(),
+            tier: "default".into(),
+        };
+
+        let columns = table_columns(&format, distribution.bucket_id_column()).unwrap();
+        let roles: Vec<_> = columns.iter().map(|c| (c.name.as_str(), &c.role)).collect();
+        assert_eq!(
+            roles,
+            vec![
+                ("id", &ColumnRole::User),
+                ("bucket_id", &ColumnRole::User),
+                ("my_bucket", &ColumnRole::Sharding),
+            ]
+        );
+    }
+
+    #[test]
+    fn table_columns_default_bucket_id_column() {
+        let format = [Field::integer("id"), Field::unsigned("bucket_id")];
+        let distribution = Distribution::ShardedImplicitly {
+            sharding_key: vec!["id".into()],
+            sharding_fn: ShardingFn::Murmur3,
+            tier: "default".into(),
+        };
+        let columns = table_columns(&format, distribution.bucket_id_column()).unwrap();
+        assert_eq!(columns[1].role, ColumnRole::Sharding);
+
+        let columns = table_columns(&format, Distribution::Global.bucket_id_column()).unwrap();
+        assert!(columns.iter().all(|c| c.role == ColumnRole::User));
+    }
+}
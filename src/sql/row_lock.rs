@@ -0,0 +1,166 @@
+//! Row locks taken by `SELECT ... FOR UPDATE/FOR SHARE` in the transaction
+//! blocks of the PostgreSQL protocol.
+//!
+//! The locks are kept in memory on the master of the replicaset storing
+//! the rows until the transaction block ends. They only conflict with each
+//! other: reads and modifications don't wait for them. The locks of a router
+//! which has been restarted or has gone offline are treated as released.
+
+use crate::has_states;
+use crate::instance::InstanceName;
+use crate::traft::node;
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tarantool::fiber::{self, Cond};
+use tarantool::space::SpaceId;
+use tarantool::time::Instant;
+
+/// Transaction block holding row locks.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LockOwner {
+    /// Router instance serving the client.
+    pub instance: InstanceName,
+    /// Incarnation of the router instance when the locks were taken.
+    pub incarnation: u64,
+    /// PostgreSQL protocol client of the router.
+    pub client_id: u64,
+}
+
+impl LockOwner {
+    /// The router is restarted or offline, so the transaction is lost.
+    fn is_gone(&self) -> bool {
+        let Ok(node) = node::global() else {
+            return false;
+        };
+        let topology = node.topology_cache.get();
+        let Ok(instance) = topology.instance_by_name(&self.instance) else {
+            return true;
+        };
+        instance.current_state.incarnation > self.incarnation || !has_states!(instance, Online -> *)
+    }
+}
+
+/// Space id and the primary key encoded as msgpack array.
+type RowKey = (SpaceId, ByteBuf);
+
+struct RowLock {
+    exclusive: bool,
+    owners: Vec<LockOwner>,
+}
+
+#[derive(Default)]
+struct RowLocks {
+    rows: HashMap<RowKey, RowLock>,
+    by_owner: HashMap<LockOwner, HashSet<RowKey>>,
+}
+
+impl RowLocks {
+    /// Release the locks of the other owners of the row which are gone,
+    /// see [`LockOwner::is_gone`].
+    fn release_gone_owners(&mut self, owner: &LockOwner, key: &RowKey) -> bool {
+        let Some(lock) = self.rows.get(key) else {
+            return false;
+        };
+        let gone: Vec<_> = lock
+            .owners
+            .iter()
+            .filter(|other| *other != owner && other.is_gone())
+            .cloned()
+            .collect();
+        for other in &gone {
+            self.release(other);
+        }
+        !gone.is_empty()
+    }
+
+    fn try_lock(&mut self, owner: &LockOwner, key: &RowKey, exclusive: bool) -> bool {
+        let lock = self.rows.entry(key.clone()).or_insert_with(|| RowLock {
+            exclusive,
+            owners: vec![],
+        });
+        let shared_with_others = lock.owners.iter().any(|other| other != owner);
+        if shared_with_others && (exclusive || lock.exclusive) {
+            return false;
+        }
+        lock.exclusive |= exclusive;
+        if !lock.owners.contains(owner) {
+            lock.owners.push(owner.clone());
+            self.by_owner
+                .entry(owner.clone())
+                .or_default()
+                .insert(key.clone());
+        }
+        true
+    }
+
+    fn release(&mut self, owner: &LockOwner) -> bool {
+        let Some(keys) = self.by_owner.remove(owner) else {
+            return false;
+        };
+        for key in keys {
+            let Some(lock) = self.rows.get_mut(&key) else {
+                continue;
+            };
+            lock.owners.retain(|other| other != owner);
+            if lock.owners.is_empty() {
+                self.rows.remove(&key);
+            }
+        }
+        true
+    }
+}
+
+thread_local! {
+    static ROW_LOCKS: RefCell<RowLocks> = RefCell::new(RowLocks::default());
+    /// Broadcast when some row locks are released.
+    static ROW_LOCKS_RELEASED: Cond = Cond::new();
+}
+
+/// How often a waiting transaction checks whether the lock holder is gone.
+const GONE_OWNER_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Lock the rows of the space with the given primary keys, waiting for the
+/// conflicting locks of the other transactions until the deadline. Returns
+/// `false` if a row remained locked, the rows locked by then stay locked
+/// until [`unlock_rows`].
+pub fn lock_rows(
+    owner: &LockOwner,
+    space_id: SpaceId,
+    keys: Vec<ByteBuf>,
+    exclusive: bool,
+    deadline: Instant,
+) -> bool {
+    for key in keys {
+        let key = (space_id, key);
+        loop {
+            let locked = ROW_LOCKS.with(|locks| {
+                let mut locks = locks.borrow_mut();
+                if locks.release_gone_owners(owner, &key) {
+                    ROW_LOCKS_RELEASED.with(|cond| cond.broadcast());
+                }
+                locks.try_lock(owner, &key, exclusive)
+            });
+            if locked {
+                break;
+            }
+            let now = Instant::now_fiber();
+            if now >= deadline || fiber::is_cancelled() {
+                return false;
+            }
+            let wakeup = now.saturating_add(GONE_OWNER_CHECK_INTERVAL).min(deadline);
+            ROW_LOCKS_RELEASED.with(|cond| cond.wait_deadline(wakeup));
+        }
+    }
+    true
+}
+
+/// Release all the row locks of the transaction.
+pub fn unlock_rows(owner: &LockOwner) {
+    let released = ROW_LOCKS.with(|locks| locks.borrow_mut().release(owner));
+    if released {
+        ROW_LOCKS_RELEASED.with(|cond| cond.broadcast());
+    }
+}
@@ -0,0 +1,111 @@
+//! Cluster sequences backing the identity columns.
+//!
+//! Every instance reserves the sequence values in batches by advancing the
+//! `allocated` counter of the table in `_pico_sequence` with a CaS request,
+//! and then hands the reserved values out locally. So the instances never
+//! produce the same value, and consensus is only needed once per batch.
+//! The values produced by a single instance grow monotonically, but the
+//! values of different instances interleave and the unused remainders of
+//! the batches are lost on restart.
+
+use crate::cas::{self, Predicate};
+use crate::catalog::pico_sequence::{PicoSequence, SequenceRecord};
+use crate::schema::ADMIN_ID;
+use crate::storage::SystemTable;
+use crate::tlog;
+use crate::traft::error::Error;
+use crate::traft::op::{Dml, Op};
+use crate::traft::{self, node};
+use ::tarantool::fiber;
+use ::tarantool::session::with_su;
+use ::tarantool::space::SpaceId;
+use ::tarantool::time::Instant;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Number of the sequence values reserved by an instance at once.
+const BATCH_SIZE: u64 = 1000;
+
+thread_local! {
+    /// Reserved but not yet used values of the sequences by table id.
+    static RESERVED: RefCell<HashMap<SpaceId, Range<u64>>> = RefCell::new(HashMap::new());
+}
+
+/// Take `count` next values of the table sequence, reserving a new batch
+/// in the cluster when the local one is exhausted.
+pub fn next_values(table_id: SpaceId, count: usize, deadline: Instant) -> traft::Result<Vec<u64>> {
+    let mut values = Vec::with_capacity(count);
+    take_reserved(table_id, count, &mut values);
+    while values.len() < count {
+        let needed = (count - values.len()) as u64;
+        let mut batch = reserve(table_id, needed.max(BATCH_SIZE), deadline)?;
+        let taken = batch.start..batch.start + needed;
+        batch.start = taken.end;
+        values.extend(taken);
+
+        RESERVED.with(|reserved| {
+            let mut reserved = reserved.borrow_mut();
+            let range = reserved.entry(table_id).or_default();
+            // Another fiber might have reserved a batch while we were
+            // waiting for the CaS. Keep the higher one to stay monotonic,
+            // the rest of the lower batch is lost.
+            if range.start < batch.start {
+                *range = batch;
+            }
+        });
+    }
+    Ok(values)
+}
+
+fn take_reserved(table_id: SpaceId, count: usize, values: &mut Vec<u64>) {
+    RESERVED.with(|reserved| {
+        let mut reserved = reserved.borrow_mut();
+        let Some(range) = reserved.get_mut(&table_id) else {
+            return;
+        };
+        let taken = range.start..range.end.min(range.start + count as u64);
+        range.start = taken.end;
+        values.extend(taken);
+    });
+}
+
+/// Reserve `count` values of the table sequence in the cluster.
+fn reserve(table_id: SpaceId, count: u64, deadline: Instant) -> traft::Result<Range<u64>> {
+    let node = node::global()?;
+    loop {
+        if fiber::clock() > deadline {
+            return Err(Error::timeout());
+        }
+
+        // The value must be read at the same index as the predicate is built,
+        // so no yields are allowed in between.
+        let ranges = [cas::Range::new(PicoSequence::TABLE_ID).eq([table_id])];
+        let predicate = Predicate::with_applied_index(ranges);
+        let allocated = node
+            .storage
+            .pico_sequence
+            .get(table_id)?
+            .map_or(0, |record| record.allocated);
+
+        let record = SequenceRecord {
+            table_id,
+            allocated: allocated + count,
+        };
+        // CAS must be done under admin, as we access system spaces there.
+        let res = with_su(ADMIN_ID, || -> traft::Result<_> {
+            let op = Op::Dml(Dml::replace(PicoSequence::TABLE_ID, &record, ADMIN_ID)?);
+            let req = cas::Request::new(op, predicate, ADMIN_ID)?;
+            cas::compare_and_swap_and_wait(&req, deadline)
+        })??;
+        if let Some(e) = res.into_retriable_error() {
+            tlog!(
+                Debug,
+                "sequence of table {table_id} reservation rejected: {e}"
+            );
+            continue;
+        }
+
+        return Ok(allocated + 1..allocated + count + 1);
+    }
+}
@@ -2,7 +2,7 @@
 //! Implements the `sbroad` crate infrastructure
 //! for execution of the dispatched query plan subtrees.
 
-use crate::sql::dispatch::port_write_metadata;
+use crate::sql::dispatch::{port_write_metadata, storage_count};
 use crate::sql::execute::explain_execute_guarded;
 use crate::sql::execute::{dml_execute, dql_execute, explain_execute};
 use crate::sql::router::{
@@ -30,7 +30,8 @@ use crate::metrics::{
     report_storage_cache_hit, report_storage_cache_miss, STORAGE_CACHE_STATEMENTS_ADDED_TOTAL,
     STORAGE_CACHE_STATEMENTS_EVICTED_TOTAL,
 };
-use smol_str::{format_smolstr, SmolStr};
+use serde_bytes::ByteBuf;
+use smol_str::{format_smolstr, SmolStr, ToSmolStr};
 use sql::executor::vdbe::SqlStmt;
 use sql::executor::vtable::{VirtualTable, VirtualTableTupleEncoder};
 use sql::ir::node::BlockStatement;
@@ -60,6 +61,7 @@ use sql::executor::result::MetadataColumn;
 use tarantool::fiber::Mutex;
 use tarantool::msgpack;
 use tarantool::session::with_su;
+use tarantool::transaction;
 
 thread_local!(
     // OnceCell is used for interior mutability
@@ -601,6 +603,10 @@ impl Vshard for StorageRuntime {
         get_random_bucket(self)
     }
 
+    fn storage_count(&self, buckets: &Buckets) -> Result<u64, SbroadError> {
+        storage_count(buckets, None)
+    }
+
     fn determine_bucket_id(&self, s: &[&Value]) -> Result<u64, SbroadError> {
         calculate_bucket_id(s, self.bucket_count())
     }
@@ -899,6 +905,50 @@ pub fn execute_block_locally<'p>(
     Ok(())
 }
 
+/// Execute DML messages one after another in a single transaction and
+/// return the number of rows changed by each of them. With `dry_run` the
+/// transaction is rolled back instead of being committed.
+///
+/// A yield aborts the transaction, so the batch fails as a whole if one
+/// of the messages can't be executed without yielding.
+pub fn execute_dml_batch_locally(
+    messages: &[ByteBuf],
+    dry_run: bool,
+    timeout: f64,
+) -> Result<Vec<u64>, SbroadError> {
+    let runtime = StorageRuntime::new();
+    let mut row_counts = Vec::with_capacity(messages.len());
+    transaction::begin().map_err(|e| SbroadError::Other(e.to_smolstr()))?;
+    let result = (|| -> Result<(), SbroadError> {
+        for message in messages {
+            let package = ProtocolMessage::decode_from_bytes(message).map_err(|e| {
+                SbroadError::Other(format_smolstr!("failed to decode DML message: {e}"))
+            })?;
+            if !matches!(
+                package.msg_type,
+                ProtocolMessageType::Dml(_) | ProtocolMessageType::LocalDml(_)
+            ) {
+                return Err(SbroadError::Invalid(
+                    Entity::Plan,
+                    Some("Expected a DML plan.".into()),
+                ));
+            }
+            let mut port = PicoPortOwned::new();
+            runtime.execute_plan(package, &mut port, timeout)?;
+            row_counts.push(parse_row_count_from_port(&port));
+        }
+        Ok(())
+    })();
+    let end = if result.is_ok() && !dry_run {
+        transaction::commit()
+    } else {
+        transaction::rollback()
+    };
+    end.map_err(|e| SbroadError::Other(e.to_smolstr()))?;
+    result?;
+    Ok(row_counts)
+}
+
 /// Extract row_count written by vdbe into the port.
 fn parse_row_count_from_port(port: &PicoPortOwned) -> u64 {
     let mp = port.iter().next().expect("port must not be emtpy");
@@ -2,6 +2,7 @@
 use crate::catalog::governor_queue::GovernorQueue;
 use crate::catalog::pico_bucket::PicoBucket;
 use crate::catalog::pico_resharding_state::PicoReshardingState;
+use crate::catalog::pico_sequence::PicoSequence;
 use crate::catalog::pico_table::PicoTable;
 use crate::catalog::user_audit_policy::PicoUserAuditPolicy;
 use crate::config::observer::AtomicObserver;
@@ -155,6 +156,7 @@ pub fn storage_is_initialized() -> bool {
 /// 532 - _bucket
 /// 533 - _pico_bucket
 /// 534 - _pico_resharding_state
+/// 535 - _pico_sequence
 ////////////////////////////////////////////////////////////////////////////////
 pub const SYSTEM_TABLES_ID_RANGE: RangeInclusive<u32> = 512..=SPACE_ID_INTERNAL_MAX;
 
@@ -198,6 +200,7 @@ pub struct Catalog {
     pub users_audit_policies: PicoUserAuditPolicy,
     pub pico_bucket: PicoBucket,
     pub pico_resharding_state: PicoReshardingState,
+    pub pico_sequence: PicoSequence,
 }
 
 /// Id of system table `_bucket`. Note that we don't add in to `Clusterwide`
@@ -241,6 +244,7 @@ impl Catalog {
             users_audit_policies: PicoUserAuditPolicy::new(),
             pico_bucket: PicoBucket::new(),
             pico_resharding_state: PicoReshardingState::new(),
+            pico_sequence: PicoSequence::new(),
             snapshot_cache: Default::default(),
             login_attempts: Default::default(),
         }
@@ -272,6 +276,7 @@ impl Catalog {
             users_audit_policies,
             pico_bucket,
             pico_resharding_state,
+            pico_sequence,
             snapshot_cache,
             login_attempts,
         } = self;
@@ -300,6 +305,7 @@ impl Catalog {
         users_audit_policies.create()?;
         pico_bucket.create()?;
         pico_resharding_state.create()?;
+        pico_sequence.create()?;
 
         Ok(())
     }
@@ -326,6 +332,7 @@ impl Catalog {
             PicoUserAuditPolicy::TABLE_ID => Some(PicoUserAuditPolicy::TABLE_NAME),
             PicoBucket::TABLE_ID => Some(PicoBucket::TABLE_NAME),
             PicoReshardingState::TABLE_ID => Some(PicoReshardingState::TABLE_NAME),
+            PicoSequence::TABLE_ID => Some(PicoSequence::TABLE_NAME),
             _ => None,
         }
     }
@@ -3347,6 +3354,15 @@ impl DbConfig {
             sql_motion_row_max,
             sql_vdbe_opcode_max,
             read_preference,
+            sql_recursion_max: sql::ir::options::DEFAULT_SQL_RECURSION_MAX as i64,
+            replica_lag_max: sql::ir::options::DEFAULT_REPLICA_LAG_MAX as i64,
+            sql_max_buckets_per_query: sql::ir::options::DEFAULT_SQL_MAX_BUCKETS_PER_QUERY as i64,
+            sql_partial_results: sql::ir::options::DEFAULT_SQL_PARTIAL_RESULTS,
+            wait_index: sql::ir::options::DEFAULT_WAIT_INDEX as i64,
+            wait_index_timeout: sql::ir::options::DEFAULT_WAIT_INDEX_TIMEOUT as i64,
+            sql_result_row_max: sql::ir::options::DEFAULT_SQL_RESULT_ROW_MAX as i64,
+            sql_dispatch_concurrency_max: sql::ir::options::DEFAULT_SQL_DISPATCH_CONCURRENCY_MAX
+                as i64,
         }
     }
 
@@ -15,7 +15,7 @@ use crate::traft::op::Ddl;
 use crate::{column_name, tlog, traft};
 use serde::Serialize;
 use smol_str::SmolStr;
-use sql::frontend::sql::FUNCTION_NAME_MAPPINGS;
+use sql::frontend::sql::sql_procedure_parameter_list;
 use std::collections::HashMap;
 use std::ffi::CString;
 use std::fs;
@@ -70,6 +70,10 @@ pub fn ddl_meta_drop_space(storage: &Catalog, space_id: SpaceId) -> traft::Resul
     for index in iter {
         storage.indexes.delete(index.table_id, index.id)?;
     }
+    let table_def = storage.pico_table.get(space_id)?;
+    if table_def.is_some_and(|def| def.identity_column().is_some()) {
+        storage.pico_sequence.delete(space_id)?;
+    }
     storage.pico_table.delete(space_id)?;
     Ok(())
 }
@@ -176,9 +180,19 @@ pub fn ddl_abort_on_master(storage: &Catalog, ddl: &Ddl, version: u64) -> traft:
         Ddl::ChangeFormat {
             table_id,
             ref old_format,
+            ref new_format,
             // we don't need to care about column renames here because tarantool operates on column indices under the hood, yay
             ..
         } => {
+            if new_format.len() < old_format.len() {
+                // Columns are only dropped on commit, so there's nothing to abort.
+                //
+                // When dropping columns we update local schema version when
+                // applying the DdlCommit raft op unlike other DDL operations.
+                // This means that during DdlAbort local schema version should already be up to date
+                debug_assert_eq!(version, local_schema_version()?);
+                return Ok(());
+            }
             ddl_change_format_on_master(table_id, old_format)?;
         }
     }
@@ -869,6 +883,34 @@ pub fn ddl_change_format_on_master(
     Ok(())
 }
 
+/// Remove the fields of dropped trailing columns from every tuple of the space,
+/// so that the data doesn't reappear if a column is added at the same position.
+///
+/// Return values:
+/// * `Ok(())` in case of success.
+/// * `Err(e)` in case of error which should result in a ddl abort.
+pub fn ddl_drop_trailing_fields_on_master(
+    space_id: SpaceId,
+    field_count: usize,
+) -> Result<(), TntError> {
+    debug_assert!(unsafe { tarantool::ffi::tarantool::box_txn() });
+
+    let space = space_by_id_unchecked(space_id);
+    let mut rewritten = Vec::new();
+    for tuple in space.select(IteratorType::All, &())? {
+        if tuple.len() as usize <= field_count {
+            continue;
+        }
+        let mut fields: Vec<rmpv::Value> = tuple.decode()?;
+        fields.truncate(field_count);
+        rewritten.push(fields);
+    }
+    for fields in &rewritten {
+        space.replace(fields)?;
+    }
+    Ok(())
+}
+
 pub fn ddl_create_tt_proc_on_master(new_proc_name: &str) -> traft::Result<()> {
     let lua = ::tarantool::lua_state();
 
@@ -881,16 +923,11 @@ pub fn ddl_create_tt_proc_on_master(new_proc_name: &str) -> traft::Result<()> {
         )));
     };
 
-    let proc_mapping = FUNCTION_NAME_MAPPINGS
-        .iter()
-        .find(|mapping| mapping.rust_procedure == proc.name());
-
-    let proc_params = proc_mapping
-        .map(|mapping| mapping.parameter_list)
-        .unwrap_or_default();
+    let proc_opt = sql_procedure_parameter_list(proc.name());
+    let proc_params = proc_opt.unwrap_or_default();
 
     let mut proc_exports = vec!["LUA"];
-    if proc_mapping.is_some() {
+    if proc_opt.is_some() {
         proc_exports.push("SQL");
     }
 
@@ -369,6 +369,33 @@ impl IntoBoxError for Error {
             Self::NotALeader { leader_id } => {
                 let mut e = BoxError::new(self.error_code(), self.to_string());
                 e.set_field("leader_id", leader_id.into());
+                // Clients which don't know the raft id to address mapping
+                // (e.g. a joining instance) need the leader address to redirect.
+                let leader_address = crate::traft::node::global().ok().and_then(|node| {
+                    let connection_type = crate::traft::ConnectionType::Iproto;
+                    node.storage
+                        .peer_addresses
+                        .get(leader_id, &connection_type)
+                        .ok()
+                        .flatten()
+                });
+                if let Some(leader_address) = leader_address {
+                    e.set_field("leader_address", leader_address.as_str().into());
+                }
+                e
+            }
+            Self::Sbroad(ref sbroad) => {
+                let mut e = BoxError::new(self.error_code(), self.to_string());
+                // Clients (e.g. `picodata connect` in verbose mode)
+                // show these details along with the message.
+                let sqlstate = crate::pgproto::error::PgErrorCode::from_sbroad(sbroad);
+                e.set_field("sqlstate", sqlstate.as_str().into());
+                if let Some(entity) = sbroad.entity() {
+                    e.set_field("entity", entity.to_string().into());
+                }
+                if let Some(hint) = sbroad.hint() {
+                    e.set_field("hint", hint.into());
+                }
                 e
             }
             other => {
@@ -1504,6 +1504,7 @@ impl NodeImpl {
                         let initiator_def = user_by_id(initiator).expect("user must exist");
 
                         let name = &space.name;
+                        invalidate_table_plans(name);
                         crate::audit!(
                             message: "dropped table `{name}`",
                             title: "drop_table",
@@ -1559,6 +1560,8 @@ impl NodeImpl {
                             .update_schema_version(table_id, schema_version)
                             .expect("storage shouldn't fail");
 
+                        invalidate_table_plans(&old_name);
+
                         let initiator_def = user_by_id(initiator_id).expect("user must exist");
                         crate::audit!(
                             message: "renamed table `{old_name}` to `{new_name}`",
@@ -1578,6 +1581,7 @@ impl NodeImpl {
                     } => {
                         ddl_meta_space_update_operable(&self.storage, table_id, true)
                             .expect("storage shouldn't fail");
+
                         // TODO: it would be nice to fuse the update of `operable` field with the update of `schema_version`
                         // this needs some API design though
                         self.storage
@@ -1590,6 +1594,7 @@ impl NodeImpl {
                         let space_raw = self.storage.pico_table.get(table_id);
                         let space = space_raw.ok().flatten().expect("failed to get space");
                         let name = &space.name;
+                        invalidate_table_plans(name);
                         crate::audit!(
                             message: "changed table format for `{name}`",
                             title: "change_table_format",
@@ -3471,6 +3476,17 @@ fn read_only(e: impl Into<String>) -> BoxError {
     BoxError::new(TarantoolErrorCode::Readonly, e)
 }
 
+/// Drop cached SQL plans of the table after its schema has changed.
+fn invalidate_table_plans(table_name: &str) {
+    let router = crate::sql::router::RouterRuntime::new();
+    if let Err(e) = router.invalidate_schema(table_name) {
+        tlog!(
+            Warning,
+            "failed to invalidate plans for table {table_name}: {e}"
+        );
+    }
+}
+
 pub(crate) struct MainLoop {
     #[allow(dead_code)]
     fiber_id: fiber::FiberId,
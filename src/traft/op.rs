@@ -705,6 +705,20 @@ impl Dml {
         Ok(res)
     }
 
+    #[inline(always)]
+    pub fn replace_raw(
+        space: impl Into<SpaceId>,
+        tuple: Vec<u8>,
+        initiator: UserId,
+    ) -> tarantool::Result<Self> {
+        let res = Self::Replace {
+            table: space.into(),
+            tuple: TupleBuffer::try_from_vec(tuple)?,
+            initiator,
+        };
+        Ok(res)
+    }
+
     /// Serializes `key` and returns an [`Dml::Update`] in case of success.
     #[inline(always)]
     pub fn update(
@@ -1061,6 +1075,25 @@ impl RenameMapping {
         result
     }
 
+    /// Apply a rename to the [`TableOption`]s which refer to the table columns
+    pub fn transform_option_columns(&self, opts: &mut [TableOption]) -> bool {
+        let mut result = false;
+
+        for opt in opts {
+            match opt {
+                TableOption::Identity { column }
+                | TableOption::Check { column, .. }
+                | TableOption::Default { column, .. }
+                | TableOption::ColumnComment { column, .. } => {
+                    result |= self.transform_name(column);
+                }
+                TableOption::Unlogged(_) | TableOption::PkContainsBucketId(_) => {}
+            }
+        }
+
+        result
+    }
+
     /// Apply a rename to an [`IndexDef`] by renaming the table columns
     pub fn transform_index_columns(&self, index_def: &mut IndexDef) -> bool {
         let mut result = false;
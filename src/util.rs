@@ -866,6 +866,7 @@ pub fn cast_and_encode<'a>(value: &'a Value, column_type: &SbroadType) -> Option
         (SbroadType::Integer, Value::Integer(_)) => return Some(value.into()),
         (SbroadType::String, Value::String(_)) => return Some(value.into()),
         (SbroadType::Uuid, Value::Uuid(_)) => return Some(value.into()),
+        (SbroadType::Bytea, Value::Bytea(_)) => return Some(value.into()),
         // Manually check for non-negativeness of underlying value if possible.
         (SbroadType::Unsigned, Value::Decimal(decimal)) => {
             // Return None in case of bad value, otherwise try to cast.
@@ -916,6 +917,7 @@ pub fn check_msgpack_matches_type(
         }
         SbroadType::String => is_str(marker) || is_bin(marker),
         SbroadType::Uuid => is_ext(msgpack, tarantool::ffi::uuid::MP_UUID),
+        SbroadType::Bytea => is_bin(marker),
         SbroadType::Datetime => is_ext(msgpack, tarantool::ffi::datetime::MP_DATETIME),
         SbroadType::Json => is_map(marker),
     };
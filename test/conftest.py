@@ -2064,6 +2064,11 @@ Last governor error is:
             if res != [["_pico_resharding_state", 534]]:
                 log.error(f"Invalid metadata for _pico_bucket: {res}")
 
+        if parse_version_exc(cluster_version) >= Version("26.1.0"):
+            res = self.sql("SELECT name, id FROM _pico_table WHERE name = '_pico_sequence'")
+            if res != [["_pico_sequence", 535]]:
+                log.error(f"Invalid metadata for _pico_sequence: {res}")
+
         res = self.sql("SELECT value FROM _pico_property WHERE key = 'system_catalog_version'")
         if res[0][0] not in ["25.3.7", "25.4.1", "25.5.1", "25.5.2", "25.5.3", "25.5.8", "26.1.0"]:
             return False
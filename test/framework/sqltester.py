@@ -171,6 +171,9 @@ class PgprotoRunner:
         # Connect via psycopg
         host, port = leader.pg_host, leader.pg_port
         conn = psycopg.connect(f"postgres://postgres:Passw0rd@{host}:{port}")
+        # Statements of the test files are independent, like in the iproto runner,
+        # so they must not share the implicit transaction block opened by psycopg.
+        conn.autocommit = True
 
         self.cluster = cluster
         self.conn = conn
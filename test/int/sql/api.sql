@@ -125,6 +125,31 @@ select "id" from "t";
 -- EXPECTED:
 1, 2
 
+-- TEST: test_union_common_type
+-- SQL:
+SELECT 1 UNION ALL SELECT 2.5 ORDER BY 1;
+-- EXPECTED:
+Decimal('1'), Decimal('2.5')
+
+-- TEST: test_union_types_cannot_be_matched
+-- SQL:
+SELECT 1 UNION SELECT true;
+-- ERROR:
+UNION types int and bool cannot be matched for column "col_1"
+
+-- TEST: test_union_string_literal-1
+-- SQL:
+SELECT 1 UNION ALL SELECT '2' ORDER BY 1;
+-- EXPECTED:
+1, 2
+
+-- TEST: test_union_string_literal-2
+-- SQL:
+SELECT count(*) FROM (SELECT '2024-01-01'::datetime AS d UNION ALL SELECT '2024-01-02')
+WHERE d >= '2024-01-01'::datetime;
+-- EXPECTED:
+2
+
 -- TEST: test_lower_upper-1
 -- SQL:
 select lower("COLUMN_1") as a, upper("COLUMN_1") as b from (values ('Aba'));
@@ -295,3 +295,39 @@ INSERT INTO t2 VALUES(123,456);
 SELECT count(DISTINCT c) FROM t1 LEFT JOIN t2 ON TRUE;
 -- EXPECTED:
 1
+
+-- TEST: distinctagg-7.0
+-- SQL:
+DROP TABLE IF EXISTS t5;
+CREATE TABLE t5(pk INT PRIMARY KEY, a INT);
+INSERT INTO t5 VALUES (1, 1), (2, 2), (3, 3), (4, 1), (5, 2), (6, 3), (7, 1), (8, 4), (9, 4), (10, 5);
+
+-- TEST: distinctagg-7.1
+-- SQL:
+SELECT count(DISTINCT a) FROM t5;
+-- EXPECTED:
+5
+
+-- TEST: distinctagg-7.2
+-- SQL:
+SELECT count(*) FROM (SELECT DISTINCT a FROM t5);
+-- EXPECTED:
+5
+
+-- TEST: distinctagg-8.1
+-- SQL:
+SELECT sum(DISTINCT a), avg(DISTINCT a) FROM t5;
+-- EXPECTED:
+15, 3
+
+-- TEST: distinctagg-8.2
+-- SQL:
+SELECT sum(a), avg(a) FROM (SELECT DISTINCT a FROM t5);
+-- EXPECTED:
+15, 3
+
+-- TEST: distinctagg-8.3
+-- SQL:
+SELECT sum(DISTINCT a), count(a), avg(DISTINCT a), avg(a) FROM t5;
+-- EXPECTED:
+15, 10, 3, 2.6
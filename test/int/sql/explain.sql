@@ -337,7 +337,7 @@ SELECT "COL_0" as "id", "COL_1" as "name", "COL_2" as "product_units" FROM ( SEL
 EXPLAIN (RAW) SELECT * from testing_space WHERE "id" = 1 GROUP BY 1, 2, 3 ORDER BY 1 LIMIT 1;
 -- EXPECTED:
 1. Query (FILTERED STORAGE):
-SELECT "testing_space"."id" as "gr_expr_1", "testing_space"."name" as "gr_expr_2", "testing_space"."product_units" as "gr_expr_3" FROM "testing_space" WHERE "testing_space"."id" = CAST(1 AS int) GROUP BY "testing_space"."id", "testing_space"."name", "testing_space"."product_units"
+SELECT "testing_space"."id", "testing_space"."name", "testing_space"."product_units" FROM "testing_space" WHERE "testing_space"."id" = CAST(1 AS int) GROUP BY "testing_space"."id", "testing_space"."name", "testing_space"."product_units"
 +----------+-------+------+--------------------------------------------------------------+
 | selectid | order | from | detail                                                       |
 +========================================================================================+
@@ -345,14 +345,12 @@ SELECT "testing_space"."id" as "gr_expr_1", "testing_space"."name" as "gr_expr_2
 +----------+-------+------+--------------------------------------------------------------+
 ''
 2. Query (ROUTER):
-SELECT "id", "name", "product_units" FROM ( SELECT "COL_0" as "id", "COL_1" as "name", "COL_2" as "product_units" FROM ( SELECT "COL_0", "COL_1", "COL_2" FROM "TMP_9550002613441822564_0136" ) GROUP BY "COL_0", "COL_1", "COL_2" ) ORDER BY 1 LIMIT 1
+SELECT "COL_0" as "id", "COL_1" as "name", "COL_2" as "product_units" FROM ( SELECT "COL_0", "COL_1", "COL_2" FROM "TMP_9550002613441822564_0136" ) ORDER BY 1 LIMIT 1
 +----------+-------+------+---------------------------------------------------------+
 | selectid | order | from | detail                                                  |
 +===================================================================================+
 | 0        | 0     | 0    | SCAN TABLE TMP_9550002613441822564_0136 (~1048576 rows) |
 |----------+-------+------+---------------------------------------------------------|
-| 0        | 0     | 0    | USE TEMP B-TREE FOR GROUP BY                            |
-|----------+-------+------+---------------------------------------------------------|
 | 0        | 0     | 0    | USE TEMP B-TREE FOR ORDER BY                            |
 +----------+-------+------+---------------------------------------------------------+
 ''
@@ -377,12 +375,12 @@ FROM
 ''
 2. Query (STORAGE):
 SELECT
-  "testing_space"."id" as "gr_expr_1",
-  "testing_space"."name" as "gr_expr_2",
-  "testing_space"."product_units" as "gr_expr_3",
-  "testing_space"."COL_0" as "gr_expr_4",
-  "testing_space"."COL_2" as "gr_expr_5",
-  "testing_space"."COL_3" as "gr_expr_6"
+  "testing_space"."id",
+  "testing_space"."name",
+  "testing_space"."product_units",
+  "testing_space"."COL_0" as "id",
+  "testing_space"."COL_2" as "name",
+  "testing_space"."COL_3" as "product_units"
 FROM
   "testing_space"
   INNER JOIN (
@@ -413,40 +411,23 @@ GROUP BY
 ''
 3. Query (ROUTER):
 SELECT
-  "id",
-  "name",
-  "product_units",
-  "id",
-  "name",
-  "product_units"
+  "COL_0" as "id",
+  "COL_1" as "name",
+  "COL_2" as "product_units",
+  "COL_3" as "id",
+  "COL_4" as "name",
+  "COL_5" as "product_units"
 FROM
   (
     SELECT
-      "COL_0" as "id",
-      "COL_1" as "name",
-      "COL_2" as "product_units",
-      "COL_3" as "id",
-      "COL_4" as "name",
-      "COL_5" as "product_units"
-    FROM
-      (
-        SELECT
-          "COL_0",
-          "COL_1",
-          "COL_2",
-          "COL_3",
-          "COL_4",
-          "COL_5"
-        FROM
-          "TMP_7751910730270557539_0136"
-      )
-    GROUP BY
       "COL_0",
       "COL_1",
       "COL_2",
       "COL_3",
       "COL_4",
       "COL_5"
+    FROM
+      "TMP_7751910730270557539_0136"
   )
 ORDER BY
 '  1'
@@ -457,8 +438,6 @@ LIMIT
 +===================================================================================+
 | 0        | 0     | 0    | SCAN TABLE TMP_7751910730270557539_0136 (~1048576 rows) |
 |----------+-------+------+---------------------------------------------------------|
-| 0        | 0     | 0    | USE TEMP B-TREE FOR GROUP BY                            |
-|----------+-------+------+---------------------------------------------------------|
 | 0        | 0     | 0    | USE TEMP B-TREE FOR ORDER BY                            |
 +----------+-------+------+---------------------------------------------------------+
 ''
@@ -523,25 +502,7 @@ EXPLAIN (RAW) DELETE FROM testing_space;
 EXPLAIN (RAW) DELETE FROM testing_space WHERE id IN ( SELECT id FROM testing_space_hist GROUP BY id HAVING SUM(product_units) = 0 );
 -- EXPECTED:
 1. Query (STORAGE):
-SELECT "testing_space_hist"."id" as "gr_expr_1", sum ( CAST ("testing_space_hist"."product_units" as int) ) as "sum_1" FROM "testing_space_hist" GROUP BY "testing_space_hist"."id"
-+----------+-------+------+-----------------------------------------------+
-| selectid | order | from | detail                                        |
-+=========================================================================+
-| 0        | 0     | 0    | SCAN TABLE testing_space_hist (~1048576 rows) |
-+----------+-------+------+-----------------------------------------------+
-''
-2. Query (ROUTER):
-SELECT "COL_0" as "id" FROM ( SELECT "COL_0", "COL_1" FROM "TMP_1599265012539281798_0136" ) GROUP BY "COL_0" HAVING sum ("COL_1") = CAST(0 AS int)
-+----------+-------+------+---------------------------------------------------------+
-| selectid | order | from | detail                                                  |
-+===================================================================================+
-| 0        | 0     | 0    | SCAN TABLE TMP_1599265012539281798_0136 (~1048576 rows) |
-|----------+-------+------+---------------------------------------------------------|
-| 0        | 0     | 0    | USE TEMP B-TREE FOR GROUP BY                            |
-+----------+-------+------+---------------------------------------------------------+
-''
-3. Query (STORAGE):
-SELECT "testing_space"."id" as "pk_col_0" FROM "testing_space" WHERE "testing_space"."id" in ( SELECT "COL_0" FROM "TMP_12108310727876432219_0136" )
+SELECT "testing_space"."id" as "pk_col_0" FROM "testing_space" WHERE "testing_space"."id" in ( SELECT "testing_space_hist"."id" FROM "testing_space_hist" GROUP BY "testing_space_hist"."id" HAVING sum ( CAST ("testing_space_hist"."product_units" as int) ) = CAST(0 AS int) )
 +----------+-------+------+----------------------------------------------------------------+
 | selectid | order | from | detail                                                         |
 +==========================================================================================+
@@ -549,7 +510,7 @@ SELECT "testing_space"."id" as "pk_col_0" FROM "testing_space" WHERE "testing_sp
 |----------+-------+------+----------------------------------------------------------------|
 | 0        | 0     | 0    | EXECUTE LIST SUBQUERY 1                                        |
 |----------+-------+------+----------------------------------------------------------------|
-| 1        | 0     | 0    | SCAN TABLE TMP_12108310727876432219_0136 (~1048576 rows)       |
+| 1        | 0     | 0    | SCAN TABLE testing_space_hist (~1048576 rows)                  |
 +----------+-------+------+----------------------------------------------------------------+
 ''
 
@@ -575,25 +536,7 @@ SELECT CAST(0 AS int) as "col_0", "testing_space"."id" as "col_1" FROM "testing_
 EXPLAIN (RAW) UPDATE testing_space SET product_units = -1 WHERE id IN ( SELECT id FROM testing_space_hist GROUP BY id HAVING SUM(product_units) = 0 );
 -- EXPECTED:
 1. Query (STORAGE):
-SELECT "testing_space_hist"."id" as "gr_expr_1", sum ( CAST ("testing_space_hist"."product_units" as int) ) as "sum_1" FROM "testing_space_hist" GROUP BY "testing_space_hist"."id"
-+----------+-------+------+-----------------------------------------------+
-| selectid | order | from | detail                                        |
-+=========================================================================+
-| 0        | 0     | 0    | SCAN TABLE testing_space_hist (~1048576 rows) |
-+----------+-------+------+-----------------------------------------------+
-''
-2. Query (ROUTER):
-SELECT "COL_0" as "id" FROM ( SELECT "COL_0", "COL_1" FROM "TMP_17992124938322492506_0136" ) GROUP BY "COL_0" HAVING sum ("COL_1") = CAST(0 AS int)
-+----------+-------+------+----------------------------------------------------------+
-| selectid | order | from | detail                                                   |
-+====================================================================================+
-| 0        | 0     | 0    | SCAN TABLE TMP_17992124938322492506_0136 (~1048576 rows) |
-|----------+-------+------+----------------------------------------------------------|
-| 0        | 0     | 0    | USE TEMP B-TREE FOR GROUP BY                             |
-+----------+-------+------+----------------------------------------------------------+
-''
-3. Query (STORAGE):
-SELECT CAST(-1 AS int) as "col_0", "testing_space"."id" as "col_1" FROM "testing_space" WHERE "testing_space"."id" in ( SELECT "COL_0" FROM "TMP_17388536538104774043_0136" )
+SELECT CAST(-1 AS int) as "col_0", "testing_space"."id" as "col_1" FROM "testing_space" WHERE "testing_space"."id" in ( SELECT "testing_space_hist"."id" FROM "testing_space_hist" GROUP BY "testing_space_hist"."id" HAVING sum ( CAST ("testing_space_hist"."product_units" as int) ) = CAST(0 AS int) )
 +----------+-------+------+----------------------------------------------------------------+
 | selectid | order | from | detail                                                         |
 +==========================================================================================+
@@ -601,7 +544,7 @@ SELECT CAST(-1 AS int) as "col_0", "testing_space"."id" as "col_1" FROM "testing
 |----------+-------+------+----------------------------------------------------------------|
 | 0        | 0     | 0    | EXECUTE LIST SUBQUERY 1                                        |
 |----------+-------+------+----------------------------------------------------------------|
-| 1        | 0     | 0    | SCAN TABLE TMP_17388536538104774043_0136 (~1048576 rows)       |
+| 1        | 0     | 0    | SCAN TABLE testing_space_hist (~1048576 rows)                  |
 +----------+-------+------+----------------------------------------------------------------+
 ''
 
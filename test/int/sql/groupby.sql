@@ -696,6 +696,26 @@ SELECT "a", max("b"), max(distinct "b") FROM "arithmetic_space" GROUP BY "a";
 -- EXPECTED:
 1, 2, 2, 2, 3, 3
 
+-- TEST: test_min_max_string_with_groupby
+-- SQL:
+SELECT "product_units", max("name"), min("name") FROM "testing_space"
+GROUP BY "product_units" ORDER BY "product_units";
+-- EXPECTED:
+1, '123', '1', 2, '2', '123', 4, '2', '2'
+
+-- TEST: test_min_max_boolean_with_groupby
+-- SQL:
+SELECT "a", max("boolean_col"), min("boolean_col") FROM "arithmetic_space2"
+GROUP BY "a" ORDER BY "a";
+-- EXPECTED:
+1, true, false, 2, true, false
+
+-- TEST: test_max_string_all_null_group
+-- SQL:
+SELECT "nc", max(cast("nb" as string)) FROM "null_t" GROUP BY "nc" ORDER BY "nc";
+-- EXPECTED:
+null, null, 1, null, 2, '1', 3, null
+
 -- TEST: test_total
 -- SQL:
 SELECT total("id"), total(distinct "d" / 2) from "arithmetic_space";
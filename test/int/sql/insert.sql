@@ -263,3 +263,55 @@ SELECT * FROM "double_t";
 -- TEST: test_double_conversion-3
 -- SQL:
 DELETE FROM "double_t";
+
+-- TEST: test_insert_default-1
+-- SQL:
+CREATE TABLE t_default (id INT PRIMARY KEY, a INT, b TEXT NOT NULL);
+
+-- TEST: test_insert_default-2
+-- SQL:
+INSERT INTO t_default (id, a, b) VALUES (1, DEFAULT, 'x'), (2, 2, 'y');
+
+-- TEST: test_insert_default-3
+-- SQL:
+SELECT * FROM t_default ORDER BY id;
+-- EXPECTED:
+1, null, 'x',
+2, 2, 'y'
+
+-- TEST: test_insert_default-4
+-- SQL:
+INSERT INTO t_default (id, a, b) VALUES (3, 3, DEFAULT);
+-- ERROR:
+NonNull column "b" has no default value
+
+-- TEST: test_insert_declared_default-1
+-- SQL:
+CREATE TABLE t_declared_default (id INT PRIMARY KEY, a INT DEFAULT 7, b TEXT DEFAULT 'x' NOT NULL);
+
+-- TEST: test_insert_declared_default-2
+-- SQL:
+INSERT INTO t_declared_default (id, a, b) VALUES (1, DEFAULT, DEFAULT), (2, 2, 'y');
+INSERT INTO t_declared_default (id) VALUES (3);
+
+-- TEST: test_insert_declared_default-3
+-- SQL:
+SELECT * FROM t_declared_default ORDER BY id;
+-- EXPECTED:
+1, 7, 'x',
+2, 2, 'y',
+3, 7, 'x'
+
+-- TEST: test_insert_declared_default-4
+-- SQL:
+ALTER TABLE t_declared_default RENAME COLUMN a TO a_new;
+
+-- TEST: test_insert_declared_default-5
+-- SQL:
+INSERT INTO t_declared_default (id) VALUES (4);
+
+-- TEST: test_insert_declared_default-6
+-- SQL:
+SELECT a_new, b FROM t_declared_default WHERE id = 4;
+-- EXPECTED:
+7, 'x'
@@ -0,0 +1,83 @@
+-- TEST: init
+-- SQL:
+DROP TABLE IF EXISTS t;
+CREATE TABLE t (a INT PRIMARY KEY, d DOUBLE, n DECIMAL);
+INSERT INTO t VALUES (1, 2.5, 2.5), (2, -2.5, -2.5), (3, 3.0, 3.0);
+
+-- TEST: ceil-1
+-- SQL:
+SELECT ceil(n) FROM t ORDER BY a;
+-- EXPECTED:
+Decimal('3'),
+Decimal('-2'),
+Decimal('3')
+
+-- TEST: ceil-2
+-- SQL:
+SELECT ceil(d) FROM t ORDER BY a;
+-- EXPECTED:
+3.0,
+-2.0,
+3.0
+
+-- TEST: ceil-3
+-- SQL:
+SELECT ceil(a) FROM t ORDER BY a;
+-- EXPECTED:
+1,
+2,
+3
+
+-- TEST: floor-1
+-- SQL:
+SELECT floor(n) FROM t ORDER BY a;
+-- EXPECTED:
+Decimal('2'),
+Decimal('-3'),
+Decimal('3')
+
+-- TEST: floor-2
+-- SQL:
+SELECT floor(d) FROM t ORDER BY a;
+-- EXPECTED:
+2.0,
+-3.0,
+3.0
+
+-- TEST: sign-1
+-- SQL:
+SELECT sign(n), sign(d), sign(a - 2) FROM t ORDER BY a;
+-- EXPECTED:
+1, 1, -1,
+-1, -1, 0,
+1, 1, 1
+
+-- TEST: round-1
+-- SQL:
+SELECT round(n) FROM t ORDER BY a;
+-- EXPECTED:
+Decimal('3'),
+Decimal('-3'),
+Decimal('3')
+
+-- TEST: round-double
+-- SQL:
+SELECT round(d), floor(d * 2) FROM t ORDER BY a;
+-- EXPECTED:
+3.0, 5.0,
+-3.0, -5.0,
+3.0, 6.0
+
+-- TEST: round-2
+-- SQL:
+SELECT round(1234.5678, 2), round(1234.5678, 0), round(1234.5678, -2);
+-- EXPECTED:
+Decimal('1234.57'), Decimal('1235'), Decimal('1200')
+
+-- TEST: round-3
+-- SQL:
+SELECT round(a * 125, -2) FROM t ORDER BY a;
+-- EXPECTED:
+Decimal('100'),
+Decimal('300'),
+Decimal('400')
@@ -93,6 +93,18 @@ SELECT * FROM "t" where "id" < 2 and "a" > 5;
 -- EXPECTED:
 
 
+-- TEST: test_compare_rows_arity-1
+-- SQL:
+SELECT * FROM "t" WHERE ("id", "a") = (1, 4.2, 3);
+-- ERROR:
+unequal number of entries in row expression: 2 and 3
+
+-- TEST: test_compare_rows_arity-2
+-- SQL:
+SELECT * FROM "t" WHERE ("id", "a") = (SELECT "id" FROM "t" WHERE "id" = 1);
+-- ERROR:
+subquery returns 1 columns, expected 2
+
 -- TEST: test_except-1
 -- SQL:
 insert into "t" ("id", "a") values (3, 777), (1000001, 6.66), (1000002, 6.66);
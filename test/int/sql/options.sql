@@ -105,18 +105,18 @@ Exceeded maximum number of rows \(1\) in virtual table: 3
 
 -- TEST: test_sql_motion_row_max_on_router-1
 -- SQL:
-select "id" from "testing_space" group by "id" option(sql_motion_row_max = 5);
+select "id" + 0 from "testing_space" group by "id" + 0 option(sql_motion_row_max = 5);
 -- ERROR:
 Exceeded maximum number of rows \(5\) in virtual table: 6
 
 -- TEST: test_sql_motion_row_max_on_router-2
 -- SQL:
-select "id" from "testing_space" group by "id" option(sql_motion_row_max = 7);
+select "id" + 0 from "testing_space" group by "id" + 0 option(sql_motion_row_max = 7);
 -- EXPECTED:
 1, 2, 3, 4, 5, 6
 
 -- TEST: test_sql_motion_row_max_on_router-3
 -- SQL:
-select "id" from "testing_space" group by "id" option(sql_motion_row_max = 6);
+select "id" + 0 from "testing_space" group by "id" + 0 option(sql_motion_row_max = 6);
 -- EXPECTED:
 1, 2, 3, 4, 5, 6
@@ -85,6 +85,25 @@ select * from "null_t" order by 4;
 -- ERROR:
 invalid expression: Ordering index \(4\) is bigger than child projection output length \(3\)
 
+-- TEST: orderby6-negative
+-- SQL:
+select * from "null_t" order by -1;
+-- ERROR:
+ORDER BY position -1 is not in select list
+
+-- TEST: orderby6-projection
+-- SQL:
+select "na", "nc" from "null_t" order by 2, 1;
+-- EXPECTED:
+8, -1,
+1, 1,
+4, 1,
+7, 1,
+2, 3,
+3, 3,
+6, 3,
+5, 5
+
 -- TEST: orderby7
 -- SQL:
 select * from "null_t" order by "nb", "na";
@@ -201,3 +220,45 @@ with t(a) as (values (1), (2), (3)) select a from t order by (select 1), a desc;
 -- EXPECTED:
 3, 2, 1
 
+
+-- TEST: test-orderby-collate-init
+-- SQL:
+DROP TABLE IF EXISTS collate_t;
+CREATE TABLE collate_t (id int primary key, s text);
+INSERT INTO collate_t VALUES (1, 'b'), (2, 'B'), (3, 'a'), (4, 'A');
+
+-- TEST: test-orderby-collate-default
+-- SQL:
+select s from collate_t order by s;
+-- EXPECTED:
+'A', 'B', 'a', 'b'
+
+-- TEST: test-orderby-collate-c
+-- SQL:
+select s from collate_t order by s collate "C" desc;
+-- EXPECTED:
+'b', 'a', 'B', 'A'
+
+-- TEST: test-orderby-collate-unicode
+-- SQL:
+select s from collate_t order by s collate "unicode";
+-- EXPECTED:
+'a', 'A', 'b', 'B'
+
+-- TEST: test-orderby-collate-unicode-ci
+-- SQL:
+select id from collate_t order by s collate "unicode_ci", id;
+-- EXPECTED:
+3, 4, 1, 2
+
+-- TEST: test-where-collate-unicode-ci
+-- SQL:
+select id from collate_t where s collate "unicode_ci" = 'a' order by id;
+-- EXPECTED:
+3, 4
+
+-- TEST: test-orderby-collate-unknown
+-- SQL:
+select s from collate_t order by s collate "foo";
+-- ERROR:
+collation "foo" not found
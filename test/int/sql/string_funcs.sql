@@ -0,0 +1,195 @@
+-- TEST: init
+-- SQL:
+DROP TABLE IF EXISTS t;
+CREATE TABLE t (a INT PRIMARY KEY, s TEXT);
+INSERT INTO t VALUES (1, 'abc'), (2, 'привет'), (3, ''), (4, NULL);
+
+-- TEST: length-1
+-- SQL:
+SELECT length(s), char_length(s), octet_length(s) FROM t ORDER BY a;
+-- EXPECTED:
+3, 3, 3,
+6, 6, 12,
+0, 0, 0,
+None, None, None
+
+-- TEST: ascii-1
+-- SQL:
+SELECT ascii(s) FROM t ORDER BY a;
+-- EXPECTED:
+97,
+1087,
+0,
+None
+
+-- TEST: chr-1
+-- SQL:
+SELECT chr(97), chr(1078);
+-- EXPECTED:
+'a', 'ж'
+
+-- TEST: chr-2
+-- SQL:
+SELECT chr(ascii(s)) FROM t WHERE a = 2;
+-- EXPECTED:
+'п'
+
+-- TEST: lpad-1
+-- SQL:
+SELECT lpad('hi', 5), lpad('hi', 5, 'xy'), lpad('привет', 3), lpad('hi', 0), lpad('hi', -1, 'x');
+-- EXPECTED:
+'   hi', 'xyxhi', 'при', '', ''
+
+-- TEST: rpad-1
+-- SQL:
+SELECT rpad('hi', 5), rpad('hi', 5, 'жы'), rpad('hello', 2, 'x'), rpad('hi', 5, '');
+-- EXPECTED:
+'hi   ', 'hiжыж', 'he', 'hi'
+
+-- TEST: pad-null
+-- SQL:
+SELECT lpad(s, 4, '*'), rpad(s, 4, NULL) FROM t ORDER BY a;
+-- EXPECTED:
+'*abc', None,
+'прив', None,
+'****', None,
+None, None
+
+-- TEST: repeat-1
+-- SQL:
+SELECT repeat('ab', 3), repeat('ab', 0), repeat('ab', -2), repeat(s, 2) FROM t WHERE a = 2;
+-- EXPECTED:
+'ababab', '', '', 'приветпривет'
+
+-- TEST: reverse-1
+-- SQL:
+SELECT reverse(s) FROM t ORDER BY a;
+-- EXPECTED:
+'cba',
+'тевирп',
+'',
+None
+
+-- TEST: split_part-1
+-- SQL:
+SELECT split_part('a,b,c', ',', 2), split_part('a<>b<>c', '<>', 3), split_part('a,b,c', ',', -1),
+    split_part('привет,мир', ',', 2);
+-- EXPECTED:
+'b', 'c', 'c', 'мир'
+
+-- TEST: split_part-out-of-range
+-- SQL:
+SELECT split_part('a,b,c', ',', 4), split_part('a,b,c', ',', -4), split_part('', ',', 1);
+-- EXPECTED:
+'', '', ''
+
+-- TEST: split_part-empty-delimiter
+-- SQL:
+SELECT split_part('abc', '', 1), split_part('abc', '', -1), split_part('abc', '', 2);
+-- EXPECTED:
+'abc', 'abc', ''
+
+-- TEST: split_part-null
+-- SQL:
+SELECT split_part(s, ',', 1), split_part('a,b', NULL, 1) FROM t ORDER BY a;
+-- EXPECTED:
+'abc', None,
+'привет', None,
+'', None,
+None, None
+
+-- TEST: split_part-zero
+-- SQL:
+SELECT split_part('a,b', ',', 0);
+-- ERROR:
+field position must not be zero
+
+-- TEST: string_to_array-1
+-- SQL:
+SELECT string_to_array('a,b,c', ',')[2]::text, string_to_array('a<>b', '<>')[2]::text,
+    string_to_array('привет', NULL)[2]::text, string_to_array('a,b', '')[1]::text;
+-- EXPECTED:
+'b', 'b', 'р', 'a,b'
+
+-- TEST: string_to_array-null
+-- SQL:
+SELECT string_to_array(NULL, ',') IS NULL, string_to_array('a,*,c', ',', '*')[2] IS NULL,
+    string_to_array('a,*,c', ',', '*')[3]::text;
+-- EXPECTED:
+true, true, 'c'
+
+-- TEST: array_to_string-1
+-- SQL:
+SELECT array_to_string(string_to_array('a,*,c', ',', '*'), '-'),
+    array_to_string(string_to_array('a,*,c', ',', '*'), '-', '?'),
+    array_to_string(string_to_array('a,b', ','), NULL), array_to_string(string_to_array('', ','), ',');
+-- EXPECTED:
+'a-c', 'a-?-c', None, ''
+
+-- TEST: array_to_string-round-trip
+-- SQL:
+SELECT array_to_string(string_to_array(s, ','), ','), array_to_string(string_to_array(s, NULL), '')
+FROM (SELECT a, s FROM t UNION ALL SELECT 5, 'a,,b,') ORDER BY a;
+-- EXPECTED:
+'abc', 'abc',
+'привет', 'привет',
+'', '',
+None, None,
+'a,,b,', 'a,,b,'
+
+-- TEST: to_char-number-1
+-- SQL:
+SELECT to_char(1234.5, '999,999.99'), to_char(-1234.5, '999,999.99'), to_char(1234567, '9,999,999'),
+    to_char(7, '000'), to_char(0.5, '9.99'), to_char(1234.5, 'FM999,999.99'), to_char(12345, '999');
+-- EXPECTED:
+'   1,234.50', '  -1,234.50', ' 1,234,567', ' 007', '  .50', '1,234.5', ' ###'
+
+-- TEST: to_char-date-1
+-- SQL:
+SELECT to_char(to_date('2024-01-02', 'YYYY-MM-DD'), 'DD.MM.YYYY'),
+    to_date('02/01/2024', 'DD/MM/YYYY') = '2024-01-02'::datetime;
+-- EXPECTED:
+'02.01.2024', true
+
+-- TEST: to_number-1
+-- SQL:
+SELECT to_number('1,234.50', '9,999.99'), to_number(' -12', '999'), to_number('1,234', '9G999');
+-- EXPECTED:
+Decimal('1234.50'), Decimal('-12'), Decimal('1234')
+
+-- TEST: to_number-2
+-- SQL:
+SELECT to_number('12a', '999');
+-- ERROR:
+value "12a" does not match format "999"
+
+-- TEST: to_number-3
+-- SQL:
+SELECT to_number('12345', '999');
+-- ERROR:
+value "12345" does not match format "999"
+
+-- TEST: to_date-mismatch
+-- SQL:
+SELECT to_date('2024/01/02', 'YYYY-MM-DD');
+-- ERROR:
+could not parse
+
+-- TEST: md5-sha256-1
+-- SQL:
+SELECT md5('abc'), md5(''), sha256('abc'), md5(NULL), sha256(NULL);
+-- EXPECTED:
+'900150983cd24fb0d6963f7d28e17f72', 'd41d8cd98f00b204e9800998ecf8427e',
+'ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad', None, None
+
+-- TEST: md5-sha256-2
+-- SQL:
+SELECT md5(s) = '900150983cd24fb0d6963f7d28e17f72', length(md5(s)), length(sha256(s)) FROM t WHERE a = 1;
+-- EXPECTED:
+true, 32, 64
+
+-- TEST: sha256-bytea
+-- SQL:
+SELECT sha256('abc'::bytea) = '\xba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad'::bytea, sha256(NULL::bytea);
+-- EXPECTED:
+true, None
@@ -1,3 +1,5 @@
+import pytest
+from conftest import TarantoolError
 from framework.sqltester import (
     Cluster,
     ClusterTwoInstances,
@@ -22,3 +24,34 @@ def test_index_metadata_is_updated(cluster: Cluster):
     i1.sql("ALTER TABLE test_table RENAME COLUMN status TO new_name")
     results = i1.sql("SELECT parts FROM _pico_index WHERE name = 'test_index'")
     assert results == [[[["new_name", "string", None, True, None]]]]
+
+
+def test_sharding_column_rename(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+    cluster.wait_balanced()
+
+    i1.sql("CREATE TABLE t (id INT PRIMARY KEY, status TEXT) DISTRIBUTED BY (id)")
+    i1.sql("INSERT INTO t VALUES (1, 'hi'), (2, 'kitty')")
+
+    # Cache the plans referencing the old name on both routers.
+    for i in [i1, i2]:
+        assert i.sql("SELECT status FROM t WHERE id = 1") == [["hi"]]
+
+    i1.sql("ALTER TABLE t RENAME COLUMN id TO key")
+
+    res = i1.sql("SELECT distribution FROM _pico_table WHERE name = 't'")
+    assert res == [[{"ShardedImplicitly": [["key"], "murmur3", "default"]}]]
+
+    for i in [i1, i2]:
+        i.raft_read_index()
+        with pytest.raises(TarantoolError, match='column with name "id" not found'):
+            i.sql("SELECT status FROM t WHERE id = 1")
+        assert i.sql("SELECT status FROM t WHERE key = 1") == [["hi"]]
+
+        # The renamed column still routes the query to a single bucket.
+        lines = i.sql("EXPLAIN SELECT status FROM t WHERE key = 1")
+        assert lines[-1] != "buckets = [1-3000]"
+
+    i1.sql("INSERT INTO t VALUES (3, 'cat')")
+    assert sorted(i1.sql("SELECT key, status FROM t")) == [[1, "hi"], [2, "kitty"], [3, "cat"]]
@@ -63,3 +63,180 @@ true
 select '2026-01-13'::datetime between '2026-01-01'::datetime and '2026-01-20'::datetime;
 -- EXPECTED:
 true
+
+-- TEST: datetime-4.1
+-- SQL:
+DROP TABLE IF EXISTS ts_t;
+CREATE TABLE ts_t (ts TIMESTAMPTZ PRIMARY KEY, id INT) DISTRIBUTED BY (ts);
+INSERT INTO ts_t VALUES
+    ('2024-01-01 00:00:00', 1),
+    ('2024-01-01 03:00:00.5+03', 2),
+    ('2024-01-02T00:00:00Z', 3),
+    ('2023-12-31 23:59:59.999999', 4),
+    ('2024-01-01 10:00 -01:30', 5);
+
+-- TEST: datetime-4.2
+-- SQL:
+SELECT id FROM ts_t ORDER BY ts;
+-- EXPECTED:
+4, 1, 2, 5, 3
+
+-- TEST: datetime-4.3
+-- SQL:
+SELECT id FROM ts_t WHERE ts > '2024-01-01 00:00:00' ORDER BY ts DESC;
+-- EXPECTED:
+3, 5, 2
+
+-- TEST: datetime-4.4
+-- SQL:
+SELECT id FROM ts_t WHERE ts = '2024-01-01 03:00:00+03:00';
+-- EXPECTED:
+1
+
+-- TEST: datetime-4.5
+-- SQL:
+SELECT id FROM ts_t WHERE ts = '2024-01-01 11:30:00 Z';
+-- EXPECTED:
+5
+
+-- TEST: datetime-4.6
+-- SQL:
+SELECT id FROM ts_t WHERE ts BETWEEN '2023-12-31 23:59:59.9'::timestamp AND '2024-01-01 00:00:00.5';
+-- UNORDERED:
+4, 1, 2
+
+-- TEST: datetime-5.1
+-- SQL:
+SELECT '2024-01-01 00:00:00'::timestamp = '2024-01-01T03:00:00+03'::timestamptz;
+-- EXPECTED:
+true
+
+-- TEST: datetime-5.2
+-- SQL:
+SELECT CAST('2024-01-01 00:00:00.25' AS TIMESTAMP WITH TIME ZONE) < '2024-01-01 00:00:00.3';
+-- EXPECTED:
+true
+
+-- TEST: datetime-5.3
+-- SQL:
+SELECT '2024-01-01 00:00:00 +25'::timestamptz;
+-- ERROR:
+to datetime
+
+-- TEST: datetime-6.1
+-- SQL:
+SELECT '2024-01-01 10:00:00+03'::timestamp = '2024-01-01 10:00:00Z'::timestamptz,
+    CAST('2024-01-01 10:00:00+03'::timestamptz AS TIMESTAMP WITHOUT TIME ZONE) = '2024-01-01 07:00:00Z';
+-- EXPECTED:
+true, true
+
+-- TEST: datetime-6.2
+-- SQL:
+SELECT '2024-01-01 23:30:00-03'::date = '2024-01-01'::timestamptz,
+    '2024-01-01 23:30:00-03'::timestamptz::date = '2024-01-02'::timestamptz;
+-- EXPECTED:
+true, true
+
+-- TEST: datetime-6.3
+-- SQL:
+SELECT '2024-01-01 10:20:30.5+03'::time, '7:05'::time, CAST('23:59:59.123456789' AS TIME WITHOUT TIME ZONE);
+-- EXPECTED:
+'10:20:30.5', '07:05:00', '23:59:59.123456'
+
+-- TEST: datetime-6.4
+-- SQL:
+DROP TABLE IF EXISTS temporal_t;
+CREATE TABLE temporal_t (id INT PRIMARY KEY, ts TIMESTAMPTZ, s TEXT);
+INSERT INTO temporal_t VALUES
+    (1, '2024-01-01 23:30:00-03', '2024-03-05 10:20:30.25 +05'),
+    (2, '2024-01-02 00:00:00Z', '8:00');
+
+-- TEST: datetime-6.5
+-- SQL:
+SELECT id, ts::date = '2024-01-02'::timestamptz, ts::time FROM temporal_t ORDER BY id;
+-- EXPECTED:
+1, true, '02:30:00',
+2, true, '00:00:00'
+
+-- TEST: datetime-6.6
+-- SQL:
+SELECT s::time FROM temporal_t ORDER BY id;
+-- EXPECTED:
+'10:20:30.25', '08:00:00'
+
+-- TEST: datetime-6.7
+-- SQL:
+SELECT s::timestamp = '2024-03-05 10:20:30.25Z'::timestamptz, s::date = '2024-03-05'
+FROM temporal_t WHERE id = 1;
+-- EXPECTED:
+true, true
+
+-- TEST: datetime-6.8
+-- SQL:
+SELECT id FROM temporal_t ORDER BY s::time DESC;
+-- EXPECTED:
+1, 2
+
+-- TEST: datetime-6.9
+-- SQL:
+SELECT 'yesterday'::date;
+-- ERROR:
+invalid input syntax for type date: "yesterday"
+
+-- TEST: datetime-6.10
+-- SQL:
+SELECT '25:00'::time;
+-- ERROR:
+invalid input syntax for type time: "25:00"
+
+-- TEST: datetime-6.11
+-- SQL:
+CREATE TABLE date_t (id INT PRIMARY KEY, d DATE);
+-- ERROR:
+date columns, use datetime instead
+
+-- TEST: datetime-6.12
+-- SQL:
+CREATE TABLE timestamp_t (id INT PRIMARY KEY, ts TIMESTAMP WITHOUT TIME ZONE);
+-- ERROR:
+timestamp columns, use datetime instead
+
+-- TEST: date_trunc-1.1
+-- SQL:
+SELECT date_trunc('hour', '2024-01-01 10:45:12.5'::timestamp) = '2024-01-01 10:00:00'::timestamp,
+    date_trunc('day', '2024-01-01 10:45:12 +03'::timestamptz) = '2024-01-01 00:00:00 +03'::timestamptz,
+    date_trunc('MONTH', '2024-02-17 10:45:12'::timestamp) = '2024-02-01'::timestamp,
+    date_trunc('week', '2024-01-04 10:45:12'::timestamp) = '2024-01-01'::timestamp,
+    date_trunc('quarter', '2024-05-17 10:45:12'::timestamp) = '2024-04-01'::timestamp,
+    date_trunc('year', '2024-05-17 10:45:12'::timestamp) = '2024-01-01'::timestamp;
+-- EXPECTED:
+true, true, true, true, true, true
+
+-- TEST: date_trunc-1.2
+-- SQL:
+SELECT date_trunc('day', NULL::timestamp);
+-- EXPECTED:
+null
+
+-- TEST: date_trunc-1.3
+-- SQL:
+DROP TABLE IF EXISTS events_t;
+CREATE TABLE events_t (id INT PRIMARY KEY, ts TIMESTAMPTZ);
+INSERT INTO events_t VALUES
+    (1, '2024-01-01 10:05:00Z'),
+    (2, '2024-01-01 10:59:59.9Z'),
+    (3, '2024-01-01 11:00:00Z'),
+    (4, '2024-01-02 10:30:00Z'),
+    (5, '2024-01-01 10:00:00Z');
+
+-- TEST: date_trunc-1.4
+-- SQL:
+SELECT count(*) FROM events_t GROUP BY date_trunc('hour', ts) ORDER BY 1;
+-- EXPECTED:
+1, 1, 3
+
+-- TEST: date_trunc-1.5
+-- SQL:
+SELECT date_trunc('decade', '2024-01-01'::timestamp);
+-- ERROR:
+date_trunc: unit "decade" not recognized
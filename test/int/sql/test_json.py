@@ -102,3 +102,96 @@ def test_json_extract(cluster: Cluster):
 
     res = cur.execute("SELECT id, col FROM t WHERE id = 2 AND id = JSON_EXTRACT_PATH(col, 'a')::int").fetchall()
     assert [[2, {"a": 2}]]
+
+
+def test_json_operators(cluster: Cluster):
+    i1, *_ = cluster.deploy(instance_count=1)
+    user = "postgres"
+    password = "Passw0rd"
+    i1.sql(f"CREATE USER {user} WITH PASSWORD '{password}'")
+    i1.sql(f"GRANT CREATE TABLE TO {user}", sudo=True)
+    host, port = i1.pg_host, i1.pg_port
+    conn = psycopg.connect(f"postgres://{user}:{password}@{host}:{port}")
+    conn.autocommit = True
+    cur = conn.cursor()
+
+    cur.execute("CREATE TABLE t (id int, data jsonb, primary key (id))")
+    i1.eval("""box.space.t:insert{1, 1, require('json').decode('{"name":"alice","info":{"age":30},"tags":[1,2]}')}""")
+    i1.eval("""box.space.t:insert{2, 1, require('json').decode('{"name":"bob","info":"none"}')}""")
+
+    cur.execute("SELECT data->>'name' FROM t ORDER BY id")
+    assert cur.description[0].type_code == 25  # text oid
+    assert cur.fetchall() == [("alice",), ("bob",)]
+
+    cur.execute("SELECT data->'info' FROM t ORDER BY id")
+    assert cur.description[0].type_code == 114  # json oid
+    assert cur.fetchall() == [({"age": 30},), ("none",)]
+
+    # Indexing a value that is neither an object nor an array yields NULL.
+    res = cur.execute("SELECT data->'info'->>'age' FROM t ORDER BY id").fetchall()
+    assert res == [("30",), (None,)]
+
+    res = cur.execute("SELECT data->>'tags' FROM t WHERE id = 1").fetchall()
+    assert res == [("[1,2]",)]
+
+    res = cur.execute("SELECT id FROM t WHERE data->>'name' = 'bob'").fetchall()
+    assert res == [(2,)]
+
+    res = cur.execute("""SELECT '{"a": {"b": "c"}}'::json->'a'->>'b'""").fetchall()
+    assert res == [("c",)]
+
+    res = cur.execute("""SELECT JSON_EXTRACT_PATH_TEXT(data, 'info', 'age') FROM t ORDER BY id""").fetchall()
+    assert res == [("30",), (None,)]
+
+    with pytest.raises(psycopg.errors.InternalError, match='invalid input syntax for type json: "{"a": 1"'):
+        cur.execute("""SELECT '{"a": 1'::json->'a'""").fetchall()
+
+    with pytest.raises(
+        psycopg.errors.InternalError,
+        match="could not resolve function overload for json_extract_path_text\\(int, text\\)",
+    ):
+        cur.execute("SELECT id->>'a' FROM t").fetchall()
+
+
+def test_json_predicates(cluster: Cluster):
+    i1, *_ = cluster.deploy(instance_count=1)
+    user = "postgres"
+    password = "Passw0rd"
+    i1.sql(f"CREATE USER {user} WITH PASSWORD '{password}'")
+    i1.sql(f"GRANT CREATE TABLE TO {user}", sudo=True)
+    host, port = i1.pg_host, i1.pg_port
+    conn = psycopg.connect(f"postgres://{user}:{password}@{host}:{port}")
+    conn.autocommit = True
+    cur = conn.cursor()
+
+    cur.execute("CREATE TABLE s (id int, txt text, primary key (id))")
+    cur.execute("""INSERT INTO s VALUES (1, '{"a": 1}'), (2, '[1, 2'), (3, '42'), (4, 'abc'), (5, NULL)""")
+
+    # Malformed text is not json rather than an error.
+    res = cur.execute("SELECT id, txt IS JSON, txt IS NOT JSON FROM s ORDER BY id").fetchall()
+    assert res == [(1, True, False), (2, False, True), (3, True, False), (4, False, True), (5, None, None)]
+
+    res = cur.execute("SELECT id FROM s WHERE txt IS JSON ORDER BY id").fetchall()
+    assert res == [(1,), (3,)]
+
+    cur.execute("CREATE TABLE t (id int, data jsonb, primary key (id))")
+    i1.eval("""box.space.t:insert{1, 1, require('json').decode('{"name":"alice","tags":[1,2],"info":{"age":30}}')}""")
+    i1.eval("""box.space.t:insert{2, 1, require('json').decode('{"name":"bob","tags":[3]}')}""")
+
+    res = cur.execute("""SELECT id FROM t WHERE data @> '{"name": "bob"}'::json""").fetchall()
+    assert res == [(2,)]
+
+    res = cur.execute("""SELECT id FROM t WHERE data @> '{"tags": [2], "info": {}}'::json""").fetchall()
+    assert res == [(1,)]
+
+    # A top level array contains its scalar elements, other scalars
+    # are contained only in equal scalars.
+    res = cur.execute("""SELECT id FROM t WHERE data->'tags' @> '3'::json""").fetchall()
+    assert res == [(2,)]
+    res = cur.execute("""SELECT '"a"'::json @> '"a"'::json, '1'::json @> '[1]'::json""").fetchall()
+    assert res == [(True, False)]
+
+    res = cur.execute("""SELECT jsonb_path_query(data, '$.tags[1]') FROM t ORDER BY id""").fetchall()
+    assert res == [(2,), (None,)]
+    res = cur.execute("""SELECT jsonb_path_query(data, '$.info.age') FROM t WHERE id = 1""").fetchall()
+    assert res == [(30,)]
@@ -0,0 +1,9 @@
+from framework.sqltester import (
+    ClusterSingleInstance,
+    sql_test_file,
+)
+
+
+@sql_test_file("numeric_funcs.sql")
+class TestNumericFuncs(ClusterSingleInstance):
+    pass
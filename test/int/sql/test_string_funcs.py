@@ -0,0 +1,9 @@
+from framework.sqltester import (
+    ClusterSingleInstance,
+    sql_test_file,
+)
+
+
+@sql_test_file("string_funcs.sql")
+class TestStringFuncs(ClusterSingleInstance):
+    pass
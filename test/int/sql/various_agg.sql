@@ -46,3 +46,89 @@ could not resolve function overload for group_concat\(text, int\)
 select group_concat(a, b) from t2;
 -- ERROR:
 GROUP_CONCAT aggregate function second argument must be a string literal
+
+-- TEST: string_agg_order_by_init
+-- SQL:
+create table t3 (id int primary key, g int, s text);
+insert into t3 values (1, 1, 'c'), (2, 1, 'a'), (3, 1, null), (4, 1, 'b'), (5, 2, null), (6, 3, 'z'), (7, 3, 'y');
+
+-- TEST: string_agg_order_by_grouped
+-- SQL:
+select g, string_agg(s, ',' order by s) from t3 group by g order by g;
+-- EXPECTED:
+1, 'a,b,c', 2, null, 3, 'y,z'
+
+-- TEST: string_agg_order_by_desc
+-- SQL:
+select string_agg(s, ',' order by s desc) from t3;
+-- EXPECTED:
+'z,y,c,b,a'
+
+-- TEST: string_agg_order_by_nulls
+-- SQL:
+select string_agg(s, ',' order by s desc nulls first) from t3;
+-- EXPECTED:
+'z,y,c,b,a'
+
+-- TEST: string_agg_order_by_collate_init
+-- SQL:
+create table t4 (id int primary key, s text);
+insert into t4 values (1, 'B'), (2, 'a'), (3, 'C');
+
+-- TEST: string_agg_order_by_collate
+-- SQL:
+select string_agg(s, ',' order by s collate "unicode_ci") from t4;
+-- EXPECTED:
+'a,B,C'
+
+-- TEST: string_agg_order_by_binary
+-- SQL:
+select string_agg(s, ',' order by s) from t4;
+-- EXPECTED:
+'B,C,a'
+
+-- TEST: string_agg_order_by_motion_row_max
+-- SQL:
+select string_agg(s, ',' order by s) from t3 option(sql_motion_row_max = 3);
+-- ERROR:
+Exceeded maximum number of rows \(3\) in virtual table
+
+-- TEST: string_agg_order_by_other_key
+-- SQL:
+select string_agg(s, ',' order by id) from t3;
+-- ERROR:
+ORDER BY inside GROUP_CONCAT aggregate function must use the aggregated expression
+
+-- TEST: bool_agg_init
+-- SQL:
+create table tb (id int primary key, g int, b bool);
+insert into tb values (1, 1, true), (2, 1, true), (3, 2, true), (4, 2, false),
+    (5, 3, true), (6, 3, null), (7, 4, null), (8, 4, null), (9, 5, false), (10, 5, null);
+
+-- TEST: bool_agg_grouped
+-- SQL:
+select g, bool_and(b), bool_or(b) from tb group by g order by g;
+-- EXPECTED:
+1, True, True,
+2, False, True,
+3, True, True,
+4, None, None,
+5, False, False
+
+-- TEST: bool_agg_all_rows
+-- SQL:
+select bool_and(b), bool_or(b) from tb;
+-- EXPECTED:
+False, True
+
+-- TEST: bool_agg_nulls_only
+-- SQL:
+select bool_and(b), bool_or(b) from tb where g = 4;
+-- EXPECTED:
+None, None
+
+-- TEST: bool_agg_empty
+-- SQL:
+select bool_and(b), bool_or(b) from tb where g > 5;
+-- EXPECTED:
+None, None
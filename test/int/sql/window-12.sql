@@ -168,7 +168,7 @@ projection (row_number() over (partition by ("x"::int + ROW($1)) ) -> "col_1", s
         projection ("t6"."x"::int -> "x", "t6"."y"::int -> "y")
             scan "t6"
 subquery $0:
-motion [policy: full, program: ReshardIfNeeded]
+motion [policy: single, program: ReshardIfNeeded]
         scan
             projection (count(*::int) over (rows between current row and unbounded following) -> "col_1")
                 scan "unnamed_subquery"
@@ -419,7 +419,9 @@ Replace(_pico_db_config, ["iproto_net_msg_max","default",768]),
 Replace(_pico_db_config, ["plugin_check_migration_hash","",true]),
 Replace(_pico_db_config, ["governor_check_replication_error","",true]),
 Replace(_pico_db_config, ["jwt_secret","","<jwt_secret>"]),
-Replace(_pico_db_config, ["sql_log","",false]))|
+Replace(_pico_db_config, ["sql_log","",false]),
+Replace(_pico_db_config, ["sql_identifier_case","","fold"]),
+Replace(_pico_db_config, ["sql_expression_depth_max","",10000]))|
 |  0  | 1  |BatchDml(
 Insert(_pico_db_config, ["shredding","",false])
 )|
@@ -501,7 +503,9 @@ Insert(_pico_index, [{_pico_user_audit_policy},0,"_pico_user_audit_policy_pkey",
 Insert(_pico_table, [{_pico_bucket},"_pico_bucket",{{"Global":null}},[{{"name":"tier_name","field_type":"string","is_nullable":false}},{{"name":"bucket_id_start","field_type":"unsigned","is_nullable":false}},{{"name":"bucket_id_end","field_type":"unsigned","is_nullable":false}},{{"name":"state","field_type":"string","is_nullable":false}},{{"name":"current_replicaset_name","field_type":"string","is_nullable":false}},{{"name":"target_replicaset_name","field_type":"string","is_nullable":true}}],0,true,"memtx",1,"",[]]),
 Insert(_pico_index, [{_pico_bucket},0,"_pico_bucket_index_primary","tree",[{{"unique":true}}],[["tier_name","string",null,false,null],["bucket_id_start","unsigned",null,false,null]],true,0]),
 Insert(_pico_table, [{_pico_resharding_state},"_pico_resharding_state",{{"Global":null}},[{{"name":"tier_name","field_type":"string","is_nullable":false}},{{"name":"type","field_type":"string","is_nullable":false}},{{"name":"current_replicaset_name","field_type":"string","is_nullable":false}},{{"name":"target_replicaset_name","field_type":"string","is_nullable":false}},{{"name":"bucket_id_start","field_type":"unsigned","is_nullable":true}},{{"name":"bucket_count","field_type":"unsigned","is_nullable":false}}],0,true,"memtx",1,"",[]]),
-Insert(_pico_index, [{_pico_resharding_state},0,"_pico_resharding_state_index_primary","tree",[{{"unique":true}}],[["tier_name","string",null,false,null],["type","string",null,false,null],["current_replicaset_name","string",null,false,null],["target_replicaset_name","string",null,false,null]],true,0])
+Insert(_pico_index, [{_pico_resharding_state},0,"_pico_resharding_state_index_primary","tree",[{{"unique":true}}],[["tier_name","string",null,false,null],["type","string",null,false,null],["current_replicaset_name","string",null,false,null],["target_replicaset_name","string",null,false,null]],true,0]),
+Insert(_pico_table, [{_pico_sequence},"_pico_sequence",{{"Global":null}},[{{"name":"table_id","field_type":"unsigned","is_nullable":false}},{{"name":"allocated","field_type":"unsigned","is_nullable":false}}],0,true,"memtx",1,"",[]]),
+Insert(_pico_index, [{_pico_sequence},0,"_pico_sequence_index_primary","tree",[{{"unique":true}}],[["table_id","unsigned",null,false,null]],true,0])
 )|
 |  0  | 1  |GrantPrivilege(2, 1, 2, table, Some(515), read)|
 |  0  | 1  |GrantPrivilege(3, 1, 2, table, Some(514), read)|
@@ -554,6 +558,7 @@ Update(_pico_tier, ["default"], [["=","target_vshard_config_version",3]])
         _pico_user_audit_policy=space_id("_pico_user_audit_policy"),
         _pico_bucket=space_id("_pico_bucket"),
         _pico_resharding_state=space_id("_pico_resharding_state"),
+        _pico_sequence=space_id("_pico_sequence"),
     )
     actual = preprocess(raft_log)
     expected = preprocess(expected)
@@ -121,6 +121,11 @@ def test_admin_ux(cluster: Cluster):
     cli.expect_exact("Language switched to lua")
     cli.expect_exact("(admin) lua> ")
 
+    cli.sendline("\\conninfo")
+    cli.expect_exact('Connected to admin console by socket path "./admin.sock"')
+    cli.expect_exact("Current language is lua")
+    cli.expect_exact("(admin) lua> ")
+
     # Lua does not require delimiter
     cli.sendline("box.session.user()")
     cli.expect_exact("admin")
@@ -672,6 +677,108 @@ def test_input_with_delimiter(cluster: Cluster):
     cli.expect_exact("Delimiter changed to ';'")
 
 
+def test_connect_error_verbosity(cluster: Cluster):
+    i1 = cluster.add_instance(wait_online=False)
+    i1.start()
+    i1.wait_online()
+    i1.create_user(with_name="andy", with_password="Testpa55")
+    i1.sql('GRANT CREATE TABLE TO "andy"', sudo=True)
+
+    cli = pexpect.spawn(
+        command=i1.runtime.command,
+        args=["connect", f"{i1.host}:{i1.port}", "-u", "andy"],
+        encoding="utf-8",
+        timeout=CLI_TIMEOUT,
+    )
+    cli.logfile = sys.stdout
+
+    cli.expect_exact("Enter password for andy: ")
+    cli.sendline("Testpa55")
+    cli.expect_exact("sql> ")
+
+    cli.sendline("CREATE TABLE ids (id INTEGER PRIMARY KEY) DISTRIBUTED BY (id);")
+    cli.expect_exact("1")
+    cli.expect_exact("sql> ")
+
+    # terse mode is the default, only the message is printed
+    cli.sendline("SELECT unknown FROM ids;")
+    cli.expect_exact('column with name "unknown"')
+    cli.expect_exact("sql> ")
+    assert "SQLSTATE" not in cli.before
+
+    cli.sendline("\\set verbosity verbose")
+    cli.expect_exact("Verbosity changed to 'verbose'")
+
+    cli.sendline("SELECT unknown FROM ids;")
+    cli.expect_exact('column with name "unknown"')
+    cli.expect_exact("SQLSTATE: 42703")
+    cli.expect_exact("ENTITY: column")
+    cli.expect_exact("HINT: unquoted names are case-folded")
+    cli.expect_exact("LOCATION: ")
+    cli.expect_exact("sql> ")
+
+    cli.sendline("\\set verbosity terse")
+    cli.expect_exact("Verbosity changed to 'terse'")
+
+    cli.sendline("SELECT unknown FROM ids;")
+    cli.expect_exact('column with name "unknown"')
+    cli.expect_exact("sql> ")
+    assert "SQLSTATE" not in cli.before
+
+
+def test_admin_describe_table(cluster: Cluster):
+    i1 = cluster.add_instance(wait_online=True)
+    i1.sql("CREATE TABLE t (a INT NOT NULL, b TEXT, PRIMARY KEY (a, b)) DISTRIBUTED BY (b, a)")
+    i1.sql("CREATE TABLE g (id INT PRIMARY KEY) DISTRIBUTED GLOBALLY")
+    i1.sql(
+        """
+        CREATE TABLE c (
+            id INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+            qty INT DEFAULT 1 CONSTRAINT qty_positive CHECK (qty > 0)
+        ) DISTRIBUTED BY (id)
+        """
+    )
+
+    cli = pexpect.spawn(
+        cwd=i1.instance_dir,
+        command=i1.runtime.command,
+        args=["admin", "./admin.sock"],
+        encoding="utf-8",
+        timeout=CLI_TIMEOUT,
+    )
+    cli.logfile = sys.stdout
+    cli.expect_exact("(admin) sql> ")
+
+    cli.sendline("\\d t")
+    cli.expect_exact('Table "t"')
+    cli.expect_exact("not null")
+    cli.expect_exact("Primary key: (a, b)")
+    cli.expect_exact("(admin) sql> ")
+    assert "Distribution" not in cli.before
+
+    cli.sendline("\\d+ t")
+    cli.expect_exact("Primary key: (a, b)")
+    cli.expect_exact("Distribution: sharded by (b, a)")
+    cli.expect_exact("Sharding function: murmur3")
+    cli.expect_exact("Tier: default")
+    cli.expect_exact("Bucket count: 3000")
+
+    cli.sendline("\\d+ c")
+    cli.expect_exact("Default")
+    cli.expect_exact("generated always as identity")
+    cli.expect_exact("Check constraints:")
+    cli.expect_exact('"qty_positive" CHECK (qty > 0)')
+    cli.expect_exact("(admin) sql> ")
+
+    cli.sendline("\\d+ g")
+    cli.expect_exact("Distribution: global (replicated to every instance)")
+    cli.expect_exact("(admin) sql> ")
+    assert "Sharding function" not in cli.before
+
+    cli.sendline("\\d missing")
+    cli.expect_exact('Did not find any table named "missing"')
+
+
 def test_cat_file_to_picodata_admin_stdin(cluster: Cluster):
     instance = cluster.add_instance()
     data = subprocess.check_output(
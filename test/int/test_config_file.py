@@ -130,6 +130,9 @@ instance:
                 listen=dict(source="default", value="127.0.0.1:4327"),
                 advertise=dict(source="default", value="127.0.0.1:4327"),
                 ssl=dict(source="default", value=False),
+                idle_timeout=dict(source="default", value=0.0),
+                idle_timeout_in_transaction=dict(source="default", value=True),
+                tcp_keepalive=dict(source="default", value=0.0),
             ),
             boot_timeout=dict(value=7200, source="default"),
             iproto_tls=dict(
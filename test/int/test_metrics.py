@@ -651,3 +651,31 @@ def test_plan_id_sql_collisions(instance: Instance):
     check_metric(metrics, "pico_router_cache_misses", 11)
     check_metric(metrics, "pico_storage_cache_hits", 2, query_type="dql", rpc_type="local")
     check_metric(metrics, "pico_storage_cache_misses", 6, query_type="dql", rpc_type="local")
+
+
+@pytest.mark.webui
+def test_sql_point_get_metrics(instance: Instance):
+    instance.sql("CREATE TABLE t (a INT PRIMARY KEY, b TEXT) DISTRIBUTED BY (a)")
+    instance.sql("INSERT INTO t VALUES (1, 'one'), (2, 'two')")
+    check_metric(instance.get_metrics(), "pico_sql_point_get", None)
+
+    # Primary key equality is served by a point get.
+    assert instance.sql("SELECT b, a FROM t WHERE a = ?", 2) == [["two", 2]]
+    check_metric(instance.get_metrics(), "pico_sql_point_get", 1)
+
+    # A missing key returns an empty result.
+    dql = instance.sql("SELECT * FROM t WHERE a = 3", strip_metadata=False)
+    assert dql["rows"] == []
+    assert [column["name"] for column in dql["metadata"]] == ["a", "b"]
+    check_metric(instance.get_metrics(), "pico_sql_point_get", 2)
+
+    # Other predicates go through the regular execution.
+    assert instance.sql("SELECT * FROM t WHERE a > 1") == [[2, "two"]]
+    assert instance.sql("SELECT * FROM t WHERE b = 'one'") == [[1, "one"]]
+    assert instance.sql("SELECT a + 1 FROM t WHERE a = 1") == [[2]]
+    check_metric(instance.get_metrics(), "pico_sql_point_get", 2)
+
+    # The point get sees the table as changed by DDL.
+    instance.sql("ALTER TABLE t ADD COLUMN c INT")
+    assert instance.sql("SELECT * FROM t WHERE a = ?", 1) == [[1, "one", None]]
+    check_metric(instance.get_metrics(), "pico_sql_point_get", 3)
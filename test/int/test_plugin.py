@@ -2547,6 +2547,19 @@ cluster:
     assert instance_name == replicaset_master_name("r2")
     assert echo == b"replicaset:master"
 
+    # Check calling RPC to the raft leader via the plugin SDK
+    context = make_context()
+    input = dict(
+        path="/ping",
+        raft_leader=True,
+        input="raft_leader",
+    )
+    output = i3.call(".proc_rpc_dispatch", "/proxy", msgpack.dumps(input), context)
+    pong, instance_name, echo = msgpack.loads(output)
+    assert pong == "pong"
+    assert instance_name == cluster.leader().name
+    assert echo == b"raft_leader"
+
     # Make sure buckets are balanced before routing via bucket_id to eliminate
     # flakiness due to bucket rebalancing
     for i in cluster.instances:
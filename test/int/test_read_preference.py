@@ -1,6 +1,6 @@
 import pytest
 
-from conftest import Cluster, Instance, TarantoolError
+from conftest import Cluster, Instance, Retriable, TarantoolError
 
 
 def init_table(i: Instance):
@@ -241,6 +241,62 @@ def test_replica_custom_plan(cluster: Cluster):
     assert dql == [[i2.name, 3]]
 
 
+def test_replica_lag_max(cluster: Cluster):
+    i1 = cluster.add_instance(wait_online=True, replicaset_name="r99")
+    i2 = cluster.add_instance(wait_online=True, replicaset_name="r99")
+    i3 = cluster.add_instance(wait_online=True, replicaset_name="r99")
+    # i1 is leader as the first member of the replicaset
+    assert i2.replicaset_master_name() == i1.name
+
+    # prepare data for DQL
+    init_table(i1)
+
+    dql_query = """
+        select pico_instance_name(pico_instance_uuid()), creature
+        from wonderland
+        order by creature
+        option(read_preference = replica, replica_lag_max = 1000)
+    """
+
+    # the lags of the replicas are not known yet, so the master handles DQL
+    dql_expect = [[i1.name, "alice"], [i1.name, "gorilla"], [i1.name, "krolik"]]
+    dql = i1.sql(dql_query)
+    assert dql == dql_expect
+
+    # replicas are up to date, so one of them handles DQL
+    # once their lags are refreshed in the background
+    def served_by_replica():
+        dql = i1.sql(dql_query)
+        assert dql[0][0] in (i2.name, i3.name)
+
+    Retriable(timeout=5, rps=10).call(served_by_replica)
+
+    # simulate a lagging replica, the other one handles DQL
+    i2.eval("pico.dispatch.replication_lag = function() return 10 end")
+
+    def served_by_i3():
+        for _ in range(3):
+            dql = i1.sql(dql_query)
+            assert dql == [[i3.name, "alice"], [i3.name, "gorilla"], [i3.name, "krolik"]]
+
+    Retriable(timeout=5, rps=10).call(served_by_i3)
+
+    # all replicas lag behind too much, the master handles DQL
+    i3.eval("pico.dispatch.replication_lag = function() return 10 end")
+
+    def served_by_master():
+        dql = i1.sql(dql_query)
+        assert dql == dql_expect
+
+    Retriable(timeout=5, rps=10).call(served_by_master)
+    dql = i2.sql(dql_query)
+    assert dql == dql_expect
+
+    # the option is applicable only to DQL
+    with pytest.raises(TarantoolError, match="replica_lag_max option is supported only for DQL queries"):
+        i1.sql("delete from wonderland option(replica_lag_max = 1000)")
+
+
 @pytest.mark.parametrize(
     "should_succeed, read_preference",
     [
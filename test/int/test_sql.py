@@ -1,7 +1,11 @@
 import pytest
 import re
+import subprocess
 import uuid
 
+from datetime import datetime, timezone
+from decimal import Decimal
+
 # mypy: disable-error-code="attr-defined"
 from tarantool import Datetime as tt_datetime
 
@@ -12,6 +16,7 @@ from conftest import (
     KeyPart,
     ReturnError,
     TarantoolError,
+    log_crawler,
 )
 
 
@@ -664,6 +669,8 @@ def test_read_from_system_tables(cluster: Cluster):
         "raft_wal_size_max",
         "read_preference",
         "shredding",
+        "sql_expression_depth_max",
+        "sql_identifier_case",
         "sql_log",
         "sql_motion_row_max",
         "sql_preemption",
@@ -1082,6 +1089,51 @@ def test_datetime(cluster: Cluster):
     assert sorted(data, key=lambda e: e[0]) == [["1970-01-01 -0300"]]
 
 
+def test_datetime_sharding_key(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+    cluster.wait_balanced()
+
+    ddl = i1.sql(
+        """
+        create table t (a int primary key, d datetime not null)
+        distributed by (d)
+    """
+    )
+    assert ddl["row_count"] == 1
+
+    # Rows are inserted with the +03:00 offset.
+    values = ", ".join(f"({a}, '2024-01-01T{a + 3:02}:00:00+03:00'::datetime)" for a in range(20))
+    data = i1.sql(f"insert into t values {values}")
+    assert data["row_count"] == 20
+
+    # Every row is stored on the instance owning its bucket,
+    # and both replicasets hold some of the rows.
+    counts = []
+    for i in cluster.instances:
+        count = i.eval(
+            """
+            local count = 0
+            for _, t in box.space.t:pairs() do
+                if box.space._bucket:get(t.bucket_id) == nil then
+                    error("row " .. t.a .. " is stored on a wrong instance")
+                end
+                count = count + 1
+            end
+            return count
+            """
+        )
+        counts.append(count)
+    assert sum(counts) == 20
+    assert all(count > 0 for count in counts)
+
+    # The same instant in UTC is routed to the bucket of the stored row.
+    for i in [i1, i2]:
+        for a in range(20):
+            data = i.sql(f"select a from t where d = '2024-01-01T{a:02}:00:00Z'::datetime")
+            assert data == [[a]]
+
+
 def test_subqueries_on_global_tbls(cluster: Cluster):
     cluster.deploy(instance_count=1)
     i1 = cluster.instances[0]
@@ -2671,6 +2723,67 @@ def test_insert(cluster: Cluster):
         )
 
 
+def test_insert_select_redistribution(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, _ = cluster.instances
+    cluster.wait_balanced()
+
+    for name, key in [("t1", "a"), ("t2", "b"), ("t3", "a")]:
+        ddl = i1.sql(
+            f"""
+            create table {name} (a int not null, b int not null, primary key (a))
+            using memtx
+            distributed by ({key})
+            """
+        )
+        assert ddl["row_count"] == 1
+
+    rows = [(i, i * 7 % 100) for i in range(1, 51)]
+    values = ", ".join(f"({a}, {b})" for a, b in rows)
+    data = i1.sql(f"insert into t1 values {values}")
+    assert data["row_count"] == len(rows)
+
+    # Differently sharded tables: rows are resharded by the target key.
+    lines = i1.sql("explain insert into t2 select a, b from t1")
+    assert 'motion [policy: segment([ref("b")])' in "\n".join(lines)
+    data = i1.sql("insert into t2 select a, b from t1")
+    assert data["row_count"] == len(rows)
+
+    # The same sharding key: the rows stay on their storages.
+    lines = i1.sql("explain insert into t3 select a, b from t1")
+    assert 'motion [policy: local segment([ref("a")])' in "\n".join(lines)
+    data = i1.sql("insert into t3 select a, b from t1")
+    assert data["row_count"] == len(rows)
+
+    for table in ["t2", "t3"]:
+        data = i1.sql(f"select a, b from {table}")
+        assert sorted(data) == [list(row) for row in rows]
+
+    # Every row is stored on the instance owning its bucket, and the
+    # bucket is computed from the target table sharding key.
+    key_def = KeyDef([KeyPart(1, "integer", True)])
+    bucket_count = 3000
+    for table, key in [("t2", "b"), ("t3", "a")]:
+        total = 0
+        for i in cluster.instances:
+            local = i.eval(
+                f"""
+                local res = {{}}
+                for _, t in box.space.{table}:pairs() do
+                    if box.space._bucket:get(t.bucket_id) == nil then
+                        error("row " .. t.a .. " is stored on a wrong instance")
+                    end
+                    table.insert(res, {{t.{key}, t.bucket_id}})
+                end
+                return res
+                """
+            )
+            for value, bucket_id in local:
+                assert bucket_id == i1.hash((value,), key_def) % bucket_count + 1
+            total += len(local)
+        assert total == len(rows)
+
+
 def test_insert_on_conflict(cluster: Cluster):
     cluster.deploy(instance_count=1)
     i1 = cluster.instances[0]
@@ -2864,6 +2977,246 @@ def test_sql_limits(cluster: Cluster):
     """
         )
 
+    # A full scan touches every bucket and is rejected under a low cap.
+    with pytest.raises(
+        TarantoolError,
+        match=r"Exceeded maximum number of buckets per query \(10\): query touches 3000 buckets",
+    ):
+        i1.sql("""select * from "t" option(sql_max_buckets_per_query=10)""")
+
+    # A query pruned by the sharding key passes the same cap.
+    dql = i1.sql("""select * from "t" where "b" = 1 option(sql_max_buckets_per_query=10)""")
+    assert sorted(dql) == [[1, 1], [2, 1]]
+
+    lines = i1.sql("""explain select * from "t" where "b" = 1 option(sql_max_buckets_per_query=10)""")
+    assert "    sql_max_buckets_per_query = 10" in lines
+
+
+def test_sql_partial_results(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, _ = cluster.instances
+    cluster.wait_balanced()
+
+    ddl = i1.sql("create table t (a int not null, primary key (a)) distributed by (a)")
+    assert ddl["row_count"] == 1
+    dml = i1.sql("insert into t select * from (values (1), (2), (3), (4), (5), (6), (7), (8), (9), (10))")
+    assert dml["row_count"] == 10
+
+    # Only the storage holding the row with a = 5 fails.
+    [owner] = [i for i in cluster.instances if i.eval("return box.space.t:get(5) ~= nil")]
+    [other] = [i for i in cluster.instances if i is not owner]
+    other_rows = other.eval("return box.space.t:select()")
+
+    with pytest.raises(TarantoolError) as e:
+        i1.sql("select 1 / (a - 5) from t")
+    assert f"Error on replicaset {owner.replicaset_uuid()}" in str(e.value)
+    assert "(failed on 1 of 2 replicasets, 1 succeeded)" in str(e.value)
+
+    # In the best-effort mode the rows of the succeeded storage are returned.
+    dql = i1.sql("select a from t where 1 / (a - 5) is not null option(sql_partial_results = true)")
+    assert sorted(dql) == sorted([row[0]] for row in other_rows)
+
+    # Queries with motions fail as a whole: the next stage would be computed
+    # over the partial data.
+    with pytest.raises(TarantoolError, match="failed on 1 of 2 replicasets"):
+        i1.sql("select count(*) from t where 1 / (a - 5) is not null option(sql_partial_results = true)")
+
+    # A pgproto client is warned about the partial result.
+    user, password = "alice", "P@ssw0rd"
+    i1.sql(f"create user {user} with password '{password}' using md5")
+    i1.sql(f"grant read on table t to {user}")
+    warnings = []
+    with i1.connect_via_pgproto(user=user, password=password) as conn:
+        conn.add_notice_handler(lambda diag: warnings.append((diag.severity, diag.message_primary)))
+        rows = conn.execute("select a from t where 1 / (a - 5) is not null option(sql_partial_results = true)")
+        assert sorted(rows.fetchall()) == sorted((row[0],) for row in other_rows)
+    assert warnings == [("WARNING", "partial results: 1 of 2 replicasets failed")]
+
+    lines = i1.sql("explain select a from t option(sql_partial_results = true)")
+    assert "    sql_partial_results = true" in lines
+
+
+def test_sql_dispatch_concurrency_max(cluster: Cluster):
+    cluster.deploy(instance_count=3)
+    i1 = cluster.instances[0]
+    cluster.wait_balanced()
+
+    ddl = i1.sql("create table t (a int not null, primary key (a)) distributed by (a)")
+    assert ddl["row_count"] == 1
+    dml = i1.sql("insert into t select * from (values (1), (2), (3), (4), (5), (6))")
+    assert dml["row_count"] == 6
+
+    # Every storage holds the reference request of a dispatch for a while
+    # and records when the request was being served.
+    for i in cluster.instances:
+        i.eval(
+            """
+            local fiber = require('fiber')
+            local clock = require('clock')
+            local lref = pico.dispatch.lref
+            local add = lref.add
+            _G.ref_intervals = {}
+            lref.add = function(...)
+                local start = clock.realtime()
+                fiber.sleep(0.2)
+                table.insert(_G.ref_intervals, { start, clock.realtime() })
+                return add(...)
+            end
+            """
+        )
+
+    def max_in_flight(sql: str) -> int:
+        for i in cluster.instances:
+            i.eval("_G.ref_intervals = {}")
+        dql = i1.sql(sql)
+        assert sorted(dql) == [[1], [2], [3], [4], [5], [6]]
+        events = []
+        for i in cluster.instances:
+            for start, end in i.eval("return _G.ref_intervals"):
+                events += [(start, 1), (end, -1)]
+        in_flight = peak = 0
+        for _, delta in sorted(events):
+            in_flight += delta
+            peak = max(peak, in_flight)
+        return peak
+
+    assert max_in_flight("select a from t") == 3
+    assert max_in_flight("select a from t option(sql_dispatch_concurrency_max = 1)") == 1
+    assert max_in_flight("select a from t option(sql_dispatch_concurrency_max = 2)") <= 2
+    # A limit above the number of the storages doesn't hold the requests back.
+    assert max_in_flight("select a from t option(sql_dispatch_concurrency_max = 10)") == 3
+
+    lines = i1.sql("explain select a from t option(sql_dispatch_concurrency_max = 2)")
+    assert "    sql_dispatch_concurrency_max = 2" in lines
+
+
+def test_sql_trace_dispatch(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, _ = cluster.instances
+
+    ddl = i1.sql("create table t (a int primary key) distributed by (a)")
+    assert ddl["row_count"] == 1
+    dml = i1.sql("insert into t values (1), (2), (3), (4)")
+    assert dml["row_count"] == 4
+
+    i1.call("pico._trace_sql_dispatch", True)
+    trace = (
+        r"sql dispatch: replicaset \S+, buckets \[[\d, ]*\], top Projection, "
+        r"payload [1-9]\d* bytes, query_id: [0-9a-f]{16}"
+    )
+    lc = log_crawler(i1, trace, use_regex=True)
+    assert i1.sql("select a from t where a = 1") == [[1]]
+    lc.wait_matched()
+
+    i1.call("pico._trace_sql_dispatch", False)
+    lc = log_crawler(i1, "sql dispatch:")
+    assert sorted(i1.sql("select a from t")) == [[1], [2], [3], [4]]
+    assert not lc.matched
+
+
+def test_sql_wait_index(cluster: Cluster):
+    i1, i2 = cluster.deploy(instance_count=2)
+    i1.promote_or_fail()
+
+    i1.sql("create table g (id int primary key) distributed globally")
+    i2.raft_wait_index(i1.raft_get_index())
+
+    # Make the second instance apply the raft log with a delay.
+    i2.call("pico._inject_error", "DELAY_BEFORE_APPLYING_RAFT_ENTRY", "3")
+
+    dml = i1.sql("insert into g values (1)")
+    assert dml["row_count"] == 1
+    index = i1.raft_get_index()
+
+    # The read waits for the write to be applied on the router.
+    data = i2.sql(f"select id from g option(wait_index = {index}, wait_index_timeout = 10000)")
+    assert data == [[1]]
+    assert i2.raft_get_index() >= index
+    i2.call("pico._inject_error", "DELAY_BEFORE_APPLYING_RAFT_ENTRY", False)
+
+    # The read fails rather than returns stale data when the index is not reached.
+    with pytest.raises(TarantoolError, match=f"raft index {index + 1000} was not applied within 100 ms"):
+        i2.sql(f"select id from g option(wait_index = {index + 1000}, wait_index_timeout = 100)")
+
+    with pytest.raises(TarantoolError, match="wait_index option is supported only for DQL queries"):
+        i2.sql(f"insert into g values (2) option(wait_index = {index})")
+
+    # Sharded tables are read on the storages, which don't wait for the index.
+    i1.sql("create table s (id int primary key) distributed by (id)")
+    with pytest.raises(TarantoolError, match="wait_index option is supported only for reads of global tables"):
+        i2.sql(f"select id from s option(wait_index = {index})")
+
+    lines = i2.sql(f"explain select id from g option(wait_index = {index})")
+    assert f"    wait_index = {index}" in lines
+    assert "    wait_index_timeout = 5000" in lines
+
+
+def test_sql_result_row_max(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, _ = cluster.instances
+    cluster.wait_balanced()
+
+    ddl = i1.sql("create table t (a int not null, primary key (a)) distributed by (a)")
+    assert ddl["row_count"] == 1
+    dml = i1.sql("insert into t select * from (values (1), (2), (3), (4), (5), (6), (7), (8), (9), (10))")
+    assert dml["row_count"] == 10
+
+    # An unlimited scan exceeding the cap is aborted on the first extra row.
+    with pytest.raises(
+        TarantoolError,
+        match=r"Exceeded maximum number of rows \(5\) in query result: 6",
+    ):
+        i1.sql("select a from t option(sql_result_row_max = 5)")
+
+    # A query with a LIMIT below the cap is unaffected, though all the rows
+    # go through the motion.
+    dql = i1.sql("select a from t order by a limit 3 option(sql_result_row_max = 5)")
+    assert dql == [[1], [2], [3]]
+
+    # The result of exactly the cap size is allowed.
+    dql = i1.sql("select a from t where a <= 5 option(sql_result_row_max = 5)")
+    assert sorted(dql) == [[1], [2], [3], [4], [5]]
+
+    with pytest.raises(TarantoolError, match="sql_result_row_max option is supported only for DQL queries"):
+        i1.sql("insert into t values (11) option(sql_result_row_max = 5)")
+
+    lines = i1.sql("explain select a from t option(sql_result_row_max = 5)")
+    assert "    sql_result_row_max = 5" in lines
+
+
+def test_update_from_join(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, _ = cluster.instances
+    cluster.wait_balanced()
+
+    i1.sql("create table t (id int primary key, x int) distributed by (id)")
+    i1.sql("create table other (id int primary key, ref int, y int) distributed by (id)")
+    dml = i1.sql("insert into t values (1, 0), (2, 0), (3, 0), (4, 0)")
+    assert dml["row_count"] == 4
+    dml = i1.sql("insert into other values (1, 1, 10), (2, 2, 20), (3, 3, 30), (4, 3, 31)")
+    assert dml["row_count"] == 4
+
+    # The row with id = 3 matches twice but is updated only once.
+    dml = i1.sql("update t set x = other.y from other where t.id = other.ref")
+    assert dml["row_count"] == 3
+
+    data = i1.sql("select id, x from t order by id")
+    assert data[:2] == [[1, 10], [2, 20]]
+    assert data[2][1] in (30, 31)
+    assert data[3] == [4, 0]
+
+    # The same for the update of the sharding key.
+    i1.sql("create table s (id int primary key, x int) distributed by (x)")
+    dml = i1.sql("insert into s values (1, 0), (2, 0), (3, 0), (4, 0)")
+    assert dml["row_count"] == 4
+    dml = i1.sql("update s set x = other.y from other where s.id = other.ref")
+    assert dml["row_count"] == 3
+
+    data = i1.sql("select id, x from s order by id")
+    assert data[:2] == [[1, 10], [2, 20]]
+    assert data[2][1] in (30, 31)
+    assert data[3] == [4, 0]
+
 
 def test_sql_acl_password_length(cluster: Cluster):
     cluster.deploy(instance_count=2)
@@ -4921,6 +5274,107 @@ def test_index(cluster: Cluster):
         i1.sql(""" create index i19 on v (b)""")
 
 
+def test_unique_index_nulls_not_distinct(cluster: Cluster):
+    cluster.deploy(instance_count=1)
+    i1 = cluster.instances[0]
+
+    ddl = i1.sql(
+        """
+        create table t (id int primary key, a int not null, b int)
+        distributed by (a)
+        """
+    )
+    assert ddl["row_count"] == 1
+    ddl = i1.sql(""" create table g (id int primary key, b int) distributed globally """)
+    assert ddl["row_count"] == 1
+
+    # NULLs are distinct by default.
+    ddl = i1.sql(""" create unique index t_ab on t (a, b) nulls distinct """)
+    assert ddl["row_count"] == 1
+    dml = i1.sql(""" insert into t values (1, 1, null), (2, 1, null) """)
+    assert dml["row_count"] == 2
+    ddl = i1.sql(""" drop index t_ab """)
+    assert ddl["row_count"] == 1
+    dml = i1.sql(""" delete from t """)
+    assert dml["row_count"] == 2
+
+    ddl = i1.sql(""" create unique index t_ab on t (a, b) nulls not distinct """)
+    assert ddl["row_count"] == 1
+    data = i1.sql(""" select "opts" from "_pico_index" where "name" = 't_ab' """)
+    assert data == [[[{"unique": True}, {"nulls_distinct": False}]]]
+
+    dml = i1.sql(""" insert into t values (1, 1, null) """)
+    assert dml["row_count"] == 1
+    duplicate = 'Duplicate key exists in unique index "t_ab" with NULLS NOT DISTINCT'
+    with pytest.raises(TarantoolError, match=duplicate):
+        i1.sql(""" insert into t values (2, 1, null) """)
+    with pytest.raises(TarantoolError, match=duplicate):
+        i1.sql(""" insert into t select 3, 1, null """)
+
+    # The conflict is skipped on DO NOTHING.
+    dml = i1.sql(""" insert into t values (2, 1, null) on conflict do nothing """)
+    assert dml["row_count"] == 0
+
+    # Non-NULL keys and NULL keys with another prefix are fine.
+    dml = i1.sql(""" insert into t values (2, 1, 2), (3, 2, null) """)
+    assert dml["row_count"] == 2
+
+    with pytest.raises(TarantoolError, match=duplicate):
+        i1.sql(""" update t set b = null where id = 2 """)
+    assert i1.sql(""" select * from t order by id """) == [
+        [1, 1, None],
+        [2, 1, 2],
+        [3, 2, None],
+    ]
+
+    with pytest.raises(TarantoolError, match="allowed only for unique indexes"):
+        i1.sql(""" create index t_b on t (b) nulls not distinct """)
+    with pytest.raises(
+        TarantoolError, match="NULLS NOT DISTINCT is not supported for global tables"
+    ):
+        i1.sql(""" create unique index g_b on g (b) nulls not distinct """)
+
+
+def test_index_collate(cluster: Cluster):
+    cluster.deploy(instance_count=1)
+    i1 = cluster.instances[0]
+
+    ddl = i1.sql(""" create table g (id int primary key, name text) distributed globally """)
+    assert ddl["row_count"] == 1
+    ddl = i1.sql(""" create table t (id int primary key, name text not null) distributed by (name) """)
+    assert ddl["row_count"] == 1
+
+    def describe(table: str) -> str:
+        return subprocess.check_output(
+            [cluster.runtime.command, "admin", f"{i1.instance_dir}/admin.sock"],
+            input=f"\\d {table}\n".encode(),
+        ).decode()
+
+    ddl = i1.sql(""" create unique index g_name on g (name collate unicode_ci) """)
+    assert ddl["row_count"] == 1
+    assert 'Indexes:\n    "g_name" (name COLLATE unicode_ci)' in describe("g")
+
+    # The collation is honored by the index: keys differing only in case collide.
+    dml = i1.sql(""" insert into g values (1, 'abc') """)
+    assert dml["row_count"] == 1
+    with pytest.raises(TarantoolError, match="Duplicate key exists"):
+        i1.sql(""" insert into g values (2, 'ABC') """)
+
+    ddl = i1.sql(""" create index t_name on t (name collate "C") """)
+    assert ddl["row_count"] == 1
+    assert 'Indexes:\n    "t_name" (name COLLATE binary)' in describe("t")
+
+    with pytest.raises(TarantoolError, match='collation "de_DE" not found'):
+        i1.sql(""" create index t_name_de on t (name collate "de_DE") """)
+    with pytest.raises(TarantoolError, match="collation is not supported for column id of type integer"):
+        i1.sql(""" create index t_id on t (id collate unicode) """)
+    with pytest.raises(
+        TarantoolError,
+        match="unique index for the sharded table cannot use collation unicode_ci for sharding key column name",
+    ):
+        i1.sql(""" create unique index t_name_ci on t (name collate unicode_ci) """)
+
+
 def test_order_by(cluster: Cluster):
     cluster.deploy(instance_count=1)
     i1 = cluster.instances[0]
@@ -5344,6 +5798,117 @@ def test_cte(cluster: Cluster):
         i1.sql(""" with cte as (select 1 + true) select * from cte; """)
 
 
+def test_recursive_cte(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1 = cluster.instances[0]
+
+    ddl = i1.sql(
+        """
+        create table edges (src int not null, dst int not null, primary key (src, dst))
+        distributed by (src)
+        option (timeout = 3)
+        """
+    )
+    assert ddl["row_count"] == 1
+    dml = i1.sql("insert into edges values (1, 2), (2, 3), (3, 4), (4, 2)")
+    assert dml["row_count"] == 4
+
+    # transitive closure of a graph with a cycle
+    data = i1.sql(
+        """
+        with recursive reach (node) as (
+            select 1
+            union
+            select dst from edges join reach on src = node
+        )
+        select node from reach order by node
+        """
+    )
+    assert data == [[1], [2], [3], [4]]
+
+    # recursion with a counter
+    data = i1.sql(
+        """
+        with recursive cnt (n) as (
+            select 1
+            union all
+            select n + 1 from cnt where n < 5
+        )
+        select sum(n) from cnt
+        """
+    )
+    assert data == [[15]]
+
+    # UNION ALL never terminates on a cycle
+    with pytest.raises(
+        TarantoolError,
+        match=r"Exceeded maximum number of iterations \(10\) of the recursive CTE",
+    ):
+        i1.sql(
+            """
+            with recursive reach (node) as (
+                select 1
+                union all
+                select dst from edges join reach on src = node
+            )
+            select * from reach
+            option (sql_recursion_max = 10)
+            """
+        )
+
+    with pytest.raises(TarantoolError, match="must not refer to itself in the non-recursive term"):
+        i1.sql(
+            """
+            with recursive r (n) as (
+                select n from r
+                union all
+                select 1
+            )
+            select * from r
+            """
+        )
+
+
+def test_intersect_and_except_all(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1 = cluster.instances[0]
+
+    for name in ["l", "r"]:
+        ddl = i1.sql(
+            f"""
+            create table {name} (id int primary key, a int)
+            distributed by (id)
+            option (timeout = 3)
+            """
+        )
+        assert ddl["row_count"] == 1
+    dml = i1.sql("insert into l values (1, 1), (2, 1), (3, 1), (4, 2), (5, 2), (6, 3)")
+    assert dml["row_count"] == 6
+    dml = i1.sql("insert into r values (1, 1), (2, 1), (3, 2), (4, 4)")
+    assert dml["row_count"] == 4
+
+    data = i1.sql("select a from l intersect select a from r")
+    assert sorted(data) == [[1], [2]]
+    data = i1.sql("select a from l intersect all select a from r")
+    assert sorted(data) == [[1], [1], [2]]
+    data = i1.sql("select a from l except all select a from r")
+    assert sorted(data) == [[1], [2], [3]]
+    data = i1.sql("select a from l except select a from r")
+    assert sorted(data) == [[3]]
+
+    # INTERSECT binds tighter than EXCEPT
+    data = i1.sql(
+        """
+        select a from l
+        except all
+        select a from l
+        intersect all
+        select a from r
+        """
+    )
+    assert sorted(data) == [[1], [2], [3]]
+
+
 def test_unique_index_name_for_sharded_table(cluster: Cluster):
     cluster.deploy(instance_count=1)
     i1 = cluster.instances[0]
@@ -6138,6 +6703,32 @@ def test_limit(cluster: Cluster):
     )
     assert data == [[1], [2], [3]]
 
+    # OFFSET and the standard FETCH FIRST form of LIMIT.
+    i1.sql("CREATE TABLE scores (id INTEGER PRIMARY KEY, score INTEGER) DISTRIBUTED BY (id)")
+    i1.sql("INSERT INTO scores VALUES (1, 10), (2, 20), (3, 20), (4, 20), (5, 30), (6, 40)")
+
+    data = i1.retriable_sql("SELECT id FROM scores ORDER BY id OFFSET 2 ROWS FETCH FIRST 3 ROWS ONLY")
+    assert data == [[3], [4], [5]]
+
+    data = i2.retriable_sql("SELECT id FROM scores ORDER BY id LIMIT 2 OFFSET 3")
+    assert data == [[4], [5]]
+
+    data = i3.retriable_sql("SELECT id FROM scores ORDER BY id FETCH NEXT ROW ONLY")
+    assert data == [[1]]
+
+    data = i1.retriable_sql("SELECT id FROM scores OFFSET 1 FETCH FIRST 10 ROWS ONLY")
+    assert len(data) == 5
+
+    data = i2.retriable_sql("SELECT id FROM scores OFFSET 10")
+    assert data == []
+
+    # WITH TIES returns the rows tied with the last one.
+    data = i3.retriable_sql("SELECT score FROM scores ORDER BY score FETCH FIRST 2 ROWS WITH TIES")
+    assert data == [[10], [20], [20], [20]]
+
+    data = i1.retriable_sql("SELECT score FROM scores ORDER BY score DESC OFFSET 1 FETCH FIRST 2 ROWS WITH TIES")
+    assert data == [[30], [20], [20], [20]]
+
 
 def test_alter_system_property(cluster: Cluster):
     cluster.deploy(instance_count=1)
@@ -6288,6 +6879,47 @@ def test_alter_system_property_errors(cluster: Cluster):
         assert e.value.args[1] == "timeout value cannot be negative"
 
 
+def test_sql_identifier_case(instance: Instance):
+    instance.sql('CREATE TABLE t (a INT PRIMARY KEY, "A" STRING) DISTRIBUTED GLOBALLY')
+    instance.sql("INSERT INTO t VALUES (1, 'X')")
+    assert instance.sql('SELECT A FROM "t"') == [[1]]
+
+    instance.sql("ALTER SYSTEM SET sql_identifier_case = 'preserve'")
+    # The same query text must not reuse the plan built with the old policy.
+    assert instance.sql('SELECT A FROM "t"') == [["X"]]
+    # Function names are folded regardless of the policy.
+    assert instance.sql('SELECT LOWER(A) FROM "t"') == [["x"]]
+
+    instance.sql("ALTER SYSTEM RESET sql_identifier_case")
+    assert instance.sql('SELECT A FROM "t"') == [[1]]
+
+    with pytest.raises(
+        TarantoolError,
+        match="invalid value for 'sql_identifier_case': expected 'fold' or 'preserve'",
+    ):
+        instance.sql("ALTER SYSTEM SET sql_identifier_case = 'upper'")
+
+
+def test_sql_expression_depth_max(instance: Instance):
+    instance.sql("CREATE TABLE t (a INT PRIMARY KEY) DISTRIBUTED GLOBALLY")
+    query = "SELECT a FROM t WHERE a IN ({})".format(", ".join(map(str, range(200))))
+    assert instance.sql(query) == []
+
+    # The IN list is replaced with a chain of ORs, which is checked as well.
+    instance.sql("ALTER SYSTEM SET sql_expression_depth_max = 100")
+    with pytest.raises(TarantoolError, match="expression nesting depth exceeds the limit of 100"):
+        instance.sql(query)
+
+    instance.sql("ALTER SYSTEM RESET sql_expression_depth_max")
+    assert instance.sql(query) == []
+
+    with pytest.raises(
+        TarantoolError,
+        match="invalid value for 'sql_expression_depth_max': value must be greater than 0",
+    ):
+        instance.sql("ALTER SYSTEM SET sql_expression_depth_max = 0")
+
+
 def test_already_exists_error(instance: Instance):
     def do_sql_twice(sql):
         data = instance.sql(sql)
@@ -6514,6 +7146,35 @@ def test_like(instance: Instance):
     data = instance.sql("select n from str where n iLiKe 'prod%'")
     assert data == [["PRODUCT"], ["Product"], ["prod_1"]]
 
+    # test LIKE ANY and ILIKE ANY
+    data = instance.sql("select 'abc' like any (array['x%', 'a%']) from (values (1))")
+    assert data[0] == [True]
+
+    data = instance.sql("select 'abc' like any (array['x%', 'y%']) from (values (1))")
+    assert data[0] == [False]
+
+    data = instance.sql("select 'abc' like any (array[]) from (values (1))")
+    assert data[0] == [False]
+
+    data = instance.sql("select 'abc' like any (array['x%', null]) from (values (1))")
+    assert data[0] == [None]
+
+    data = instance.sql("select 'abc' like any (array['a%', null]) from (values (1))")
+    assert data[0] == [True]
+
+    data = instance.sql("select n from str where n ilike any ('prod_%', 'PRODUCT')")
+    assert data == [["PRODUCT"], ["Product"], ["prod_1"]]
+
+    # array parameters are expanded on binding
+    data = instance.sql("select n from str where n like any (?)", ["prod_%", "PRODUCT"])
+    assert data == [["PRODUCT"], ["prod_1"]]
+
+    data = instance.sql("select n from str where n ilike any ($1)", ["prod_%", "PRODUCT"])
+    assert data == [["PRODUCT"], ["Product"], ["prod_1"]]
+
+    data = instance.sql("select 'abc' like any (?) from (values (1))", [])
+    assert data[0] == [False]
+
 
 def test_select_without_scan(cluster: Cluster):
     cluster.deploy(instance_count=2)
@@ -6766,6 +7427,85 @@ buckets = any"""
     assert "\n".join(lines) == expected_explain
 
 
+def test_explain_dml_is_not_executed(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    cluster.wait_balanced()
+
+    ddl = i1.sql("create table t (a int primary key, b int)")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("create table t2 (a int primary key, b int)")
+    assert ddl["row_count"] == 1
+    dml = i1.sql("insert into t values (1, 1), (2, 2), (3, 3)")
+    assert dml["row_count"] == 3
+
+    lines = i1.sql("explain insert into t2 select a, b from t")
+    assert lines[0] == 'insert "t2" on conflict: fail'
+    assert i2.sql("select * from t2") == []
+
+    lines = i1.sql("explain update t set b = 42")
+    assert lines[0] == 'update "t"'
+    lines = i1.sql("explain delete from t")
+    assert lines[0] == 'delete "t"'
+    assert sorted(i2.sql("select * from t")) == [[1, 1], [2, 2], [3, 3]]
+
+
+def test_generate_series(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, _ = cluster.instances
+
+    cluster.wait_balanced()
+
+    data = i1.sql("select * from generate_series(1, 5)")
+    assert data == [[1], [2], [3], [4], [5]]
+
+    data = i1.sql("select * from generate_series(5, 1, -2)")
+    assert data == [[5], [3], [1]]
+
+    data = i1.sql("select * from generate_series(5, 1)")
+    assert data == []
+
+    data = i1.sql("select * from generate_series($1, $2, $3)", 1, 9, 4)
+    assert data == [[1], [5], [9]]
+
+    data = i1.sql("select * from generate_series($1, $2, $3)", 3, 1, -1)
+    assert data == [[3], [2], [1]]
+
+    data = i1.sql(
+        """
+        select * from generate_series(
+            '2024-01-01T00:00:00Z'::datetime, '2024-01-03T00:00:00Z'::datetime, '1 day'
+        )
+        """
+    )
+    assert data == [
+        [tt_datetime(year=2024, month=1, day=1)],
+        [tt_datetime(year=2024, month=1, day=2)],
+        [tt_datetime(year=2024, month=1, day=3)],
+    ]
+
+    ddl = i1.sql("create table t (a int primary key, b int)")
+    assert ddl["row_count"] == 1
+    dml = i1.sql("insert into t values (1, 10), (2, 20), (5, 50)")
+    assert dml["row_count"] == 3
+
+    data = i1.sql(
+        """
+        select g.generate_series, t.b from generate_series(1, 3) as g
+        left join t on t.a = g.generate_series
+        order by 1
+        """
+    )
+    assert data == [[1, 10], [2, 20], [3, None]]
+
+    with pytest.raises(TarantoolError, match="step size cannot equal zero"):
+        i1.sql("select * from generate_series(1, 5, 0)")
+
+    with pytest.raises(TarantoolError, match="arguments must be constants or parameters"):
+        i1.sql("select * from generate_series(1, 2 + 3)")
+
+
 def test_extreme_integer_values(cluster: Cluster):
     cluster.deploy(instance_count=1)
     i1 = cluster.instances[0]
@@ -6935,6 +7675,35 @@ def test_varchar_cast(cluster: Cluster):
     assert data["metadata"] == [{"name": "col_1", "type": "string"}]
 
 
+def test_boolean_cast_of_columns(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1 = cluster.instances[0]
+
+    ddl = i1.sql("create table t (id int primary key, s text, b bool) distributed by (id)")
+    assert ddl["row_count"] == 1
+    dml = i1.sql(
+        """
+        insert into t values (1, 'Yes', true), (2, ' off ', false),
+            (3, 't', null), (4, '0', true), (5, null, false)
+        """
+    )
+    assert dml["row_count"] == 5
+
+    # The storages accept the same spellings as the router does for literals.
+    data = i1.sql("select id, s::bool, b::text from t order by id")
+    assert data == [
+        [1, True, "true"],
+        [2, False, "false"],
+        [3, True, None],
+        [4, False, "true"],
+        [5, None, "false"],
+    ]
+
+    i1.sql("insert into t values (6, 'maybe', true)")
+    with pytest.raises(TarantoolError, match='invalid input syntax for type boolean: "maybe"'):
+        i1.sql("select s::bool from t")
+
+
 def test_forbid_order_by_with_array(cluster: Cluster):
     cluster.deploy(instance_count=2)
     i1 = cluster.instances[0]
@@ -7073,6 +7842,18 @@ def test_groupby_with_column_positions(cluster: Cluster):
     ):
         i1.sql("""SELECT * FROM t GROUP BY -1""")
 
+    dql = i1.sql("""SELECT a + b, count(*) FROM t GROUP BY 1 ORDER BY 1""")
+    assert dql == [[3, 1], [7, 1], [11, 1], [15, 1]]
+
+    dql = i1.sql("""SELECT b % 4 AS r, count(*) FROM t GROUP BY r ORDER BY 1""")
+    assert dql == [[0, 2], [2, 2]]
+
+    with pytest.raises(
+        TarantoolError,
+        match="aggregate functions are not allowed in GROUP BY",
+    ):
+        i1.sql("""SELECT count(*) AS c FROM t GROUP BY c""")
+
 
 def test_cte_with_tmp_space(cluster: Cluster):
     cluster.deploy(instance_count=1)
@@ -7207,6 +7988,236 @@ def test_alter_table_rename(cluster: Cluster):
         assert ddl["row_count"] == 1
 
 
+def test_alter_table_drop_column(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+    cluster.wait_balanced()
+
+    i1.sql("CREATE TABLE t (id INT PRIMARY KEY, a INT, b TEXT, c INT NOT NULL) DISTRIBUTED BY (id)")
+    i1.sql("CREATE INDEX t_a ON t (a)")
+    i1.sql("INSERT INTO t VALUES (1, 10, 'one', 100), (2, 20, 'two', 200)")
+
+    with pytest.raises(TarantoolError, match="a sharding key column can't be dropped"):
+        i1.sql("ALTER TABLE t DROP COLUMN bucket_id")
+    with pytest.raises(TarantoolError, match="column is used by index"):
+        i1.sql("ALTER TABLE t DROP COLUMN a")
+    with pytest.raises(TarantoolError, match="only the last column of a table can be dropped"):
+        i1.sql("ALTER TABLE t DROP COLUMN b")
+    with pytest.raises(TarantoolError, match="can't add a column after dropping one in the same statement"):
+        i1.sql("ALTER TABLE t DROP COLUMN c, ADD COLUMN d INT")
+
+    ddl = i1.sql("ALTER TABLE t DROP COLUMN c, DROP COLUMN b")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("ALTER TABLE t DROP COLUMN IF EXISTS c")
+    assert ddl["row_count"] == 0
+
+    for i in [i1, i2]:
+        data = i.sql("SELECT * FROM t ORDER BY id")
+        assert data == [[1, 10], [2, 20]]
+
+    # The data of the dropped column doesn't come back.
+    i1.sql("ALTER TABLE t ADD COLUMN b INT")
+    data = i1.sql("SELECT * FROM t ORDER BY id")
+    assert data == [[1, 10, None], [2, 20, None]]
+
+    i1.sql("CREATE TABLE g (id INT PRIMARY KEY, a INT) DISTRIBUTED GLOBALLY")
+    with pytest.raises(TarantoolError, match="columns of global tables can't be dropped"):
+        i1.sql("ALTER TABLE g DROP COLUMN a")
+
+    i1.sql("CREATE TABLE s (id INT PRIMARY KEY, r INT GENERATED ALWAYS AS IDENTITY) DISTRIBUTED BY (id)")
+    with pytest.raises(TarantoolError, match="an identity column can't be dropped"):
+        i1.sql("ALTER TABLE s DROP COLUMN r")
+
+
+def test_in_list_array_parameter(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, _ = cluster.instances
+    cluster.wait_balanced()
+
+    i1.sql("CREATE TABLE t (id INT PRIMARY KEY, name TEXT) DISTRIBUTED BY (id)")
+    i1.sql("INSERT INTO t VALUES (1, 'a'), (2, 'b'), (3, 'c'), (4, 'd'), (5, 'e')")
+
+    data = i1.sql("SELECT name FROM t WHERE id IN (?) ORDER BY name", [1, 3, 5])
+    assert data == [["a"], ["c"], ["e"]]
+
+    data = i1.sql("SELECT id FROM t WHERE name IN ($1) AND id > $2 ORDER BY id", ["b", "d", "x"], 2)
+    assert data == [[4]]
+
+
+def test_percentile_within_group(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, _ = cluster.instances
+    cluster.wait_balanced()
+
+    i1.sql("CREATE TABLE t (id INT PRIMARY KEY, g INT, v DOUBLE) DISTRIBUTED BY (id)")
+    i1.sql("INSERT INTO t VALUES (1, 1, 4.0), (2, 1, 1.0), (3, 1, 3.0), (4, 1, 2.0), (5, 2, 10.0)")
+
+    # The median of 1, 2, 3 and 4 is 2.5.
+    data = i1.sql(
+        """
+        SELECT g, percentile_cont(0.5) WITHIN GROUP (ORDER BY v),
+            percentile_disc(0.5) WITHIN GROUP (ORDER BY v)
+        FROM t GROUP BY g ORDER BY g
+        """
+    )
+    assert data == [[1, 2.5, 2], [2, 10, 10]]
+
+    data = i1.sql("SELECT percentile_cont(0.25) WITHIN GROUP (ORDER BY v DESC) FROM t WHERE g = 1")
+    assert data == [[3.25]]
+
+    data = i1.sql("SELECT percentile_cont(0.5) WITHIN GROUP (ORDER BY v) FROM t WHERE id > 100")
+    assert data == [[None]]
+
+    # The interpolation is exact and the discrete percentile keeps the key type.
+    i1.sql("CREATE TABLE d (id INT PRIMARY KEY, x DECIMAL) DISTRIBUTED BY (id)")
+    i1.sql("INSERT INTO d VALUES (1, 0.1), (2, 0.2)")
+    data = i1.sql("SELECT percentile_cont(0.5) WITHIN GROUP (ORDER BY x) FROM d")
+    assert data == [[Decimal("0.15")]]
+    data = i1.sql("SELECT percentile_disc(0.5) WITHIN GROUP (ORDER BY x) FROM d")
+    assert data == [[Decimal("0.1")]]
+    data = i1.sql("SELECT percentile_disc(1) WITHIN GROUP (ORDER BY id) FROM d")
+    assert data == [[2]]
+    assert isinstance(data[0][0], int)
+
+    with pytest.raises(TarantoolError, match="percentile value 2 is not between 0 and 1"):
+        i1.sql("SELECT percentile_cont(2) WITHIN GROUP (ORDER BY v) FROM t")
+
+
+def test_delete_using(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, _ = cluster.instances
+    cluster.wait_balanced()
+
+    i1.sql("CREATE TABLE t (id INT PRIMARY KEY, a INT) DISTRIBUTED BY (id)")
+    i1.sql("CREATE TABLE s (id INT PRIMARY KEY, a INT) DISTRIBUTED BY (id)")
+    i1.sql("CREATE TABLE g (id INT PRIMARY KEY, a INT) DISTRIBUTED GLOBALLY")
+    i1.sql("INSERT INTO t VALUES (1, 10), (2, 20), (3, 30), (4, 40), (5, 50)")
+    i1.sql("INSERT INTO g VALUES (1, 10), (2, 20), (3, 30), (4, 40), (5, 50)")
+    # Rows with `a = 20` match twice, but must be deleted (and counted) once.
+    i1.sql("INSERT INTO s VALUES (1, 20), (2, 20), (3, 40), (4, 60)")
+
+    data = i1.sql("DELETE FROM t USING s WHERE t.a = s.a")
+    assert data["row_count"] == 2
+    data = i1.sql("SELECT id FROM t ORDER BY id")
+    assert data == [[1], [3], [5]]
+
+    data = i1.sql("DELETE FROM g USING s WHERE g.a = s.a AND s.id > 1")
+    assert data["row_count"] == 2
+    data = i1.sql("SELECT id FROM g ORDER BY id")
+    assert data == [[1], [3], [5]]
+
+
+def test_tablesample(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, _ = cluster.instances
+    cluster.wait_balanced()
+
+    i1.sql("CREATE TABLE t (id INT PRIMARY KEY, a INT) DISTRIBUTED BY (id)")
+    values = ", ".join(f"({i}, {i})" for i in range(1, 201))
+    i1.sql(f"INSERT INTO t VALUES {values}")
+
+    query = "SELECT id FROM t TABLESAMPLE BERNOULLI(50) REPEATABLE(7) ORDER BY id"
+    first = i1.sql(query)
+    second = i1.sql(query)
+    assert first == second
+    assert 0 < len(first) < 200
+
+    data = i1.sql("SELECT count(*) FROM t TABLESAMPLE BERNOULLI(100)")
+    assert data == [[200]]
+
+    with pytest.raises(
+        TarantoolError,
+        match=r"TABLESAMPLE percentage 150 is not in range \(0, 100\]",
+    ):
+        i1.sql("SELECT id FROM t TABLESAMPLE BERNOULLI(150)")
+
+
+def test_identity_column(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+    cluster.wait_balanced()
+
+    ddl = i1.sql(
+        """
+        CREATE TABLE t (id INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY, a TEXT, n INT)
+        DISTRIBUTED BY (id)
+        """
+    )
+    assert ddl["row_count"] == 1
+
+    for n in range(3):
+        for instance in (i1, i2):
+            data = instance.sql(
+                "INSERT INTO t (a, n) VALUES (?, ?), (?, ?)",
+                instance.name,
+                2 * n,
+                instance.name,
+                2 * n + 1,
+            )
+            assert data["row_count"] == 2
+
+    rows = i1.sql("SELECT id, a, n FROM t")
+    ids = [row[0] for row in rows]
+    assert len(ids) == 12
+    assert len(set(ids)) == 12
+
+    # Values generated by a single instance grow monotonically.
+    for instance in (i1, i2):
+        generated = sorted((n, id_) for id_, a, n in rows if a == instance.name)
+        assert len(generated) == 6
+        instance_ids = [id_ for _, id_ in generated]
+        assert instance_ids == sorted(instance_ids)
+
+    with pytest.raises(TarantoolError, match="cannot insert a non-DEFAULT value into identity column"):
+        i1.sql("INSERT INTO t (id, a, n) VALUES (100, 'x', 0)")
+
+    with pytest.raises(TarantoolError, match="it is illegal to update identity column"):
+        i1.sql("UPDATE t SET id = 100")
+
+    # The identity follows the renamed column.
+    ddl = i1.sql("ALTER TABLE t RENAME COLUMN id TO uid")
+    assert ddl["row_count"] == 1
+    assert i1.sql("INSERT INTO t (a, n) VALUES ('renamed', 12)")["row_count"] == 1
+    [[uid]] = i1.sql("SELECT uid FROM t WHERE a = 'renamed'")
+    assert uid not in ids
+    with pytest.raises(TarantoolError, match="cannot insert a non-DEFAULT value into identity column"):
+        i1.sql("INSERT INTO t (uid, a, n) VALUES (100, 'x', 0)")
+
+
+def test_check_constraint(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, _ = cluster.instances
+
+    ddl = i1.sql(
+        """
+        CREATE TABLE t (
+            id INT PRIMARY KEY,
+            qty INT CONSTRAINT qty_positive CHECK (qty > 0),
+            name TEXT CHECK (name <> '')
+        ) DISTRIBUTED BY (id)
+        """
+    )
+    assert ddl["row_count"] == 1
+
+    assert i1.sql("INSERT INTO t VALUES (1, 10, 'a'), (2, NULL, 'b')")["row_count"] == 2
+
+    with pytest.raises(TarantoolError, match='violates check constraint "qty_positive"'):
+        i1.sql("INSERT INTO t VALUES (3, 0, 'c')")
+    with pytest.raises(TarantoolError, match='violates check constraint "t_name_check"'):
+        i1.sql("INSERT INTO t VALUES (3, 1, '')")
+    with pytest.raises(TarantoolError, match='violates check constraint "qty_positive"'):
+        i1.sql("UPDATE t SET qty = -1 WHERE id = 1")
+
+    # Rejected rows are not written.
+    assert i1.sql("SELECT id, qty, name FROM t ORDER BY id") == [[1, 10, "a"], [2, None, "b"]]
+
+    # The constraint follows the renamed column.
+    ddl = i1.sql("ALTER TABLE t RENAME COLUMN qty TO amount")
+    assert ddl["row_count"] == 1
+    with pytest.raises(TarantoolError, match='violates check constraint "qty_positive"'):
+        i1.sql("INSERT INTO t VALUES (3, 0, 'c')")
+
+
 def test_identifiers_with_semicolon(instance: Instance):
     queries = [
         "CREATE TABLE t; (a INT PRIMARY KEY)",
@@ -7223,6 +8234,16 @@ def test_identifiers_with_semicolon(instance: Instance):
             instance.sql(q)
 
 
+def test_savepoints_outside_of_pgproto(instance: Instance):
+    # Statements autocommit here, so there is nothing to roll back to.
+    for q in ["SAVEPOINT s", "RELEASE SAVEPOINT s", "ROLLBACK TO SAVEPOINT s"]:
+        with pytest.raises(
+            TarantoolError,
+            match="unsupported transaction: savepoints are supported only in pgproto transaction blocks",
+        ):
+            instance.sql(q)
+
+
 def test_gl2405(cluster: Cluster):
     cluster.deploy(instance_count=1)
     i1 = cluster.instances[0]
@@ -7305,3 +8326,135 @@ def test_gl2626(cluster: Cluster):
         """
     )
     assert result == [[1]]
+
+
+def test_sql_dispatch_arrow(instance: Instance):
+    import pyarrow  # type: ignore
+    import pyarrow.ipc  # type: ignore
+
+    instance.sql("CREATE TABLE t (id INT PRIMARY KEY, name TEXT) DISTRIBUTED BY (id)")
+    instance.sql("INSERT INTO t VALUES (1, 'first'), (2, NULL), (3, 'third')")
+
+    stream = instance.call(".proc_sql_dispatch_arrow", "SELECT id, name FROM t ORDER BY id", [])
+    table = pyarrow.ipc.open_stream(stream).read_all()
+    assert table.schema == pyarrow.schema([("id", pyarrow.int64()), ("name", pyarrow.utf8())])
+    assert table.to_pydict() == {"id": [1, 2, 3], "name": ["first", None, "third"]}
+
+    stream = instance.call(
+        ".proc_sql_dispatch_arrow",
+        """
+        SELECT id > 1 AS b, id / 2.0::double AS f, NULL AS n,
+               '2024-01-02T03:04:05Z'::datetime AS ts
+        FROM t ORDER BY id
+        """,
+        [],
+    )
+    table = pyarrow.ipc.open_stream(stream).read_all()
+    assert table.schema == pyarrow.schema(
+        [
+            ("b", pyarrow.bool_()),
+            ("f", pyarrow.float64()),
+            ("n", pyarrow.null()),
+            ("ts", pyarrow.timestamp("us", tz="UTC")),
+        ]
+    )
+    ts = datetime(2024, 1, 2, 3, 4, 5, tzinfo=timezone.utc)
+    assert table.to_pydict() == {
+        "b": [False, True, True],
+        "f": [0.5, 1.0, 1.5],
+        "n": [None, None, None],
+        "ts": [ts, ts, ts],
+    }
+
+    with pytest.raises(TarantoolError, match="only results of SELECT and VALUES can be encoded as Arrow"):
+        instance.call(".proc_sql_dispatch_arrow", "INSERT INTO t VALUES (4, 'fourth')", [])
+    # The statement is rejected before being dispatched.
+    assert instance.sql("SELECT count(*) FROM t") == [[3]]
+
+    with pytest.raises(
+        TarantoolError,
+        match='unsupported column: type decimal of column "d" can\'t be encoded as Arrow',
+    ):
+        instance.call(".proc_sql_dispatch_arrow", "SELECT 1.5::decimal AS d", [])
+
+
+def test_sql_estimate_fanout(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, _ = cluster.instances
+    cluster.wait_balanced()
+
+    i1.sql("CREATE TABLE t (id INT PRIMARY KEY, name TEXT) DISTRIBUTED BY (id)")
+    [[bucket_count]] = i1.sql("SELECT bucket_count FROM _pico_tier")
+
+    fanout = i1.call(".proc_sql_estimate_fanout", "SELECT * FROM t WHERE id = ?", [1])
+    assert fanout == dict(buckets=1, storages=1)
+
+    fanout = i1.call(".proc_sql_estimate_fanout", "SELECT * FROM t", [])
+    assert fanout == dict(buckets=bucket_count, storages=2)
+
+    # Nothing is executed.
+    i1.call(".proc_sql_estimate_fanout", "INSERT INTO t VALUES (1, 'a')", [])
+    assert i1.sql("SELECT * FROM t") == []
+
+
+def test_select_into(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    i1.sql("CREATE TABLE t (id INT PRIMARY KEY, name TEXT, price DOUBLE) DISTRIBUTED BY (id)")
+    i1.sql("INSERT INTO t VALUES (1, 'a', 1.5), (2, 'b', 2.5), (3, NULL, 3.5)")
+
+    data = i1.sql("SELECT name AS key, id * 10 AS value INTO new_t PRIMARY KEY (key) FROM t WHERE name IS NOT NULL")
+    assert data["row_count"] == 2
+
+    data = i2.sql("SELECT * FROM new_t ORDER BY key", strip_metadata=False)
+    assert data["metadata"] == [
+        {"name": "key", "type": "string"},
+        {"name": "value", "type": "int"},
+    ]
+    assert data["rows"] == [["a", 10], ["b", 20]]
+
+    # The explicit primary key is also the sharding key.
+    data = i1.sql("SELECT distribution FROM _pico_table WHERE name = 'new_t'")
+    assert data == [[{"ShardedImplicitly": [["key"], "murmur3", "default"]}]]
+    data = i1.sql("INSERT INTO new_t VALUES ('c', NULL)")
+    assert data["row_count"] == 1
+    with pytest.raises(TarantoolError, match="Duplicate key exists"):
+        i1.sql("INSERT INTO new_t VALUES ('a', 30)")
+
+    # The parameters of the query fill the table too.
+    data = i1.sql("SELECT id, price + ? AS price INTO priced PRIMARY KEY (id) FROM t WHERE id > ?", 1, 1)
+    assert data["row_count"] == 2
+    data = i2.sql("SELECT * FROM priced ORDER BY id")
+    assert data == [[2, 3.5], [3, 4.5]]
+
+    # The table is dropped if it can't be filled.
+    i1.sql("INSERT INTO t VALUES (4, 'a', 4.5)")
+    with pytest.raises(TarantoolError, match="Duplicate key exists"):
+        i1.sql("SELECT name, price INTO by_name PRIMARY KEY (name) FROM t WHERE name IS NOT NULL")
+    with pytest.raises(TarantoolError, match=r"not-null|nil"):
+        i1.sql("SELECT name, price INTO by_name PRIMARY KEY (name) FROM t WHERE id > 2")
+    assert i1.sql("SELECT name FROM _pico_table WHERE name = 'by_name'") == []
+
+    with pytest.raises(TarantoolError, match="SELECT INTO requires PRIMARY KEY of the created table"):
+        i1.sql("SELECT name, price INTO by_name FROM t")
+
+    # Wide rows are filled as well.
+    i1.sql("CREATE TABLE w (id INT PRIMARY KEY)")
+    for start in range(0, 1000, 500):
+        values = ", ".join(f"({i})" for i in range(start, start + 500))
+        i1.sql(f"INSERT INTO w VALUES {values}")
+    columns = ", ".join(f"id AS c{i}" for i in range(70))
+    data = i1.sql(f"SELECT {columns} INTO wide PRIMARY KEY (c0) FROM w")
+    assert data["row_count"] == 1000
+    data = i2.sql("SELECT count(*), sum(c69) FROM wide")
+    assert data == [[1000, sum(range(1000))]]
+
+    with pytest.raises(TarantoolError, match="new_t already exists"):
+        i1.sql("SELECT id INTO new_t PRIMARY KEY (id) FROM t")
+
+    with pytest.raises(
+        TarantoolError,
+        match="INTO is allowed only in a top-level SELECT without set operations",
+    ):
+        i1.sql("SELECT id INTO other_t PRIMARY KEY (id) FROM t UNION ALL SELECT id FROM t")
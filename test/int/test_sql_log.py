@@ -1,9 +1,11 @@
 import os
+import re
 
 import pg8000.dbapi as pg  # type: ignore
 import pytest
 from conftest import (
     Instance,
+    Retriable,
     log_crawler,
 )
 
@@ -67,3 +69,95 @@ def test_sql_log(instance: Instance, protocol_type: str):
     execute_func(sql)
     with pytest.raises(AssertionError):
         lc.wait_matched(timeout=2)
+
+
+def test_sql_log_application_name(instance: Instance):
+    user = "pico_service"
+    password = "P@ssw0rd"
+    instance.sql(f"ALTER USER \"{user}\" WITH PASSWORD '{password}'")
+
+    os.environ["PGSSLMODE"] = "disable"
+    conn = pg.Connection(
+        user,
+        password=password,
+        host=instance.pg_host,
+        port=instance.pg_port,
+        application_name="billing\nreport",
+    )
+    conn.autocommit = True
+    cur = conn.cursor()
+
+    # Run ALTER SYSTEM twice to wait for parameter application.
+    cur.execute("ALTER SYSTEM SET sql_log = true")
+    cur.execute("ALTER SYSTEM SET sql_log = true")
+
+    # Control characters are replaced to keep the log line intact.
+    sql = "SELECT 1"
+    lc = log_crawler(instance, f"sql-log: {sql}, application_name: billing?report")
+    cur.execute(sql)
+    lc.wait_matched()
+
+    cur.execute("SET application_name = 'etl'")
+    sql = "SELECT 2"
+    lc = log_crawler(instance, f"sql-log: {sql}, application_name: etl")
+    cur.execute(sql)
+    lc.wait_matched()
+
+    # The value set with SET LOCAL is restored when the transaction block ends.
+    for end, n in [("COMMIT", 3), ("ROLLBACK", 5)]:
+        cur.execute("BEGIN")
+        cur.execute("SET LOCAL application_name = 'etl_batch'")
+        sql = f"SELECT {n}"
+        lc = log_crawler(instance, f"sql-log: {sql}, application_name: etl_batch")
+        cur.execute(sql)
+        lc.wait_matched()
+        cur.execute(end)
+
+        sql = f"SELECT {n + 1}"
+        lc = log_crawler(instance, f"sql-log: {sql}, application_name: etl")
+        cur.execute(sql)
+        lc.wait_matched()
+
+    # Outside of a transaction block SET LOCAL has no effect.
+    cur.execute("SET LOCAL application_name = 'etl_batch'")
+    sql = "SELECT 7"
+    lc = log_crawler(instance, f"sql-log: {sql}, application_name: etl")
+    cur.execute(sql)
+    lc.wait_matched()
+
+    cur.execute("ALTER SYSTEM SET sql_log = false")
+
+
+def test_sql_log_query_id(instance: Instance):
+    query_ids: dict[str, str] = {}
+
+    def collect_query_id(line: bytes):
+        match = re.search(r"sql-log: (.*), query_id: ([0-9a-f]{16})", line.decode("utf-8"))
+        if match:
+            query_ids[match.group(1)] = match.group(2)
+
+    instance.on_output_line(collect_query_id)
+
+    # Run ALTER SYSTEM twice to wait for parameter application.
+    instance.sql("ALTER SYSTEM SET sql_log = true")
+    instance.sql("ALTER SYSTEM SET sql_log = true")
+
+    instance.sql("CREATE TABLE t (id INT PRIMARY KEY, value TEXT)")
+    queries = [
+        "SELECT value FROM t WHERE id = 1",
+        "select value  from t where id = 2",
+        "SELECT id FROM t WHERE value = 'x'",
+    ]
+    for sql in queries:
+        instance.sql(sql)
+
+    def all_logged():
+        assert all(sql in query_ids for sql in queries)
+
+    Retriable(timeout=10, rps=4).call(all_logged)
+
+    # The same query shape with different constants shares the query id.
+    assert query_ids[queries[0]] == query_ids[queries[1]]
+    assert query_ids[queries[0]] != query_ids[queries[2]]
+
+    instance.sql("ALTER SYSTEM SET sql_log = false")
@@ -0,0 +1,59 @@
+import pytest
+import psycopg
+from conftest import Postgres
+
+
+def connect(postgres: Postgres) -> psycopg.Connection:
+    user = "admin"
+    password = "P@ssw0rd"
+    postgres.instance.sql(f"ALTER USER \"{user}\" WITH PASSWORD '{password}'")
+    conn = psycopg.connect(
+        f"user={user} password={password} host={postgres.host} port={postgres.port} sslmode=disable"
+    )
+    conn.autocommit = True
+    return conn
+
+
+def copy_out(conn: psycopg.Connection, sql: str) -> tuple[bytes, str | None]:
+    cur = conn.cursor()
+    with cur.copy(sql) as copy:
+        data = b"".join(copy)
+    return data, cur.statusmessage
+
+
+def test_copy_to_stdout(postgres: Postgres):
+    conn = connect(postgres)
+    conn.execute("CREATE TABLE t (id INT PRIMARY KEY, name TEXT)")
+    conn.execute("INSERT INTO t VALUES (1, 'plain'), (2, 'with, comma'), (3, ''), (4, NULL), (5, 'say \"hi\"')")
+
+    data, status = copy_out(conn, "COPY (SELECT id, name FROM t ORDER BY id) TO STDOUT WITH CSV HEADER")
+    assert data == b'id,name\n1,plain\n2,"with, comma"\n3,""\n4,\n5,"say ""hi"""\n'
+    assert status == "COPY 5"
+
+    data, _ = copy_out(conn, "COPY (SELECT id, name FROM t WHERE id IN (1, 4) ORDER BY id) TO STDOUT")
+    assert data == b"1\tplain\n4\t\\N\n"
+
+    data, _ = copy_out(conn, "COPY (SELECT id FROM t WHERE id > 100) TO STDOUT (FORMAT csv, HEADER true)")
+    assert data == b"id\n"
+
+    # The query is checked before execution, so nothing is modified.
+    with pytest.raises(psycopg.errors.FeatureNotSupported, match="COPY supports only SELECT queries"):
+        copy_out(conn, "COPY (DELETE FROM t) TO STDOUT")
+    assert conn.execute("SELECT count(*) FROM t").fetchone() == (5,)
+
+    with pytest.raises(psycopg.errors.FeatureNotSupported, match="binary format"):
+        copy_out(conn, "COPY (SELECT id FROM t) TO STDOUT (FORMAT binary)")
+
+    # An error of the query aborts the copy.
+    with pytest.raises(psycopg.Error, match="not found"):
+        copy_out(conn, "COPY (SELECT id FROM missing) TO STDOUT")
+    assert conn.execute("SELECT 1").fetchone() == (1,)
+
+    # Rows are sent in several batches.
+    conn.execute("CREATE TABLE big (id INT PRIMARY KEY)")
+    for start in range(0, 2500, 500):
+        values = ", ".join(f"({i})" for i in range(start, start + 500))
+        conn.execute(f"INSERT INTO big VALUES {values}")
+    data, status = copy_out(conn, "COPY (SELECT id FROM big ORDER BY id) TO STDOUT")
+    assert data == b"".join(f"{i}\n".encode() for i in range(2500))
+    assert status == "COPY 2500"
@@ -1,4 +1,4 @@
-from conftest import PgStorage, PgClient, ReturnError
+from conftest import PgStorage, PgClient, ReturnError, TarantoolError
 import pytest
 
 
@@ -429,3 +429,68 @@ def test_interactive_portals(pg_client: PgClient):
     assert ["""    sql_motion_row_max = 5000"""] == data["rows"][3]
     assert ["""buckets = [1-3000]"""] == data["rows"][4]
     assert data["is_finished"] is True
+
+
+def test_where_current_of(pg_client: PgClient):
+    instance = pg_client.instance
+    instance.sql(
+        """
+        create table "t" ("key" int not null, "value" string not null, primary key ("key"))
+        using memtx
+        distributed by ("key")
+        option (timeout = 3)
+    """
+    )
+    instance.sql(""" insert into "t" values (1, 'kek'), (2, 'lol'), (3, 'foo') """)
+
+    pg_client.parse("cursor", """ select * from "t" where "key" > 1 """)
+    pg_client.bind("cursor", "cur", [], [])
+    pg_client.parse("update", """ update "t" set "value" = $1 where current of cur """)
+    pg_client.parse("delete", """ delete from "t" where current of cur """)
+
+    # The cursor is not positioned on a row until the first fetch.
+    with pytest.raises(ReturnError, match='cursor "cur" is not positioned on a row'):
+        pg_client.bind("update", "", ["new"], [])
+
+    data = pg_client.execute("cur", 1)
+    [[key, _]] = data["rows"]
+    pg_client.bind("update", "", ["new"], [])
+    assert pg_client.execute("")["row_count"] == 1
+    assert instance.sql(""" select "key" from "t" where "value" = 'new' """) == [[key]]
+
+    data = pg_client.execute("cur", 1)
+    [[key, _]] = data["rows"]
+    pg_client.bind("delete", "", [], [])
+    assert pg_client.execute("")["row_count"] == 1
+    assert instance.sql(""" select count(*) from "t" where "key" = ? """, key) == [[0]]
+    assert instance.sql(""" select count(*) from "t" """) == [[2]]
+
+    # A cursor that has returned all its rows is positioned after the last one.
+    pg_client.bind("cursor", "all", [], [])
+    pg_client.execute("all", -1)
+    pg_client.parse("update_all", """ update "t" set "value" = 'x' where current of "all" """)
+    with pytest.raises(ReturnError, match='cursor "all" is not positioned on a row'):
+        pg_client.bind("update_all", "", [], [])
+
+    pg_client.parse("count", """ select count(*) from "t" """)
+    pg_client.bind("count", "cnt", [], [])
+    pg_client.execute("cnt", 1)
+    pg_client.parse("update_cnt", """ update "t" set "value" = 'x' where current of cnt """)
+    with pytest.raises(ReturnError, match='cursor "cnt" is not a simply updatable scan of table "t"'):
+        pg_client.bind("update_cnt", "", [], [])
+
+    # The key must be returned as is, not computed or renamed from another column.
+    pg_client.parse("renamed", """ select "key" + 1 as "key", "value" from "t" """)
+    pg_client.bind("renamed", "ren", [], [])
+    pg_client.execute("ren", 1)
+    pg_client.parse("update_ren", """ update "t" set "value" = 'x' where current of ren """)
+    with pytest.raises(ReturnError, match='cursor "ren" does not return key column "key" of table "t"'):
+        pg_client.bind("update_ren", "", [], [])
+
+    pg_client.parse("update_missing", """ update "t" set "value" = 'x' where current of missing """)
+    with pytest.raises(ReturnError, match='cursor "missing" does not exist'):
+        pg_client.bind("update_missing", "", [], [])
+
+    # Outside of the PostgreSQL protocol there are no cursors.
+    with pytest.raises(TarantoolError, match="requires a cursor of a PostgreSQL protocol session"):
+        instance.sql(""" update "t" set "value" = 'x' where current of cur """)
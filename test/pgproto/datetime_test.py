@@ -146,6 +146,50 @@ def test_timezones(postgres: Postgres):
     assert result == [(dt_utc_minus_8,)]
 
 
+def test_timestamp_literals(postgres: Postgres):
+    conn = setup_psycopg_test_env(postgres)
+    cur = conn.cursor()
+
+    # Storages have no timestamps without time zone.
+    with pytest.raises(psycopg.Error, match="timestamp columns, use datetime instead"):
+        cur.execute("CREATE TABLE ts_t (ts TIMESTAMP PRIMARY KEY, id INT) DISTRIBUTED BY (ts)")
+
+    cur.execute("CREATE TABLE ts_t (ts TIMESTAMPTZ PRIMARY KEY, id INT) DISTRIBUTED BY (ts)")
+    cur.execute(
+        """
+        INSERT INTO ts_t VALUES
+            ('2024-01-01 00:00:00', 1),
+            ('2024-01-01T05:30:00.25+05:30', 2),
+            ('2023-12-31 23:59:59.999999 -00:30', 3)
+        """
+    )
+
+    # Datetime columns are reported as timestamptz.
+    cur.execute("SELECT ts, id FROM ts_t WHERE ts >= '2024-01-01 00:00:00' ORDER BY ts")
+    assert [column.type_code for column in cur.description] == [1184, 20]
+    utc = datetime.timezone.utc
+    assert [(ts.astimezone(utc), row_id) for ts, row_id in cur.fetchall()] == [
+        (datetime.datetime(2024, 1, 1, tzinfo=utc), 1),
+        (datetime.datetime(2024, 1, 1, 0, 0, 0, 250000, tzinfo=utc), 2),
+        (datetime.datetime(2024, 1, 1, 0, 29, 59, 999999, tzinfo=utc), 3),
+    ]
+
+    # The same instant in another time zone is found in the same bucket.
+    dt = datetime.datetime(2024, 1, 1, 3, 0, tzinfo=datetime.timezone(datetime.timedelta(hours=3)))
+    cur.execute("SELECT id FROM ts_t WHERE ts = %t", (dt,))
+    assert cur.fetchall() == [(1,)]
+    cur.execute("SELECT id FROM ts_t WHERE ts = '2023-12-31 23:00:00.25 -01'")
+    assert cur.fetchall() == [(2,)]
+
+    # Dates and timestamps without time zone are datetimes in UTC.
+    cur.execute("SELECT ts::date, ts::timestamp, ts::time FROM ts_t WHERE id = 3")
+    assert [column.type_code for column in cur.description] == [1184, 1184, 25]
+    day, ts, time_of_day = cur.fetchone()
+    assert day.astimezone(utc) == datetime.datetime(2024, 1, 1, tzinfo=utc)
+    assert ts.astimezone(utc) == datetime.datetime(2024, 1, 1, 0, 29, 59, 999999, tzinfo=utc)
+    assert time_of_day == "00:29:59.999999"
+
+
 def test_subseconds(postgres: Postgres):
     conn = setup_psycopg_test_env(postgres)
     cur = conn.cursor()
@@ -327,6 +371,38 @@ def test_localtime_not_cached(postgres: Postgres):
     assert result1 != result2
 
 
+def test_statement_time(postgres: Postgres):
+    conn = setup_psycopg_test_env(postgres)
+    cur = conn.cursor()
+
+    # `now()` is a synonym for `current_timestamp`
+    cur.execute("SELECT now() = current_timestamp, now() = now();")
+    assert cur.fetchall() == [(True, True)]
+
+    # The time is evaluated once on the router, so every bucket gets the same value.
+    cur.execute("CREATE TABLE stamps (id INT PRIMARY KEY, ts DATETIME) DISTRIBUTED BY (id);")
+    cur.execute("INSERT INTO stamps (id) VALUES (1), (2), (3), (4), (5);")
+    cur.execute("SELECT count(DISTINCT bucket_id) FROM stamps;")
+    assert cur.fetchall()[0][0] > 1
+    cur.execute("UPDATE stamps SET ts = now();")
+    cur.execute("SELECT count(DISTINCT ts), count(*) FROM stamps;")
+    assert cur.fetchall() == [(1, 5)]
+
+    # Inside a transaction block the time is the start time of the transaction.
+    cur.execute("BEGIN;")
+    cur.execute("SELECT current_timestamp;")
+    started = cur.fetchall()[0][0]
+    time.sleep(1)
+    cur.execute("SELECT now(), current_date;")
+    [(now, today)] = cur.fetchall()
+    assert now == started
+    assert today.date() == started.date()
+    cur.execute("COMMIT;")
+
+    cur.execute("SELECT now();")
+    assert cur.fetchall()[0][0] > started
+
+
 def test_current_date(postgres: Postgres):
     conn = setup_psycopg_test_env(postgres)
     cur = conn.cursor()
@@ -281,18 +281,18 @@ def test_tcl(postgres: Postgres):
     cur = conn.execute("CREATE TABLE test_table (id INT PRIMARY KEY, name TEXT);")
 
     cur = conn.execute("INSERT INTO test_table (id, name) VALUES (1,'Alice'), (2,'Bob');")
+    assert cur.rowcount == 2
 
+    # Modifications are applied on COMMIT, so they are not visible yet.
     cur = conn.execute("SELECT * FROM test_table;")
-    rows = cur.fetchall()
-    assert rows == [(1, "Alice"), (2, "Bob")]
+    assert cur.fetchall() == []
 
     cur = conn.execute("ROLLBACK;", prepare=True)
     assert cur.pgresult is not None
     assert cur.pgresult.status == ExecStatus.COMMAND_OK
 
     cur = conn.execute("SELECT * FROM test_table;")
-    rows = cur.fetchall()
-    assert rows == [(1, "Alice"), (2, "Bob")]
+    assert cur.fetchall() == []
 
     cur = conn.execute("DROP TABLE test_table;")
 
@@ -367,6 +367,8 @@ def test_wrong_number_of_params(postgres: Postgres):
                 match="bind message supplies 0 parameters, but prepared statement .* requires 1",
             ):
                 cur.execute("SELECT $1", [], prepare=True)
+            # The error aborts the transaction block opened by psycopg.
+            conn.rollback()
 
             # insufficient params
             with pytest.raises(
@@ -374,6 +376,7 @@ def test_wrong_number_of_params(postgres: Postgres):
                 match="bind message supplies 2 parameters, but prepared statement .* requires 4",
             ):
                 cur.execute("SELECT $1, $2, $3, $4", ["Hello", "4"], prepare=True)
+            conn.rollback()
 
             # excessive params
             with pytest.raises(
@@ -381,6 +384,7 @@ def test_wrong_number_of_params(postgres: Postgres):
                 match="bind message supplies 1 parameters, but prepared statement .* requires 0",
             ):
                 cur.execute("SELECT 1", ["hello"], prepare=True)
+            conn.rollback()
 
             # excessive params
             with pytest.raises(
@@ -388,6 +392,7 @@ def test_wrong_number_of_params(postgres: Postgres):
                 match="bind message supplies 3 parameters, but prepared statement .* requires 2",
             ):
                 cur.execute("SELECT $1, $2", [2, 1, 4], prepare=True)
+            conn.rollback()
 
 
 def test_large_literals_cant_fit_into_target_type(postgres: Postgres):
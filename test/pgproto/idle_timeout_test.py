@@ -0,0 +1,86 @@
+import time
+import pytest
+import psycopg
+from conftest import Postgres
+from psycopg.pq import TransactionStatus
+
+
+def setup_idle_timeout(postgres: Postgres, parameters: str):
+    i1 = postgres.instance
+    i1.env["PICODATA_CONFIG_PARAMETERS"] = parameters
+    i1.restart()
+    i1.wait_online()
+
+
+def connect(postgres: Postgres) -> psycopg.Connection:
+    user = "admin"
+    password = "P@ssw0rd"
+    conn = psycopg.connect(
+        f"user={user} password={password} host={postgres.host} port={postgres.port} sslmode=disable"
+    )
+    conn.autocommit = True
+    return conn
+
+
+def test_idle_timeout(postgres: Postgres):
+    postgres.instance.sql("ALTER USER \"admin\" WITH PASSWORD 'P@ssw0rd'")
+    setup_idle_timeout(postgres, "instance.pg.idle_timeout=1")
+
+    conn = connect(postgres)
+    conn.execute("CREATE TABLE t (id INT PRIMARY KEY);")
+
+    # An idle connection is closed after the timeout.
+    time.sleep(2)
+    with pytest.raises(psycopg.OperationalError):
+        conn.execute("SELECT * FROM t;")
+    assert conn.closed
+
+    # An open transaction of an idle connection is rolled back.
+    conn = connect(postgres)
+    conn.execute("BEGIN;", prepare=False)
+    conn.execute("INSERT INTO t VALUES (1);")
+    assert conn.info.transaction_status == TransactionStatus.INTRANS
+    time.sleep(2)
+    with pytest.raises(psycopg.Error):
+        conn.execute("COMMIT;", prepare=False)
+    assert conn.closed
+
+    conn = connect(postgres)
+    assert conn.execute("SELECT * FROM t;").fetchall() == []
+
+    # A connection that keeps sending queries is not closed.
+    for _ in range(3):
+        time.sleep(0.5)
+        assert conn.execute("SELECT * FROM t;").fetchall() == []
+    conn.close()
+
+
+def test_idle_timeout_exempts_transactions(postgres: Postgres):
+    postgres.instance.sql("ALTER USER \"admin\" WITH PASSWORD 'P@ssw0rd'")
+    setup_idle_timeout(
+        postgres,
+        "instance.pg.idle_timeout=1;instance.pg.idle_timeout_in_transaction=false",
+    )
+
+    conn = connect(postgres)
+    conn.execute("CREATE TABLE t (id INT PRIMARY KEY);")
+    conn.execute("BEGIN;", prepare=False)
+    conn.execute("INSERT INTO t VALUES (1);")
+    time.sleep(2)
+    conn.execute("COMMIT;", prepare=False)
+    assert conn.execute("SELECT * FROM t;").fetchall() == [(1,)]
+
+    # Outside of a transaction the timeout still applies.
+    time.sleep(2)
+    with pytest.raises(psycopg.OperationalError):
+        conn.execute("SELECT * FROM t;")
+    assert conn.closed
+
+
+def test_tcp_keepalive(postgres: Postgres):
+    postgres.instance.sql("ALTER USER \"admin\" WITH PASSWORD 'P@ssw0rd'")
+    setup_idle_timeout(postgres, "instance.pg.tcp_keepalive=60")
+
+    conn = connect(postgres)
+    assert conn.execute("SELECT 1;").fetchall() == [(1,)]
+    conn.close()
@@ -0,0 +1,84 @@
+import time
+
+import pytest
+import psycopg
+from conftest import Postgres
+
+
+def connect(postgres: Postgres) -> psycopg.Connection:
+    user = "admin"
+    password = "P@ssw0rd"
+    conn = psycopg.connect(
+        f"user={user} password={password} host={postgres.host} port={postgres.port} sslmode=disable"
+    )
+    conn.autocommit = True
+    return conn
+
+
+def receive(conn: psycopg.Connection, count: int, timeout: float = 5) -> list[tuple[str, str]]:
+    notifies = conn.notifies(timeout=timeout, stop_after=count)
+    return [(n.channel, n.payload) for n in notifies]
+
+
+def test_listen_notify(postgres: Postgres):
+    postgres.instance.sql("ALTER USER \"admin\" WITH PASSWORD 'P@ssw0rd'")
+
+    listener = connect(postgres)
+    notifier = connect(postgres)
+
+    cur = listener.execute("LISTEN events")
+    assert cur.statusmessage == "LISTEN"
+
+    cur = notifier.execute("NOTIFY events, 'hello'")
+    assert cur.statusmessage == "NOTIFY"
+    notifier.execute("NOTIFY other, 'not listened'")
+    notifier.execute("NOTIFY events")
+    assert receive(listener, 2) == [("events", "hello"), ("events", "")]
+
+    # A notification sent from another instance of the cluster is delivered as well.
+    i2 = postgres.cluster.add_instance(wait_online=True)
+    i2.sql("NOTIFY events, 'from i2'")
+    assert receive(listener, 1) == [("events", "from i2")]
+
+    # Notifications of a transaction block are delivered on COMMIT,
+    # the duplicates within the block are sent once.
+    notifier.execute("BEGIN")
+    notifier.execute("NOTIFY events, 'first'")
+    notifier.execute("NOTIFY events, 'second'")
+    notifier.execute("NOTIFY events, 'first'")
+    assert receive(listener, 1, timeout=0.5) == []
+    notifier.execute("COMMIT")
+    assert receive(listener, 2) == [("events", "first"), ("events", "second")]
+
+    # ROLLBACK discards them, as does ROLLBACK TO SAVEPOINT.
+    notifier.execute("BEGIN")
+    notifier.execute("NOTIFY events, 'rolled back'")
+    notifier.execute("ROLLBACK")
+    notifier.execute("BEGIN")
+    notifier.execute("NOTIFY events, 'kept'")
+    notifier.execute("SAVEPOINT s")
+    notifier.execute("NOTIFY events, 'rolled back'")
+    notifier.execute("ROLLBACK TO SAVEPOINT s")
+    notifier.execute("COMMIT")
+    assert receive(listener, 2, timeout=1) == [("events", "kept")]
+
+    # Notifications are queued while the listener is busy and sent
+    # along with the response to its next query.
+    listener.execute("BEGIN")
+    notifier.execute("NOTIFY events, 'queued'")
+    # The delivery is asynchronous, give it time to reach the listener.
+    time.sleep(0.5)
+    received = []
+    listener.add_notify_handler(lambda n: received.append((n.channel, n.payload)))
+    listener.execute("SELECT 1")
+    assert received == []
+    listener.execute("COMMIT")
+    assert received == [("events", "queued")]
+
+    cur = listener.execute("UNLISTEN *")
+    assert cur.statusmessage == "UNLISTEN"
+    notifier.execute("NOTIFY events, 'missed'")
+    assert receive(listener, 1) == []
+
+    with pytest.raises(psycopg.Error, match="notification payload must be shorter than 8000 bytes"):
+        notifier.execute(f"NOTIFY events, '{'x' * 8000}'")
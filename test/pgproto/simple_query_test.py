@@ -1,11 +1,12 @@
 import pytest
 import pg8000.dbapi as pg  # type: ignore
 import os
-from conftest import log_crawler
+import time
+from concurrent.futures import ThreadPoolExecutor
 from conftest import Postgres
 from decimal import Decimal
 import psycopg
-from psycopg.pq import ExecStatus
+from psycopg.pq import ExecStatus, TransactionStatus
 import pg8000.native as pg2  # type: ignore
 from pg8000.exceptions import DatabaseError  # type: ignore
 
@@ -320,14 +321,68 @@ def test_deallocate(postgres: Postgres):
     conn.close()
 
 
-def test_tcl(postgres: Postgres):
+def test_list_prepared_statements(postgres: Postgres):
     user = "admin"
     password = "P@ssw0rd"
     host = postgres.host
     port = postgres.port
     postgres.instance.sql(f"ALTER USER \"{user}\" WITH PASSWORD '{password}'")
 
-    prefix = "Transactions are currently unsupported. Empty query response provided for"
+    conn = psycopg.connect(f"user = {user} password={password} host={host} port={port} sslmode=disable")
+    conn.autocommit = True
+    # Use the low level connection to control the protocol messages.
+    pgconn = conn.pgconn
+
+    def list_statements():
+        res = pgconn.exec_(b"SELECT name, statement FROM pg_catalog.pg_prepared_statements")
+        assert res.status == ExecStatus.TUPLES_OK
+        assert [res.fname(i) for i in range(res.nfields)] == [b"name", b"statement"]
+        return [[res.get_value(i, j).decode() for j in range(2)] for i in range(res.ntuples)]
+
+    assert list_statements() == []
+
+    assert pgconn.prepare(b"s1", b"SELECT 1").status == ExecStatus.COMMAND_OK
+    assert pgconn.prepare(b"s2", b"SELECT 2").status == ExecStatus.COMMAND_OK
+    # The unnamed statement isn't listed, like in Postgres.
+    assert pgconn.prepare(b"", b"SELECT 3").status == ExecStatus.COMMAND_OK
+    assert list_statements() == [["s1", "SELECT 1"], ["s2", "SELECT 2"]]
+
+    # The projection is honored.
+    res = pgconn.exec_(b"SELECT statement FROM pg_prepared_statements")
+    assert res.status == ExecStatus.TUPLES_OK
+    assert [res.fname(i) for i in range(res.nfields)] == [b"statement"]
+    assert [res.get_value(i, 0) for i in range(res.ntuples)] == [b"SELECT 1", b"SELECT 2"]
+
+    # Other query shapes aren't recognized rather than answered with the whole view.
+    res = pgconn.exec_(b"SELECT name FROM pg_prepared_statements WHERE name = 's1'")
+    assert res.status == ExecStatus.FATAL_ERROR
+    assert b"pg_prepared_statements" in res.error_message
+
+    # Statements of the other clients aren't visible.
+    other = psycopg.connect(f"user = {user} password={password} host={host} port={port} sslmode=disable")
+    res = other.pgconn.exec_(b"SELECT * FROM pg_prepared_statements")
+    assert res.ntuples == 0
+    other.close()
+
+    assert pgconn.exec_(b"DEALLOCATE s1").status == ExecStatus.COMMAND_OK
+    assert list_statements() == [["s2", "SELECT 2"]]
+
+    res = pgconn.exec_(b"DEALLOCATE s1")
+    assert res.status == ExecStatus.FATAL_ERROR
+    assert b"prepared statement s1 does not exist" in res.error_message
+
+    assert pgconn.exec_(b"DEALLOCATE ALL").status == ExecStatus.COMMAND_OK
+    assert list_statements() == []
+
+    conn.close()
+
+
+def test_tcl(postgres: Postgres):
+    user = "admin"
+    password = "P@ssw0rd"
+    host = postgres.host
+    port = postgres.port
+    postgres.instance.sql(f"ALTER USER \"{user}\" WITH PASSWORD '{password}'")
 
     conn = psycopg.connect(f"user={user} password={password} host={host} port={port} sslmode=disable")
     # With autocommit
@@ -346,32 +401,29 @@ def test_tcl(postgres: Postgres):
     # Without autocommit
     conn.autocommit = False
 
-    message = f"{prefix} Begin"
-    crawler = log_crawler(postgres.instance, message)
     cur = conn.execute("BEGIN;", prepare=False)
-    crawler.wait_matched()
     assert cur.pgresult is not None
     assert cur.pgresult.status == ExecStatus.COMMAND_OK
+    assert cur.statusmessage == "BEGIN"
 
     cur = conn.execute("CREATE TABLE test_table (id INT PRIMARY KEY, name TEXT);")
 
     cur = conn.execute("INSERT INTO test_table (id, name) VALUES (1,'Alice'), (2,'Bob');")
+    assert cur.rowcount == 2
 
+    # Modifications are applied on COMMIT, so they are not visible yet.
     cur = conn.execute("SELECT * FROM test_table;")
-    rows = cur.fetchall()
-    assert sorted(rows, key=lambda x: x[0]) == [(1, "Alice"), (2, "Bob")]
+    assert cur.fetchall() == []
 
-    message = f"{prefix} Rollback"
-    crawler = log_crawler(postgres.instance, message)
     cur = conn.execute("ROLLBACK;", prepare=False)
-    crawler.wait_matched()
     assert cur.pgresult is not None
     assert cur.pgresult.status == ExecStatus.COMMAND_OK
+    assert cur.statusmessage == "ROLLBACK"
 
-    # ROLLBACK does nothing, the table is still created and is populated
+    # ROLLBACK discards the modifications, but DDL is applied right away,
+    # so the table is still created.
     cur = conn.execute("SELECT * FROM test_table;")
-    rows = cur.fetchall()
-    assert sorted(rows, key=lambda x: x[0]) == [(1, "Alice"), (2, "Bob")]
+    assert cur.fetchall() == []
 
     cur = conn.execute("DROP TABLE test_table;")
 
@@ -383,12 +435,10 @@ def test_tcl(postgres: Postgres):
 
     cur = conn.execute("INSERT INTO test_table (id, name) VALUES (1,'Alice'), (2,'Bob');")
 
-    message = f"{prefix} Commit"
-    crawler = log_crawler(postgres.instance, message)
     cur = conn.execute("COMMIT;", prepare=False)
-    crawler.wait_matched()
     assert cur.pgresult is not None
     assert cur.pgresult.status == ExecStatus.COMMAND_OK
+    assert cur.statusmessage == "COMMIT"
 
     cur = conn.execute("SELECT * FROM test_table;")
     rows = cur.fetchall()
@@ -416,6 +466,181 @@ def test_tcl(postgres: Postgres):
     cur = conn.execute("DROP TABLE test_table;")
 
 
+def test_transaction_block(postgres: Postgres):
+    user = "admin"
+    password = "P@ssw0rd"
+    host = postgres.host
+    port = postgres.port
+    postgres.instance.sql(f"ALTER USER \"{user}\" WITH PASSWORD '{password}'")
+
+    conn = psycopg.connect(f"user={user} password={password} host={host} port={port} sslmode=disable")
+    conn.autocommit = True
+    conn.execute("CREATE TABLE t (id INT PRIMARY KEY, name TEXT);")
+    assert conn.info.transaction_status == TransactionStatus.IDLE
+
+    # Statements sent in separate messages share the transaction.
+    conn.execute("BEGIN;", prepare=False)
+    assert conn.info.transaction_status == TransactionStatus.INTRANS
+    assert conn.execute("INSERT INTO t VALUES (1, 'Alice');").rowcount == 1
+    assert conn.execute("INSERT INTO t VALUES (2, 'Bob');").rowcount == 1
+    conn.execute("ROLLBACK;", prepare=False)
+    assert conn.info.transaction_status == TransactionStatus.IDLE
+    assert conn.execute("SELECT * FROM t;").fetchall() == []
+
+    conn.execute("BEGIN;", prepare=False)
+    conn.execute("INSERT INTO t VALUES (1, 'Alice');")
+    conn.execute("SAVEPOINT s;", prepare=False)
+    conn.execute("INSERT INTO t VALUES (2, 'Bob');")
+    conn.execute("ROLLBACK TO SAVEPOINT s;", prepare=False)
+    conn.execute("COMMIT;", prepare=False)
+    assert conn.execute("SELECT * FROM t;").fetchall() == [(1, "Alice")]
+
+    # Modifications are checked right away and report the real row count.
+    conn.execute("BEGIN;", prepare=False)
+    assert conn.execute("UPDATE t SET name = 'Alicia' WHERE id = 1;").rowcount == 1
+    assert conn.execute("DELETE FROM t WHERE id = 2;").rowcount == 0
+    with pytest.raises(psycopg.InternalError, match="Duplicate key"):
+        conn.execute("INSERT INTO t VALUES (1, 'Alice');")
+    conn.execute("ROLLBACK;", prepare=False)
+    assert conn.execute("SELECT * FROM t;").fetchall() == [(1, "Alice")]
+
+    # Conflicts between the commands of the block are reported on COMMIT.
+    conn.execute("BEGIN;", prepare=False)
+    assert conn.execute("INSERT INTO t VALUES (3, 'Carol');").rowcount == 1
+    assert conn.execute("INSERT INTO t VALUES (3, 'Carol');").rowcount == 1
+    with pytest.raises(psycopg.Error, match="Duplicate key"):
+        conn.execute("COMMIT;", prepare=False)
+    assert conn.execute("SELECT * FROM t;").fetchall() == [(1, "Alice")]
+
+    # Global tables are modified through raft, which can't be rolled back.
+    conn.execute("CREATE TABLE g (id INT PRIMARY KEY) DISTRIBUTED GLOBALLY;")
+    conn.execute("BEGIN;", prepare=False)
+    with pytest.raises(
+        psycopg.errors.FeatureNotSupported,
+        match="modification of global tables in a transaction block",
+    ):
+        conn.execute("INSERT INTO g VALUES (1);")
+    conn.execute("ROLLBACK;", prepare=False)
+    assert conn.execute("SELECT * FROM g;").fetchall() == []
+
+    # Values set with SET LOCAL last until the end of the transaction block.
+    conn.execute("BEGIN;", prepare=False)
+    conn.execute("SET LOCAL application_name = 'etl';")
+    conn.execute("SET LOCAL plan_cache = false;")
+    conn.execute("ROLLBACK;", prepare=False)
+
+    # After an error only the end of the transaction block is accepted.
+    conn.execute("BEGIN;", prepare=False)
+    conn.execute("INSERT INTO t VALUES (2, 'Bob');")
+    with pytest.raises(psycopg.Error):
+        conn.execute("SELECT * FROM no_such_table;")
+    assert conn.info.transaction_status == TransactionStatus.INERROR
+    with pytest.raises(
+        psycopg.errors.InFailedSqlTransaction,
+        match="current transaction is aborted, commands ignored until end of transaction block",
+    ):
+        conn.execute("SELECT * FROM t;")
+    cur = conn.execute("COMMIT;", prepare=False)
+    assert cur.statusmessage == "ROLLBACK"
+    assert conn.info.transaction_status == TransactionStatus.IDLE
+    assert conn.execute("SELECT * FROM t;").fetchall() == [(1, "Alice")]
+
+
+def test_transaction_isolation_level(postgres: Postgres):
+    user = "admin"
+    password = "P@ssw0rd"
+    host = postgres.host
+    port = postgres.port
+    postgres.instance.sql(f"ALTER USER \"{user}\" WITH PASSWORD '{password}'")
+
+    conn = psycopg.connect(f"user={user} password={password} host={host} port={port} sslmode=disable")
+    conn.autocommit = True
+
+    conn.execute("BEGIN;", prepare=False)
+    cur = conn.execute("SET TRANSACTION ISOLATION LEVEL READ COMMITTED;", prepare=False)
+    assert cur.pgresult is not None
+    assert cur.pgresult.status == ExecStatus.COMMAND_OK
+    assert cur.statusmessage == "SET"
+    conn.execute("SELECT 1;")
+    # Like in Postgres, the level can't be changed after the first query.
+    with pytest.raises(
+        psycopg.errors.ActiveSqlTransaction,
+        match="SET TRANSACTION ISOLATION LEVEL must be called before any query",
+    ):
+        conn.execute("SET TRANSACTION ISOLATION LEVEL READ COMMITTED;", prepare=False)
+    conn.execute("COMMIT;", prepare=False)
+
+    # Levels the storage can't provide are rejected instead of being downgraded.
+    for level in ["SERIALIZABLE", "REPEATABLE READ"]:
+        conn.execute("BEGIN;", prepare=False)
+        with pytest.raises(
+            psycopg.errors.FeatureNotSupported,
+            match=f"isolation level {level}, only READ COMMITTED is available",
+        ):
+            conn.execute(f"SET TRANSACTION ISOLATION LEVEL {level};", prepare=False)
+        conn.execute("ROLLBACK;", prepare=False)
+
+
+def test_select_for_update(postgres: Postgres):
+    user = "admin"
+    password = "P@ssw0rd"
+    host = postgres.host
+    port = postgres.port
+    postgres.instance.sql(f"ALTER USER \"{user}\" WITH PASSWORD '{password}'")
+
+    dsn = f"user={user} password={password} host={host} port={port} sslmode=disable"
+    first = psycopg.connect(dsn, autocommit=True)
+    second = psycopg.connect(dsn, autocommit=True)
+    first.execute("CREATE TABLE t (id INT PRIMARY KEY, name TEXT);")
+    first.execute("INSERT INTO t VALUES (1, 'Alice'), (2, 'Bob');")
+
+    # Outside of a transaction block the rows are not kept locked.
+    assert first.execute("SELECT name FROM t WHERE id = 1 FOR UPDATE;").fetchall() == [("Alice",)]
+
+    # Rows locked FOR SHARE can be locked FOR SHARE by another transaction.
+    first.execute("BEGIN;", prepare=False)
+    second.execute("BEGIN;", prepare=False)
+    assert first.execute("SELECT name FROM t WHERE id = 1 FOR SHARE;").fetchall() == [("Alice",)]
+    assert second.execute("SELECT name FROM t WHERE id = 1 FOR SHARE;").fetchall() == [("Alice",)]
+    first.execute("COMMIT;", prepare=False)
+    second.execute("COMMIT;", prepare=False)
+
+    # A row locked FOR UPDATE can't be locked by another transaction until the first one commits.
+    first.execute("BEGIN;", prepare=False)
+    assert first.execute("SELECT name FROM t WHERE id = 1 FOR UPDATE;").fetchall() == [("Alice",)]
+    second.execute("BEGIN;", prepare=False)
+    assert second.execute("SELECT name FROM t WHERE id = 2 FOR UPDATE;").fetchall() == [("Bob",)]
+    with ThreadPoolExecutor(max_workers=1) as executor:
+        blocked = executor.submit(lambda: second.execute("SELECT name FROM t WHERE id = 1 FOR UPDATE;").fetchall())
+        time.sleep(1)
+        assert not blocked.done()
+        first.execute("UPDATE t SET name = 'Alicia' WHERE id = 1;")
+        first.execute("COMMIT;", prepare=False)
+        assert blocked.result(timeout=10) == [("Alicia",)]
+    second.execute("COMMIT;", prepare=False)
+
+    # The locks are released on ROLLBACK too.
+    first.execute("BEGIN;", prepare=False)
+    first.execute("SELECT name FROM t WHERE id = 1 FOR UPDATE;")
+    first.execute("ROLLBACK;", prepare=False)
+    second.execute("BEGIN;", prepare=False)
+    assert sorted(second.execute("SELECT name FROM t FOR UPDATE;").fetchall()) == [("Alicia",), ("Bob",)]
+    second.execute("COMMIT;", prepare=False)
+
+    # The rows are located by the primary key, so only simple scans of a table can lock them.
+    first.execute("BEGIN;", prepare=False)
+    with pytest.raises(
+        psycopg.errors.FeatureNotSupported,
+        match="FOR UPDATE and FOR SHARE in transaction blocks are supported only",
+    ):
+        first.execute("SELECT name FROM t ORDER BY id LIMIT 1 FOR UPDATE;")
+    first.execute("ROLLBACK;", prepare=False)
+
+    # Like in Postgres, the locking clause can't be used with aggregates.
+    with pytest.raises(psycopg.Error, match="FOR UPDATE is not allowed with aggregate functions"):
+        first.execute("SELECT count(*) FROM t FOR UPDATE;")
+
+
 def test_create_schema(postgres: Postgres):
     user = "admin"
     password = "P@ssw0rd"
@@ -448,6 +673,34 @@ def test_drop_schema(postgres: Postgres):
     assert cur.statusmessage == "DROP SCHEMA"
 
 
+def test_dml_command_tag_row_count(postgres: Postgres):
+    user = "admin"
+    password = "P@ssw0rd"
+    host = postgres.host
+    port = postgres.port
+    postgres.instance.sql(f"ALTER USER \"{user}\" WITH PASSWORD '{password}'")
+
+    conn = psycopg.connect(f"user={user} password={password} host={host} port={port} sslmode=disable")
+    conn.autocommit = True
+
+    conn.execute("CREATE TABLE tag_t (id INT PRIMARY KEY, a INT) DISTRIBUTED BY (id);", prepare=False)
+
+    # Both simple (prepare=False) and extended (prepare=True) protocols
+    # must report the number of affected rows in the CommandComplete tag.
+    for prepare in [False, True]:
+        cur = conn.execute("INSERT INTO tag_t VALUES (1, 1), (2, 2), (3, 3);", prepare=prepare)
+        assert cur.statusmessage == "INSERT 0 3"
+        assert cur.rowcount == 3
+
+        cur = conn.execute("UPDATE tag_t SET a = 10 WHERE id < 3;", prepare=prepare)
+        assert cur.statusmessage == "UPDATE 2"
+        assert cur.rowcount == 2
+
+        cur = conn.execute("DELETE FROM tag_t;", prepare=prepare)
+        assert cur.statusmessage == "DELETE 3"
+        assert cur.rowcount == 3
+
+
 def test_procedures(postgres: Postgres):
     user = "postgres"
     password = "Passw0rd"
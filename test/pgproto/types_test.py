@@ -6,6 +6,7 @@ from uuid import UUID
 import pg8000.native as pg8000  # type: ignore
 import pytest
 import datetime
+import hashlib
 
 
 def test_decimal(postgres: Postgres):
@@ -172,6 +173,56 @@ def test_uuid(postgres: Postgres):
     assert sorted(cur.fetchall()) == [(id1,), (id2,)]
 
 
+def test_bytea(postgres: Postgres):
+    user = "postgres"
+    password = "P@ssw0rd"
+    host = postgres.host
+    port = postgres.port
+
+    postgres.instance.sql(f"CREATE USER \"{user}\" WITH PASSWORD '{password}'")
+    postgres.instance.sql(f'GRANT CREATE TABLE TO "{user}"', sudo=True)
+
+    conn = psycopg.connect(f"user = {user} password={password} host={host} port={port} sslmode=disable")
+    conn.autocommit = True
+
+    conn.execute(
+        """
+        CREATE TABLE T (
+            ID BYTEA NOT NULL,
+            PRIMARY KEY (ID)
+        )
+        USING MEMTX DISTRIBUTED BY (ID);
+        """
+    )
+
+    # test hex and escape input formats of literals
+    conn.execute(r""" INSERT INTO T VALUES ('\xDEADBEEF'), ('a\\b\000'); """)
+
+    # test text and binary decoding of parameters
+    conn.execute(""" INSERT INTO T VALUES(%t); """, (b"\x01\x02",))
+    conn.execute(""" INSERT INTO T VALUES(%b); """, (b"\xff",))
+
+    expected = [(b"\x01\x02",), (b"a\\b\x00",), (b"\xde\xad\xbe\xef",), (b"\xff",)]
+
+    # test text encoding
+    cur = conn.execute(""" SELECT * FROM T; """, binary=False)
+    assert cur.description is not None
+    assert cur.description[0].type_code == 17  # bytea oid
+    assert sorted(cur.fetchall()) == expected
+
+    # test binary encoding
+    cur = conn.execute(""" SELECT * FROM T; """, binary=True)
+    assert sorted(cur.fetchall()) == expected
+
+    # test equality on the sharding column
+    cur = conn.execute(r""" SELECT * FROM T WHERE ID = '\xdeadbeef'; """)
+    assert cur.fetchall() == [(b"\xde\xad\xbe\xef",)]
+
+    # SHA256 of a bytea parameter is computed by the bytea variant
+    cur = conn.execute(""" SELECT sha256(%b); """, (b"abc",))
+    assert cur.fetchall() == [(hashlib.sha256(b"abc").digest(),)]
+
+
 def test_text_and_varchar(postgres: Postgres):
     user = "postgres"
     password = "P@ssw0rd"
@@ -685,3 +736,51 @@ def test_gl_1730_via_psycopg_int2(postgres: Postgres):
 
     cur = conn.execute("select %b", [Int2(128)])
     assert sorted(cur.fetchall()) == [(128,)]
+
+
+def test_integer_narrowing_cast(postgres: Postgres):
+    user = "postgres"
+    password = "P@ssw0rd"
+    host = postgres.host
+    port = postgres.port
+
+    postgres.instance.sql(f"CREATE USER \"{user}\" WITH PASSWORD '{password}'")
+
+    conn = psycopg.connect(f"user = {user} password={password} host={host} port={port} sslmode=disable")
+    conn.autocommit = True
+
+    # in-range values are kept as is, fractional values are rounded
+    cur = conn.execute("SELECT 32767::smallint, 2147483647::bigint::int4, 2.5::int, 1.5::double::int")
+    assert cur.fetchall() == [(32767, 2147483647, 3, 2)]
+
+    # NULL is not checked
+    cur = conn.execute("SELECT NULL::int2")
+    assert cur.fetchall() == [(None,)]
+
+    with pytest.raises(psycopg.errors.NumericValueOutOfRange, match="smallint out of range"):
+        conn.execute("SELECT 32768::smallint")
+
+    with pytest.raises(psycopg.errors.NumericValueOutOfRange, match="integer out of range"):
+        conn.execute("SELECT CAST(2147483648::bigint AS int4)")
+
+    with pytest.raises(psycopg.errors.NumericValueOutOfRange, match="bigint out of range"):
+        conn.execute("SELECT 1e19::int")
+
+    # column values are checked on the storages
+    conn.execute("CREATE TABLE t (id INT PRIMARY KEY, v INT)")
+    conn.execute("INSERT INTO t VALUES (1, 32767), (2, NULL)")
+    cur = conn.execute("SELECT v::smallint FROM t ORDER BY id")
+    assert cur.fetchall() == [(32767,), (None,)]
+
+    conn.execute("INSERT INTO t VALUES (3, 32768)")
+    with pytest.raises(psycopg.errors.NumericValueOutOfRange, match="smallint out of range") as e:
+        conn.execute("SELECT v::smallint FROM t")
+    assert e.value.sqlstate == "22003"
+
+    # column values are rounded like the constants
+    conn.execute("CREATE TABLE f (id INT PRIMARY KEY, d DOUBLE, n DECIMAL)")
+    conn.execute("INSERT INTO f VALUES (1, 2.5, 2.5), (2, 1.5, 1.5), (3, -2.5, -2.5)")
+    cur = conn.execute("SELECT d::int, n::int, d::smallint FROM f ORDER BY id")
+    assert cur.fetchall() == [(2, 3, 2), (2, 2, 2), (-2, -3, -2)]
+    cur = conn.execute("SELECT 2.5::double::int, 2.5::int, -2.5::double::int, -2.5::int")
+    assert cur.fetchall() == [(2, 3, -2, -3)]
@@ -731,6 +731,7 @@ impl Service for ServiceWithRpcTests {
                     bucket_id: Option<u64>,
                     tier_and_bucket_id: Option<(String, u64)>,
                     to_master: Option<bool>,
+                    raft_leader: Option<bool>,
                     #[serde(with = "serde_bytes")]
                     input: Vec<u8>,
                 }
@@ -738,7 +739,9 @@ impl Service for ServiceWithRpcTests {
                 let request: Request = input.decode_rmp()?;
 
                 let mut target = rpc::RequestTarget::Any;
-                if let Some(instance_name) = &request.instance_name {
+                if request.raft_leader == Some(true) {
+                    target = rpc::RequestTarget::RaftLeader;
+                } else if let Some(instance_name) = &request.instance_name {
                     target = rpc::RequestTarget::InstanceName(instance_name);
                 } else if let Some(replicaset_name) = &request.replicaset_name {
                     target = rpc::RequestTarget::ReplicasetName(